@@ -239,6 +239,20 @@ pub(crate) async fn apply_bespoke_event_handling(
             }
         },
         // TODO(celia): properly construct McpToolCall TurnItem in core.
+        EventMsg::BudgetExceeded(_) => {
+            // Clients have no budget prompt yet; end the task instead of
+            // leaving it paused. Core follows up with an `Error` event naming
+            // the budget, which reaches the client as an error notification.
+            if let Err(err) = conversation
+                .submit(Op::BudgetApproval {
+                    id: event_turn_id,
+                    decision: ReviewDecision::Denied,
+                })
+                .await
+            {
+                error!("failed to submit BudgetApproval: {err}");
+            }
+        }
         EventMsg::McpToolCallBegin(begin_event) => {
             let notification = construct_mcp_tool_call_notification(
                 begin_event,
@@ -1,14 +1,39 @@
 use std::env;
+use std::path::Path;
+use std::path::PathBuf;
 use std::process::Command;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=CODEX_BUILD_GIT_SHA");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    for path in git_head_files() {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
 
     let sha = env_sha()
         .or_else(git_sha)
         .unwrap_or_else(|| "unknown".to_string());
 
     println!("cargo:rustc-env=CODEX_CLI_GIT_SHA={sha}");
+    println!(
+        "cargo:rustc-env=CODEX_CLI_BUILD_TIMESTAMP={}",
+        build_timestamp()
+    );
+    println!(
+        "cargo:rustc-env=CODEX_CLI_RUSTC_VERSION={}",
+        rustc_version().unwrap_or_else(|| "unknown".to_string())
+    );
+    println!(
+        "cargo:rustc-env=CODEX_CLI_TARGET={}",
+        env::var("TARGET").unwrap_or_else(|_| "unknown".to_string())
+    );
+    println!(
+        "cargo:rustc-env=CODEX_CLI_FEATURES={}",
+        features().join(",")
+    );
 }
 
 fn env_sha() -> Option<String> {
@@ -21,18 +46,84 @@ fn env_sha() -> Option<String> {
 }
 
 fn git_sha() -> Option<String> {
+    git(&["rev-parse", "HEAD"])
+}
+
+/// `.git/HEAD`, the branch ref it points to and `packed-refs`, so a commit
+/// or checkout reruns this script and the SHA and timestamp describe the
+/// binary being built. Files that do not exist are skipped, since Cargo
+/// would rerun on every build for them.
+fn git_head_files() -> Vec<PathBuf> {
+    let mut names = vec!["HEAD".to_string(), "packed-refs".to_string()];
+    names.extend(git(&["symbolic-ref", "-q", "HEAD"]));
+    let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") else {
+        return Vec::new();
+    };
+    names
+        .iter()
+        .filter_map(|name| git(&["rev-parse", "--git-path", name]))
+        .map(|path| Path::new(&manifest_dir).join(path))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// Trimmed stdout of `git` run in this crate, unless it fails or prints
+/// nothing.
+fn git(args: &[&str]) -> Option<String> {
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").ok()?;
     let output = Command::new("git")
-        .args(["rev-parse", "HEAD"])
+        .args(args)
         .current_dir(manifest_dir)
         .output()
         .ok()?;
     if !output.status.success() {
         return None;
     }
-    let sha = String::from_utf8(output.stdout).ok()?.trim().to_string();
-    if sha.is_empty() {
+    let stdout = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    if stdout.is_empty() {
+        return None;
+    }
+    Some(stdout)
+}
+
+/// Seconds since the Unix epoch. `SOURCE_DATE_EPOCH` wins, so packagers get
+/// reproducible builds. Without it this is when the script last ran: the
+/// first build of a commit, not later rebuilds of the same commit.
+fn build_timestamp() -> i64 {
+    env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|secs| *secs >= 0)
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX))
+                .unwrap_or_default()
+        })
+}
+
+/// `rustc 1.90.0 (1159e78c4 2025-09-14)` for the compiler Cargo uses.
+fn rustc_version() -> Option<String> {
+    let rustc = env::var("RUSTC").ok()?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    if version.is_empty() {
         return None;
     }
-    Some(sha)
+    Some(version)
+}
+
+/// Cargo features enabled for this crate, in lower case.
+fn features() -> Vec<String> {
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|name| name.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+    features
 }
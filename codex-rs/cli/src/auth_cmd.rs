@@ -0,0 +1,327 @@
+use std::time::SystemTime;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::ModelProviderInfo;
+use codex_core::auth::AuthCredentialsStoreMode;
+use codex_core::auth::AuthDotJson;
+use codex_core::auth::CODEX_API_KEY_ENV_VAR;
+use codex_core::auth::OPENAI_API_KEY_ENV_VAR;
+use codex_core::auth::load_auth_dot_json;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+
+use codex_cli::login::safe_format_key;
+
+use crate::workspace_cmd::format_age;
+use crate::workspace_cmd::unix_seconds;
+
+/// Inspect the credentials Codex can send with model requests.
+///
+/// Subcommands:
+/// - `status` — every credential source, how old it is and which one the
+///   current config uses (with `--json`)
+#[derive(Debug, clap::Parser)]
+pub struct AuthCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: AuthSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum AuthSubcommand {
+    /// Show every credential source and which one the current config uses.
+    Status(StatusArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct StatusArgs {
+    /// Output the sources as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Whether model requests carry a credential.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Usage {
+    /// Sent with model requests under the current config.
+    InUse,
+    /// Sent by `codex exec` only, which prefers `CODEX_API_KEY`.
+    ExecOnly,
+    /// Found but not sent.
+    Unused,
+}
+
+impl Usage {
+    fn as_str(self) -> &'static str {
+        match self {
+            Usage::InUse => "in use",
+            Usage::ExecOnly => "codex exec only",
+            Usage::Unused => "unused",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CredentialSource {
+    /// File, environment variable or config key the credential comes from.
+    location: String,
+    /// What the credential is, with secrets masked.
+    credential: String,
+    /// When it was saved or last refreshed, when known.
+    updated: Option<SystemTime>,
+    usage: Usage,
+}
+
+impl AuthCli {
+    pub async fn run(self) -> Result<()> {
+        let cli_overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config =
+            Config::load_with_cli_overrides(cli_overrides, ConfigOverrides::default()).await?;
+        match self.subcommand {
+            AuthSubcommand::Status(args) => run_status(&config, args),
+        }
+    }
+}
+
+fn run_status(config: &Config, status_args: StatusArgs) -> Result<()> {
+    let provider = &config.model_provider;
+    let sources = credential_sources(config)?;
+
+    if status_args.json {
+        let json_sources: Vec<_> = sources
+            .iter()
+            .map(|source| {
+                serde_json::json!({
+                    "location": source.location,
+                    "credential": source.credential,
+                    "updated": source.updated.and_then(unix_seconds),
+                    "usage": source.usage.as_str(),
+                })
+            })
+            .collect();
+        let output = serde_json::to_string_pretty(&serde_json::json!({
+            "provider": config.model_provider_id,
+            "sources": json_sources,
+        }))?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    println!(
+        "Provider: {} ({})",
+        config.model_provider_id,
+        provider_auth_summary(provider)
+    );
+    println!();
+
+    if sources.is_empty() {
+        println!("No credentials found.");
+    } else {
+        let now = SystemTime::now();
+        let rows: Vec<[String; 4]> = sources
+            .iter()
+            .map(|source| {
+                [
+                    source.location.clone(),
+                    source.credential.clone(),
+                    source
+                        .updated
+                        .map(|updated| format_age(now, updated))
+                        .unwrap_or_else(|| "-".to_string()),
+                    source.usage.as_str().to_string(),
+                ]
+            })
+            .collect();
+
+        let mut widths = ["Source".len(), "Credential".len(), "Updated".len()];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        println!(
+            "{source:<source_w$}  {credential:<credential_w$}  {updated:<updated_w$}  Status",
+            source = "Source",
+            credential = "Credential",
+            updated = "Updated",
+            source_w = widths[0],
+            credential_w = widths[1],
+            updated_w = widths[2],
+        );
+        for [source, credential, updated, usage] in &rows {
+            println!(
+                "{source:<source_w$}  {credential:<credential_w$}  {updated:<updated_w$}  {usage}",
+                source_w = widths[0],
+                credential_w = widths[1],
+                updated_w = widths[2],
+            );
+        }
+    }
+
+    if !sources.iter().any(|source| source.usage == Usage::InUse) {
+        println!();
+        println!("{}", missing_credential_hint(provider));
+    }
+
+    Ok(())
+}
+
+/// Every credential found for `config`, in the order Codex would use them.
+fn credential_sources(config: &Config) -> Result<Vec<CredentialSource>> {
+    let provider = &config.model_provider;
+    let mut sources = Vec::new();
+
+    if let Some(resolved) = config.code88_token_store().resolve_token() {
+        sources.push(CredentialSource {
+            location: resolved.source.location(&config.codex_home),
+            credential: "88code login token".to_string(),
+            updated: resolved.saved.map(|file| file.created_at.into()),
+            usage: usage_if(provider.code88_auth),
+        });
+    }
+
+    let mut env_key_in_use = false;
+    if let Some(env_key) = provider.env_key.as_ref()
+        && let Some(value) = read_env(env_key)
+    {
+        env_key_in_use = !provider.code88_auth;
+        sources.push(CredentialSource {
+            location: format!("${env_key}"),
+            credential: format!("API key {}", safe_format_key(&value)),
+            updated: None,
+            usage: usage_if(env_key_in_use),
+        });
+    }
+
+    let bearer_in_use =
+        !provider.code88_auth && !env_key_in_use && provider.experimental_bearer_token.is_some();
+    if let Some(token) = provider.experimental_bearer_token.as_ref() {
+        sources.push(CredentialSource {
+            location: format!(
+                "model_providers.{}.experimental_bearer_token",
+                config.model_provider_id
+            ),
+            credential: format!("bearer token {}", safe_format_key(token)),
+            updated: None,
+            usage: usage_if(bearer_in_use),
+        });
+    }
+
+    // Providers without their own key fall back to the OpenAI login, which
+    // `codex exec` takes from `CODEX_API_KEY` when it is set.
+    let login_in_use = !provider.code88_auth && !env_key_in_use && !bearer_in_use;
+    let codex_api_key = read_env(CODEX_API_KEY_ENV_VAR);
+    if let Some(value) = codex_api_key.as_ref() {
+        sources.push(CredentialSource {
+            location: format!("${CODEX_API_KEY_ENV_VAR}"),
+            credential: format!("API key {}", safe_format_key(value)),
+            updated: None,
+            usage: if login_in_use {
+                Usage::ExecOnly
+            } else {
+                Usage::Unused
+            },
+        });
+    }
+
+    let stored = load_auth_dot_json(&config.codex_home, config.cli_auth_credentials_store_mode)
+        .context("failed to read stored credentials")?;
+    if let Some(stored) = stored {
+        sources.push(CredentialSource {
+            location: stored_location(config),
+            credential: stored_credential(&stored),
+            updated: stored.last_refresh.map(Into::into).or_else(|| {
+                std::fs::metadata(config.codex_home.join("auth.json"))
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            }),
+            usage: usage_if(login_in_use),
+        });
+    }
+
+    if provider.env_key.as_deref() != Some(OPENAI_API_KEY_ENV_VAR)
+        && let Some(value) = read_env(OPENAI_API_KEY_ENV_VAR)
+    {
+        sources.push(CredentialSource {
+            location: format!("${OPENAI_API_KEY_ENV_VAR}"),
+            credential: format!("API key {}", safe_format_key(&value)),
+            updated: None,
+            usage: Usage::Unused,
+        });
+    }
+
+    Ok(sources)
+}
+
+fn usage_if(in_use: bool) -> Usage {
+    if in_use { Usage::InUse } else { Usage::Unused }
+}
+
+fn read_env(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn stored_location(config: &Config) -> String {
+    let auth_file = config.codex_home.join("auth.json");
+    match config.cli_auth_credentials_store_mode {
+        AuthCredentialsStoreMode::File => auth_file.display().to_string(),
+        AuthCredentialsStoreMode::Keyring => "keyring".to_string(),
+        AuthCredentialsStoreMode::Auto if auth_file.exists() => auth_file.display().to_string(),
+        AuthCredentialsStoreMode::Auto => "keyring".to_string(),
+    }
+}
+
+fn stored_credential(stored: &AuthDotJson) -> String {
+    if let Some(api_key) = stored.openai_api_key.as_ref() {
+        return format!("API key {}", safe_format_key(api_key));
+    }
+    match stored
+        .tokens
+        .as_ref()
+        .and_then(|tokens| tokens.id_token.email.as_ref())
+    {
+        Some(email) => format!("ChatGPT login ({email})"),
+        None => "ChatGPT login".to_string(),
+    }
+}
+
+/// How `provider` authenticates, for the header line.
+fn provider_auth_summary(provider: &ModelProviderInfo) -> String {
+    if provider.code88_auth {
+        "88code login token".to_string()
+    } else if let Some(env_key) = provider.env_key.as_ref() {
+        format!("API key from ${env_key}")
+    } else if provider.experimental_bearer_token.is_some() {
+        "bearer token from config".to_string()
+    } else if provider.requires_openai_auth {
+        "ChatGPT login or OpenAI API key".to_string()
+    } else {
+        "no authentication required".to_string()
+    }
+}
+
+fn missing_credential_hint(provider: &ModelProviderInfo) -> String {
+    if provider.code88_auth {
+        "No 88code token: run `codex login --provider 88code`.".to_string()
+    } else if let Some(env_key) = provider.env_key.as_ref() {
+        match provider.env_key_instructions.as_ref() {
+            Some(instructions) => format!("${env_key} is not set. {instructions}"),
+            None => format!("${env_key} is not set."),
+        }
+    } else if provider.requires_openai_auth {
+        "Not logged in: run `codex login`.".to_string()
+    } else {
+        "This provider does not need credentials.".to_string()
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::find_codex_home;
+use codex_core::config::validate::ConfigDiagnostic;
+use codex_core::config::validate::Severity;
+use codex_core::config::validate::validate_config_toml;
+
+/// Check `config.toml` for mistakes Codex would otherwise ignore.
+///
+/// Subcommands:
+/// - `validate` — print each problem in `config.toml` with its line
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    /// Check config.toml: syntax, value types, unknown keys, status line
+    /// skins and layout, and `[code88]` settings.
+    Validate(ValidateArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ValidateArgs {
+    /// File to check; defaults to `CODEX_HOME/config.toml`.
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+}
+
+impl ConfigCli {
+    pub fn run(self) -> Result<()> {
+        match self.subcommand {
+            ConfigSubcommand::Validate(args) => run_validate(args),
+        }
+    }
+}
+
+fn run_validate(args: ValidateArgs) -> Result<()> {
+    let path = match args.path {
+        Some(path) => path,
+        None => find_codex_home()
+            .context("failed to resolve CODEX_HOME")?
+            .join(CONFIG_TOML_FILE),
+    };
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let diagnostics = validate_config_toml(&contents, &codex_tui::statusline_skin_names());
+    for diagnostic in &diagnostics {
+        println!("{}: {diagnostic}", path.display());
+    }
+
+    let errors = count(&diagnostics, Severity::Error);
+    let warnings = count(&diagnostics, Severity::Warning);
+    if errors > 0 {
+        bail!(
+            "{} has {errors} error(s) and {warnings} warning(s)",
+            path.display()
+        );
+    }
+    if warnings > 0 {
+        println!("{} is valid, with {warnings} warning(s)", path.display());
+    } else {
+        println!("{} is valid", path.display());
+    }
+    Ok(())
+}
+
+fn count(diagnostics: &[ConfigDiagnostic], severity: Severity) -> usize {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == severity)
+        .count()
+}
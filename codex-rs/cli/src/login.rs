@@ -91,6 +91,24 @@ pub async fn run_login_with_api_key(
     }
 }
 
+/// Sign in to 88code in the browser, replacing any saved token once the new
+/// one is in hand. Asks for the token on stdin when the browser flow fails.
+pub async fn run_login_with_88code(cli_config_overrides: CliConfigOverrides) -> ! {
+    let config = load_config_or_exit(cli_config_overrides).await;
+    let store = config.code88_token_store();
+
+    match codex_code88::refresh_token_with_fallback(&store, &config.code88_browser).await {
+        Ok(_) => {
+            eprintln!("Successfully logged in to 88code");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error logging in to 88code: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
 pub fn read_api_key_from_stdin() -> String {
     let mut stdin = std::io::stdin();
 
@@ -154,6 +172,21 @@ pub async fn run_login_with_device_code(
 pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
 
+    let code88 = config.code88_token_store().resolve_token();
+    match code88.as_ref() {
+        Some(codex_code88::ResolvedToken {
+            saved: Some(file), ..
+        }) => eprintln!(
+            "Logged in to 88code since {}",
+            file.created_at.format("%Y-%m-%d")
+        ),
+        Some(resolved) => eprintln!(
+            "Logged in to 88code with the token from {}",
+            resolved.source.location(&config.codex_home)
+        ),
+        None => {}
+    }
+
     match CodexAuth::from_auth_storage(&config.codex_home, config.cli_auth_credentials_store_mode) {
         Ok(Some(auth)) => match auth.mode {
             AuthMode::ApiKey => match auth.get_token().await {
@@ -171,6 +204,7 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
                 std::process::exit(0);
             }
         },
+        Ok(None) if code88.is_some() => std::process::exit(0),
         Ok(None) => {
             eprintln!("Not logged in");
             std::process::exit(1);
@@ -185,11 +219,30 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
 pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
 
+    let store = config.code88_token_store();
+    let logged_in_to_88code = store
+        .resolve_token()
+        .is_some_and(|resolved| resolved.saved.is_some());
+    if let Err(e) = store.delete_token() {
+        eprintln!("Error logging out of 88code: {e}");
+        std::process::exit(1);
+    }
+    if let Some(resolved) = store.resolve_token() {
+        eprintln!(
+            "The 88code token from {} still applies; remove it there to log out of 88code",
+            resolved.source.location(&config.codex_home)
+        );
+    }
+
     match logout(&config.codex_home, config.cli_auth_credentials_store_mode) {
         Ok(true) => {
             eprintln!("Successfully logged out");
             std::process::exit(0);
         }
+        Ok(false) if logged_in_to_88code => {
+            eprintln!("Successfully logged out");
+            std::process::exit(0);
+        }
         Ok(false) => {
             eprintln!("Not logged in");
             std::process::exit(0);
@@ -220,7 +273,8 @@ async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config
     }
 }
 
-fn safe_format_key(key: &str) -> String {
+/// `sk-proj-***ABCDE`: enough of a key to tell it apart without printing it.
+pub fn safe_format_key(key: &str) -> String {
     if key.len() <= 13 {
         return "***".to_string();
     }
@@ -11,6 +11,7 @@ use codex_cli::SeatbeltCommand;
 use codex_cli::WindowsCommand;
 use codex_cli::login::read_api_key_from_stdin;
 use codex_cli::login::run_login_status;
+use codex_cli::login::run_login_with_88code;
 use codex_cli::login::run_login_with_api_key;
 use codex_cli::login::run_login_with_chatgpt;
 use codex_cli::login::run_login_with_device_code;
@@ -29,11 +30,19 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod auth_cmd;
+mod config_cmd;
 mod mcp_cmd;
+mod version_cmd;
+mod workspace_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::auth_cmd::AuthCli;
+use crate::config_cmd::ConfigCli;
 use crate::mcp_cmd::McpCli;
+use crate::version_cmd::VersionCommand;
+use crate::workspace_cmd::WorkspaceCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -47,6 +56,10 @@ const CLI_VERSION: &str = concat!(
     ")"
 );
 
+/// Commit this binary was built from, passed to the TUI for `/status`, the
+/// status line and the release check.
+const BUILD_GIT_SHA: &str = env!("CODEX_CLI_GIT_SHA");
+
 /// Codex CLI
 ///
 /// If no subcommand is specified, options will be forwarded to the interactive CLI.
@@ -91,6 +104,9 @@ enum Subcommand {
     /// Remove stored authentication credentials.
     Logout(LogoutCommand),
 
+    /// Inspect every credential source and which one is in use.
+    Auth(AuthCli),
+
     /// [experimental] Run Codex as an MCP server and manage MCP servers.
     Mcp(McpCli),
 
@@ -132,6 +148,27 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Preview status line skins without starting a session.
+    Statusline(StatuslineCli),
+
+    /// Show the `/status` card for the latest session, e.g. a running `codex exec`.
+    Status(StatusCommand),
+
+    /// List, inspect or clear settings saved per workspace.
+    Workspace(WorkspaceCli),
+
+    /// Inspect what recorded sessions did.
+    Sessions(SessionsCli),
+
+    /// Print the version, or build metadata with `--json`.
+    Version(VersionCommand),
+
+    /// Print the command that installs the latest release.
+    Upgrade,
+
+    /// Check config.toml for mistakes.
+    Config(ConfigCli),
 }
 
 #[derive(Debug, Parser)]
@@ -215,6 +252,10 @@ struct LoginCommand {
     #[arg(long = "device-auth")]
     use_device_code: bool,
 
+    /// Account to sign in to. `88code` opens the 88code login in your browser.
+    #[arg(long = "provider", value_enum, default_value_t = LoginProvider::Chatgpt)]
+    provider: LoginProvider,
+
     /// EXPERIMENTAL: Use custom OAuth issuer base URL (advanced)
     /// Override the OAuth issuer base URL (advanced)
     #[arg(long = "experimental_issuer", value_name = "URL", hide = true)]
@@ -228,6 +269,15 @@ struct LoginCommand {
     action: Option<LoginSubcommand>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum LoginProvider {
+    /// ChatGPT or an OpenAI API key.
+    Chatgpt,
+    /// The 88code relay's login token.
+    #[value(name = "88code")]
+    Code88,
+}
+
 #[derive(Debug, clap::Subcommand)]
 enum LoginSubcommand {
     /// Show login status.
@@ -408,6 +458,78 @@ enum FeaturesSubcommand {
     List,
 }
 
+#[derive(Debug, Parser)]
+struct StatuslineCli {
+    #[command(subcommand)]
+    sub: StatuslineSubcommand,
+}
+
+#[derive(Debug, Parser)]
+enum StatuslineSubcommand {
+    /// Render a canned status line with a built-in skin, using ANSI colors.
+    Preview(StatuslinePreviewCommand),
+
+    /// Check glyph, color and background support in this terminal and
+    /// suggest status line settings for anything that will degrade.
+    Doctor,
+}
+
+#[derive(Debug, Parser)]
+struct StatuslinePreviewCommand {
+    /// Terminal width, in columns, to render at.
+    #[arg(long, default_value_t = 80)]
+    width: u16,
+
+    /// Built-in skin to render with (see `/statusline` for the list).
+    #[arg(long, default_value = "powerline")]
+    skin: String,
+
+    /// Canned snapshot to render: `busy`, `idle`, `low-context` or `full`.
+    #[arg(long, default_value = "busy")]
+    fixture: String,
+}
+
+#[derive(Debug, Parser)]
+struct StatusCommand {
+    /// Keep redrawing the card until interrupted.
+    #[arg(long)]
+    watch: bool,
+
+    /// Seconds between redraws with `--watch`.
+    #[arg(
+        long,
+        value_name = "SECS",
+        default_value_t = 5,
+        requires = "watch",
+        value_parser = clap::value_parser!(i64).range(1..)
+    )]
+    interval: i64,
+
+    /// Session to show instead of the most recent one.
+    #[arg(long, value_name = "SESSION_ID")]
+    session: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+struct SessionsCli {
+    #[command(subcommand)]
+    sub: SessionsSubcommand,
+}
+
+#[derive(Debug, Parser)]
+enum SessionsSubcommand {
+    /// Print when a session's tasks started, changed label, paused, failed a
+    /// command and finished. Needs `tui.statusline.session_timeline`.
+    Timeline(SessionsTimelineCommand),
+}
+
+#[derive(Debug, Parser)]
+struct SessionsTimelineCommand {
+    /// Session id, as shown by `/status` or on exit.
+    #[arg(value_name = "SESSION_ID")]
+    session_id: String,
+}
+
 fn stage_str(stage: codex_core::features::Stage) -> &'static str {
     use codex_core::features::Stage;
     match stage {
@@ -441,6 +563,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
         mut interactive,
         subcommand,
     } = MultitoolCli::parse();
+    interactive.build_sha = Some(BUILD_GIT_SHA);
 
     // Fold --enable/--disable into config overrides so they flow to all subcommands.
     let toggle_overrides = feature_toggles.to_overrides()?;
@@ -453,11 +576,24 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 root_config_overrides.clone(),
             );
 
-            // Check and trigger 88code login if no token exists
-            if let Ok(codex_home) = find_codex_home() {
-                if codex_code88::load_token(&codex_home).is_none() {
+            // Check and trigger 88code login if no token exists. The config
+            // is loaded the way the TUI will load it: with the `-c` overrides,
+            // the profile and the working directory from the command line.
+            let overrides = ConfigOverrides {
+                config_profile: interactive.config_profile.clone(),
+                cwd: interactive.cwd.clone(),
+                ..ConfigOverrides::default()
+            };
+            if let Ok(cli_overrides) = interactive.config_overrides.parse_overrides()
+                && let Ok(config) = Config::load_with_cli_overrides(cli_overrides, overrides).await
+            {
+                let store = config.code88_token_store();
+                if store.resolve_token().is_none() {
                     // No token found, try browser login (with fallback to manual input)
-                    if let Err(e) = codex_code88::ensure_token_with_fallback(&codex_home).await {
+                    if let Err(e) =
+                        codex_code88::ensure_token_with_fallback(&store, &config.code88_browser)
+                            .await
+                    {
                         tracing::warn!("88code login failed: {e}");
                         // Continue without 88code - non-fatal error
                     }
@@ -532,7 +668,9 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                     run_login_status(login_cli.config_overrides).await;
                 }
                 None => {
-                    if login_cli.use_device_code {
+                    if login_cli.provider == LoginProvider::Code88 {
+                        run_login_with_88code(login_cli.config_overrides).await;
+                    } else if login_cli.use_device_code {
                         run_login_with_device_code(
                             login_cli.config_overrides,
                             login_cli.issuer_base_url,
@@ -560,6 +698,13 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             run_logout(logout_cli.config_overrides).await;
         }
+        Some(Subcommand::Auth(mut auth_cli)) => {
+            prepend_config_flags(
+                &mut auth_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            auth_cli.run().await?;
+        }
         Some(Subcommand::Completion(completion_cli)) => {
             print_completion(completion_cli);
         }
@@ -654,6 +799,72 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 }
             }
         },
+        Some(Subcommand::Statusline(StatuslineCli { sub })) => match sub {
+            StatuslineSubcommand::Preview(StatuslinePreviewCommand {
+                width,
+                skin,
+                fixture,
+            }) => {
+                codex_tui::write_statusline_preview(
+                    &mut std::io::stdout().lock(),
+                    &skin,
+                    &fixture,
+                    width,
+                )?;
+            }
+            StatuslineSubcommand::Doctor => {
+                codex_tui::run_statusline_doctor(&mut std::io::stdout().lock())?;
+            }
+        },
+        Some(Subcommand::Status(StatusCommand {
+            watch,
+            interval,
+            session,
+        })) => {
+            let cli_kv_overrides = root_config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?;
+            let overrides = ConfigOverrides {
+                config_profile: interactive.config_profile.clone(),
+                ..Default::default()
+            };
+            let config = Config::load_with_cli_overrides(cli_kv_overrides, overrides).await?;
+            codex_tui::run_status_watch(
+                config,
+                codex_tui::StatusWatchOptions {
+                    session_id: session,
+                    interval: watch.then(|| {
+                        std::time::Duration::from_secs(u64::try_from(interval).unwrap_or(1))
+                    }),
+                    build_sha: Some(BUILD_GIT_SHA),
+                },
+            )
+            .await?;
+        }
+        Some(Subcommand::Workspace(workspace_cli)) => {
+            workspace_cli.run().await?;
+        }
+        Some(Subcommand::Sessions(SessionsCli { sub })) => match sub {
+            SessionsSubcommand::Timeline(SessionsTimelineCommand { session_id }) => {
+                codex_tui::write_session_timeline(
+                    &mut std::io::stdout().lock(),
+                    &find_codex_home()?,
+                    &session_id,
+                )
+                .await?;
+            }
+        },
+        Some(Subcommand::Version(version_cli)) => {
+            version_cli.run()?;
+        }
+        Some(Subcommand::Upgrade) => {
+            for line in codex_tui::upgrade_instructions(&find_codex_home()?, Some(BUILD_GIT_SHA)) {
+                println!("{line}");
+            }
+        }
+        Some(Subcommand::Config(config_cli)) => {
+            config_cli.run()?;
+        }
     }
 
     Ok(())
@@ -752,6 +963,7 @@ fn print_completion(cmd: CompletionCommand) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config_cmd::ConfigSubcommand;
     use assert_matches::assert_matches;
     use codex_core::protocol::TokenUsage;
     use codex_protocol::ConversationId;
@@ -808,6 +1020,35 @@ mod tests {
         assert_eq!(CLI_VERSION, version);
     }
 
+    #[test]
+    fn version_subcommand_takes_json_flag() {
+        let cli = MultitoolCli::try_parse_from(["codex", "version", "--json"])
+            .expect("version --json should parse");
+        assert_matches!(
+            cli.subcommand,
+            Some(Subcommand::Version(VersionCommand { json: true }))
+        );
+    }
+
+    #[test]
+    fn upgrade_subcommand_parses() {
+        let cli = MultitoolCli::try_parse_from(["codex", "upgrade"]).expect("upgrade should parse");
+        assert_matches!(cli.subcommand, Some(Subcommand::Upgrade));
+    }
+
+    #[test]
+    fn config_validate_takes_an_optional_path() {
+        let cli = MultitoolCli::try_parse_from(["codex", "config", "validate", "/tmp/config.toml"])
+            .expect("config validate should parse");
+        let Some(Subcommand::Config(ConfigCli {
+            subcommand: ConfigSubcommand::Validate(args),
+        })) = cli.subcommand
+        else {
+            panic!("expected config validate");
+        };
+        assert_eq!(args.path, Some(PathBuf::from("/tmp/config.toml")));
+    }
+
     #[test]
     fn format_exit_messages_skips_zero_usage() {
         let exit_info = AppExitInfo {
@@ -985,4 +1226,74 @@ mod tests {
             .expect_err("feature should be rejected");
         assert_eq!(err.to_string(), "Unknown feature flag: does_not_exist");
     }
+
+    #[test]
+    fn statusline_preview_parses_flags() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "statusline",
+            "preview",
+            "--width",
+            "120",
+            "--skin",
+            "minimal-plain",
+        ])
+        .expect("parse");
+        let Some(Subcommand::Statusline(StatuslineCli {
+            sub: StatuslineSubcommand::Preview(preview),
+        })) = cli.subcommand
+        else {
+            panic!("expected statusline preview");
+        };
+        assert_eq!(preview.width, 120);
+        assert_eq!(preview.skin, "minimal-plain");
+        assert_eq!(preview.fixture, "busy");
+    }
+
+    #[test]
+    fn sessions_timeline_takes_a_session_id() {
+        let cli = MultitoolCli::try_parse_from(["codex", "sessions", "timeline", "abc-123"])
+            .expect("parse");
+        let Some(Subcommand::Sessions(SessionsCli {
+            sub: SessionsSubcommand::Timeline(timeline),
+        })) = cli.subcommand
+        else {
+            panic!("expected sessions timeline");
+        };
+        assert_eq!(timeline.session_id, "abc-123");
+    }
+
+    #[test]
+    fn status_watch_parses_flags() {
+        let cli = MultitoolCli::try_parse_from(["codex", "status", "--watch", "--interval", "2"])
+            .expect("parse");
+        let Some(Subcommand::Status(status)) = cli.subcommand else {
+            panic!("expected status");
+        };
+        assert!(status.watch);
+        assert_eq!(status.interval, 2);
+        assert_eq!(status.session, None);
+
+        assert!(MultitoolCli::try_parse_from(["codex", "status", "--interval", "2"]).is_err());
+        assert!(
+            MultitoolCli::try_parse_from(["codex", "status", "--watch", "--interval", "0"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn login_provider_accepts_88code() {
+        let cli = MultitoolCli::try_parse_from(["codex", "login", "--provider", "88code"])
+            .expect("parse");
+        let Some(Subcommand::Login(login)) = cli.subcommand else {
+            panic!("expected login");
+        };
+        assert_eq!(login.provider, LoginProvider::Code88);
+
+        let cli = MultitoolCli::try_parse_from(["codex", "login"]).expect("parse");
+        let Some(Subcommand::Login(login)) = cli.subcommand else {
+            panic!("expected login");
+        };
+        assert_eq!(login.provider, LoginProvider::Chatgpt);
+    }
 }
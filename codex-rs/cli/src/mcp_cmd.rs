@@ -828,7 +828,7 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
     Ok(())
 }
 
-fn parse_env_pair(raw: &str) -> Result<(String, String), String> {
+pub(crate) fn parse_env_pair(raw: &str) -> Result<(String, String), String> {
     let mut parts = raw.splitn(2, '=');
     let key = parts
         .next()
@@ -0,0 +1,134 @@
+use chrono::DateTime;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::CLI_VERSION;
+
+/// Print the version, or with `--json` the build metadata packagers and bug
+/// reports need.
+#[derive(Debug, clap::Parser)]
+pub struct VersionCommand {
+    /// Print the version and build metadata as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// What `build.rs` recorded about this binary.
+#[derive(Debug, PartialEq)]
+struct BuildInfo {
+    version: &'static str,
+    git_sha: &'static str,
+    /// Seconds since the Unix epoch.
+    build_timestamp: i64,
+    rustc_version: &'static str,
+    /// Comma-separated Cargo features, empty when none are enabled.
+    features: &'static str,
+    target: &'static str,
+}
+
+const BUILD_INFO: BuildInfo = BuildInfo {
+    version: env!("CARGO_PKG_VERSION"),
+    git_sha: env!("CODEX_CLI_GIT_SHA"),
+    build_timestamp: parse_timestamp(env!("CODEX_CLI_BUILD_TIMESTAMP")),
+    rustc_version: env!("CODEX_CLI_RUSTC_VERSION"),
+    features: env!("CODEX_CLI_FEATURES"),
+    target: env!("CODEX_CLI_TARGET"),
+};
+
+/// `CODEX_CLI_BUILD_TIMESTAMP`, which `build.rs` always sets to the decimal
+/// digits of a non-negative number of seconds; anything else fails the build.
+const fn parse_timestamp(digits: &str) -> i64 {
+    let digits = digits.as_bytes();
+    assert!(!digits.is_empty(), "empty build timestamp");
+    let mut secs: i64 = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        assert!(
+            digits[i].is_ascii_digit(),
+            "build timestamp is not a number"
+        );
+        secs = secs * 10 + (digits[i] - b'0') as i64;
+        i += 1;
+    }
+    secs
+}
+
+impl VersionCommand {
+    pub fn run(self) -> anyhow::Result<()> {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&BUILD_INFO.to_json())?);
+        } else {
+            println!("codex-cli {CLI_VERSION}");
+        }
+        Ok(())
+    }
+}
+
+impl BuildInfo {
+    fn to_json(&self) -> Value {
+        // A timestamp chrono cannot represent is passed through as seconds.
+        let build_timestamp = DateTime::from_timestamp(self.build_timestamp, 0).map_or_else(
+            || json!(self.build_timestamp),
+            |time| json!(time.to_rfc3339()),
+        );
+        let features: Vec<&str> = self
+            .features
+            .split(',')
+            .filter(|feature| !feature.is_empty())
+            .collect();
+        json!({
+            "version": self.version,
+            "git_sha": self.git_sha,
+            "build_timestamp": build_timestamp,
+            "rustc_version": self.rustc_version,
+            "features": features,
+            "target": self.target,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn json_lists_features_and_formats_the_timestamp() {
+        let info = BuildInfo {
+            version: "0.58.0",
+            git_sha: "1a2b3c4d",
+            build_timestamp: 1760659200,
+            rustc_version: "rustc 1.90.0 (1159e78c4 2025-09-14)",
+            features: "vendored-openssl,zsh",
+            target: "x86_64-unknown-linux-musl",
+        };
+        assert_eq!(
+            info.to_json(),
+            json!({
+                "version": "0.58.0",
+                "git_sha": "1a2b3c4d",
+                "build_timestamp": "2025-10-17T00:00:00+00:00",
+                "rustc_version": "rustc 1.90.0 (1159e78c4 2025-09-14)",
+                "features": ["vendored-openssl", "zsh"],
+                "target": "x86_64-unknown-linux-musl",
+            })
+        );
+
+        let bare = BuildInfo {
+            features: "",
+            build_timestamp: i64::MAX,
+            ..info
+        };
+        assert_eq!(bare.to_json()["features"], json!([]));
+        assert_eq!(bare.to_json()["build_timestamp"], json!(i64::MAX));
+        assert_eq!(parse_timestamp("1760659200"), 1760659200);
+    }
+
+    #[test]
+    fn this_build_reports_its_metadata() {
+        let json = BUILD_INFO.to_json();
+        assert_eq!(json["version"], json!(env!("CARGO_PKG_VERSION")));
+        assert_eq!(json["git_sha"], json!(env!("CODEX_CLI_GIT_SHA")));
+        assert!(json["features"].is_array());
+    }
+}
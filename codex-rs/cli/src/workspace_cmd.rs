@@ -0,0 +1,765 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::find_codex_home;
+use codex_core::config::load_global_mcp_servers;
+use codex_core::config::types::BudgetConfig;
+use codex_core::workspace_state::WorkspaceState;
+use codex_core::workspace_state::WorkspaceStateEntry;
+use codex_core::workspace_state::clear_workspace_state;
+use codex_core::workspace_state::list_workspace_states;
+use codex_core::workspace_state::load_workspace_budget;
+use codex_core::workspace_state::load_workspace_env;
+use codex_core::workspace_state::load_workspace_state;
+use codex_core::workspace_state::persist_env_var;
+use codex_core::workspace_state::persist_pinned_docs;
+use codex_core::workspace_state::persist_pinned_instructions;
+use codex_core::workspace_state::persist_workspace_budget;
+use codex_core::workspace_state::remove_workspace_state_entry;
+use codex_core::workspace_state::update_workspace_mcp_server;
+use codex_core::workspace_state::workspace_state_exists;
+use codex_core::workspace_state::workspace_state_location;
+
+use crate::mcp_cmd::parse_env_pair;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Inspect or clean up the per-workspace settings Codex saves under
+/// `~/.codex/workspace_state/` (model, approval mode, MCP toggles, status line).
+///
+/// Subcommands:
+/// - `list`  — list workspaces with saved state (with `--json`)
+/// - `show`  — print the state saved for one workspace (with `--json`)
+/// - `clear` — delete one workspace's state, or all state unused for N days
+/// - `env`   — show or edit environment variables for commands in a workspace
+/// - `pin`   — show or edit instructions and docs pinned to a workspace
+/// - `stats` — token usage and estimated cost per workspace (with `--json`)
+/// - `budget` — show or edit the spending limit for a workspace
+/// - `mcp`   — show or edit how an MCP server is launched in a workspace
+#[derive(Debug, clap::Parser)]
+pub struct WorkspaceCli {
+    #[command(subcommand)]
+    pub subcommand: WorkspaceSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum WorkspaceSubcommand {
+    /// List workspaces with saved state.
+    List(ListArgs),
+
+    /// Show the state saved for a workspace.
+    Show(ShowArgs),
+
+    /// Delete saved workspace state.
+    Clear(ClearArgs),
+
+    /// Show or edit environment variables applied to commands run in a
+    /// workspace.
+    Env(EnvArgs),
+
+    /// Show or edit instructions and extra AGENTS.md-style files added to the
+    /// project docs of a workspace.
+    Pin(PinArgs),
+
+    /// Show token usage and estimated cost recorded for each workspace.
+    Stats(StatsArgs),
+
+    /// Show or edit the spending limit shared by every session in a
+    /// workspace.
+    Budget(BudgetArgs),
+
+    /// Show or edit per-workspace overrides for an MCP server from
+    /// `[mcp_servers]`, applied when a session starts in the workspace.
+    Mcp(McpArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Output the workspaces as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ShowArgs {
+    /// Workspace directory; defaults to the current directory.
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Output the state as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ClearArgs {
+    /// Workspace directory; defaults to the current directory.
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Instead of one workspace, delete the state of every workspace not
+    /// used in the last DAYS days.
+    #[arg(
+        long = "older-than",
+        value_name = "DAYS",
+        conflicts_with = "path",
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub older_than_days: Option<i64>,
+
+    /// Print what would be deleted without deleting anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct EnvArgs {
+    /// Workspace directory; defaults to the current directory.
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Set a variable for commands run in this workspace.
+    #[arg(long, value_parser = parse_env_pair, value_name = "KEY=VALUE")]
+    pub set: Vec<(String, String)>,
+
+    /// Remove a variable previously set with `--set`.
+    #[arg(long, value_name = "KEY")]
+    pub unset: Vec<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct PinArgs {
+    /// Workspace directory; defaults to the current directory.
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Instruction text appended after the project docs.
+    #[arg(long, value_name = "TEXT")]
+    pub text: Option<String>,
+
+    /// Extra doc file to read after the repository's AGENTS.md files.
+    /// Repeat to pin several; replaces the previously pinned files.
+    #[arg(long = "doc", value_name = "FILE")]
+    pub docs: Vec<PathBuf>,
+
+    /// Remove the pinned text and files.
+    #[arg(long, conflicts_with_all = ["text", "docs"])]
+    pub clear: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct StatsArgs {
+    /// Output the usage totals as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct BudgetArgs {
+    /// Workspace directory; defaults to the current directory.
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Estimated cost, in USD, the workspace may reach before Codex asks to
+    /// continue. Needs a `[model_prices]` entry for the model.
+    #[arg(long = "max-usd", value_name = "USD")]
+    pub max_usd: Option<f64>,
+
+    /// Input plus output tokens the workspace may reach before Codex asks to
+    /// continue.
+    #[arg(
+        long = "max-tokens",
+        value_name = "TOKENS",
+        value_parser = clap::value_parser!(i64).range(1..)
+    )]
+    pub max_tokens: Option<i64>,
+
+    /// Remove the workspace's budget.
+    #[arg(long, conflicts_with_all = ["max_usd", "max_tokens"])]
+    pub clear: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct McpArgs {
+    /// Name of the server in `[mcp_servers]`.
+    #[arg(value_name = "NAME")]
+    pub name: String,
+
+    /// Workspace directory; defaults to the current directory.
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Whether to start the server in this workspace, regardless of its
+    /// global `enabled` setting.
+    #[arg(long, value_name = "BOOL")]
+    pub enabled: Option<bool>,
+
+    /// Argument to launch the server with instead of its configured `args`.
+    /// Repeat for several; stdio servers only.
+    #[arg(long = "arg", value_name = "ARG", allow_hyphen_values = true)]
+    pub args: Vec<String>,
+
+    /// Set an environment variable for the server; stdio servers only.
+    #[arg(long = "env", value_parser = parse_env_pair, value_name = "KEY=VALUE")]
+    pub env: Vec<(String, String)>,
+
+    /// Remove a variable previously set with `--env`.
+    #[arg(long = "unset-env", value_name = "KEY")]
+    pub unset_env: Vec<String>,
+
+    /// Startup timeout in seconds.
+    #[arg(long = "startup-timeout", value_name = "SECONDS")]
+    pub startup_timeout_sec: Option<f64>,
+
+    /// Remove every override for this server before applying the other flags.
+    #[arg(long)]
+    pub reset: bool,
+}
+
+impl WorkspaceCli {
+    pub async fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        match self.subcommand {
+            WorkspaceSubcommand::List(args) => run_list(&codex_home, args),
+            WorkspaceSubcommand::Show(args) => run_show(&codex_home, args),
+            WorkspaceSubcommand::Clear(args) => run_clear(&codex_home, args),
+            WorkspaceSubcommand::Env(args) => run_env(&codex_home, args),
+            WorkspaceSubcommand::Pin(args) => run_pin(&codex_home, args),
+            WorkspaceSubcommand::Stats(args) => run_stats(&codex_home, args),
+            WorkspaceSubcommand::Budget(args) => run_budget(&codex_home, args),
+            WorkspaceSubcommand::Mcp(args) => run_mcp(&codex_home, args).await,
+        }
+    }
+}
+
+fn run_list(codex_home: &Path, list_args: ListArgs) -> Result<()> {
+    let mut entries = list_workspace_states(codex_home).with_context(|| {
+        format!(
+            "failed to read workspace state from {}",
+            codex_home.display()
+        )
+    })?;
+    // Most recently used first.
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_used()));
+
+    if list_args.json {
+        let json_entries: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                serde_json::json!({
+                    "workspace_path": entry.state.workspace_path,
+                    "file": entry.file,
+                    "last_used": entry.last_used().and_then(unix_seconds),
+                    "state": entry.state,
+                })
+            })
+            .collect();
+        let output = serde_json::to_string_pretty(&json_entries)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No workspace state saved yet.");
+        return Ok(());
+    }
+
+    let now = SystemTime::now();
+    let rows: Vec<[String; 3]> = entries
+        .iter()
+        .map(|entry| {
+            [
+                workspace_display(entry),
+                entry
+                    .last_used()
+                    .map(|last_used| format_age(now, last_used))
+                    .unwrap_or_else(|| "-".to_string()),
+                summarize_state(&entry.state),
+            ]
+        })
+        .collect();
+
+    let mut widths = ["Workspace".len(), "Last used".len()];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    println!(
+        "{workspace:<workspace_w$}  {last_used:<last_used_w$}  Settings",
+        workspace = "Workspace",
+        last_used = "Last used",
+        workspace_w = widths[0],
+        last_used_w = widths[1],
+    );
+    for [workspace, last_used, settings] in &rows {
+        println!(
+            "{workspace:<workspace_w$}  {last_used:<last_used_w$}  {settings}",
+            workspace_w = widths[0],
+            last_used_w = widths[1],
+        );
+    }
+
+    Ok(())
+}
+
+fn run_show(codex_home: &Path, show_args: ShowArgs) -> Result<()> {
+    let ShowArgs { path, json } = show_args;
+    let workspace = resolve_workspace(path)?;
+    let file = workspace_state_location(codex_home, &workspace);
+    if !workspace_state_exists(codex_home, &workspace)
+        .with_context(|| format!("failed to read {}", file.display()))?
+    {
+        println!("No state saved for {}.", workspace.display());
+        return Ok(());
+    }
+    let state = load_workspace_state(codex_home, &workspace)
+        .with_context(|| format!("failed to read {}", file.display()))?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&serde_json::json!({
+            "workspace_path": workspace,
+            "file": file,
+            "state": state,
+        }))?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    println!("{}", workspace.display());
+    println!("  file: {}", file.display());
+    let contents = toml::to_string_pretty(&state)?;
+    for line in contents.lines() {
+        println!("  {line}");
+    }
+
+    Ok(())
+}
+
+fn run_clear(codex_home: &Path, clear_args: ClearArgs) -> Result<()> {
+    let ClearArgs {
+        path,
+        older_than_days,
+        dry_run,
+    } = clear_args;
+
+    let Some(days) = older_than_days else {
+        let workspace = resolve_workspace(path)?;
+        if dry_run {
+            let file = workspace_state_location(codex_home, &workspace);
+            if workspace_state_exists(codex_home, &workspace)
+                .with_context(|| format!("failed to read {}", file.display()))?
+            {
+                println!("Would remove the state for {}.", workspace.display());
+            } else {
+                println!("No state saved for {}.", workspace.display());
+            }
+            return Ok(());
+        }
+        let removed = clear_workspace_state(codex_home, &workspace)
+            .with_context(|| format!("failed to clear state for {}", workspace.display()))?;
+        if removed {
+            println!("Cleared saved state for {}.", workspace.display());
+        } else {
+            println!("No state saved for {}.", workspace.display());
+        }
+        return Ok(());
+    };
+
+    let max_age =
+        Duration::from_secs(u64::try_from(days.saturating_mul(SECONDS_PER_DAY)).unwrap_or(0));
+    let now = SystemTime::now();
+    let entries = list_workspace_states(codex_home).with_context(|| {
+        format!(
+            "failed to read workspace state from {}",
+            codex_home.display()
+        )
+    })?;
+    let mut removed = 0;
+    for entry in entries {
+        let stale = entry
+            .last_used()
+            .and_then(|last_used| now.duration_since(last_used).ok())
+            .is_some_and(|age| age >= max_age);
+        if !stale {
+            continue;
+        }
+        let workspace = workspace_display(&entry);
+        if dry_run {
+            println!("Would remove {workspace}");
+        } else {
+            remove_workspace_state_entry(codex_home, &entry)
+                .with_context(|| format!("failed to remove the state for {workspace}"))?;
+            println!("Removed {workspace}");
+        }
+        removed += 1;
+    }
+
+    if removed == 0 {
+        println!("No workspace state older than {days} days.");
+    }
+
+    Ok(())
+}
+
+fn run_env(codex_home: &Path, env_args: EnvArgs) -> Result<()> {
+    let EnvArgs { path, set, unset } = env_args;
+    let workspace = resolve_workspace(path)?;
+
+    for key in &unset {
+        persist_env_var(codex_home, &workspace, key, None)
+            .with_context(|| format!("failed to unset {key} for {}", workspace.display()))?;
+    }
+    for (key, value) in &set {
+        persist_env_var(codex_home, &workspace, key, Some(value))
+            .with_context(|| format!("failed to set {key} for {}", workspace.display()))?;
+    }
+
+    let env = load_workspace_env(codex_home, &workspace)
+        .with_context(|| format!("failed to read state for {}", workspace.display()))?;
+    if env.is_empty() {
+        println!("No environment variables set for {}.", workspace.display());
+        return Ok(());
+    }
+    let mut vars: Vec<_> = env.into_iter().collect();
+    vars.sort();
+    for (key, value) in vars {
+        println!("{key}={value}");
+    }
+
+    Ok(())
+}
+
+fn run_pin(codex_home: &Path, pin_args: PinArgs) -> Result<()> {
+    let PinArgs {
+        path,
+        text,
+        docs,
+        clear,
+    } = pin_args;
+    let workspace = resolve_workspace(path)?;
+    let context = || format!("failed to update pins for {}", workspace.display());
+
+    if clear {
+        persist_pinned_instructions(codex_home, &workspace, None).with_context(context)?;
+        persist_pinned_docs(codex_home, &workspace, Vec::new()).with_context(context)?;
+    }
+    if let Some(text) = text.as_deref() {
+        persist_pinned_instructions(codex_home, &workspace, Some(text)).with_context(context)?;
+    }
+    if !docs.is_empty() {
+        let docs = docs
+            .iter()
+            .map(std::path::absolute)
+            .collect::<std::io::Result<Vec<_>>>()
+            .context("failed to resolve pinned doc paths")?;
+        persist_pinned_docs(codex_home, &workspace, docs).with_context(context)?;
+    }
+
+    let state = load_workspace_state(codex_home, &workspace)
+        .with_context(|| format!("failed to read state for {}", workspace.display()))?;
+    if state.pinned_instructions.is_none() && state.pinned_docs.is_empty() {
+        println!("Nothing pinned for {}.", workspace.display());
+        return Ok(());
+    }
+    for doc in &state.pinned_docs {
+        println!("doc: {}", doc.display());
+    }
+    if let Some(text) = state.pinned_instructions.as_deref() {
+        println!("text:");
+        for line in text.lines() {
+            println!("  {line}");
+        }
+    }
+
+    Ok(())
+}
+
+fn run_budget(codex_home: &Path, budget_args: BudgetArgs) -> Result<()> {
+    let BudgetArgs {
+        path,
+        max_usd,
+        max_tokens,
+        clear,
+    } = budget_args;
+    let workspace = resolve_workspace(path)?;
+    let context = || format!("failed to update the budget for {}", workspace.display());
+
+    if clear {
+        persist_workspace_budget(codex_home, &workspace, None).with_context(context)?;
+    } else if max_usd.is_some() || max_tokens.is_some() {
+        let current = load_workspace_budget(codex_home, &workspace)
+            .with_context(context)?
+            .map(|budget| budget.limits)
+            .unwrap_or_default();
+        let budget = BudgetConfig {
+            max_usd: max_usd.or(current.max_usd),
+            max_tokens: max_tokens.or(current.max_tokens),
+        };
+        persist_workspace_budget(codex_home, &workspace, Some(budget)).with_context(context)?;
+    }
+
+    let Some(budget) = load_workspace_budget(codex_home, &workspace)
+        .with_context(|| format!("failed to read state for {}", workspace.display()))?
+    else {
+        println!("No budget set for {}.", workspace.display());
+        return Ok(());
+    };
+    let usage = &budget.usage;
+    if let Some(max_usd) = budget.limits.max_usd {
+        println!("cost:   ${:.2} of ${max_usd:.2}", usage.estimated_cost_usd);
+    }
+    if let Some(max_tokens) = budget.limits.max_tokens {
+        println!("tokens: {} of {max_tokens}", usage.total_tokens());
+    }
+
+    Ok(())
+}
+
+fn run_stats(codex_home: &Path, stats_args: StatsArgs) -> Result<()> {
+    let mut entries = list_workspace_states(codex_home).with_context(|| {
+        format!(
+            "failed to read workspace state from {}",
+            codex_home.display()
+        )
+    })?;
+    entries.retain(|entry| !entry.state.usage.is_empty());
+    // Heaviest users first.
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.state.usage.total_tokens()));
+
+    if stats_args.json {
+        let json_entries: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                serde_json::json!({
+                    "workspace_path": entry.state.workspace_path,
+                    "usage": entry.state.usage,
+                })
+            })
+            .collect();
+        let output = serde_json::to_string_pretty(&json_entries)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No workspace usage recorded yet.");
+        return Ok(());
+    }
+
+    let headers = ["Workspace", "Sessions", "Input", "Output", "Est. cost"];
+    let rows: Vec<[String; 5]> = entries
+        .iter()
+        .map(|entry| {
+            let usage = &entry.state.usage;
+            let cost = if usage.unpriced_sessions < usage.sessions {
+                format!("${:.2}", usage.estimated_cost_usd)
+            } else {
+                "-".to_string()
+            };
+            [
+                workspace_display(entry),
+                usage.sessions.to_string(),
+                usage.input_tokens.to_string(),
+                usage.output_tokens.to_string(),
+                cost,
+            ]
+        })
+        .collect();
+
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    for row in std::iter::once(headers.map(str::to_string)).chain(rows) {
+        let [workspace, sessions, input, output, cost] = row;
+        println!(
+            "{workspace:<w0$}  {sessions:>w1$}  {input:>w2$}  {output:>w3$}  {cost:>w4$}",
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+        );
+    }
+    if entries
+        .iter()
+        .any(|entry| entry.state.usage.unpriced_sessions > 0)
+    {
+        println!();
+        println!(
+            "Costs only cover sessions whose model has a `[model_prices]` entry in config.toml."
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_mcp(codex_home: &Path, mcp_args: McpArgs) -> Result<()> {
+    let McpArgs {
+        name,
+        path,
+        enabled,
+        args,
+        env,
+        unset_env,
+        startup_timeout_sec,
+        reset,
+    } = mcp_args;
+    if let Some(secs) = startup_timeout_sec
+        && !(secs.is_finite() && secs >= 0.0)
+    {
+        bail!("--startup-timeout must be a non-negative number of seconds");
+    }
+    let workspace = resolve_workspace(path)?;
+
+    let changed = reset
+        || enabled.is_some()
+        || !args.is_empty()
+        || !env.is_empty()
+        || !unset_env.is_empty()
+        || startup_timeout_sec.is_some();
+    if changed {
+        update_workspace_mcp_server(codex_home, &workspace, &name, |overrides| {
+            if reset {
+                *overrides = Default::default();
+            }
+            if enabled.is_some() {
+                overrides.enabled = enabled;
+            }
+            if !args.is_empty() {
+                overrides.args = Some(args);
+            }
+            for key in &unset_env {
+                overrides.env.remove(key);
+            }
+            overrides.env.extend(env);
+            if let Some(secs) = startup_timeout_sec {
+                overrides.startup_timeout_sec = Some(secs);
+            }
+        })
+        .with_context(|| format!("failed to update MCP overrides for {}", workspace.display()))?;
+    }
+
+    // Overrides for unknown servers are kept but have no effect.
+    if let Ok(servers) = load_global_mcp_servers(codex_home).await
+        && !servers.contains_key(&name)
+    {
+        eprintln!(
+            "Note: no MCP server named `{name}` is configured; these overrides apply once it is added."
+        );
+    }
+
+    let state = load_workspace_state(codex_home, &workspace)
+        .with_context(|| format!("failed to read state for {}", workspace.display()))?;
+    let Some(overrides) = state.mcp_servers.get(&name) else {
+        println!(
+            "No overrides for MCP server `{name}` in {}.",
+            workspace.display()
+        );
+        return Ok(());
+    };
+    println!("{name} in {}:", workspace.display());
+    let contents = toml::to_string_pretty(overrides)?;
+    for line in contents.lines() {
+        println!("  {line}");
+    }
+
+    Ok(())
+}
+
+fn resolve_workspace(path: Option<PathBuf>) -> Result<PathBuf> {
+    match path {
+        Some(path) => Ok(path),
+        None => std::env::current_dir().context("failed to resolve the current directory"),
+    }
+}
+
+fn workspace_display(entry: &WorkspaceStateEntry) -> String {
+    match entry.state.workspace_path.as_ref() {
+        Some(path) => path.display().to_string(),
+        None => format!("(unknown, {})", entry.key),
+    }
+}
+
+/// `model gpt-5-codex, approval never, MCP off: docs`, or `-` when nothing
+/// but the path is saved.
+fn summarize_state(state: &WorkspaceState) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    if let Some(model) = state.model.as_ref() {
+        parts.push(format!("model {model}"));
+    }
+    if let Some(effort) = state.model_reasoning_effort {
+        parts.push(format!("effort {effort}"));
+    }
+    if let Some(approval) = state.approval_policy {
+        parts.push(format!("approval {approval}"));
+    }
+    if let Some(sandbox) = state.sandbox_mode {
+        parts.push(format!("sandbox {sandbox}"));
+    }
+    let mut disabled: Vec<&str> = state
+        .mcp_servers
+        .iter()
+        .filter(|(_, server)| server.enabled == Some(false))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !disabled.is_empty() {
+        disabled.sort_unstable();
+        parts.push(format!("MCP off: {}", disabled.join(", ")));
+    }
+    let mut launched: Vec<&str> = state
+        .mcp_servers
+        .iter()
+        .filter(|(_, server)| server.overrides_launch())
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !launched.is_empty() {
+        launched.sort_unstable();
+        parts.push(format!("MCP overrides: {}", launched.join(", ")));
+    }
+    if !state.env.is_empty() {
+        let mut keys: Vec<&str> = state.env.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        parts.push(format!("env: {}", keys.join(", ")));
+    }
+    if state.pinned_instructions.is_some() || !state.pinned_docs.is_empty() {
+        parts.push("pinned instructions".to_string());
+    }
+    if state.statusline.is_some() {
+        parts.push("status line".to_string());
+    }
+    if parts.is_empty() {
+        "-".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+pub(crate) fn format_age(now: SystemTime, last_used: SystemTime) -> String {
+    let seconds = now
+        .duration_since(last_used)
+        .map(|age| i64::try_from(age.as_secs()).unwrap_or(i64::MAX))
+        .unwrap_or_default();
+    match seconds {
+        ..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", seconds / 60),
+        3600..SECONDS_PER_DAY => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / SECONDS_PER_DAY),
+    }
+}
+
+pub(crate) fn unix_seconds(time: SystemTime) -> Option<i64> {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+}
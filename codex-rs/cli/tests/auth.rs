@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home)
+        .env_remove("CODEX_API_KEY")
+        .env_remove("OPENAI_API_KEY");
+    Ok(cmd)
+}
+
+#[test]
+fn status_without_credentials_suggests_login() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["auth", "status"])
+        .assert()
+        .success()
+        .stdout(contains("No credentials found."))
+        .stdout(contains("Not logged in: run `codex login`."));
+
+    Ok(())
+}
+
+#[test]
+fn status_marks_the_credential_in_use() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("auth.json"),
+        r#"{"OPENAI_API_KEY":"sk-proj-1234567890ABCDE"}"#,
+    )?;
+    codex_code88::TokenStore::new(codex_home.path()).save_token("login-token", "browser_login")?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .env("CODEX_API_KEY", "sk-exec-1234567890VWXYZ")
+        .args(["auth", "status", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(parsed["provider"], "openai");
+    let usages: Vec<(String, String)> = parsed["sources"]
+        .as_array()
+        .expect("sources")
+        .iter()
+        .map(|source| {
+            (
+                source["credential"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                source["usage"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        usages,
+        vec![
+            ("88code login token".to_string(), "unused".to_string()),
+            (
+                "API key sk-exec-***VWXYZ".to_string(),
+                "codex exec only".to_string()
+            ),
+            ("API key sk-proj-***ABCDE".to_string(), "in use".to_string()),
+        ]
+    );
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args([
+        "-c",
+        "model_provider=\"relay\"",
+        "-c",
+        "model_providers.relay={name=\"relay\", base_url=\"https://relay.example/v1\", code88_auth=true}",
+        "auth",
+        "status",
+    ])
+    .assert()
+    .success()
+    .stdout(contains("Provider: relay (88code login token)"))
+    .stdout(contains("88code login token"))
+    .stdout(contains("in use"));
+
+    Ok(())
+}
@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+use codex_core::config::types::ModelPrice;
+use codex_core::config::types::WorkspaceStateBackend;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::TokenUsage;
+use codex_core::workspace_state::ensure_workspace_state_backend;
+use codex_core::workspace_state::list_workspace_states;
+use codex_core::workspace_state::load_workspace_env;
+use codex_core::workspace_state::persist_mcp_enabled;
+use codex_core::workspace_state::persist_model_selection;
+use codex_core::workspace_state::persist_policy_selection;
+use codex_core::workspace_state::record_session_usage;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn list_shows_empty_state() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["workspace", "list"])
+        .assert()
+        .success()
+        .stdout(contains("No workspace state saved yet."));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_and_show_render_saved_settings() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    persist_model_selection(codex_home.path(), workspace.path(), "gpt-5-codex", None)?;
+    persist_policy_selection(
+        codex_home.path(),
+        workspace.path(),
+        AskForApproval::Never,
+        &SandboxPolicy::DangerFullAccess,
+    )
+    .await?;
+    persist_mcp_enabled(codex_home.path(), workspace.path(), "docs", false)?;
+    let workspace_path = canonical_display(workspace.path());
+
+    let mut list = codex_command(codex_home.path())?;
+    let output = list.args(["workspace", "list"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Workspace"), "{stdout}");
+    assert!(stdout.contains(&workspace_path), "{stdout}");
+    assert!(
+        stdout.contains(
+            "model gpt-5-codex, approval never, sandbox danger-full-access, MCP off: docs"
+        ),
+        "{stdout}"
+    );
+
+    let mut list_json = codex_command(codex_home.path())?;
+    let output = list_json.args(["workspace", "list", "--json"]).output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    let entries = parsed.as_array().expect("json array");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["workspace_path"], workspace_path.as_str());
+    assert_eq!(entries[0]["state"]["model"], "gpt-5-codex");
+
+    let mut show = codex_command(codex_home.path())?;
+    show.args(["workspace", "show"])
+        .arg(workspace.path())
+        .assert()
+        .success()
+        .stdout(contains("model = \"gpt-5-codex\""))
+        .stdout(contains("approval_policy = \"never\""));
+
+    Ok(())
+}
+
+#[cfg(feature = "sqlite-workspace-state")]
+#[test]
+fn show_and_clear_use_the_sqlite_store() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    ensure_workspace_state_backend(codex_home.path(), WorkspaceStateBackend::Sqlite)?;
+    persist_model_selection(codex_home.path(), workspace.path(), "gpt-5-codex", None)?;
+    let db = codex_home.path().join("workspace_state.sqlite");
+
+    let mut show = codex_command(codex_home.path())?;
+    show.args(["workspace", "show"])
+        .arg(workspace.path())
+        .assert()
+        .success()
+        .stdout(contains(format!("file: {}", db.display())))
+        .stdout(contains("model = \"gpt-5-codex\""));
+
+    let mut clear = codex_command(codex_home.path())?;
+    clear
+        .args(["workspace", "clear", "--older-than", "0"])
+        .assert()
+        .success()
+        .stdout(contains("Removed"));
+    assert!(list_workspace_states(codex_home.path())?.is_empty());
+    assert!(db.exists());
+
+    Ok(())
+}
+
+#[test]
+fn clear_removes_one_workspace_or_stale_entries() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let first = TempDir::new()?;
+    let second = TempDir::new()?;
+    persist_model_selection(codex_home.path(), first.path(), "gpt-5-codex", None)?;
+    persist_model_selection(codex_home.path(), second.path(), "gpt-5", None)?;
+
+    let mut clear = codex_command(codex_home.path())?;
+    clear
+        .args(["workspace", "clear"])
+        .arg(first.path())
+        .assert()
+        .success()
+        .stdout(contains("Cleared saved state"));
+    assert_eq!(list_workspace_states(codex_home.path())?.len(), 1);
+
+    let mut dry_run = codex_command(codex_home.path())?;
+    dry_run
+        .args(["workspace", "clear", "--older-than", "0", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("Would remove"));
+    assert_eq!(list_workspace_states(codex_home.path())?.len(), 1);
+
+    let mut recent = codex_command(codex_home.path())?;
+    recent
+        .args(["workspace", "clear", "--older-than", "30"])
+        .assert()
+        .success()
+        .stdout(contains("No workspace state older than 30 days."));
+    assert_eq!(list_workspace_states(codex_home.path())?.len(), 1);
+
+    let mut stale = codex_command(codex_home.path())?;
+    stale
+        .args(["workspace", "clear", "--older-than", "0"])
+        .assert()
+        .success()
+        .stdout(contains("Removed"));
+    assert!(list_workspace_states(codex_home.path())?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn env_sets_and_unsets_workspace_variables() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+
+    let mut set = codex_command(codex_home.path())?;
+    set.args(["workspace", "env"])
+        .arg(workspace.path())
+        .args([
+            "--set",
+            "AWS_PROFILE=staging",
+            "--set",
+            "DATABASE_URL=postgres://db",
+        ])
+        .assert()
+        .success()
+        .stdout("AWS_PROFILE=staging\nDATABASE_URL=postgres://db\n");
+
+    let mut unset = codex_command(codex_home.path())?;
+    unset
+        .args(["workspace", "env"])
+        .arg(workspace.path())
+        .args(["--unset", "DATABASE_URL"])
+        .assert()
+        .success()
+        .stdout("AWS_PROFILE=staging\n");
+    assert_eq!(
+        load_workspace_env(codex_home.path(), workspace.path())?,
+        HashMap::from([("AWS_PROFILE".to_string(), "staging".to_string())])
+    );
+
+    let mut list = codex_command(codex_home.path())?;
+    list.args(["workspace", "list"])
+        .assert()
+        .success()
+        .stdout(contains("env: AWS_PROFILE"));
+
+    Ok(())
+}
+
+#[test]
+fn pin_sets_and_clears_instructions() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    let doc = workspace.path().join("team.md");
+    std::fs::write(&doc, "conventions")?;
+
+    let mut pin = codex_command(codex_home.path())?;
+    pin.args(["workspace", "pin"])
+        .arg(workspace.path())
+        .args(["--text", "Use tabs.", "--doc"])
+        .arg(&doc)
+        .assert()
+        .success()
+        .stdout(contains("text:\n  Use tabs."))
+        .stdout(contains("team.md"));
+
+    let mut clear = codex_command(codex_home.path())?;
+    clear
+        .args(["workspace", "pin"])
+        .arg(workspace.path())
+        .arg("--clear")
+        .assert()
+        .success()
+        .stdout(contains("Nothing pinned"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn stats_reports_usage_per_workspace() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let busy = TempDir::new()?;
+    let quiet = TempDir::new()?;
+    let usage = TokenUsage {
+        input_tokens: 900_000,
+        cached_input_tokens: 0,
+        output_tokens: 100_000,
+        reasoning_output_tokens: 0,
+        total_tokens: 1_000_000,
+    };
+    let price = ModelPrice {
+        input: 1.0,
+        cached_input: None,
+        output: 10.0,
+    };
+    record_session_usage(codex_home.path(), busy.path(), &usage, Some(&price)).await?;
+    record_session_usage(codex_home.path(), busy.path(), &usage, Some(&price)).await?;
+    record_session_usage(codex_home.path(), quiet.path(), &usage, None).await?;
+    persist_model_selection(codex_home.path(), quiet.path(), "gpt-5", None)?;
+
+    let mut stats = codex_command(codex_home.path())?;
+    let output = stats.args(["workspace", "stats"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("Workspace"), "{stdout}");
+    assert!(
+        lines[1].starts_with(&canonical_display(busy.path())),
+        "{stdout}"
+    );
+    assert!(
+        lines[1].ends_with("2  1800000  200000      $3.80"),
+        "{stdout}"
+    );
+    assert!(
+        lines[2].starts_with(&canonical_display(quiet.path())),
+        "{stdout}"
+    );
+    assert!(lines[2].ends_with("-"), "{stdout}");
+    assert!(stdout.contains("[model_prices]"), "{stdout}");
+
+    let mut stats_json = codex_command(codex_home.path())?;
+    let output = stats_json.args(["workspace", "stats", "--json"]).output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(parsed[0]["usage"]["sessions"], 2);
+    assert_eq!(parsed[1]["usage"]["unpriced_sessions"], 1);
+
+    Ok(())
+}
+
+#[test]
+fn mcp_sets_and_resets_server_overrides() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "[mcp_servers.db]\ncommand = \"db-mcp\"\n",
+    )?;
+
+    let mut set = codex_command(codex_home.path())?;
+    set.args(["workspace", "mcp", "db"])
+        .arg(workspace.path())
+        .args([
+            "--arg",
+            "--db",
+            "--arg",
+            "local",
+            "--env",
+            "DATABASE_URL=postgres://localhost",
+            "--startup-timeout",
+            "30",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("\"--db\""))
+        .stdout(contains("DATABASE_URL = \"postgres://localhost\""))
+        .stdout(contains("startup_timeout_sec = 30"));
+
+    let mut list = codex_command(codex_home.path())?;
+    list.args(["workspace", "list"])
+        .assert()
+        .success()
+        .stdout(contains("MCP overrides: db"));
+
+    let mut unknown = codex_command(codex_home.path())?;
+    unknown
+        .args(["workspace", "mcp", "search"])
+        .arg(workspace.path())
+        .args(["--enabled", "false"])
+        .assert()
+        .success()
+        .stderr(contains("no MCP server named `search`"));
+
+    let mut reset = codex_command(codex_home.path())?;
+    reset
+        .args(["workspace", "mcp", "db"])
+        .arg(workspace.path())
+        .arg("--reset")
+        .assert()
+        .success()
+        .stdout(contains("No overrides for MCP server `db`"));
+
+    Ok(())
+}
+
+fn canonical_display(path: &Path) -> String {
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
@@ -13,6 +13,14 @@ use crate::Code88Error;
 /// Default CDP debug port.
 const DEFAULT_DEBUG_PORT: u16 = 9222;
 
+/// Codex sets the remote debugging endpoint itself to reach the browser over
+/// CDP; extra flags must not redirect it.
+const RESERVED_FLAG_PREFIX: &str = "--remote-debugging-";
+
+/// The login always runs in a throwaway profile (`--user-data-dir`), so
+/// flags choosing another profile would be silently overridden.
+const PROFILE_FLAGS: &[&str] = &["--user-data-dir", "--profile-directory"];
+
 /// Alternative ports to try if default is in use.
 const ALTERNATIVE_PORTS: &[u16] = &[9223, 9224, 9225, 9226];
 
@@ -28,10 +36,12 @@ impl BrowserInstance {
         format!("http://localhost:{}", self.debug_port)
     }
 
-    /// Kill the browser process.
+    /// Kill the browser process and wait for it to exit, which also frees
+    /// its profile for [`relaunch`].
     pub fn kill(&mut self) {
         if let Some(ref mut process) = self.process {
             let _ = process.kill();
+            let _ = process.wait();
             debug!("Browser process killed");
         }
         self.process = None;
@@ -45,11 +55,26 @@ impl Drop for BrowserInstance {
     }
 }
 
+/// Names (lowercased, without extension) that mark a browser executable as
+/// Chromium-based, so it can be driven over CDP.
+#[cfg(any(target_os = "windows", target_os = "macos", test))]
+const CHROMIUM_NAMES: &[&str] = &["chrome", "edge", "chromium", "brave", "vivaldi"];
+
 /// Detect a Chromium-based browser on the system.
 ///
-/// Searches for Chrome, Edge, or Chromium in common installation paths.
-/// Returns the path to the browser executable if found.
+/// Prefers the system default browser when it is Chromium-based (macOS and
+/// Windows), so the login opens in the browser the user actually signs in
+/// with. Otherwise searches for Chrome, Edge, or Chromium in common
+/// installation paths. Returns the path to the browser executable if found.
 pub fn detect_browser() -> Option<PathBuf> {
+    if let Some(path) = default_browser() {
+        info!("Using the default browser: {path:?}");
+        return Some(path);
+    }
+    detect_installed_browser()
+}
+
+fn detect_installed_browser() -> Option<PathBuf> {
     #[cfg(target_os = "windows")]
     {
         detect_browser_windows()
@@ -71,6 +96,92 @@ pub fn detect_browser() -> Option<PathBuf> {
     }
 }
 
+#[cfg(any(target_os = "windows", target_os = "macos", test))]
+fn is_chromium_based(executable: &Path) -> bool {
+    let Some(stem) = executable.file_stem() else {
+        return false;
+    };
+    let stem = stem.to_string_lossy().to_lowercase();
+    CHROMIUM_NAMES.iter().any(|name| stem.contains(name))
+}
+
+/// The default browser, from the `https` handler the user chose in Windows
+/// settings, when it is Chromium-based.
+#[cfg(target_os = "windows")]
+fn default_browser() -> Option<PathBuf> {
+    let prog_id = reg_query(
+        r"HKCU\Software\Microsoft\Windows\Shell\Associations\UrlAssociations\https\UserChoice",
+        Some("ProgId"),
+    )?;
+    let command = reg_query(&format!(r"HKCR\{prog_id}\shell\open\command"), None)?;
+    let path = PathBuf::from(command_executable(&command)?);
+    debug!("Default browser for https: {prog_id} ({path:?})");
+    (is_chromium_based(&path) && path.exists()).then_some(path)
+}
+
+/// The default browser, as `NSWorkspace` (LaunchServices) resolves `https`
+/// URLs, when it is Chromium-based. Asked through `osascript` so the crate
+/// needs no Objective-C bindings.
+#[cfg(target_os = "macos")]
+fn default_browser() -> Option<PathBuf> {
+    const SCRIPT: &str = "ObjC.import('AppKit'); \
+        $.NSWorkspace.sharedWorkspace.URLForApplicationToOpenURL(\
+        $.NSURL.URLWithString('https://www.88code.org/')).path.js";
+    let output = Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", SCRIPT])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let app = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    // Chromium browsers name their executable after the bundle.
+    let path = app.join("Contents/MacOS").join(app.file_stem()?);
+    debug!("Default browser for https: {path:?}");
+    (is_chromium_based(&path) && path.exists()).then_some(path)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn default_browser() -> Option<PathBuf> {
+    None
+}
+
+/// The string data of `value` under `key` (the default value when `None`).
+#[cfg(target_os = "windows")]
+fn reg_query(key: &str, value: Option<&str>) -> Option<String> {
+    let mut cmd = Command::new("reg");
+    cmd.args(["query", key]);
+    match value {
+        Some(value) => cmd.args(["/v", value]),
+        None => cmd.arg("/ve"),
+    };
+    let output = cmd.output().ok().filter(|output| output.status.success())?;
+    parse_reg_value(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// The data of the first string value in `reg query` output, e.g.
+/// `    ProgId    REG_SZ    MSEdgeHTM`.
+#[cfg(any(target_os = "windows", test))]
+fn parse_reg_value(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        ["REG_SZ", "REG_EXPAND_SZ"].iter().find_map(|kind| {
+            let (_, data) = line.split_once(&format!("    {kind}    "))?;
+            let data = data.trim();
+            (!data.is_empty()).then(|| data.to_string())
+        })
+    })
+}
+
+/// The executable of a shell open command such as
+/// `"C:\...\msedge.exe" --single-argument %1`.
+#[cfg(any(target_os = "windows", test))]
+fn command_executable(command: &str) -> Option<&str> {
+    let command = command.trim();
+    let executable = match command.strip_prefix('"') {
+        Some(rest) => rest.split('"').next()?,
+        None => command.split_whitespace().next()?,
+    };
+    (!executable.is_empty()).then_some(executable)
+}
+
 #[cfg(target_os = "windows")]
 fn detect_browser_windows() -> Option<PathBuf> {
     let candidates = [
@@ -204,31 +315,114 @@ fn find_available_port() -> Option<u16> {
         .copied()
 }
 
+/// How the browser login launches the browser and leaves it, from
+/// `[code88]` in `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BrowserOptions {
+    /// Extra Chromium flags (`browser_args`), see [`validate_browser_args`].
+    pub args: Vec<String>,
+    /// Reopen the browser without remote debugging once the token is
+    /// captured instead of leaving it closed (`keep_browser_open`).
+    pub keep_open: bool,
+    /// Page the reopened browser shows (`success_url`); the 88code page
+    /// when `None`.
+    pub success_url: Option<String>,
+}
+
+impl BrowserOptions {
+    /// Check the options before a login uses them.
+    pub fn validate(&self) -> Result<(), Code88Error> {
+        validate_browser_args(&self.args)?;
+        if let Some(url) = &self.success_url
+            && !url.starts_with("https://")
+            && !url.starts_with("http://")
+        {
+            return Err(Code88Error::InvalidSuccessUrl(url.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// Check extra Chromium flags for the browser login: each must be a single
+/// `--flag` or `--flag=value`, and none may set the remote debugging
+/// endpoint Codex connects to or the profile Codex launches with.
+pub fn validate_browser_args(args: &[String]) -> Result<(), Code88Error> {
+    for arg in args {
+        let name = arg.split('=').next().unwrap_or(arg);
+        if !arg.starts_with("--") || name.len() <= 2 || name.chars().any(char::is_whitespace) {
+            return Err(Code88Error::InvalidBrowserArg(arg.clone()));
+        }
+        if name.starts_with(RESERVED_FLAG_PREFIX) || PROFILE_FLAGS.contains(&name) {
+            return Err(Code88Error::InvalidBrowserArg(arg.clone()));
+        }
+    }
+    Ok(())
+}
+
 /// Launch a browser with remote debugging enabled.
 ///
 /// # Arguments
 /// * `browser_path` - Path to the browser executable
 /// * `url` - Initial URL to navigate to
+/// * `extra_args` - Flags from `code88.browser_args`, appended after Codex's
+///   own; see [`validate_browser_args`]
 ///
 /// # Returns
 /// A `BrowserInstance` containing the process handle and debug port.
-pub fn launch_with_debug(browser_path: &Path, url: &str) -> Result<BrowserInstance, Code88Error> {
+pub fn launch_with_debug(
+    browser_path: &Path,
+    url: &str,
+    extra_args: &[String],
+) -> Result<BrowserInstance, Code88Error> {
+    validate_browser_args(extra_args)?;
     let port = find_available_port().ok_or(Code88Error::PortInUse(DEFAULT_DEBUG_PORT))?;
 
     info!("Launching browser with debug port {}", port);
 
-    // Build command with appropriate flags
+    let debugging_flag = format!("--remote-debugging-port={port}");
+    let process = browser_command(browser_path, Some(debugging_flag.as_str()), url, extra_args)
+        .spawn()
+        .map_err(|e| Code88Error::BrowserLaunchFailed(e.to_string()))?;
+
+    Ok(BrowserInstance {
+        process: Some(process),
+        debug_port: port,
+    })
+}
+
+/// Open `url` in the login's profile again, without remote debugging, so
+/// the signed-in browser stays open without any local process being able
+/// to drive it. Call it once the debugging instance has been killed.
+pub fn relaunch(browser_path: &Path, url: &str, extra_args: &[String]) -> Result<(), Code88Error> {
+    validate_browser_args(extra_args)?;
+    info!("Reopening the browser without remote debugging");
+
+    let mut process = browser_command(browser_path, None, url, extra_args)
+        .spawn()
+        .map_err(|e| Code88Error::BrowserLaunchFailed(e.to_string()))?;
+    // The browser outlives the login; reap it whenever the user closes it.
+    std::thread::spawn(move || process.wait());
+    Ok(())
+}
+
+fn browser_command(
+    browser_path: &Path,
+    debugging_flag: Option<&str>,
+    url: &str,
+    extra_args: &[String],
+) -> Command {
     let mut cmd = Command::new(browser_path);
 
     // Common flags for all platforms
+    cmd.args(debugging_flag);
     cmd.args([
-        &format!("--remote-debugging-port={port}"),
         "--no-first-run",
         "--no-default-browser-check",
         // Create a separate user data directory to avoid conflicts
         &format!("--user-data-dir={}", temp_user_data_dir().to_string_lossy()),
-        url,
     ]);
+    cmd.args(extra_args);
+    cmd.arg(url);
 
     // Platform-specific flags
     #[cfg(target_os = "windows")]
@@ -238,14 +432,7 @@ pub fn launch_with_debug(browser_path: &Path, url: &str) -> Result<BrowserInstan
         cmd.creation_flags(0x08000000);
     }
 
-    let process = cmd
-        .spawn()
-        .map_err(|e| Code88Error::BrowserLaunchFailed(e.to_string()))?;
-
-    Ok(BrowserInstance {
-        process: Some(process),
-        debug_port: port,
-    })
+    cmd
 }
 
 /// Get a temporary directory for browser user data.
@@ -257,6 +444,7 @@ fn temp_user_data_dir() -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::LOGIN_URL;
 
     #[test]
     fn test_detect_browser() {
@@ -267,6 +455,131 @@ mod tests {
         }
     }
 
+    #[test]
+    fn recognizes_chromium_based_executables() {
+        for path in [
+            r"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe",
+            r"C:\Program Files\Google\Chrome\Application\chrome.exe",
+            "/Applications/Brave Browser.app/Contents/MacOS/Brave Browser",
+            "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+        ] {
+            assert!(is_chromium_based(Path::new(path)), "{path}");
+        }
+        for path in [
+            r"C:\Program Files\Mozilla Firefox\firefox.exe",
+            "/Applications/Safari.app/Contents/MacOS/Safari",
+        ] {
+            assert!(!is_chromium_based(Path::new(path)), "{path}");
+        }
+    }
+
+    #[test]
+    fn reads_the_default_browser_from_reg_query_output() {
+        let prog_id = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\Shell\\Associations\\UrlAssociations\\https\\UserChoice\r\n    ProgId    REG_SZ    MSEdgeHTM\r\n\r\n";
+        assert_eq!(parse_reg_value(prog_id).as_deref(), Some("MSEdgeHTM"));
+
+        let command = "\r\nHKEY_CLASSES_ROOT\\MSEdgeHTM\\shell\\open\\command\r\n    (Default)    REG_SZ    \"C:\\Program Files (x86)\\Microsoft\\Edge\\Application\\msedge.exe\" --single-argument %1\r\n";
+        let command = parse_reg_value(command).expect("command");
+        assert_eq!(
+            command_executable(&command),
+            Some(r"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe")
+        );
+        assert_eq!(command_executable("chrome.exe %1"), Some("chrome.exe"));
+        assert_eq!(parse_reg_value("ERROR: not found\r\n"), None);
+    }
+
+    #[test]
+    fn browser_args_may_not_touch_the_debugging_endpoint_or_profile() {
+        let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(
+            validate_browser_args(&args(&[
+                "--proxy-server=http://proxy.corp:8080",
+                "--ignore-certificate-errors",
+            ]))
+            .is_ok()
+        );
+        for bad in [
+            "--remote-debugging-port=9999",
+            "--remote-debugging-address=0.0.0.0",
+            "--remote-debugging-pipe",
+            "--profile-directory=Profile 1",
+            "--user-data-dir=/tmp/profile",
+            "https://example.com",
+            "-v",
+            "--",
+            "--bad flag",
+        ] {
+            assert!(
+                matches!(
+                    validate_browser_args(&args(&[bad])),
+                    Err(Code88Error::InvalidBrowserArg(_))
+                ),
+                "{bad} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn only_the_login_launch_enables_remote_debugging() {
+        let extra = vec!["--proxy-server=http://proxy.corp:8080".to_string()];
+        let profile = format!("--user-data-dir={}", temp_user_data_dir().to_string_lossy());
+        let args = |cmd: Command| {
+            cmd.get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            args(browser_command(
+                Path::new("chrome"),
+                Some("--remote-debugging-port=9222"),
+                LOGIN_URL,
+                &extra,
+            )),
+            vec![
+                "--remote-debugging-port=9222".to_string(),
+                "--no-first-run".to_string(),
+                "--no-default-browser-check".to_string(),
+                profile.clone(),
+                extra[0].clone(),
+                LOGIN_URL.to_string(),
+            ]
+        );
+        assert_eq!(
+            args(browser_command(
+                Path::new("chrome"),
+                None,
+                LOGIN_URL,
+                &extra
+            )),
+            vec![
+                "--no-first-run".to_string(),
+                "--no-default-browser-check".to_string(),
+                profile,
+                extra[0].clone(),
+                LOGIN_URL.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn success_url_must_be_a_web_page() {
+        let options = |url: &str| BrowserOptions {
+            keep_open: true,
+            success_url: Some(url.to_string()),
+            ..BrowserOptions::default()
+        };
+        assert!(
+            options("https://intranet.corp/codex-ready")
+                .validate()
+                .is_ok()
+        );
+        assert!(matches!(
+            options("javascript:alert(1)").validate(),
+            Err(Code88Error::InvalidSuccessUrl(_))
+        ));
+    }
+
     #[test]
     fn test_port_availability() {
         // Default port might be in use, but function should work
@@ -2,15 +2,23 @@
 //!
 //! This module implements a minimal CDP client for network monitoring.
 //! It only supports the features needed for capturing login responses.
+//!
+//! A login with SSO or 2FA can leave the socket idle for minutes, long enough
+//! for a proxy or the browser to drop it without a close frame. The session
+//! pings the browser while it waits and reports a dropped connection as
+//! [`Code88Error::CdpDisconnected`], which the login retries by connecting
+//! again.
 
 use std::sync::atomic::AtomicU32;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use futures::SinkExt;
 use futures::StreamExt;
 use serde_json::Value;
 use serde_json::json;
 use tokio::net::TcpStream;
+use tokio::time::Instant;
 use tokio_tungstenite::MaybeTlsStream;
 use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::connect_async;
@@ -19,11 +27,24 @@ use tracing::debug;
 use tracing::trace;
 
 use crate::Code88Error;
+use crate::response::decode_body;
+
+/// How long the socket may stay quiet before the session pings the browser.
+const PING_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How long without any frame, pongs included, before the connection counts
+/// as silently dropped.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(45);
 
 /// CDP session for communicating with browser.
 pub struct CdpSession {
     ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
     msg_id: AtomicU32,
+    /// When the browser last sent a frame.
+    last_frame: Instant,
+    /// [`PING_INTERVAL`] and [`IDLE_TIMEOUT`]; tests shorten them.
+    ping_interval: Duration,
+    idle_timeout: Duration,
 }
 
 impl CdpSession {
@@ -52,9 +73,14 @@ impl CdpSession {
                 Code88Error::CdpConnectionFailed("No debuggable page found".to_string())
             })?;
 
+        // 3. Connect via WebSocket
+        Self::connect_websocket(ws_url).await
+    }
+
+    /// Connect to a page target's `webSocketDebuggerUrl`.
+    async fn connect_websocket(ws_url: &str) -> Result<Self, Code88Error> {
         debug!("Connecting to CDP WebSocket: {}", ws_url);
 
-        // 3. Connect via WebSocket
         let (ws, _response) = connect_async(ws_url).await.map_err(|e| {
             Code88Error::CdpConnectionFailed(format!("WebSocket connection failed: {e}"))
         })?;
@@ -64,9 +90,56 @@ impl CdpSession {
         Ok(Self {
             ws,
             msg_id: AtomicU32::new(0),
+            last_frame: Instant::now(),
+            ping_interval: PING_INTERVAL,
+            idle_timeout: IDLE_TIMEOUT,
         })
     }
 
+    /// The next data frame from the browser. Pings the browser while the
+    /// socket is quiet; a close frame, a read error, the stream ending or
+    /// [`IDLE_TIMEOUT`] without any frame is [`Code88Error::CdpDisconnected`].
+    async fn next_message(&mut self) -> Result<Message, Code88Error> {
+        loop {
+            let msg = match tokio::time::timeout(self.ping_interval, self.ws.next()).await {
+                Ok(Some(Ok(msg))) => msg,
+                Ok(Some(Err(e))) => return Err(Code88Error::CdpDisconnected(e.to_string())),
+                Ok(None) => {
+                    return Err(Code88Error::CdpDisconnected(
+                        "connection ended without a close frame".to_string(),
+                    ));
+                }
+                Err(_) => {
+                    if self.last_frame.elapsed() >= self.idle_timeout {
+                        return Err(Code88Error::CdpDisconnected(format!(
+                            "no reply for {:?}",
+                            self.idle_timeout
+                        )));
+                    }
+                    trace!("CDP socket idle, sending ping");
+                    self.ws
+                        .send(Message::Ping(Vec::new()))
+                        .await
+                        .map_err(|e| Code88Error::CdpDisconnected(e.to_string()))?;
+                    continue;
+                }
+            };
+            self.last_frame = Instant::now();
+            match msg {
+                Message::Close(frame) => {
+                    let reason = frame
+                        .map(|frame| frame.reason.to_string())
+                        .filter(|reason| !reason.is_empty())
+                        .unwrap_or_else(|| "browser closed the connection".to_string());
+                    return Err(Code88Error::CdpDisconnected(reason));
+                }
+                // tungstenite answers pings itself.
+                Message::Ping(_) | Message::Pong(_) => {}
+                msg => return Ok(msg),
+            }
+        }
+    }
+
     /// Send a CDP command and wait for response.
     async fn send_command(&mut self, method: &str, params: Value) -> Result<Value, Code88Error> {
         let id = self.msg_id.fetch_add(1, Ordering::SeqCst) + 1;
@@ -82,16 +155,11 @@ impl CdpSession {
         self.ws
             .send(Message::Text(msg.to_string()))
             .await
-            .map_err(|e| Code88Error::WebSocketError(e.to_string()))?;
+            .map_err(|e| Code88Error::CdpDisconnected(e.to_string()))?;
 
         // Wait for the response with matching id
         loop {
-            let msg = self
-                .ws
-                .next()
-                .await
-                .ok_or_else(|| Code88Error::CdpResponseError("Connection closed".to_string()))?
-                .map_err(|e| Code88Error::WebSocketError(e.to_string()))?;
+            let msg = self.next_message().await?;
 
             if let Message::Text(text) = msg {
                 let data: Value = serde_json::from_str(&text)?;
@@ -129,12 +197,7 @@ impl CdpSession {
         debug!("Waiting for response matching: {}", url_pattern);
 
         loop {
-            let msg = self
-                .ws
-                .next()
-                .await
-                .ok_or_else(|| Code88Error::CdpResponseError("Connection closed".to_string()))?
-                .map_err(|e| Code88Error::WebSocketError(e.to_string()))?;
+            let msg = self.next_message().await?;
 
             if let Message::Text(text) = msg {
                 let data: Value = serde_json::from_str(&text)?;
@@ -206,17 +269,7 @@ impl CdpSession {
             .and_then(Value::as_bool)
             .unwrap_or(false);
 
-        if is_base64 {
-            debug!("Response body is base64 encoded, decoding...");
-            use base64::Engine;
-            let decoded = base64::engine::general_purpose::STANDARD
-                .decode(body)
-                .map_err(|e| Code88Error::ParseError(format!("Base64 decode failed: {e}")))?;
-            String::from_utf8(decoded)
-                .map_err(|e| Code88Error::ParseError(format!("UTF-8 decode failed: {e}")))
-        } else {
-            Ok(body.to_string())
-        }
+        decode_body(body, is_base64)
     }
 
     /// Navigate to a URL.
@@ -248,3 +301,101 @@ impl CdpSession {
         Ok(())
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+    use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+    /// The browser side of one CDP connection.
+    pub(crate) type BrowserSocket = WebSocketStream<TcpStream>;
+
+    /// A WebSocket server on a free local port; `serve` plays the browser
+    /// for each connection in turn.
+    pub(crate) async fn spawn_browser<F, Fut>(serve: F) -> String
+    where
+        F: Fn(i64, BrowserSocket) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let url = format!(
+            "ws://{}/devtools/page/1",
+            listener.local_addr().expect("addr")
+        );
+        tokio::spawn(async move {
+            let mut connection = 0;
+            while let Ok((stream, _)) = listener.accept().await {
+                let socket = accept_async(stream).await.expect("handshake");
+                tokio::spawn(serve(connection, socket));
+                connection += 1;
+            }
+        });
+        url
+    }
+
+    /// A session with short ping and idle timeouts.
+    async fn connect_impatient(url: &str) -> CdpSession {
+        let mut session = CdpSession::connect_websocket(url).await.expect("connect");
+        session.ping_interval = Duration::from_millis(50);
+        session.idle_timeout = Duration::from_millis(200);
+        session
+    }
+
+    #[tokio::test]
+    async fn quiet_socket_is_pinged_and_stays_open() {
+        let url = spawn_browser(|_, mut socket| async move {
+            // Reading answers the ping; only then does the page speak.
+            if let Some(Ok(Message::Ping(_))) = socket.next().await {
+                let _ = socket.send(Message::Text("pinged".to_string())).await;
+            }
+            let _ = socket.next().await;
+        })
+        .await;
+
+        let mut session = connect_impatient(&url).await;
+        assert_eq!(
+            session.next_message().await.expect("message"),
+            Message::Text("pinged".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn unanswered_pings_end_the_session() {
+        let url = spawn_browser(|_, socket| async move {
+            // Never read, so pings go unanswered.
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            drop(socket);
+        })
+        .await;
+
+        let mut session = connect_impatient(&url).await;
+        let err = session.next_message().await.expect_err("idle timeout");
+        assert!(err.is_retriable());
+        assert_eq!(
+            err.to_string(),
+            "与浏览器的调试连接已断开: no reply for 200ms"
+        );
+    }
+
+    #[tokio::test]
+    async fn close_frame_ends_the_session_with_its_reason() {
+        let url = spawn_browser(|_, mut socket| async move {
+            let _ = socket
+                .send(Message::Close(Some(CloseFrame {
+                    code: CloseCode::Normal,
+                    reason: "target closed".into(),
+                })))
+                .await;
+            let _ = socket.next().await;
+        })
+        .await;
+
+        let mut session = connect_impatient(&url).await;
+        let err = session.next_message().await.expect_err("closed");
+        assert!(err.is_retriable());
+        assert_eq!(err.to_string(), "与浏览器的调试连接已断开: target closed");
+    }
+}
@@ -13,6 +13,15 @@ pub enum Code88Error {
     #[error("启动浏览器失败: {0}")]
     BrowserLaunchFailed(String),
 
+    /// A `code88.browser_args` entry is not a flag, or sets the debugging
+    /// endpoint.
+    #[error("code88.browser_args 中的参数无效: {0}")]
+    InvalidBrowserArg(String),
+
+    /// `code88.success_url` is not an http(s) URL.
+    #[error("code88.success_url 必须是 http(s) 地址: {0}")]
+    InvalidSuccessUrl(String),
+
     /// Debug port is already in use.
     #[error("调试端口 {0} 被占用")]
     PortInUse(u16),
@@ -25,10 +34,20 @@ pub enum Code88Error {
     #[error("WebSocket 通信错误: {0}")]
     WebSocketError(String),
 
+    /// The CDP connection was closed or went silent; connecting again may
+    /// succeed while the browser is still open.
+    #[error("与浏览器的调试连接已断开: {0}")]
+    CdpDisconnected(String),
+
     /// Failed to get response from CDP.
     #[error("获取浏览器响应失败: {0}")]
     CdpResponseError(String),
 
+    /// A token from `CODEX_88CODE_TOKEN` or `code88_token` would outrank the
+    /// one a login saves; holds where it comes from.
+    #[error("正在使用来自 {0} 的 88code token，登录保存的 token 不会生效；请先移除它再登录")]
+    TokenOverridden(String),
+
     /// Login operation timed out.
     #[error("登录超时，请重试")]
     Timeout,
@@ -54,6 +73,13 @@ pub enum Code88Error {
     HttpError(String),
 }
 
+impl Code88Error {
+    /// Whether the login may succeed by connecting to the browser again.
+    pub fn is_retriable(&self) -> bool {
+        matches!(self, Code88Error::CdpDisconnected(_))
+    }
+}
+
 impl From<std::io::Error> for Code88Error {
     fn from(e: std::io::Error) -> Self {
         Code88Error::IoError(e.to_string())
@@ -4,106 +4,119 @@
 //! - Detect and launch Chrome/Edge browser with remote debugging
 //! - Connect to browser via Chrome DevTools Protocol (CDP)
 //! - Monitor network requests to capture login token
-//! - Store and retrieve token from local file
+//! - Store and retrieve the token, see [`TokenStore`]
 
 mod browser;
 mod cdp;
 mod error;
+mod response;
 mod token;
 
+pub use browser::BrowserOptions;
+pub use browser::validate_browser_args;
 pub use error::Code88Error;
-pub use token::delete_token;
-pub use token::load_token;
-pub use token::save_token;
+pub use token::ResolvedToken;
+pub use token::TOKEN_ENV_VAR;
+pub use token::TokenFile;
+pub use token::TokenSource;
+pub use token::TokenStore;
+pub use token::api_key;
 pub use token::token_path;
 
-use std::path::Path;
 use std::time::Duration;
 
+use response::CapturedLogin;
+use response::parse_login_response;
 use tracing::info;
 use tracing::warn;
 
-const LOGIN_URL: &str = "https://www.88code.org/";
+/// The page users sign in on; its `getLoginInfo` response carries the token.
+pub const LOGIN_URL: &str = "https://www.88code.org/";
 const TOKEN_API_PATTERN: &str = "/admin-api/login/getLoginInfo";
 const DEFAULT_TIMEOUT_SECS: u64 = 300; // 5 minutes
+/// Reconnects to the browser after a dropped CDP connection, per login.
+const MAX_CDP_RECONNECTS: i64 = 3;
 
 /// Result type for code88 operations.
 pub type Result<T> = std::result::Result<T, Code88Error>;
 
+/// Receives the login flow's progress messages. The `_with_progress`
+/// variants take one so a TUI can show them; the others print to stderr.
+pub type LoginProgress<'a> = &'a (dyn Fn(&str) + Send + Sync);
+
+fn stderr_progress(message: &str) {
+    eprintln!("{message}");
+}
+
 /// Ensure a valid 88code token exists.
 ///
-/// If a token already exists in the config directory, it is returned directly.
+/// If `store` resolves a token, it is returned directly.
 /// Otherwise, this function will:
-/// 1. Launch browser with remote debugging enabled
+/// 1. Launch browser with remote debugging enabled, as `browser` says
 /// 2. Navigate to 88code.org login page
 /// 3. Monitor network requests for the login API response
-/// 4. Extract and save the token
+/// 4. Extract and save the token to `store`
 ///
 /// Returns the token string on success.
-pub async fn ensure_token(codex_home: &Path) -> Result<String> {
+pub async fn ensure_token(store: &TokenStore, browser: &BrowserOptions) -> Result<String> {
+    ensure_token_with_progress(store, browser, &stderr_progress).await
+}
+
+/// [`ensure_token`], reporting the login flow's progress to `progress`.
+pub async fn ensure_token_with_progress(
+    store: &TokenStore,
+    browser: &BrowserOptions,
+    progress: LoginProgress<'_>,
+) -> Result<String> {
     // Check for existing token first
-    if let Some(existing_token) = load_token(codex_home) {
-        info!("Found existing 88code token");
-        return Ok(existing_token);
+    if let Some(existing) = store.resolve_token() {
+        info!("Found existing 88code token ({:?})", existing.source);
+        return Ok(existing.token);
     }
 
     info!("No 88code token found, starting browser login flow");
-    run_browser_login(codex_home, DEFAULT_TIMEOUT_SECS).await
+    run_browser_login_with_progress(store, browser, DEFAULT_TIMEOUT_SECS, progress).await
 }
 
 /// Run the browser login flow to obtain a token.
 ///
 /// This is the main entry point for the login process when no token exists.
-pub async fn run_browser_login(codex_home: &Path, timeout_secs: u64) -> Result<String> {
-    eprintln!("\n88code: 首次使用，需要登录获取 token...");
+pub async fn run_browser_login(
+    store: &TokenStore,
+    browser: &BrowserOptions,
+    timeout_secs: u64,
+) -> Result<String> {
+    run_browser_login_with_progress(store, browser, timeout_secs, &stderr_progress).await
+}
+
+/// [`run_browser_login`], reporting progress to `progress`.
+pub async fn run_browser_login_with_progress(
+    store: &TokenStore,
+    options: &BrowserOptions,
+    timeout_secs: u64,
+    progress: LoginProgress<'_>,
+) -> Result<String> {
+    progress("88code: 首次使用，需要登录获取 token...");
 
     // 1. Detect browser
     let browser_path = browser::detect_browser().ok_or(Code88Error::NoBrowser)?;
     info!("Detected browser: {:?}", browser_path);
 
     // 2. Launch browser with remote debugging
-    let mut instance = browser::launch_with_debug(&browser_path, LOGIN_URL)?;
+    let mut instance = browser::launch_with_debug(&browser_path, LOGIN_URL, &options.args)?;
     info!("Browser launched with debug port: {}", instance.debug_port);
 
     // 3. Wait for browser to start
-    eprintln!("88code: 正在启动浏览器...");
+    progress("88code: 正在启动浏览器...");
     tokio::time::sleep(Duration::from_secs(2)).await;
 
     // 4. Connect to CDP and monitor network
-    let result = async {
-        let mut cdp = cdp::CdpSession::connect(&instance.debug_url()).await?;
-        cdp.enable_network().await?;
-
-        eprintln!("88code: 正在自动刷新页面获取 token...");
-
-        // 5. Auto-reload page to trigger getLoginInfo API
-        // Wait a bit for page to be ready before reload
-        tokio::time::sleep(Duration::from_secs(1)).await;
-        if let Err(e) = cdp.reload().await {
-            warn!(
-                "Failed to auto-reload page: {}, user needs to refresh manually",
-                e
-            );
-            eprintln!("88code: 自动刷新失败，请手动刷新网页或完成登录...\n");
-        } else {
-            eprintln!("88code: 页面已刷新，等待获取 token...\n");
-        }
-
-        // 6. Wait for login response
-        let body = cdp.wait_for_response(TOKEN_API_PATTERN).await?;
-
-        // 7. Parse token from response
-        let token = parse_token_from_response(&body)?;
-
-        // 8. Close CDP session
-        let _ = cdp.close().await;
-
-        Ok::<String, Code88Error>(token)
-    };
+    let debug_url = instance.debug_url();
+    let result = wait_with_reconnects(&debug_url, progress);
 
     // Apply timeout
-    let token = match tokio::time::timeout(Duration::from_secs(timeout_secs), result).await {
-        Ok(Ok(token)) => token,
+    let login = match tokio::time::timeout(Duration::from_secs(timeout_secs), result).await {
+        Ok(Ok(login)) => login,
         Ok(Err(e)) => {
             instance.kill();
             return Err(e);
@@ -115,37 +128,83 @@ pub async fn run_browser_login(codex_home: &Path, timeout_secs: u64) -> Result<S
     };
 
     // 8. Save token
-    save_token(codex_home, &token)?;
-    eprintln!("\n88code: 登录成功！Token 已保存。\n");
+    store.save_token_with_claims(&login.token, "browser_login", Some(login.claims))?;
+    progress("88code: 登录成功！Token 已保存。");
 
-    // Clean up browser (optional - user might want to keep it)
+    // Clean up the browser. Left running, its debugging port would let any
+    // local process drive the signed-in session, so `keep_browser_open`
+    // reopens the profile without it instead.
     instance.kill();
+    if options.keep_open {
+        let url = options.success_url.as_deref().unwrap_or(LOGIN_URL);
+        if let Err(e) = browser::relaunch(&browser_path, url, &options.args) {
+            warn!("Failed to reopen the browser: {e}");
+        }
+    }
 
-    Ok(token)
+    Ok(login.token)
 }
 
-/// Parse token from the API response body.
-fn parse_token_from_response(body: &str) -> Result<String> {
-    #[derive(serde::Deserialize)]
-    struct Response {
-        code: i32,
-        ok: bool,
-        data: Option<Data>,
+/// [`wait_for_login_token`], connecting again when the connection drops
+/// during a long SSO or 2FA login, up to [`MAX_CDP_RECONNECTS`] times.
+async fn wait_with_reconnects(
+    debug_url: &str,
+    progress: LoginProgress<'_>,
+) -> Result<CapturedLogin> {
+    let mut reconnects: i64 = 0;
+    loop {
+        match wait_for_login_token(debug_url, reconnects == 0, progress).await {
+            Err(e) if e.is_retriable() && reconnects < MAX_CDP_RECONNECTS => {
+                reconnects += 1;
+                warn!(
+                    "CDP connection lost ({e}), reconnecting ({reconnects}/{MAX_CDP_RECONNECTS})"
+                );
+                progress("88code: 与浏览器的连接中断，正在重新连接...");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+            result => return result,
+        }
     }
+}
 
-    #[derive(serde::Deserialize)]
-    struct Data {
-        token: String,
-    }
+/// Connect to the browser and wait for the login response. The page is
+/// reloaded on the first attempt only, so a reconnect does not interrupt a
+/// login in progress.
+async fn wait_for_login_token(
+    debug_url: &str,
+    reload: bool,
+    progress: LoginProgress<'_>,
+) -> Result<CapturedLogin> {
+    let mut cdp = cdp::CdpSession::connect(debug_url).await?;
+    cdp.enable_network().await?;
 
-    let resp: Response =
-        serde_json::from_str(body).map_err(|e| Code88Error::ParseError(e.to_string()))?;
+    if reload {
+        progress("88code: 正在自动刷新页面获取 token...");
 
-    if !resp.ok || resp.code != 0 {
-        return Err(Code88Error::ApiError(resp.code));
+        // 5. Auto-reload page to trigger getLoginInfo API
+        // Wait a bit for page to be ready before reload
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        if let Err(e) = cdp.reload().await {
+            warn!(
+                "Failed to auto-reload page: {}, user needs to refresh manually",
+                e
+            );
+            progress("88code: 自动刷新失败，请手动刷新网页或完成登录...");
+        } else {
+            progress("88code: 页面已刷新，等待获取 token...");
+        }
     }
 
-    resp.data.map(|d| d.token).ok_or(Code88Error::NoToken)
+    // 6. Wait for login response
+    let body = cdp.wait_for_response(TOKEN_API_PATTERN).await?;
+
+    // 7. Parse token from response
+    let login = parse_login_response(&body)?;
+
+    // 8. Close CDP session
+    let _ = cdp.close().await;
+
+    Ok(login)
 }
 
 /// Prompt user for manual token input as fallback.
@@ -171,13 +230,16 @@ pub fn prompt_manual_token_input() -> Result<String> {
 }
 
 /// Ensure token with fallback to manual input.
-pub async fn ensure_token_with_fallback(codex_home: &Path) -> Result<String> {
-    match ensure_token(codex_home).await {
+pub async fn ensure_token_with_fallback(
+    store: &TokenStore,
+    browser: &BrowserOptions,
+) -> Result<String> {
+    match ensure_token(store, browser).await {
         Ok(token) => Ok(token),
         Err(e) => {
             warn!("Auto login failed: {}, falling back to manual input", e);
             let token = prompt_manual_token_input()?;
-            save_token(codex_home, &token)?;
+            store.save_token(&token, "manual_input")?;
             Ok(token)
         }
     }
@@ -187,30 +249,193 @@ pub async fn ensure_token_with_fallback(codex_home: &Path) -> Result<String> {
 ///
 /// This is used when the existing token has expired and needs to be refreshed.
 /// Unlike `ensure_token`, this function always runs the browser login flow.
+/// It fails with [`Code88Error::TokenOverridden`] without opening a browser
+/// while `CODEX_88CODE_TOKEN` or `code88_token` is set, since that token
+/// would still outrank the one a login saves.
 ///
 /// Returns the new token string on success.
-pub async fn refresh_token(codex_home: &Path) -> Result<String> {
+pub async fn refresh_token(store: &TokenStore, browser: &BrowserOptions) -> Result<String> {
+    refresh_token_with_progress(store, browser, &stderr_progress).await
+}
+
+/// [`refresh_token`], reporting the login flow's progress to `progress`.
+pub async fn refresh_token_with_progress(
+    store: &TokenStore,
+    browser: &BrowserOptions,
+    progress: LoginProgress<'_>,
+) -> Result<String> {
     info!("Refreshing 88code token (existing token expired)");
 
-    // Delete existing token first
-    if let Err(e) = delete_token(codex_home) {
-        warn!("Failed to delete old token: {}", e);
-    }
+    ensure_login_takes_effect(store, store.resolve_token().as_ref())?;
 
-    // Run browser login to get new token
-    eprintln!("\n88code: Token已过期，需要重新登录...");
-    run_browser_login(codex_home, DEFAULT_TIMEOUT_SECS).await
+    // The saved token stays in place until the login has produced a new one,
+    // so a cancelled or failed login leaves the user signed in as before.
+    progress("88code: Token已过期，需要重新登录...");
+    run_browser_login_with_progress(store, browser, DEFAULT_TIMEOUT_SECS, progress).await
+}
+
+/// Fails when `existing` comes from the environment or `config.toml`: it
+/// would still outrank whatever token a login saves.
+fn ensure_login_takes_effect(store: &TokenStore, existing: Option<&ResolvedToken>) -> Result<()> {
+    match existing {
+        Some(existing) if existing.saved.is_none() => Err(Code88Error::TokenOverridden(
+            existing.source.location(store.codex_home()),
+        )),
+        _ => Ok(()),
+    }
 }
 
 /// Force refresh token with fallback to manual input.
-pub async fn refresh_token_with_fallback(codex_home: &Path) -> Result<String> {
-    match refresh_token(codex_home).await {
+pub async fn refresh_token_with_fallback(
+    store: &TokenStore,
+    browser: &BrowserOptions,
+) -> Result<String> {
+    match refresh_token(store, browser).await {
         Ok(token) => Ok(token),
+        Err(e @ Code88Error::TokenOverridden(_)) => Err(e),
         Err(e) => {
             warn!("Auto refresh failed: {}, falling back to manual input", e);
             let token = prompt_manual_token_input()?;
-            save_token(codex_home, &token)?;
+            store.save_token(&token, "manual_input")?;
             Ok(token)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::tests::BrowserSocket;
+    use crate::cdp::tests::spawn_browser;
+    use chrono::Utc;
+    use futures::SinkExt;
+    use futures::StreamExt;
+    use serde_json::Value;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message;
+
+    fn resolved(source: TokenSource, saved: Option<TokenFile>) -> ResolvedToken {
+        ResolvedToken {
+            token: "token".to_string(),
+            source,
+            saved,
+        }
+    }
+
+    #[test]
+    fn login_is_refused_while_an_unsaved_token_outranks_it() {
+        let store = TokenStore::new(std::path::Path::new("/tmp/codex-home"));
+        let err = ensure_login_takes_effect(&store, Some(&resolved(TokenSource::Config, None)))
+            .expect_err("config token outranks a login");
+        assert_eq!(
+            err.to_string(),
+            "正在使用来自 code88_token in config.toml 的 88code token，登录保存的 token 不会生效；请先移除它再登录"
+        );
+        assert!(matches!(
+            ensure_login_takes_effect(&store, Some(&resolved(TokenSource::Env, None))),
+            Err(Code88Error::TokenOverridden(_))
+        ));
+
+        let saved = TokenFile {
+            token: "token".to_string(),
+            created_at: Utc::now(),
+            source: "browser_login".to_string(),
+            claims: None,
+        };
+        assert!(
+            ensure_login_takes_effect(&store, Some(&resolved(TokenSource::File, Some(saved))))
+                .is_ok()
+        );
+        assert!(ensure_login_takes_effect(&store, None).is_ok());
+    }
+
+    /// Serve `/json` the way Chromium's debugging endpoint does, listing one
+    /// page at `ws_url`. Returns the endpoint's base URL.
+    async fn spawn_debug_endpoint(ws_url: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+        let body = json!([{ "type": "page", "webSocketDebuggerUrl": ws_url }]).to_string();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0; 1024];
+                let _ = stream.read(&mut request).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        base_url
+    }
+
+    /// Answer CDP commands until the connection ends. The first connection
+    /// drops without a close frame right after the page reload; later ones
+    /// deliver the login response.
+    async fn serve_login(connection: i64, mut socket: BrowserSocket) {
+        while let Some(Ok(Message::Text(text))) = socket.next().await {
+            let command: Value = serde_json::from_str(&text).expect("command");
+            let method = command["method"].as_str().unwrap_or_default();
+            let result = match method {
+                "Network.getResponseBody" => {
+                    let body = r#"{"code":0,"ok":true,"data":{"token":"fresh"}}"#;
+                    json!({ "body": body, "base64Encoded": false })
+                }
+                _ => json!({}),
+            };
+            let reply = json!({ "id": command["id"], "result": result });
+            if socket.send(Message::Text(reply.to_string())).await.is_err() {
+                return;
+            }
+            if connection == 0 && method == "Page.reload" {
+                return;
+            }
+            if connection > 0 && method == "Network.enable" {
+                let event = json!({
+                    "method": "Network.responseReceived",
+                    "params": {
+                        "requestId": "1",
+                        "response": { "url": format!("https://www.88code.org{TOKEN_API_PATTERN}") },
+                    },
+                });
+                let _ = socket.send(Message::Text(event.to_string())).await;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn dropped_connection_is_reconnected_without_reloading() {
+        let ws_url = spawn_browser(serve_login).await;
+        let debug_url = spawn_debug_endpoint(ws_url).await;
+        let messages = Mutex::new(Vec::new());
+        let progress = |message: &str| {
+            if let Ok(mut messages) = messages.lock() {
+                messages.push(message.to_string());
+            }
+        };
+
+        let login = wait_with_reconnects(&debug_url, &progress)
+            .await
+            .expect("login after reconnecting");
+
+        assert_eq!(
+            login,
+            CapturedLogin {
+                token: "fresh".to_string(),
+                claims: json!({}),
+            }
+        );
+        assert_eq!(
+            messages.into_inner().expect("progress"),
+            vec![
+                "88code: 正在自动刷新页面获取 token...".to_string(),
+                "88code: 页面已刷新，等待获取 token...".to_string(),
+                "88code: 与浏览器的连接中断，正在重新连接...".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Parsing of the `getLoginInfo` response the browser login captures.
+//!
+//! A parse failure here sends the user to manual token entry, so bodies are
+//! taken in every shape CDP and the relay can hand over: base64 encoded by
+//! CDP, still gzip-compressed, with a UTF-8 BOM, or with fields this crate
+//! does not know about.
+
+use std::io::Read;
+
+use base64::Engine;
+use flate2::read::GzDecoder;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::Code88Error;
+
+/// First bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// What the login response carried: the token, and every other field of
+/// its `data` as claims.
+#[derive(Debug, PartialEq)]
+pub(crate) struct CapturedLogin {
+    pub token: String,
+    pub claims: Value,
+}
+
+/// The response body as text, from what `Network.getResponseBody` returned:
+/// base64 decoded when CDP flags it, and gunzipped when the bytes are still
+/// gzip-compressed.
+pub(crate) fn decode_body(body: &str, base64_encoded: bool) -> Result<String, Code88Error> {
+    if !base64_encoded {
+        return Ok(body.to_string());
+    }
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(body.trim())
+        .map_err(|e| Code88Error::ParseError(format!("Base64 decode failed: {e}")))?;
+    let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+        let mut inflated = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut inflated)
+            .map_err(|e| Code88Error::ParseError(format!("Gzip decode failed: {e}")))?;
+        inflated
+    } else {
+        bytes
+    };
+    String::from_utf8(bytes)
+        .map_err(|e| Code88Error::ParseError(format!("UTF-8 decode failed: {e}")))
+}
+
+/// Parse the token and claims from the API response body.
+pub(crate) fn parse_login_response(body: &str) -> Result<CapturedLogin, Code88Error> {
+    #[derive(serde::Deserialize)]
+    struct Response {
+        code: i32,
+        ok: Option<bool>,
+        data: Option<Map<String, Value>>,
+    }
+
+    let body = body.trim_start_matches('\u{feff}').trim();
+    let resp: Response =
+        serde_json::from_str(body).map_err(|e| Code88Error::ParseError(e.to_string()))?;
+
+    // Some deployments leave out `ok`; `code` alone decides then.
+    if resp.code != 0 || resp.ok == Some(false) {
+        return Err(Code88Error::ApiError(resp.code));
+    }
+
+    let mut data = resp.data.ok_or(Code88Error::NoToken)?;
+    match data.remove("token") {
+        Some(Value::String(token)) if !token.is_empty() => Ok(CapturedLogin {
+            token,
+            claims: Value::Object(data),
+        }),
+        _ => Err(Code88Error::NoToken),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Hand-written responses in the shape of `getLoginInfo`, not captured
+    /// from the live API.
+    fn synthetic(name: &str) -> &'static str {
+        match name {
+            "success" => include_str!("../tests/fixtures/synthetic/login_info_success.json"),
+            "token_expired" => {
+                include_str!("../tests/fixtures/synthetic/login_info_token_expired.json")
+            }
+            "missing_data" => {
+                include_str!("../tests/fixtures/synthetic/login_info_missing_data.json")
+            }
+            "no_token" => include_str!("../tests/fixtures/synthetic/login_info_no_token.json"),
+            "extra_fields" => {
+                include_str!("../tests/fixtures/synthetic/login_info_extra_fields.json")
+            }
+            "bom" => include_str!("../tests/fixtures/synthetic/login_info_bom.json"),
+            "base64" => include_str!("../tests/fixtures/synthetic/login_info_success.base64"),
+            "gzip_base64" => {
+                include_str!("../tests/fixtures/synthetic/login_info_success.gzip.base64")
+            }
+            other => panic!("unknown fixture {other}"),
+        }
+    }
+
+    fn success_claims() -> Value {
+        json!({
+            "userId": 1024,
+            "username": "dev@example.com",
+            "accountGroupCode": "service_tier3",
+            "expireTime": "2026-12-31 23:59:59",
+        })
+    }
+
+    #[test]
+    fn success_keeps_the_other_data_fields_as_claims() {
+        assert_eq!(
+            parse_login_response(synthetic("success")).unwrap(),
+            CapturedLogin {
+                token: "tok_9f2c1e7a".to_string(),
+                claims: success_claims(),
+            }
+        );
+    }
+
+    #[test]
+    fn error_codes_and_missing_tokens_are_reported() {
+        assert!(matches!(
+            parse_login_response(synthetic("token_expired")),
+            Err(Code88Error::ApiError(30007))
+        ));
+        assert!(matches!(
+            parse_login_response(synthetic("missing_data")),
+            Err(Code88Error::NoToken)
+        ));
+        assert!(matches!(
+            parse_login_response(synthetic("no_token")),
+            Err(Code88Error::NoToken)
+        ));
+        assert!(matches!(
+            parse_login_response("<html>502 Bad Gateway</html>"),
+            Err(Code88Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn extra_fields_are_kept_nested() {
+        let login = parse_login_response(synthetic("extra_fields")).unwrap();
+        assert_eq!(login.token, "tok_9f2c1e7a");
+        assert_eq!(login.claims["subscription"]["credits"]["limit"], json!(50));
+        assert_eq!(login.claims["avatar"], Value::Null);
+        assert!(login.claims.get("token").is_none());
+    }
+
+    #[test]
+    fn bom_base64_and_gzip_bodies_decode_to_the_same_login() {
+        let expected = parse_login_response(synthetic("success")).unwrap();
+        assert_eq!(parse_login_response(synthetic("bom")).unwrap(), expected);
+        for name in ["base64", "gzip_base64"] {
+            let body = decode_body(synthetic(name), true).unwrap();
+            assert_eq!(parse_login_response(&body).unwrap(), expected, "{name}");
+        }
+        assert_eq!(
+            decode_body(synthetic("success"), false).unwrap(),
+            synthetic("success")
+        );
+        assert!(matches!(
+            decode_body("not base64!", true),
+            Err(Code88Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn missing_ok_is_decided_by_code() {
+        let login = parse_login_response(r#"{"code":0,"data":{"token":"t"}}"#).unwrap();
+        assert_eq!(login.token, "t");
+        assert!(matches!(
+            parse_login_response(r#"{"code":500,"data":{"token":"t"}}"#),
+            Err(Code88Error::ApiError(500))
+        ));
+    }
+
+    /// Deterministic xorshift, so the property tests need no extra crates
+    /// and always cover the same cases.
+    struct Cases(u64);
+
+    impl Cases {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn string(&mut self) -> String {
+            const ALPHABET: &[char] = &[
+                'a', 'Z', '0', '_', '-', '.', '"', '\\', ' ', '/', '登', '录', '🔑', '\u{feff}',
+            ];
+            let len = 1 + self.next() % 24;
+            (0..len)
+                .map(|_| ALPHABET[(self.next() % ALPHABET.len() as u64) as usize])
+                .collect()
+        }
+    }
+
+    #[test]
+    fn any_token_and_claims_round_trip() {
+        let mut cases = Cases(0x88c0de);
+        for _ in 0..500 {
+            let token = cases.string();
+            let mut data = Map::new();
+            for _ in 0..cases.next() % 5 {
+                let key = cases.string();
+                if key != "token" {
+                    data.insert(key, Value::String(cases.string()));
+                }
+            }
+            let claims = Value::Object(data.clone());
+            data.insert("token".to_string(), Value::String(token.clone()));
+            let mut body = json!({ "code": 0, "ok": true, "data": data }).to_string();
+            if cases.next() % 2 == 0 {
+                body.insert(0, '\u{feff}');
+            }
+            if cases.next() % 2 == 0 {
+                body = base64::engine::general_purpose::STANDARD.encode(body);
+                body = decode_body(&body, true).unwrap();
+            }
+
+            assert_eq!(
+                parse_login_response(&body).unwrap(),
+                CapturedLogin {
+                    token: token.clone(),
+                    claims
+                },
+                "{body}"
+            );
+        }
+    }
+
+    #[test]
+    fn truncated_bodies_fail_without_panicking() {
+        let body = synthetic("extra_fields").trim();
+        for end in (0..body.len()).filter(|end| body.is_char_boundary(*end)) {
+            assert!(parse_login_response(&body[..end]).is_err(), "{end}");
+        }
+    }
+}
@@ -1,21 +1,47 @@
 //! Token storage and retrieval.
+//!
+//! A login token is looked up in one order everywhere, by
+//! [`TokenStore::resolve_token`]:
+//!
+//! 1. the `CODEX_88CODE_TOKEN` environment variable,
+//! 2. `code88_token` in `config.toml`,
+//! 3. the OS keychain, when the store uses it,
+//! 4. `CODEX_HOME/88code-token.json`.
+//!
+//! Saved tokens go to the keychain when the store uses it, and to the file
+//! otherwise. A token file left by an older version, including one holding
+//! just the token as plain text, is upgraded on the first lookup.
 
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use chrono::DateTime;
 use chrono::Utc;
+use codex_keyring_store::DefaultKeyringStore;
+use codex_keyring_store::KeyringStore;
 use serde::Deserialize;
 use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
 use tracing::debug;
+use tracing::warn;
 
 use crate::Code88Error;
 
 /// File name for storing the 88code token.
 const TOKEN_FILE_NAME: &str = "88code-token.json";
 
+/// Environment variable that overrides every saved token.
+pub const TOKEN_ENV_VAR: &str = "CODEX_88CODE_TOKEN";
+
+const KEYRING_SERVICE: &str = "Codex 88code";
+
+/// `source` of a token file upgraded from the plain-text format.
+const MIGRATED_SOURCE: &str = "migrated";
+
 /// Structure for storing token data.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TokenFile {
     /// The authentication token.
     pub token: String,
@@ -24,75 +50,303 @@ pub struct TokenFile {
     /// How the token was obtained (e.g., "browser_login", "manual_input").
     #[serde(default)]
     pub source: String,
+    /// The rest of the login response's `data` (plan, expiry, user id, ...),
+    /// kept as-is so later features can read it without another login.
+    /// Only browser logins have it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claims: Option<serde_json::Value>,
 }
 
-/// Get the path to the token file.
-pub fn token_path(codex_home: &Path) -> PathBuf {
-    codex_home.join(TOKEN_FILE_NAME)
+/// Where a resolved token came from, highest precedence first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSource {
+    Env,
+    Config,
+    Keychain,
+    File,
 }
 
-/// Load token from the config directory.
-///
-/// Returns `None` if the token file doesn't exist or is invalid.
-pub fn load_token(codex_home: &Path) -> Option<String> {
-    let path = token_path(codex_home);
+impl TokenSource {
+    /// Where to find the token, for `codex login status` and friends.
+    pub fn location(self, codex_home: &Path) -> String {
+        match self {
+            TokenSource::Env => format!("${TOKEN_ENV_VAR}"),
+            TokenSource::Config => "code88_token in config.toml".to_string(),
+            TokenSource::Keychain => format!("OS keychain ({KEYRING_SERVICE})"),
+            TokenSource::File => token_path(codex_home).display().to_string(),
+        }
+    }
+}
 
-    if !path.exists() {
-        debug!("Token file does not exist: {:?}", path);
-        return None;
+/// The token [`TokenStore::resolve_token`] picked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedToken {
+    pub token: String,
+    pub source: TokenSource,
+    /// When and how the token was obtained; only saved tokens (keychain or
+    /// file) have it.
+    pub saved: Option<TokenFile>,
+}
+
+/// The places a login token is read from and saved to.
+#[derive(Debug, Clone)]
+pub struct TokenStore {
+    codex_home: PathBuf,
+    configured: Option<String>,
+    keyring: Option<Arc<dyn KeyringStore>>,
+}
+
+impl TokenStore {
+    /// The token file in `codex_home`, below `CODEX_88CODE_TOKEN`.
+    pub fn new(codex_home: &Path) -> Self {
+        Self {
+            codex_home: codex_home.to_path_buf(),
+            configured: None,
+            keyring: None,
+        }
     }
 
-    let content = match std::fs::read_to_string(&path) {
-        Ok(c) => c,
-        Err(e) => {
-            debug!("Failed to read token file: {}", e);
-            return None;
+    /// `code88_token` from `config.toml`, which takes precedence over saved
+    /// tokens.
+    pub fn with_configured_token(mut self, token: Option<String>) -> Self {
+        self.configured = token.filter(|token| !token.trim().is_empty());
+        self
+    }
+
+    /// Keep saved tokens in the OS keychain, falling back to the file when
+    /// the keychain is unavailable.
+    pub fn with_keychain(self) -> Self {
+        self.with_keyring(Arc::new(DefaultKeyringStore))
+    }
+
+    fn with_keyring(mut self, keyring: Arc<dyn KeyringStore>) -> Self {
+        self.keyring = Some(keyring);
+        self
+    }
+
+    pub fn codex_home(&self) -> &Path {
+        &self.codex_home
+    }
+
+    /// The token to use, by the precedence in the module docs. `None` when
+    /// no source has one.
+    pub fn resolve_token(&self) -> Option<ResolvedToken> {
+        self.resolve_with_env(std::env::var(TOKEN_ENV_VAR).ok())
+    }
+
+    fn resolve_with_env(&self, env_token: Option<String>) -> Option<ResolvedToken> {
+        let unsaved = |token: String, source| ResolvedToken {
+            token,
+            source,
+            saved: None,
+        };
+        if let Some(token) = env_token.filter(|token| !token.trim().is_empty()) {
+            return Some(unsaved(token.trim().to_string(), TokenSource::Env));
+        }
+        if let Some(token) = self.configured.clone() {
+            return Some(unsaved(token.trim().to_string(), TokenSource::Config));
+        }
+        self.migrate();
+        let saved = |file: TokenFile, source| ResolvedToken {
+            token: file.token.clone(),
+            source,
+            saved: Some(file),
+        };
+        if let Some(file) = self.load_keychain() {
+            return Some(saved(file, TokenSource::Keychain));
+        }
+        self.load_file().map(|file| saved(file, TokenSource::File))
+    }
+
+    /// Save `token`, obtained via `source` (e.g. `browser_login`).
+    pub fn save_token(&self, token: &str, source: &str) -> Result<(), Code88Error> {
+        self.save_token_with_claims(token, source, None)
+    }
+
+    /// [`Self::save_token`], keeping the login response's other fields as
+    /// [`TokenFile::claims`].
+    pub fn save_token_with_claims(
+        &self,
+        token: &str,
+        source: &str,
+        claims: Option<serde_json::Value>,
+    ) -> Result<(), Code88Error> {
+        let file = TokenFile {
+            token: token.to_string(),
+            created_at: Utc::now(),
+            source: source.to_string(),
+            claims,
+        };
+        if self.save_keychain(&file) {
+            return Ok(());
+        }
+        write_token_file(&self.codex_home, &file)
+    }
+
+    /// Forget the saved token, in the keychain and the file. Tokens from the
+    /// environment or `config.toml` are left alone. An unreachable keychain
+    /// holds no token, so it is only logged.
+    pub fn delete_token(&self) -> Result<(), Code88Error> {
+        if let Some(keyring) = self.keyring.as_ref()
+            && let Err(err) = keyring.delete(KEYRING_SERVICE, &keyring_account(&self.codex_home))
+        {
+            warn!("Failed to remove 88code token from the keychain: {err}");
+        }
+        delete_token_file(&self.codex_home)
+    }
+
+    /// Upgrade the token file: a plain-text token becomes a [`TokenFile`],
+    /// and with a keychain the token moves there. Failures leave the file as
+    /// it was, so the token stays usable.
+    fn migrate(&self) {
+        let path = token_path(&self.codex_home);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return;
+        };
+        let file = match serde_json::from_str::<TokenFile>(&content) {
+            Ok(file) => file,
+            Err(_) => match plain_token(&content) {
+                Some(token) => TokenFile {
+                    token: token.to_string(),
+                    created_at: std::fs::metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .map(DateTime::<Utc>::from)
+                        .unwrap_or_else(|_| Utc::now()),
+                    source: MIGRATED_SOURCE.to_string(),
+                    claims: None,
+                },
+                None => return,
+            },
+        };
+        if file.token.is_empty() {
+            return;
+        }
+        let keychain_empty = self.keyring.as_ref().is_some_and(|keyring| {
+            matches!(
+                keyring.load(KEYRING_SERVICE, &keyring_account(&self.codex_home)),
+                Ok(None)
+            )
+        });
+        if keychain_empty && self.save_keychain(&file) {
+            debug!("Moved 88code token from {:?} to the keychain", path);
+            return;
+        }
+        if file.source == MIGRATED_SOURCE
+            && let Err(err) = write_token_file(&self.codex_home, &file)
+        {
+            warn!("Failed to upgrade 88code token file: {err}");
+        }
+    }
+
+    fn load_keychain(&self) -> Option<TokenFile> {
+        let keyring = self.keyring.as_ref()?;
+        let value = match keyring.load(KEYRING_SERVICE, &keyring_account(&self.codex_home)) {
+            Ok(value) => value?,
+            Err(err) => {
+                debug!("Failed to read 88code token from the keychain: {err}");
+                return None;
+            }
+        };
+        serde_json::from_str::<TokenFile>(&value)
+            .ok()
+            .filter(|file| !file.token.is_empty())
+    }
+
+    /// Whether `file` is now in the keychain. The token file is removed once
+    /// it is, so an older token cannot resurface.
+    fn save_keychain(&self, file: &TokenFile) -> bool {
+        let Some(keyring) = self.keyring.as_ref() else {
+            return false;
+        };
+        let saved = serde_json::to_string(file)
+            .map_err(|err| err.to_string())
+            .and_then(|value| {
+                keyring
+                    .save(KEYRING_SERVICE, &keyring_account(&self.codex_home), &value)
+                    .map_err(|err| err.message())
+            });
+        match saved {
+            Ok(()) => {
+                if let Err(err) = delete_token_file(&self.codex_home) {
+                    warn!("Failed to remove 88code token file: {err}");
+                }
+                true
+            }
+            Err(err) => {
+                warn!("Failed to save 88code token to the keychain, using the token file: {err}");
+                false
+            }
         }
-    };
+    }
+
+    fn load_file(&self) -> Option<TokenFile> {
+        let path = token_path(&self.codex_home);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                debug!("Failed to read token file {:?}: {}", path, e);
+                return None;
+            }
+        };
 
-    let file: TokenFile = match serde_json::from_str(&content) {
-        Ok(f) => f,
-        Err(e) => {
-            debug!("Failed to parse token file: {}", e);
+        let file: TokenFile = match serde_json::from_str(&content) {
+            Ok(f) => f,
+            Err(e) => {
+                debug!("Failed to parse token file: {}", e);
+                return None;
+            }
+        };
+
+        if file.token.is_empty() {
+            debug!("Token file contains empty token");
             return None;
         }
-    };
 
-    if file.token.is_empty() {
-        debug!("Token file contains empty token");
-        return None;
+        Some(file)
     }
+}
 
-    Some(file.token)
+/// Get the path to the token file.
+pub fn token_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(TOKEN_FILE_NAME)
 }
 
-/// Save token to the config directory.
-///
-/// Creates the directory if it doesn't exist.
-/// Sets restrictive file permissions on Unix systems.
-pub fn save_token(codex_home: &Path, token: &str) -> Result<(), Code88Error> {
-    save_token_with_source(codex_home, token, "browser_login")
+/// The token of a plain-text token file: one line, no spaces.
+fn plain_token(content: &str) -> Option<&str> {
+    let token = content.trim();
+    (!token.is_empty() && !token.contains(char::is_whitespace) && !token.starts_with('{'))
+        .then_some(token)
 }
 
-/// Save token with a specific source identifier.
-pub fn save_token_with_source(
-    codex_home: &Path,
-    token: &str,
-    source: &str,
-) -> Result<(), Code88Error> {
-    // Ensure directory exists
-    std::fs::create_dir_all(codex_home)?;
+/// Keychain account for `codex_home`, so each `CODEX_HOME` keeps its own
+/// token.
+fn keyring_account(codex_home: &Path) -> String {
+    let canonical = codex_home
+        .canonicalize()
+        .unwrap_or_else(|_| codex_home.to_path_buf());
+    let digest = Sha256::digest(canonical.to_string_lossy().as_bytes());
+    let hex = format!("{digest:x}");
+    format!("88code|{}", hex.get(..16).unwrap_or(&hex))
+}
 
-    let file = TokenFile {
-        token: token.to_string(),
-        created_at: Utc::now(),
-        source: source.to_string(),
-    };
+/// The API key for a login token, as the 88code relay and usage API expect
+/// it: the token with an `88_` prefix.
+pub fn api_key(token: &str) -> String {
+    if token.starts_with("88_") {
+        token.to_string()
+    } else {
+        format!("88_{token}")
+    }
+}
 
-    let content = serde_json::to_string_pretty(&file)?;
+/// Write `file` to the config directory, creating it if needed, with
+/// restrictive permissions on Unix.
+fn write_token_file(codex_home: &Path, file: &TokenFile) -> Result<(), Code88Error> {
+    std::fs::create_dir_all(codex_home)?;
+
+    let content = serde_json::to_string_pretty(file)?;
     let path = token_path(codex_home);
 
-    // Write with restrictive permissions
     #[cfg(unix)]
     {
         use std::os::unix::fs::OpenOptionsExt;
@@ -111,8 +365,7 @@ pub fn save_token_with_source(
     Ok(())
 }
 
-/// Delete the token file.
-pub fn delete_token(codex_home: &Path) -> Result<(), Code88Error> {
+fn delete_token_file(codex_home: &Path) -> Result<(), Code88Error> {
     let path = token_path(codex_home);
     if path.exists() {
         std::fs::remove_file(&path)?;
@@ -124,33 +377,150 @@ pub fn delete_token(codex_home: &Path) -> Result<(), Code88Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_keyring_store::tests::MockKeyringStore;
     use tempfile::tempdir;
 
+    fn resolve(store: &TokenStore) -> Option<(String, TokenSource)> {
+        store
+            .resolve_with_env(None)
+            .map(|resolved| (resolved.token, resolved.source))
+    }
+
     #[test]
     fn test_save_and_load_token() {
         let dir = tempdir().unwrap();
+        let store = TokenStore::new(dir.path());
         let token = "test_token_12345";
 
-        save_token(dir.path(), token).unwrap();
-        let loaded = load_token(dir.path());
+        store.save_token(token, "browser_login").unwrap();
+        let loaded = resolve(&store);
+
+        assert_eq!(loaded, Some((token.to_string(), TokenSource::File)));
+    }
+
+    #[test]
+    fn test_load_token_file_keeps_metadata() {
+        let dir = tempdir().unwrap();
+        let store = TokenStore::new(dir.path());
+        store.save_token("test_token", "manual_input").unwrap();
+
+        let file = store.resolve_with_env(None).unwrap().saved.unwrap();
+        assert_eq!(file.token, "test_token");
+        assert_eq!(file.source, "manual_input");
+        assert!(file.created_at <= Utc::now());
+    }
+
+    #[test]
+    fn test_claims_survive_a_round_trip() {
+        let dir = tempdir().unwrap();
+        let store = TokenStore::new(dir.path());
+        let claims = serde_json::json!({ "userId": 42, "expireTime": "2026-12-31" });
+        store
+            .save_token_with_claims("test_token", "browser_login", Some(claims.clone()))
+            .unwrap();
 
-        assert_eq!(loaded, Some(token.to_string()));
+        let file = store.resolve_with_env(None).unwrap().saved.unwrap();
+        assert_eq!(file.claims, Some(claims));
     }
 
     #[test]
     fn test_load_nonexistent_token() {
         let dir = tempdir().unwrap();
-        let loaded = load_token(dir.path());
+        let loaded = resolve(&TokenStore::new(dir.path()));
         assert_eq!(loaded, None);
     }
 
+    #[test]
+    fn test_api_key_adds_prefix_once() {
+        assert_eq!(api_key("abc"), "88_abc");
+        assert_eq!(api_key("88_abc"), "88_abc");
+    }
+
     #[test]
     fn test_delete_token() {
         let dir = tempdir().unwrap();
-        save_token(dir.path(), "test").unwrap();
+        let store = TokenStore::new(dir.path());
+        store.save_token("test", "browser_login").unwrap();
         assert!(token_path(dir.path()).exists());
 
-        delete_token(dir.path()).unwrap();
+        store.delete_token().unwrap();
         assert!(!token_path(dir.path()).exists());
     }
+
+    #[test]
+    fn test_precedence_is_env_config_keychain_file() {
+        let dir = tempdir().unwrap();
+        let keyring = MockKeyringStore::default();
+        let file = TokenFile {
+            token: "from-file".to_string(),
+            created_at: Utc::now(),
+            source: "browser_login".to_string(),
+            claims: None,
+        };
+        write_token_file(dir.path(), &file).unwrap();
+        let store = TokenStore::new(dir.path()).with_keyring(Arc::new(keyring.clone()));
+        let keychain_file = TokenFile {
+            token: "from-keychain".to_string(),
+            ..file
+        };
+        keyring
+            .save(
+                KEYRING_SERVICE,
+                &keyring_account(dir.path()),
+                &serde_json::to_string(&keychain_file).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            resolve(&store),
+            Some(("from-keychain".to_string(), TokenSource::Keychain))
+        );
+        let store = store.with_configured_token(Some("from-config".to_string()));
+        assert_eq!(
+            resolve(&store),
+            Some(("from-config".to_string(), TokenSource::Config))
+        );
+        let resolved = store
+            .resolve_with_env(Some("from-env".to_string()))
+            .unwrap();
+        assert_eq!(
+            (resolved.token.as_str(), resolved.source),
+            ("from-env", TokenSource::Env)
+        );
+    }
+
+    #[test]
+    fn test_plain_token_file_is_upgraded() {
+        let dir = tempdir().unwrap();
+        std::fs::write(token_path(dir.path()), "legacy-token\n").unwrap();
+
+        let store = TokenStore::new(dir.path());
+        let resolved = store.resolve_with_env(None).unwrap();
+        assert_eq!(resolved.token, "legacy-token");
+        assert_eq!(resolved.source, TokenSource::File);
+        let content = std::fs::read_to_string(token_path(dir.path())).unwrap();
+        let file: TokenFile = serde_json::from_str(&content).unwrap();
+        assert_eq!(
+            (file.token.as_str(), file.source.as_str()),
+            ("legacy-token", MIGRATED_SOURCE)
+        );
+    }
+
+    #[test]
+    fn test_token_file_moves_into_keychain() {
+        let dir = tempdir().unwrap();
+        std::fs::write(token_path(dir.path()), "legacy-token").unwrap();
+        let keyring = MockKeyringStore::default();
+        let store = TokenStore::new(dir.path()).with_keyring(Arc::new(keyring.clone()));
+
+        assert_eq!(
+            resolve(&store),
+            Some(("legacy-token".to_string(), TokenSource::Keychain))
+        );
+        assert!(!token_path(dir.path()).exists());
+        assert!(keyring.contains(&keyring_account(dir.path())));
+
+        store.delete_token().unwrap();
+        assert_eq!(resolve(&store), None);
+    }
 }
@@ -1,3 +1,5 @@
+use std::sync::Mutex;
+
 use chrono::DateTime;
 use chrono::Utc;
 use codex_api::AuthProvider as ApiAuthProvider;
@@ -132,6 +134,54 @@ pub(crate) async fn auth_provider_from_auth(
     }
 }
 
+/// The 88code login token a session sends to a provider with
+/// `code88_auth`. Resolving it migrates the legacy token file and may read
+/// the OS keychain, so it happens once per session rather than per request.
+#[derive(Debug)]
+pub(crate) struct Code88Credentials {
+    store: codex_code88::TokenStore,
+    token: Mutex<Option<String>>,
+}
+
+impl Code88Credentials {
+    pub(crate) fn new(store: codex_code88::TokenStore) -> Self {
+        Self {
+            store,
+            token: Mutex::new(None),
+        }
+    }
+
+    /// The token as the relay's API key, resolved on first use.
+    pub(crate) fn auth_provider(&self) -> crate::error::Result<CoreAuthProvider> {
+        let mut token = self
+            .token
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if token.is_none() {
+            *token = self.store.resolve_token().map(|resolved| resolved.token);
+        }
+        let token = token.as_deref().ok_or(CodexErr::Code88SignInRequired)?;
+        Ok(CoreAuthProvider {
+            token: Some(codex_code88::api_key(token)),
+            account_id: None,
+        })
+    }
+
+    /// Resolve the token again after the relay rejected it. True when a
+    /// different token turned up, e.g. the user signed in again meanwhile,
+    /// so the request is worth retrying.
+    pub(crate) fn reload(&self) -> bool {
+        let mut token = self
+            .token
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let reloaded = self.store.resolve_token().map(|resolved| resolved.token);
+        let changed = reloaded.is_some() && reloaded != *token;
+        *token = reloaded;
+        changed
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct UsageErrorResponse {
     error: UsageErrorBody,
@@ -160,3 +210,38 @@ impl ApiAuthProvider for CoreAuthProvider {
         self.account_id.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn bearer(credentials: &Code88Credentials) -> Option<String> {
+        credentials
+            .auth_provider()
+            .ok()
+            .and_then(|auth| auth.bearer_token())
+    }
+
+    #[test]
+    fn code88_token_is_resolved_once_until_reloaded() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = codex_code88::TokenStore::new(dir.path());
+        let credentials = Code88Credentials::new(store.clone());
+        assert!(matches!(
+            credentials.auth_provider(),
+            Err(CodexErr::Code88SignInRequired)
+        ));
+
+        store.save_token("first", "browser_login").expect("save");
+        assert_eq!(bearer(&credentials), Some("88_first".to_string()));
+
+        // A token saved later is only picked up once the relay rejects the
+        // cached one.
+        store.save_token("second", "browser_login").expect("save");
+        assert_eq!(bearer(&credentials), Some("88_first".to_string()));
+        assert!(credentials.reload());
+        assert_eq!(bearer(&credentials), Some("88_second".to_string()));
+        assert!(!credentials.reload());
+    }
+}
@@ -0,0 +1,234 @@
+//! Spending limits for a session: what it has used against `[budget]` and
+//! the workspace's saved budget, and which limits the user has already been
+//! warned about or agreed to go past.
+
+use std::collections::HashSet;
+
+use crate::config::Config;
+use crate::protocol::BudgetScope;
+use crate::protocol::BudgetUsage;
+use crate::protocol::TokenUsage;
+use crate::workspace_state::estimate_cost;
+
+/// Share of a limit at which the user is warned.
+const WARN_FRACTION: f64 = 0.8;
+
+/// Spending against every configured budget, session first, for a session
+/// that has used `usage` with `model` so far. Costs are only known when
+/// `model` has a `[model_prices]` entry.
+pub(crate) fn budget_usage(config: &Config, model: &str, usage: &TokenUsage) -> Vec<BudgetUsage> {
+    let spent_tokens = usage
+        .input_tokens
+        .max(0)
+        .saturating_add(usage.output_tokens.max(0));
+    let spent_usd = config
+        .model_price(model)
+        .map(|price| estimate_cost(price, usage));
+    let mut budgets = Vec::new();
+    if !config.budget.is_empty() {
+        budgets.push(BudgetUsage {
+            scope: BudgetScope::Session,
+            spent_tokens,
+            max_tokens: config.budget.max_tokens,
+            spent_usd,
+            max_usd: config.budget.max_usd,
+        });
+    }
+    if let Some(workspace) = config.workspace_budget.as_ref() {
+        budgets.push(BudgetUsage {
+            scope: BudgetScope::Workspace,
+            spent_tokens: workspace.usage.total_tokens().saturating_add(spent_tokens),
+            max_tokens: workspace.limits.max_tokens,
+            spent_usd: spent_usd.map(|spent| workspace.usage.estimated_cost_usd + spent),
+            max_usd: workspace.limits.max_usd,
+        });
+    }
+    budgets
+}
+
+/// Which budgets the session has already warned about or been allowed to
+/// exceed, so each is raised once.
+#[derive(Debug, Default)]
+pub(crate) struct BudgetTracker {
+    warned: HashSet<BudgetScope>,
+    approved: HashSet<BudgetScope>,
+}
+
+impl BudgetTracker {
+    /// Warnings for the budgets that reached 80% since the last call.
+    pub(crate) fn take_warnings(&mut self, budgets: &[BudgetUsage]) -> Vec<String> {
+        budgets
+            .iter()
+            .filter(|budget| {
+                budget
+                    .used_fraction()
+                    .is_some_and(|fraction| fraction >= WARN_FRACTION)
+            })
+            .filter(|budget| self.warned.insert(budget.scope))
+            .map(warning_message)
+            .collect()
+    }
+
+    /// The first used-up budget the user has not agreed to go past.
+    pub(crate) fn first_unapproved(&self, budgets: Vec<BudgetUsage>) -> Option<BudgetUsage> {
+        budgets
+            .into_iter()
+            .find(|budget| budget.is_exceeded() && !self.approved.contains(&budget.scope))
+    }
+
+    /// Stop asking about `scope` for the rest of the session.
+    pub(crate) fn approve(&mut self, scope: BudgetScope) {
+        self.approved.insert(scope);
+    }
+}
+
+/// `Session budget 84% used ($4.20 of $5.00)`
+fn warning_message(budget: &BudgetUsage) -> String {
+    let percent = (budget.used_fraction().unwrap_or_default() * 100.0).floor();
+    format!(
+        "{} budget {percent}% used ({})",
+        scope_label(budget.scope),
+        budget.spend_text()
+    )
+}
+
+/// Why the task ended when going past `budget` was not approved; sent as an
+/// error so clients that answer the prompt on the user's behalf still report
+/// it.
+pub(crate) fn stopped_message(budget: &BudgetUsage) -> String {
+    format!(
+        "{} budget used up ({}); the task was stopped. Raise the limit to continue.",
+        scope_label(budget.scope),
+        budget.spend_text()
+    )
+}
+
+fn scope_label(scope: BudgetScope) -> &'static str {
+    match scope {
+        BudgetScope::Session => "Session",
+        BudgetScope::Workspace => "Workspace",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigOverrides;
+    use crate::config::ConfigToml;
+    use crate::config::types::BudgetConfig;
+    use crate::config::types::ModelPrice;
+    use crate::workspace_state::WorkspaceBudget;
+    use crate::workspace_state::WorkspaceUsage;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn config(codex_home: &TempDir) -> Config {
+        let mut config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect("config");
+        config.model_prices.insert(
+            "gpt-5".to_string(),
+            ModelPrice {
+                input: 1.0,
+                cached_input: None,
+                output: 10.0,
+            },
+        );
+        config.budget = BudgetConfig {
+            max_usd: Some(1.0),
+            max_tokens: None,
+        };
+        config.workspace_budget = Some(WorkspaceBudget {
+            limits: BudgetConfig {
+                max_usd: None,
+                max_tokens: Some(1_000_000),
+            },
+            usage: WorkspaceUsage {
+                sessions: 3,
+                input_tokens: 550_000,
+                output_tokens: 100_000,
+                estimated_cost_usd: 1.5,
+                ..Default::default()
+            },
+        });
+        config
+    }
+
+    fn usage(input_tokens: i64, output_tokens: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn workspace_budget_adds_earlier_sessions() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let config = config(&codex_home);
+        let budgets = budget_usage(&config, "gpt-5", &usage(250_000, 50_000));
+        assert_eq!(
+            budgets,
+            vec![
+                BudgetUsage {
+                    scope: BudgetScope::Session,
+                    spent_tokens: 300_000,
+                    max_tokens: None,
+                    spent_usd: Some(0.75),
+                    max_usd: Some(1.0),
+                },
+                BudgetUsage {
+                    scope: BudgetScope::Workspace,
+                    spent_tokens: 950_000,
+                    max_tokens: Some(1_000_000),
+                    spent_usd: Some(2.25),
+                    max_usd: None,
+                },
+            ]
+        );
+
+        let unpriced = budget_usage(&config, "unknown-model", &usage(250_000, 50_000));
+        assert_eq!(unpriced[0].spent_usd, None);
+        assert_eq!(unpriced[0].used_fraction(), None);
+    }
+
+    #[test]
+    fn warns_once_and_asks_until_approved() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let config = config(&codex_home);
+        let mut tracker = BudgetTracker::default();
+
+        let budgets = budget_usage(&config, "gpt-5", &usage(250_000, 50_000));
+        assert_eq!(
+            tracker.take_warnings(&budgets),
+            vec!["Workspace budget 95% used (950000 of 1000000 tokens)".to_string()]
+        );
+        assert_eq!(tracker.first_unapproved(budgets), None);
+
+        let budgets = budget_usage(&config, "gpt-5", &usage(300_000, 80_000));
+        assert_eq!(
+            tracker.take_warnings(&budgets),
+            vec!["Session budget 110% used ($1.10 of $1.00)".to_string()]
+        );
+        let exceeded = tracker
+            .first_unapproved(budgets.clone())
+            .expect("session budget is used up");
+        assert_eq!(exceeded.scope, BudgetScope::Session);
+
+        assert_eq!(
+            stopped_message(&exceeded),
+            "Session budget used up ($1.10 of $1.00); the task was stopped. Raise the limit to \
+             continue."
+        );
+
+        tracker.approve(BudgetScope::Session);
+        assert_eq!(
+            tracker.first_unapproved(budgets).map(|budget| budget.scope),
+            Some(BudgetScope::Workspace)
+        );
+    }
+}
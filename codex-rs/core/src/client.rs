@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use crate::api_bridge::Code88Credentials;
+use crate::api_bridge::CoreAuthProvider;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use codex_api::AggregateStreamExt;
@@ -37,6 +39,7 @@ use tokio::sync::mpsc;
 use tracing::warn;
 
 use crate::AuthManager;
+use crate::auth::CodexAuth;
 use crate::auth::RefreshTokenError;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
@@ -64,6 +67,8 @@ pub struct ModelClient {
     effort: Option<ReasoningEffortConfig>,
     summary: ReasoningSummaryConfig,
     session_source: SessionSource,
+    /// Shared by the session's clients, so the token is resolved once.
+    code88_credentials: Arc<Code88Credentials>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -79,6 +84,7 @@ impl ModelClient {
         conversation_id: ConversationId,
         session_source: SessionSource,
     ) -> Self {
+        let code88_credentials = Arc::new(Code88Credentials::new(config.code88_token_store()));
         Self {
             config,
             auth_manager,
@@ -89,9 +95,21 @@ impl ModelClient {
             effort,
             summary,
             session_source,
+            code88_credentials,
         }
     }
 
+    /// Send the session's 88code token instead of resolving it again for
+    /// this client.
+    pub(crate) fn with_code88_credentials(mut self, credentials: Arc<Code88Credentials>) -> Self {
+        self.code88_credentials = credentials;
+        self
+    }
+
+    pub(crate) fn code88_credentials(&self) -> Arc<Code88Credentials> {
+        Arc::clone(&self.code88_credentials)
+    }
+
     pub fn get_model_context_window(&self) -> Option<i64> {
         let model_family = self.get_model_family();
         let effective_context_window_percent = model_family.effective_context_window_percent;
@@ -167,7 +185,7 @@ impl ModelClient {
             let api_provider = self
                 .provider
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
-            let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
+            let api_auth = self.api_auth(auth.clone()).await?;
             let transport = ReqwestTransport::new(build_reqwest_client());
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiChatClient::new(transport, api_provider, api_auth)
@@ -187,7 +205,15 @@ impl ModelClient {
                 Err(ApiError::Transport(TransportError::Http { status, .. }))
                     if status == StatusCode::UNAUTHORIZED =>
                 {
-                    handle_unauthorized(status, &mut refreshed, &auth_manager, &auth).await?;
+                    handle_unauthorized(
+                        status,
+                        &mut refreshed,
+                        &self.provider,
+                        &self.code88_credentials,
+                        &auth_manager,
+                        &auth,
+                    )
+                    .await?;
                     continue;
                 }
                 Err(err) => return Err(map_api_error(err)),
@@ -252,7 +278,7 @@ impl ModelClient {
             let api_provider = self
                 .provider
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
-            let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
+            let api_auth = self.api_auth(auth.clone()).await?;
             let transport = ReqwestTransport::new(build_reqwest_client());
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiResponsesClient::new(transport, api_provider, api_auth)
@@ -279,7 +305,15 @@ impl ModelClient {
                 Err(ApiError::Transport(TransportError::Http { status, .. }))
                     if status == StatusCode::UNAUTHORIZED =>
                 {
-                    handle_unauthorized(status, &mut refreshed, &auth_manager, &auth).await?;
+                    handle_unauthorized(
+                        status,
+                        &mut refreshed,
+                        &self.provider,
+                        &self.code88_credentials,
+                        &auth_manager,
+                        &auth,
+                    )
+                    .await?;
                     continue;
                 }
                 Err(err) => return Err(map_api_error(err)),
@@ -287,6 +321,16 @@ impl ModelClient {
         }
     }
 
+    /// Credentials for the provider: the 88code login token when it uses
+    /// `code88_auth`, otherwise its API key or the OpenAI login.
+    async fn api_auth(&self, auth: Option<CodexAuth>) -> Result<CoreAuthProvider> {
+        if self.provider.code88_auth {
+            self.code88_credentials.auth_provider()
+        } else {
+            auth_provider_from_auth(auth, &self.provider).await
+        }
+    }
+
     pub fn get_provider(&self) -> ModelProviderInfo {
         self.provider.clone()
     }
@@ -336,7 +380,7 @@ impl ModelClient {
         let api_provider = self
             .provider
             .to_api_provider(auth.as_ref().map(|a| a.mode))?;
-        let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
+        let api_auth = self.api_auth(auth.clone()).await?;
         let transport = ReqwestTransport::new(build_reqwest_client());
         let request_telemetry = self.build_request_telemetry();
         let client = ApiCompactClient::new(transport, api_provider, api_auth)
@@ -465,13 +509,25 @@ where
 /// Handles a 401 response by optionally refreshing ChatGPT tokens once.
 ///
 /// When refresh succeeds, the caller should retry the API call; otherwise
-/// the mapped `CodexErr` is returned to the caller.
+/// the mapped `CodexErr` is returned to the caller. A rejected 88code token
+/// is retried once when a newer one was saved meanwhile, and otherwise
+/// returned as [`CodexErr::Code88SignInRequired`] without touching the saved
+/// token.
 async fn handle_unauthorized(
     status: StatusCode,
     refreshed: &mut bool,
+    provider: &ModelProviderInfo,
+    code88_credentials: &Code88Credentials,
     auth_manager: &Option<Arc<AuthManager>>,
     auth: &Option<crate::auth::CodexAuth>,
 ) -> Result<()> {
+    if provider.code88_auth {
+        if !*refreshed && code88_credentials.reload() {
+            *refreshed = true;
+            return Ok(());
+        }
+        return Err(CodexErr::Code88SignInRequired);
+    }
     if *refreshed {
         return Err(map_unauthorized_status(status));
     }
@@ -6,6 +6,9 @@ use std::sync::atomic::AtomicU64;
 
 use crate::AuthManager;
 use crate::SandboxState;
+use crate::api_bridge::Code88Credentials;
+use crate::budget::budget_usage;
+use crate::budget::stopped_message;
 use crate::client_common::REVIEW_PROMPT;
 use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
@@ -86,7 +89,11 @@ use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::BudgetExceededEvent;
+use crate::protocol::BudgetUpdateEvent;
+use crate::protocol::BudgetUsage;
 use crate::protocol::DeprecationNoticeEvent;
+use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
@@ -99,12 +106,15 @@ use crate::protocol::SandboxCommandAssessment;
 use crate::protocol::SandboxPolicy;
 use crate::protocol::SessionConfiguredEvent;
 use crate::protocol::StreamErrorEvent;
+use crate::protocol::StructuredWarningEvent;
 use crate::protocol::Submission;
 use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::protocol::TurnDiffEvent;
 use crate::protocol::WarningEvent;
+use crate::protocol::WarningKind;
+use crate::protocol::WarningSeverity;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
@@ -398,8 +408,10 @@ pub(crate) struct SessionSettingsUpdate {
 }
 
 impl Session {
+    #[allow(clippy::too_many_arguments)]
     fn make_turn_context(
         auth_manager: Option<Arc<AuthManager>>,
+        code88_credentials: Arc<Code88Credentials>,
         models_manager: Arc<ModelsManager>,
         otel_event_manager: &OtelEventManager,
         provider: ModelProviderInfo,
@@ -435,7 +447,8 @@ impl Session {
             session_configuration.model_reasoning_summary,
             conversation_id,
             session_configuration.session_source.clone(),
-        );
+        )
+        .with_code88_credentials(code88_credentials);
 
         let tools_config = ToolsConfig::new(&ToolsConfigParams {
             model_family: &model_family,
@@ -582,6 +595,7 @@ impl Session {
             user_shell: default_shell,
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
             auth_manager: Arc::clone(&auth_manager),
+            code88_credentials: Arc::new(Code88Credentials::new(config.code88_token_store())),
             otel_event_manager,
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
@@ -770,6 +784,7 @@ impl Session {
 
         let mut turn_context: TurnContext = Self::make_turn_context(
             Some(Arc::clone(&self.services.auth_manager)),
+            Arc::clone(&self.services.code88_credentials),
             Arc::clone(&self.services.models_manager),
             &self.services.otel_event_manager,
             session_configuration.provider.clone(),
@@ -872,6 +887,7 @@ impl Session {
             config,
             provider,
             auth_manager,
+            turn_context.client.code88_credentials(),
             &otel,
             self.conversation_id,
             self.services.models_manager.clone(),
@@ -1240,11 +1256,16 @@ impl Session {
         turn_context: &TurnContext,
         new_rate_limits: RateLimitSnapshot,
     ) {
-        {
+        let warning = {
             let mut state = self.state.lock().await;
             state.set_rate_limits(new_rate_limits);
-        }
+            state.take_rate_limit_warning()
+        };
         self.send_token_count_event(turn_context).await;
+        if let Some(warning) = warning {
+            self.send_event(turn_context, EventMsg::StructuredWarning(warning))
+                .await;
+        }
     }
 
     async fn send_token_count_event(&self, turn_context: &TurnContext) {
@@ -1256,6 +1277,99 @@ impl Session {
         self.send_event(turn_context, event).await;
     }
 
+    /// Report spending against the configured budgets, warning once about
+    /// each budget that reaches 80%. Sent once per model turn rather than
+    /// with every token count.
+    async fn send_budget_update(&self, turn_context: &TurnContext) {
+        let config = turn_context.client.config();
+        let (budgets, warnings) = {
+            let mut state = self.state.lock().await;
+            let usage = state
+                .token_info()
+                .map(|info| info.total_token_usage)
+                .unwrap_or_default();
+            let budgets = budget_usage(&config, &turn_context.client.get_model(), &usage);
+            let warnings = state.budget.take_warnings(&budgets);
+            (budgets, warnings)
+        };
+        if budgets.is_empty() {
+            return;
+        }
+        self.send_event(
+            turn_context,
+            EventMsg::BudgetUpdate(BudgetUpdateEvent { budgets }),
+        )
+        .await;
+        for message in warnings {
+            self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+        }
+    }
+
+    /// Wait for the user's go-ahead for every used-up budget they have not
+    /// already agreed to exceed. Returns `false` when the task should stop.
+    async fn confirm_budgets(&self, turn_context: &TurnContext) -> bool {
+        let config = turn_context.client.config();
+        loop {
+            let budget = {
+                let state = self.state.lock().await;
+                let usage = state
+                    .token_info()
+                    .map(|info| info.total_token_usage)
+                    .unwrap_or_default();
+                let budgets = budget_usage(&config, &turn_context.client.get_model(), &usage);
+                match state.budget.first_unapproved(budgets) {
+                    Some(budget) => budget,
+                    None => return true,
+                }
+            };
+            let scope = budget.scope;
+            let message = stopped_message(&budget);
+            match self.request_budget_approval(turn_context, budget).await {
+                ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
+                    self.state.lock().await.budget.approve(scope);
+                }
+                _ => {
+                    let event = EventMsg::Error(ErrorEvent {
+                        message,
+                        codex_error_info: None,
+                    });
+                    self.send_event(turn_context, event).await;
+                    return false;
+                }
+            }
+        }
+    }
+
+    async fn request_budget_approval(
+        &self,
+        turn_context: &TurnContext,
+        budget: BudgetUsage,
+    ) -> ReviewDecision {
+        let sub_id = turn_context.sub_id.clone();
+        let (tx_approve, rx_approve) = oneshot::channel();
+        let prev_entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.insert_pending_approval(sub_id.clone(), tx_approve)
+                }
+                None => None,
+            }
+        };
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending approval for sub_id: {sub_id}");
+        }
+
+        let event = EventMsg::BudgetExceeded(BudgetExceededEvent {
+            turn_id: sub_id,
+            budget,
+        });
+        self.send_event(turn_context, event).await;
+        rx_approve.await.unwrap_or_default()
+    }
+
     pub(crate) async fn set_total_tokens_full(&self, turn_context: &TurnContext) {
         let context_window = turn_context.client.get_model_context_window();
         if let Some(context_window) = context_window {
@@ -1495,6 +1609,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::PatchApproval { id, decision } => {
                 handlers::patch_approval(&sess, id, decision).await;
             }
+            Op::BudgetApproval { id, decision } => {
+                handlers::budget_approval(&sess, id, decision).await;
+            }
             Op::AddToHistory { text } => {
                 handlers::add_to_history(&sess, &config, text).await;
             }
@@ -1720,6 +1837,15 @@ mod handlers {
         }
     }
 
+    pub async fn budget_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
+        match decision {
+            ReviewDecision::Abort => {
+                sess.interrupt_task().await;
+            }
+            other => sess.notify_approval(&id, other).await,
+        }
+    }
+
     pub async fn add_to_history(sess: &Arc<Session>, config: &Arc<Config>, text: String) {
         let id = sess.conversation_id;
         let config = Arc::clone(config);
@@ -1953,7 +2079,8 @@ async fn spawn_review_thread(
         per_turn_config.model_reasoning_summary,
         sess.conversation_id,
         parent_turn_context.client.get_session_source(),
-    );
+    )
+    .with_code88_credentials(parent_turn_context.client.code88_credentials());
 
     let review_turn_context = TurnContext {
         sub_id: sub_id.to_string(),
@@ -2031,6 +2158,11 @@ pub(crate) async fn run_task(
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
 
     loop {
+        // A used-up budget pauses the task until the user agrees to go on.
+        if !sess.confirm_budgets(&turn_context).await {
+            break;
+        }
+
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
@@ -2066,6 +2198,7 @@ pub(crate) async fn run_task(
         .await
         {
             Ok(turn_output) => {
+                sess.send_budget_update(&turn_context).await;
                 let TurnRunResult {
                     needs_follow_up,
                     last_agent_message: turn_last_agent_message,
@@ -2115,8 +2248,17 @@ pub(crate) async fn run_task(
             }
             Err(e) => {
                 info!("Turn error: {e:#}");
-                let event = EventMsg::Error(e.to_error_event(None));
-                sess.send_event(&turn_context, event).await;
+                let event = e.to_error_event(None);
+                if matches!(event.codex_error_info, Some(CodexErrorInfo::Unauthorized)) {
+                    let warning = StructuredWarningEvent {
+                        kind: WarningKind::Auth,
+                        severity: WarningSeverity::Critical,
+                        message: event.message.clone(),
+                    };
+                    sess.send_event(&turn_context, EventMsg::StructuredWarning(warning))
+                        .await;
+                }
+                sess.send_event(&turn_context, EventMsg::Error(event)).await;
                 // let the user continue the conversation
                 break;
             }
@@ -2210,6 +2352,9 @@ async fn run_turn(
             Err(e @ CodexErr::InvalidImageRequest()) => return Err(e),
             Err(e @ CodexErr::InvalidRequest(_)) => return Err(e),
             Err(e @ CodexErr::RefreshTokenFailed(_)) => return Err(e),
+            // Signing in again opens a browser; only an interactive client
+            // can decide to do that, so it gets the error.
+            Err(e @ CodexErr::Code88SignInRequired) => return Err(e),
             Err(e) => {
                 // Use the configured provider-specific stream retry budget.
                 let max_retries = turn_context.client.get_provider().stream_max_retries();
@@ -2700,6 +2845,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rate_limit_warnings_are_sent_once_per_threshold() {
+        let codex_home = tempfile::tempdir().expect("create temp dir");
+        let config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect("load default test config");
+        let config = Arc::new(config);
+        let session_configuration = SessionConfiguration {
+            provider: config.model_provider.clone(),
+            model: config.model.clone(),
+            model_reasoning_effort: config.model_reasoning_effort,
+            model_reasoning_summary: config.model_reasoning_summary,
+            developer_instructions: config.developer_instructions.clone(),
+            user_instructions: config.user_instructions.clone(),
+            base_instructions: config.base_instructions.clone(),
+            compact_prompt: config.compact_prompt.clone(),
+            approval_policy: config.approval_policy,
+            sandbox_policy: config.sandbox_policy.clone(),
+            cwd: config.cwd.clone(),
+            original_config_do_not_use: Arc::clone(&config),
+            exec_policy: Arc::new(RwLock::new(ExecPolicy::empty())),
+            session_source: SessionSource::Exec,
+        };
+        let mut state = SessionState::new(session_configuration);
+        let mut warning_at = |used_percent: f64| {
+            state.set_rate_limits(RateLimitSnapshot {
+                primary: Some(RateLimitWindow {
+                    used_percent,
+                    window_minutes: Some(300),
+                    resets_at: None,
+                }),
+                secondary: None,
+                credits: None,
+                plan_type: None,
+            });
+            state.take_rate_limit_warning()
+        };
+
+        assert_eq!(warning_at(50.0), None);
+        assert_eq!(
+            warning_at(80.0),
+            Some(StructuredWarningEvent {
+                kind: WarningKind::RateLimit,
+                severity: WarningSeverity::Warning,
+                message: "80% of the 5h rate limit used".to_string(),
+            })
+        );
+        assert_eq!(warning_at(85.0), None);
+        assert_eq!(
+            warning_at(96.0),
+            Some(StructuredWarningEvent {
+                kind: WarningKind::RateLimit,
+                severity: WarningSeverity::Critical,
+                message: "96% of the 5h rate limit used".to_string(),
+            })
+        );
+        // Once the window resets, the next crossing is reported again.
+        assert_eq!(warning_at(1.0), None);
+        assert_eq!(
+            warning_at(76.0),
+            Some(StructuredWarningEvent {
+                kind: WarningKind::RateLimit,
+                severity: WarningSeverity::Warning,
+                message: "76% of the 5h rate limit used".to_string(),
+            })
+        );
+    }
+
     #[test]
     fn prefers_structured_content_when_present() {
         let ctr = CallToolResult {
@@ -2843,6 +3059,7 @@ mod tests {
         let auth_manager =
             AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
         let models_manager = Arc::new(ModelsManager::new(auth_manager.clone()));
+        let code88_credentials = Arc::new(Code88Credentials::new(config.code88_token_store()));
         let otel_event_manager =
             otel_event_manager(conversation_id, config.as_ref(), &models_manager);
 
@@ -2874,6 +3091,7 @@ mod tests {
             user_shell: default_user_shell(),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
             auth_manager: auth_manager.clone(),
+            code88_credentials: Arc::clone(&code88_credentials),
             otel_event_manager: otel_event_manager.clone(),
             models_manager: models_manager.clone(),
             tool_approvals: Mutex::new(ApprovalStore::default()),
@@ -2881,6 +3099,7 @@ mod tests {
 
         let turn_context = Session::make_turn_context(
             Some(Arc::clone(&auth_manager)),
+            Arc::clone(&code88_credentials),
             models_manager,
             &otel_event_manager,
             session_configuration.provider.clone(),
@@ -2922,6 +3141,7 @@ mod tests {
         let auth_manager =
             AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
         let models_manager = Arc::new(ModelsManager::new(auth_manager.clone()));
+        let code88_credentials = Arc::new(Code88Credentials::new(config.code88_token_store()));
         let otel_event_manager =
             otel_event_manager(conversation_id, config.as_ref(), &models_manager);
 
@@ -2953,6 +3173,7 @@ mod tests {
             user_shell: default_user_shell(),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
             auth_manager: Arc::clone(&auth_manager),
+            code88_credentials: Arc::clone(&code88_credentials),
             otel_event_manager: otel_event_manager.clone(),
             models_manager: models_manager.clone(),
             tool_approvals: Mutex::new(ApprovalStore::default()),
@@ -2960,6 +3181,7 @@ mod tests {
 
         let turn_context = Arc::new(Session::make_turn_context(
             Some(Arc::clone(&auth_manager)),
+            Arc::clone(&code88_credentials),
             models_manager,
             &otel_event_manager,
             session_configuration.provider.clone(),
@@ -1,7 +1,10 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::BudgetConfig;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::Devspaces;
 use crate::config::types::History;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelPrice;
 use crate::config::types::Notice;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
@@ -11,8 +14,13 @@ use crate::config::types::ReasoningSummaryFormat;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
+use crate::config::types::SpinnerSettings;
+use crate::config::types::StatusLineSettings;
+use crate::config::types::TerminalProgressMode;
+use crate::config::types::TokenCountFormat;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
+use crate::config::types::WorkspaceStateBackend;
 use crate::config_loader::LoadedConfigLayers;
 use crate::config_loader::load_config_as_toml;
 use crate::config_loader::load_config_layers_with_overrides;
@@ -33,6 +41,7 @@ use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
 use crate::util::resolve_path;
+use crate::workspace_state::WorkspaceBudget;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
 use codex_protocol::config_types::ForcedLoginMethod;
@@ -59,6 +68,7 @@ use toml_edit::DocumentMut;
 pub mod edit;
 pub mod profile;
 pub mod types;
+pub mod validate;
 
 pub const OPENAI_DEFAULT_MODEL: &str = "gpt-5.1-codex-max";
 const OPENAI_DEFAULT_REVIEW_MODEL: &str = "gpt-5.1-codex-max";
@@ -70,6 +80,9 @@ pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
+/// How long 88code usage responses are reused when `[code88]` does not say.
+const DEFAULT_CODE88_USAGE_CACHE_TTL_SECS: u64 = 10;
+
 /// Application configuration loaded from disk and merged with overrides.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Config {
@@ -155,10 +168,50 @@ pub struct Config {
     /// Toggle for the bespoke Codex status line rendering.
     pub tui_custom_statusline: bool,
 
+    /// Which task progress the TUI reports through OSC 9;4 sequences.
+    pub tui_terminal_progress: TerminalProgressMode,
+
+    /// Whether the TUI mirrors the run state into the terminal title.
+    pub tui_terminal_title: bool,
+
+    /// Whether rate-limit history is persisted per session on disk.
+    pub tui_rate_limit_history: bool,
+
+    /// Screen-reader mode: plain-text status line and no animations.
+    pub tui_accessible: bool,
+
+    /// How token counts are written in the TUI.
+    pub tui_token_format: TokenCountFormat,
+
+    /// Frames, pace and reduced motion for the TUI spinner.
+    pub tui_spinner: SpinnerSettings,
+
+    /// Devspaces registered under `[tui.devspaces]`.
+    pub tui_devspaces: Devspaces,
+
+    /// Rendering options for the custom status line.
+    pub tui_statusline: StatusLineSettings,
+
     /// 88code API key for usage tracking in status line.
     /// Sourced from config file or CODE88_API_KEY environment variable.
     pub tui_code88_api_key: Option<String>,
 
+    /// 88code login token from `code88_token`, ranked below
+    /// `CODEX_88CODE_TOKEN` and above any saved token.
+    pub code88_token: Option<String>,
+
+    /// How the 88code browser login runs the browser (`[code88]`), already
+    /// validated.
+    pub code88_browser: codex_code88::BrowserOptions,
+
+    /// How long 88code usage responses are reused (`[code88]
+    /// usage_cache_ttl_secs`); zero when caching is off.
+    pub code88_usage_cache_ttl: std::time::Duration,
+
+    /// Whether the 88code usage cache is also kept on disk under
+    /// `codex_home`.
+    pub code88_usage_cache_file: bool,
+
     /// Enable ASCII animations and shimmer effects in the TUI.
     pub animations: bool,
 
@@ -191,6 +244,20 @@ pub struct Config {
     /// Combined provider map (defaults merged with user-defined overrides).
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// Per-model token prices used to estimate the cost recorded in
+    /// workspace usage totals.
+    pub model_prices: HashMap<String, ModelPrice>,
+
+    /// Spending limits for one session, from `[budget]`.
+    pub budget: BudgetConfig,
+
+    /// Spending limits saved for the workspace, with what earlier sessions
+    /// there already spent.
+    pub workspace_budget: Option<WorkspaceBudget>,
+
+    /// Store used for per-workspace state.
+    pub workspace_state_backend: WorkspaceStateBackend,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: usize,
 
@@ -269,6 +336,10 @@ pub struct Config {
     /// The active profile name used to derive this `Config` (if any).
     pub active_profile: Option<String>,
 
+    /// The `-c key=value` overrides this `Config` was loaded with, so a
+    /// reload of `config.toml` can apply them again.
+    pub cli_overrides: Vec<(String, TomlValue)>,
+
     /// The currently active project config, resolved by checking if cwd:
     /// is (1) part of a git repo, (2) a git worktree, or (3) just using the cwd
     pub active_project: ProjectConfig,
@@ -284,6 +355,11 @@ pub struct Config {
     /// Defaults to `true`.
     pub check_for_update_on_startup: bool,
 
+    /// When `true`, compares this build with the latest release of the fork
+    /// on GitHub (at most once a day) and shows when a newer one exists.
+    /// Defaults to `false`.
+    pub check_for_release_on_startup: bool,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -302,7 +378,7 @@ impl Config {
 
         let root_value = load_resolved_config(
             &codex_home,
-            cli_overrides,
+            cli_overrides.clone(),
             crate::config_loader::LoaderOverrides::default(),
         )
         .await?;
@@ -312,7 +388,25 @@ impl Config {
             std::io::Error::new(std::io::ErrorKind::InvalidData, e)
         })?;
 
-        Self::load_from_base_config_with_overrides(cfg, overrides, codex_home)
+        let mut config = Self::load_from_base_config_with_overrides(cfg, overrides, codex_home)?;
+        config.cli_overrides = cli_overrides;
+        Ok(config)
+    }
+
+    /// Where the 88code login token of this configuration is looked up and
+    /// saved, see [`codex_code88::TokenStore::resolve_token`].
+    pub fn code88_token_store(&self) -> codex_code88::TokenStore {
+        code88_token_store(
+            &self.codex_home,
+            self.code88_token.clone(),
+            self.cli_auth_credentials_store_mode,
+        )
+    }
+
+    /// The `[model_prices]` entry for `model`, which every cost estimate
+    /// (workspace totals and budgets) is priced from.
+    pub fn model_price(&self, model: &str) -> Option<&ModelPrice> {
+        self.model_prices.get(model)
     }
 }
 
@@ -628,6 +722,14 @@ pub struct ConfigToml {
     #[serde(default)]
     pub cli_auth_credentials_store: Option<AuthCredentialsStoreMode>,
 
+    /// 88code login token to use instead of the one saved by `codex login
+    /// 88code`. `CODEX_88CODE_TOKEN` still takes precedence.
+    pub code88_token: Option<String>,
+
+    /// Options for the 88code browser login.
+    #[serde(default)]
+    pub code88: Option<crate::config::types::Code88Toml>,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     #[serde(default)]
     pub mcp_servers: HashMap<String, McpServerConfig>,
@@ -644,6 +746,17 @@ pub struct ConfigToml {
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// Token prices (USD per million tokens) keyed by model slug.
+    #[serde(default)]
+    pub model_prices: HashMap<String, ModelPrice>,
+
+    /// Per-session spending limits.
+    pub budget: Option<BudgetConfig>,
+
+    /// `toml` (default) keeps one file per workspace; `sqlite` moves the
+    /// saved workspace state into a single database.
+    pub workspace_state_backend: Option<WorkspaceStateBackend>,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: Option<usize>,
 
@@ -707,6 +820,11 @@ pub struct ConfigToml {
     /// Defaults to `true`.
     pub check_for_update_on_startup: Option<bool>,
 
+    /// When `true`, compares this build with the latest release of the fork
+    /// on GitHub (at most once a day) and shows when a newer one exists.
+    /// Defaults to `false`.
+    pub check_for_release_on_startup: Option<bool>,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -800,15 +918,19 @@ pub struct SandboxPolicyResolution {
 
 impl ConfigToml {
     /// Derive the effective sandbox policy from the configuration.
+    /// `workspace_sandbox_mode` is the mode last picked with `/approvals` in
+    /// this workspace; anything set in config wins over it.
     fn derive_sandbox_policy(
         &self,
         sandbox_mode_override: Option<SandboxMode>,
         profile_sandbox_mode: Option<SandboxMode>,
+        workspace_sandbox_mode: Option<SandboxMode>,
         resolved_cwd: &Path,
     ) -> SandboxPolicyResolution {
         let resolved_sandbox_mode = sandbox_mode_override
             .or(profile_sandbox_mode)
             .or(self.sandbox_mode)
+            .or(workspace_sandbox_mode)
             .or_else(|| {
                 // if no sandbox_mode is set, but user has marked directory as trusted or untrusted, use WorkspaceWrite
                 self.get_active_project(resolved_cwd).and_then(|p| {
@@ -1036,10 +1158,33 @@ impl Config {
             .get_active_project(&resolved_cwd)
             .unwrap_or(ProjectConfig { trust_level: None });
 
+        let workspace_state_backend = cfg.workspace_state_backend.unwrap_or_default();
+        if let Err(err) = crate::workspace_state::ensure_workspace_state_backend(
+            &codex_home,
+            workspace_state_backend,
+        ) {
+            tracing::warn!(
+                "failed to switch workspace state to {workspace_state_backend:?}: {err}"
+            );
+        }
+        // The mode last picked with `/approvals` here only fills in what the
+        // command line, profile and config.toml leave unset.
+        let (workspace_approval_policy, workspace_sandbox_mode) =
+            crate::workspace_state::load_policy_selection(&codex_home, &resolved_cwd)
+                .unwrap_or_else(|err| {
+                    tracing::warn!("failed to load workspace approval policy: {err}");
+                    (None, None)
+                });
+
         let SandboxPolicyResolution {
             policy: mut sandbox_policy,
             forced_auto_mode_downgraded_on_windows,
-        } = cfg.derive_sandbox_policy(sandbox_mode, config_profile.sandbox_mode, &resolved_cwd);
+        } = cfg.derive_sandbox_policy(
+            sandbox_mode,
+            config_profile.sandbox_mode,
+            workspace_sandbox_mode,
+            &resolved_cwd,
+        );
         if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy {
             for path in additional_writable_roots {
                 if !writable_roots.iter().any(|existing| existing == &path) {
@@ -1050,6 +1195,7 @@ impl Config {
         let approval_policy = approval_policy_override
             .or(config_profile.approval_policy)
             .or(cfg.approval_policy)
+            .or(workspace_approval_policy)
             .unwrap_or_else(|| {
                 if active_project.is_trusted() {
                     // If no explicit approval policy is set, but we trust cwd, default to OnRequest
@@ -1089,7 +1235,27 @@ impl Config {
             })?
             .clone();
 
-        let shell_environment_policy = cfg.shell_environment_policy.into();
+        let mut shell_environment_policy: ShellEnvironmentPolicy =
+            cfg.shell_environment_policy.into();
+        // Variables saved for this workspace win over `[shell_environment_policy].set`.
+        match crate::workspace_state::load_workspace_env(&codex_home, &resolved_cwd) {
+            Ok(env) => shell_environment_policy.r#set.extend(env),
+            Err(err) => tracing::warn!("failed to load workspace environment: {err}"),
+        }
+        let mut mcp_servers = cfg.mcp_servers;
+        if let Err(err) = crate::workspace_state::apply_workspace_mcp_overrides(
+            &codex_home,
+            &resolved_cwd,
+            &mut mcp_servers,
+        ) {
+            tracing::warn!("failed to load workspace MCP overrides: {err}");
+        }
+        let workspace_budget =
+            crate::workspace_state::load_workspace_budget(&codex_home, &resolved_cwd)
+                .unwrap_or_else(|err| {
+                    tracing::warn!("failed to load workspace budget: {err}");
+                    None
+                });
 
         let history = cfg.history.unwrap_or_default();
 
@@ -1113,7 +1279,38 @@ impl Config {
         let forced_login_method = cfg.forced_login_method;
 
         // Detect 88code API key before codex_home is moved
-        let tui_code88_api_key = Self::detect_88code_api_key(&codex_home);
+        let code88_token = cfg.code88_token.filter(|token| !token.trim().is_empty());
+        let cli_auth_credentials_store_mode = cfg.cli_auth_credentials_store.unwrap_or_default();
+        let tui_code88_api_key = Self::detect_88code_api_key(&code88_token_store(
+            &codex_home,
+            code88_token.clone(),
+            cli_auth_credentials_store_mode,
+        ));
+        let code88 = cfg.code88.unwrap_or_default();
+        let code88_usage_cache_ttl = std::time::Duration::from_secs(
+            code88
+                .usage_cache_ttl_secs
+                .map_or(DEFAULT_CODE88_USAGE_CACHE_TTL_SECS, |secs| {
+                    u64::try_from(secs).unwrap_or(0)
+                }),
+        );
+        let code88_usage_cache_file = code88.usage_cache_file;
+        let code88_browser = codex_code88::BrowserOptions {
+            args: code88.browser_args,
+            keep_open: code88.keep_browser_open,
+            success_url: code88.success_url,
+        };
+        code88_browser.validate().map_err(|err| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, err.to_string())
+        })?;
+        let tui_statusline = cfg
+            .tui
+            .as_ref()
+            .map(|t| t.statusline.clone())
+            .unwrap_or_default();
+        tui_statusline
+            .validate()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
 
         let model = model
             .or(config_profile.model)
@@ -1194,12 +1391,16 @@ impl Config {
             compact_prompt,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
-            cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
-            mcp_servers: cfg.mcp_servers,
+            cli_auth_credentials_store_mode,
+            mcp_servers,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
             model_providers,
+            model_prices: cfg.model_prices,
+            budget: cfg.budget.unwrap_or_default(),
+            workspace_budget,
+            workspace_state_backend,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
             project_doc_fallback_filenames: cfg
                 .project_doc_fallback_filenames
@@ -1248,24 +1449,58 @@ impl Config {
             use_experimental_use_rmcp_client,
             features,
             active_profile: active_profile_name,
+            cli_overrides: Vec::new(),
             active_project,
             windows_wsl_setup_acknowledged: cfg.windows_wsl_setup_acknowledged.unwrap_or(false),
             notices: cfg.notice.unwrap_or_default(),
             check_for_update_on_startup,
+            check_for_release_on_startup: cfg.check_for_release_on_startup.unwrap_or(false),
             disable_paste_burst: cfg.disable_paste_burst.unwrap_or(false),
             tui_notifications: cfg
                 .tui
                 .as_ref()
                 .map(|t| t.notifications.clone())
                 .unwrap_or_default(),
-            animations: cfg.tui.as_ref().map(|t| t.animations).unwrap_or(true),
+            animations: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.animations && !t.accessible)
+                .unwrap_or(true),
             show_tooltips: cfg.tui.as_ref().map(|t| t.show_tooltips).unwrap_or(true),
             tui_custom_statusline: cfg
                 .tui
                 .as_ref()
                 .map(|t| t.custom_statusline)
                 .unwrap_or_else(|| Tui::default().custom_statusline),
+            tui_terminal_progress: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.terminal_progress)
+                .unwrap_or_default(),
+            tui_terminal_title: cfg.tui.as_ref().is_some_and(|t| t.terminal_title),
+            tui_rate_limit_history: cfg.tui.as_ref().is_some_and(|t| t.rate_limit_history),
+            tui_accessible: cfg.tui.as_ref().is_some_and(|t| t.accessible),
+            tui_token_format: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.token_format.clone())
+                .unwrap_or_default(),
+            tui_spinner: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.spinner.clone())
+                .unwrap_or_default(),
+            tui_devspaces: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.devspaces.clone())
+                .unwrap_or_default(),
+            tui_statusline,
             tui_code88_api_key,
+            code88_token,
+            code88_browser,
+            code88_usage_cache_ttl,
+            code88_usage_cache_file,
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1298,10 +1533,10 @@ impl Config {
         None
     }
 
-    /// Detect 88code API key from environment variable, token file, or auth.json.
-    /// Priority: OPENAI_API_KEY env > key88 env > 88code-token.json > auth.json
+    /// Detect 88code API key from environment variable, login token, or auth.json.
+    /// Priority: OPENAI_API_KEY env > key88 env > 88code login token > auth.json
     /// The key is identified by the `88_` prefix.
-    fn detect_88code_api_key(codex_home: &Path) -> Option<String> {
+    fn detect_88code_api_key(code88: &codex_code88::TokenStore) -> Option<String> {
         // First, check OPENAI_API_KEY environment variable (highest priority)
         if let Ok(key) = std::env::var("OPENAI_API_KEY")
             && key.starts_with("88_")
@@ -1316,23 +1551,13 @@ impl Config {
             return Some(key);
         }
 
-        // Third, check 88code-token.json (from browser login)
-        let token_file = codex_home.join("88code-token.json");
-        if let Ok(contents) = std::fs::read_to_string(&token_file) {
-            #[derive(serde::Deserialize)]
-            struct TokenFile {
-                token: String,
-            }
-            if let Ok(tf) = serde_json::from_str::<TokenFile>(&contents)
-                && !tf.token.is_empty()
-            {
-                // Format as 88code API key
-                return Some(format!("88_{}", tf.token));
-            }
+        // Third, the 88code login token (see `TokenStore::resolve_token`)
+        if let Some(resolved) = code88.resolve_token() {
+            return Some(codex_code88::api_key(&resolved.token));
         }
 
         // Fallback to auth.json
-        let auth_file = codex_home.join("auth.json");
+        let auth_file = code88.codex_home().join("auth.json");
         let contents = std::fs::read_to_string(&auth_file).ok()?;
 
         #[derive(serde::Deserialize)]
@@ -1435,6 +1660,20 @@ pub fn log_dir(cfg: &Config) -> std::io::Result<PathBuf> {
     Ok(p)
 }
 
+/// The 88code token store follows `cli_auth_credentials_store`: any mode
+/// but `file` keeps the token in the OS keychain.
+fn code88_token_store(
+    codex_home: &Path,
+    configured: Option<String>,
+    mode: AuthCredentialsStoreMode,
+) -> codex_code88::TokenStore {
+    let store = codex_code88::TokenStore::new(codex_home).with_configured_token(configured);
+    match mode {
+        AuthCredentialsStoreMode::File => store,
+        AuthCredentialsStoreMode::Keyring | AuthCredentialsStoreMode::Auto => store.with_keychain(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::config::edit::ConfigEdit;
@@ -1443,6 +1682,11 @@ mod tests {
     use crate::config::types::HistoryPersistence;
     use crate::config::types::McpServerTransportConfig;
     use crate::config::types::Notifications;
+    use crate::config::types::SpinnerFrameSet;
+    use crate::config::types::StatusLineMaxWidths;
+    use crate::config::types::StatusLineSegments;
+    use crate::config::types::StatusLineTimerFormat;
+    use crate::config::types::TokenCountStyle;
     use crate::features::Feature;
 
     use super::*;
@@ -1495,6 +1739,213 @@ persistence = "none"
 
         assert_eq!(tui.notifications, Notifications::Enabled(true));
         assert!(tui.show_tooltips);
+        assert_eq!(tui.terminal_progress, TerminalProgressMode::Off);
+        assert!(!tui.terminal_title);
+        assert!(!tui.rate_limit_history);
+        assert!(!tui.accessible);
+        assert_eq!(tui.statusline, StatusLineSettings::default());
+    }
+
+    #[test]
+    fn tui_config_parses_statusline_table() {
+        let cfg = r#"
+[tui.statusline]
+context_warning_threshold = 25
+context_baseline_tokens = 0
+skin = "minimal-plain"
+script = "statusline.rhai"
+script_timeout_ms = 20
+run_label_max_width = 24
+run_label_marquee = true
+exec_label = "$ {binary}"
+tool_label = "{name} via {server}"
+approval_label = "Approve {subject}?"
+queue_preview_cycle_secs = 4
+middle_queue_max = 3
+timer_format = "mm:ss"
+timer_min_secs = 3
+git_base_branch = "upstream/develop"
+environment_refresh_secs = 15
+reachability_probe_secs = 20
+metrics_file = "metrics/codex.prom"
+session_timeline = true
+control_socket = true
+hot_reload = true
+run_summary_secs = 8
+
+[tui.statusline.segments]
+aws_profile = false
+kubernetes = false
+
+[tui.devspaces]
+earth = { color = "blue", description = "payments" }
+"#;
+
+        let parsed =
+            toml::from_str::<ConfigToml>(cfg).expect("[tui.statusline] should deserialize");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(tui.statusline.context_warning_threshold, 25);
+        assert_eq!(tui.statusline.context_baseline_tokens, 0);
+        assert_eq!(tui.statusline.skin, "minimal-plain");
+        assert_eq!(
+            tui.statusline.script,
+            Some(PathBuf::from("statusline.rhai"))
+        );
+        assert_eq!(tui.statusline.script_timeout_ms, 20);
+        assert_eq!(tui.statusline.run_label_max_width, 24);
+        assert!(tui.statusline.run_label_marquee);
+        assert_eq!(tui.statusline.exec_label, "$ {binary}");
+        assert_eq!(tui.statusline.tool_label, "{name} via {server}");
+        assert_eq!(tui.statusline.approval_label, "Approve {subject}?");
+        assert_eq!(tui.statusline.queue_preview_cycle_secs, 4);
+        assert_eq!(tui.statusline.middle_queue_max, 3);
+        assert_eq!(
+            tui.statusline.timer_format,
+            StatusLineTimerFormat::MinutesSeconds
+        );
+        assert_eq!(tui.statusline.timer_min_secs, 3);
+        assert_eq!(tui.statusline.git_base_branch, "upstream/develop");
+        assert_eq!(tui.statusline.environment_refresh_secs, 15);
+        assert_eq!(tui.statusline.reachability_probe_secs, 20);
+        assert_eq!(
+            tui.statusline.metrics_file,
+            Some(PathBuf::from("metrics/codex.prom"))
+        );
+        assert!(tui.statusline.session_timeline);
+        assert!(tui.statusline.control_socket);
+        assert!(tui.statusline.hot_reload);
+        assert_eq!(tui.statusline.run_summary_secs, 8);
+        let (index, earth) = tui.devspaces.get("earth").expect("earth is registered");
+        assert_eq!(index, 0);
+        assert_eq!(earth.color.as_deref(), Some("blue"));
+        assert_eq!(earth.description.as_deref(), Some("payments"));
+        assert_eq!(
+            tui.statusline.segments,
+            StatusLineSegments {
+                aws_profile: false,
+                kubernetes: false,
+                ..StatusLineSegments::default()
+            }
+        );
+    }
+
+    #[test]
+    fn tui_config_validates_statusline_max_widths() {
+        let cfg = r#"
+[tui.statusline]
+path_max_width = 24
+
+[tui.statusline.max_widths]
+git = 48
+hostname = 12
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("max_widths should deserialize");
+        let tui = parsed.tui.expect("config should include tui section");
+        assert_eq!(
+            tui.statusline.max_widths,
+            StatusLineMaxWidths {
+                git: 48,
+                hostname: 12,
+                ..StatusLineMaxWidths::default()
+            }
+        );
+
+        let err = toml::from_str::<ConfigToml>("[tui.statusline.max_widths]\ngit = 2\niac = 500\n")
+            .expect_err("out-of-range widths are rejected");
+        let message = err.to_string();
+        assert!(
+            message.contains("`git = 2`, `iac = 500`"),
+            "unexpected error: {message}"
+        );
+        assert!(
+            toml::from_str::<ConfigToml>("[tui.statusline.max_widths]\nbranch = 30\n").is_err(),
+            "unknown segments are rejected"
+        );
+    }
+
+    #[test]
+    fn tui_config_rejects_out_of_range_statusline_numbers() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str("[tui.statusline]\ncontext_warning_threshold = 101\n")
+            .expect("TOML deserialization should succeed");
+        let error = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("thresholds are percentages");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error.to_string(),
+            "[tui.statusline] values out of range: `context_warning_threshold = 101` (expected 0 to 100)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn tui_config_parses_token_format() {
+        let cfg = r#"
+[tui.token_format]
+style = "exact"
+locale = "de_DE.UTF-8"
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("token_format should deserialize");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(
+            tui.token_format,
+            TokenCountFormat {
+                style: TokenCountStyle::Exact,
+                locale: Some("de_DE.UTF-8".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn tui_config_parses_spinner() {
+        let cfg = r#"
+[tui.spinner]
+frame_set = "braille"
+frames = ["◐", "◓", "◑", "◒"]
+interval_ms = 120
+reduced_motion = true
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("spinner should deserialize");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(
+            tui.spinner,
+            SpinnerSettings {
+                frame_set: SpinnerFrameSet::Braille,
+                frames: vec![
+                    "◐".to_string(),
+                    "◓".to_string(),
+                    "◑".to_string(),
+                    "◒".to_string(),
+                ],
+                interval_ms: Some(120),
+                reduced_motion: true,
+            }
+        );
+    }
+
+    #[test]
+    fn tui_config_parses_terminal_progress_mode() {
+        let cfg = r#"
+[tui]
+terminal_progress = "context"
+"#;
+
+        let parsed =
+            toml::from_str::<ConfigToml>(cfg).expect("terminal_progress should deserialize");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(tui.terminal_progress, TerminalProgressMode::Context);
     }
 
     #[test]
@@ -1511,6 +1962,7 @@ network_access = false  # This should be ignored.
         let resolution = sandbox_full_access_cfg.derive_sandbox_policy(
             sandbox_mode_override,
             None,
+            None,
             &PathBuf::from("/tmp/test"),
         );
         assert_eq!(
@@ -1534,6 +1986,7 @@ network_access = true  # This should be ignored.
         let resolution = sandbox_read_only_cfg.derive_sandbox_policy(
             sandbox_mode_override,
             None,
+            None,
             &PathBuf::from("/tmp/test"),
         );
         assert_eq!(
@@ -1561,6 +2014,7 @@ exclude_slash_tmp = true
         let resolution = sandbox_workspace_write_cfg.derive_sandbox_policy(
             sandbox_mode_override,
             None,
+            None,
             &PathBuf::from("/tmp/test"),
         );
         if cfg!(target_os = "windows") {
@@ -1606,6 +2060,7 @@ trust_level = "trusted"
         let resolution = sandbox_workspace_write_cfg.derive_sandbox_policy(
             sandbox_mode_override,
             None,
+            None,
             &PathBuf::from("/tmp/test"),
         );
         if cfg!(target_os = "windows") {
@@ -1724,6 +2179,77 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn code88_browser_args_may_not_set_the_debugging_port() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[code88]
+browser_args = ["--proxy-server=http://proxy.corp:8080"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(
+            config.code88_browser.args,
+            vec!["--proxy-server=http://proxy.corp:8080".to_string()]
+        );
+
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[code88]
+browser_args = ["--remote-debugging-port=9999"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let error = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("the debugging port is reserved");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+
+    #[test]
+    fn code88_usage_cache_defaults_to_ten_seconds_in_memory() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(
+            config.code88_usage_cache_ttl,
+            std::time::Duration::from_secs(10)
+        );
+        assert!(!config.code88_usage_cache_file);
+
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[code88]
+usage_cache_ttl_secs = 0
+usage_cache_file = true
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.code88_usage_cache_ttl, std::time::Duration::ZERO);
+        assert!(config.code88_usage_cache_file);
+
+        Ok(())
+    }
+
     #[test]
     fn config_defaults_to_auto_oauth_store_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2895,6 +3421,195 @@ model = "gpt-5.1-codex"
         Ok(())
     }
 
+    #[test]
+    fn workspace_env_overrides_shell_environment_set() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let workspace = codex_home.path().join("workspace");
+        std::fs::create_dir_all(&workspace)?;
+        crate::workspace_state::persist_env_var(
+            codex_home.path(),
+            &workspace,
+            "AWS_PROFILE",
+            Some("staging"),
+        )?;
+
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[shell_environment_policy.set]
+AWS_PROFILE = "default"
+RUST_LOG = "info"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let overrides = ConfigOverrides {
+            cwd: Some(workspace),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            overrides,
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.shell_environment_policy.r#set,
+            HashMap::from([
+                ("AWS_PROFILE".to_string(), "staging".to_string()),
+                ("RUST_LOG".to_string(), "info".to_string()),
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn workspace_policy_selection_is_the_lowest_precedence_default() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let workspace = codex_home.path().join("workspace");
+        std::fs::create_dir_all(&workspace)?;
+        crate::workspace_state::persist_policy_selection(
+            codex_home.path(),
+            &workspace,
+            AskForApproval::Never,
+            &SandboxPolicy::DangerFullAccess,
+        )
+        .await?;
+        let load = |cfg: &str| {
+            Config::load_from_base_config_with_overrides(
+                toml::from_str(cfg).expect("TOML deserialization should succeed"),
+                ConfigOverrides {
+                    cwd: Some(workspace.clone()),
+                    ..Default::default()
+                },
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        let config = load("")?;
+        assert_eq!(config.approval_policy, AskForApproval::Never);
+        assert_eq!(config.sandbox_policy, SandboxPolicy::DangerFullAccess);
+        assert!(!config.did_user_set_custom_approval_policy_or_sandbox_mode);
+
+        let config = load(
+            r#"
+approval_policy = "on-request"
+sandbox_mode = "read-only"
+"#,
+        )?;
+        assert_eq!(config.approval_policy, AskForApproval::OnRequest);
+        assert_eq!(config.sandbox_policy, SandboxPolicy::new_read_only_policy());
+
+        Ok(())
+    }
+
+    #[test]
+    fn workspace_mcp_overrides_merge_over_global_servers() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let workspace = codex_home.path().join("workspace");
+        std::fs::create_dir_all(&workspace)?;
+        crate::workspace_state::update_workspace_mcp_server(
+            codex_home.path(),
+            &workspace,
+            "db",
+            |overrides| {
+                overrides.args = Some(vec!["--db".to_string(), "local".to_string()]);
+                overrides.env.insert(
+                    "DATABASE_URL".to_string(),
+                    "postgres://localhost".to_string(),
+                );
+                overrides.startup_timeout_sec = Some(30.0);
+            },
+        )?;
+        crate::workspace_state::persist_mcp_enabled(codex_home.path(), &workspace, "docs", false)?;
+
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[mcp_servers.db]
+command = "db-mcp"
+args = ["--db", "prod"]
+env = { LOG_LEVEL = "info", DATABASE_URL = "postgres://prod" }
+
+[mcp_servers.docs]
+url = "https://docs.example.com/mcp"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let overrides = ConfigOverrides {
+            cwd: Some(workspace),
+            ..Default::default()
+        };
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            overrides,
+            codex_home.path().to_path_buf(),
+        )?;
+
+        let db = &config.mcp_servers["db"];
+        assert_eq!(
+            db.transport,
+            McpServerTransportConfig::Stdio {
+                command: "db-mcp".to_string(),
+                args: vec!["--db".to_string(), "local".to_string()],
+                env: Some(HashMap::from([
+                    ("LOG_LEVEL".to_string(), "info".to_string()),
+                    (
+                        "DATABASE_URL".to_string(),
+                        "postgres://localhost".to_string()
+                    ),
+                ])),
+                env_vars: Vec::new(),
+                cwd: None,
+            }
+        );
+        assert_eq!(db.startup_timeout_sec, Some(Duration::from_secs(30)));
+        assert!(db.enabled);
+        assert!(!config.mcp_servers["docs"].enabled);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite-workspace-state")]
+    #[test]
+    fn sqlite_workspace_state_backend_migrates_on_load() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let workspace = codex_home.path().join("workspace");
+        std::fs::create_dir_all(&workspace)?;
+        crate::workspace_state::persist_env_var(
+            codex_home.path(),
+            &workspace,
+            "AWS_PROFILE",
+            Some("staging"),
+        )?;
+
+        let cfg: ConfigToml = toml::from_str(r#"workspace_state_backend = "sqlite""#)
+            .expect("TOML deserialization should succeed");
+        let overrides = ConfigOverrides {
+            cwd: Some(workspace.clone()),
+            ..Default::default()
+        };
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            overrides,
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.workspace_state_backend,
+            WorkspaceStateBackend::Sqlite
+        );
+        assert_eq!(
+            crate::workspace_state::workspace_state_location(codex_home.path(), &workspace),
+            codex_home.path().join("workspace_state.sqlite")
+        );
+        assert_eq!(
+            config.shell_environment_policy.r#set.get("AWS_PROFILE"),
+            Some(&"staging".to_string())
+        );
+
+        Ok(())
+    }
+
     fn create_test_fixture() -> std::io::Result<PrecedenceTestFixture> {
         let toml = r#"
 model = "o3"
@@ -2964,6 +3679,7 @@ model_verbosity = "high"
             stream_max_retries: Some(10),
             stream_idle_timeout_ms: Some(300_000),
             requires_openai_auth: false,
+            code88_auth: false,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -3035,6 +3751,10 @@ model_verbosity = "high"
                 mcp_servers: HashMap::new(),
                 mcp_oauth_credentials_store_mode: Default::default(),
                 model_providers: fixture.model_provider_map.clone(),
+                model_prices: HashMap::new(),
+                budget: BudgetConfig::default(),
+                workspace_budget: None,
+                workspace_state_backend: WorkspaceStateBackend::Toml,
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
@@ -3062,16 +3782,30 @@ model_verbosity = "high"
                 use_experimental_use_rmcp_client: false,
                 features: Features::with_defaults(),
                 active_profile: Some("o3".to_string()),
+                cli_overrides: Vec::new(),
                 active_project: ProjectConfig { trust_level: None },
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 check_for_update_on_startup: true,
+                check_for_release_on_startup: false,
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
                 animations: true,
                 show_tooltips: true,
                 tui_custom_statusline: true,
+                tui_terminal_progress: TerminalProgressMode::Off,
+                tui_terminal_title: false,
+                tui_rate_limit_history: false,
+                tui_accessible: false,
+                tui_token_format: TokenCountFormat::default(),
+                tui_spinner: SpinnerSettings::default(),
+                tui_devspaces: Devspaces::default(),
+                tui_statusline: StatusLineSettings::default(),
                 tui_code88_api_key: None,
+                code88_token: None,
+                code88_browser: Default::default(),
+                code88_usage_cache_ttl: std::time::Duration::from_secs(10),
+                code88_usage_cache_file: false,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3112,6 +3846,10 @@ model_verbosity = "high"
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
+            model_prices: HashMap::new(),
+            budget: BudgetConfig::default(),
+            workspace_budget: None,
+            workspace_state_backend: WorkspaceStateBackend::Toml,
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
@@ -3139,16 +3877,30 @@ model_verbosity = "high"
             use_experimental_use_rmcp_client: false,
             features: Features::with_defaults(),
             active_profile: Some("gpt3".to_string()),
+            cli_overrides: Vec::new(),
             active_project: ProjectConfig { trust_level: None },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            check_for_release_on_startup: false,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
             show_tooltips: true,
             tui_custom_statusline: true,
+            tui_terminal_progress: TerminalProgressMode::Off,
+            tui_terminal_title: false,
+            tui_rate_limit_history: false,
+            tui_accessible: false,
+            tui_token_format: TokenCountFormat::default(),
+            tui_spinner: SpinnerSettings::default(),
+            tui_devspaces: Devspaces::default(),
+            tui_statusline: StatusLineSettings::default(),
             tui_code88_api_key: None,
+            code88_token: None,
+            code88_browser: Default::default(),
+            code88_usage_cache_ttl: std::time::Duration::from_secs(10),
+            code88_usage_cache_file: false,
             otel: OtelConfig::default(),
         };
 
@@ -3204,6 +3956,10 @@ model_verbosity = "high"
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
+            model_prices: HashMap::new(),
+            budget: BudgetConfig::default(),
+            workspace_budget: None,
+            workspace_state_backend: WorkspaceStateBackend::Toml,
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
@@ -3231,16 +3987,30 @@ model_verbosity = "high"
             use_experimental_use_rmcp_client: false,
             features: Features::with_defaults(),
             active_profile: Some("zdr".to_string()),
+            cli_overrides: Vec::new(),
             active_project: ProjectConfig { trust_level: None },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            check_for_release_on_startup: false,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
             show_tooltips: true,
             tui_custom_statusline: true,
+            tui_terminal_progress: TerminalProgressMode::Off,
+            tui_terminal_title: false,
+            tui_rate_limit_history: false,
+            tui_accessible: false,
+            tui_token_format: TokenCountFormat::default(),
+            tui_spinner: SpinnerSettings::default(),
+            tui_devspaces: Devspaces::default(),
+            tui_statusline: StatusLineSettings::default(),
             tui_code88_api_key: None,
+            code88_token: None,
+            code88_browser: Default::default(),
+            code88_usage_cache_ttl: std::time::Duration::from_secs(10),
+            code88_usage_cache_file: false,
             otel: OtelConfig::default(),
         };
 
@@ -3282,6 +4052,10 @@ model_verbosity = "high"
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
+            model_prices: HashMap::new(),
+            budget: BudgetConfig::default(),
+            workspace_budget: None,
+            workspace_state_backend: WorkspaceStateBackend::Toml,
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
@@ -3309,16 +4083,30 @@ model_verbosity = "high"
             use_experimental_use_rmcp_client: false,
             features: Features::with_defaults(),
             active_profile: Some("gpt5".to_string()),
+            cli_overrides: Vec::new(),
             active_project: ProjectConfig { trust_level: None },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            check_for_release_on_startup: false,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
             show_tooltips: true,
             tui_custom_statusline: true,
+            tui_terminal_progress: TerminalProgressMode::Off,
+            tui_terminal_title: false,
+            tui_rate_limit_history: false,
+            tui_accessible: false,
+            tui_token_format: TokenCountFormat::default(),
+            tui_spinner: SpinnerSettings::default(),
+            tui_devspaces: Devspaces::default(),
+            tui_statusline: StatusLineSettings::default(),
             tui_code88_api_key: None,
+            code88_token: None,
+            code88_browser: Default::default(),
+            code88_usage_cache_ttl: std::time::Duration::from_secs(10),
+            code88_usage_cache_file: false,
             otel: OtelConfig::default(),
         };
 
@@ -3486,7 +4274,7 @@ trust_level = "untrusted"
         let cfg = toml::from_str::<ConfigToml>(config_with_untrusted)
             .expect("TOML deserialization should succeed");
 
-        let resolution = cfg.derive_sandbox_policy(None, None, &PathBuf::from("/tmp/test"));
+        let resolution = cfg.derive_sandbox_policy(None, None, None, &PathBuf::from("/tmp/test"));
 
         // Verify that untrusted projects get WorkspaceWrite (or ReadOnly on Windows due to downgrade)
         if cfg!(target_os = "windows") {
@@ -3639,8 +4427,10 @@ trust_level = "untrusted"
 
 #[cfg(test)]
 mod notifications_tests {
+    use crate::config::types::NotificationChannels;
     use crate::config::types::Notifications;
     use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
     use serde::Deserialize;
 
     #[derive(Deserialize, Debug, PartialEq)]
@@ -3676,4 +4466,23 @@ mod notifications_tests {
             Notifications::Custom(ref v) if v == &vec!["foo".to_string()]
         );
     }
+
+    #[test]
+    fn test_tui_notifications_channels_table() {
+        let toml = r#"
+            [tui.notifications]
+            bell = true
+            osc777 = true
+        "#;
+        let parsed: RootTomlTest =
+            toml::from_str(toml).expect("deserialize [tui.notifications] table");
+        assert_eq!(
+            parsed.tui.notifications,
+            Notifications::Channels(NotificationChannels {
+                bell: true,
+                osc777: true,
+                desktop: true,
+            })
+        );
+    }
 }
@@ -4,6 +4,7 @@
 // definitions that do not contain business logic.
 
 use serde::Deserializer;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -271,6 +272,48 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// Where per-workspace state is stored under `CODEX_HOME`.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkspaceStateBackend {
+    /// One TOML file per workspace in `workspace_state/`.
+    #[default]
+    Toml,
+    /// A single `workspace_state.sqlite` database.
+    Sqlite,
+}
+
+/// Token prices for one model, in USD per million tokens.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+
+    /// Price for cached input tokens; defaults to `input` when unset.
+    #[serde(default)]
+    pub cached_input: Option<f64>,
+
+    pub output: f64,
+}
+
+/// Spending limits from `[budget]`, or saved for a workspace. Codex warns
+/// when 80% of a limit is used and asks before going past it.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Default, PartialEq)]
+pub struct BudgetConfig {
+    /// Estimated cost in USD, priced from `[model_prices]`.
+    #[serde(default)]
+    pub max_usd: Option<f64>,
+
+    /// Input plus output tokens.
+    #[serde(default)]
+    pub max_tokens: Option<i64>,
+}
+
+impl BudgetConfig {
+    pub fn is_empty(&self) -> bool {
+        self.max_usd.is_none() && self.max_tokens.is_none()
+    }
+}
+
 // ===== OTEL configuration =====
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -312,6 +355,32 @@ pub enum OtelExporterKind {
     },
 }
 
+/// `[code88]`: options for the 88code browser login and usage API.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Code88Toml {
+    /// Extra Chromium flags appended when the browser login launches Chrome
+    /// or Edge, e.g. `--proxy-server=...`.
+    #[serde(default)]
+    pub browser_args: Vec<String>,
+
+    /// Reopen the signed-in browser, without remote debugging, after the
+    /// token is captured.
+    #[serde(default)]
+    pub keep_browser_open: bool,
+
+    /// Page the browser reopened by `keep_browser_open` shows.
+    pub success_url: Option<String>,
+
+    /// How long a usage response is reused before the API is asked again.
+    /// Defaults to 10 seconds; `0` turns the cache off.
+    pub usage_cache_ttl_secs: Option<i64>,
+
+    /// Also keep the usage cache in `CODEX_HOME/88code-usage-cache.json`,
+    /// so separate Codex processes share it.
+    #[serde(default)]
+    pub usage_cache_file: bool,
+}
+
 /// OTEL settings loaded from config.toml. Fields are optional so we can apply defaults.
 #[derive(Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct OtelConfigToml {
@@ -348,6 +417,26 @@ impl Default for OtelConfig {
 pub enum Notifications {
     Enabled(bool),
     Custom(Vec<String>),
+    Channels(NotificationChannels),
+}
+
+/// Per-channel notification settings, configured as a `[tui.notifications]`
+/// table. When set, a finished task is reported with its run label and
+/// elapsed time on every enabled channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct NotificationChannels {
+    /// Ring the terminal bell (BEL).
+    #[serde(default)]
+    pub bell: bool,
+
+    /// Post an OSC 777 notification (urxvt, foot, Ghostty, WezTerm).
+    #[serde(default)]
+    pub osc777: bool,
+
+    /// Post an OSC 9 desktop notification.
+    /// Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub desktop: bool,
 }
 
 impl Default for Notifications {
@@ -377,6 +466,1020 @@ pub struct Tui {
     /// Enable the custom Codex status line presentation.
     #[serde(default = "Tui::default_custom_statusline")]
     pub custom_statusline: bool,
+
+    /// Report task progress to the terminal with OSC 9;4 so it stays visible
+    /// in the tab or taskbar while the window is unfocused.
+    /// Defaults to `off`.
+    #[serde(default)]
+    pub terminal_progress: TerminalProgressMode,
+
+    /// Mirror the current run state and elapsed time into the terminal
+    /// title, restoring the previous title on exit.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub terminal_title: bool,
+
+    /// Keep each session's rate-limit history under
+    /// `CODEX_HOME/rate_limit_history` so `/status` still shows the trend
+    /// after resuming. History is always kept in memory.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub rate_limit_history: bool,
+
+    /// Screen-reader friendly mode: the status line becomes plain
+    /// comma-separated text without icons or colors, and spinners and other
+    /// animations are turned off.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub accessible: bool,
+
+    /// How token counts are written in the status line, the footer and
+    /// `/status`.
+    #[serde(default)]
+    pub token_format: TokenCountFormat,
+
+    /// The spinner shown while commands and turns run.
+    #[serde(default)]
+    pub spinner: SpinnerSettings,
+
+    /// Devspaces known to the status line, keyed by name, with the icon,
+    /// color and description to show for each.
+    #[serde(default)]
+    pub devspaces: Devspaces,
+
+    /// Rendering options for the custom status line.
+    #[serde(default)]
+    pub statusline: StatusLineSettings,
+}
+
+/// Options for the custom status line, configured under `[tui.statusline]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StatusLineSettings {
+    /// Percentage of context window remaining below which the context bar
+    /// pulses red and suggests compacting. `0` disables the warning.
+    /// Defaults to `10`.
+    #[serde(default = "StatusLineSettings::default_context_warning_threshold")]
+    pub context_warning_threshold: i64,
+
+    /// Percentage of the context window used at which the context area
+    /// suggests pressing ctrl+k to compact. `0` disables the hint.
+    /// Defaults to `85`.
+    #[serde(default = "StatusLineSettings::default_compact_hint_threshold")]
+    pub compact_hint_threshold: i64,
+
+    /// Tokens that are always present in the context (system prompt, tool
+    /// definitions) and excluded from the context percentage. Set to `0` to
+    /// compute the percentage from the raw window. Windows no larger than the
+    /// baseline always use the raw window.
+    /// Defaults to `12000`.
+    #[serde(default = "StatusLineSettings::default_context_baseline_tokens")]
+    pub context_baseline_tokens: i64,
+
+    /// Name of the skin used to draw the status line: a built-in one
+    /// (`powerline`, `minimal-plain`, `compact-single-capsule`,
+    /// `verbose-two-line`) or one registered by an embedding application.
+    /// Can be switched live with `/statusline`. Defaults to `powerline`.
+    #[serde(default = "StatusLineSettings::default_skin")]
+    pub skin: String,
+
+    /// Format string such as `"{path} {model} {fill}{git}"` that draws the
+    /// status line as text instead of the skin. Unknown `{tokens}` are
+    /// rejected when the config is loaded.
+    #[serde(default)]
+    pub format: Option<StatusLineFormat>,
+
+    /// Colors the status line may use. `auto` follows `NO_COLOR` and what
+    /// the terminal reports. Defaults to `auto`.
+    #[serde(default)]
+    pub color: StatusLineColorMode,
+
+    /// Path to a Rhai script that renders the status line instead of the
+    /// built-in skin. Relative paths resolve against `CODEX_HOME`. The
+    /// configured skin is used whenever the script fails.
+    #[serde(default)]
+    pub script: Option<PathBuf>,
+
+    /// Time budget, in milliseconds, for each call into the status line
+    /// script. Defaults to `5`.
+    #[serde(default = "StatusLineSettings::default_script_timeout_ms")]
+    pub script_timeout_ms: i64,
+
+    /// Maximum width, in terminal columns, of the command, tool, or subject
+    /// shown in the run capsule. Defaults to `40`.
+    #[serde(default = "StatusLineSettings::default_run_label_max_width")]
+    pub run_label_max_width: i64,
+
+    /// Scroll run labels that exceed `run_label_max_width` instead of
+    /// truncating them. Defaults to `false`.
+    #[serde(default)]
+    pub run_label_marquee: bool,
+
+    /// Run label while a command executes. `{command}` expands to the full
+    /// command line and `{binary}` to the program name.
+    /// Defaults to `Running {command}`.
+    #[serde(default = "StatusLineSettings::default_exec_label")]
+    pub exec_label: String,
+
+    /// Run label while an MCP tool runs. `{tool}` expands to `server:tool`,
+    /// `{server}` and `{name}` to its parts.
+    /// Defaults to `Running tool {tool}`.
+    #[serde(default = "StatusLineSettings::default_tool_label")]
+    pub tool_label: String,
+
+    /// Run label while waiting for approval. `{subject}` expands to
+    /// `command` or `patch`.
+    /// Defaults to `Awaiting approval for {subject}`.
+    #[serde(default = "StatusLineSettings::default_approval_label")]
+    pub approval_label: String,
+
+    /// Seconds each queued message stays in the run capsule preview before
+    /// the next one is shown. `0` always previews the first queued message.
+    /// Defaults to `0`.
+    #[serde(default)]
+    pub queue_preview_cycle_secs: i64,
+
+    /// Queued messages listed, dim and comma-separated, in the spare middle
+    /// of the status line while the context bar is hidden or compact. `0`
+    /// keeps the middle blank. Defaults to `0`.
+    #[serde(default)]
+    pub middle_queue_max: i64,
+
+    /// How run timers are written: `compact` (`1m 02s`), `mm:ss` or
+    /// `hh:mm:ss`. Defaults to `compact`.
+    #[serde(default)]
+    pub timer_format: StatusLineTimerFormat,
+
+    /// Seconds a run must last before its timer appears, so quick
+    /// operations do not flash one. `0` always shows it. Defaults to `0`.
+    #[serde(default)]
+    pub timer_min_secs: i64,
+
+    /// strftime format of the clock segment. The segment refreshes once a
+    /// minute, so seconds are not useful here. Defaults to `%H:%M`.
+    #[serde(default = "StatusLineSettings::default_clock_format")]
+    pub clock_format: String,
+
+    /// Whether the clock segment shows the time of day or how long ago the
+    /// session started. Defaults to `time`.
+    #[serde(default)]
+    pub clock_style: StatusLineClockStyle,
+
+    /// Maximum width, in characters, of the full path segment. Longer paths
+    /// keep their start and end and lose the middle. Defaults to `40`.
+    #[serde(default = "StatusLineSettings::default_path_max_width")]
+    pub path_max_width: i64,
+
+    /// Widest each of the other text segments may get before its text is
+    /// cut with `…`.
+    #[serde(default)]
+    pub max_widths: StatusLineMaxWidths,
+
+    /// Number of trailing directories the path segment shows in full. The
+    /// ones before them collapse into `…`, or are abbreviated when
+    /// `path_fish_style_length` is set. `0` keeps every directory.
+    /// Defaults to `0`.
+    #[serde(default)]
+    pub path_truncation_length: i64,
+
+    /// Abbreviate collapsed directories to this many characters, fish-style
+    /// (`~/w/m/s/pkg`), instead of dropping them. `0` turns this off.
+    /// Defaults to `0`.
+    #[serde(default)]
+    pub path_fish_style_length: i64,
+
+    /// Also abbreviate the trailing directories, all but the last one.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub path_fish_style_tail: bool,
+
+    /// Branch the git segment counts commits ahead of, shown as `main↟12`.
+    /// An empty string turns this off. Defaults to `origin/main`.
+    #[serde(default = "StatusLineSettings::default_git_base_branch")]
+    pub git_base_branch: String,
+
+    /// Seconds between background re-scans of the environment segments
+    /// (devspace, hostname, AWS, Nix/direnv, Kubernetes, Terraform/Pulumi),
+    /// so a context switched outside Codex still shows up. `0` scans only
+    /// at startup, after each turn and on `/refresh-status`.
+    /// Defaults to `60`.
+    #[serde(default = "StatusLineSettings::default_environment_refresh_secs")]
+    pub environment_refresh_secs: i64,
+
+    /// Seconds between HEAD requests to the model provider's base URL while
+    /// a task runs. When one fails or times out the run pill shows a red
+    /// `offline` chip until a later probe gets through. `0` turns the probe
+    /// off. At most one hour. Defaults to `0`.
+    #[serde(default)]
+    pub reachability_probe_secs: i64,
+
+    /// Write Prometheus text-format metrics for the session (tokens per
+    /// model, turns, task durations, 88code credits) to this file after
+    /// every task, for node_exporter's textfile collector. Relative paths
+    /// resolve against `CODEX_HOME`. Unset by default.
+    #[serde(default)]
+    pub metrics_file: Option<PathBuf>,
+
+    /// Append every task start, run label change, pause, failed command and
+    /// completion to `timeline-<session id>.jsonl` next to the session's
+    /// rollout file, printed by `codex sessions timeline <id>`.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub session_timeline: bool,
+
+    /// Listen on a local socket (`CODEX_HOME/statusline/<pid>.sock`, a
+    /// named pipe on Windows) that streams run state changes and lets
+    /// external tools set the `custom` segment.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub control_socket: bool,
+
+    /// Re-read `[tui.statusline]` from `config.toml` whenever the file
+    /// changes and apply it without restarting the TUI. `control_socket`
+    /// still needs a restart.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub hot_reload: bool,
+
+    /// Seconds the run pill recaps a finished task, e.g.
+    /// `✓ Applied patch in 2m 05s · 3.10K tokens`, before returning to the
+    /// idle message; an interrupted task shows `⏹` instead of `✓`.
+    /// `0` returns immediately. Defaults to `5`.
+    #[serde(default = "StatusLineSettings::default_run_summary_secs")]
+    pub run_summary_secs: i64,
+
+    /// Segments to show; all but `diff_stats`, `clock`, `process` and `build`
+    /// are on by default.
+    #[serde(default)]
+    pub segments: StatusLineSegments,
+
+    /// Short labels and colors shown in place of matching hostnames, keyed
+    /// by hostname or `*` / `?` pattern.
+    #[serde(default)]
+    pub hosts: StatusLineHosts,
+
+    /// Icons and colors for the model segment, keyed by model name or `*` /
+    /// `?` pattern. Models without an entry get an icon picked from their
+    /// name.
+    #[serde(default)]
+    pub models: StatusLineModels,
+
+    /// Side and order overrides for individual segments of the `powerline`
+    /// skin.
+    #[serde(default)]
+    pub layout: StatusLineSegmentLayout,
+}
+
+impl StatusLineSettings {
+    const fn default_context_warning_threshold() -> i64 {
+        10
+    }
+
+    const fn default_compact_hint_threshold() -> i64 {
+        85
+    }
+
+    const fn default_context_baseline_tokens() -> i64 {
+        12_000
+    }
+
+    fn default_skin() -> String {
+        "powerline".to_string()
+    }
+
+    const fn default_script_timeout_ms() -> i64 {
+        5
+    }
+
+    const fn default_run_label_max_width() -> i64 {
+        40
+    }
+
+    fn default_exec_label() -> String {
+        "Running {command}".to_string()
+    }
+
+    fn default_tool_label() -> String {
+        "Running tool {tool}".to_string()
+    }
+
+    fn default_approval_label() -> String {
+        "Awaiting approval for {subject}".to_string()
+    }
+
+    fn default_clock_format() -> String {
+        "%H:%M".to_string()
+    }
+
+    const fn default_path_max_width() -> i64 {
+        40
+    }
+
+    fn default_git_base_branch() -> String {
+        "origin/main".to_string()
+    }
+
+    const fn default_environment_refresh_secs() -> i64 {
+        60
+    }
+
+    const fn default_run_summary_secs() -> i64 {
+        5
+    }
+
+    /// Reject numbers outside the range each setting accepts; checked when
+    /// the config is loaded and when it is hot-reloaded.
+    pub fn validate(&self) -> Result<(), String> {
+        let out_of_range: Vec<String> = [
+            (
+                "context_warning_threshold",
+                self.context_warning_threshold,
+                0..=100,
+            ),
+            (
+                "compact_hint_threshold",
+                self.compact_hint_threshold,
+                0..=100,
+            ),
+            ("script_timeout_ms", self.script_timeout_ms, 1..=1_000),
+            ("run_label_max_width", self.run_label_max_width, 4..=200),
+            (
+                "queue_preview_cycle_secs",
+                self.queue_preview_cycle_secs,
+                0..=3_600,
+            ),
+            ("timer_min_secs", self.timer_min_secs, 0..=3_600),
+            ("path_max_width", self.path_max_width, 8..=200),
+            (
+                "path_truncation_length",
+                self.path_truncation_length,
+                0..=32,
+            ),
+            (
+                "path_fish_style_length",
+                self.path_fish_style_length,
+                0..=32,
+            ),
+            (
+                "environment_refresh_secs",
+                self.environment_refresh_secs,
+                0..=86_400,
+            ),
+            ("run_summary_secs", self.run_summary_secs, 0..=3_600),
+            ("middle_queue_max", self.middle_queue_max, 0..=32),
+            (
+                "reachability_probe_secs",
+                self.reachability_probe_secs,
+                0..=3_600,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, value, range)| !range.contains(value))
+        .map(|(name, value, range)| {
+            format!(
+                "`{name} = {value}` (expected {} to {})",
+                range.start(),
+                range.end()
+            )
+        })
+        .collect();
+        if out_of_range.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "[tui.statusline] values out of range: {}",
+                out_of_range.join(", ")
+            ))
+        }
+    }
+}
+
+impl Default for StatusLineSettings {
+    fn default() -> Self {
+        Self {
+            context_warning_threshold: Self::default_context_warning_threshold(),
+            compact_hint_threshold: Self::default_compact_hint_threshold(),
+            context_baseline_tokens: Self::default_context_baseline_tokens(),
+            skin: Self::default_skin(),
+            format: None,
+            color: StatusLineColorMode::default(),
+            script: None,
+            script_timeout_ms: Self::default_script_timeout_ms(),
+            run_label_max_width: Self::default_run_label_max_width(),
+            run_label_marquee: false,
+            exec_label: Self::default_exec_label(),
+            tool_label: Self::default_tool_label(),
+            approval_label: Self::default_approval_label(),
+            queue_preview_cycle_secs: 0,
+            middle_queue_max: 0,
+            timer_format: StatusLineTimerFormat::default(),
+            timer_min_secs: 0,
+            clock_format: Self::default_clock_format(),
+            clock_style: StatusLineClockStyle::default(),
+            path_max_width: Self::default_path_max_width(),
+            max_widths: StatusLineMaxWidths::default(),
+            path_truncation_length: 0,
+            path_fish_style_length: 0,
+            path_fish_style_tail: false,
+            git_base_branch: Self::default_git_base_branch(),
+            environment_refresh_secs: Self::default_environment_refresh_secs(),
+            reachability_probe_secs: 0,
+            metrics_file: None,
+            session_timeline: false,
+            control_socket: false,
+            hot_reload: false,
+            run_summary_secs: Self::default_run_summary_secs(),
+            segments: StatusLineSegments::default(),
+            hosts: StatusLineHosts::default(),
+            models: StatusLineModels::default(),
+            layout: StatusLineSegmentLayout::default(),
+        }
+    }
+}
+
+/// `[tui.statusline.max_widths]`: columns each segment's text may take,
+/// checked against [`StatusLineMaxWidths::MIN`] and
+/// [`StatusLineMaxWidths::MAX`] when the config is loaded.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "StatusLineMaxWidthsToml")]
+pub struct StatusLineMaxWidths {
+    pub provider: i64,
+    pub custom: i64,
+    pub attention: i64,
+    pub devspace: i64,
+    pub hostname: i64,
+    pub git: i64,
+    pub aws_profile: i64,
+    pub iac: i64,
+    pub kubernetes: i64,
+}
+
+impl StatusLineMaxWidths {
+    /// Room for an icon, one character and the ellipsis.
+    pub const MIN: i64 = 4;
+    pub const MAX: i64 = 200;
+}
+
+impl Default for StatusLineMaxWidths {
+    fn default() -> Self {
+        Self {
+            provider: 16,
+            custom: 32,
+            attention: 32,
+            devspace: 16,
+            hostname: 20,
+            git: 24,
+            aws_profile: 16,
+            iac: 16,
+            kubernetes: 18,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct StatusLineMaxWidthsToml {
+    provider: Option<i64>,
+    custom: Option<i64>,
+    attention: Option<i64>,
+    devspace: Option<i64>,
+    hostname: Option<i64>,
+    git: Option<i64>,
+    aws_profile: Option<i64>,
+    iac: Option<i64>,
+    kubernetes: Option<i64>,
+}
+
+impl TryFrom<StatusLineMaxWidthsToml> for StatusLineMaxWidths {
+    type Error = String;
+
+    fn try_from(toml: StatusLineMaxWidthsToml) -> Result<Self, Self::Error> {
+        let defaults = Self::default();
+        let mut out_of_range = Vec::new();
+        let mut width = |name: &str, value: Option<i64>, default: i64| match value {
+            Some(value) if !(Self::MIN..=Self::MAX).contains(&value) => {
+                out_of_range.push(format!("`{name} = {value}`"));
+                default
+            }
+            value => value.unwrap_or(default),
+        };
+        let widths = Self {
+            provider: width("provider", toml.provider, defaults.provider),
+            custom: width("custom", toml.custom, defaults.custom),
+            attention: width("attention", toml.attention, defaults.attention),
+            devspace: width("devspace", toml.devspace, defaults.devspace),
+            hostname: width("hostname", toml.hostname, defaults.hostname),
+            git: width("git", toml.git, defaults.git),
+            aws_profile: width("aws_profile", toml.aws_profile, defaults.aws_profile),
+            iac: width("iac", toml.iac, defaults.iac),
+            kubernetes: width("kubernetes", toml.kubernetes, defaults.kubernetes),
+        };
+        if out_of_range.is_empty() {
+            Ok(widths)
+        } else {
+            Err(format!(
+                "status line max widths must be between {} and {}: {}",
+                Self::MIN,
+                Self::MAX,
+                out_of_range.join(", ")
+            ))
+        }
+    }
+}
+
+/// Color depth of the status line, whatever the skin.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusLineColorMode {
+    /// No color when `NO_COLOR` is set, otherwise as many colors as the
+    /// terminal reports.
+    #[default]
+    Auto,
+    /// 24-bit RGB colors.
+    Truecolor,
+    /// The xterm 256-color palette.
+    #[serde(rename = "256")]
+    Ansi256,
+    /// The 16 ANSI colors, as themed by the terminal.
+    #[serde(rename = "16")]
+    Ansi16,
+    /// Monochrome: bold, dim and reverse video only.
+    None,
+}
+
+/// How run timers are written in the status line.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum StatusLineTimerFormat {
+    /// `1m 02s`, growing units as the run gets longer.
+    #[default]
+    #[serde(rename = "compact")]
+    Compact,
+    /// `01:02`; minutes keep counting past the hour.
+    #[serde(rename = "mm:ss")]
+    MinutesSeconds,
+    /// `00:01:02`.
+    #[serde(rename = "hh:mm:ss")]
+    HoursMinutesSeconds,
+}
+
+/// What the clock segment shows.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusLineClockStyle {
+    /// Time of day, formatted with `clock_format`.
+    #[default]
+    Time,
+    /// `started 1h 05m ago`, counted from when the session opened.
+    SessionAge,
+}
+
+/// Devspaces registered under `[tui.devspaces]`, e.g.
+/// `payments = { icon = "󰠖", color = "#89dceb", description = "billing API" }`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(transparent)]
+pub struct Devspaces(BTreeMap<String, DevspaceEntry>);
+
+impl Devspaces {
+    /// The entry registered as `name` and its position among the registered
+    /// devspaces, in name order.
+    pub fn get(&self, name: &str) -> Option<(usize, &DevspaceEntry)> {
+        self.0
+            .iter()
+            .enumerate()
+            .find(|(_, (key, _))| key.as_str() == name)
+            .map(|(index, (_, entry))| (index, entry))
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct DevspaceEntry {
+    /// Glyph shown before the devspace name. Registered devspaces without one
+    /// take the built-in icons in name order.
+    #[serde(default)]
+    pub icon: Option<String>,
+
+    /// Segment color: a color name such as `blue` or a `#rrggbb` value.
+    #[serde(default)]
+    pub color: Option<String>,
+
+    /// What the devspace is for, shown by skins with room for it.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Hostname aliases configured under `[tui.statusline.hosts]`, e.g.
+/// `"prod-bastion-*" = { label = "PROD", color = "red" }`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(transparent)]
+pub struct StatusLineHosts(HashMap<String, StatusLineHostAlias>);
+
+impl StatusLineHosts {
+    /// Alias for `hostname`: an exact key wins, otherwise the longest
+    /// matching pattern.
+    pub fn alias_for(&self, hostname: &str) -> Option<&StatusLineHostAlias> {
+        lookup_pattern(&self.0, hostname)
+    }
+}
+
+type NamePattern = WildMatchPattern<'*', '?'>;
+
+/// The entry keyed by `name`, otherwise the one whose pattern is the longest
+/// case-insensitive match.
+fn lookup_pattern<'a, T>(entries: &'a HashMap<String, T>, name: &str) -> Option<&'a T> {
+    if let Some(entry) = entries.get(name) {
+        return Some(entry);
+    }
+    entries
+        .iter()
+        .filter(|(pattern, _)| NamePattern::new_case_insensitive(pattern).matches(name))
+        .max_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| b.cmp(a)))
+        .map(|(_, entry)| entry)
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StatusLineHostAlias {
+    /// Text shown instead of the hostname.
+    pub label: String,
+
+    /// Segment color: a color name such as `red` or a `#rrggbb` value.
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Model icons and colors configured under `[tui.statusline.models]`, e.g.
+/// `"o*" = { icon = "󰧑", color = "magenta" }`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(transparent)]
+pub struct StatusLineModels(HashMap<String, StatusLineModelStyle>);
+
+impl StatusLineModels {
+    /// Style for `model`: an exact key wins, otherwise the longest matching
+    /// pattern.
+    pub fn style_for(&self, model: &str) -> Option<&StatusLineModelStyle> {
+        lookup_pattern(&self.0, model)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct StatusLineModelStyle {
+    /// Glyph shown before the model name instead of the picked one.
+    #[serde(default)]
+    pub icon: Option<String>,
+
+    /// Segment color: a color name such as `green` or a `#rrggbb` value.
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Where segments sit on the status line, configured under
+/// `[tui.statusline.layout]`, e.g. `git = { side = "left", priority = 25 }`.
+/// Segments without an entry keep their default side and priority.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(transparent)]
+pub struct StatusLineSegmentLayout(HashMap<StatusLineSegmentId, StatusLineSegmentPlacement>);
+
+impl StatusLineSegmentLayout {
+    pub fn placement(&self, segment: StatusLineSegmentId) -> Option<StatusLineSegmentPlacement> {
+        self.0.get(&segment).copied()
+    }
+}
+
+/// Status line segments whose side can be configured. Run state segments
+/// always lead the left side.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusLineSegmentId {
+    Path,
+    Model,
+    Provider,
+    DiffStats,
+    Custom,
+    Attention,
+    Budget,
+    Policy,
+    DevEnv,
+    Devspace,
+    Hostname,
+    Git,
+    AwsProfile,
+    Iac,
+    Kubernetes,
+    Process,
+    Clock,
+    Session,
+    Build,
+}
+
+impl StatusLineSegmentId {
+    /// Config names of every segment, in default display order.
+    pub const NAMES: &[&str] = &[
+        "path",
+        "model",
+        "provider",
+        "diff_stats",
+        "custom",
+        "attention",
+        "budget",
+        "policy",
+        "dev_env",
+        "devspace",
+        "hostname",
+        "git",
+        "aws_profile",
+        "iac",
+        "kubernetes",
+        "process",
+        "clock",
+        "session",
+        "build",
+    ];
+}
+
+/// Placeholder names accepted by `tui.statusline.format`, besides the
+/// segment names.
+const STATUS_LINE_FORMAT_EXTRA_TOKENS: &[&str] = &["context", "fill"];
+
+/// Parsed `tui.statusline.format`: literal text interleaved with segment
+/// placeholders. `{{` and `}}` stand for literal braces.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub struct StatusLineFormat(Vec<StatusLineFormatItem>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusLineFormatItem {
+    Text(String),
+    Segment(StatusLineSegmentId),
+    /// Context window usage, drawn in the middle area by the skins.
+    Context,
+    /// Padding that pushes everything after it to the right edge.
+    Fill,
+}
+
+impl StatusLineFormat {
+    pub fn items(&self) -> &[StatusLineFormatItem] {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for StatusLineFormat {
+    type Error = String;
+
+    fn try_from(format: String) -> Result<Self, Self::Error> {
+        let mut items = Vec::new();
+        let mut text = String::new();
+        let mut unknown = Vec::new();
+        let mut chars = format.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err("unmatched `}` in status line format; write `}}`".to_string()),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(ch) => name.push(ch),
+                            None => {
+                                return Err(format!(
+                                    "unclosed `{{{name}` in status line format; write `{{{{` for a literal brace"
+                                ));
+                            }
+                        }
+                    }
+                    if !text.is_empty() {
+                        items.push(StatusLineFormatItem::Text(std::mem::take(&mut text)));
+                    }
+                    let item = match name.trim() {
+                        "context" => StatusLineFormatItem::Context,
+                        "fill" => StatusLineFormatItem::Fill,
+                        segment => {
+                            match StatusLineSegmentId::deserialize(
+                                serde::de::value::StrDeserializer::<serde::de::value::Error>::new(
+                                    segment,
+                                ),
+                            ) {
+                                Ok(id) => StatusLineFormatItem::Segment(id),
+                                Err(_) => {
+                                    unknown.push(format!("`{{{name}}}`"));
+                                    continue;
+                                }
+                            }
+                        }
+                    };
+                    items.push(item);
+                }
+                ch => text.push(ch),
+            }
+        }
+        if !text.is_empty() {
+            items.push(StatusLineFormatItem::Text(text));
+        }
+        if !unknown.is_empty() {
+            let known = StatusLineSegmentId::NAMES
+                .iter()
+                .chain(STATUS_LINE_FORMAT_EXTRA_TOKENS)
+                .copied()
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(format!(
+                "unknown status line format token {}; expected one of: {known}",
+                unknown.join(", ")
+            ));
+        }
+        let fills = items
+            .iter()
+            .filter(|item| **item == StatusLineFormatItem::Fill)
+            .count();
+        if fills > 1 {
+            return Err("`{fill}` may appear only once in the status line format".to_string());
+        }
+        Ok(Self(items))
+    }
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct StatusLineSegmentPlacement {
+    /// Side of the status line the segment sits on.
+    #[serde(default)]
+    pub side: Option<StatusLineSide>,
+
+    /// Position within its side; lower values sit further left.
+    #[serde(default)]
+    pub priority: Option<i32>,
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusLineSide {
+    Left,
+    Right,
+}
+
+/// Status line segments that can be switched on or off, configured under
+/// `[tui.statusline.segments]`. A workspace can override any of them.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusLineSegments {
+    #[serde(default = "default_true")]
+    pub path: bool,
+    #[serde(default = "default_true")]
+    pub model: bool,
+    #[serde(default = "default_true")]
+    pub provider: bool,
+    /// Lines added and removed by patches applied this session. Off by
+    /// default.
+    #[serde(default)]
+    pub diff_stats: bool,
+    /// Text pushed by external tools over the control socket.
+    #[serde(default = "default_true")]
+    pub custom: bool,
+    /// Warnings raised this session, with a count badge.
+    #[serde(default = "default_true")]
+    pub attention: bool,
+    /// Share of the session or workspace budget used, as a bar. Only shown
+    /// while a budget is set.
+    #[serde(default = "default_true")]
+    pub budget: bool,
+    /// Wall clock or session age. Off by default.
+    #[serde(default)]
+    pub clock: bool,
+    /// Memory and CPU use of the codex process. Off by default.
+    #[serde(default)]
+    pub process: bool,
+    /// Last characters of the session id, with the key that copies the full
+    /// id or the resume command.
+    #[serde(default = "default_true")]
+    pub session: bool,
+    /// Version and commit this binary was built from, for bug reports. Off
+    /// by default.
+    #[serde(default)]
+    pub build: bool,
+    #[serde(default = "default_true")]
+    pub context: bool,
+    #[serde(default = "default_true")]
+    pub git: bool,
+    #[serde(default = "default_true")]
+    pub policy: bool,
+    /// Whether commands run inside a Nix shell or a direnv environment.
+    #[serde(default = "default_true")]
+    pub dev_env: bool,
+    #[serde(default = "default_true")]
+    pub devspace: bool,
+    #[serde(default = "default_true")]
+    pub hostname: bool,
+    #[serde(default = "default_true")]
+    pub aws_profile: bool,
+    /// Terraform workspace or Pulumi stack of the working directory.
+    #[serde(default = "default_true")]
+    pub iac: bool,
+    #[serde(default = "default_true")]
+    pub kubernetes: bool,
+    #[serde(default = "default_true")]
+    pub code88: bool,
+}
+
+impl Default for StatusLineSegments {
+    fn default() -> Self {
+        Self {
+            path: true,
+            model: true,
+            provider: true,
+            diff_stats: false,
+            custom: true,
+            attention: true,
+            budget: true,
+            clock: false,
+            process: false,
+            session: true,
+            build: false,
+            context: true,
+            git: true,
+            policy: true,
+            dev_env: true,
+            devspace: true,
+            hostname: true,
+            aws_profile: true,
+            iac: true,
+            kubernetes: true,
+            code88: true,
+        }
+    }
+}
+
+/// `[tui.token_format]`: token counts as `12.3K` or `12,345`, with the
+/// separators of a locale.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TokenCountFormat {
+    #[serde(default)]
+    pub style: TokenCountStyle,
+    /// Language tag choosing the separators, such as `en`, `de` or `fr_CA`.
+    /// Defaults to `LC_ALL`, `LC_NUMERIC` or `LANG`.
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenCountStyle {
+    /// `12.3K`, `1.5M`.
+    #[default]
+    Compact,
+    /// Every digit, grouped: `12,345`.
+    Exact,
+}
+
+/// `[tui.spinner]`: the glyphs the TUI animates while commands and turns
+/// run.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct SpinnerSettings {
+    #[serde(default)]
+    pub frame_set: SpinnerFrameSet,
+    /// Frames to cycle through instead of `frame_set`.
+    #[serde(default)]
+    pub frames: Vec<String>,
+    /// Milliseconds per frame. Defaults to the frame set's own pace, as does
+    /// a value below 1.
+    #[serde(default)]
+    pub interval_ms: Option<i64>,
+    /// Show a static glyph instead of animating.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub reduced_motion: bool,
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpinnerFrameSet {
+    /// A shimmering dot, or `•` blinking to `◦` without true color.
+    #[default]
+    Dot,
+    /// `⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏`
+    Braille,
+    /// `-\|/`
+    Line,
+    /// `◐◓◑◒`
+    Circle,
+    /// `◜◠◝◞◡◟`
+    Arc,
+}
+
+/// What the TUI reports through OSC 9;4 terminal progress sequences.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TerminalProgressMode {
+    /// Never emit progress sequences.
+    #[default]
+    Off,
+    /// Show an indeterminate progress indicator while a task runs.
+    Spinner,
+    /// Show context window usage while a task runs, falling back to the
+    /// indeterminate indicator when the window size is unknown.
+    Context,
 }
 
 const fn default_true() -> bool {
@@ -390,6 +1493,14 @@ impl Default for Tui {
             animations: true,
             show_tooltips: true,
             custom_statusline: Tui::default_custom_statusline(),
+            terminal_progress: TerminalProgressMode::default(),
+            terminal_title: false,
+            rate_limit_history: false,
+            accessible: false,
+            token_format: TokenCountFormat::default(),
+            spinner: SpinnerSettings::default(),
+            devspaces: Devspaces::default(),
+            statusline: StatusLineSettings::default(),
         }
     }
 }
@@ -555,6 +1666,152 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn host_alias_prefers_exact_then_longest_pattern() {
+        let settings: StatusLineSettings = toml::from_str(
+            r##"
+            [hosts]
+            "prod-*" = { label = "PROD", color = "red" }
+            "prod-bastion-*" = { label = "BASTION", color = "#ff0000" }
+            "prod-bastion-3021" = { label = "B3021" }
+        "##,
+        )
+        .expect("should deserialize host aliases");
+
+        let label = |host: &str| {
+            settings
+                .hosts
+                .alias_for(host)
+                .map(|alias| alias.label.as_str())
+        };
+        assert_eq!(label("prod-bastion-3021"), Some("B3021"));
+        assert_eq!(label("PROD-Bastion-7"), Some("BASTION"));
+        assert_eq!(label("prod-db-1"), Some("PROD"));
+        assert_eq!(label("devbox"), None);
+        assert_eq!(
+            settings
+                .hosts
+                .alias_for("prod-db-1")
+                .and_then(|alias| alias.color.as_deref()),
+            Some("red")
+        );
+    }
+
+    #[test]
+    fn model_styles_match_exact_names_then_patterns() {
+        let settings: StatusLineSettings = toml::from_str(
+            r##"
+            [models]
+            "gpt-5*" = { color = "#a6e3a1" }
+            "gpt-5-codex" = { icon = "C", color = "green" }
+            "o*" = { icon = "O" }
+        "##,
+        )
+        .expect("should deserialize model styles");
+
+        let style = |model: &str| settings.models.style_for(model).cloned();
+        assert_eq!(
+            style("gpt-5-codex"),
+            Some(StatusLineModelStyle {
+                icon: Some("C".to_string()),
+                color: Some("green".to_string()),
+            })
+        );
+        assert_eq!(
+            style("GPT-5-mini").and_then(|style| style.color),
+            Some("#a6e3a1".to_string())
+        );
+        assert_eq!(
+            style("o3").and_then(|style| style.icon),
+            Some("O".to_string())
+        );
+        assert_eq!(style("llama3"), None);
+    }
+
+    #[test]
+    fn statusline_color_accepts_numeric_depths() {
+        let color = |value: &str| {
+            toml::from_str::<StatusLineSettings>(&format!("color = \"{value}\""))
+                .map(|settings| settings.color)
+        };
+        assert_eq!(color("auto").ok(), Some(StatusLineColorMode::Auto));
+        assert_eq!(
+            color("truecolor").ok(),
+            Some(StatusLineColorMode::Truecolor)
+        );
+        assert_eq!(color("256").ok(), Some(StatusLineColorMode::Ansi256));
+        assert_eq!(color("16").ok(), Some(StatusLineColorMode::Ansi16));
+        assert_eq!(color("none").ok(), Some(StatusLineColorMode::None));
+        assert!(color("8").is_err());
+    }
+
+    #[test]
+    fn statusline_format_parses_placeholders_and_rejects_unknown_tokens() {
+        let format = |value: &str| {
+            toml::from_str::<StatusLineSettings>(&format!("format = '{value}'"))
+                .map(|settings| settings.format.expect("format set"))
+        };
+        assert_eq!(
+            format("{path} {{{model}}}{fill}{git}")
+                .expect("valid format")
+                .items(),
+            [
+                StatusLineFormatItem::Segment(StatusLineSegmentId::Path),
+                StatusLineFormatItem::Text(" {".to_string()),
+                StatusLineFormatItem::Segment(StatusLineSegmentId::Model),
+                StatusLineFormatItem::Text("}".to_string()),
+                StatusLineFormatItem::Fill,
+                StatusLineFormatItem::Segment(StatusLineSegmentId::Git),
+            ]
+        );
+
+        let err = format("{path} {branch} {cpu}").expect_err("unknown tokens");
+        assert!(
+            err.to_string().contains(
+                "unknown status line format token `{branch}`, `{cpu}`; expected one of: path, \
+                 model, provider, diff_stats, custom, attention, budget, policy, dev_env, \
+                 devspace, hostname, git, aws_profile, iac, kubernetes, process, clock, session, \
+                 build, context, fill"
+            ),
+            "{err}"
+        );
+        assert!(format("{path").is_err());
+        assert!(format("path}").is_err());
+        assert!(format("{fill}{git}{fill}").is_err());
+    }
+
+    #[test]
+    fn layout_placements_are_keyed_by_segment() {
+        let settings: StatusLineSettings = toml::from_str(
+            r#"
+            [layout]
+            git = { side = "left", priority = 25 }
+            clock = { priority = -1 }
+        "#,
+        )
+        .expect("should deserialize layout");
+
+        assert_eq!(
+            settings.layout.placement(StatusLineSegmentId::Git),
+            Some(StatusLineSegmentPlacement {
+                side: Some(StatusLineSide::Left),
+                priority: Some(25),
+            })
+        );
+        assert_eq!(
+            settings.layout.placement(StatusLineSegmentId::Clock),
+            Some(StatusLineSegmentPlacement {
+                side: None,
+                priority: Some(-1),
+            })
+        );
+        assert_eq!(settings.layout.placement(StatusLineSegmentId::Path), None);
+        assert!(
+            toml::from_str::<StatusLineSettings>("[layout]\ncontext = { side = \"left\" }")
+                .is_err()
+        );
+    }
+
     #[test]
     fn deserialize_stdio_command_server_config() {
         let cfg: McpServerConfig = toml::from_str(
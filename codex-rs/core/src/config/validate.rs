@@ -0,0 +1,529 @@
+//! `codex config validate`: check `config.toml` the way loading it would,
+//! and also report what loading quietly ignores (unknown keys, unknown
+//! skins, segments placed on top of each other), each with its line.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Deserializer;
+use serde::de::DeserializeOwned;
+use serde::de::Visitor;
+use toml::Table;
+use toml::Value;
+
+use crate::config::ConfigToml;
+use crate::config::types::Code88Toml;
+use crate::config::types::SpinnerSettings;
+use crate::config::types::StatusLineHostAlias;
+use crate::config::types::StatusLineMaxWidths;
+use crate::config::types::StatusLineModelStyle;
+use crate::config::types::StatusLineSegmentPlacement;
+use crate::config::types::StatusLineSegments;
+use crate::config::types::StatusLineSettings;
+use crate::config::types::Tui;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Codex refuses to start, or the setting does not do what it says.
+    Error,
+    /// Allowed, but probably not what was meant.
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    pub severity: Severity,
+    /// 1-based line the problem is on, when it could be found.
+    pub line: Option<i64>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match self.line {
+            Some(line) => write!(f, "line {line}: {severity}: {}", self.message),
+            None => write!(f, "{severity}: {}", self.message),
+        }
+    }
+}
+
+/// Problems in `contents`, in the order of the file. `skins` are the status
+/// line skin names the TUI accepts.
+pub fn validate_config_toml(contents: &str, skins: &[String]) -> Vec<ConfigDiagnostic> {
+    let table: Table = match toml::from_str(contents) {
+        Ok(table) => table,
+        Err(err) => return vec![parse_error(contents, &err)],
+    };
+    let lines = KeyLines::new(contents);
+    let mut diagnostics = Vec::new();
+
+    let rejected = toml::from_str::<ConfigToml>(contents)
+        .err()
+        .map(|err| parse_error(contents, &err));
+
+    let sections: [(&str, &[&str]); 7] = [
+        ("", struct_fields::<ConfigToml>()),
+        ("code88", struct_fields::<Code88Toml>()),
+        ("tui", struct_fields::<Tui>()),
+        ("tui.spinner", struct_fields::<SpinnerSettings>()),
+        ("tui.statusline", struct_fields::<StatusLineSettings>()),
+        (
+            "tui.statusline.segments",
+            struct_fields::<StatusLineSegments>(),
+        ),
+        (
+            "tui.statusline.max_widths",
+            struct_fields::<StatusLineMaxWidths>(),
+        ),
+    ];
+    for (path, known) in sections {
+        if let Some(table) = table_at(&table, path) {
+            unknown_keys(path, table, known, &lines, &mut diagnostics);
+        }
+    }
+    // Tables of entries keyed by a name pattern, which may contain dots.
+    let entry_sections: [(&str, &[&str]); 2] = [
+        (
+            "tui.statusline.hosts",
+            struct_fields::<StatusLineHostAlias>(),
+        ),
+        (
+            "tui.statusline.models",
+            struct_fields::<StatusLineModelStyle>(),
+        ),
+    ];
+    for (path, known) in entry_sections {
+        for (name, entry) in table_at(&table, path).into_iter().flatten() {
+            if let Some(entry) = entry.as_table() {
+                let path = join(path, name);
+                unknown_keys(&path, entry, known, &lines, &mut diagnostics);
+            }
+        }
+    }
+
+    if let Some(statusline) = table_at(&table, "tui.statusline") {
+        check_skin(statusline, skins, &lines, &mut diagnostics);
+        check_layout(statusline, &lines, &mut diagnostics);
+    }
+    if let Some(code88) = table_at(&table, "code88") {
+        check_code88(code88, &lines, &mut diagnostics);
+    }
+
+    // A key loading rejects, such as an unknown one in a section that
+    // denies them, is reported once: by the load error.
+    if let Some(rejected) = rejected {
+        diagnostics
+            .retain(|diagnostic| rejected.line.is_none() || diagnostic.line != rejected.line);
+        diagnostics.push(rejected);
+    }
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line.unwrap_or(i64::MAX));
+    diagnostics
+}
+
+fn parse_error(contents: &str, err: &toml::de::Error) -> ConfigDiagnostic {
+    ConfigDiagnostic {
+        severity: Severity::Error,
+        line: err.span().map(|span| line_of_offset(contents, span.start)),
+        message: err.message().trim().to_string(),
+    }
+}
+
+fn line_of_offset(contents: &str, offset: usize) -> i64 {
+    let newlines = contents
+        .get(..offset)
+        .map_or(0, |before| before.matches('\n').count());
+    i64::try_from(newlines + 1).unwrap_or(i64::MAX)
+}
+
+/// The table at dotted `path`; the root for `""`.
+fn table_at<'a>(root: &'a Table, path: &str) -> Option<&'a Table> {
+    if path.is_empty() {
+        return Some(root);
+    }
+    path.split('.')
+        .try_fold(root, |table, key| table.get(key)?.as_table())
+}
+
+fn unknown_keys(
+    path: &str,
+    table: &Table,
+    known: &[&str],
+    lines: &KeyLines,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    // Types that deserialize through a map report no fields; nothing to check.
+    if known.is_empty() {
+        return;
+    }
+    for key in table.keys().filter(|key| !known.contains(&key.as_str())) {
+        let full = join(path, key);
+        let section = if path.is_empty() {
+            "at the top level".to_string()
+        } else {
+            format!("in [{path}]")
+        };
+        let mut message = format!("unknown key `{key}` {section} is ignored");
+        if let Some(suggestion) = closest(key, known) {
+            message.push_str(&format!("; did you mean `{suggestion}`?"));
+        }
+        diagnostics.push(ConfigDiagnostic {
+            // Unknown top-level keys are usually typos; sections are where
+            // the statusline and 88code settings are easy to get wrong.
+            severity: if path.is_empty() {
+                Severity::Warning
+            } else {
+                Severity::Error
+            },
+            line: lines.get(&full),
+            message,
+        });
+    }
+}
+
+fn check_skin(
+    statusline: &Table,
+    skins: &[String],
+    lines: &KeyLines,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    let Some(skin) = statusline.get("skin").and_then(Value::as_str) else {
+        return;
+    };
+    if skins.iter().any(|known| known == skin) {
+        return;
+    }
+    diagnostics.push(ConfigDiagnostic {
+        severity: Severity::Error,
+        line: lines.get("tui.statusline.skin"),
+        message: format!(
+            "unknown skin `{skin}`, the status line falls back to `powerline`; expected one of: {}",
+            skins.join(", ")
+        ),
+    });
+}
+
+/// Segments given the same side and priority in `[tui.statusline.layout]`
+/// land in an order that depends on the built-in defaults.
+fn check_layout(statusline: &Table, lines: &KeyLines, diagnostics: &mut Vec<ConfigDiagnostic>) {
+    let Some(layout) = statusline.get("layout").and_then(Value::as_table) else {
+        return;
+    };
+    let known = struct_fields::<StatusLineSegmentPlacement>();
+    let mut segments: Vec<_> = layout.iter().collect();
+    segments.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut taken: HashMap<(String, i64), &str> = HashMap::new();
+    for (segment, placement) in segments {
+        let Some(placement) = placement.as_table() else {
+            continue;
+        };
+        unknown_keys(
+            &format!("tui.statusline.layout.{segment}"),
+            placement,
+            known,
+            lines,
+            diagnostics,
+        );
+        let (Some(side), Some(priority)) = (
+            placement.get("side").and_then(Value::as_str),
+            placement.get("priority").and_then(Value::as_integer),
+        ) else {
+            continue;
+        };
+        if let Some(other) = taken.insert((side.to_string(), priority), segment) {
+            diagnostics.push(ConfigDiagnostic {
+                severity: Severity::Warning,
+                line: lines.get(&format!("tui.statusline.layout.{segment}.priority")),
+                message: format!(
+                    "segments `{other}` and `{segment}` both sit at {side} priority {priority}"
+                ),
+            });
+        }
+    }
+}
+
+fn check_code88(code88: &Table, lines: &KeyLines, diagnostics: &mut Vec<ConfigDiagnostic>) {
+    if let Some(url) = code88.get("success_url").and_then(Value::as_str)
+        && !(url.starts_with("https://") || url.starts_with("http://"))
+    {
+        diagnostics.push(ConfigDiagnostic {
+            severity: Severity::Error,
+            line: lines.get("code88.success_url"),
+            message: format!("success_url `{url}` is not an http(s) URL"),
+        });
+    }
+    if let Some(args) = code88.get("browser_args").and_then(Value::as_array) {
+        for arg in args.iter().filter_map(Value::as_str) {
+            if !arg.starts_with('-') {
+                diagnostics.push(ConfigDiagnostic {
+                    severity: Severity::Warning,
+                    line: lines.get("code88.browser_args"),
+                    message: format!(
+                        "browser_args entry `{arg}` is not a flag; Chrome opens it as a page"
+                    ),
+                });
+            }
+        }
+    }
+    let cache_off = code88
+        .get("usage_cache_ttl_secs")
+        .and_then(Value::as_integer)
+        .is_some_and(|secs| secs <= 0);
+    if cache_off && code88.get("usage_cache_file").and_then(Value::as_bool) == Some(true) {
+        diagnostics.push(ConfigDiagnostic {
+            severity: Severity::Warning,
+            line: lines.get("code88.usage_cache_file"),
+            message: "usage_cache_file has no effect while usage_cache_ttl_secs = 0".to_string(),
+        });
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// The known key within two edits of `key`, for typos.
+fn closest<'a>(key: &str, known: &[&'a str]) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Line of every `[table]` header and `key = value`, by dotted path. A
+/// line scan rather than a parse, so keys inside inline tables and
+/// multi-line values are not found.
+struct KeyLines(HashMap<String, i64>);
+
+impl KeyLines {
+    fn new(contents: &str) -> Self {
+        let mut lines = HashMap::new();
+        let mut table = String::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            let number = i64::try_from(index + 1).unwrap_or(i64::MAX);
+            if let Some(header) = line.strip_prefix('[') {
+                let header = header.trim_start_matches('[');
+                let Some(end) = header.find(']') else {
+                    continue;
+                };
+                table = dotted(&header[..end]);
+                lines.entry(table.clone()).or_insert(number);
+            } else if let Some((key, _)) = line.split_once('=')
+                && !line.starts_with('#')
+            {
+                lines.entry(join(&table, &dotted(key))).or_insert(number);
+            }
+        }
+        Self(lines)
+    }
+
+    fn get(&self, path: &str) -> Option<i64> {
+        self.0.get(path).copied()
+    }
+}
+
+/// `tui . "statusline"` as `tui.statusline`.
+fn dotted(key: &str) -> String {
+    key.split('.')
+        .map(|part| part.trim().trim_matches('"').trim_matches('\''))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Keys a `#[derive(Deserialize)]` struct accepts, taken from the field
+/// list serde passes to `deserialize_struct`, so they never drift from the
+/// config types.
+fn struct_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut names = FieldNames(&[]);
+    let _ = T::deserialize(&mut names);
+    names.0
+}
+
+struct FieldNames(&'static [&'static str]);
+
+impl<'de> Deserializer<'de> for &mut FieldNames {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(serde::de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0 = fields;
+        Err(serde::de::Error::custom("fields recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn skins() -> Vec<String> {
+        vec!["powerline".to_string(), "minimal-plain".to_string()]
+    }
+
+    fn messages(contents: &str) -> Vec<String> {
+        validate_config_toml(contents, &skins())
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn a_valid_config_has_no_diagnostics() {
+        let contents = r#"
+model = "gpt-5.1-codex-max"
+
+[code88]
+success_url = "https://www.88code.org/"
+usage_cache_ttl_secs = 30
+
+[tui.statusline]
+skin = "minimal-plain"
+
+[tui.statusline.segments]
+clock = true
+
+[tui.statusline.layout.clock]
+side = "left"
+priority = 5
+"#;
+        assert_eq!(messages(contents), Vec::<String>::new());
+    }
+
+    #[test]
+    fn unknown_keys_are_reported_with_lines_and_suggestions() {
+        let contents = r#"modle = "o3"
+
+[tui.statusline]
+skinn = "powerline"
+
+[tui.statusline.segments]
+clocks = true
+
+[code88]
+keep_browser = true
+
+[tui.spinner]
+frame = "dots"
+
+[tui.statusline.hosts."prod-*"]
+label = "PROD"
+colour = "red"
+
+[tui.statusline.models]
+"gpt-5.1" = { icon = "G", colr = "green" }
+"#;
+        assert_eq!(
+            messages(contents),
+            vec![
+                "line 1: warning: unknown key `modle` at the top level is ignored; did you mean `model`?",
+                "line 4: error: unknown key `skinn` in [tui.statusline] is ignored; did you mean `skin`?",
+                "line 7: error: unknown key `clocks` in [tui.statusline.segments] is ignored; did you mean `clock`?",
+                "line 10: error: unknown key `keep_browser` in [code88] is ignored",
+                "line 13: error: unknown key `frame` in [tui.spinner] is ignored; did you mean `frames`?",
+                "line 17: error: unknown key `colour` in [tui.statusline.hosts.prod-*] is ignored; did you mean `color`?",
+                "error: unknown key `colr` in [tui.statusline.models.gpt-5.1] is ignored; did you mean `color`?",
+            ]
+        );
+    }
+
+    #[test]
+    fn values_loading_rejects_point_at_their_line() {
+        let contents = r#"[tui.statusline]
+skin = "powerline"
+timer_format = "forever"
+"#;
+        let diagnostics = validate_config_toml(contents, &skins());
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].line, Some(3));
+
+        let broken = validate_config_toml("[tui\nskin = 1\n", &skins());
+        assert_eq!(broken.len(), 1, "{broken:?}");
+        assert_eq!(broken[0].line, Some(1));
+
+        // `[tui.statusline.max_widths]` denies unknown keys, so loading
+        // already reports this one.
+        let denied = validate_config_toml("[tui.statusline.max_widths]\ngitt = 30\n", &skins());
+        assert_eq!(denied.len(), 1, "{denied:?}");
+        assert_eq!(denied[0].line, Some(2));
+        assert!(denied[0].message.contains("gitt"), "{denied:?}");
+    }
+
+    #[test]
+    fn skins_layout_and_code88_values_are_checked() {
+        let contents = r#"[tui.statusline]
+skin = "neon"
+
+[tui.statusline.layout.clock]
+side = "right"
+priority = 10
+
+[tui.statusline.layout.build]
+side = "right"
+priority = 10
+
+[code88]
+success_url = "www.88code.org"
+browser_args = ["proxy-server=socks5://localhost:1080"]
+usage_cache_ttl_secs = 0
+usage_cache_file = true
+"#;
+        assert_eq!(
+            messages(contents),
+            vec![
+                "line 2: error: unknown skin `neon`, the status line falls back to `powerline`; expected one of: powerline, minimal-plain",
+                "line 6: warning: segments `build` and `clock` both sit at right priority 10",
+                "line 13: error: success_url `www.88code.org` is not an http(s) URL",
+                "line 14: warning: browser_args entry `proxy-server=socks5://localhost:1080` is not a flag; Chrome opens it as a page",
+                "line 16: warning: usage_cache_file has no effect while usage_cache_ttl_secs = 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_fields_follow_the_config_types() {
+        let fields = struct_fields::<Code88Toml>();
+        assert!(fields.contains(&"usage_cache_ttl_secs"), "{fields:?}");
+        assert!(struct_fields::<StatusLineSegments>().contains(&"build"));
+        assert!(struct_fields::<StatusLineMaxWidths>().contains(&"git"));
+        assert_eq!(edit_distance("skinn", "skin"), 1);
+    }
+}
@@ -149,6 +149,12 @@ pub enum CodexErr {
     #[error("{0}")]
     RefreshTokenFailed(RefreshTokenFailedError),
 
+    /// The provider authenticates with the 88code login token and there is
+    /// none, or the relay rejected it. The saved token is left alone; the
+    /// interactive client decides whether to sign in again.
+    #[error("88code sign-in required. Run `codex login --provider 88code` to sign in again.")]
+    Code88SignInRequired,
+
     #[error("Fatal error: {0}")]
     Fatal(String),
 
@@ -452,7 +458,9 @@ impl CodexErr {
             CodexErr::ResponseStreamFailed(_) => CodexErrorInfo::ResponseStreamConnectionFailed {
                 http_status_code: self.http_status_code_value(),
             },
-            CodexErr::RefreshTokenFailed(_) => CodexErrorInfo::Unauthorized,
+            CodexErr::RefreshTokenFailed(_) | CodexErr::Code88SignInRequired => {
+                CodexErrorInfo::Unauthorized
+            }
             CodexErr::SessionConfiguredNotFirstEvent
             | CodexErr::InternalServerError
             | CodexErr::InternalAgentDied => CodexErrorInfo::InternalServerError,
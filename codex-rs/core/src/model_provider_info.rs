@@ -96,6 +96,12 @@ pub struct ModelProviderInfo {
     /// and API key (if needed) comes from the "env_key" environment variable.
     #[serde(default)]
     pub requires_openai_auth: bool,
+
+    /// Authenticate with the 88code login token saved under CODEX_HOME (see
+    /// `codex-code88`) instead of `env_key` or the OpenAI login. When the
+    /// relay rejects it, Codex signs in to 88code again and retries the turn.
+    #[serde(default)]
+    pub code88_auth: bool,
 }
 
 impl ModelProviderInfo {
@@ -125,19 +131,24 @@ impl ModelProviderInfo {
         Ok(headers)
     }
 
-    pub(crate) fn to_api_provider(
-        &self,
-        auth_mode: Option<AuthMode>,
-    ) -> crate::error::Result<ApiProvider> {
+    /// The base URL requests go to: the configured one, or the ChatGPT
+    /// backend or the OpenAI API depending on how the user signed in.
+    pub fn base_url_for(&self, auth_mode: Option<AuthMode>) -> String {
         let default_base_url = if matches!(auth_mode, Some(AuthMode::ChatGPT)) {
             "https://chatgpt.com/backend-api/codex"
         } else {
             "https://api.openai.com/v1"
         };
-        let base_url = self
-            .base_url
+        self.base_url
             .clone()
-            .unwrap_or_else(|| default_base_url.to_string());
+            .unwrap_or_else(|| default_base_url.to_string())
+    }
+
+    pub(crate) fn to_api_provider(
+        &self,
+        auth_mode: Option<AuthMode>,
+    ) -> crate::error::Result<ApiProvider> {
+        let base_url = self.base_url_for(auth_mode);
 
         let headers = self.build_header_map()?;
         let retry = ApiRetryConfig {
@@ -263,6 +274,7 @@ pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: true,
+                code88_auth: false,
             },
         ),
         (
@@ -314,6 +326,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
+        code88_auth: false,
     }
 }
 
@@ -342,12 +355,27 @@ base_url = "http://localhost:11434/v1"
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            code88_auth: false,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn test_deserialize_code88_model_provider_toml() {
+        let code88_provider_toml = r#"
+name = "88code"
+base_url = "https://relay.example.com/openai/v1"
+wire_api = "responses"
+code88_auth = true
+        "#;
+        let provider: ModelProviderInfo = toml::from_str(code88_provider_toml).unwrap();
+        assert!(provider.code88_auth);
+        assert_eq!(provider.env_key, None);
+        assert_eq!(provider.wire_api, WireApi::Responses);
+    }
+
     #[test]
     fn test_deserialize_azure_model_provider_toml() {
         let azure_provider_toml = r#"
@@ -372,6 +400,7 @@ query_params = { api-version = "2025-04-01-preview" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            code88_auth: false,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -405,6 +434,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            code88_auth: false,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -436,6 +466,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: false,
+                code88_auth: false,
             };
             let api = provider.to_api_provider(None).expect("api provider");
             assert!(
@@ -458,6 +489,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            code88_auth: false,
         };
         let named_api = named_provider.to_api_provider(None).expect("api provider");
         assert!(named_api.is_azure_responses_endpoint());
@@ -482,6 +514,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: false,
+                code88_auth: false,
             };
             let api = provider.to_api_provider(None).expect("api provider");
             assert!(
@@ -490,4 +523,39 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             );
         }
     }
+
+    #[test]
+    fn base_url_follows_the_auth_mode_unless_configured() {
+        let mut provider = ModelProviderInfo {
+            name: "OpenAI".into(),
+            base_url: None,
+            env_key: None,
+            env_key_instructions: None,
+            experimental_bearer_token: None,
+            wire_api: WireApi::Responses,
+            query_params: None,
+            http_headers: None,
+            env_http_headers: None,
+            request_max_retries: None,
+            stream_max_retries: None,
+            stream_idle_timeout_ms: None,
+            requires_openai_auth: true,
+            code88_auth: false,
+        };
+        assert_eq!(
+            provider.base_url_for(Some(AuthMode::ChatGPT)),
+            "https://chatgpt.com/backend-api/codex"
+        );
+        assert_eq!(
+            provider.base_url_for(Some(AuthMode::ApiKey)),
+            "https://api.openai.com/v1"
+        );
+        assert_eq!(provider.base_url_for(None), "https://api.openai.com/v1");
+
+        provider.base_url = Some("https://proxy.example.com/v1".into());
+        assert_eq!(
+            provider.base_url_for(Some(AuthMode::ChatGPT)),
+            "https://proxy.example.com/v1"
+        );
+    }
 }
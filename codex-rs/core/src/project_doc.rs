@@ -12,11 +12,18 @@
 //!     current working directory (inclusive) and concatenate their contents in
 //!     that order.
 //! 3.  We do **not** walk past the Git root.
+//!
+//! Docs and instruction text pinned to the workspace in
+//! `CODEX_HOME/workspace_state/` come after the repository's own docs, so
+//! per-repo conventions apply even when the repository cannot be modified.
 
 use crate::config::Config;
 use crate::features::Feature;
 use crate::skills::load_skills;
 use crate::skills::render_skills_section;
+use crate::workspace_state::WorkspaceState;
+use crate::workspace_state::load_workspace_state;
+use codex_utils_string::take_bytes_at_char_boundary;
 use dunce::canonicalize as normalize_path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
@@ -92,9 +99,7 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
     }
 
     let paths = discover_project_doc_paths(config)?;
-    if paths.is_empty() {
-        return Ok(None);
-    }
+    let pinned_instructions = workspace_pins(config).pinned_instructions;
 
     let mut remaining: u64 = max_total as u64;
     let mut parts: Vec<String> = Vec::new();
@@ -130,6 +135,19 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
         }
     }
 
+    if let Some(text) = pinned_instructions
+        && remaining > 0
+    {
+        let budget = usize::try_from(remaining).unwrap_or(usize::MAX);
+        let kept = take_bytes_at_char_boundary(&text, budget);
+        if kept.len() < text.len() {
+            tracing::warn!(
+                "Pinned workspace instructions exceed remaining budget ({remaining} bytes) - truncating."
+            );
+        }
+        parts.push(kept.to_string());
+    }
+
     if parts.is_empty() {
         Ok(None)
     } else {
@@ -207,9 +225,28 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
         }
     }
 
+    for pinned in workspace_pins(config).pinned_docs {
+        let path = config.cwd.join(pinned);
+        if found.contains(&path) {
+            continue;
+        }
+        if path.exists() {
+            found.push(path);
+        } else {
+            tracing::warn!("Pinned project doc `{}` not found.", path.display());
+        }
+    }
+
     Ok(found)
 }
 
+fn workspace_pins(config: &Config) -> WorkspaceState {
+    load_workspace_state(&config.codex_home, &config.cwd).unwrap_or_else(|err| {
+        tracing::warn!("failed to load workspace state: {err}");
+        WorkspaceState::default()
+    })
+}
+
 fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
     let mut names: Vec<&'a str> =
         Vec::with_capacity(2 + config.project_doc_fallback_filenames.len());
@@ -543,6 +580,46 @@ mod tests {
         assert_eq!(res, expected);
     }
 
+    /// Docs and text pinned to the workspace follow the repository's docs
+    /// and share the byte budget.
+    #[tokio::test]
+    async fn pinned_workspace_docs_follow_project_docs() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        fs::write(tmp.path().join("AGENTS.md"), "repo doc").unwrap();
+        let extra = tempfile::tempdir().expect("tempdir");
+        let extra_doc = extra.path().join("conventions.md");
+        fs::write(&extra_doc, "team conventions").unwrap();
+
+        let cfg = make_config(&tmp, 4096, None);
+        crate::workspace_state::persist_pinned_docs(
+            &cfg.codex_home,
+            &cfg.cwd,
+            vec![extra_doc.clone(), PathBuf::from("missing.md")],
+        )
+        .unwrap();
+        crate::workspace_state::persist_pinned_instructions(
+            &cfg.codex_home,
+            &cfg.cwd,
+            Some("  Use tabs.  "),
+        )
+        .unwrap();
+
+        let paths = discover_project_doc_paths(&cfg).unwrap();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[1], extra_doc);
+
+        let res = read_project_docs(&cfg).await.unwrap();
+        assert_eq!(
+            res.as_deref(),
+            Some("repo doc\n\nteam conventions\n\nUse tabs.")
+        );
+
+        let mut small = make_config(&tmp, 27, None);
+        small.codex_home = cfg.codex_home.clone();
+        let res = read_project_docs(&small).await.unwrap();
+        assert_eq!(res.as_deref(), Some("repo doc\n\nteam conventions\n\nUse"));
+    }
+
     fn create_skill(codex_home: PathBuf, name: &str, description: &str) {
         let skill_dir = codex_home.join(format!("skills/{name}"));
         fs::create_dir_all(&skill_dir).unwrap();
@@ -472,9 +472,11 @@ pub async fn find_conversation_path_by_id_str(
     if !root.exists() {
         return Ok(None);
     }
+    // Other per-session files (such as the TUI's run timeline) sit next to
+    // the rollout and carry the same id, so look past them.
     // This is safe because we know the values are valid.
     #[allow(clippy::unwrap_used)]
-    let limit = NonZero::new(1).unwrap();
+    let limit = NonZero::new(8).unwrap();
     // This is safe because we know the values are valid.
     #[allow(clippy::unwrap_used)]
     let threads = NonZero::new(2).unwrap();
@@ -497,6 +499,10 @@ pub async fn find_conversation_path_by_id_str(
     Ok(results
         .matches
         .into_iter()
-        .next()
-        .map(|m| root.join(m.path)))
+        .map(|m| root.join(m.path))
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("rollout-") && name.ends_with(".jsonl"))
+        }))
 }
@@ -16,6 +16,7 @@ use crate::rollout::INTERACTIVE_SESSION_SOURCES;
 use crate::rollout::list::ConversationItem;
 use crate::rollout::list::ConversationsPage;
 use crate::rollout::list::Cursor;
+use crate::rollout::list::find_conversation_path_by_id_str;
 use crate::rollout::list::get_conversations;
 use anyhow::Result;
 use codex_protocol::ConversationId;
@@ -916,3 +917,18 @@ async fn test_model_provider_filter_selects_only_matching_sessions() -> Result<(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn find_by_id_skips_other_files_of_the_session() -> Result<()> {
+    let temp = TempDir::new()?;
+    let home = temp.path();
+    let uuid = Uuid::new_v4();
+    let ts = "2025-04-01T10-30-00";
+    write_session_file(home, ts, uuid, 1, Some(SessionSource::Cli))?;
+    let day = home.join("sessions").join("2025").join("04").join("01");
+    fs::write(day.join(format!("timeline-{uuid}.jsonl")), "{}\n")?;
+
+    let found = find_conversation_path_by_id_str(home, &uuid.to_string()).await?;
+    assert_eq!(found, Some(day.join(format!("rollout-{ts}-{uuid}.jsonl"))));
+    Ok(())
+}
@@ -6,6 +6,7 @@ use std::time::Instant;
 
 use crate::AuthManager;
 use crate::ModelProviderInfo;
+use crate::api_bridge::Code88Credentials;
 use crate::client::ModelClient;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
@@ -45,6 +46,7 @@ pub(crate) async fn assess_command(
     config: Arc<Config>,
     provider: ModelProviderInfo,
     auth_manager: Arc<AuthManager>,
+    code88_credentials: Arc<Code88Credentials>,
     parent_otel: &OtelEventManager,
     conversation_id: ConversationId,
     models_manager: Arc<ModelsManager>,
@@ -140,7 +142,8 @@ pub(crate) async fn assess_command(
         config.model_reasoning_summary,
         conversation_id,
         session_source,
-    );
+    )
+    .with_code88_credentials(code88_credentials);
 
     let start = Instant::now();
     let assessment_result = timeout(SANDBOX_ASSESSMENT_TIMEOUT, async move {
@@ -2,18 +2,32 @@
 
 use codex_protocol::models::ResponseItem;
 
+use crate::budget::BudgetTracker;
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
 use crate::protocol::RateLimitSnapshot;
+use crate::protocol::RateLimitWindow;
+use crate::protocol::StructuredWarningEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
+use crate::protocol::WarningKind;
+use crate::protocol::WarningSeverity;
 use crate::truncate::TruncationPolicy;
 
+/// Share of a rate limit window used, in percent, past which a warning is
+/// sent, and past which it turns critical.
+const RATE_LIMIT_WARNING_PERCENT: f64 = 75.0;
+const RATE_LIMIT_CRITICAL_PERCENT: f64 = 95.0;
+
 /// Persistent, session-scoped state previously stored directly on `Session`.
 pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    pub(crate) budget: BudgetTracker,
+    /// Severity of the last rate limit warning sent; `None` once usage drops
+    /// back below the thresholds, e.g. after the window resets.
+    rate_limit_warning: Option<WarningSeverity>,
 }
 
 impl SessionState {
@@ -24,6 +38,8 @@ impl SessionState {
             session_configuration,
             history,
             latest_rate_limits: None,
+            budget: BudgetTracker::default(),
+            rate_limit_warning: None,
         }
     }
 
@@ -68,6 +84,19 @@ impl SessionState {
         ));
     }
 
+    /// A warning when the latest rate limits crossed a threshold the last
+    /// warning had not, so each crossing is reported once.
+    pub(crate) fn take_rate_limit_warning(&mut self) -> Option<StructuredWarningEvent> {
+        let warning = self
+            .latest_rate_limits
+            .as_ref()
+            .and_then(rate_limit_warning);
+        let severity = warning.as_ref().map(|warning| warning.severity);
+        let raised = severity > self.rate_limit_warning;
+        self.rate_limit_warning = severity;
+        warning.filter(|_| raised)
+    }
+
     pub(crate) fn token_info_and_rate_limits(
         &self,
     ) -> (Option<TokenUsageInfo>, Option<RateLimitSnapshot>) {
@@ -96,3 +125,43 @@ fn merge_rate_limit_fields(
     }
     snapshot
 }
+
+/// A warning about the most used rate limit window, when it is past
+/// [`RATE_LIMIT_WARNING_PERCENT`].
+fn rate_limit_warning(snapshot: &RateLimitSnapshot) -> Option<StructuredWarningEvent> {
+    let (used_percent, label) = [
+        (snapshot.primary.as_ref(), "5h"),
+        (snapshot.secondary.as_ref(), "weekly"),
+    ]
+    .into_iter()
+    .filter_map(|(window, fallback)| {
+        let window = window?;
+        Some((window.used_percent, window_label(window, fallback)))
+    })
+    .max_by(|(a, _), (b, _)| a.total_cmp(b))?;
+    let severity = if used_percent >= RATE_LIMIT_CRITICAL_PERCENT {
+        WarningSeverity::Critical
+    } else if used_percent >= RATE_LIMIT_WARNING_PERCENT {
+        WarningSeverity::Warning
+    } else {
+        return None;
+    };
+    Some(StructuredWarningEvent {
+        kind: WarningKind::RateLimit,
+        severity,
+        message: format!("{used_percent:.0}% of the {label} rate limit used"),
+    })
+}
+
+fn window_label(window: &RateLimitWindow, fallback: &str) -> String {
+    const MINUTES_PER_HOUR: i64 = 60;
+    const MINUTES_PER_WEEK: i64 = 7 * 24 * MINUTES_PER_HOUR;
+    match window.window_minutes {
+        Some(minutes) if minutes >= MINUTES_PER_WEEK => "weekly".to_string(),
+        Some(minutes) if minutes > 0 && minutes % MINUTES_PER_HOUR == 0 => {
+            format!("{}h", minutes / MINUTES_PER_HOUR)
+        }
+        Some(minutes) if minutes > 0 => format!("{minutes}m"),
+        _ => fallback.to_string(),
+    }
+}
@@ -16,6 +16,7 @@ use tokio_util::sync::CancellationToken;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex_delegate::run_codex_conversation_one_shot;
+use crate::config::types::BudgetConfig;
 use crate::protocol::SandboxPolicy;
 use crate::review_format::format_review_findings_block;
 use crate::review_format::render_review_output_text;
@@ -90,6 +91,11 @@ async fn start_review_conversation(
         .disable(crate::features::Feature::WebSearchRequest)
         .disable(crate::features::Feature::ViewImageTool);
 
+    // Budgets are enforced on the parent session; the reviewer has nobody to
+    // ask for more.
+    sub_agent_config.budget = BudgetConfig::default();
+    sub_agent_config.workspace_budget = None;
+
     // Set explicit review rubric for the sub-agent
     sub_agent_config.base_instructions = Some(crate::REVIEW_PROMPT.to_string());
     (run_codex_conversation_one_shot(
@@ -5,10 +5,12 @@ Central place for approvals + sandbox selection + retry semantics. Drives a
 simple sequence for any ToolRuntime: approval → select sandbox → attempt →
 retry without sandbox on denial (no re‑approval thanks to caching).
 */
+use crate::bash::extract_bash_command;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::error::get_error_message_ui;
 use crate::exec::ExecToolCallOutput;
+use crate::parse_command::shlex_join;
 use crate::sandboxing::SandboxManager;
 use crate::tools::sandboxing::ApprovalCtx;
 use crate::tools::sandboxing::ExecApprovalRequirement;
@@ -20,7 +22,11 @@ use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::default_exec_approval_requirement;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::StructuredWarningEvent;
+use codex_protocol::protocol::WarningKind;
+use codex_protocol::protocol::WarningSeverity;
 
 pub(crate) struct ToolOrchestrator {
     sandbox: SandboxManager,
@@ -183,6 +189,22 @@ impl ToolOrchestrator {
                     }
                 }
 
+                if let Some(metadata) = req.sandbox_retry_data() {
+                    let command = match extract_bash_command(&metadata.command) {
+                        Some((_, script)) => script.to_string(),
+                        None => shlex_join(&metadata.command),
+                    };
+                    let warning = StructuredWarningEvent {
+                        kind: WarningKind::SandboxEscalation,
+                        severity: WarningSeverity::Warning,
+                        message: format!("`{command}` is running outside the sandbox"),
+                    };
+                    tool_ctx
+                        .session
+                        .send_event(turn_ctx, EventMsg::StructuredWarning(warning))
+                        .await;
+                }
+
                 let escalated_attempt = SandboxAttempt {
                     sandbox: crate::exec::SandboxType::None,
                     policy: &turn_ctx.sandbox_policy,
@@ -1,44 +1,692 @@
+use crate::config::types::BudgetConfig;
+use crate::config::types::McpServerConfig;
+use crate::config::types::McpServerTransportConfig;
+use crate::config::types::ModelPrice;
+use crate::config::types::StatusLineSettings;
+use crate::config::types::WorkspaceStateBackend;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::ConversationId;
+use codex_protocol::config_types::SandboxMode;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::TokenUsage;
+use fs2::FileExt;
 use serde::Deserialize;
 use serde::Serialize;
 use sha1::Digest;
 use sha1::Sha1;
 use std::collections::HashMap;
 use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
 use tempfile::NamedTempFile;
 use tracing::warn;
 
+#[cfg(feature = "sqlite-workspace-state")]
+mod sqlite;
+
 const WORKSPACE_STATE_DIR: &str = "workspace_state";
+/// Database that replaces `WORKSPACE_STATE_DIR` once
+/// `workspace_state_backend = "sqlite"` is set.
+const SQLITE_STORE_FILE: &str = "workspace_state.sqlite";
+
+/// How long a write waits for another Codex instance to finish its own
+/// update of the same workspace before going ahead without the lock.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+const LOCK_RETRY_SLEEP: Duration = Duration::from_millis(10);
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WorkspaceState {
+    /// Directory this state belongs to. Files written before this was
+    /// recorded only carry the hash in their name.
+    #[serde(default)]
+    pub workspace_path: Option<PathBuf>,
+    /// Last time a session started in this workspace or any of its settings
+    /// was saved.
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
     pub model: Option<String>,
     pub model_reasoning_effort: Option<ReasoningEffort>,
+    /// Approval policy last chosen in this workspace; used when neither the
+    /// CLI, the profile nor config.toml sets one.
+    pub approval_policy: Option<AskForApproval>,
+    /// Sandbox mode last chosen in this workspace; used when neither the CLI,
+    /// the profile nor config.toml sets one.
+    pub sandbox_mode: Option<SandboxMode>,
     #[serde(default)]
     pub mcp_servers: HashMap<String, WorkspaceMcpServerState>,
+    /// Extra environment for commands run in this workspace, applied on top
+    /// of `[shell_environment_policy].set`.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Instruction text appended after the project docs for this workspace.
+    pub pinned_instructions: Option<String>,
+    /// Extra AGENTS.md-style files read after the ones discovered in the
+    /// repository; relative paths resolve against the workspace.
+    #[serde(default)]
+    pub pinned_docs: Vec<PathBuf>,
+    /// Token usage summed over the sessions that ended in this workspace.
+    #[serde(default)]
+    pub usage: WorkspaceUsage,
+    /// Limits on `usage` plus the running session.
+    pub budget: Option<BudgetConfig>,
+    /// Most recent session that ended here, offered for resuming at startup.
+    pub last_session: Option<WorkspaceLastSession>,
+    /// Set to `false` to stop offering `last_session` at startup.
+    pub resume_prompt: Option<bool>,
+    pub statusline: Option<WorkspaceStatusLineState>,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceUsage {
+    pub sessions: i64,
+    pub input_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub output_tokens: i64,
+    /// Sum of the costs estimated from `[model_prices]`; sessions whose
+    /// model has no price entry are counted in `unpriced_sessions` instead.
+    pub estimated_cost_usd: f64,
+    pub unpriced_sessions: i64,
+}
+
+impl WorkspaceUsage {
+    pub fn is_empty(&self) -> bool {
+        self.sessions == 0
+    }
+
+    pub fn total_tokens(&self) -> i64 {
+        self.input_tokens.saturating_add(self.output_tokens)
+    }
+
+    fn add_session(&mut self, usage: &TokenUsage, price: Option<&ModelPrice>) {
+        self.sessions += 1;
+        self.input_tokens = self.input_tokens.saturating_add(usage.input_tokens.max(0));
+        self.cached_input_tokens = self
+            .cached_input_tokens
+            .saturating_add(usage.cached_input());
+        self.output_tokens = self
+            .output_tokens
+            .saturating_add(usage.output_tokens.max(0));
+        match price {
+            Some(price) => self.estimated_cost_usd += estimate_cost(price, usage),
+            None => self.unpriced_sessions += 1,
+        }
+    }
+}
+
+/// The budget saved for a workspace with what earlier sessions already
+/// spent, loaded when a session starts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkspaceBudget {
+    pub limits: BudgetConfig,
+    pub usage: WorkspaceUsage,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceLastSession {
+    pub id: ConversationId,
+    pub ended_at: DateTime<Utc>,
+}
+
+/// Estimated cost in USD of `usage` at the given per-million-token prices.
+pub fn estimate_cost(price: &ModelPrice, usage: &TokenUsage) -> f64 {
+    let cached_input = price.cached_input.unwrap_or(price.input);
+    (usage.non_cached_input() as f64 * price.input
+        + usage.cached_input() as f64 * cached_input
+        + usage.output_tokens.max(0) as f64 * price.output)
+        / 1_000_000.0
+}
+
+/// Overrides for one MCP server from `[mcp_servers]`, applied when a session
+/// starts in this workspace.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct WorkspaceMcpServerState {
     pub enabled: Option<bool>,
+    /// Replaces the server's `args`; stdio servers only.
+    pub args: Option<Vec<String>>,
+    /// Merged over the server's `env`; stdio servers only.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    pub startup_timeout_sec: Option<f64>,
+}
+
+impl WorkspaceMcpServerState {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether anything beyond `enabled` is overridden.
+    pub fn overrides_launch(&self) -> bool {
+        self.args.is_some() || !self.env.is_empty() || self.startup_timeout_sec.is_some()
+    }
+
+    /// `server` with these overrides applied.
+    pub fn apply_to(&self, name: &str, server: &mut McpServerConfig) {
+        if let Some(enabled) = self.enabled {
+            server.enabled = enabled;
+        }
+        if let Some(timeout) = self.startup_timeout_sec {
+            match Duration::try_from_secs_f64(timeout) {
+                Ok(timeout) => server.startup_timeout_sec = Some(timeout),
+                Err(err) => {
+                    warn!("Ignoring workspace startup timeout for MCP server {name}: {err}")
+                }
+            }
+        }
+        if self.args.is_none() && self.env.is_empty() {
+            return;
+        }
+        match &mut server.transport {
+            McpServerTransportConfig::Stdio { args, env, .. } => {
+                if let Some(workspace_args) = self.args.as_ref() {
+                    *args = workspace_args.clone();
+                }
+                if !self.env.is_empty() {
+                    env.get_or_insert_default().extend(self.env.clone());
+                }
+            }
+            McpServerTransportConfig::StreamableHttp { .. } => {
+                warn!(
+                    "Ignoring workspace args/env for MCP server {name}: it is not a stdio server"
+                );
+            }
+        }
+    }
+}
+
+/// Status line settings for one workspace. Unset fields keep the global
+/// `[tui]` values.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceStatusLineState {
+    pub enabled: Option<bool>,
+    pub skin: Option<String>,
+    #[serde(default)]
+    pub segments: WorkspaceStatusLineSegments,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceStatusLineSegments {
+    pub path: Option<bool>,
+    pub model: Option<bool>,
+    pub provider: Option<bool>,
+    pub diff_stats: Option<bool>,
+    pub custom: Option<bool>,
+    pub attention: Option<bool>,
+    pub budget: Option<bool>,
+    pub clock: Option<bool>,
+    pub process: Option<bool>,
+    pub session: Option<bool>,
+    pub build: Option<bool>,
+    pub context: Option<bool>,
+    pub git: Option<bool>,
+    pub policy: Option<bool>,
+    pub dev_env: Option<bool>,
+    pub devspace: Option<bool>,
+    pub hostname: Option<bool>,
+    pub aws_profile: Option<bool>,
+    pub iac: Option<bool>,
+    pub kubernetes: Option<bool>,
+    pub code88: Option<bool>,
+}
+
+impl WorkspaceStatusLineState {
+    /// Whether the custom status line is on, given the global
+    /// `tui.custom_statusline` value.
+    pub fn enabled_or(&self, global: bool) -> bool {
+        self.enabled.unwrap_or(global)
+    }
+
+    /// The global `[tui.statusline]` settings with this workspace's
+    /// overrides applied.
+    pub fn apply_to(&self, global: &StatusLineSettings) -> StatusLineSettings {
+        let mut settings = global.clone();
+        if let Some(skin) = self.skin.as_ref() {
+            settings.skin = skin.clone();
+        }
+        let overrides = &self.segments;
+        let segments = &mut settings.segments;
+        for (value, override_value) in [
+            (&mut segments.path, overrides.path),
+            (&mut segments.model, overrides.model),
+            (&mut segments.provider, overrides.provider),
+            (&mut segments.diff_stats, overrides.diff_stats),
+            (&mut segments.custom, overrides.custom),
+            (&mut segments.attention, overrides.attention),
+            (&mut segments.budget, overrides.budget),
+            (&mut segments.clock, overrides.clock),
+            (&mut segments.process, overrides.process),
+            (&mut segments.session, overrides.session),
+            (&mut segments.build, overrides.build),
+            (&mut segments.context, overrides.context),
+            (&mut segments.git, overrides.git),
+            (&mut segments.policy, overrides.policy),
+            (&mut segments.dev_env, overrides.dev_env),
+            (&mut segments.devspace, overrides.devspace),
+            (&mut segments.hostname, overrides.hostname),
+            (&mut segments.aws_profile, overrides.aws_profile),
+            (&mut segments.iac, overrides.iac),
+            (&mut segments.kubernetes, overrides.kubernetes),
+            (&mut segments.code88, overrides.code88),
+        ] {
+            if let Some(override_value) = override_value {
+                *value = override_value;
+            }
+        }
+        settings
+    }
+}
+
+/// File holding the persisted state for `workspace` in the TOML store; it may
+/// not exist yet.
+pub fn workspace_state_path(codex_home: &Path, workspace: &Path) -> PathBuf {
+    codex_home
+        .join(WORKSPACE_STATE_DIR)
+        .join(format!("{}.toml", workspace_key(workspace)))
 }
 
-fn workspace_state_path(codex_home: &Path, workspace: &Path) -> PathBuf {
-    let canonical = dunce::canonicalize(workspace).unwrap_or_else(|_| workspace.to_path_buf());
+/// Where the state for `workspace` lives: its TOML file, or the database when
+/// the SQLite store is in use.
+pub fn workspace_state_location(codex_home: &Path, workspace: &Path) -> PathBuf {
+    sqlite_store(codex_home).unwrap_or_else(|| workspace_state_path(codex_home, workspace))
+}
+
+/// Whether any state has been saved for `workspace`.
+pub fn workspace_state_exists(codex_home: &Path, workspace: &Path) -> std::io::Result<bool> {
+    match sqlite_store(codex_home) {
+        Some(db) => Ok(sqlite::load(&db, &workspace_key(workspace))?.is_some()),
+        None => Ok(workspace_state_path(codex_home, workspace).exists()),
+    }
+}
+
+/// Hash of the canonical workspace path; names the TOML file and keys the
+/// SQLite rows.
+fn workspace_key(workspace: &Path) -> String {
+    let canonical = canonical_workspace(workspace);
     let mut hasher = Sha1::new();
     hasher.update(canonical.as_os_str().to_string_lossy().as_bytes());
     let digest = hasher.finalize();
-    let filename = format!("{digest:x}.toml");
-    codex_home.join(WORKSPACE_STATE_DIR).join(filename)
+    format!("{digest:x}")
+}
+
+fn canonical_workspace(workspace: &Path) -> PathBuf {
+    dunce::canonicalize(workspace).unwrap_or_else(|_| workspace.to_path_buf())
+}
+
+/// The SQLite database, when it has replaced the TOML files.
+#[cfg(feature = "sqlite-workspace-state")]
+fn sqlite_store(codex_home: &Path) -> Option<PathBuf> {
+    let db = codex_home.join(SQLITE_STORE_FILE);
+    db.exists().then_some(db)
+}
+
+/// Built without SQLite support: the TOML files are the only store, and a
+/// database left by another build is ignored.
+#[cfg(not(feature = "sqlite-workspace-state"))]
+fn sqlite_store(_codex_home: &Path) -> Option<PathBuf> {
+    None
+}
+
+/// Stand-in for the SQLite store when it is not compiled in.
+/// [`sqlite_store`] never finds a database then, so none of these run.
+#[cfg(not(feature = "sqlite-workspace-state"))]
+mod sqlite {
+    use std::path::Path;
+
+    use super::WorkspaceState;
+    use super::WorkspaceStateEntry;
+
+    fn unsupported() -> std::io::Error {
+        std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "this build of Codex has no SQLite workspace state support",
+        )
+    }
+
+    pub(super) fn load(_db: &Path, _key: &str) -> std::io::Result<Option<WorkspaceState>> {
+        Err(unsupported())
+    }
+
+    pub(super) fn update(
+        _db: &Path,
+        _key: &str,
+        _update: impl FnOnce(&mut WorkspaceState),
+    ) -> std::io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub(super) fn list(_db: &Path) -> std::io::Result<Vec<WorkspaceStateEntry>> {
+        Err(unsupported())
+    }
+
+    pub(super) fn remove(_db: &Path, _key: &str) -> std::io::Result<bool> {
+        Err(unsupported())
+    }
+
+    pub(super) fn create(
+        _codex_home: &Path,
+        _db: &Path,
+        _entries: &[WorkspaceStateEntry],
+    ) -> std::io::Result<()> {
+        Err(unsupported())
+    }
+}
+
+/// Move saved workspace state into `backend` if it lives in the other store,
+/// then delete the other store so switching back never reads stale data.
+/// Switching to SQLite imports every TOML file and removes them; switching
+/// back replaces the TOML files with the rows and deletes the database.
+pub fn ensure_workspace_state_backend(
+    codex_home: &Path,
+    backend: WorkspaceStateBackend,
+) -> std::io::Result<()> {
+    match (backend, sqlite_store(codex_home)) {
+        (WorkspaceStateBackend::Sqlite, None) => {
+            let entries = list_toml_states(codex_home)?;
+            sqlite::create(codex_home, &codex_home.join(SQLITE_STORE_FILE), &entries)?;
+            for entry in &entries {
+                remove_state_file(&entry.file)?;
+            }
+            Ok(())
+        }
+        (WorkspaceStateBackend::Toml, Some(db)) => {
+            let dir = codex_home.join(WORKSPACE_STATE_DIR);
+            fs::create_dir_all(&dir)?;
+            // Files written before an earlier switch to SQLite are out of date.
+            for entry in list_toml_states(codex_home)? {
+                remove_state_file(&entry.file)?;
+            }
+            for entry in sqlite::list(&db)? {
+                write_state_file(&dir.join(format!("{}.toml", entry.key)), &entry.state)?;
+            }
+            fs::remove_file(&db)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// One workspace with saved state.
+#[derive(Debug, Clone)]
+pub struct WorkspaceStateEntry {
+    /// Hash identifying the workspace in the store.
+    pub key: String,
+    /// The state file, or the database for the SQLite store.
+    pub file: PathBuf,
+    pub state: WorkspaceState,
+    pub modified: Option<SystemTime>,
+}
+
+impl WorkspaceStateEntry {
+    /// `last_used_at`, or the time the entry was last written for state saved
+    /// before it was recorded.
+    pub fn last_used(&self) -> Option<SystemTime> {
+        self.state
+            .last_used_at
+            .map(SystemTime::from)
+            .or(self.modified)
+    }
+}
+
+/// Every workspace with saved state, sorted by path. Entries that fail to
+/// parse are skipped with a warning.
+pub fn list_workspace_states(codex_home: &Path) -> std::io::Result<Vec<WorkspaceStateEntry>> {
+    let mut entries = match sqlite_store(codex_home) {
+        Some(db) => sqlite::list(&db)?,
+        None => list_toml_states(codex_home)?,
+    };
+    entries.sort_by(|a, b| {
+        a.state
+            .workspace_path
+            .cmp(&b.state.workspace_path)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    Ok(entries)
+}
+
+fn list_toml_states(codex_home: &Path) -> std::io::Result<Vec<WorkspaceStateEntry>> {
+    let dir = codex_home.join(WORKSPACE_STATE_DIR);
+    let read_dir = match fs::read_dir(&dir) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut entries = Vec::new();
+    for dir_entry in read_dir {
+        let file = match dir_entry {
+            Ok(dir_entry) => dir_entry.path(),
+            Err(err) => {
+                warn!("Failed to read an entry of {}: {err}", dir.display());
+                continue;
+            }
+        };
+        if file.extension().is_none_or(|ext| ext != "toml") {
+            continue;
+        }
+        let Some(key) = file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+        else {
+            continue;
+        };
+        // One unreadable file should not hide every other workspace.
+        let contents = match fs::read_to_string(&file) {
+            Ok(contents) => contents,
+            Err(err) => {
+                warn!(
+                    "Failed to read workspace state from {}: {err}",
+                    file.display()
+                );
+                continue;
+            }
+        };
+        let state = match toml::from_str::<WorkspaceState>(&contents) {
+            Ok(state) => state,
+            Err(err) => {
+                warn!(
+                    "Failed to parse workspace state from {}: {err}",
+                    file.display()
+                );
+                continue;
+            }
+        };
+        let modified = fs::metadata(&file)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        entries.push(WorkspaceStateEntry {
+            key,
+            file,
+            state,
+            modified,
+        });
+    }
+    Ok(entries)
+}
+
+/// Delete the state persisted for `workspace`. Returns whether any existed.
+pub fn clear_workspace_state(codex_home: &Path, workspace: &Path) -> std::io::Result<bool> {
+    match sqlite_store(codex_home) {
+        Some(db) => sqlite::remove(&db, &workspace_key(workspace)),
+        None => remove_state_file(&workspace_state_path(codex_home, workspace)),
+    }
+}
+
+/// Delete an entry found by [`list_workspace_states`]. Returns whether it
+/// still existed.
+pub fn remove_workspace_state_entry(
+    codex_home: &Path,
+    entry: &WorkspaceStateEntry,
+) -> std::io::Result<bool> {
+    match sqlite_store(codex_home) {
+        Some(db) => sqlite::remove(&db, &entry.key),
+        None => remove_state_file(&entry.file),
+    }
+}
+
+/// Delete a state file under its lock. The lock file stays: another
+/// instance may be waiting on it, and removing it would let the next writer
+/// lock a fresh file while that one still holds the old.
+fn remove_state_file(file: &Path) -> std::io::Result<bool> {
+    if !file.exists() {
+        return Ok(false);
+    }
+    let _lock = WorkspaceStateLock::acquire_blocking(file)?;
+    match fs::remove_file(file) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+fn lock_path(state_file: &Path) -> PathBuf {
+    let mut name = state_file.as_os_str().to_owned();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
+/// Exclusive advisory lock on a workspace's `<state>.toml.lock`, held across
+/// a load-modify-persist so concurrent Codex instances do not drop each
+/// other's updates. The state file itself is replaced on every write, so it
+/// cannot carry the lock.
+struct WorkspaceStateLock {
+    file: File,
+}
+
+impl WorkspaceStateLock {
+    /// One attempt at the lock; `None` while another instance holds it.
+    fn try_acquire(state_file: &Path) -> std::io::Result<Option<Self>> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(lock_path(state_file))?;
+        match FileExt::try_lock_exclusive(&file) {
+            Ok(()) => Ok(Some(Self { file })),
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Waits for the lock by sleeping the thread, for the CLI. Returns `None`
+    /// after [`LOCK_TIMEOUT`] of contention.
+    fn acquire_blocking(state_file: &Path) -> std::io::Result<Option<Self>> {
+        let started = Instant::now();
+        loop {
+            if let Some(lock) = Self::try_acquire(state_file)? {
+                return Ok(Some(lock));
+            }
+            if lock_wait_expired(started, state_file) {
+                return Ok(None);
+            }
+            std::thread::sleep(LOCK_RETRY_SLEEP);
+        }
+    }
+
+    /// Like [`Self::acquire_blocking`], but yields to the runtime between
+    /// attempts so the TUI keeps drawing while another instance writes.
+    async fn acquire(state_file: &Path) -> std::io::Result<Option<Self>> {
+        let started = Instant::now();
+        loop {
+            if let Some(lock) = Self::try_acquire(state_file)? {
+                return Ok(Some(lock));
+            }
+            if lock_wait_expired(started, state_file) {
+                return Ok(None);
+            }
+            tokio::time::sleep(LOCK_RETRY_SLEEP).await;
+        }
+    }
+}
+
+fn lock_wait_expired(started: Instant, state_file: &Path) -> bool {
+    let expired = started.elapsed() >= LOCK_TIMEOUT;
+    if expired {
+        warn!(
+            "{} is still locked after {LOCK_TIMEOUT:?}; writing workspace state without the lock",
+            lock_path(state_file).display()
+        );
+    }
+    expired
+}
+
+impl Drop for WorkspaceStateLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Load, modify and persist the state for `workspace` under its lock.
+fn update_workspace_state(
+    codex_home: &Path,
+    workspace: &Path,
+    update: impl FnOnce(&mut WorkspaceState),
+) -> std::io::Result<()> {
+    if let Some(db) = sqlite_store(codex_home) {
+        return sqlite::update(&db, &workspace_key(workspace), |state| {
+            update(state);
+            prepare_for_persist(workspace, state);
+        });
+    }
+
+    let path = workspace_state_path(codex_home, workspace);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let _lock = WorkspaceStateLock::acquire_blocking(&path)?;
+    write_update(codex_home, workspace, &path, update)
+}
+
+/// [`update_workspace_state`] for the TUI: waiting on another instance's
+/// lock does not block the thread.
+async fn update_workspace_state_async(
+    codex_home: &Path,
+    workspace: &Path,
+    update: impl FnOnce(&mut WorkspaceState),
+) -> std::io::Result<()> {
+    if let Some(db) = sqlite_store(codex_home) {
+        return sqlite::update(&db, &workspace_key(workspace), |state| {
+            update(state);
+            prepare_for_persist(workspace, state);
+        });
+    }
+
+    let path = workspace_state_path(codex_home, workspace);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let _lock = WorkspaceStateLock::acquire(&path).await?;
+    write_update(codex_home, workspace, &path, update)
+}
+
+fn write_update(
+    codex_home: &Path,
+    workspace: &Path,
+    path: &Path,
+    update: impl FnOnce(&mut WorkspaceState),
+) -> std::io::Result<()> {
+    let mut state = load_workspace_state(codex_home, workspace)?;
+    update(&mut state);
+    prepare_for_persist(workspace, &mut state);
+    write_state_file(path, &state)
 }
 
 pub fn load_workspace_state(
     codex_home: &Path,
     workspace: &Path,
 ) -> std::io::Result<WorkspaceState> {
+    if let Some(db) = sqlite_store(codex_home) {
+        return Ok(sqlite::load(&db, &workspace_key(workspace))?.unwrap_or_default());
+    }
+
     let path = workspace_state_path(codex_home, workspace);
     let contents = match fs::read_to_string(&path) {
         Ok(contents) => contents,
@@ -60,27 +708,22 @@ pub fn load_workspace_state(
     }
 }
 
-fn persist_workspace_state(
-    codex_home: &Path,
-    workspace: &Path,
-    mut state: WorkspaceState,
-) -> std::io::Result<()> {
+fn prepare_for_persist(workspace: &Path, state: &mut WorkspaceState) {
     // Avoid storing empty MCP server entries with no data.
-    state.mcp_servers.retain(|_, entry| entry.enabled.is_some());
-
-    let path = workspace_state_path(codex_home, workspace);
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
+    state.mcp_servers.retain(|_, entry| !entry.is_empty());
+    state.workspace_path = Some(canonical_workspace(workspace));
+    state.last_used_at = Some(Utc::now());
+}
 
+fn write_state_file(path: &Path, state: &WorkspaceState) -> std::io::Result<()> {
     let mut temp = NamedTempFile::new_in(
         path.parent()
             .ok_or_else(|| std::io::Error::other("missing parent dir"))?,
     )?;
-    let serialized = toml::to_string_pretty(&state).map_err(std::io::Error::other)?;
+    let serialized = toml::to_string_pretty(state).map_err(std::io::Error::other)?;
     temp.write_all(serialized.as_bytes())?;
     temp.flush()?;
-    temp.persist(&path).map_err(|err| err.error)?;
+    temp.persist(path).map_err(|err| err.error)?;
     Ok(())
 }
 
@@ -90,10 +733,43 @@ pub fn persist_model_selection(
     model: &str,
     effort: Option<ReasoningEffort>,
 ) -> std::io::Result<()> {
-    let mut state = load_workspace_state(codex_home, workspace)?;
-    state.model = Some(model.to_string());
-    state.model_reasoning_effort = effort;
-    persist_workspace_state(codex_home, workspace, state)
+    update_workspace_state(codex_home, workspace, |state| {
+        state.model = Some(model.to_string());
+        state.model_reasoning_effort = effort;
+    })
+}
+
+/// Remember the approval policy and sandbox chosen for `workspace`. Writable
+/// roots and network access are not persisted; the sandbox is stored as its
+/// mode.
+pub async fn persist_policy_selection(
+    codex_home: &Path,
+    workspace: &Path,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+) -> std::io::Result<()> {
+    update_workspace_state_async(codex_home, workspace, |state| {
+        state.approval_policy = Some(approval_policy);
+        state.sandbox_mode = Some(sandbox_mode_for(sandbox_policy));
+    })
+    .await
+}
+
+/// The approval policy and sandbox mode persisted for `workspace`, if any.
+pub fn load_policy_selection(
+    codex_home: &Path,
+    workspace: &Path,
+) -> std::io::Result<(Option<AskForApproval>, Option<SandboxMode>)> {
+    let state = load_workspace_state(codex_home, workspace)?;
+    Ok((state.approval_policy, state.sandbox_mode))
+}
+
+fn sandbox_mode_for(policy: &SandboxPolicy) -> SandboxMode {
+    match policy {
+        SandboxPolicy::DangerFullAccess => SandboxMode::DangerFullAccess,
+        SandboxPolicy::ReadOnly => SandboxMode::ReadOnly,
+        SandboxPolicy::WorkspaceWrite { .. } => SandboxMode::WorkspaceWrite,
+    }
 }
 
 pub fn persist_mcp_enabled(
@@ -102,13 +778,168 @@ pub fn persist_mcp_enabled(
     server: &str,
     enabled: bool,
 ) -> std::io::Result<()> {
-    let mut state = load_workspace_state(codex_home, workspace)?;
-    state
-        .mcp_servers
-        .entry(server.to_string())
-        .or_default()
-        .enabled = Some(enabled);
-    persist_workspace_state(codex_home, workspace, state)
+    update_workspace_mcp_server(codex_home, workspace, server, |overrides| {
+        overrides.enabled = Some(enabled);
+    })
+}
+
+/// Edit the overrides saved for MCP server `server` in `workspace`.
+pub fn update_workspace_mcp_server(
+    codex_home: &Path,
+    workspace: &Path,
+    server: &str,
+    update: impl FnOnce(&mut WorkspaceMcpServerState),
+) -> std::io::Result<()> {
+    update_workspace_state(codex_home, workspace, |state| {
+        update(state.mcp_servers.entry(server.to_string()).or_default());
+    })
+}
+
+/// Apply the MCP overrides saved for `workspace` to `servers`. Overrides for
+/// servers that are not configured are ignored.
+pub fn apply_workspace_mcp_overrides(
+    codex_home: &Path,
+    workspace: &Path,
+    servers: &mut HashMap<String, McpServerConfig>,
+) -> std::io::Result<()> {
+    let state = load_workspace_state(codex_home, workspace)?;
+    for (name, overrides) in &state.mcp_servers {
+        match servers.get_mut(name) {
+            Some(server) => overrides.apply_to(name, server),
+            None => {
+                tracing::debug!("workspace overrides MCP server {name}, which is not configured")
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Set (`Some`) or remove (`None`) an environment variable for commands run
+/// in `workspace`.
+pub fn persist_env_var(
+    codex_home: &Path,
+    workspace: &Path,
+    key: &str,
+    value: Option<&str>,
+) -> std::io::Result<()> {
+    update_workspace_state(codex_home, workspace, |state| match value {
+        Some(value) => {
+            state.env.insert(key.to_string(), value.to_string());
+        }
+        None => {
+            state.env.remove(key);
+        }
+    })
+}
+
+/// Set or clear the instruction text pinned to `workspace`.
+pub fn persist_pinned_instructions(
+    codex_home: &Path,
+    workspace: &Path,
+    instructions: Option<&str>,
+) -> std::io::Result<()> {
+    update_workspace_state(codex_home, workspace, |state| {
+        state.pinned_instructions = instructions
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(ToOwned::to_owned);
+    })
+}
+
+/// Replace the extra project doc files pinned to `workspace`.
+pub fn persist_pinned_docs(
+    codex_home: &Path,
+    workspace: &Path,
+    docs: Vec<PathBuf>,
+) -> std::io::Result<()> {
+    update_workspace_state(codex_home, workspace, |state| {
+        state.pinned_docs = docs;
+    })
+}
+
+/// Bump `last_used_at` for `workspace` when a session starts there, so a
+/// workspace whose saved settings are still read is not reported as stale.
+/// Does nothing when nothing is saved for it.
+pub async fn touch_workspace_state(codex_home: &Path, workspace: &Path) -> std::io::Result<()> {
+    if !workspace_state_exists(codex_home, workspace)? {
+        return Ok(());
+    }
+    update_workspace_state_async(codex_home, workspace, |_| {}).await
+}
+
+/// Add one finished session's token usage to the totals for `workspace`.
+/// Sessions that used no tokens are not counted.
+pub async fn record_session_usage(
+    codex_home: &Path,
+    workspace: &Path,
+    usage: &TokenUsage,
+    price: Option<&ModelPrice>,
+) -> std::io::Result<()> {
+    if usage.is_zero() {
+        return Ok(());
+    }
+    update_workspace_state_async(codex_home, workspace, |state| {
+        state.usage.add_session(usage, price);
+    })
+    .await
+}
+
+/// Set or clear the budget for `workspace`. An empty budget clears it.
+pub fn persist_workspace_budget(
+    codex_home: &Path,
+    workspace: &Path,
+    budget: Option<BudgetConfig>,
+) -> std::io::Result<()> {
+    update_workspace_state(codex_home, workspace, |state| {
+        state.budget = budget.filter(|budget| !budget.is_empty());
+    })
+}
+
+/// The budget saved for `workspace`, if any.
+pub fn load_workspace_budget(
+    codex_home: &Path,
+    workspace: &Path,
+) -> std::io::Result<Option<WorkspaceBudget>> {
+    let state = load_workspace_state(codex_home, workspace)?;
+    Ok(state.budget.map(|limits| WorkspaceBudget {
+        limits,
+        usage: state.usage,
+    }))
+}
+
+/// Remember `id` as the session that most recently ended in `workspace`.
+pub async fn persist_last_session(
+    codex_home: &Path,
+    workspace: &Path,
+    id: ConversationId,
+) -> std::io::Result<()> {
+    update_workspace_state_async(codex_home, workspace, |state| {
+        state.last_session = Some(WorkspaceLastSession {
+            id,
+            ended_at: Utc::now(),
+        });
+    })
+    .await
+}
+
+/// Turn the startup offer to resume the last session on or off.
+pub async fn persist_resume_prompt(
+    codex_home: &Path,
+    workspace: &Path,
+    enabled: bool,
+) -> std::io::Result<()> {
+    update_workspace_state_async(codex_home, workspace, |state| {
+        state.resume_prompt = Some(enabled);
+    })
+    .await
+}
+
+/// Environment variables persisted for `workspace`.
+pub fn load_workspace_env(
+    codex_home: &Path,
+    workspace: &Path,
+) -> std::io::Result<HashMap<String, String>> {
+    Ok(load_workspace_state(codex_home, workspace)?.env)
 }
 
 #[cfg(test)]
@@ -141,4 +972,430 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn lists_and_clears_workspace_states() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let first = TempDir::new().expect("workspace");
+        let second = TempDir::new().expect("workspace");
+        assert!(list_workspace_states(codex_home.path())?.is_empty());
+
+        persist_model_selection(codex_home.path(), first.path(), "gpt-5-codex", None)?;
+        persist_mcp_enabled(codex_home.path(), second.path(), "docs", false)?;
+
+        let entries = list_workspace_states(codex_home.path())?;
+        let paths: Vec<PathBuf> = entries
+            .iter()
+            .filter_map(|entry| entry.state.workspace_path.clone())
+            .collect();
+        let mut expected = vec![
+            canonical_workspace(first.path()),
+            canonical_workspace(second.path()),
+        ];
+        expected.sort();
+        assert_eq!(paths, expected);
+        assert!(entries.iter().all(|entry| entry.modified.is_some()));
+
+        assert!(clear_workspace_state(codex_home.path(), first.path())?);
+        assert!(!clear_workspace_state(codex_home.path(), first.path())?);
+        let remaining = list_workspace_states(codex_home.path())?;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(
+            remaining[0].state.workspace_path,
+            Some(canonical_workspace(second.path()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reads_state_written_before_path_and_timestamp() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+        let path = workspace_state_path(codex_home.path(), workspace.path());
+        fs::create_dir_all(path.parent().expect("parent"))?;
+        fs::write(&path, "model = \"gpt-5\"\n")?;
+
+        let entries = list_workspace_states(codex_home.path())?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].state.workspace_path, None);
+        assert_eq!(entries[0].state.last_used_at, None);
+        assert_eq!(entries[0].last_used(), entries[0].modified);
+
+        let before = Utc::now();
+        persist_mcp_enabled(codex_home.path(), workspace.path(), "docs", true)?;
+        let state = load_workspace_state(codex_home.path(), workspace.path())?;
+        assert_eq!(state.model.as_deref(), Some("gpt-5"));
+        assert_eq!(
+            state.workspace_path,
+            Some(canonical_workspace(workspace.path()))
+        );
+        assert!(state.last_used_at.is_some_and(|at| at >= before));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn touching_bumps_last_used_only_for_saved_workspaces() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+        touch_workspace_state(codex_home.path(), workspace.path()).await?;
+        assert!(!workspace_state_exists(
+            codex_home.path(),
+            workspace.path()
+        )?);
+
+        let path = workspace_state_path(codex_home.path(), workspace.path());
+        fs::create_dir_all(path.parent().expect("parent"))?;
+        fs::write(&path, "model = \"gpt-5\"\n")?;
+        let before = Utc::now();
+        touch_workspace_state(codex_home.path(), workspace.path()).await?;
+        let state = load_workspace_state(codex_home.path(), workspace.path())?;
+        assert_eq!(state.model.as_deref(), Some("gpt-5"));
+        assert!(state.last_used_at.is_some_and(|at| at >= before));
+        Ok(())
+    }
+
+    #[test]
+    fn unreadable_state_files_are_skipped_when_listing() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+        persist_mcp_enabled(codex_home.path(), workspace.path(), "docs", true)?;
+        // A directory where a state file is expected cannot be read as one.
+        fs::create_dir_all(
+            codex_home
+                .path()
+                .join(WORKSPACE_STATE_DIR)
+                .join("broken.toml"),
+        )?;
+
+        let entries = list_workspace_states(codex_home.path())?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].state.workspace_path,
+            Some(canonical_workspace(workspace.path()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn persists_env_vars() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+
+        persist_env_var(
+            codex_home.path(),
+            workspace.path(),
+            "AWS_PROFILE",
+            Some("staging"),
+        )?;
+        persist_env_var(
+            codex_home.path(),
+            workspace.path(),
+            "DATABASE_URL",
+            Some("postgres://db"),
+        )?;
+        persist_env_var(codex_home.path(), workspace.path(), "DATABASE_URL", None)?;
+
+        let env = load_workspace_env(codex_home.path(), workspace.path())?;
+        assert_eq!(
+            env,
+            HashMap::from([("AWS_PROFILE".to_string(), "staging".to_string())])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn records_session_usage_and_estimated_cost() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            cached_input_tokens: 400_000,
+            output_tokens: 100_000,
+            reasoning_output_tokens: 0,
+            total_tokens: 1_100_000,
+        };
+        let price = ModelPrice {
+            input: 1.25,
+            cached_input: Some(0.125),
+            output: 10.0,
+        };
+
+        record_session_usage(codex_home.path(), workspace.path(), &usage, Some(&price)).await?;
+        record_session_usage(codex_home.path(), workspace.path(), &usage, None).await?;
+        record_session_usage(
+            codex_home.path(),
+            workspace.path(),
+            &TokenUsage::default(),
+            None,
+        )
+        .await?;
+
+        let state = load_workspace_state(codex_home.path(), workspace.path())?;
+        assert_eq!(
+            state.usage,
+            WorkspaceUsage {
+                sessions: 2,
+                input_tokens: 2_000_000,
+                cached_input_tokens: 800_000,
+                output_tokens: 200_000,
+                estimated_cost_usd: 1.8,
+                unpriced_sessions: 1,
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn workspace_budget_carries_past_usage() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+        assert_eq!(
+            load_workspace_budget(codex_home.path(), workspace.path())?,
+            None
+        );
+
+        let usage = TokenUsage {
+            input_tokens: 3_000,
+            output_tokens: 1_000,
+            total_tokens: 4_000,
+            ..Default::default()
+        };
+        record_session_usage(codex_home.path(), workspace.path(), &usage, None).await?;
+        let limits = BudgetConfig {
+            max_usd: Some(20.0),
+            max_tokens: None,
+        };
+        persist_workspace_budget(codex_home.path(), workspace.path(), Some(limits))?;
+        let budget =
+            load_workspace_budget(codex_home.path(), workspace.path())?.expect("workspace budget");
+        assert_eq!(budget.limits, limits);
+        assert_eq!(budget.usage.total_tokens(), 4_000);
+
+        persist_workspace_budget(
+            codex_home.path(),
+            workspace.path(),
+            Some(BudgetConfig::default()),
+        )?;
+        assert_eq!(
+            load_workspace_budget(codex_home.path(), workspace.path())?,
+            None
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn persists_last_session_and_resume_prompt() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+        let first = ConversationId::new();
+        let second = ConversationId::new();
+
+        persist_last_session(codex_home.path(), workspace.path(), first).await?;
+        persist_last_session(codex_home.path(), workspace.path(), second).await?;
+        persist_resume_prompt(codex_home.path(), workspace.path(), false).await?;
+
+        let state = load_workspace_state(codex_home.path(), workspace.path())?;
+        assert_eq!(state.last_session.map(|session| session.id), Some(second));
+        assert_eq!(state.resume_prompt, Some(false));
+        Ok(())
+    }
+
+    #[test]
+    fn concurrent_updates_are_not_lost() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+
+        std::thread::scope(|scope| {
+            for writer in 0..4 {
+                let codex_home = codex_home.path();
+                let workspace = workspace.path();
+                scope.spawn(move || {
+                    for server in 0..5 {
+                        persist_mcp_enabled(
+                            codex_home,
+                            workspace,
+                            &format!("server-{writer}-{server}"),
+                            false,
+                        )
+                        .expect("persist mcp");
+                    }
+                });
+            }
+        });
+
+        let state = load_workspace_state(codex_home.path(), workspace.path())?;
+        assert_eq!(state.mcp_servers.len(), 20);
+        let file = workspace_state_path(codex_home.path(), workspace.path());
+        assert!(lock_path(&file).exists());
+        assert!(clear_workspace_state(codex_home.path(), workspace.path())?);
+        assert!(!file.exists());
+        assert!(lock_path(&file).exists());
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite-workspace-state")]
+    #[test]
+    fn concurrent_sqlite_updates_are_not_lost() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+        ensure_workspace_state_backend(codex_home.path(), WorkspaceStateBackend::Sqlite)?;
+
+        std::thread::scope(|scope| {
+            for writer in 0..4 {
+                let codex_home = codex_home.path();
+                let workspace = workspace.path();
+                scope.spawn(move || {
+                    for server in 0..5 {
+                        persist_mcp_enabled(
+                            codex_home,
+                            workspace,
+                            &format!("server-{writer}-{server}"),
+                            false,
+                        )
+                        .expect("persist mcp");
+                    }
+                });
+            }
+        });
+
+        let state = load_workspace_state(codex_home.path(), workspace.path())?;
+        assert_eq!(state.mcp_servers.len(), 20);
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite-workspace-state")]
+    #[test]
+    fn migrates_between_toml_and_sqlite_stores() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let first = TempDir::new().expect("workspace");
+        let second = TempDir::new().expect("workspace");
+        persist_model_selection(codex_home.path(), first.path(), "gpt-5-codex", None)?;
+        persist_env_var(
+            codex_home.path(),
+            second.path(),
+            "AWS_PROFILE",
+            Some("staging"),
+        )?;
+        let db = codex_home.path().join(SQLITE_STORE_FILE);
+
+        // Switching to SQLite imports the TOML files and removes them.
+        let first_file = workspace_state_path(codex_home.path(), first.path());
+        ensure_workspace_state_backend(codex_home.path(), WorkspaceStateBackend::Sqlite)?;
+        assert!(db.exists());
+        assert!(!first_file.exists());
+        assert_eq!(
+            workspace_state_location(codex_home.path(), first.path()),
+            db
+        );
+        assert_eq!(
+            load_workspace_state(codex_home.path(), first.path())?
+                .model
+                .as_deref(),
+            Some("gpt-5-codex")
+        );
+        assert_eq!(list_workspace_states(codex_home.path())?.len(), 2);
+
+        // Later writes go to the database only.
+        persist_model_selection(codex_home.path(), first.path(), "gpt-5", None)?;
+        assert!(clear_workspace_state(codex_home.path(), second.path())?);
+        assert!(!workspace_state_exists(codex_home.path(), second.path())?);
+        ensure_workspace_state_backend(codex_home.path(), WorkspaceStateBackend::Sqlite)?;
+        let entries = list_workspace_states(codex_home.path())?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].file, db);
+        assert!(remove_workspace_state_entry(
+            codex_home.path(),
+            &entries[0]
+        )?);
+        persist_model_selection(codex_home.path(), first.path(), "gpt-5", None)?;
+
+        // Switching back writes the rows out as TOML and drops the database;
+        // the workspace cleared in SQLite does not come back.
+        ensure_workspace_state_backend(codex_home.path(), WorkspaceStateBackend::Toml)?;
+        assert!(!db.exists());
+        assert!(first_file.exists());
+        assert!(!workspace_state_exists(codex_home.path(), second.path())?);
+        assert_eq!(
+            load_workspace_state(codex_home.path(), first.path())?
+                .model
+                .as_deref(),
+            Some("gpt-5")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn persists_policy_selection_alongside_model() -> std::io::Result<()> {
+        let codex_home = TempDir::new().expect("tempdir");
+        let workspace = TempDir::new().expect("workspace");
+        assert_eq!(
+            load_policy_selection(codex_home.path(), workspace.path())?,
+            (None, None)
+        );
+
+        persist_model_selection(codex_home.path(), workspace.path(), "gpt-5-codex", None)?;
+        persist_policy_selection(
+            codex_home.path(),
+            workspace.path(),
+            AskForApproval::Never,
+            &SandboxPolicy::DangerFullAccess,
+        )
+        .await?;
+
+        assert_eq!(
+            load_policy_selection(codex_home.path(), workspace.path())?,
+            (
+                Some(AskForApproval::Never),
+                Some(SandboxMode::DangerFullAccess)
+            )
+        );
+        let state = load_workspace_state(codex_home.path(), workspace.path())?;
+        assert_eq!(state.model.as_deref(), Some("gpt-5-codex"));
+        let contents =
+            fs::read_to_string(workspace_state_path(codex_home.path(), workspace.path()))?;
+        assert!(
+            contents.contains("approval_policy = \"never\""),
+            "{contents}"
+        );
+        assert!(
+            contents.contains("sandbox_mode = \"danger-full-access\""),
+            "{contents}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn statusline_overrides_merge_over_global_settings() {
+        let state: WorkspaceState = toml::from_str(
+            r#"
+[statusline]
+skin = "minimal-plain"
+
+[statusline.segments]
+aws_profile = false
+kubernetes = false
+git = true
+"#,
+        )
+        .expect("workspace state should parse");
+        let statusline = state.statusline.expect("statusline section");
+
+        let mut global = StatusLineSettings::default();
+        global.segments.git = false;
+        let merged = statusline.apply_to(&global);
+
+        assert_eq!(merged.skin, "minimal-plain");
+        assert!(merged.segments.git);
+        assert!(!merged.segments.aws_profile);
+        assert!(!merged.segments.kubernetes);
+        assert!(merged.segments.hostname);
+        assert!(statusline.enabled_or(true));
+        assert!(
+            !WorkspaceStatusLineState {
+                enabled: Some(false),
+                ..Default::default()
+            }
+            .enabled_or(true)
+        );
+    }
 }
@@ -0,0 +1,170 @@
+//! SQLite store for workspace state, used instead of the per-workspace TOML
+//! files once `workspace_state_backend = "sqlite"` is set. Each row holds the
+//! same TOML document the file store writes, keyed by the workspace hash, so
+//! both stores share one serialization format.
+
+use std::path::Path;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::TransactionBehavior;
+use rusqlite::params;
+use tracing::warn;
+
+use super::LOCK_TIMEOUT;
+use super::WorkspaceState;
+use super::WorkspaceStateEntry;
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS workspace_state (
+    key TEXT PRIMARY KEY NOT NULL,
+    workspace_path TEXT,
+    state TEXT NOT NULL,
+    updated_at INTEGER NOT NULL
+);";
+
+fn open(db: &Path) -> std::io::Result<Connection> {
+    let conn = Connection::open(db).map_err(std::io::Error::other)?;
+    // Writers from other Codex instances hold the database lock only for a
+    // single read-modify-write, so waiting briefly is enough.
+    conn.busy_timeout(LOCK_TIMEOUT)
+        .map_err(std::io::Error::other)?;
+    conn.execute_batch(SCHEMA).map_err(std::io::Error::other)?;
+    Ok(conn)
+}
+
+fn parse_state(key: &str, contents: &str) -> WorkspaceState {
+    toml::from_str(contents).unwrap_or_else(|err| {
+        warn!("Failed to parse workspace state {key} from the SQLite store: {err}");
+        WorkspaceState::default()
+    })
+}
+
+fn select_state(conn: &Connection, key: &str) -> std::io::Result<Option<WorkspaceState>> {
+    let contents: Option<String> = conn
+        .query_row(
+            "SELECT state FROM workspace_state WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(std::io::Error::other)?;
+    Ok(contents.map(|contents| parse_state(key, &contents)))
+}
+
+fn upsert_state(
+    conn: &Connection,
+    key: &str,
+    state: &WorkspaceState,
+    updated_at: SystemTime,
+) -> std::io::Result<()> {
+    let serialized = toml::to_string_pretty(state).map_err(std::io::Error::other)?;
+    let workspace_path = state
+        .workspace_path
+        .as_ref()
+        .map(|path| path.to_string_lossy().into_owned());
+    conn.execute(
+        "INSERT INTO workspace_state (key, workspace_path, state, updated_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(key) DO UPDATE SET
+             workspace_path = excluded.workspace_path,
+             state = excluded.state,
+             updated_at = excluded.updated_at",
+        params![key, workspace_path, serialized, unix_seconds(updated_at)],
+    )
+    .map_err(std::io::Error::other)?;
+    Ok(())
+}
+
+pub(super) fn load(db: &Path, key: &str) -> std::io::Result<Option<WorkspaceState>> {
+    select_state(&open(db)?, key)
+}
+
+/// Load, modify and store one workspace's state in a single write
+/// transaction, so concurrent updates from other instances are not lost.
+pub(super) fn update(
+    db: &Path,
+    key: &str,
+    update: impl FnOnce(&mut WorkspaceState),
+) -> std::io::Result<()> {
+    let mut conn = open(db)?;
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(std::io::Error::other)?;
+    let mut state = select_state(&tx, key)?.unwrap_or_default();
+    update(&mut state);
+    upsert_state(&tx, key, &state, SystemTime::now())?;
+    tx.commit().map_err(std::io::Error::other)
+}
+
+pub(super) fn list(db: &Path) -> std::io::Result<Vec<WorkspaceStateEntry>> {
+    let conn = open(db)?;
+    let mut stmt = conn
+        .prepare("SELECT key, state, updated_at FROM workspace_state")
+        .map_err(std::io::Error::other)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })
+        .map_err(std::io::Error::other)?;
+
+    let mut entries = Vec::new();
+    for row in rows {
+        let (key, contents, updated_at) = row.map_err(std::io::Error::other)?;
+        let state = parse_state(&key, &contents);
+        entries.push(WorkspaceStateEntry {
+            key,
+            file: db.to_path_buf(),
+            state,
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(updated_at.max(0) as u64)),
+        });
+    }
+    Ok(entries)
+}
+
+pub(super) fn remove(db: &Path, key: &str) -> std::io::Result<bool> {
+    let removed = open(db)?
+        .execute("DELETE FROM workspace_state WHERE key = ?1", params![key])
+        .map_err(std::io::Error::other)?;
+    Ok(removed > 0)
+}
+
+/// Create the database at `db` holding `entries`. It is built next to `db`
+/// and moved into place at the end so other instances never see a partial
+/// import; if one of them finished first, its database is kept.
+pub(super) fn create(
+    codex_home: &Path,
+    db: &Path,
+    entries: &[WorkspaceStateEntry],
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(codex_home)?;
+    let temp = tempfile::Builder::new()
+        .prefix(".workspace_state")
+        .suffix(".sqlite")
+        .tempfile_in(codex_home)?;
+    {
+        let mut conn = open(temp.path())?;
+        let tx = conn.transaction().map_err(std::io::Error::other)?;
+        for entry in entries {
+            let updated_at = entry.last_used().unwrap_or_else(SystemTime::now);
+            upsert_state(&tx, &entry.key, &entry.state, updated_at)?;
+        }
+        tx.commit().map_err(std::io::Error::other)?;
+    }
+    match temp.persist_noclobber(db) {
+        Ok(_) => Ok(()),
+        Err(_) if db.exists() => Ok(()),
+        Err(err) => Err(err.error),
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+        .unwrap_or_default()
+}
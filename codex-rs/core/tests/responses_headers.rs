@@ -53,6 +53,7 @@ async fn responses_stream_includes_subagent_header_on_review() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        code88_auth: false,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -145,6 +146,7 @@ async fn responses_stream_includes_subagent_header_on_other() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        code88_auth: false,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -235,6 +237,7 @@ async fn responses_respects_model_family_overrides_from_config() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        code88_auth: false,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
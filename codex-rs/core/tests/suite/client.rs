@@ -1006,6 +1006,7 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        code88_auth: false,
     };
 
     let codex_home = TempDir::new().unwrap();
@@ -1500,6 +1501,7 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
+        code88_auth: false,
     };
 
     // Init session
@@ -1578,6 +1580,7 @@ async fn env_var_overrides_loaded_auth() {
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
+        code88_auth: false,
     };
 
     // Init session
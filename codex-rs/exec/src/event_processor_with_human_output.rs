@@ -18,6 +18,7 @@ use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::StructuredWarningEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnDiffEvent;
@@ -165,7 +166,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 let prefix = "ERROR:".style(self.red);
                 ts_msg!(self, "{prefix} {message}");
             }
-            EventMsg::Warning(WarningEvent { message }) => {
+            EventMsg::Warning(WarningEvent { message })
+            | EventMsg::StructuredWarning(StructuredWarningEvent { message, .. }) => {
                 ts_msg!(
                     self,
                     "{} {message}",
@@ -566,6 +568,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::WebSearchBegin(_)
             | EventMsg::ExecApprovalRequest(_)
             | EventMsg::ApplyPatchApprovalRequest(_)
+            | EventMsg::BudgetUpdate(_)
+            // Denied in `run_main`; core then reports the stop as an error.
+            | EventMsg::BudgetExceeded(_)
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
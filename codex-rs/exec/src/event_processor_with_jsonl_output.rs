@@ -46,8 +46,10 @@ use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::StructuredWarningEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
+use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -130,11 +132,12 @@ impl EventProcessorWithJsonOutput {
                 self.last_critical_error = Some(error.clone());
                 vec![ThreadEvent::Error(error)]
             }
-            EventMsg::Warning(ev) => {
+            EventMsg::Warning(WarningEvent { message })
+            | EventMsg::StructuredWarning(StructuredWarningEvent { message, .. }) => {
                 let item = ThreadItem {
                     id: self.get_next_item_id(),
                     details: ThreadItemDetails::Error(ErrorItem {
-                        message: ev.message.clone(),
+                        message: message.clone(),
                     }),
                 };
                 vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
@@ -31,6 +31,7 @@ use codex_core::protocol::AskForApproval;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
@@ -444,6 +445,16 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 })
                 .await?;
         }
+        if let EventMsg::BudgetExceeded(_) = &event.msg {
+            // Nobody can approve going over budget in exec mode; stop the task.
+            // Core reports the stop as an error, so the run still fails loudly.
+            conversation
+                .submit(Op::BudgetApproval {
+                    id: event.id.clone(),
+                    decision: ReviewDecision::Denied,
+                })
+                .await?;
+        }
         if matches!(event.msg, EventMsg::Error(_)) {
             error_seen = true;
         }
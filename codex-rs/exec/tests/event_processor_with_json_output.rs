@@ -15,7 +15,10 @@ use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::StructuredWarningEvent;
 use codex_core::protocol::WarningEvent;
+use codex_core::protocol::WarningKind;
+use codex_core::protocol::WarningSeverity;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_exec::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
 use codex_exec::exec_events::AgentMessageItem;
@@ -573,6 +576,30 @@ fn warning_event_produces_error_item() {
     );
 }
 
+#[test]
+fn structured_warning_event_produces_error_item() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let out = ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::StructuredWarning(StructuredWarningEvent {
+            kind: WarningKind::SandboxEscalation,
+            severity: WarningSeverity::Warning,
+            message: "`cargo test` is running outside the sandbox".to_string(),
+        }),
+    ));
+    assert_eq!(
+        out,
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::Error(ErrorItem {
+                    message: "`cargo test` is running outside the sandbox".to_string(),
+                }),
+            },
+        })]
+    );
+}
+
 #[test]
 fn stream_error_event_produces_error() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -19,6 +19,7 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::Submission;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_protocol::ConversationId;
@@ -206,13 +207,29 @@ async fn run_codex_tool_session_inner(
                         outgoing.send_response(request_id.clone(), result).await;
                         break;
                     }
-                    EventMsg::Warning(_) => {
+                    EventMsg::Warning(_) | EventMsg::StructuredWarning(_) => {
                         continue;
                     }
                     EventMsg::ElicitationRequest(_) => {
                         // TODO: forward elicitation requests to the client?
                         continue;
                     }
+                    EventMsg::BudgetExceeded(_) => {
+                        // MCP clients cannot raise a budget; end the task
+                        // instead of leaving it paused. Core follows up with
+                        // an `Error` event, which ends the tool call with the
+                        // reason.
+                        if let Err(err) = codex
+                            .submit(Op::BudgetApproval {
+                                id: event.id.clone(),
+                                decision: ReviewDecision::Denied,
+                            })
+                            .await
+                        {
+                            tracing::error!("failed to submit BudgetApproval: {err}");
+                        }
+                        continue;
+                    }
                     EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
                         call_id,
                         turn_id: _,
@@ -275,6 +292,7 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::AgentReasoningRawContentDelta(_)
                     | EventMsg::TaskStarted(_)
                     | EventMsg::TokenCount(_)
+                    | EventMsg::BudgetUpdate(_)
                     | EventMsg::AgentReasoning(_)
                     | EventMsg::AgentReasoningSectionBreak(_)
                     | EventMsg::McpToolCallBegin(_)
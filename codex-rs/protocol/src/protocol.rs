@@ -157,6 +157,15 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Answer a [`EventMsg::BudgetExceeded`] request: approving lets the task
+    /// keep running past the budget, anything else ends it.
+    BudgetApproval {
+        /// The id of the submission whose task is paused
+        id: String,
+        /// The user's decision in response to the request.
+        decision: ReviewDecision,
+    },
+
     /// Resolve an MCP elicitation request.
     ResolveElicitation {
         /// Name of the MCP server that issued the request.
@@ -468,10 +477,18 @@ pub enum EventMsg {
     /// Agent has completed all actions
     TaskComplete(TaskCompleteEvent),
 
+    /// Something worth keeping in view after it scrolls by, such as a rate
+    /// limit nearly used up or a command run outside the sandbox.
+    StructuredWarning(StructuredWarningEvent),
+
     /// Usage update for the current session, including totals and last turn.
     /// Optional means unknown — UIs should not display when `None`.
     TokenCount(TokenCountEvent),
 
+    /// Spending against the session and workspace budgets, sent with every
+    /// usage update while a budget is configured.
+    BudgetUpdate(BudgetUpdateEvent),
+
     /// Agent text output message
     AgentMessage(AgentMessageEvent),
 
@@ -529,6 +546,10 @@ pub enum EventMsg {
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    /// A budget has been used up; the task is paused until the user answers
+    /// with [`Op::BudgetApproval`].
+    BudgetExceeded(BudgetExceededEvent),
+
     /// Notification advising the user that something they are using has been
     /// deprecated and should be phased out.
     DeprecationNotice(DeprecationNoticeEvent),
@@ -746,6 +767,40 @@ pub struct WarningEvent {
     pub message: String,
 }
 
+/// A warning clients can sort and color by what raised it and how serious
+/// it is. Clients that do not care can show `message` like a `Warning`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct StructuredWarningEvent {
+    pub kind: WarningKind,
+    pub severity: WarningSeverity,
+    pub message: String,
+}
+
+/// What raised a `StructuredWarningEvent`. Kinds that describe an ongoing
+/// condition replace their previous warning instead of adding another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// The sign-in was rejected or could not be refreshed. Tokens are
+    /// refreshed on demand and their expiry is not known in advance, so there
+    /// is no warning before a sign-in expires.
+    Auth,
+    /// A rate limit window is nearly used up.
+    RateLimit,
+    /// A command was approved to run outside the sandbox.
+    SandboxEscalation,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize, JsonSchema, TS,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
@@ -842,6 +897,85 @@ pub struct TokenCountEvent {
     pub rate_limits: Option<RateLimitSnapshot>,
 }
 
+/// Which usage a budget limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetScope {
+    /// This session, from `[budget]` in config.toml.
+    Session,
+    /// Every session in the workspace, including earlier ones.
+    Workspace,
+}
+
+/// Spending against one budget. Limits that are not set are `None`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct BudgetUsage {
+    pub scope: BudgetScope,
+    pub spent_tokens: i64,
+    pub max_tokens: Option<i64>,
+    /// `None` when the model has no `[model_prices]` entry.
+    pub spent_usd: Option<f64>,
+    pub max_usd: Option<f64>,
+}
+
+impl BudgetUsage {
+    /// The largest share of a limit used so far, e.g. `0.8` at 80%; `None`
+    /// when no limit can be measured.
+    pub fn used_fraction(&self) -> Option<f64> {
+        let tokens = self
+            .max_tokens
+            .filter(|max| *max > 0)
+            .map(|max| self.spent_tokens as f64 / max as f64);
+        let usd = self
+            .max_usd
+            .filter(|max| *max > 0.0)
+            .zip(self.spent_usd)
+            .map(|(max, spent)| spent / max);
+        match (tokens, usd) {
+            (Some(tokens), Some(usd)) => Some(tokens.max(usd)),
+            (tokens, usd) => tokens.or(usd),
+        }
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.used_fraction().is_some_and(|fraction| fraction >= 1.0)
+    }
+
+    /// The spend against whichever limit is closest to being reached, e.g.
+    /// `$4.20 of $5.00` or `41000 of 50000 tokens`.
+    pub fn spend_text(&self) -> String {
+        let usd = self
+            .max_usd
+            .zip(self.spent_usd)
+            .map(|(max, spent)| (spent / max, format!("${spent:.2} of ${max:.2}")));
+        let tokens = self.max_tokens.filter(|max| *max > 0).map(|max| {
+            (
+                self.spent_tokens as f64 / max as f64,
+                format!("{} of {max} tokens", self.spent_tokens),
+            )
+        });
+        match (usd, tokens) {
+            (Some(usd), Some(tokens)) if tokens.0 > usd.0 => tokens.1,
+            (Some(usd), _) => usd.1,
+            (None, Some(tokens)) => tokens.1,
+            (None, None) => format!("{} tokens", self.spent_tokens),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct BudgetUpdateEvent {
+    /// The session budget first, then the workspace budget.
+    pub budgets: Vec<BudgetUsage>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct BudgetExceededEvent {
+    /// Turn ID that this request belongs to.
+    pub turn_id: String,
+    pub budget: BudgetUsage,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
 pub struct RateLimitSnapshot {
     pub primary: Option<RateLimitWindow>,
@@ -0,0 +1,33 @@
+use codex_tui::statusline_bench::BenchStatusLine;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use std::hint::black_box;
+
+const WIDTHS: [u16; 3] = [40, 80, 200];
+
+fn status_line(c: &mut Criterion) {
+    let status_line = BenchStatusLine::new();
+    let mut group = c.benchmark_group("status_line");
+    for width in WIDTHS {
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |b, width| {
+            b.iter(|| status_line.render_line(black_box(*width)));
+        });
+    }
+    group.finish();
+}
+
+fn run_pill(c: &mut Criterion) {
+    let status_line = BenchStatusLine::new();
+    let mut group = c.benchmark_group("run_pill");
+    for width in WIDTHS {
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |b, width| {
+            b.iter(|| status_line.render_run_pill(black_box(*width)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, status_line, run_pill);
+criterion_main!(benches);
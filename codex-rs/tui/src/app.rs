@@ -11,14 +11,17 @@ use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_config;
 use crate::model_migration::run_model_migration_prompt;
 use crate::pager_overlay::Overlay;
+use crate::release_check::newer_release;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::ResumeSelection;
 use crate::skill_error_prompt::SkillErrorPromptOutcome;
 use crate::skill_error_prompt::run_skill_error_prompt;
+use crate::statusline::skins::skin_names;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
+use crate::version::BuildStatus;
 use codex_ansi_escape::ansi_escape_line;
 use codex_app_server_protocol::AuthMode;
 use codex_core::AuthManager;
@@ -36,6 +39,10 @@ use codex_core::protocol::SessionSource;
 use codex_core::protocol::TokenUsage;
 use codex_core::skills::load_skills;
 use codex_core::skills::model::SkillMetadata;
+use codex_core::workspace_state::persist_last_session;
+use codex_core::workspace_state::persist_policy_selection;
+use codex_core::workspace_state::record_session_usage;
+use codex_core::workspace_state::touch_workspace_state;
 use codex_protocol::ConversationId;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelUpgrade;
@@ -237,11 +244,16 @@ pub(crate) struct App {
     skip_world_writable_scan_once: bool,
 
     pub(crate) skills: Option<Vec<SkillMetadata>>,
+
+    /// This binary's commit and the newer release found at startup, shown
+    /// by every session's status line.
+    pub(crate) build: BuildStatus,
 }
 
 impl App {
     async fn shutdown_current_conversation(&mut self) {
         if let Some(conversation_id) = self.chat_widget.conversation_id() {
+            self.record_workspace_session().await;
             self.suppress_shutdown_complete = true;
             self.chat_widget.submit_op(Op::Shutdown);
             self.server.remove_conversation(&conversation_id).await;
@@ -259,6 +271,7 @@ impl App {
         resume_selection: ResumeSelection,
         feedback: codex_feedback::CodexFeedback,
         is_first_run: bool,
+        build_sha: Option<&'static str>,
     ) -> Result<AppExitInfo> {
         use tokio_stream::StreamExt;
         let (app_event_tx, mut app_event_rx) = unbounded_channel();
@@ -281,6 +294,10 @@ impl App {
             return Ok(exit_info);
         }
 
+        if let Err(err) = touch_workspace_state(&config.codex_home, &config.cwd).await {
+            tracing::warn!(error = %err, "failed to update workspace last-used time");
+        }
+
         let skills_outcome = load_skills(&config);
         if !skills_outcome.errors.is_empty() {
             match run_skill_error_prompt(tui, &skills_outcome.errors).await {
@@ -302,6 +319,10 @@ impl App {
         };
 
         let enhanced_keys_supported = tui.enhanced_keys_supported();
+        let build = BuildStatus {
+            sha: build_sha,
+            newer_release: newer_release(&config, build_sha),
+        };
 
         let mut chat_widget = match resume_selection {
             ResumeSelection::StartFresh | ResumeSelection::Exit => {
@@ -318,6 +339,7 @@ impl App {
                     skills: skills.clone(),
                     is_first_run,
                     status_renderer: None,
+                    build: build.clone(),
                 };
                 ChatWidget::new(init, conversation_manager.clone())
             }
@@ -345,6 +367,7 @@ impl App {
                     skills: skills.clone(),
                     is_first_run,
                     status_renderer: None,
+                    build: build.clone(),
                 };
                 ChatWidget::new_from_existing(
                     init,
@@ -380,6 +403,7 @@ impl App {
             suppress_shutdown_complete: false,
             skip_world_writable_scan_once: false,
             skills,
+            build,
         };
 
         // On startup, if Agent mode (workspace-write) or ReadOnly is active, warn about world-writable dirs on Windows.
@@ -431,7 +455,10 @@ impl App {
                 app.handle_tui_event(tui, event).await?
             }
         } {}
+        tui.set_terminal_progress(tui::TerminalProgress::Clear);
+        tui.restore_terminal_title();
         tui.terminal.clear()?;
+        app.record_workspace_session().await;
         Ok(AppExitInfo {
             token_usage: app.token_usage(),
             conversation_id: app.chat_widget.conversation_id(),
@@ -439,6 +466,29 @@ impl App {
         })
     }
 
+    /// Add the current session's token usage to this workspace's totals and
+    /// remember it as the session to offer for resuming next time.
+    async fn record_workspace_session(&self) {
+        let usage = self.chat_widget.session_token_usage();
+        // The widget's config follows `/model`, like the budgets core checks.
+        let config = self.chat_widget.config_ref();
+        let price = config.model_price(&config.model);
+        if let Err(err) =
+            record_session_usage(&self.config.codex_home, &self.config.cwd, &usage, price).await
+        {
+            tracing::warn!(error = %err, "failed to record workspace usage");
+        }
+        // Sessions that never reached the model are not worth resuming.
+        if let Some(conversation_id) = self.chat_widget.conversation_id()
+            && !self.chat_widget.token_usage().is_zero()
+            && let Err(err) =
+                persist_last_session(&self.config.codex_home, &self.config.cwd, conversation_id)
+                    .await
+        {
+            tracing::warn!(error = %err, "failed to record last workspace session");
+        }
+    }
+
     pub(crate) async fn handle_tui_event(
         &mut self,
         tui: &mut tui::Tui,
@@ -461,6 +511,7 @@ impl App {
                 }
                 TuiEvent::Draw => {
                     self.chat_widget.maybe_post_pending_notification(tui);
+                    self.chat_widget.sync_terminal_state(tui);
                     if self
                         .chat_widget
                         .handle_paste_burst_tick(tui.frame_requester())
@@ -503,6 +554,7 @@ impl App {
                     skills: self.skills.clone(),
                     is_first_run: false,
                     status_renderer: None,
+                    build: self.build.clone(),
                 };
                 self.chat_widget = ChatWidget::new(init, self.server.clone());
                 if let Some(summary) = summary {
@@ -553,6 +605,7 @@ impl App {
                                     skills: self.skills.clone(),
                                     is_first_run: false,
                                     status_renderer: None,
+                                    build: self.build.clone(),
                                 };
                                 self.chat_widget = ChatWidget::new_from_existing(
                                     init,
@@ -691,23 +744,70 @@ impl App {
                 self.chat_widget.update_statusline_git(snapshot);
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::StatusLineHostEnvironment(environment) => {
+                self.chat_widget
+                    .update_statusline_host_environment(environment);
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::StatusLineKubeContext(context) => {
                 self.chat_widget.update_statusline_kube_context(context);
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::StatusLineIac(iac) => {
+                self.chat_widget.update_statusline_iac(iac);
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::StatusLine88Code(data) => {
                 self.chat_widget.update_statusline_88code(data);
                 tui.frame_requester().schedule_frame();
             }
-            AppEvent::Refresh88CodeTokenResult(result) => match result {
-                Ok(token) => {
-                    self.chat_widget.update_88code_api_key(token);
-                    tui.frame_requester().schedule_frame();
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to refresh 88code token: {}", e);
-                }
-            },
+            AppEvent::StatusLineProcess(process) => {
+                self.chat_widget.update_statusline_process(process);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::StatusLineReachability(reachable) => {
+                self.chat_widget.update_statusline_reachability(reachable);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::StatusLineEnvironmentTick => {
+                self.chat_widget.refresh_statusline_environment();
+            }
+            AppEvent::StatusLineCustomSegment(text) => {
+                self.chat_widget.update_statusline_custom_segment(text);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::StatusLineAttention(attention) => {
+                self.chat_widget.push_status_line_attention(attention);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::ClearStatusLineAttention => {
+                self.chat_widget.clear_status_line_attention();
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::CopyToClipboard { text, what } => {
+                self.chat_widget.copy_to_clipboard(&text, &what);
+            }
+            AppEvent::Code88SignInExpired => {
+                self.chat_widget.open_code88_relogin_confirmation();
+            }
+            AppEvent::Code88ReloginDeclined => {
+                self.chat_widget.on_code88_relogin_declined();
+            }
+            AppEvent::Start88CodeRelogin => {
+                self.chat_widget.start_code88_relogin();
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::Open88CodeTokenPrompt => {
+                self.chat_widget.open_code88_token_prompt();
+            }
+            AppEvent::Submit88CodeToken(token) => {
+                self.chat_widget.submit_code88_token(token);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::Refresh88CodeTokenResult(result) => {
+                self.chat_widget.on_code88_relogin_result(result);
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::OpenFullAccessConfirmation { preset } => {
                 self.chat_widget.open_full_access_confirmation(preset);
             }
@@ -776,6 +876,10 @@ impl App {
                                     .send(AppEvent::UpdateAskForApprovalPolicy(preset.approval));
                                 self.app_event_tx
                                     .send(AppEvent::UpdateSandboxPolicy(preset.sandbox.clone()));
+                                self.app_event_tx.send(AppEvent::PersistWorkspacePolicy {
+                                    approval: preset.approval,
+                                    sandbox: preset.sandbox.clone(),
+                                });
                                 self.chat_widget.add_info_message(
                                     "Enabled experimental Windows sandbox.".to_string(),
                                     None,
@@ -841,6 +945,48 @@ impl App {
             AppEvent::UpdateAskForApprovalPolicy(policy) => {
                 self.chat_widget.set_approval_policy(policy);
             }
+            AppEvent::PersistWorkspacePolicy { approval, sandbox } => {
+                if let Err(err) = persist_policy_selection(
+                    &self.config.codex_home,
+                    &self.config.cwd,
+                    approval,
+                    &sandbox,
+                )
+                .await
+                {
+                    tracing::error!(
+                        error = %err,
+                        "failed to persist workspace approval policy"
+                    );
+                    self.chat_widget.add_error_message(format!(
+                        "Failed to save approval mode for this workspace: {err}"
+                    ));
+                }
+            }
+            AppEvent::SetStatusLineSkin(name) => {
+                if skin_names().contains(&name) {
+                    self.chat_widget.set_status_line_skin(&name);
+                    self.config.tui_statusline.skin = name;
+                } else {
+                    self.chat_widget.add_error_message(format!(
+                        "Unknown status line skin `{name}`; keeping `{}`.",
+                        self.config.tui_statusline.skin
+                    ));
+                }
+            }
+            AppEvent::SetStatusLineEnabled(enabled) => {
+                self.chat_widget.set_status_line_enabled(enabled);
+                self.config.tui_custom_statusline = enabled;
+            }
+            AppEvent::StatusLineSettingsReloaded(Ok(settings)) => {
+                self.config.tui_statusline = (*settings).clone();
+                self.chat_widget.apply_status_line_settings(*settings);
+            }
+            AppEvent::StatusLineSettingsReloaded(Err(err)) => {
+                self.chat_widget.add_error_message(format!(
+                    "Kept the current status line settings: config.toml does not parse: {err}"
+                ));
+            }
             AppEvent::UpdateSandboxPolicy(policy) => {
                 #[cfg(target_os = "windows")]
                 let policy_is_workspace_write_or_ro = matches!(
@@ -1004,6 +1150,8 @@ impl App {
                         "E L I C I T A T I O N".to_string(),
                     ));
                 }
+                // The prompt already shows everything there is to say.
+                ApprovalRequest::Budget { .. } => {}
             },
         }
         Ok(true)
@@ -1159,6 +1307,10 @@ mod tests {
     use codex_core::protocol::EventMsg;
     use codex_core::protocol::SandboxPolicy;
     use codex_core::protocol::SessionConfiguredEvent;
+    use codex_core::protocol::TokenCountEvent;
+    use codex_core::protocol::TokenUsageInfo;
+    use codex_core::workspace_state::WorkspaceUsage;
+    use codex_core::workspace_state::load_workspace_state;
     use codex_protocol::ConversationId;
     use ratatui::prelude::Line;
     use std::path::PathBuf;
@@ -1195,6 +1347,7 @@ mod tests {
             suppress_shutdown_complete: false,
             skip_world_writable_scan_once: false,
             skills: None,
+            build: BuildStatus::default(),
         }
     }
 
@@ -1233,6 +1386,7 @@ mod tests {
                 suppress_shutdown_complete: false,
                 skip_world_writable_scan_once: false,
                 skills: None,
+                build: BuildStatus::default(),
             },
             rx,
             op_rx,
@@ -1414,6 +1568,73 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn resumed_session_does_not_record_replayed_usage_again() {
+        fn token_count(input_tokens: i64, output_tokens: i64) -> EventMsg {
+            let usage = TokenUsage {
+                input_tokens,
+                output_tokens,
+                total_tokens: input_tokens + output_tokens,
+                ..TokenUsage::default()
+            };
+            EventMsg::TokenCount(TokenCountEvent {
+                info: Some(TokenUsageInfo {
+                    total_token_usage: usage.clone(),
+                    last_token_usage: usage,
+                    model_context_window: None,
+                }),
+                rate_limits: None,
+            })
+        }
+
+        let mut app = make_test_app();
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let workspace = tempfile::tempdir().expect("workspace");
+        app.config.codex_home = codex_home.path().to_path_buf();
+        app.config.cwd = workspace.path().to_path_buf();
+        app.chat_widget.handle_codex_event(Event {
+            id: String::new(),
+            msg: EventMsg::SessionConfigured(SessionConfiguredEvent {
+                session_id: ConversationId::new(),
+                model: "gpt-test".to_string(),
+                model_provider_id: "test-provider".to_string(),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::ReadOnly,
+                cwd: workspace.path().to_path_buf(),
+                reasoning_effort: None,
+                history_log_id: 0,
+                history_entry_count: 0,
+                initial_messages: Some(vec![token_count(100, 20)]),
+                rollout_path: PathBuf::new(),
+            }),
+        });
+
+        // Exiting right after the resume adds nothing: the earlier run
+        // already recorded these tokens.
+        app.record_workspace_session().await;
+        let state = load_workspace_state(codex_home.path(), workspace.path()).expect("state");
+        assert_eq!(state.usage, WorkspaceUsage::default());
+
+        // Core counts from zero after a resume, so the next total is this
+        // run's usage alone.
+        app.chat_widget.handle_codex_event(Event {
+            id: String::new(),
+            msg: token_count(30, 10),
+        });
+        app.record_workspace_session().await;
+        let state = load_workspace_state(codex_home.path(), workspace.path()).expect("state");
+        assert_eq!(
+            state.usage,
+            WorkspaceUsage {
+                sessions: 1,
+                input_tokens: 30,
+                output_tokens: 10,
+                unpriced_sessions: 1,
+                ..WorkspaceUsage::default()
+            }
+        );
+    }
+
     #[test]
     fn session_summary_skip_zero_usage() {
         assert!(session_summary(TokenUsage::default(), None).is_none());
@@ -351,6 +351,7 @@ impl App {
             skills: self.skills.clone(),
             is_first_run: false,
             status_renderer: None,
+            build: self.build.clone(),
         };
         self.chat_widget =
             crate::chatwidget::ChatWidget::new_from_existing(init, conv, session_configured);
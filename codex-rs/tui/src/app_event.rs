@@ -9,9 +9,14 @@ use codex_protocol::openai_models::ModelPreset;
 
 use crate::bottom_pane::ApprovalRequest;
 use crate::history_cell::HistoryCell;
+use crate::statusline::HostEnvironment;
 use crate::statusline::StatusLine88CodeSnapshot;
 use crate::statusline::StatusLineGitSnapshot;
+use crate::statusline::StatusLineIacSnapshot;
+use crate::statusline::StatusLineProcessSnapshot;
+use crate::statusline::attention::Attention;
 
+use codex_core::config::types::StatusLineSettings;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
 use codex_protocol::openai_models::ReasoningEffort;
@@ -52,11 +57,53 @@ pub(crate) enum AppEvent {
 
     /// Background Git detection updates for the custom status line.
     StatusLineGit(Option<StatusLineGitSnapshot>),
+    /// Background devspace, hostname, AWS profile and dev environment
+    /// detection for the custom status line.
+    StatusLineHostEnvironment(HostEnvironment),
     /// Background kube context updates for the custom status line.
     StatusLineKubeContext(Option<String>),
+    /// Background Terraform workspace / Pulumi stack updates for the custom
+    /// status line.
+    StatusLineIac(Option<StatusLineIacSnapshot>),
     /// Background 88code usage updates for the custom status line.
     StatusLine88Code(Option<StatusLine88CodeSnapshot>),
-    /// Result of 88code token refresh (triggered automatically when token expires).
+    /// Background samples of the codex process for the custom status line.
+    StatusLineProcess(Option<StatusLineProcessSnapshot>),
+    /// Whether the model endpoint answered the latest reachability probe,
+    /// sent on the `tui.statusline.reachability_probe_secs` interval while
+    /// a task runs.
+    StatusLineReachability(bool),
+    /// Re-scan the environment segments; sent on the
+    /// `tui.statusline.environment_refresh_secs` interval.
+    StatusLineEnvironmentTick,
+    /// Text for the custom status line segment from the control socket;
+    /// `None` clears it.
+    StatusLineCustomSegment(Option<String>),
+    /// Raise an entry in the status line attention segment.
+    StatusLineAttention(Attention),
+    /// Dismiss every attention entry, from the `ctrl + g` popup.
+    ClearStatusLineAttention,
+    /// Put `text` on the system clipboard, from the `ctrl + o` session popup.
+    /// `what` names it in the confirmation message.
+    CopyToClipboard {
+        text: String,
+        what: String,
+    },
+    /// The 88code token was rejected or reported expired; ask whether to
+    /// sign in again.
+    Code88SignInExpired,
+    /// The user agreed to sign in to 88code again from the expired-token
+    /// prompt.
+    Start88CodeRelogin,
+    /// The user answered "Not now" to the expired-token prompt.
+    Code88ReloginDeclined,
+    /// The user chose to paste an 88code token from the expired-token
+    /// prompt.
+    Open88CodeTokenPrompt,
+    /// A token pasted into the paste-a-token popup, to be checked and saved.
+    Submit88CodeToken(String),
+    /// Result of the 88code sign-in started from the expired-token prompt,
+    /// in the browser or with a pasted token.
     Refresh88CodeTokenResult(Result<String, String>),
 
     /// Result of computing a `/diff` command.
@@ -120,9 +167,26 @@ pub(crate) enum AppEvent {
     /// Update the current approval policy in the running app and widget.
     UpdateAskForApprovalPolicy(AskForApproval),
 
+    /// Switch the status line to the named built-in skin.
+    SetStatusLineSkin(String),
+
+    /// Turn the custom status line on or off for the rest of the run, from
+    /// `/statusline on|off|toggle` or `ctrl + l`.
+    SetStatusLineEnabled(bool),
+
+    /// `[tui.statusline]` re-read after `config.toml` changed, with
+    /// `tui.statusline.hot_reload` on, or why the file no longer parses.
+    StatusLineSettingsReloaded(Result<Box<StatusLineSettings>, String>),
+
     /// Update the current sandbox policy in the running app and widget.
     UpdateSandboxPolicy(SandboxPolicy),
 
+    /// Remember the approval policy and sandbox for the current workspace.
+    PersistWorkspacePolicy {
+        approval: AskForApproval,
+        sandbox: SandboxPolicy,
+    },
+
     /// Update whether the full access warning prompt has been acknowledged.
     UpdateFullAccessWarningAcknowledged(bool),
 
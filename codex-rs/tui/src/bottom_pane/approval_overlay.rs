@@ -16,8 +16,13 @@ use crate::key_hint::KeyBinding;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
+use crate::statusline::attention::Attention;
+use crate::statusline::attention::AttentionSeverity;
+use crate::statusline::attention::AttentionSource;
 use codex_core::features::Feature;
 use codex_core::features::Features;
+use codex_core::protocol::BudgetScope;
+use codex_core::protocol::BudgetUsage;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::FileChange;
@@ -59,6 +64,10 @@ pub(crate) enum ApprovalRequest {
         request_id: RequestId,
         message: String,
     },
+    Budget {
+        id: String,
+        budget: BudgetUsage,
+    },
 }
 
 /// Modal overlay asking the user to approve or deny one or more requests.
@@ -126,6 +135,9 @@ impl ApprovalOverlay {
                 elicitation_options(),
                 format!("{server_name} needs your approval."),
             ),
+            ApprovalVariant::Budget { .. } => {
+                (budget_options(), "Keep going past the budget?".to_string())
+            }
         };
 
         let header = Box::new(ColumnRenderable::with([
@@ -186,6 +198,9 @@ impl ApprovalOverlay {
                 ) => {
                     self.handle_elicitation_decision(server_name, request_id, *decision);
                 }
+                (ApprovalVariant::Budget { id }, ApprovalDecision::Review(decision)) => {
+                    self.handle_budget_decision(id, decision.clone());
+                }
                 _ => {}
             }
         }
@@ -197,10 +212,28 @@ impl ApprovalOverlay {
     fn handle_exec_decision(&self, id: &str, command: &[String], decision: ReviewDecision) {
         let cell = history_cell::new_approval_decision_cell(command.to_vec(), decision.clone());
         self.app_event_tx.send(AppEvent::InsertHistoryCell(cell));
+        // A standing approval outlives this prompt; keep it in view.
+        let attention = match &decision {
+            ReviewDecision::ApprovedExecpolicyAmendment {
+                proposed_execpolicy_amendment,
+            } => Some(Attention::new(
+                AttentionSource::Approval,
+                AttentionSeverity::Info,
+                format!(
+                    "Commands starting with `{}` now run without approval",
+                    strip_bash_lc_and_escape(proposed_execpolicy_amendment.command())
+                ),
+            )),
+            _ => None,
+        };
         self.app_event_tx.send(AppEvent::CodexOp(Op::ExecApproval {
             id: id.to_string(),
             decision,
         }));
+        if let Some(attention) = attention {
+            self.app_event_tx
+                .send(AppEvent::StatusLineAttention(attention));
+        }
     }
 
     fn handle_patch_decision(&self, id: &str, decision: ReviewDecision) {
@@ -210,6 +243,14 @@ impl ApprovalOverlay {
         }));
     }
 
+    fn handle_budget_decision(&self, id: &str, decision: ReviewDecision) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::BudgetApproval {
+                id: id.to_string(),
+                decision,
+            }));
+    }
+
     fn handle_elicitation_decision(
         &self,
         server_name: &str,
@@ -299,6 +340,9 @@ impl BottomPaneView for ApprovalOverlay {
                         ElicitationAction::Cancel,
                     );
                 }
+                ApprovalVariant::Budget { id } => {
+                    self.handle_budget_decision(id, ReviewDecision::Abort);
+                }
             }
         }
         self.queue.clear();
@@ -310,6 +354,10 @@ impl BottomPaneView for ApprovalOverlay {
         self.done
     }
 
+    fn pending_approvals(&self) -> usize {
+        if self.done { 0 } else { 1 + self.queue.len() }
+    }
+
     fn try_consume_approval_request(
         &mut self,
         request: ApprovalRequest,
@@ -415,6 +463,21 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     header: Box::new(header),
                 }
             }
+            ApprovalRequest::Budget { id, budget } => {
+                let scope = match budget.scope {
+                    BudgetScope::Session => "Session",
+                    BudgetScope::Workspace => "Workspace",
+                };
+                let header = Paragraph::new(vec![Line::from(vec![
+                    format!("{scope} budget used up: ").into(),
+                    budget.spend_text().bold(),
+                ])])
+                .wrap(Wrap { trim: false });
+                Self {
+                    variant: ApprovalVariant::Budget { id },
+                    header: Box::new(header),
+                }
+            }
         }
     }
 }
@@ -455,6 +518,9 @@ enum ApprovalVariant {
         server_name: String,
         request_id: RequestId,
     },
+    Budget {
+        id: String,
+    },
 }
 
 #[derive(Clone)]
@@ -518,6 +584,23 @@ fn exec_options(
     .collect()
 }
 
+fn budget_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Yes, keep going".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Approved),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: "No, stop here".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Denied),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
 fn patch_options() -> Vec<ApprovalOption> {
     vec![
         ApprovalOption {
@@ -604,6 +687,33 @@ mod tests {
         assert!(saw_op, "expected approval decision to emit an op");
     }
 
+    #[test]
+    fn budget_prompt_stops_on_escape() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let request = ApprovalRequest::Budget {
+            id: "turn-1".to_string(),
+            budget: BudgetUsage {
+                scope: BudgetScope::Session,
+                spent_tokens: 0,
+                max_tokens: None,
+                spent_usd: Some(5.25),
+                max_usd: Some(5.0),
+            },
+        };
+        let mut view = ApprovalOverlay::new(request, tx, Features::with_defaults());
+        view.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::BudgetApproval { id, decision: d }) = ev {
+                assert_eq!(id, "turn-1");
+                decision = Some(d);
+            }
+        }
+        assert_eq!(decision, Some(ReviewDecision::Denied));
+        assert!(view.is_complete());
+    }
+
     #[test]
     fn exec_prefix_option_emits_execpolicy_amendment() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
@@ -641,6 +751,16 @@ mod tests {
             saw_op,
             "expected approval decision to emit an op with command prefix"
         );
+        let attention = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|ev| match ev {
+                AppEvent::StatusLineAttention(attention) => Some(attention),
+                _ => None,
+            })
+            .expect("standing approval raises an attention entry");
+        assert_eq!(
+            attention.message,
+            "Commands starting with `echo` now run without approval"
+        );
     }
 
     #[test]
@@ -26,6 +26,11 @@ pub(crate) trait BottomPaneView: Renderable {
         false
     }
 
+    /// Number of approval requests this view is still waiting on.
+    fn pending_approvals(&self) -> usize {
+        0
+    }
+
     /// Try to handle approval request; return the original value if not
     /// consumed.
     fn try_consume_approval_request(
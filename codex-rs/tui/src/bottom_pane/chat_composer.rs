@@ -49,6 +49,7 @@ use crate::bottom_pane::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
+use crate::status::TokenCountFormatter;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
@@ -68,6 +69,8 @@ const LARGE_PASTE_CHAR_THRESHOLD: usize = 1000;
 pub enum InputResult {
     Submitted(String),
     Command(SlashCommand),
+    /// A built-in command typed with arguments, such as `/statusline off`.
+    CommandWithArgs(SlashCommand, String),
     None,
 }
 
@@ -113,6 +116,7 @@ pub(crate) struct ChatComposer {
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
+    token_format: TokenCountFormatter,
     skills: Option<Vec<SkillMetadata>>,
     dismissed_skill_popup_token: Option<String>,
 }
@@ -194,6 +198,7 @@ impl ChatComposer {
             footer_hint_override: None,
             context_window_percent: None,
             context_window_used_tokens: None,
+            token_format: TokenCountFormatter::default(),
             skills: None,
             dismissed_skill_popup_token: None,
         };
@@ -664,6 +669,11 @@ impl ChatComposer {
                     return (InputResult::Submitted(expanded), true);
                 }
 
+                if let Some((cmd, args)) = parse_command_with_args(first_line) {
+                    self.textarea.set_text("");
+                    return (InputResult::CommandWithArgs(cmd, args), true);
+                }
+
                 if let Some(sel) = popup.selected_item() {
                     match sel {
                         CommandItem::Builtin(cmd) => {
@@ -1226,6 +1236,10 @@ impl ChatComposer {
                     self.textarea.set_text("");
                     return (InputResult::Command(cmd), true);
                 }
+                if let Some((cmd, args)) = parse_command_with_args(first_line) {
+                    self.textarea.set_text("");
+                    return (InputResult::CommandWithArgs(cmd, args), true);
+                }
                 // If we're in a paste-like burst capture, treat Enter as part of the burst
                 // and accumulate it rather than submitting or inserting immediately.
                 // Do not treat Enter as paste inside a slash-command context.
@@ -1665,6 +1679,7 @@ impl ChatComposer {
             is_task_running: self.is_task_running,
             context_window_percent: self.context_window_percent,
             context_window_used_tokens: self.context_window_used_tokens,
+            token_format: self.token_format,
         }
     }
 
@@ -1861,6 +1876,10 @@ impl ChatComposer {
         self.context_window_used_tokens = used_tokens;
     }
 
+    pub(crate) fn set_token_format(&mut self, token_format: TokenCountFormatter) {
+        self.token_format = token_format;
+    }
+
     pub(crate) fn set_esc_backtrack_hint(&mut self, show: bool) {
         self.esc_backtrack_hint = show;
         if show {
@@ -2019,6 +2038,18 @@ fn prompt_selection_action(
     }
 }
 
+/// `/name args` for a built-in command that takes arguments.
+fn parse_command_with_args(line: &str) -> Option<(SlashCommand, String)> {
+    let (name, rest) = parse_slash_name(line)?;
+    if rest.is_empty() {
+        return None;
+    }
+    let (_, cmd) = built_in_slash_commands()
+        .into_iter()
+        .find(|(command_name, _)| *command_name == name)?;
+    cmd.takes_args().then(|| (cmd, rest.trim_end().to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2963,6 +2994,9 @@ mod tests {
             InputResult::Submitted(text) => {
                 panic!("expected command dispatch, but composer submitted literal text: {text}")
             }
+            InputResult::CommandWithArgs(cmd, args) => {
+                panic!("expected a bare command, got /{} {args}", cmd.command())
+            }
             InputResult::None => panic!("expected Command result for '/init'"),
         }
         assert!(composer.textarea.is_empty(), "composer should be cleared");
@@ -3036,11 +3070,49 @@ mod tests {
             InputResult::Submitted(text) => {
                 panic!("expected command dispatch after Tab completion, got literal submit: {text}")
             }
+            InputResult::CommandWithArgs(cmd, args) => {
+                panic!("expected a bare command, got /{} {args}", cmd.command())
+            }
             InputResult::None => panic!("expected Command result for '/diff'"),
         }
         assert!(composer.textarea.is_empty());
     }
 
+    #[test]
+    fn slash_command_with_args_dispatches_args() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        type_chars_humanlike(
+            &mut composer,
+            &"/statusline off".chars().collect::<Vec<_>>(),
+        );
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(
+            result,
+            InputResult::CommandWithArgs(SlashCommand::Statusline, "off".to_string())
+        );
+        assert!(composer.textarea.is_empty());
+
+        // Commands without arguments still go to the model as text.
+        type_chars_humanlike(&mut composer, &"/diff main".chars().collect::<Vec<_>>());
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(result, InputResult::Submitted("/diff main".to_string()));
+    }
+
     #[test]
     fn slash_mention_dispatches_command_and_inserts_at() {
         use crossterm::event::KeyCode;
@@ -3069,6 +3141,9 @@ mod tests {
             InputResult::Submitted(text) => {
                 panic!("expected command dispatch, but composer submitted literal text: {text}")
             }
+            InputResult::CommandWithArgs(cmd, args) => {
+                panic!("expected a bare command, got /{} {args}", cmd.command())
+            }
             InputResult::None => panic!("expected Command result for '/mention'"),
         }
         assert!(composer.textarea.is_empty(), "composer should be cleared");
@@ -3,7 +3,7 @@ use crate::clipboard_paste::is_probably_wsl;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::render::line_utils::prefix_lines;
-use crate::status::format_tokens_compact;
+use crate::status::TokenCountFormatter;
 use crate::ui_consts::FOOTER_INDENT_COLS;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
@@ -22,6 +22,7 @@ pub(crate) struct FooterProps {
     pub(crate) is_task_running: bool,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
+    pub(crate) token_format: TokenCountFormatter,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -87,6 +88,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             let mut line = context_window_line(
                 props.context_window_percent,
                 props.context_window_used_tokens,
+                props.token_format,
             );
             line.push_span(" · ".dim());
             line.extend(vec![
@@ -112,6 +114,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
         FooterMode::ContextOnly => vec![context_window_line(
             props.context_window_percent,
             props.context_window_used_tokens,
+            props.token_format,
         )],
     }
 }
@@ -240,14 +243,18 @@ fn build_columns(entries: Vec<Line<'static>>) -> Vec<Line<'static>> {
         .collect()
 }
 
-fn context_window_line(percent: Option<i64>, used_tokens: Option<i64>) -> Line<'static> {
+fn context_window_line(
+    percent: Option<i64>,
+    used_tokens: Option<i64>,
+    token_format: TokenCountFormatter,
+) -> Line<'static> {
     if let Some(percent) = percent {
         let percent = percent.clamp(0, 100);
         return Line::from(vec![Span::from(format!("{percent}% context left")).dim()]);
     }
 
     if let Some(tokens) = used_tokens {
-        let used_fmt = format_tokens_compact(tokens);
+        let used_fmt = token_format.format(tokens);
         return Line::from(vec![Span::from(format!("{used_fmt} used")).dim()]);
     }
 
@@ -439,6 +446,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_format: TokenCountFormatter::default(),
             },
         );
 
@@ -451,6 +459,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_format: TokenCountFormatter::default(),
             },
         );
 
@@ -463,6 +472,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_format: TokenCountFormatter::default(),
             },
         );
 
@@ -475,6 +485,7 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_format: TokenCountFormatter::default(),
             },
         );
 
@@ -487,6 +498,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_format: TokenCountFormatter::default(),
             },
         );
 
@@ -499,6 +511,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_format: TokenCountFormatter::default(),
             },
         );
 
@@ -511,6 +524,7 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: Some(72),
                 context_window_used_tokens: None,
+                token_format: TokenCountFormatter::default(),
             },
         );
 
@@ -523,6 +537,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: Some(123_456),
+                token_format: TokenCountFormatter::default(),
             },
         );
     }
@@ -536,6 +551,7 @@ mod tests {
             is_task_running: false,
             context_window_percent: Some(88),
             context_window_used_tokens: None,
+            token_format: TokenCountFormatter::default(),
         });
         for line in lines {
             let mut text = String::new();
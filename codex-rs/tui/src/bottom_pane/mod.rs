@@ -52,6 +52,8 @@ pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
 
+use crate::exec_cell::SpinnerStyle;
+use crate::status::TokenCountFormatter;
 use crate::status_indicator_widget::StatusIndicatorWidget;
 pub(crate) use list_selection_view::SelectionAction;
 pub(crate) use list_selection_view::SelectionItem;
@@ -73,6 +75,7 @@ pub(crate) struct BottomPane {
     ctrl_c_quit_hint: bool,
     esc_backtrack_hint: bool,
     animations_enabled: bool,
+    spinner_style: SpinnerStyle,
 
     /// Inline status indicator shown above the composer while a task is running.
     status: Option<StatusIndicatorWidget>,
@@ -126,6 +129,7 @@ impl BottomPane {
             queued_user_messages: QueuedUserMessages::new(),
             esc_backtrack_hint: false,
             animations_enabled,
+            spinner_style: SpinnerStyle::default(),
             context_window_percent: None,
             context_window_used_tokens: None,
         }
@@ -153,6 +157,14 @@ impl BottomPane {
         self.active_view().is_some()
     }
 
+    /// Approval requests shown or queued in the bottom pane's views.
+    pub(crate) fn pending_approvals(&self) -> usize {
+        self.view_stack
+            .iter()
+            .map(|view| view.pending_approvals())
+            .sum()
+    }
+
     fn push_view(&mut self, view: Box<dyn BottomPaneView>) {
         self.view_stack.push(view);
         self.request_redraw();
@@ -326,11 +338,14 @@ impl BottomPane {
         if running {
             if !was_running {
                 if self.status.is_none() {
-                    self.status = Some(StatusIndicatorWidget::new(
-                        self.app_event_tx.clone(),
-                        self.frame_requester.clone(),
-                        self.animations_enabled,
-                    ));
+                    self.status = Some(
+                        StatusIndicatorWidget::new(
+                            self.app_event_tx.clone(),
+                            self.frame_requester.clone(),
+                            self.animations_enabled,
+                        )
+                        .with_spinner_style(self.spinner_style.clone()),
+                    );
                 }
                 if let Some(status) = self.status.as_mut() {
                     status.set_interrupt_hint_visible(true);
@@ -352,11 +367,14 @@ impl BottomPane {
 
     pub(crate) fn ensure_status_indicator(&mut self) {
         if self.status.is_none() {
-            self.status = Some(StatusIndicatorWidget::new(
-                self.app_event_tx.clone(),
-                self.frame_requester.clone(),
-                self.animations_enabled,
-            ));
+            self.status = Some(
+                StatusIndicatorWidget::new(
+                    self.app_event_tx.clone(),
+                    self.frame_requester.clone(),
+                    self.animations_enabled,
+                )
+                .with_spinner_style(self.spinner_style.clone()),
+            );
             self.request_redraw();
         }
     }
@@ -368,6 +386,18 @@ impl BottomPane {
         }
     }
 
+    /// The status indicator's spinner (`[tui.spinner]`), from the next task
+    /// on.
+    pub(crate) fn set_spinner_style(&mut self, style: SpinnerStyle) {
+        self.spinner_style = style;
+    }
+
+    /// How the footer writes token counts (`[tui.token_format]`).
+    pub(crate) fn set_token_format(&mut self, token_format: TokenCountFormatter) {
+        self.composer.set_token_format(token_format);
+        self.request_redraw();
+    }
+
     pub(crate) fn set_context_window(&mut self, percent: Option<i64>, used_tokens: Option<i64>) {
         if self.context_window_percent == percent && self.context_window_used_tokens == used_tokens
         {
@@ -9,6 +9,7 @@ use codex_app_server_protocol::AuthMode;
 use codex_backend_client::Client as BackendClient;
 use codex_core::config::Config;
 use codex_core::config::types::Notifications;
+use codex_core::config::types::StatusLineSettings;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
 use codex_core::openai_models::models_manager::ModelsManager;
@@ -21,6 +22,8 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::BudgetExceededEvent;
+use codex_core::protocol::CodexErrorInfo;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -92,10 +95,12 @@ use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::clipboard_paste::copy_text_to_clipboard;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
+use crate::exec_cell::SpinnerStyle;
 use crate::exec_cell::new_active_exec_command;
 use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
@@ -111,14 +116,33 @@ use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
 use crate::slash_command::SlashCommand;
+use crate::status::RateLimitHistory;
 use crate::status::RateLimitSnapshotDisplay;
+use crate::status::TokenCountFormatter;
+use crate::statusline::CompletedRun;
+use crate::statusline::HostEnvironment;
+use crate::statusline::RunPillPlacement;
 use crate::statusline::StatusLine88CodeSnapshot;
 use crate::statusline::StatusLineGitSnapshot;
+use crate::statusline::StatusLineIacSnapshot;
 use crate::statusline::StatusLineLayout;
 use crate::statusline::StatusLineOverlay;
+use crate::statusline::StatusLineProcessSnapshot;
 use crate::statusline::StatusLineRenderer;
+use crate::statusline::attention::Attention;
+use crate::statusline::attention::AttentionSeverity;
+use crate::statusline::attention::AttentionSource;
+use crate::statusline::attention::rate_limit_attention;
+use crate::statusline::format_elapsed_compact;
+use crate::statusline::relogin::relogin_confirmation_params;
+use crate::statusline::relogin::spawn_relogin;
+use crate::statusline::relogin::spawn_token_check;
+use crate::statusline::relogin::token_prompt_view;
+use crate::statusline::skins;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
+use crate::tui::TerminalProgress;
+use crate::version::BuildStatus;
 mod interrupts;
 use self::interrupts::InterruptManager;
 mod agent;
@@ -145,6 +169,8 @@ use strum::IntoEnumIterator;
 
 const USER_SHELL_COMMAND_HELP_TITLE: &str = "Prefix a command with ! to run it locally";
 const USER_SHELL_COMMAND_HELP_HINT: &str = "Example: !ls";
+/// Sent when the user resumes a paused task.
+const RESUME_PROMPT: &str = "Continue where you left off.";
 // Track information about an in-flight exec command.
 struct RunningCommand {
     command: Vec<String>,
@@ -169,6 +195,17 @@ impl UnifiedExecWaitState {
 const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
 const NUDGE_MODEL_SLUG: &str = "gpt-5-codex-mini";
 const RATE_LIMIT_SWITCH_PROMPT_THRESHOLD: f64 = 90.0;
+/// What warnings and the attention segment call the 88code credit limit.
+const CODE88_CREDIT_LIMIT_LABEL: &str = "88code credit";
+
+/// Where a rate limit snapshot came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RateLimitSource {
+    /// Response headers, reported by core with each token count.
+    Core,
+    /// The 88code usage poll: the credit limit as the primary window.
+    Code88,
+}
 
 #[derive(Default)]
 struct RateLimitWarningState {
@@ -194,14 +231,9 @@ impl RateLimitWarningState {
         let mut warnings = Vec::new();
 
         if let Some(secondary_used_percent) = secondary_used_percent {
-            let mut highest_secondary: Option<f64> = None;
-            while self.secondary_index < RATE_LIMIT_WARNING_THRESHOLDS.len()
-                && secondary_used_percent >= RATE_LIMIT_WARNING_THRESHOLDS[self.secondary_index]
+            if let Some(threshold) =
+                highest_crossed_threshold(&mut self.secondary_index, secondary_used_percent)
             {
-                highest_secondary = Some(RATE_LIMIT_WARNING_THRESHOLDS[self.secondary_index]);
-                self.secondary_index += 1;
-            }
-            if let Some(threshold) = highest_secondary {
                 let limit_label = secondary_window_minutes
                     .map(get_limits_duration)
                     .unwrap_or_else(|| "weekly".to_string());
@@ -212,14 +244,9 @@ impl RateLimitWarningState {
         }
 
         if let Some(primary_used_percent) = primary_used_percent {
-            let mut highest_primary: Option<f64> = None;
-            while self.primary_index < RATE_LIMIT_WARNING_THRESHOLDS.len()
-                && primary_used_percent >= RATE_LIMIT_WARNING_THRESHOLDS[self.primary_index]
+            if let Some(threshold) =
+                highest_crossed_threshold(&mut self.primary_index, primary_used_percent)
             {
-                highest_primary = Some(RATE_LIMIT_WARNING_THRESHOLDS[self.primary_index]);
-                self.primary_index += 1;
-            }
-            if let Some(threshold) = highest_primary {
                 let limit_label = primary_window_minutes
                     .map(get_limits_duration)
                     .unwrap_or_else(|| "5h".to_string());
@@ -231,6 +258,35 @@ impl RateLimitWarningState {
 
         warnings
     }
+
+    /// Warnings for the 88code credit limit, which has no time window; it
+    /// takes the primary window's thresholds.
+    fn take_credit_warnings(&mut self, used_percent: f64) -> Vec<String> {
+        if used_percent == 100.0 {
+            return Vec::new();
+        }
+        highest_crossed_threshold(&mut self.primary_index, used_percent)
+            .map(|threshold| {
+                format!(
+                    "Heads up, you've used over {threshold:.0}% of your {CODE88_CREDIT_LIMIT_LABEL} limit. Run /status for a breakdown."
+                )
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Advance `index` past every threshold `used_percent` reached and return
+/// the highest one, if it crossed any not yet warned about.
+fn highest_crossed_threshold(index: &mut usize, used_percent: f64) -> Option<f64> {
+    let mut highest = None;
+    while let Some(&threshold) = RATE_LIMIT_WARNING_THRESHOLDS.get(*index)
+        && used_percent >= threshold
+    {
+        highest = Some(threshold);
+        *index += 1;
+    }
+    highest
 }
 
 pub(crate) fn get_limits_duration(windows_minutes: i64) -> String {
@@ -269,6 +325,7 @@ pub(crate) struct ChatWidgetInit {
     pub(crate) skills: Option<Vec<SkillMetadata>>,
     pub(crate) is_first_run: bool,
     pub(crate) status_renderer: Option<Box<dyn StatusLineRenderer>>,
+    pub(crate) build: BuildStatus,
 }
 
 #[derive(Default)]
@@ -284,6 +341,8 @@ pub(crate) struct ChatWidget {
     codex_op_tx: UnboundedSender<Op>,
     bottom_pane: BottomPane,
     status_overlay: Option<StatusLineOverlay>,
+    /// This binary's commit and the newer release found at startup.
+    build: BuildStatus,
     active_cell: Option<Box<dyn HistoryCell>>,
     config: Config,
     auth_manager: Arc<AuthManager>,
@@ -291,7 +350,15 @@ pub(crate) struct ChatWidget {
     session_header: SessionHeader,
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
+    /// `token_info` was replayed from a resumed rollout. Core starts counting
+    /// from zero on resume, so those tokens were already recorded for the
+    /// workspace by the session that used them.
+    token_info_replayed: bool,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
+    rate_limit_history: RateLimitHistory,
+    /// A browser sign-in to 88code started from the expired-token prompt is
+    /// still running.
+    code88_relogin_running: bool,
     plan_type: Option<PlanType>,
     rate_limit_warnings: RateLimitWarningState,
     rate_limit_switch_prompt: RateLimitSwitchPromptState,
@@ -302,6 +369,9 @@ pub(crate) struct ChatWidget {
     suppressed_exec_calls: HashSet<String>,
     last_unified_wait: Option<UnifiedExecWaitState>,
     task_complete_pending: bool,
+    // The user paused the task (Ctrl+S): its turn was interrupted but the run
+    // stays open until they resume it or end it with Esc.
+    run_paused: bool,
     mcp_startup_status: Option<HashMap<String, McpStartupStatus>>,
     // Queue of interruptive UI events deferred during an active write cycle
     interrupts: InterruptManager,
@@ -384,21 +454,160 @@ impl ChatWidget {
         }
     }
 
+    pub(crate) fn update_statusline_host_environment(&mut self, environment: HostEnvironment) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.update_host_environment(environment);
+        }
+    }
+
     pub(crate) fn update_statusline_kube_context(&mut self, context: Option<String>) {
         if let Some(overlay) = self.status_overlay.as_mut() {
             overlay.update_kube_context(context);
         }
     }
 
+    pub(crate) fn update_statusline_iac(&mut self, iac: Option<StatusLineIacSnapshot>) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.update_iac(iac);
+        }
+    }
+
     pub(crate) fn update_statusline_88code(&mut self, data: Option<StatusLine88CodeSnapshot>) {
+        // With the 88code provider, its credits are this session's rate
+        // limits: `/status` shows them in the same rows and sparkline.
+        if self.config.model_provider.code88_auth
+            && let Some(snapshot) = data
+                .as_ref()
+                .and_then(crate::status::code88_rate_limit_snapshot)
+        {
+            self.record_rate_limit_snapshot(snapshot, RateLimitSource::Code88);
+        }
         if let Some(overlay) = self.status_overlay.as_mut() {
             overlay.update_88code(data);
         }
     }
 
-    pub(crate) fn update_88code_api_key(&mut self, token: String) {
+    pub(crate) fn update_statusline_process(&mut self, process: Option<StatusLineProcessSnapshot>) {
         if let Some(overlay) = self.status_overlay.as_mut() {
-            overlay.update_api_key(token);
+            overlay.update_process(process);
+        }
+    }
+
+    pub(crate) fn update_statusline_reachability(&mut self, reachable: bool) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.update_reachability(reachable);
+        }
+    }
+
+    pub(crate) fn update_statusline_custom_segment(&mut self, text: Option<String>) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.update_custom_segment(text);
+        }
+    }
+
+    pub(crate) fn push_status_line_attention(&mut self, attention: Attention) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.push_attention(attention);
+        }
+    }
+
+    pub(crate) fn clear_status_line_attention(&mut self) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.clear_attention();
+        }
+    }
+
+    pub(crate) fn refresh_statusline_environment(&mut self) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.spawn_environment_refresh();
+        }
+    }
+
+    /// Ask whether to sign in to 88code again, unless a sign-in is already
+    /// running.
+    pub(crate) fn open_code88_relogin_confirmation(&mut self) {
+        if self.code88_relogin_running {
+            return;
+        }
+        self.bottom_pane
+            .show_selection_view(relogin_confirmation_params());
+        self.request_redraw();
+    }
+
+    pub(crate) fn start_code88_relogin(&mut self) {
+        if self.code88_relogin_running {
+            return;
+        }
+        self.code88_relogin_running = true;
+        self.push_status_line_attention(Attention::new(
+            AttentionSource::Auth,
+            AttentionSeverity::Info,
+            "88code: opening the browser to sign in…",
+        ));
+        spawn_relogin(
+            self.config.code88_token_store(),
+            self.config.code88_browser.clone(),
+            self.app_event_tx.clone(),
+        );
+    }
+
+    pub(crate) fn open_code88_token_prompt(&mut self) {
+        if self.code88_relogin_running {
+            return;
+        }
+        self.bottom_pane
+            .show_view(Box::new(token_prompt_view(self.app_event_tx.clone())));
+        self.request_redraw();
+    }
+
+    pub(crate) fn submit_code88_token(&mut self, token: String) {
+        if self.code88_relogin_running || token.is_empty() {
+            return;
+        }
+        self.code88_relogin_running = true;
+        self.push_status_line_attention(Attention::new(
+            AttentionSource::Auth,
+            AttentionSeverity::Info,
+            "88code: checking the pasted token…",
+        ));
+        spawn_token_check(
+            self.config.code88_token_store(),
+            token,
+            self.app_event_tx.clone(),
+        );
+    }
+
+    pub(crate) fn on_code88_relogin_declined(&mut self) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.relogin_declined();
+        }
+    }
+
+    pub(crate) fn on_code88_relogin_result(&mut self, result: Result<String, String>) {
+        self.code88_relogin_running = false;
+        match result {
+            Ok(token) => {
+                if let Some(overlay) = self.status_overlay.as_mut() {
+                    overlay.clear_attention_source(AttentionSource::Auth);
+                    overlay.update_api_key(token);
+                }
+                self.add_info_message(
+                    "Signed in to 88code again.".to_string(),
+                    Some("Send your message again to retry it.".to_string()),
+                );
+            }
+            Err(err) => {
+                tracing::warn!("Failed to refresh 88code token: {err}");
+                if let Some(overlay) = self.status_overlay.as_mut() {
+                    overlay.relogin_failed();
+                }
+                self.push_status_line_attention(Attention::new(
+                    AttentionSource::Auth,
+                    AttentionSeverity::Warning,
+                    format!("88code sign-in could not be refreshed: {err}"),
+                ));
+                self.request_redraw();
+            }
         }
     }
 
@@ -409,6 +618,65 @@ impl ChatWidget {
         }
     }
 
+    pub(crate) fn set_status_line_skin(&mut self, name: &str) {
+        if let Some(overlay) = self.status_overlay.as_mut()
+            && overlay.set_skin(name)
+        {
+            self.config.tui_statusline.skin = name.to_string();
+            self.request_redraw();
+        }
+    }
+
+    /// Apply `[tui.statusline]` re-read from `config.toml`.
+    pub(crate) fn apply_status_line_settings(&mut self, settings: StatusLineSettings) {
+        self.config.tui_statusline = settings;
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.apply_settings(&self.config);
+            self.request_redraw();
+        }
+    }
+
+    /// Build or drop the status line overlay mid-session. Dropping it hands
+    /// its reserved rows back on the next frame and brings back the bottom
+    /// pane's status indicator for a running task.
+    pub(crate) fn set_status_line_enabled(&mut self, enabled: bool) {
+        if enabled == self.status_overlay.is_some() {
+            return;
+        }
+        let task_running = self.bottom_pane.is_task_running();
+        if enabled {
+            let mut overlay = StatusLineOverlay::new_enabled(
+                &self.config,
+                self.frame_requester.clone(),
+                self.app_event_tx.clone(),
+            );
+            let queued = self
+                .queued_user_messages
+                .iter()
+                .map(|m| m.text.clone())
+                .collect();
+            overlay.set_auth_mode(&self.config, self.auth_manager.auth().map(|auth| auth.mode));
+            overlay.set_build(&self.build);
+            overlay.bootstrap(&self.config, self.token_info.clone(), queued);
+            overlay.set_rollout_path(self.current_rollout_path.clone());
+            overlay.set_session_id(self.conversation_id.as_ref().map(ToString::to_string));
+            if task_running {
+                overlay.set_interrupt_hint_visible(true);
+                overlay.start_task(&self.current_status_header);
+                self.bottom_pane.hide_status_indicator();
+            }
+            self.status_overlay = Some(overlay);
+        } else {
+            self.status_overlay = None;
+            if task_running {
+                self.bottom_pane.ensure_status_indicator();
+                self.bottom_pane
+                    .update_status_header(self.current_status_header.clone());
+            }
+        }
+        self.request_redraw();
+    }
+
     #[cfg(test)]
     pub(crate) fn status_line_mut(
         &mut self,
@@ -432,7 +700,12 @@ impl ChatWidget {
             .set_history_metadata(event.history_log_id, event.history_entry_count);
         self.conversation_id = Some(event.session_id);
         self.current_rollout_path = Some(event.rollout_path.clone());
+        if self.config.tui_rate_limit_history {
+            self.rate_limit_history
+                .persist_to(&self.config.codex_home, &event.session_id.to_string());
+        }
         if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.set_rollout_path(Some(event.rollout_path.clone()));
             overlay.set_session_id(Some(event.session_id.to_string()));
             overlay.sync_model(&self.config);
             overlay.spawn_background_tasks();
@@ -549,6 +822,7 @@ impl ChatWidget {
     // Raw reasoning uses the same flow as summarized reasoning
 
     fn on_task_started(&mut self) {
+        self.run_paused = false;
         self.bottom_pane.clear_ctrl_c_quit_hint();
         self.bottom_pane.set_task_running(true);
         self.retry_status_header = None;
@@ -574,9 +848,10 @@ impl ChatWidget {
         self.running_commands.clear();
         self.suppressed_exec_calls.clear();
         self.last_unified_wait = None;
+        let mut completed_run = None;
         if let Some(overlay) = self.status_overlay.as_mut() {
             overlay.set_interrupt_hint_visible(false);
-            overlay.complete_task();
+            completed_run = overlay.complete_task();
         }
         if let Some(overlay) = self.status_overlay.as_ref() {
             overlay.spawn_background_tasks();
@@ -586,9 +861,17 @@ impl ChatWidget {
         // If there is a queued user message, send exactly one now to begin the next turn.
         self.maybe_send_next_queued_input();
         // Emit a notification when the turn completes (suppressed if focused).
-        self.notify(Notification::AgentTurnComplete {
-            response: last_agent_message.unwrap_or_default(),
-        });
+        // Per-channel settings report the run label and elapsed time instead
+        // of the agent's reply.
+        let notification = match completed_run {
+            Some(run) if matches!(self.config.tui_notifications, Notifications::Channels(_)) => {
+                Notification::TaskComplete(run)
+            }
+            _ => Notification::AgentTurnComplete {
+                response: last_agent_message.unwrap_or_default(),
+            },
+        };
+        self.notify(notification);
         self.maybe_show_pending_rate_limit_prompt();
     }
 
@@ -645,22 +928,37 @@ impl ChatWidget {
     }
 
     pub(crate) fn on_rate_limit_snapshot(&mut self, snapshot: Option<RateLimitSnapshot>) {
-        if let Some(mut snapshot) = snapshot {
-            if snapshot.credits.is_none() {
-                snapshot.credits = self
-                    .rate_limit_snapshot
-                    .as_ref()
-                    .and_then(|display| display.credits.as_ref())
-                    .map(|credits| CreditsSnapshot {
-                        has_credits: credits.has_credits,
-                        unlimited: credits.unlimited,
-                        balance: credits.balance.clone(),
-                    });
-            }
+        match snapshot {
+            Some(snapshot) => self.record_rate_limit_snapshot(snapshot, RateLimitSource::Core),
+            // The 88code provider sends no rate limit headers; its credits
+            // from the last usage poll stand until the next poll.
+            None if self.config.model_provider.code88_auth => {}
+            None => self.rate_limit_snapshot = None,
+        }
+    }
 
-            self.plan_type = snapshot.plan_type.or(self.plan_type);
+    fn record_rate_limit_snapshot(
+        &mut self,
+        mut snapshot: RateLimitSnapshot,
+        source: RateLimitSource,
+    ) {
+        if snapshot.credits.is_none() {
+            snapshot.credits = self
+                .rate_limit_snapshot
+                .as_ref()
+                .and_then(|display| display.credits.as_ref())
+                .map(|credits| CreditsSnapshot {
+                    has_credits: credits.has_credits,
+                    unlimited: credits.unlimited,
+                    balance: credits.balance.clone(),
+                });
+        }
+
+        self.plan_type = snapshot.plan_type.or(self.plan_type);
+        self.update_rate_limit_attention(&snapshot, source);
 
-            let warnings = self.rate_limit_warnings.take_warnings(
+        let warnings = match source {
+            RateLimitSource::Core => self.rate_limit_warnings.take_warnings(
                 snapshot
                     .secondary
                     .as_ref()
@@ -674,43 +972,97 @@ impl ChatWidget {
                     .primary
                     .as_ref()
                     .and_then(|window| window.window_minutes),
-            );
+            ),
+            RateLimitSource::Code88 => snapshot
+                .primary
+                .as_ref()
+                .map(|window| {
+                    self.rate_limit_warnings
+                        .take_credit_warnings(window.used_percent)
+                })
+                .unwrap_or_default(),
+        };
 
-            let high_usage = snapshot
-                .secondary
+        let high_usage = snapshot
+            .secondary
+            .as_ref()
+            .map(|w| w.used_percent >= RATE_LIMIT_SWITCH_PROMPT_THRESHOLD)
+            .unwrap_or(false)
+            || snapshot
+                .primary
                 .as_ref()
                 .map(|w| w.used_percent >= RATE_LIMIT_SWITCH_PROMPT_THRESHOLD)
-                .unwrap_or(false)
-                || snapshot
-                    .primary
-                    .as_ref()
-                    .map(|w| w.used_percent >= RATE_LIMIT_SWITCH_PROMPT_THRESHOLD)
-                    .unwrap_or(false);
+                .unwrap_or(false);
+
+        // Switching to the smaller OpenAI model does not stretch 88code
+        // credits, so only offer it for OpenAI's limits.
+        if source == RateLimitSource::Core
+            && high_usage
+            && !self.rate_limit_switch_prompt_hidden()
+            && self.config.model != NUDGE_MODEL_SLUG
+            && !matches!(
+                self.rate_limit_switch_prompt,
+                RateLimitSwitchPromptState::Shown
+            )
+        {
+            self.rate_limit_switch_prompt = RateLimitSwitchPromptState::Pending;
+        }
 
-            if high_usage
-                && !self.rate_limit_switch_prompt_hidden()
-                && self.config.model != NUDGE_MODEL_SLUG
-                && !matches!(
-                    self.rate_limit_switch_prompt,
-                    RateLimitSwitchPromptState::Shown
-                )
-            {
-                self.rate_limit_switch_prompt = RateLimitSwitchPromptState::Pending;
+        let captured_at = Local::now();
+        let mut display = match source {
+            RateLimitSource::Core => {
+                crate::status::rate_limit_snapshot_display(&snapshot, captured_at)
             }
+            RateLimitSource::Code88 => {
+                crate::status::code88_rate_limit_display(&snapshot, captured_at)
+            }
+        };
+        self.rate_limit_history.record(&snapshot, captured_at);
+        self.rate_limit_history.annotate(&mut display);
+        self.rate_limit_snapshot = Some(display);
 
-            let display = crate::status::rate_limit_snapshot_display(&snapshot, Local::now());
-            self.rate_limit_snapshot = Some(display);
-
-            if !warnings.is_empty() {
-                for warning in warnings {
-                    self.add_to_history(history_cell::new_warning_event(warning));
-                }
-                self.request_redraw();
+        if !warnings.is_empty() {
+            for warning in warnings {
+                self.add_to_history(history_cell::new_warning_event(warning));
             }
-        } else {
-            self.rate_limit_snapshot = None;
+            self.request_redraw();
         }
     }
+
+    /// Keep the most pressing rate limit window in the attention segment, and
+    /// drop it once usage falls back below the thresholds.
+    fn update_rate_limit_attention(
+        &mut self,
+        snapshot: &RateLimitSnapshot,
+        source: RateLimitSource,
+    ) {
+        let Some(overlay) = self.status_overlay.as_mut() else {
+            return;
+        };
+        let primary_label = match source {
+            RateLimitSource::Core => "5h",
+            RateLimitSource::Code88 => CODE88_CREDIT_LIMIT_LABEL,
+        };
+        let attention = [
+            (snapshot.primary.as_ref(), primary_label),
+            (snapshot.secondary.as_ref(), "weekly"),
+        ]
+        .into_iter()
+        .filter_map(|(window, default_label)| {
+            let window = window?;
+            let label = window
+                .window_minutes
+                .map(get_limits_duration)
+                .unwrap_or_else(|| default_label.to_string());
+            rate_limit_attention(window.used_percent, &label)
+        })
+        .max_by_key(|attention| attention.severity);
+        match attention {
+            Some(attention) => overlay.push_attention(attention),
+            None => overlay.clear_attention_source(AttentionSource::RateLimit),
+        }
+    }
+
     /// Finalize any active exec as failed and stop/clear running UI state.
     fn finalize_turn(&mut self) {
         // Ensure any spinner is replaced by a red ✗ and flushed into history.
@@ -734,7 +1086,13 @@ impl ChatWidget {
     }
 
     fn on_warning(&mut self, message: impl Into<String>) {
-        self.add_to_history(history_cell::new_warning_event(message.into()));
+        let message = message.into();
+        self.push_status_line_attention(Attention::new(
+            AttentionSource::Core,
+            AttentionSeverity::Warning,
+            message.clone(),
+        ));
+        self.add_to_history(history_cell::new_warning_event(message));
         self.request_redraw();
     }
 
@@ -813,7 +1171,12 @@ impl ChatWidget {
         // Finalize, log a gentle prompt, and clear running state.
         self.finalize_turn();
 
-        if reason != TurnAbortReason::ReviewEnded {
+        if self.run_paused {
+            self.add_info_message(
+                "Paused. Press ctrl + s to continue, or send new instructions.".to_string(),
+                None,
+            );
+        } else if reason != TurnAbortReason::ReviewEnded {
             self.add_to_history(history_cell::new_error_event(
                 "Conversation interrupted - tell the model what to do differently. Something went wrong? Hit `/feedback` to report the issue.".to_owned(),
             ));
@@ -888,6 +1251,9 @@ impl ChatWidget {
     }
 
     fn on_patch_apply_begin(&mut self, event: PatchApplyBeginEvent) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.set_patch_run_label();
+        }
         self.add_to_history(history_cell::new_patch_event(
             event.changes,
             &self.config.cwd,
@@ -1098,6 +1464,13 @@ impl ChatWidget {
         };
         let is_unified_exec_interaction =
             matches!(source, ExecCommandSource::UnifiedExecInteraction);
+        let ran_by_agent = matches!(
+            source,
+            ExecCommandSource::Agent | ExecCommandSource::UnifiedExecStartup
+        );
+        if ran_by_agent && let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.record_exec_exit(&command, ev.exit_code);
+        }
 
         let needs_new = self
             .active_cell
@@ -1106,14 +1479,17 @@ impl ChatWidget {
             .unwrap_or(true);
         if needs_new {
             self.flush_active_cell();
-            self.active_cell = Some(Box::new(new_active_exec_command(
-                ev.call_id.clone(),
-                command,
-                parsed,
-                source,
-                None,
-                self.config.animations,
-            )));
+            self.active_cell = Some(Box::new(
+                new_active_exec_command(
+                    ev.call_id.clone(),
+                    command,
+                    parsed,
+                    source,
+                    None,
+                    self.config.animations,
+                )
+                .with_spinner_style(self.spinner_style()),
+            ));
         }
 
         if let Some(cell) = self
@@ -1155,12 +1531,15 @@ impl ChatWidget {
     ) {
         // If the patch was successful, just let the "Edited" block stand.
         // Otherwise, add a failure block.
-        if !event.success {
-            self.add_to_history(history_cell::new_patch_apply_failure(event.stderr));
-        }
         if let Some(overlay) = self.status_overlay.as_mut() {
+            if event.success {
+                overlay.record_applied_patch(&event.changes);
+            }
             overlay.set_run_header("Working");
         }
+        if !event.success {
+            self.add_to_history(history_cell::new_patch_apply_failure(event.stderr));
+        }
         if let Some(overlay) = self.status_overlay.as_ref() {
             overlay.refresh_git();
         }
@@ -1180,7 +1559,7 @@ impl ChatWidget {
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
         };
         if let Some(overlay) = self.status_overlay.as_mut() {
-            overlay.set_run_header(&StatusLineOverlay::approval_status_label("command"));
+            overlay.set_approval_run_label("command");
         }
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -1201,7 +1580,7 @@ impl ChatWidget {
             cwd: self.config.cwd.clone(),
         };
         if let Some(overlay) = self.status_overlay.as_mut() {
-            overlay.set_run_header(&StatusLineOverlay::approval_status_label("patch"));
+            overlay.set_approval_run_label("patch");
         }
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -1228,6 +1607,22 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// Ask whether the turn may go past a used-up budget. Raised before the
+    /// turn samples, so there is no stream to wait for.
+    fn handle_budget_exceeded_now(&mut self, id: String, ev: BudgetExceededEvent) {
+        self.flush_answer_stream_with_separator();
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.set_approval_run_label("budget");
+        }
+        let request = ApprovalRequest::Budget {
+            id,
+            budget: ev.budget,
+        };
+        self.bottom_pane
+            .push_approval_request(request, &self.config.features);
+        self.request_redraw();
+    }
+
     pub(crate) fn handle_exec_begin_now(&mut self, ev: ExecCommandBeginEvent) {
         // Ensure the status indicator is visible while the command runs.
         self.running_commands.insert(
@@ -1262,7 +1657,7 @@ impl ChatWidget {
         let interaction_input = ev.interaction_input.clone();
         if let Some(overlay) = self.status_overlay.as_mut() {
             overlay.resume_timer();
-            overlay.set_run_header(&StatusLineOverlay::exec_status_label(&ev.command));
+            overlay.set_exec_run_label(&ev.command);
         }
         if let Some(cell) = self
             .active_cell
@@ -1280,31 +1675,42 @@ impl ChatWidget {
         } else {
             self.flush_active_cell();
 
-            self.active_cell = Some(Box::new(new_active_exec_command(
-                ev.call_id.clone(),
-                ev.command.clone(),
-                ev.parsed_cmd,
-                ev.source,
-                interaction_input,
-                self.config.animations,
-            )));
+            self.active_cell = Some(Box::new(
+                new_active_exec_command(
+                    ev.call_id.clone(),
+                    ev.command.clone(),
+                    ev.parsed_cmd,
+                    ev.source,
+                    interaction_input,
+                    self.config.animations,
+                )
+                .with_spinner_style(self.spinner_style()),
+            ));
         }
 
         self.request_redraw();
     }
 
+    /// `[tui.spinner]` for the cells and the status indicator.
+    fn spinner_style(&self) -> SpinnerStyle {
+        SpinnerStyle::from_config(&self.config.tui_spinner)
+    }
+
     pub(crate) fn handle_mcp_begin_now(&mut self, ev: McpToolCallBeginEvent) {
         self.flush_answer_stream_with_separator();
         if let Some(overlay) = self.status_overlay.as_mut() {
             overlay.resume_timer();
-            overlay.set_run_header(&StatusLineOverlay::tool_status_label(&ev.invocation));
+            overlay.set_tool_run_label(&ev.invocation);
         }
         self.flush_active_cell();
-        self.active_cell = Some(Box::new(history_cell::new_active_mcp_tool_call(
-            ev.call_id,
-            ev.invocation,
-            self.config.animations,
-        )));
+        self.active_cell = Some(Box::new(
+            history_cell::new_active_mcp_tool_call(
+                ev.call_id,
+                ev.invocation,
+                self.config.animations,
+            )
+            .with_spinner_style(self.spinner_style()),
+        ));
         self.request_redraw();
     }
     pub(crate) fn handle_mcp_end_now(&mut self, ev: McpToolCallEndEvent) {
@@ -1329,7 +1735,8 @@ impl ChatWidget {
                     call_id,
                     invocation,
                     self.config.animations,
-                );
+                )
+                .with_spinner_style(self.spinner_style());
                 let extra_cell = cell.complete(duration, result);
                 self.active_cell = Some(Box::new(cell));
                 extra_cell
@@ -1362,6 +1769,7 @@ impl ChatWidget {
             skills,
             is_first_run,
             status_renderer,
+            build,
         } = common;
         let mut rng = rand::rng();
         let placeholder = EXAMPLE_PROMPTS[rng.random_range(0..EXAMPLE_PROMPTS.len())].to_string();
@@ -1389,6 +1797,7 @@ impl ChatWidget {
                 skills,
             }),
             status_overlay,
+            build,
             active_cell: None,
             config: config.clone(),
             auth_manager,
@@ -1399,7 +1808,10 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            token_info_replayed: false,
             rate_limit_snapshot: None,
+            rate_limit_history: RateLimitHistory::default(),
+            code88_relogin_running: false,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -1409,6 +1821,7 @@ impl ChatWidget {
             suppressed_exec_calls: HashSet::new(),
             last_unified_wait: None,
             task_complete_pending: false,
+            run_paused: false,
             mcp_startup_status: None,
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
@@ -1428,6 +1841,12 @@ impl ChatWidget {
             current_rollout_path: None,
         };
 
+        widget
+            .bottom_pane
+            .set_token_format(TokenCountFormatter::from_config(
+                &widget.config.tui_token_format,
+            ));
+        widget.bottom_pane.set_spinner_style(widget.spinner_style());
         widget.prefetch_rate_limits();
 
         if let Some(overlay) = widget.status_overlay.as_mut() {
@@ -1436,6 +1855,11 @@ impl ChatWidget {
                 .iter()
                 .map(|m| m.text.clone())
                 .collect();
+            overlay.set_auth_mode(
+                &widget.config,
+                widget.auth_manager.auth().map(|auth| auth.mode),
+            );
+            overlay.set_build(&widget.build);
             overlay.bootstrap(&widget.config, widget.token_info.clone(), queued);
         }
         widget.refresh_queued_user_messages();
@@ -1461,6 +1885,7 @@ impl ChatWidget {
             feedback,
             skills,
             status_renderer,
+            build,
             ..
         } = common;
         let mut rng = rand::rng();
@@ -1491,6 +1916,7 @@ impl ChatWidget {
                 skills,
             }),
             status_overlay,
+            build,
             active_cell: None,
             config: config.clone(),
             auth_manager,
@@ -1501,7 +1927,10 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            token_info_replayed: false,
             rate_limit_snapshot: None,
+            rate_limit_history: RateLimitHistory::default(),
+            code88_relogin_running: false,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -1511,6 +1940,7 @@ impl ChatWidget {
             suppressed_exec_calls: HashSet::new(),
             last_unified_wait: None,
             task_complete_pending: false,
+            run_paused: false,
             mcp_startup_status: None,
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
@@ -1530,6 +1960,12 @@ impl ChatWidget {
             current_rollout_path: None,
         };
 
+        widget
+            .bottom_pane
+            .set_token_format(TokenCountFormatter::from_config(
+                &widget.config.tui_token_format,
+            ));
+        widget.bottom_pane.set_spinner_style(widget.spinner_style());
         widget.prefetch_rate_limits();
 
         if let Some(overlay) = widget.status_overlay.as_mut() {
@@ -1538,6 +1974,11 @@ impl ChatWidget {
                 .iter()
                 .map(|m| m.text.clone())
                 .collect();
+            overlay.set_auth_mode(
+                &widget.config,
+                widget.auth_manager.auth().map(|auth| auth.mode),
+            );
+            overlay.set_build(&widget.build);
             overlay.bootstrap(&widget.config, widget.token_info.clone(), queued);
         }
         widget.refresh_queued_user_messages();
@@ -1582,6 +2023,60 @@ impl ChatWidget {
                 }
                 return;
             }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } if c.eq_ignore_ascii_case(&'k') && self.compact_shortcut_active() => {
+                self.dispatch_command(SlashCommand::Compact);
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } if c.eq_ignore_ascii_case(&'g') && self.status_overlay.is_some() => {
+                self.open_attention_popup();
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } if c.eq_ignore_ascii_case(&'l') => {
+                self.app_event_tx.send(AppEvent::SetStatusLineEnabled(
+                    self.status_overlay.is_none(),
+                ));
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } if c.eq_ignore_ascii_case(&'o')
+                && self
+                    .status_overlay
+                    .as_ref()
+                    .is_some_and(|overlay| overlay.session_id().is_some()) =>
+            {
+                self.open_session_popup();
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } if c.eq_ignore_ascii_case(&'s')
+                && (self.run_paused || self.bottom_pane.is_task_running()) =>
+            {
+                self.toggle_pause();
+                return;
+            }
             other if other.kind == KeyEventKind::Press => {
                 self.bottom_pane.clear_ctrl_c_quit_hint();
             }
@@ -1594,6 +2089,13 @@ impl ChatWidget {
             self.halt_running_task();
             return;
         }
+        if key_event.kind == KeyEventKind::Press
+            && key_event.code == KeyCode::Esc
+            && self.run_paused
+        {
+            self.end_paused_run();
+            return;
+        }
 
         match key_event {
             KeyEvent {
@@ -1622,8 +2124,12 @@ impl ChatWidget {
                     InputResult::Command(cmd) => {
                         self.dispatch_command(cmd);
                     }
+                    InputResult::CommandWithArgs(cmd, args) => {
+                        self.dispatch_command_with_args(cmd, &args);
+                    }
                     InputResult::None => {}
                 }
+                self.sync_pending_approvals();
             }
         }
     }
@@ -1643,6 +2149,27 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn dispatch_command_with_args(&mut self, cmd: SlashCommand, args: &str) {
+        match cmd {
+            SlashCommand::Statusline => {
+                let enabled = match args {
+                    "on" => true,
+                    "off" => false,
+                    "toggle" => self.status_overlay.is_none(),
+                    _ => {
+                        self.add_error_message(format!(
+                            "Unknown argument '{args}'. Usage: /statusline [on|off|toggle]"
+                        ));
+                        return;
+                    }
+                };
+                self.app_event_tx
+                    .send(AppEvent::SetStatusLineEnabled(enabled));
+            }
+            _ => self.dispatch_command(cmd),
+        }
+    }
+
     fn dispatch_command(&mut self, cmd: SlashCommand) {
         if !cmd.available_during_task() && self.bottom_pane.is_task_running() {
             let message = format!(
@@ -1733,6 +2260,12 @@ impl ChatWidget {
             SlashCommand::Status => {
                 self.add_status_output();
             }
+            SlashCommand::Statusline => {
+                self.open_status_line_skin_popup();
+            }
+            SlashCommand::RefreshStatus => {
+                self.refresh_status_line();
+            }
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
@@ -1912,6 +2445,15 @@ impl ChatWidget {
     pub(crate) fn handle_codex_event(&mut self, event: Event) {
         let Event { id, msg } = event;
         self.dispatch_event_msg(Some(id), msg, false);
+        self.sync_pending_approvals();
+    }
+
+    /// Mirror the approvals still waiting on the user into the run pill.
+    fn sync_pending_approvals(&mut self) {
+        let count = self.interrupts.pending_approvals() + self.bottom_pane.pending_approvals();
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.set_pending_approvals(i64::try_from(count).unwrap_or(i64::MAX));
+        }
     }
 
     /// Dispatch a protocol `EventMsg` to the appropriate handler.
@@ -1950,11 +2492,31 @@ impl ChatWidget {
                 self.on_task_complete(last_agent_message)
             }
             EventMsg::TokenCount(ev) => {
+                self.token_info_replayed = from_replay;
                 self.set_token_info(ev.info);
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
+            EventMsg::BudgetUpdate(ev) => {
+                if let Some(overlay) = self.status_overlay.as_mut() {
+                    overlay.update_budgets(&ev.budgets);
+                }
+            }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
-            EventMsg::Error(ErrorEvent { message, .. }) => self.on_error(message),
+            EventMsg::StructuredWarning(ev) => {
+                self.push_status_line_attention(Attention::from(ev));
+                self.request_redraw();
+            }
+            EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info,
+            }) => {
+                if matches!(codex_error_info, Some(CodexErrorInfo::Unauthorized))
+                    && self.config.model_provider.code88_auth
+                {
+                    self.app_event_tx.send(AppEvent::Code88SignInExpired);
+                }
+                self.on_error(message);
+            }
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
             EventMsg::TurnAborted(ev) => match ev.reason {
@@ -1979,6 +2541,9 @@ impl ChatWidget {
             EventMsg::ElicitationRequest(ev) => {
                 self.on_elicitation_request(ev);
             }
+            EventMsg::BudgetExceeded(ev) => {
+                self.handle_budget_exceeded_now(id.unwrap_or_default(), ev)
+            }
             EventMsg::ExecCommandBegin(ev) => self.on_exec_command_begin(ev),
             EventMsg::ExecCommandOutputDelta(delta) => self.on_exec_command_output_delta(delta),
             EventMsg::PatchApplyBegin(ev) => self.on_patch_apply_begin(ev),
@@ -2102,8 +2667,45 @@ impl ChatWidget {
     }
 
     pub(crate) fn maybe_post_pending_notification(&mut self, tui: &mut crate::tui::Tui) {
-        if let Some(notif) = self.pending_notification.take() {
-            tui.notify(notif.display());
+        let Some(notif) = self.pending_notification.take() else {
+            return;
+        };
+        let message = notif.display();
+        match &self.config.tui_notifications {
+            Notifications::Channels(channels) => {
+                if channels.desktop {
+                    tui.notify(&message);
+                }
+                if channels.osc777 {
+                    tui.notify_osc777("Codex", &message);
+                }
+                if channels.bell {
+                    tui.ring_bell();
+                }
+            }
+            Notifications::Enabled(_) | Notifications::Custom(_) => {
+                tui.notify(message);
+            }
+        }
+    }
+
+    pub(crate) fn sync_terminal_state(&mut self, tui: &mut crate::tui::Tui) {
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.report_renderer_panic();
+            overlay.set_terminal_focused(tui.is_terminal_focused());
+        }
+        let progress = self
+            .status_overlay
+            .as_ref()
+            .map(StatusLineOverlay::terminal_progress)
+            .unwrap_or(TerminalProgress::Clear);
+        tui.set_terminal_progress(progress);
+        if let Some(title) = self
+            .status_overlay
+            .as_ref()
+            .and_then(StatusLineOverlay::terminal_title)
+        {
+            tui.set_terminal_title(&title);
         }
     }
 
@@ -2162,12 +2764,20 @@ impl ChatWidget {
         };
         self.add_to_history(crate::status::new_status_output(
             &self.config,
+            self.build.sha,
             self.auth_manager.as_ref(),
             total_usage,
             context_usage,
             &self.conversation_id,
             self.rate_limit_snapshot.as_ref(),
             self.plan_type,
+            self.status_overlay
+                .as_ref()
+                .and_then(StatusLineOverlay::code88_info),
+            self.status_overlay
+                .as_ref()
+                .map(StatusLineOverlay::model_token_usage)
+                .unwrap_or_default(),
             Local::now(),
         ));
     }
@@ -2634,6 +3244,154 @@ impl ChatWidget {
         });
     }
 
+    /// `/refresh-status`: re-run every status line detection now instead of
+    /// waiting for the next turn or re-scan.
+    fn refresh_status_line(&mut self) {
+        let Some(overlay) = self.status_overlay.as_mut() else {
+            self.add_info_message(
+                "The status line is off. Turn it on with `/statusline on` or ctrl + l.".to_string(),
+                None,
+            );
+            return;
+        };
+        overlay.spawn_background_tasks();
+        self.request_redraw();
+    }
+
+    pub(crate) fn open_status_line_skin_popup(&mut self) {
+        let Some(overlay) = self.status_overlay.as_ref() else {
+            self.add_info_message(
+                "The status line is off. Turn it on with `/statusline on` or ctrl + l, then pick a skin."
+                    .to_string(),
+                None,
+            );
+            return;
+        };
+        let current = overlay.skin().to_string();
+        let items: Vec<SelectionItem> = skins::SKINS
+            .iter()
+            .map(|(name, description)| ((*name).to_string(), (*description).to_string()))
+            .chain(skins::registered_skins())
+            .map(|(name, description)| {
+                let skin = name.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::SetStatusLineSkin(skin.clone()));
+                })];
+                SelectionItem {
+                    is_current: name == current,
+                    name,
+                    description: Some(description),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Select Status Line Skin".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            header: Box::new(()),
+            ..Default::default()
+        });
+    }
+
+    /// `ctrl + g`: every warning in the attention segment, newest first.
+    fn open_attention_popup(&mut self) {
+        let Some(overlay) = self.status_overlay.as_ref() else {
+            return;
+        };
+        let entries = overlay.attention_entries();
+        if entries.is_empty() {
+            self.add_info_message("Nothing needs your attention.".to_string(), None);
+            return;
+        }
+        let mut items: Vec<SelectionItem> = entries
+            .iter()
+            .rev()
+            .map(|entry| SelectionItem {
+                name: entry.message.clone(),
+                description: Some(format!(
+                    "{} · {}",
+                    entry.source.label(),
+                    entry.severity.label()
+                )),
+                dismiss_on_select: true,
+                ..Default::default()
+            })
+            .collect();
+        items.push(SelectionItem {
+            name: "Dismiss all".to_string(),
+            description: Some("Clear the attention segment".to_string()),
+            actions: vec![Box::new(|tx| {
+                tx.send(AppEvent::ClearStatusLineAttention);
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Attention".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            header: Box::new(()),
+            ..Default::default()
+        });
+    }
+
+    /// `ctrl + o`: copy the session id or the command that resumes it.
+    fn open_session_popup(&mut self) {
+        let Some(session_id) = self
+            .status_overlay
+            .as_ref()
+            .and_then(|overlay| overlay.session_id())
+            .map(str::to_string)
+        else {
+            return;
+        };
+        let copy_item = |name: &str, text: String, what: &'static str| SelectionItem {
+            name: name.to_string(),
+            description: Some(text.clone()),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::CopyToClipboard {
+                    text: text.clone(),
+                    what: what.to_string(),
+                });
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        };
+        let items = vec![
+            copy_item(
+                "Copy resume command",
+                format!("codex resume {session_id}"),
+                "the resume command",
+            ),
+            copy_item("Copy session id", session_id, "the session id"),
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Session".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            header: Box::new(()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn copy_to_clipboard(&mut self, text: &str, what: &str) {
+        match copy_text_to_clipboard(text) {
+            Ok(()) => self.add_info_message(format!("Copied {what} to the clipboard."), None),
+            Err(err) => {
+                tracing::warn!("failed to copy {what}: {err}");
+                self.add_to_history(history_cell::new_error_event(format!(
+                    "Failed to copy {what}: {err}. It is `{text}`.",
+                )));
+            }
+        }
+    }
+
     fn approval_preset_actions(
         approval: AskForApproval,
         sandbox: SandboxPolicy,
@@ -2649,7 +3407,11 @@ impl ChatWidget {
                 summary: None,
             }));
             tx.send(AppEvent::UpdateAskForApprovalPolicy(approval));
-            tx.send(AppEvent::UpdateSandboxPolicy(sandbox_clone));
+            tx.send(AppEvent::UpdateSandboxPolicy(sandbox_clone.clone()));
+            tx.send(AppEvent::PersistWorkspacePolicy {
+                approval,
+                sandbox: sandbox_clone,
+            });
         })]
     }
 
@@ -2958,6 +3720,9 @@ impl ChatWidget {
     /// Set the approval policy in the widget's config copy.
     pub(crate) fn set_approval_policy(&mut self, policy: AskForApproval) {
         self.config.approval_policy = policy;
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.sync_policy(&self.config);
+        }
     }
 
     /// Set the sandbox policy in the widget's config copy.
@@ -2967,6 +3732,9 @@ impl ChatWidget {
             || codex_core::get_platform_sandbox().is_some();
 
         self.config.sandbox_policy = policy;
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.sync_policy(&self.config);
+        }
 
         #[cfg(target_os = "windows")]
         if should_clear_downgrade {
@@ -3039,6 +3807,7 @@ impl ChatWidget {
     /// Handle Ctrl-C key press.
     fn on_ctrl_c(&mut self) {
         if self.bottom_pane.on_ctrl_c() == CancellationEvent::Handled {
+            self.sync_pending_approvals();
             return;
         }
 
@@ -3058,21 +3827,62 @@ impl ChatWidget {
         self.running_commands.clear();
         if let Some(overlay) = self.status_overlay.as_mut() {
             overlay.set_interrupt_hint_visible(false);
-            overlay.complete_task();
+            overlay.interrupt_task();
+        }
+        self.submit_op(Op::Interrupt);
+        self.request_redraw();
+    }
+
+    /// Ctrl+S: pause the running task, or resume the paused one. Pausing
+    /// interrupts the turn so the model stops streaming, but keeps the run
+    /// open with its timer stopped; the conversation keeps everything said
+    /// so far, so resuming just asks the model to carry on.
+    fn toggle_pause(&mut self) {
+        if self.run_paused {
+            self.submit_user_message(RESUME_PROMPT.to_string().into());
+            return;
+        }
+        self.run_paused = true;
+        self.bottom_pane.clear_ctrl_c_quit_hint();
+        self.bottom_pane.set_task_running(false);
+        self.bottom_pane.set_interrupt_hint_visible(false);
+        self.running_commands.clear();
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.pause_task();
         }
         self.submit_op(Op::Interrupt);
         self.request_redraw();
     }
 
+    /// Esc while paused: close the run as if it had been interrupted.
+    fn end_paused_run(&mut self) {
+        self.run_paused = false;
+        if let Some(overlay) = self.status_overlay.as_mut() {
+            overlay.set_interrupt_hint_visible(false);
+            overlay.interrupt_task();
+        }
+        self.request_redraw();
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.bottom_pane.composer_is_empty()
     }
 
+    /// Ctrl+K compacts while the status line suggests it. Only with an empty
+    /// composer and no popups, where the key would otherwise do nothing.
+    fn compact_shortcut_active(&self) -> bool {
+        self.status_overlay
+            .as_ref()
+            .is_some_and(StatusLineOverlay::compact_suggested)
+            && self.bottom_pane.is_normal_backtrack_mode()
+            && self.bottom_pane.composer_is_empty()
+    }
+
     /// True when the UI is in the regular composer state with no running task,
     /// no modal overlay (e.g. approvals or status indicator), and no composer popups.
     /// In this state Esc-Esc backtracking is enabled.
     pub(crate) fn is_normal_backtrack_mode(&self) -> bool {
-        self.bottom_pane.is_normal_backtrack_mode()
+        !self.run_paused && self.bottom_pane.is_normal_backtrack_mode()
     }
 
     pub(crate) fn insert_str(&mut self, text: &str) {
@@ -3283,6 +4093,15 @@ impl ChatWidget {
             .unwrap_or_default()
     }
 
+    /// Usage this run added, leaving out totals replayed on resume.
+    pub(crate) fn session_token_usage(&self) -> TokenUsage {
+        if self.token_info_replayed {
+            TokenUsage::default()
+        } else {
+            self.token_usage()
+        }
+    }
+
     pub(crate) fn conversation_id(&self) -> Option<ConversationId> {
         self.conversation_id
     }
@@ -3299,15 +4118,13 @@ impl ChatWidget {
 
     pub(crate) fn clear_token_usage(&mut self) {
         self.token_info = None;
+        self.token_info_replayed = false;
     }
 
     fn bottom_pane_renderable(&self) -> impl Renderable + '_ {
         BottomPaneWithOverlay {
             bottom_pane: &self.bottom_pane,
-            overlay: self
-                .status_overlay
-                .as_ref()
-                .filter(|_| !self.bottom_pane.has_active_view()),
+            overlay: self.status_overlay.as_ref(),
         }
     }
 
@@ -3378,7 +4195,17 @@ impl Renderable for BottomPaneWithOverlay<'_> {
             if !layout.status_line_area.is_empty() {
                 Clear.render(layout.status_line_area, buf);
                 if let Some(overlay) = self.overlay {
-                    overlay.render_status_line(layout.status_line_area, buf);
+                    match layout.run_pill {
+                        RunPillPlacement::OwnRow => {
+                            overlay.render_status_line(layout.status_line_area, buf);
+                        }
+                        RunPillPlacement::Inline => {
+                            overlay.render_compact_line(layout.status_line_area, buf);
+                        }
+                        RunPillPlacement::Hidden => {
+                            overlay.render_status_line_without_pill(layout.status_line_area, buf);
+                        }
+                    }
                 }
             }
         } else {
@@ -3388,8 +4215,10 @@ impl Renderable for BottomPaneWithOverlay<'_> {
 
     fn desired_height(&self, width: u16) -> u16 {
         let mut height = self.bottom_pane.desired_height(width);
-        if self.overlay.is_some() && !self.bottom_pane.has_active_view() {
-            height = height.saturating_add(StatusLineOverlay::reserved_rows());
+        if self.overlay.is_some() {
+            height = height.saturating_add(StatusLineOverlay::reserved_rows(
+                self.bottom_pane.has_active_view(),
+            ));
         }
         height
     }
@@ -3410,6 +4239,7 @@ impl Renderable for BottomPaneWithOverlay<'_> {
 
 enum Notification {
     AgentTurnComplete { response: String },
+    TaskComplete(CompletedRun),
     ExecApprovalRequested { command: String },
     EditApprovalRequested { cwd: PathBuf, changes: Vec<PathBuf> },
     ElicitationRequested { server_name: String },
@@ -3422,6 +4252,11 @@ impl Notification {
                 Notification::agent_turn_preview(response)
                     .unwrap_or_else(|| "Agent turn complete".to_string())
             }
+            Notification::TaskComplete(run) => format!(
+                "{} finished in {}",
+                run.label,
+                format_elapsed_compact(run.elapsed.as_secs())
+            ),
             Notification::ExecApprovalRequested { command } => {
                 format!("Approval requested: {}", truncate_text(command, 30))
             }
@@ -3444,7 +4279,9 @@ impl Notification {
 
     fn type_name(&self) -> &str {
         match self {
-            Notification::AgentTurnComplete { .. } => "agent-turn-complete",
+            Notification::AgentTurnComplete { .. } | Notification::TaskComplete(_) => {
+                "agent-turn-complete"
+            }
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. }
             | Notification::ElicitationRequested { .. } => "approval-requested",
@@ -3455,6 +4292,9 @@ impl Notification {
         match settings {
             Notifications::Enabled(enabled) => *enabled,
             Notifications::Custom(allowed) => allowed.iter().any(|a| a == self.type_name()),
+            Notifications::Channels(channels) => {
+                channels.bell || channels.osc777 || channels.desktop
+            }
         }
     }
 
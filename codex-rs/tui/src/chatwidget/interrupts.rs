@@ -40,6 +40,21 @@ impl InterruptManager {
         self.queue.is_empty()
     }
 
+    /// Approval and elicitation requests deferred until the stream finishes.
+    pub(crate) fn pending_approvals(&self) -> usize {
+        self.queue
+            .iter()
+            .filter(|interrupt| {
+                matches!(
+                    interrupt,
+                    QueuedInterrupt::ExecApproval(..)
+                        | QueuedInterrupt::ApplyPatchApproval(..)
+                        | QueuedInterrupt::Elicitation(_)
+                )
+            })
+            .count()
+    }
+
     pub(crate) fn push_exec_approval(&mut self, id: String, ev: ExecApprovalRequestEvent) {
         self.queue.push_back(QueuedInterrupt::ExecApproval(id, ev));
     }
@@ -4,6 +4,7 @@ use crate::app_event_sender::AppEventSender;
 use crate::statusline::CustomStatusLineRenderer;
 use crate::statusline::StatusLineOverlay;
 use crate::statusline::StatusLineRenderer;
+use crate::statusline::attention::AttentionSeverity;
 use crate::statusline::clear_devspace_override_for_tests;
 use crate::statusline::set_devspace_override_for_tests;
 use crate::test_backend::VT100Backend;
@@ -369,6 +370,7 @@ async fn helpers_are_available_and_do_not_panic() {
         skills: None,
         is_first_run: true,
         status_renderer: None,
+        build: BuildStatus::default(),
     };
     let mut w = ChatWidget::new(init, conversation_manager);
     // Basic construction sanity.
@@ -411,6 +413,7 @@ fn make_chatwidget_with_config(
         codex_op_tx: op_tx,
         bottom_pane: bottom,
         status_overlay,
+        build: BuildStatus::default(),
         active_cell: None,
         config: cfg.clone(),
         auth_manager: auth_manager.clone(),
@@ -418,7 +421,10 @@ fn make_chatwidget_with_config(
         session_header: SessionHeader::new(cfg.model),
         initial_user_message: None,
         token_info: None,
+        token_info_replayed: false,
         rate_limit_snapshot: None,
+        rate_limit_history: RateLimitHistory::default(),
+        code88_relogin_running: false,
         plan_type: None,
         rate_limit_warnings: RateLimitWarningState::default(),
         rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -580,6 +586,77 @@ fn test_rate_limit_warnings_monthly() {
     );
 }
 
+#[test]
+fn credit_warnings_name_the_88code_limit() {
+    let mut state = RateLimitWarningState::default();
+    let mut warnings: Vec<String> = Vec::new();
+
+    warnings.extend(state.take_credit_warnings(50.0));
+    warnings.extend(state.take_credit_warnings(92.0));
+    warnings.extend(state.take_credit_warnings(93.0));
+    warnings.extend(state.take_credit_warnings(100.0));
+    assert_eq!(
+        warnings,
+        vec![String::from(
+            "Heads up, you've used over 90% of your 88code credit limit. Run /status for a breakdown.",
+        )]
+    );
+}
+
+#[test]
+fn pasted_code88_token_is_submitted_trimmed() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+
+    chat.open_code88_token_prompt();
+    chat.handle_paste("  88-token ".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let submitted: Vec<String> = std::iter::from_fn(|| rx.try_recv().ok())
+        .filter_map(|event| match event {
+            AppEvent::Submit88CodeToken(token) => Some(token),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(submitted, vec!["88-token".to_string()]);
+}
+
+#[test]
+fn code88_credits_outlive_token_counts_without_rate_limits() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+    chat.config.model_provider.code88_auth = true;
+
+    chat.update_statusline_88code(Some(crate::statusline::StatusLine88CodeSnapshot {
+        credits_remaining: Some(20.0),
+        credit_limit: Some(100.0),
+        ..Default::default()
+    }));
+    // Core reports no rate limits for the 88code provider after each turn.
+    chat.on_rate_limit_snapshot(None);
+
+    let display = chat
+        .rate_limit_snapshot
+        .as_ref()
+        .expect("88code credits are kept");
+    assert_eq!(
+        display
+            .credits
+            .as_ref()
+            .and_then(|credits| credits.balance.as_deref()),
+        Some("20")
+    );
+    assert_eq!(
+        display.primary.as_ref().map(|window| window.used_percent),
+        Some(80.0)
+    );
+    // Spending 80% of the credits warns like any other limit.
+    assert_eq!(drain_insert_history(&mut rx).len(), 1);
+    assert_eq!(chat.rate_limit_warnings.primary_index, 1);
+
+    chat.config.model_provider.code88_auth = false;
+    chat.on_rate_limit_snapshot(None);
+    assert!(chat.rate_limit_snapshot.is_none());
+}
+
 #[test]
 fn rate_limit_snapshot_keeps_prior_credits_when_missing_from_headers() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
@@ -1134,6 +1211,232 @@ fn esc_interrupt_resets_status_indicator_and_statusline() {
     let _ = drain_insert_history(&mut rx);
 }
 
+#[test]
+fn status_line_turns_on_and_off_mid_session() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+    chat.on_task_started();
+    assert!(chat.status_overlay.is_none());
+    assert!(chat.bottom_pane.status_indicator_visible());
+    let height_without = chat.desired_height(80);
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL));
+    assert_matches!(rx.try_recv(), Ok(AppEvent::SetStatusLineEnabled(true)));
+    chat.set_status_line_enabled(true);
+    assert!(chat.status_overlay.is_some());
+    assert!(!chat.bottom_pane.status_indicator_visible());
+    assert_ne!(chat.desired_height(80), height_without);
+
+    chat.dispatch_command_with_args(SlashCommand::Statusline, "toggle");
+    assert_matches!(rx.try_recv(), Ok(AppEvent::SetStatusLineEnabled(false)));
+    chat.set_status_line_enabled(false);
+    assert!(chat.status_overlay.is_none());
+    assert!(chat.bottom_pane.status_indicator_visible());
+    assert_eq!(chat.desired_height(80), height_without);
+
+    chat.dispatch_command_with_args(SlashCommand::Statusline, "sideways");
+    let history = drain_insert_history(&mut rx);
+    assert!(
+        lines_to_single_string(history.last().expect("usage error"))
+            .contains("Usage: /statusline [on|off|toggle]")
+    );
+}
+
+#[test]
+fn reloaded_status_line_settings_apply_without_restart() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual_with_custom_statusline();
+    let mut settings = chat.config.tui_statusline.clone();
+    settings.skin = "minimal-plain".to_string();
+    settings.segments.clock = true;
+    chat.apply_status_line_settings(settings);
+
+    assert_eq!(chat.config.tui_statusline.skin, "minimal-plain");
+    let overlay = chat.status_overlay.as_mut().expect("status line stays on");
+    assert_eq!(overlay.skin(), "minimal-plain");
+    assert!(overlay.state_mut().segments().clock);
+}
+
+#[test]
+fn status_line_counts_queued_messages_while_a_popup_hides_the_run_pill() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual_with_custom_statusline();
+    chat.on_task_started();
+    chat.queued_user_messages
+        .push_back(UserMessage::from("first queued".to_string()));
+    chat.queued_user_messages
+        .push_back(UserMessage::from("second queued".to_string()));
+    chat.refresh_queued_user_messages();
+    assert!(!render_bottom_popup(&chat, 80).contains("2 queued"));
+
+    if let Some(overlay) = chat.status_overlay.as_mut() {
+        overlay.set_session_id(Some("0199a213-81c0-7800-8aa1-bbab2a035a53".to_string()));
+    }
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Copy session id"), "{popup}");
+    assert!(popup.contains("2 queued"), "{popup}");
+}
+
+#[test]
+fn ctrl_s_pauses_the_run_and_esc_ends_it() {
+    use std::time::Instant;
+
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual_with_custom_statusline();
+    let ctrl_s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+    let run_state = |chat: &mut ChatWidget| {
+        chat.status_overlay
+            .as_mut()
+            .and_then(|overlay| {
+                overlay
+                    .state_mut()
+                    .snapshot_for_render(Instant::now())
+                    .run_state
+            })
+            .expect("run state")
+    };
+    chat.on_task_started();
+
+    chat.handle_key_event(ctrl_s);
+    assert_matches!(op_rx.try_recv(), Ok(Op::Interrupt));
+    assert!(!chat.bottom_pane.is_task_running());
+    let paused = run_state(&mut chat);
+    assert!(paused.paused);
+    assert!(paused.timer.is_some_and(|timer| timer.is_paused));
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
+            reason: TurnAbortReason::Interrupted,
+        }),
+    });
+    let history: String = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect();
+    assert!(history.contains("Paused"), "{history}");
+    assert!(!history.contains("Conversation interrupted"), "{history}");
+    assert!(!chat.is_normal_backtrack_mode());
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+    let ended = run_state(&mut chat);
+    assert!(!ended.paused);
+    assert_eq!(ended.label, "Ready when you are");
+}
+
+#[test]
+fn ctrl_s_resumes_a_paused_run() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual_with_custom_statusline();
+    let ctrl_s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+    chat.on_task_started();
+    chat.handle_key_event(ctrl_s);
+    assert_matches!(op_rx.try_recv(), Ok(Op::Interrupt));
+
+    chat.handle_key_event(ctrl_s);
+    assert_matches!(op_rx.try_recv(), Ok(Op::UserInput { .. }));
+}
+
+#[test]
+fn warnings_and_rejected_sign_ins_raise_attention() {
+    use codex_core::protocol::StructuredWarningEvent;
+    use codex_core::protocol::WarningKind;
+    use codex_core::protocol::WarningSeverity;
+    use std::time::Instant;
+
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual_with_custom_statusline();
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::Warning(WarningEvent {
+            message: "ghost snapshot skipped".to_string(),
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::StructuredWarning(StructuredWarningEvent {
+            kind: WarningKind::Auth,
+            severity: WarningSeverity::Critical,
+            message: "sign in again".to_string(),
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::Error(codex_core::protocol::ErrorEvent {
+            message: "sign in again".to_string(),
+            codex_error_info: Some(CodexErrorInfo::Unauthorized),
+        }),
+    });
+    let attention = chat
+        .status_overlay
+        .as_mut()
+        .and_then(|overlay| {
+            overlay
+                .state_mut()
+                .snapshot_for_render(Instant::now())
+                .attention
+        })
+        .expect("attention segment");
+    assert_eq!(attention.count, 2);
+    assert_eq!(attention.severity, AttentionSeverity::Critical);
+    assert_eq!(attention.latest, "sign in again");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Attention"), "{popup}");
+    assert!(popup.contains("ghost snapshot skipped"), "{popup}");
+    assert!(popup.contains("Dismiss all"), "{popup}");
+}
+
+#[test]
+fn ctrl_o_offers_to_copy_the_session_id_and_resume_command() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual_with_custom_statusline();
+    let ctrl_o = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+    chat.handle_key_event(ctrl_o);
+    assert!(!render_bottom_popup(&chat, 80).contains("Copy session id"));
+
+    let id = "0199a213-81c0-7800-8aa1-bbab2a035a53";
+    if let Some(overlay) = chat.status_overlay.as_mut() {
+        overlay.set_session_id(Some(id.to_string()));
+    }
+    chat.handle_key_event(ctrl_o);
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Session"), "{popup}");
+    assert!(popup.contains(&format!("codex resume {id}")), "{popup}");
+    assert!(popup.contains("Copy session id"), "{popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let copied = std::iter::from_fn(|| rx.try_recv().ok()).find_map(|event| match event {
+        AppEvent::CopyToClipboard { text, .. } => Some(text),
+        _ => None,
+    });
+    assert_eq!(copied, Some(format!("codex resume {id}")));
+}
+
+#[test]
+fn ctrl_k_compacts_only_when_status_line_suggests_it() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual_with_custom_statusline();
+    let ctrl_k = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL);
+    let sent_compact = |rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>| {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .any(|event| matches!(event, AppEvent::CodexOp(Op::Compact)))
+    };
+
+    chat.handle_key_event(ctrl_k);
+    assert!(!sent_compact(&mut rx));
+
+    let info = TokenUsageInfo {
+        total_token_usage: TokenUsage::default(),
+        last_token_usage: TokenUsage {
+            input_tokens: 99_000,
+            total_tokens: 99_000,
+            ..TokenUsage::default()
+        },
+        model_context_window: Some(100_000),
+    };
+    if let Some(overlay) = chat.status_overlay.as_mut() {
+        overlay.update_tokens(Some(info));
+    }
+    chat.handle_key_event(ctrl_k);
+    assert!(sent_compact(&mut rx));
+}
+
 #[test]
 fn ctrl_c_shutdown_ignores_caps_lock() {
     let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
@@ -32,6 +32,10 @@ pub struct Cli {
     #[clap(skip)]
     pub resume_show_all: bool,
 
+    /// Resume the last session that ended in this directory.
+    #[arg(long = "resume-last", default_value_t = false)]
+    pub resume_workspace_last: bool,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -87,4 +91,10 @@ pub struct Cli {
 
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
+
+    /// Internal: the commit this binary was built from (the CLI's
+    /// `CODEX_CLI_GIT_SHA`), for `/status` and the `build` status line
+    /// segment.
+    #[clap(skip)]
+    pub build_sha: Option<&'static str>,
 }
@@ -116,6 +116,23 @@ pub fn paste_image_as_png() -> Result<(Vec<u8>, PastedImageInfo), PasteImageErro
     ))
 }
 
+/// Put `text` on the system clipboard, e.g. the session id or resume command.
+#[cfg(not(target_os = "android"))]
+pub fn copy_text_to_clipboard(text: &str) -> Result<(), PasteImageError> {
+    let mut cb = arboard::Clipboard::new()
+        .map_err(|e| PasteImageError::ClipboardUnavailable(e.to_string()))?;
+    cb.set_text(text.to_string())
+        .map_err(|e| PasteImageError::ClipboardUnavailable(e.to_string()))
+}
+
+/// Android/Termux does not support arboard; return a clear error.
+#[cfg(target_os = "android")]
+pub fn copy_text_to_clipboard(_text: &str) -> Result<(), PasteImageError> {
+    Err(PasteImageError::ClipboardUnavailable(
+        "copying to the clipboard is unsupported on Android".into(),
+    ))
+}
+
 /// Convenience: write to a temp file and return its path + info.
 #[cfg(not(target_os = "android"))]
 pub fn paste_image_to_temp_png() -> Result<(PathBuf, PastedImageInfo), PasteImageError> {
@@ -99,11 +99,7 @@ struct Row {
 fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
     let mut rows: Vec<Row> = Vec::new();
     for (path, change) in changes.iter() {
-        let (added, removed) = match change {
-            FileChange::Add { content } => (content.lines().count(), 0),
-            FileChange::Delete { content } => (0, content.lines().count()),
-            FileChange::Update { unified_diff, .. } => calculate_add_remove_from_diff(unified_diff),
-        };
+        let (added, removed) = line_counts(change);
         let move_path = match change {
             FileChange::Update {
                 move_path: Some(new),
@@ -314,6 +310,26 @@ pub(crate) fn display_path_for(path: &Path, cwd: &Path) -> String {
     chosen.display().to_string()
 }
 
+/// Total lines added and removed across `changes`.
+pub(crate) fn count_line_changes(changes: &HashMap<PathBuf, FileChange>) -> (i64, i64) {
+    let (added, removed) = changes
+        .values()
+        .map(line_counts)
+        .fold((0, 0), |(added, removed), (a, r)| (added + a, removed + r));
+    (
+        i64::try_from(added).unwrap_or(i64::MAX),
+        i64::try_from(removed).unwrap_or(i64::MAX),
+    )
+}
+
+fn line_counts(change: &FileChange) -> (usize, usize) {
+    match change {
+        FileChange::Add { content } => (content.lines().count(), 0),
+        FileChange::Delete { content } => (0, content.lines().count()),
+        FileChange::Update { unified_diff, .. } => calculate_add_remove_from_diff(unified_diff),
+    }
+}
+
 fn calculate_add_remove_from_diff(diff: &str) -> (usize, usize) {
     if let Ok(patch) = diffy::Patch::from_str(diff) {
         patch
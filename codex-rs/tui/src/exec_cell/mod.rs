@@ -1,5 +1,6 @@
 mod model;
 mod render;
+mod spinner;
 
 pub(crate) use model::CommandOutput;
 #[cfg(test)]
@@ -9,4 +10,5 @@ pub(crate) use render::OutputLinesParams;
 pub(crate) use render::TOOL_CALL_MAX_LINES;
 pub(crate) use render::new_active_exec_command;
 pub(crate) use render::output_lines;
-pub(crate) use render::spinner;
+pub(crate) use spinner::SpinnerStyle;
+pub(crate) use spinner::spinner;
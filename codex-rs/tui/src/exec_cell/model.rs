@@ -4,6 +4,8 @@ use std::time::Instant;
 use codex_core::protocol::ExecCommandSource;
 use codex_protocol::parse_command::ParsedCommand;
 
+use super::spinner::SpinnerStyle;
+
 #[derive(Clone, Debug, Default)]
 pub(crate) struct CommandOutput {
     pub(crate) exit_code: i32,
@@ -29,6 +31,7 @@ pub(crate) struct ExecCall {
 pub(crate) struct ExecCell {
     pub(crate) calls: Vec<ExecCall>,
     animations_enabled: bool,
+    spinner_style: SpinnerStyle,
 }
 
 impl ExecCell {
@@ -36,9 +39,16 @@ impl ExecCell {
         Self {
             calls: vec![call],
             animations_enabled,
+            spinner_style: SpinnerStyle::default(),
         }
     }
 
+    /// Draw the running spinner with `style` instead of the default dot.
+    pub(crate) fn with_spinner_style(mut self, style: SpinnerStyle) -> Self {
+        self.spinner_style = style;
+        self
+    }
+
     pub(crate) fn with_added_call(
         &self,
         call_id: String,
@@ -61,6 +71,7 @@ impl ExecCell {
             Some(Self {
                 calls: [self.calls.clone(), vec![call]].concat(),
                 animations_enabled: self.animations_enabled,
+                spinner_style: self.spinner_style.clone(),
             })
         } else {
             None
@@ -121,6 +132,10 @@ impl ExecCell {
         self.animations_enabled
     }
 
+    pub(crate) fn spinner_style(&self) -> &SpinnerStyle {
+        &self.spinner_style
+    }
+
     pub(crate) fn iter_calls(&self) -> impl Iterator<Item = &ExecCall> {
         self.calls.iter()
     }
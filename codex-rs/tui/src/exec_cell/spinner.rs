@@ -0,0 +1,183 @@
+//! The spinner shown by running exec cells, the status indicator and the
+//! status line, following `[tui.spinner]`: a named frame set or custom
+//! frames, their pace, and a static glyph for reduced motion.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::config::types::SpinnerFrameSet;
+use codex_core::config::types::SpinnerSettings;
+use ratatui::style::Stylize;
+use ratatui::text::Span;
+
+use crate::shimmer::shimmer_spans;
+
+/// How long the default dot stays lit, and then dimmed, when it blinks.
+const DOT_BLINK: Duration = Duration::from_millis(600);
+
+/// `[tui.spinner]`, resolved. Each widget that draws a spinner keeps its
+/// own copy; the default is the dot.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct SpinnerStyle {
+    frames: SpinnerFrames,
+    interval: Duration,
+    reduced_motion: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum SpinnerFrames {
+    /// The shimmering or blinking dot.
+    Dot,
+    Cycle(Vec<String>),
+}
+
+impl Default for SpinnerStyle {
+    fn default() -> Self {
+        Self {
+            frames: SpinnerFrames::Dot,
+            interval: DOT_BLINK,
+            reduced_motion: false,
+        }
+    }
+}
+
+impl SpinnerStyle {
+    pub(crate) fn from_config(settings: &SpinnerSettings) -> Self {
+        let (frames, default_interval) = if settings.frames.is_empty() {
+            frame_set(settings.frame_set)
+        } else {
+            (
+                SpinnerFrames::Cycle(settings.frames.clone()),
+                Duration::from_millis(100),
+            )
+        };
+        let interval = settings
+            .interval_ms
+            .and_then(|ms| u64::try_from(ms).ok())
+            .filter(|ms| *ms > 0)
+            .map_or(default_interval, Duration::from_millis);
+        Self {
+            frames,
+            interval,
+            reduced_motion: settings.reduced_motion,
+        }
+    }
+
+    fn render(&self, elapsed: Duration) -> Span<'static> {
+        if self.reduced_motion {
+            return self.static_glyph();
+        }
+        match &self.frames {
+            SpinnerFrames::Dot => {
+                if supports_color::on_cached(supports_color::Stream::Stdout)
+                    .map(|level| level.has_16m)
+                    .unwrap_or(false)
+                {
+                    shimmer_spans("•")[0].clone()
+                } else {
+                    let blink_on =
+                        (elapsed.as_millis() / self.interval.as_millis()).is_multiple_of(2);
+                    if blink_on { "•".into() } else { "◦".dim() }
+                }
+            }
+            SpinnerFrames::Cycle(frames) => {
+                let step = elapsed.as_millis() / self.interval.as_millis();
+                let index = (step % frames.len() as u128) as usize;
+                frames[index].clone().into()
+            }
+        }
+    }
+
+    /// What is drawn when nothing may move: the first frame, dimmed.
+    fn static_glyph(&self) -> Span<'static> {
+        match &self.frames {
+            SpinnerFrames::Dot => "•".dim(),
+            SpinnerFrames::Cycle(frames) => frames[0].clone().dim(),
+        }
+    }
+}
+
+fn frame_set(set: SpinnerFrameSet) -> (SpinnerFrames, Duration) {
+    let cycle = |frames: &[&str], millis| {
+        (
+            SpinnerFrames::Cycle(frames.iter().map(|frame| (*frame).to_string()).collect()),
+            Duration::from_millis(millis),
+        )
+    };
+    match set {
+        SpinnerFrameSet::Dot => (SpinnerFrames::Dot, DOT_BLINK),
+        SpinnerFrameSet::Braille => cycle(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"], 80),
+        SpinnerFrameSet::Line => cycle(&["-", "\\", "|", "/"], 130),
+        SpinnerFrameSet::Circle => cycle(&["◐", "◓", "◑", "◒"], 120),
+        SpinnerFrameSet::Arc => cycle(&["◜", "◠", "◝", "◞", "◡", "◟"], 100),
+    }
+}
+
+/// The `style` frame for a task started at `start_time`, or the static
+/// glyph when animations are off.
+pub(crate) fn spinner(
+    style: &SpinnerStyle,
+    start_time: Option<Instant>,
+    animations_enabled: bool,
+) -> Span<'static> {
+    if !animations_enabled {
+        return style.static_glyph();
+    }
+    let elapsed = start_time.map(|st| st.elapsed()).unwrap_or_default();
+    style.render(elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn text(span: Span<'static>) -> String {
+        span.content.into_owned()
+    }
+
+    #[test]
+    fn frame_sets_cycle_at_their_interval() {
+        let style = SpinnerStyle::from_config(&SpinnerSettings {
+            frame_set: SpinnerFrameSet::Line,
+            interval_ms: Some(100),
+            ..SpinnerSettings::default()
+        });
+        let frames: Vec<String> = [0, 100, 250, 399, 400]
+            .into_iter()
+            .map(|ms| text(style.render(Duration::from_millis(ms))))
+            .collect();
+        assert_eq!(frames, vec!["-", "\\", "|", "/", "-"]);
+    }
+
+    #[test]
+    fn each_caller_draws_the_style_it_was_given() {
+        let line = SpinnerStyle::from_config(&SpinnerSettings {
+            frame_set: SpinnerFrameSet::Line,
+            ..SpinnerSettings::default()
+        });
+        assert_eq!(text(spinner(&line, None, true)), "-");
+        assert_eq!(text(spinner(&line, None, false)), "-");
+        assert_eq!(text(spinner(&SpinnerStyle::default(), None, false)), "•");
+    }
+
+    #[test]
+    fn custom_frames_replace_the_frame_set_and_hold_still_for_reduced_motion() {
+        let settings = SpinnerSettings {
+            frame_set: SpinnerFrameSet::Braille,
+            frames: vec!["a".to_string(), "b".to_string()],
+            interval_ms: Some(0),
+            reduced_motion: false,
+        };
+        let style = SpinnerStyle::from_config(&settings);
+        assert_eq!(style.interval, Duration::from_millis(100));
+        assert_eq!(text(style.render(Duration::from_millis(150))), "b");
+
+        let still = SpinnerStyle::from_config(&SpinnerSettings {
+            reduced_motion: true,
+            ..settings
+        });
+        assert_eq!(text(still.render(Duration::from_millis(150))), "a");
+        assert_eq!(text(still.render(Duration::from_millis(250))), "a");
+    }
+}
@@ -2,6 +2,7 @@ use crate::diff_render::create_diff_summary;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::OutputLinesParams;
+use crate::exec_cell::SpinnerStyle;
 use crate::exec_cell::TOOL_CALL_MAX_LINES;
 use crate::exec_cell::output_lines;
 use crate::exec_cell::spinner;
@@ -852,6 +853,7 @@ pub(crate) struct McpToolCallCell {
     duration: Option<Duration>,
     result: Option<Result<mcp_types::CallToolResult, String>>,
     animations_enabled: bool,
+    spinner_style: SpinnerStyle,
 }
 
 impl McpToolCallCell {
@@ -867,9 +869,16 @@ impl McpToolCallCell {
             duration: None,
             result: None,
             animations_enabled,
+            spinner_style: SpinnerStyle::default(),
         }
     }
 
+    /// Draw the running spinner with `style` instead of the default dot.
+    pub(crate) fn with_spinner_style(mut self, style: SpinnerStyle) -> Self {
+        self.spinner_style = style;
+        self
+    }
+
     pub(crate) fn call_id(&self) -> &str {
         &self.call_id
     }
@@ -928,7 +937,11 @@ impl HistoryCell for McpToolCallCell {
         let bullet = match status {
             Some(true) => "•".green().bold(),
             Some(false) => "•".red().bold(),
-            None => spinner(Some(self.start_time), self.animations_enabled),
+            None => spinner(
+                &self.spinner_style,
+                Some(self.start_time),
+                self.animations_enabled,
+            ),
         };
         let header_text = if status.is_some() {
             "Called"
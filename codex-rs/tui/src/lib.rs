@@ -62,8 +62,10 @@ pub mod onboarding;
 mod oss_selection;
 mod pager_overlay;
 pub mod public_widgets;
+mod release_check;
 mod render;
 mod resume_picker;
+mod resume_prompt;
 mod selection_list;
 mod session_log;
 mod shimmer;
@@ -97,6 +99,22 @@ pub use cli::Cli;
 pub use markdown_render::render_markdown_text;
 pub use public_widgets::composer_input::ComposerAction;
 pub use public_widgets::composer_input::ComposerInput;
+#[cfg(feature = "statusline-widget")]
+pub use public_widgets::status_bar::StatusBar;
+pub use release_check::upgrade_instructions;
+pub use status::StatusWatchOptions;
+pub use status::run_status_watch;
+pub use statusline::StatusLineSnapshotBuilder;
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub use statusline::bench as statusline_bench;
+pub use statusline::run_statusline_doctor;
+pub use statusline::skins::SkinSnapshot;
+pub use statusline::skins::StatusLineSkin;
+pub use statusline::skins::register_skin;
+pub use statusline::skins::skin_names as statusline_skin_names;
+pub use statusline::write_session_timeline;
+pub use statusline::write_statusline_preview;
 use std::io::Write as _;
 
 // (tests access modules directly within the crate)
@@ -352,8 +370,13 @@ async fn run_ratatui_app(
     // line, but do not swallow the default/color-eyre panic handler.
     // Chain to the previous hook so users still get a rich panic report
     // (including backtraces) after we restore the terminal.
+    // Status line skin panics are caught and logged by the status line
+    // itself, which keeps running on the default skin; report nothing here.
     let prev_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
+        if statusline::state::in_guarded_render() {
+            return;
+        }
         tracing::error!("panic: {info}");
         prev_hook(info);
     }));
@@ -431,7 +454,8 @@ async fn run_ratatui_app(
         initial_config
     };
 
-    // Determine resume behavior: explicit id, then resume last, then picker.
+    // Determine resume behavior: explicit id, then resume last, then picker,
+    // then the last session of this workspace.
     let resume_selection = if let Some(id_str) = cli.resume_session_id.as_deref() {
         match find_conversation_path_by_id_str(&config.codex_home, id_str).await? {
             Some(path) => resume_picker::ResumeSelection::Resume(path),
@@ -472,6 +496,11 @@ async fn run_ratatui_app(
                 .unwrap_or(resume_picker::ResumeSelection::StartFresh),
             Err(_) => resume_picker::ResumeSelection::StartFresh,
         }
+    } else if cli.resume_workspace_last {
+        match resume_prompt::find_last_workspace_session(&config).await {
+            Some((_, path)) => resume_picker::ResumeSelection::Resume(path),
+            None => resume_picker::ResumeSelection::StartFresh,
+        }
     } else if cli.resume_picker {
         match resume_picker::run_resume_picker(
             &mut tui,
@@ -492,11 +521,19 @@ async fn run_ratatui_app(
             }
             other => other,
         }
-    } else {
+    } else if cli.prompt.as_ref().is_some_and(|prompt| !prompt.is_empty()) || !cli.images.is_empty()
+    {
         resume_picker::ResumeSelection::StartFresh
+    } else {
+        resume_prompt::run_resume_prompt_if_needed(&mut tui, &config).await?
     };
 
-    let Cli { prompt, images, .. } = cli;
+    let Cli {
+        prompt,
+        images,
+        build_sha,
+        ..
+    } = cli;
 
     let app_result = App::run(
         &mut tui,
@@ -508,6 +545,7 @@ async fn run_ratatui_app(
         resume_selection,
         feedback,
         should_show_trust_screen, // Proxy to: is it a first run in this directory?
+        build_sha,
     )
     .await;
 
@@ -1 +1,3 @@
 pub mod composer_input;
+#[cfg(feature = "statusline-widget")]
+pub mod status_bar;
@@ -0,0 +1,289 @@
+//! Public wrapper around the Codex status line renderer, for other TUIs built
+//! on codex-core that want the same powerline bar. Enabled with the
+//! `statusline-widget` feature.
+//!
+//! A [`StatusBar`] is built from plain data rather than a running session, so
+//! it needs no frame scheduling or app events: set what you know, then draw
+//! it as a ratatui widget (the status line) or ask for either row as a
+//! [`Line`].
+
+use std::path::Path;
+use std::time::Instant;
+
+use codex_core::config::types::StatusLineColorMode;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxPolicy;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
+
+use crate::status::format_directory_display;
+use crate::statusline::RunTimerSnapshot;
+use crate::statusline::StatusLineContextSnapshot;
+use crate::statusline::StatusLineGitSnapshot;
+use crate::statusline::StatusLineModelSnapshot;
+use crate::statusline::StatusLineProviderSnapshot;
+use crate::statusline::StatusLineRenderer;
+use crate::statusline::StatusLineRunState;
+use crate::statusline::StatusLineSnapshot;
+use crate::statusline::color_depth::ColorDepth;
+use crate::statusline::skins;
+use crate::statusline::state::policy_snapshot;
+
+/// The Codex status line and run pill, drawn from plain data.
+#[derive(Debug)]
+pub struct StatusBar {
+    snapshot: StatusLineSnapshot,
+    renderer: Box<dyn StatusLineRenderer>,
+    color_depth: ColorDepth,
+}
+
+impl StatusBar {
+    /// An empty bar drawn with the `powerline` skin in the colors the
+    /// terminal reports.
+    pub fn new() -> Self {
+        Self {
+            snapshot: StatusLineSnapshot::default(),
+            renderer: Box::new(skins::CustomStatusLineRenderer),
+            color_depth: ColorDepth::from_config(StatusLineColorMode::Auto),
+        }
+    }
+
+    /// Names of the built-in skins accepted by [`StatusBar::skin`].
+    pub fn skins() -> impl Iterator<Item = &'static str> {
+        skins::SKINS.iter().map(|(name, _)| *name)
+    }
+
+    /// Draw with the skin called `name`: a built-in one or one added with
+    /// [`crate::register_skin`].
+    pub fn skin(mut self, name: &str) -> anyhow::Result<Self> {
+        let Some(renderer) = skins::for_name(name) else {
+            anyhow::bail!(
+                "unknown skin `{name}`; expected one of: {}",
+                skins::skin_names().join(", ")
+            );
+        };
+        self.renderer = renderer;
+        Ok(self)
+    }
+
+    /// Colors the bar may use, as in `tui.statusline.color`.
+    pub fn color(mut self, mode: StatusLineColorMode) -> Self {
+        self.color_depth = ColorDepth::from_config(mode);
+        self
+    }
+
+    /// Working directory shown in the path segment, with the home directory
+    /// abbreviated to `~`.
+    pub fn cwd(mut self, cwd: &Path) -> Self {
+        let display = format_directory_display(cwd, None);
+        let basename = cwd
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .filter(|name| !name.is_empty());
+        self.snapshot.cwd_display = Some(display.clone());
+        self.snapshot.cwd_basename = basename.clone();
+        self.snapshot.cwd_fallback = basename.or(Some(display));
+        self
+    }
+
+    /// Model name, with an optional detail such as the reasoning effort.
+    pub fn model(mut self, label: impl Into<String>, detail: Option<String>) -> Self {
+        self.snapshot.model = Some(StatusLineModelSnapshot {
+            label: label.into(),
+            detail,
+            icon: None,
+            color: None,
+        });
+        self
+    }
+
+    /// Model provider and the config profile that selected it, if any.
+    pub fn provider(mut self, name: impl Into<String>, profile: Option<String>) -> Self {
+        self.snapshot.provider = Some(StatusLineProviderSnapshot {
+            name: name.into(),
+            profile,
+        });
+        self
+    }
+
+    /// Current branch and whether the worktree has uncommitted changes.
+    pub fn git_branch(mut self, branch: impl Into<String>, dirty: bool) -> Self {
+        let git = self
+            .snapshot
+            .git
+            .get_or_insert_with(StatusLineGitSnapshot::default);
+        git.branch = Some(branch.into());
+        git.dirty = dirty;
+        self
+    }
+
+    /// Commits ahead of and behind the upstream branch.
+    pub fn git_ahead_behind(mut self, ahead: i64, behind: i64) -> Self {
+        let git = self
+            .snapshot
+            .git
+            .get_or_insert_with(StatusLineGitSnapshot::default);
+        git.ahead = Some(ahead);
+        git.behind = Some(behind);
+        self
+    }
+
+    /// Tokens in the context window out of `window`, drawn as the context
+    /// bar.
+    pub fn context(mut self, tokens_in_context: i64, window: i64) -> Self {
+        let percent_remaining = if window > 0 {
+            let remaining = window.saturating_sub(tokens_in_context).clamp(0, window);
+            (remaining.saturating_mul(100) / window) as u8
+        } else {
+            0
+        };
+        self.snapshot.context = Some(StatusLineContextSnapshot {
+            percent_remaining,
+            tokens_in_context,
+            window,
+            low_since: None,
+            suggest_compact: false,
+        });
+        self
+    }
+
+    /// Sandbox and approval policy, colored by how much the agent may do
+    /// without asking.
+    pub fn policy(mut self, approval: AskForApproval, sandbox: &SandboxPolicy) -> Self {
+        self.snapshot.policy = Some(policy_snapshot(approval, sandbox));
+        self
+    }
+
+    /// Hostname shown in the environment segments.
+    pub fn hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.snapshot.environment.hostname = Some(hostname.into());
+        self
+    }
+
+    /// A task labelled `label` running since `started_at`; fills the run
+    /// pill with a spinner and timer.
+    pub fn running(mut self, label: impl Into<String>, started_at: Instant) -> Self {
+        let run_state = self.run_state();
+        run_state.label = label.into();
+        run_state.spinner_started_at = Some(started_at);
+        run_state.timer = Some(RunTimerSnapshot {
+            elapsed_running: Default::default(),
+            last_resume_at: Some(started_at),
+            is_paused: false,
+        });
+        run_state.status_changed_at = started_at;
+        self
+    }
+
+    /// Messages queued behind the running task, previewed in the run pill.
+    pub fn queued_messages(mut self, messages: Vec<String>) -> Self {
+        self.run_state().queued_messages = messages;
+        self
+    }
+
+    /// Approval requests waiting on the user.
+    pub fn pending_approvals(mut self, count: i64) -> Self {
+        self.run_state().pending_approvals = count;
+        self
+    }
+
+    /// The status line at `width` columns.
+    pub fn status_line(&self, width: u16) -> Line<'static> {
+        self.color_depth
+            .apply(self.renderer.render(&self.snapshot, width, Instant::now()))
+    }
+
+    /// The run pill at `width` columns; blank while nothing is running.
+    pub fn run_pill(&self, width: u16) -> Line<'static> {
+        self.color_depth.apply(
+            self.renderer
+                .render_run_pill(&self.snapshot, width, Instant::now()),
+        )
+    }
+
+    fn run_state(&mut self) -> &mut StatusLineRunState {
+        self.snapshot
+            .run_state
+            .get_or_insert_with(StatusLineRunState::default)
+    }
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws the status line on the first row of the area.
+impl WidgetRef for StatusBar {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        self.status_line(area.width).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::line_display_width;
+    use pretty_assertions::assert_eq;
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn renders_plain_data_with_any_skin() {
+        for skin in StatusBar::skins() {
+            let bar = StatusBar::new()
+                .skin(skin)
+                .expect("built-in skin")
+                .color(StatusLineColorMode::None)
+                .cwd(Path::new("/tmp/project"))
+                .model("gpt-5-codex", Some("high".to_string()))
+                .git_branch("main", true)
+                .context(54_000, 272_000)
+                .policy(AskForApproval::OnRequest, &SandboxPolicy::ReadOnly)
+                .running("Running tests", Instant::now());
+
+            let line = bar.status_line(120);
+            let pill = bar.run_pill(120);
+            assert!(line_display_width(&line) <= 120, "{skin}");
+            let rendered = format!("{}\n{}", text(&line), text(&pill));
+            assert!(rendered.contains("gpt-5-codex"), "{skin}: {rendered}");
+            assert!(rendered.contains("Running tests"), "{skin}: {rendered}");
+        }
+    }
+
+    #[test]
+    fn unknown_skin_lists_the_builtins() {
+        let err = StatusBar::new().skin("neon").expect_err("unknown skin");
+        assert_eq!(
+            err.to_string(),
+            "unknown skin `neon`; expected one of: powerline, minimal-plain, \
+             compact-single-capsule, verbose-two-line"
+        );
+    }
+
+    #[test]
+    fn widget_draws_the_status_line() {
+        let bar = StatusBar::new()
+            .color(StatusLineColorMode::None)
+            .model("gpt-5-codex", None);
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+        bar.render_ref(area, &mut buf);
+        let row: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect();
+        assert!(row.contains("gpt-5-codex"), "{row}");
+    }
+}
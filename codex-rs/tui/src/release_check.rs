@@ -0,0 +1,276 @@
+//! Opt-in comparison of this build with the fork's latest GitHub release
+//! (`check_for_release_on_startup`). The latest release is cached in
+//! `CODEX_HOME/release-check.json` and refreshed at most once a day in the
+//! background, so startup never waits on the network; a refresh shows up
+//! from the next run on.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use codex_core::config::Config;
+use codex_core::default_client::create_client;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::update_action::UpdateAction;
+use crate::version::CODEX_CLI_VERSION;
+use crate::version::is_newer;
+use crate::version::known_sha;
+
+const CACHE_FILE: &str = "release-check.json";
+const RELEASES_URL: &str = "https://api.github.com/repos/byebye-code/codex/releases/latest";
+const COMMITS_URL: &str = "https://api.github.com/repos/byebye-code/codex/commits";
+const CHECK_INTERVAL_HOURS: i64 = 24;
+
+/// The fork's latest release.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct LatestRelease {
+    /// Version from the tag, without its `v` or `rust-v` prefix.
+    pub version: String,
+    /// Commit the tag points at, when GitHub told us.
+    pub commit: Option<String>,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CachedRelease {
+    release: LatestRelease,
+    last_checked_at: DateTime<Utc>,
+}
+
+/// The cached latest release when it is newer than the build `sha`. Called
+/// once at startup; refreshes the cache in the background once it is a day
+/// old.
+pub(crate) fn newer_release(config: &Config, sha: Option<&str>) -> Option<LatestRelease> {
+    if !config.check_for_release_on_startup {
+        return None;
+    }
+    let file = cache_filepath(&config.codex_home);
+    let cached = read_cache(&file);
+    let due = cached.as_ref().is_none_or(|cached| {
+        cached.last_checked_at < Utc::now() - Duration::hours(CHECK_INTERVAL_HOURS)
+    });
+    if due {
+        tokio::spawn(async move {
+            refresh_cache(&file)
+                .await
+                .inspect_err(|e| tracing::debug!("release check failed: {e}"))
+        });
+    }
+    cached.and_then(|cached| newer_than_build(cached.release, sha))
+}
+
+/// The cached latest release when it is newer than the build `sha`; never
+/// asks GitHub.
+pub(crate) fn cached_newer_release(config: &Config, sha: Option<&str>) -> Option<LatestRelease> {
+    if !config.check_for_release_on_startup {
+        return None;
+    }
+    read_cache(&cache_filepath(&config.codex_home))
+        .and_then(|cached| newer_than_build(cached.release, sha))
+}
+
+/// What `codex upgrade` prints for the build `sha`: the newer release, if the
+/// last check found one, and the command that installs it.
+pub fn upgrade_instructions(codex_home: &Path, sha: Option<&str>) -> Vec<String> {
+    let action = UpdateAction::for_environment();
+    let mut lines = Vec::new();
+    match read_cache(&cache_filepath(codex_home))
+        .and_then(|cached| newer_than_build(cached.release, sha))
+    {
+        Some(release) => {
+            lines.push(format!(
+                "Codex {} is available (this is {CODEX_CLI_VERSION}).",
+                release.version
+            ));
+            lines.push(format!("Release notes: {}", release.url));
+        }
+        None => lines.push(format!(
+            "No newer release known; this is Codex {CODEX_CLI_VERSION}."
+        )),
+    }
+    lines.push(format!(
+        "To install the latest release, run: {}",
+        action.command_str()
+    ));
+    lines
+}
+
+fn newer_than_build(release: LatestRelease, sha: Option<&str>) -> Option<LatestRelease> {
+    is_newer_release(&release, CODEX_CLI_VERSION, known_sha(sha)).then_some(release)
+}
+
+/// A build of the release commit is up to date whatever its version says;
+/// otherwise the versions decide.
+fn is_newer_release(release: &LatestRelease, version: &str, sha: Option<&str>) -> bool {
+    if let (Some(commit), Some(sha)) = (release.commit.as_deref(), sha)
+        && commit.eq_ignore_ascii_case(sha)
+    {
+        return false;
+    }
+    is_newer(&release.version, version).unwrap_or(false)
+}
+
+fn cache_filepath(codex_home: &Path) -> PathBuf {
+    codex_home.join(CACHE_FILE)
+}
+
+fn read_cache(file: &Path) -> Option<CachedRelease> {
+    let contents = std::fs::read_to_string(file).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+async fn refresh_cache(file: &Path) -> anyhow::Result<()> {
+    let cached = CachedRelease {
+        release: fetch_latest_release().await?,
+        last_checked_at: Utc::now(),
+    };
+    let json_line = format!("{}\n", serde_json::to_string(&cached)?);
+    if let Some(parent) = file.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(file, json_line).await?;
+    Ok(())
+}
+
+async fn fetch_latest_release() -> anyhow::Result<LatestRelease> {
+    #[derive(Deserialize)]
+    struct GitHubRelease {
+        tag_name: String,
+        html_url: String,
+        #[serde(default)]
+        target_commitish: String,
+    }
+
+    let client = create_client();
+    let release = client
+        .get(RELEASES_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<GitHubRelease>()
+        .await?;
+
+    // `target_commitish` is usually a branch name; then ask which commit the
+    // tag is. Without it the versions alone decide.
+    let commit = if is_commit_sha(&release.target_commitish) {
+        Some(release.target_commitish.clone())
+    } else {
+        match client
+            .get(format!("{COMMITS_URL}/{}", release.tag_name))
+            .header("Accept", "application/vnd.github.sha")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+        {
+            Ok(response) => response
+                .text()
+                .await
+                .ok()
+                .map(|sha| sha.trim().to_string())
+                .filter(|sha| is_commit_sha(sha)),
+            Err(e) => {
+                tracing::debug!("release commit lookup failed: {e}");
+                None
+            }
+        }
+    };
+
+    Ok(LatestRelease {
+        version: version_from_tag(&release.tag_name).to_string(),
+        commit,
+        url: release.html_url,
+    })
+}
+
+fn version_from_tag(tag: &str) -> &str {
+    let tag = tag.trim();
+    let tag = tag.strip_prefix("rust-").unwrap_or(tag);
+    tag.strip_prefix('v').unwrap_or(tag)
+}
+
+fn is_commit_sha(value: &str) -> bool {
+    value.len() == 40 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const RELEASE_SHA: &str = "1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b";
+
+    fn release(version: &str) -> LatestRelease {
+        LatestRelease {
+            version: version.to_string(),
+            commit: Some(RELEASE_SHA.to_string()),
+            url: "https://github.com/byebye-code/codex/releases/tag/v2025.12.3".to_string(),
+        }
+    }
+
+    #[test]
+    fn newer_versions_count_unless_this_is_the_release_commit() {
+        assert!(is_newer_release(
+            &release("2025.12.3"),
+            "2025.12.2",
+            Some("ffffffff")
+        ));
+        assert!(is_newer_release(&release("2025.12.3"), "2025.12.2", None));
+        assert!(!is_newer_release(
+            &release("2025.12.3"),
+            "2025.12.2",
+            Some(&RELEASE_SHA.to_uppercase())
+        ));
+        assert!(!is_newer_release(
+            &release("2025.12.3"),
+            "2025.12.3",
+            Some("ffffffff")
+        ));
+        assert!(!is_newer_release(
+            &release("2025.12.3-rc.1"),
+            "2025.12.2",
+            None
+        ));
+    }
+
+    #[test]
+    fn tags_lose_their_prefix() {
+        assert_eq!(version_from_tag("v2025.12.3"), "2025.12.3");
+        assert_eq!(version_from_tag("rust-v0.58.0"), "0.58.0");
+        assert_eq!(version_from_tag("2025.12.3"), "2025.12.3");
+        assert!(is_commit_sha(RELEASE_SHA));
+        assert!(!is_commit_sha("main"));
+    }
+
+    #[test]
+    fn upgrade_instructions_name_the_cached_release() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cached = CachedRelease {
+            release: LatestRelease {
+                commit: None,
+                ..release("9999.1.1")
+            },
+            last_checked_at: Utc::now(),
+        };
+        std::fs::write(
+            cache_filepath(dir.path()),
+            serde_json::to_string(&cached).expect("serialize"),
+        )
+        .expect("write cache");
+
+        let lines = upgrade_instructions(dir.path(), None);
+        assert_eq!(
+            lines[0],
+            format!("Codex 9999.1.1 is available (this is {CODEX_CLI_VERSION}).")
+        );
+        assert!(lines[2].starts_with("To install the latest release, run: "));
+
+        let empty = tempfile::tempdir().expect("tempdir");
+        let lines = upgrade_instructions(empty.path(), None);
+        assert_eq!(lines.len(), 2);
+    }
+}
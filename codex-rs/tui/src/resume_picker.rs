@@ -898,7 +898,7 @@ fn render_empty_state_line(state: &PickerState) -> Line<'static> {
     vec!["No sessions yet".italic().dim()].into()
 }
 
-fn human_time_ago(ts: DateTime<Utc>) -> String {
+pub(crate) fn human_time_ago(ts: DateTime<Utc>) -> String {
     let now = Utc::now();
     let delta = now - ts;
     let secs = delta.num_seconds();
@@ -0,0 +1,297 @@
+use std::path::PathBuf;
+
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::render::renderable::RenderableExt as _;
+use crate::resume_picker::ResumeSelection;
+use crate::resume_picker::human_time_ago;
+use crate::selection_list::selection_option_row;
+use crate::tui::FrameRequester;
+use crate::tui::Tui;
+use crate::tui::TuiEvent;
+use codex_core::config::Config;
+use codex_core::find_conversation_path_by_id_str;
+use codex_core::workspace_state::WorkspaceLastSession;
+use codex_core::workspace_state::load_workspace_state;
+use codex_core::workspace_state::persist_resume_prompt;
+use color_eyre::Result;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::Widget;
+use ratatui::style::Stylize as _;
+use ratatui::text::Line;
+use ratatui::widgets::Clear;
+use ratatui::widgets::WidgetRef;
+use tokio_stream::StreamExt;
+
+/// Last session recorded for the current workspace, if its rollout file can
+/// still be found.
+pub(crate) async fn find_last_workspace_session(
+    config: &Config,
+) -> Option<(WorkspaceLastSession, PathBuf)> {
+    let state = load_workspace_state(&config.codex_home, &config.cwd).ok()?;
+    let last_session = state.last_session?;
+    let id = last_session.id.to_string();
+    match find_conversation_path_by_id_str(&config.codex_home, &id).await {
+        Ok(Some(path)) => Some((last_session, path)),
+        Ok(None) => None,
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to look up last workspace session {id}");
+            None
+        }
+    }
+}
+
+/// Ask whether to continue the last session that ended in this workspace.
+/// Starts fresh without asking when there is nothing to resume or the user
+/// turned the prompt off for this workspace.
+pub(crate) async fn run_resume_prompt_if_needed(
+    tui: &mut Tui,
+    config: &Config,
+) -> Result<ResumeSelection> {
+    let prompt_enabled = load_workspace_state(&config.codex_home, &config.cwd)
+        .map(|state| state.resume_prompt != Some(false))
+        .unwrap_or(false);
+    if !prompt_enabled {
+        return Ok(ResumeSelection::StartFresh);
+    }
+    let Some((last_session, path)) = find_last_workspace_session(config).await else {
+        return Ok(ResumeSelection::StartFresh);
+    };
+
+    let mut screen = ResumePromptScreen::new(
+        tui.frame_requester(),
+        last_session.id.to_string(),
+        human_time_ago(last_session.ended_at),
+    );
+    tui.draw(u16::MAX, |frame| {
+        frame.render_widget_ref(&screen, frame.area());
+    })?;
+
+    let events = tui.event_stream();
+    tokio::pin!(events);
+
+    while !screen.is_done() {
+        if let Some(event) = events.next().await {
+            match event {
+                TuiEvent::Key(key_event) => screen.handle_key(key_event),
+                TuiEvent::Paste(_) => {}
+                TuiEvent::Draw => {
+                    tui.draw(u16::MAX, |frame| {
+                        frame.render_widget_ref(&screen, frame.area());
+                    })?;
+                }
+            }
+        } else {
+            break;
+        }
+    }
+    tui.terminal.clear()?;
+
+    match screen.selection() {
+        Some(ResumePromptSelection::Resume) => Ok(ResumeSelection::Resume(path)),
+        Some(ResumePromptSelection::StartFresh) | None => Ok(ResumeSelection::StartFresh),
+        Some(ResumePromptSelection::DontAsk) => {
+            if let Err(err) = persist_resume_prompt(&config.codex_home, &config.cwd, false).await {
+                tracing::error!("Failed to persist resume prompt dismissal: {err}");
+            }
+            Ok(ResumeSelection::StartFresh)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ResumePromptSelection {
+    Resume,
+    StartFresh,
+    DontAsk,
+}
+
+struct ResumePromptScreen {
+    request_frame: FrameRequester,
+    session_id: String,
+    ended_ago: String,
+    highlighted: ResumePromptSelection,
+    selection: Option<ResumePromptSelection>,
+}
+
+impl ResumePromptScreen {
+    fn new(request_frame: FrameRequester, session_id: String, ended_ago: String) -> Self {
+        Self {
+            request_frame,
+            session_id,
+            ended_ago,
+            highlighted: ResumePromptSelection::Resume,
+            selection: None,
+        }
+    }
+
+    fn handle_key(&mut self, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release {
+            return;
+        }
+        if key_event.modifiers.contains(KeyModifiers::CONTROL)
+            && matches!(key_event.code, KeyCode::Char('c') | KeyCode::Char('d'))
+        {
+            self.select(ResumePromptSelection::StartFresh);
+            return;
+        }
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => self.set_highlight(self.highlighted.prev()),
+            KeyCode::Down | KeyCode::Char('j') => self.set_highlight(self.highlighted.next()),
+            KeyCode::Char('1') => self.select(ResumePromptSelection::Resume),
+            KeyCode::Char('2') => self.select(ResumePromptSelection::StartFresh),
+            KeyCode::Char('3') => self.select(ResumePromptSelection::DontAsk),
+            KeyCode::Enter => self.select(self.highlighted),
+            KeyCode::Esc => self.select(ResumePromptSelection::StartFresh),
+            _ => {}
+        }
+    }
+
+    fn set_highlight(&mut self, highlight: ResumePromptSelection) {
+        if self.highlighted != highlight {
+            self.highlighted = highlight;
+            self.request_frame.schedule_frame();
+        }
+    }
+
+    fn select(&mut self, selection: ResumePromptSelection) {
+        self.highlighted = selection;
+        self.selection = Some(selection);
+        self.request_frame.schedule_frame();
+    }
+
+    fn is_done(&self) -> bool {
+        self.selection.is_some()
+    }
+
+    fn selection(&self) -> Option<ResumePromptSelection> {
+        self.selection
+    }
+}
+
+impl ResumePromptSelection {
+    fn next(self) -> Self {
+        match self {
+            ResumePromptSelection::Resume => ResumePromptSelection::StartFresh,
+            ResumePromptSelection::StartFresh => ResumePromptSelection::DontAsk,
+            ResumePromptSelection::DontAsk => ResumePromptSelection::Resume,
+        }
+    }
+
+    fn prev(self) -> Self {
+        match self {
+            ResumePromptSelection::Resume => ResumePromptSelection::DontAsk,
+            ResumePromptSelection::StartFresh => ResumePromptSelection::Resume,
+            ResumePromptSelection::DontAsk => ResumePromptSelection::StartFresh,
+        }
+    }
+}
+
+impl WidgetRef for &ResumePromptScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let mut column = ColumnRenderable::new();
+
+        column.push("");
+        column.push(
+            Line::from("Continue where you left off in this directory?".bold())
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.push("");
+        column.push(
+            Line::from(vec![
+                "Last session ".dim(),
+                self.session_id.clone().into(),
+                format!(" ended {}", self.ended_ago).dim(),
+            ])
+            .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.push("");
+        column.push(selection_option_row(
+            0,
+            "Resume last session".to_string(),
+            self.highlighted == ResumePromptSelection::Resume,
+        ));
+        column.push(selection_option_row(
+            1,
+            "Start a new session".to_string(),
+            self.highlighted == ResumePromptSelection::StartFresh,
+        ));
+        column.push(selection_option_row(
+            2,
+            "Start a new session and don't ask again here".to_string(),
+            self.highlighted == ResumePromptSelection::DontAsk,
+        ));
+        column.push("");
+        column.push(
+            Line::from(vec![
+                "Press ".dim(),
+                key_hint::plain(KeyCode::Enter).into(),
+                " to continue".dim(),
+            ])
+            .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_backend::VT100Backend;
+    use crate::tui::FrameRequester;
+    use crossterm::event::KeyCode;
+    use crossterm::event::KeyEvent;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+    use ratatui::Terminal;
+
+    fn new_prompt() -> ResumePromptScreen {
+        ResumePromptScreen::new(
+            FrameRequester::test_dummy(),
+            "019a0000-0000-7000-8000-000000000000".into(),
+            "2 hours ago".into(),
+        )
+    }
+
+    #[test]
+    fn resume_prompt_snapshot() {
+        let screen = new_prompt();
+        let mut terminal = Terminal::new(VT100Backend::new(80, 12)).expect("terminal");
+        terminal
+            .draw(|frame| frame.render_widget_ref(&screen, frame.area()))
+            .expect("render resume prompt");
+        insta::assert_snapshot!("resume_prompt_modal", terminal.backend());
+    }
+
+    #[test]
+    fn resume_prompt_enter_resumes_by_default() {
+        let mut screen = new_prompt();
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(screen.is_done());
+        assert_eq!(screen.selection(), Some(ResumePromptSelection::Resume));
+    }
+
+    #[test]
+    fn resume_prompt_escape_starts_fresh() {
+        let mut screen = new_prompt();
+        screen.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(screen.selection(), Some(ResumePromptSelection::StartFresh));
+    }
+
+    #[test]
+    fn resume_prompt_dont_ask_selects_dismissal() {
+        let mut screen = new_prompt();
+        screen.handle_key(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+        assert_eq!(screen.highlighted, ResumePromptSelection::DontAsk);
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(screen.selection(), Some(ResumePromptSelection::DontAsk));
+    }
+}
@@ -24,6 +24,8 @@ pub enum SlashCommand {
     Diff,
     Mention,
     Status,
+    Statusline,
+    RefreshStatus,
     Mcp,
     Logout,
     Quit,
@@ -49,6 +51,8 @@ impl SlashCommand {
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
+            SlashCommand::Statusline => "choose the status line skin, or turn it on or off",
+            SlashCommand::RefreshStatus => "re-read git and environment info for the status line",
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Approvals => "choose what Codex can do without approval",
             SlashCommand::Mcp => "list configured MCP tools",
@@ -80,6 +84,8 @@ impl SlashCommand {
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
+            | SlashCommand::Statusline
+            | SlashCommand::RefreshStatus
             | SlashCommand::Mcp
             | SlashCommand::Feedback
             | SlashCommand::Quit
@@ -89,6 +95,12 @@ impl SlashCommand {
         }
     }
 
+    /// Whether `/name args` runs this command with `args` instead of sending
+    /// the line to the model.
+    pub fn takes_args(self) -> bool {
+        matches!(self, SlashCommand::Statusline)
+    }
+
     fn is_visible(self) -> bool {
         match self {
             SlashCommand::Rollout | SlashCommand::TestApproval => cfg!(debug_assertions),
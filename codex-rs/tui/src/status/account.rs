@@ -5,4 +5,9 @@ pub(crate) enum StatusAccountDisplay {
         plan: Option<String>,
     },
     ApiKey,
+    /// The model provider authenticates with the 88code login token.
+    Code88 {
+        /// Local date the saved token was obtained; `None` when signed out.
+        signed_in: Option<String>,
+    },
 }
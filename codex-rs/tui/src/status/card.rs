@@ -2,7 +2,10 @@ use crate::history_cell::CompositeHistoryCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::PlainHistoryCell;
 use crate::history_cell::with_border_with_inner_width;
-use crate::version::CODEX_CLI_VERSION;
+use crate::release_check::LatestRelease;
+use crate::release_check::cached_newer_release;
+use crate::statusline::StatusLine88CodeSnapshot;
+use crate::version::build_label;
 use chrono::DateTime;
 use chrono::Local;
 use codex_common::create_config_summary_entries;
@@ -17,15 +20,21 @@ use std::collections::BTreeSet;
 use std::path::PathBuf;
 
 use super::account::StatusAccountDisplay;
+use super::code88::StatusCode88Display;
+use super::code88::compose_code88_display;
 use super::format::FieldFormatter;
 use super::format::line_display_width;
 use super::format::push_label;
 use super::format::truncate_line_to_width;
+use super::helpers::StatusWorkspaceOverrides;
 use super::helpers::compose_account_display;
 use super::helpers::compose_agents_summary;
 use super::helpers::compose_model_display;
+use super::helpers::compose_workspace_overrides;
+use super::helpers::compose_workspace_usage;
 use super::helpers::format_directory_display;
-use super::helpers::format_tokens_compact;
+use super::rate_limit_history::RateLimitTrend;
+use super::rate_limit_history::RateLimitWindowHistory;
 use super::rate_limits::RateLimitSnapshotDisplay;
 use super::rate_limits::StatusRateLimitData;
 use super::rate_limits::StatusRateLimitRow;
@@ -33,6 +42,7 @@ use super::rate_limits::StatusRateLimitValue;
 use super::rate_limits::compose_rate_limit_data;
 use super::rate_limits::format_status_limit_summary;
 use super::rate_limits::render_status_limit_progress_bar;
+use super::token_format::TokenCountFormatter;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
 use codex_core::AuthManager;
@@ -52,56 +62,103 @@ pub(crate) struct StatusTokenUsageData {
     context_window: Option<StatusContextWindowData>,
 }
 
+impl StatusTokenUsageData {
+    fn from_usage(usage: &TokenUsage) -> Self {
+        Self {
+            total: usage.blended_total(),
+            input: usage.non_cached_input(),
+            output: usage.output_tokens,
+            context_window: None,
+        }
+    }
+
+    fn spans(&self, token_format: TokenCountFormatter) -> Vec<Span<'static>> {
+        let total_fmt = token_format.format(self.total);
+        let input_fmt = token_format.format(self.input);
+        let output_fmt = token_format.format(self.output);
+
+        vec![
+            Span::from(total_fmt),
+            Span::from(" total "),
+            Span::from(" (").dim(),
+            Span::from(input_fmt).dim(),
+            Span::from(" input").dim(),
+            Span::from(" + ").dim(),
+            Span::from(output_fmt).dim(),
+            Span::from(" output").dim(),
+            Span::from(")").dim(),
+        ]
+    }
+}
+
 #[derive(Debug)]
-struct StatusHistoryCell {
+pub(super) struct StatusHistoryCell {
     model_name: String,
     model_details: Vec<String>,
     directory: PathBuf,
     approval: String,
     sandbox: String,
     agents_summary: String,
+    overrides: Option<StatusWorkspaceOverrides>,
     account: Option<StatusAccountDisplay>,
     session_id: Option<String>,
     token_usage: StatusTokenUsageData,
+    token_format: TokenCountFormatter,
+    /// Session usage per model, shown once more than one model was used.
+    model_usage: Vec<(String, StatusTokenUsageData)>,
+    workspace_usage: Option<String>,
     rate_limits: StatusRateLimitData,
+    code88: Option<StatusCode88Display>,
+    /// Version and short commit of this build, see `version::build_label`.
+    build: String,
+    /// A newer release of the fork, from the opt-in release check.
+    update: Option<LatestRelease>,
 }
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn new_status_output(
     config: &Config,
+    build_sha: Option<&str>,
     auth_manager: &AuthManager,
     total_usage: &TokenUsage,
     context_usage: Option<&TokenUsage>,
     session_id: &Option<ConversationId>,
     rate_limits: Option<&RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
+    code88: Option<&StatusLine88CodeSnapshot>,
+    model_usage: &[(String, TokenUsage)],
     now: DateTime<Local>,
 ) -> CompositeHistoryCell {
     let command = PlainHistoryCell::new(vec!["/status".magenta().into()]);
     let card = StatusHistoryCell::new(
         config,
+        build_sha,
         auth_manager,
         total_usage,
         context_usage,
         session_id,
         rate_limits,
         plan_type,
+        code88,
         now,
-    );
+    )
+    .with_model_usage(model_usage);
 
     CompositeHistoryCell::new(vec![Box::new(command), Box::new(card)])
 }
 
 impl StatusHistoryCell {
     #[allow(clippy::too_many_arguments)]
-    fn new(
+    pub(super) fn new(
         config: &Config,
+        build_sha: Option<&str>,
         auth_manager: &AuthManager,
         total_usage: &TokenUsage,
         context_usage: Option<&TokenUsage>,
         session_id: &Option<ConversationId>,
         rate_limits: Option<&RateLimitSnapshotDisplay>,
         plan_type: Option<PlanType>,
+        code88: Option<&StatusLine88CodeSnapshot>,
         now: DateTime<Local>,
     ) -> Self {
         let config_entries = create_config_summary_entries(config);
@@ -117,7 +174,8 @@ impl StatusHistoryCell {
             SandboxPolicy::WorkspaceWrite { .. } => "workspace-write".to_string(),
         };
         let agents_summary = compose_agents_summary(config);
-        let account = compose_account_display(auth_manager, plan_type);
+        let overrides = compose_workspace_overrides(config);
+        let account = compose_account_display(config, auth_manager, plan_type);
         let session_id = session_id.as_ref().map(std::string::ToString::to_string);
         let context_window = config.model_context_window.and_then(|window| {
             context_usage.map(|usage| StatusContextWindowData {
@@ -128,12 +186,13 @@ impl StatusHistoryCell {
         });
 
         let token_usage = StatusTokenUsageData {
-            total: total_usage.blended_total(),
-            input: total_usage.non_cached_input(),
-            output: total_usage.output_tokens,
             context_window,
+            ..StatusTokenUsageData::from_usage(total_usage)
         };
+        let workspace_usage = compose_workspace_usage(config);
         let rate_limits = compose_rate_limit_data(rate_limits, now);
+        let code88 = compose_code88_display(config, code88, now);
+        let update = cached_newer_release(config, build_sha);
 
         Self {
             model_name,
@@ -142,36 +201,37 @@ impl StatusHistoryCell {
             approval,
             sandbox,
             agents_summary,
+            overrides,
             account,
             session_id,
             token_usage,
+            token_format: TokenCountFormatter::from_config(&config.tui_token_format),
+            model_usage: Vec::new(),
+            workspace_usage,
             rate_limits,
+            code88,
+            build: build_label(build_sha),
+            update,
         }
     }
 
-    fn token_usage_spans(&self) -> Vec<Span<'static>> {
-        let total_fmt = format_tokens_compact(self.token_usage.total);
-        let input_fmt = format_tokens_compact(self.token_usage.input);
-        let output_fmt = format_tokens_compact(self.token_usage.output);
-
-        vec![
-            Span::from(total_fmt),
-            Span::from(" total "),
-            Span::from(" (").dim(),
-            Span::from(input_fmt).dim(),
-            Span::from(" input").dim(),
-            Span::from(" + ").dim(),
-            Span::from(output_fmt).dim(),
-            Span::from(" output").dim(),
-            Span::from(")").dim(),
-        ]
+    /// Split the token usage by model. A single model adds nothing the
+    /// session total does not already show, so it is dropped.
+    fn with_model_usage(mut self, model_usage: &[(String, TokenUsage)]) -> Self {
+        if model_usage.len() > 1 {
+            self.model_usage = model_usage
+                .iter()
+                .map(|(model, usage)| (model.clone(), StatusTokenUsageData::from_usage(usage)))
+                .collect();
+        }
+        self
     }
 
     fn context_window_spans(&self) -> Option<Vec<Span<'static>>> {
         let context = self.token_usage.context_window.as_ref()?;
         let percent = context.percent_remaining;
-        let used_fmt = format_tokens_compact(context.tokens_in_context);
-        let window_fmt = format_tokens_compact(context.window);
+        let used_fmt = self.token_format.format(context.tokens_in_context);
+        let window_fmt = self.token_format.format(context.window);
 
         Some(vec![
             Span::from(format!("{percent}% left")),
@@ -226,13 +286,17 @@ impl StatusHistoryCell {
                 StatusRateLimitValue::Window {
                     percent_used,
                     resets_at,
+                    history,
                 } => {
                     let percent_remaining = (100.0 - percent_used).clamp(0.0, 100.0);
-                    let value_spans = vec![
+                    let mut value_spans = vec![
                         Span::from(render_status_limit_progress_bar(percent_remaining)),
                         Span::from(" "),
                         Span::from(format_status_limit_summary(percent_remaining)),
                     ];
+                    if let Some(history) = history.as_ref() {
+                        value_spans.extend(rate_limit_history_spans(history));
+                    }
                     let base_spans = formatter.full_spans(row.label.as_str(), value_spans);
                     let base_line = Line::from(base_spans.clone());
 
@@ -288,6 +352,22 @@ impl StatusHistoryCell {
     }
 }
 
+/// ` ▂▃▅▆ ↑`: recent usage, then whether the latest pace is above (red) or
+/// below (green) the session's usual pace.
+fn rate_limit_history_spans(history: &RateLimitWindowHistory) -> Vec<Span<'static>> {
+    let mut spans = vec![Span::from(" "), history.sparkline.clone().cyan()];
+    if let Some(trend) = history.trend {
+        let arrow = match trend {
+            RateLimitTrend::Faster => trend.arrow().red(),
+            RateLimitTrend::Steady => trend.arrow().dim(),
+            RateLimitTrend::Slower => trend.arrow().green(),
+        };
+        spans.push(Span::from(" "));
+        spans.push(arrow);
+    }
+    spans
+}
+
 impl HistoryCell for StatusHistoryCell {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
         let mut lines: Vec<Line<'static>> = Vec::new();
@@ -295,7 +375,7 @@ impl HistoryCell for StatusHistoryCell {
             Span::from(format!("{}>_ ", FieldFormatter::INDENT)).dim(),
             Span::from("OpenAI Codex").bold(),
             Span::from(" ").dim(),
-            Span::from(format!("(v{CODEX_CLI_VERSION})")).dim(),
+            Span::from(format!("(v{})", self.build)).dim(),
         ]));
         lines.push(Line::from(Vec::<Span<'static>>::new()));
 
@@ -314,6 +394,12 @@ impl HistoryCell for StatusHistoryCell {
             StatusAccountDisplay::ApiKey => {
                 "API key configured (run codex login to use ChatGPT)".to_string()
             }
+            StatusAccountDisplay::Code88 {
+                signed_in: Some(date),
+            } => format!("88code (signed in {date})"),
+            StatusAccountDisplay::Code88 { signed_in: None } => {
+                "88code, signed out (run codex login --provider 88code)".to_string()
+            }
         });
 
         let mut labels: Vec<String> =
@@ -323,17 +409,29 @@ impl HistoryCell for StatusHistoryCell {
                 .collect();
         let mut seen: BTreeSet<String> = labels.iter().cloned().collect();
 
+        if self.overrides.is_some() {
+            push_label(&mut labels, &mut seen, "Overrides");
+        }
         if account_value.is_some() {
             push_label(&mut labels, &mut seen, "Account");
         }
         if self.session_id.is_some() {
             push_label(&mut labels, &mut seen, "Session");
         }
+        if self.update.is_some() {
+            push_label(&mut labels, &mut seen, "Update");
+        }
         push_label(&mut labels, &mut seen, "Token usage");
         if self.token_usage.context_window.is_some() {
             push_label(&mut labels, &mut seen, "Context window");
         }
+        if self.workspace_usage.is_some() {
+            push_label(&mut labels, &mut seen, "Workspace usage");
+        }
         self.collect_rate_limit_labels(&mut seen, &mut labels);
+        if let Some(code88) = self.code88.as_ref() {
+            code88.collect_labels(&mut seen, &mut labels);
+        }
 
         let formatter = FieldFormatter::from_labels(labels.iter().map(String::as_str));
         let value_width = formatter.value_width(available_inner_width);
@@ -370,6 +468,12 @@ impl HistoryCell for StatusHistoryCell {
         lines.push(formatter.line("Sandbox", vec![Span::from(self.sandbox.clone())]));
         lines.push(formatter.line("Agents.md", vec![Span::from(self.agents_summary.clone())]));
 
+        if let Some(overrides) = self.overrides.as_ref() {
+            lines.push(formatter.line("Overrides", vec![Span::from(overrides.summary.clone())]));
+            let path = format_directory_display(&overrides.path, Some(value_width));
+            lines.push(formatter.continuation(vec![Span::from(format!("from {path}")).dim()]));
+        }
+
         if let Some(account_value) = account_value {
             lines.push(formatter.line("Account", vec![Span::from(account_value)]));
         }
@@ -378,18 +482,43 @@ impl HistoryCell for StatusHistoryCell {
             lines.push(formatter.line("Session", vec![Span::from(session.clone())]));
         }
 
+        if let Some(update) = self.update.as_ref() {
+            lines.push(formatter.line(
+                "Update",
+                vec![
+                    Span::from(format!("{} available", update.version)).cyan(),
+                    Span::from(" (run codex upgrade)").dim(),
+                ],
+            ));
+        }
+
         lines.push(Line::from(Vec::<Span<'static>>::new()));
         // Hide token usage only for ChatGPT subscribers
         if !matches!(self.account, Some(StatusAccountDisplay::ChatGpt { .. })) {
-            lines.push(formatter.line("Token usage", self.token_usage_spans()));
+            lines.push(formatter.line("Token usage", self.token_usage.spans(self.token_format)));
+            for (model, usage) in &self.model_usage {
+                let mut spans = vec![Span::from(format!("{model}: ")).dim()];
+                spans.extend(usage.spans(self.token_format));
+                lines.push(formatter.continuation(spans));
+            }
         }
 
         if let Some(spans) = self.context_window_spans() {
             lines.push(formatter.line("Context window", spans));
         }
 
+        if let Some(workspace_usage) = self.workspace_usage.as_ref() {
+            lines
+                .push(formatter.line("Workspace usage", vec![Span::from(workspace_usage.clone())]));
+        }
+
         lines.extend(self.rate_limit_lines(available_inner_width, &formatter));
 
+        if let Some(code88) = self.code88.as_ref() {
+            lines.push(Line::from(Vec::<Span<'static>>::new()));
+            lines.extend(code88.lines(&formatter));
+        }
+
         let content_width = lines.iter().map(line_display_width).max().unwrap_or(0);
         let inner_width = content_width.min(available_inner_width);
         let truncated_lines: Vec<Line<'static>> = lines
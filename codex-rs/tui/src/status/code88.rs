@@ -0,0 +1,159 @@
+//! 88code section of the `/status` card: subscription and credits from the
+//! status line's latest usage poll, plus the age of the saved login token.
+
+use chrono::DateTime;
+use chrono::Local;
+use codex_code88 as code88;
+use codex_core::config::Config;
+use ratatui::prelude::*;
+use ratatui::style::Stylize;
+use std::collections::BTreeSet;
+
+use super::format::FieldFormatter;
+use super::format::push_label;
+use super::rate_limits::format_status_limit_summary;
+use super::rate_limits::render_status_limit_progress_bar;
+use crate::statusline::StatusLine88CodeSnapshot;
+
+#[derive(Debug, Clone)]
+struct StatusCode88Credits {
+    remaining: f64,
+    limit: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct StatusCode88Display {
+    subscription: Option<String>,
+    credits: Option<StatusCode88Credits>,
+    token: String,
+    error: Option<String>,
+}
+
+/// Builds the 88code section when a login token or API key is configured.
+pub(crate) fn compose_code88_display(
+    config: &Config,
+    usage: Option<&StatusLine88CodeSnapshot>,
+    now: DateTime<Local>,
+) -> Option<StatusCode88Display> {
+    let resolved = config.code88_token_store().resolve_token();
+    if resolved.is_none() && config.tui_code88_api_key.is_none() {
+        return None;
+    }
+
+    let token = match resolved {
+        Some(code88::ResolvedToken {
+            saved: Some(file), ..
+        }) => {
+            let created_at = file.created_at.with_timezone(&Local);
+            let age = format_token_age(now.signed_duration_since(created_at));
+            let mut text = format!("created {} ({age})", created_at.format("%Y-%m-%d %H:%M"));
+            if !file.source.is_empty() {
+                text.push_str(&format!(" via {}", file.source));
+            }
+            text
+        }
+        Some(resolved) => format!("from {}", resolved.source.location(&config.codex_home)),
+        None => "API key from config (no saved login token)".to_string(),
+    };
+
+    let subscription = usage.and_then(subscription_label);
+    // The 88code provider reports its credits as the rate limits instead.
+    let credits = usage
+        .filter(|_| !config.model_provider.code88_auth)
+        .and_then(|usage| {
+            usage
+                .credits_remaining
+                .map(|remaining| StatusCode88Credits {
+                    remaining,
+                    limit: usage.credit_limit.filter(|limit| *limit > 0.0),
+                })
+        });
+    let error = usage
+        .filter(|usage| usage.is_error || usage.stale)
+        .map(|usage| {
+            let message = usage
+                .error_msg
+                .clone()
+                .unwrap_or_else(|| "request failed".to_string());
+            if usage.token_expired {
+                format!("{message} (refreshing login)")
+            } else if usage.stale {
+                format!("{message} (showing the last good numbers)")
+            } else {
+                message
+            }
+        });
+
+    Some(StatusCode88Display {
+        subscription,
+        credits,
+        token,
+        error,
+    })
+}
+
+/// `PRO (LV5)`: the subscription name with the service tier.
+fn subscription_label(usage: &StatusLine88CodeSnapshot) -> Option<String> {
+    match (&usage.subscription_name, &usage.service_tier) {
+        (Some(name), Some(tier)) => Some(format!("{name} ({tier})")),
+        (Some(name), None) => Some(name.clone()),
+        (None, Some(tier)) => Some(tier.clone()),
+        (None, None) => None,
+    }
+}
+
+/// `just now`, `12m ago`, `5h ago` or `3d ago`.
+fn format_token_age(age: chrono::Duration) -> String {
+    let minutes = age.num_minutes();
+    match minutes {
+        ..1 => "just now".to_string(),
+        1..60 => format!("{minutes}m ago"),
+        60..1440 => format!("{}h ago", minutes / 60),
+        _ => format!("{}d ago", minutes / 1440),
+    }
+}
+
+impl StatusCode88Display {
+    pub(crate) fn collect_labels(&self, seen: &mut BTreeSet<String>, labels: &mut Vec<String>) {
+        push_label(labels, seen, "88code");
+        if self.credits.is_some() {
+            push_label(labels, seen, "Credits");
+        }
+        push_label(labels, seen, "Token");
+        if self.error.is_some() {
+            push_label(labels, seen, "API error");
+        }
+    }
+
+    pub(crate) fn lines(&self, formatter: &FieldFormatter) -> Vec<Line<'static>> {
+        let subscription = match &self.subscription {
+            Some(subscription) => Span::from(subscription.clone()),
+            None => Span::from("data not available yet").dim(),
+        };
+        let mut lines = vec![formatter.line("88code", vec![subscription])];
+
+        if let Some(credits) = &self.credits {
+            let spans = match credits.limit {
+                Some(limit) => {
+                    let percent_remaining = credits.remaining / limit * 100.0;
+                    vec![
+                        Span::from(render_status_limit_progress_bar(percent_remaining)),
+                        Span::from(" "),
+                        Span::from(format_status_limit_summary(percent_remaining)),
+                        Span::from(format!(" ({:.2} / {limit:.2})", credits.remaining)).dim(),
+                    ]
+                }
+                None => vec![Span::from(format!("{:.2} left", credits.remaining))],
+            };
+            lines.push(formatter.line("Credits", spans));
+        }
+
+        lines.push(formatter.line("Token", vec![Span::from(self.token.clone())]));
+
+        if let Some(error) = &self.error {
+            lines.push(formatter.line("API error", vec![Span::from(error.clone()).red()]));
+        }
+
+        lines
+    }
+}
@@ -1,7 +1,7 @@
 use ratatui::prelude::*;
 use ratatui::style::Stylize;
 use std::collections::BTreeSet;
-use unicode_width::UnicodeWidthChar;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 #[derive(Debug, Clone)]
@@ -126,18 +126,9 @@ pub(crate) fn truncate_line_to_width(line: Line<'static>, max_width: usize) -> L
             continue;
         }
 
-        let mut truncated = String::new();
-        for ch in text.chars() {
-            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used + ch_width > max_width {
-                break;
-            }
-            truncated.push(ch);
-            used += ch_width;
-        }
-
+        let truncated = prefix_within_width(&text, max_width - used);
         if !truncated.is_empty() {
-            spans_out.push(Span::styled(truncated, style));
+            spans_out.push(Span::styled(truncated.to_string(), style));
         }
 
         break;
@@ -145,3 +136,31 @@ pub(crate) fn truncate_line_to_width(line: Line<'static>, max_width: usize) -> L
 
     Line::from(spans_out)
 }
+
+/// The longest prefix of `text` that fits in `max_width` columns. Cuts only
+/// between grapheme clusters, so combining marks and emoji ZWJ sequences are
+/// never split, and a wide character that would straddle the limit is left
+/// out.
+pub(crate) fn prefix_within_width(text: &str, max_width: usize) -> &str {
+    let mut used = 0usize;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        let width = UnicodeWidthStr::width(grapheme);
+        if used + width > max_width {
+            return &text[..index];
+        }
+        used += width;
+    }
+    text
+}
+
+/// `text` cut to at most `max_width` columns, ending in `…` when anything
+/// was dropped.
+pub(crate) fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if UnicodeWidthStr::width(text) <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    format!("{}…", prefix_within_width(text, max_width - 1))
+}
@@ -6,11 +6,15 @@ use codex_app_server_protocol::AuthMode;
 use codex_core::AuthManager;
 use codex_core::config::Config;
 use codex_core::project_doc::discover_project_doc_paths;
+use codex_core::workspace_state::load_workspace_state;
+use codex_core::workspace_state::workspace_state_location;
 use codex_protocol::account::PlanType;
 use std::path::Path;
+use std::path::PathBuf;
 use unicode_width::UnicodeWidthStr;
 
 use super::account::StatusAccountDisplay;
+use super::token_format::TokenCountFormatter;
 
 fn normalize_agents_display_path(path: &Path) -> String {
     dunce::simplified(path).display().to_string()
@@ -84,10 +88,109 @@ pub(crate) fn compose_agents_summary(config: &Config) -> String {
     }
 }
 
+/// Values persisted for the current workspace that take precedence over
+/// `config.toml`, and the state file they came from.
+#[derive(Debug, Clone)]
+pub(crate) struct StatusWorkspaceOverrides {
+    pub summary: String,
+    pub path: PathBuf,
+}
+
+pub(crate) fn compose_workspace_overrides(config: &Config) -> Option<StatusWorkspaceOverrides> {
+    let state = load_workspace_state(&config.codex_home, &config.cwd).ok()?;
+    let mut parts: Vec<String> = Vec::new();
+    if let Some(model) = state.model.as_ref() {
+        parts.push(format!("model {model}"));
+    }
+    if let Some(effort) = state.model_reasoning_effort {
+        parts.push(format!("effort {effort}"));
+    }
+    if let Some(approval) = state.approval_policy {
+        parts.push(format!("approval {approval}"));
+    }
+    if let Some(sandbox) = state.sandbox_mode {
+        parts.push(format!("sandbox {sandbox}"));
+    }
+    let mut disabled: Vec<&str> = state
+        .mcp_servers
+        .iter()
+        .filter(|(_, server)| server.enabled == Some(false))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !disabled.is_empty() {
+        disabled.sort_unstable();
+        parts.push(format!("MCP off: {}", disabled.join(", ")));
+    }
+    let mut launched: Vec<&str> = state
+        .mcp_servers
+        .iter()
+        .filter(|(_, server)| server.overrides_launch())
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !launched.is_empty() {
+        launched.sort_unstable();
+        parts.push(format!("MCP overrides: {}", launched.join(", ")));
+    }
+    if !state.env.is_empty() {
+        let mut keys: Vec<&str> = state.env.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        parts.push(format!("env: {}", keys.join(", ")));
+    }
+    if state.pinned_instructions.is_some() {
+        parts.push("pinned instructions".to_string());
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    Some(StatusWorkspaceOverrides {
+        summary: parts.join(", "),
+        path: workspace_state_location(&config.codex_home, &config.cwd),
+    })
+}
+
+/// Token totals recorded for this workspace across finished sessions.
+pub(crate) fn compose_workspace_usage(config: &Config) -> Option<String> {
+    let usage = load_workspace_state(&config.codex_home, &config.cwd)
+        .ok()?
+        .usage;
+    if usage.is_empty() {
+        return None;
+    }
+    let sessions = if usage.sessions == 1 {
+        "session"
+    } else {
+        "sessions"
+    };
+    let mut summary = format!(
+        "{} tokens over {} {sessions}",
+        TokenCountFormatter::from_config(&config.tui_token_format).format(usage.total_tokens()),
+        usage.sessions
+    );
+    if usage.unpriced_sessions < usage.sessions {
+        summary.push_str(&format!(" (~${:.2})", usage.estimated_cost_usd));
+    }
+    Some(summary)
+}
+
 pub(crate) fn compose_account_display(
+    config: &Config,
     auth_manager: &AuthManager,
     plan: Option<PlanType>,
 ) -> Option<StatusAccountDisplay> {
+    if config.model_provider.code88_auth {
+        let signed_in = config
+            .code88_token_store()
+            .resolve_token()
+            .and_then(|resolved| resolved.saved)
+            .map(|file| {
+                file.created_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            });
+        return Some(StatusAccountDisplay::Code88 { signed_in });
+    }
+
     let auth = auth_manager.auth()?;
 
     match auth.mode {
@@ -102,47 +205,6 @@ pub(crate) fn compose_account_display(
     }
 }
 
-pub(crate) fn format_tokens_compact(value: i64) -> String {
-    let value = value.max(0);
-    if value == 0 {
-        return "0".to_string();
-    }
-    if value < 1_000 {
-        return value.to_string();
-    }
-
-    let value_f64 = value as f64;
-    let (scaled, suffix) = if value >= 1_000_000_000_000 {
-        (value_f64 / 1_000_000_000_000.0, "T")
-    } else if value >= 1_000_000_000 {
-        (value_f64 / 1_000_000_000.0, "B")
-    } else if value >= 1_000_000 {
-        (value_f64 / 1_000_000.0, "M")
-    } else {
-        (value_f64 / 1_000.0, "K")
-    };
-
-    let decimals = if scaled < 10.0 {
-        2
-    } else if scaled < 100.0 {
-        1
-    } else {
-        0
-    };
-
-    let mut formatted = format!("{scaled:.decimals$}");
-    if formatted.contains('.') {
-        while formatted.ends_with('0') {
-            formatted.pop();
-        }
-        if formatted.ends_with('.') {
-            formatted.pop();
-        }
-    }
-
-    format!("{formatted}{suffix}")
-}
-
 pub(crate) fn format_directory_display(directory: &Path, max_width: Option<usize>) -> String {
     let formatted = if let Some(rel) = relativize_to_home(directory) {
         if rel.as_os_str().is_empty() {
@@ -1,16 +1,27 @@
 mod account;
 mod card;
+mod code88;
 mod format;
 mod helpers;
+mod rate_limit_history;
 mod rate_limits;
+mod token_format;
+mod watch;
 
 pub(crate) use card::new_status_output;
 pub(crate) use format::line_display_width;
+pub(crate) use format::prefix_within_width;
 pub(crate) use format::truncate_line_to_width;
+pub(crate) use format::truncate_to_width;
 pub(crate) use helpers::format_directory_display;
-pub(crate) use helpers::format_tokens_compact;
+pub(crate) use rate_limit_history::RateLimitHistory;
 pub(crate) use rate_limits::RateLimitSnapshotDisplay;
+pub(crate) use rate_limits::code88_rate_limit_display;
+pub(crate) use rate_limits::code88_rate_limit_snapshot;
 pub(crate) use rate_limits::rate_limit_snapshot_display;
+pub(crate) use token_format::TokenCountFormatter;
+pub use watch::StatusWatchOptions;
+pub use watch::run_status_watch;
 
 #[cfg(test)]
 mod tests;
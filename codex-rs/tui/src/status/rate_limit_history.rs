@@ -0,0 +1,398 @@
+//! Recent rate-limit snapshots for the `/status` sparkline and trend arrow.
+//!
+//! Samples live in a ring buffer for the session. With
+//! `tui.rate_limit_history` they are also appended to
+//! `CODEX_HOME/rate_limit_history/<session id>.jsonl`, so a resumed session
+//! picks its history back up. Each file keeps at most the samples the
+//! sparkline can use, and files of sessions untouched for
+//! `HISTORY_MAX_AGE` are deleted.
+
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::Utc;
+use codex_core::protocol::RateLimitSnapshot;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::rate_limits::RateLimitSnapshotDisplay;
+
+const HISTORY_CAPACITY: usize = 48;
+const SPARKLINE_WIDTH: usize = 12;
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const HISTORY_DIR: &str = "rate_limit_history";
+/// Lines a history file may grow to before it is rewritten with just the
+/// samples still in the ring buffer.
+const FILE_COMPACT_LINES: usize = HISTORY_CAPACITY * 2;
+const HISTORY_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+/// Latest burn rate relative to the session average that counts as a change.
+const TREND_FASTER_RATIO: f64 = 1.5;
+const TREND_SLOWER_RATIO: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RateLimitSample {
+    pub captured_at: DateTime<Utc>,
+    pub primary_used: Option<f64>,
+    pub secondary_used: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RateLimitTrend {
+    Faster,
+    Steady,
+    Slower,
+}
+
+impl RateLimitTrend {
+    pub(crate) fn arrow(self) -> &'static str {
+        match self {
+            RateLimitTrend::Faster => "↑",
+            RateLimitTrend::Steady => "→",
+            RateLimitTrend::Slower => "↓",
+        }
+    }
+}
+
+/// Sparkline of usage for one window, oldest sample first.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RateLimitWindowHistory {
+    pub sparkline: String,
+    pub trend: Option<RateLimitTrend>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct RateLimitHistory {
+    samples: VecDeque<RateLimitSample>,
+    file: Option<PathBuf>,
+    /// Lines in `file`, so it can be compacted once it outgrows the buffer.
+    file_lines: usize,
+}
+
+impl RateLimitHistory {
+    /// Persist samples for `session_id`, loading any saved by an earlier run
+    /// of the same session.
+    pub(crate) fn persist_to(&mut self, codex_home: &Path, session_id: &str) {
+        let dir = codex_home.join(HISTORY_DIR);
+        let path = dir.join(format!("{session_id}.jsonl"));
+        if self.file.as_ref() == Some(&path) {
+            return;
+        }
+        // Samples already written for another session stay with it.
+        let unsaved: Vec<RateLimitSample> = if self.file.is_some() {
+            self.samples.clear();
+            Vec::new()
+        } else {
+            self.samples.drain(..).collect()
+        };
+        let mut file_lines = 0;
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            for line in contents.lines() {
+                file_lines += 1;
+                match serde_json::from_str::<RateLimitSample>(line) {
+                    Ok(sample) => self.push(sample),
+                    Err(err) => tracing::debug!("skipping rate limit history line: {err}"),
+                }
+            }
+        }
+        if let Err(err) = std::fs::create_dir_all(&dir) {
+            tracing::warn!("failed to create {}: {err}", dir.display());
+            return;
+        }
+        prune_old_files(&dir, &path, SystemTime::now());
+        self.file = Some(path);
+        self.file_lines = file_lines;
+        if self.file_lines > HISTORY_CAPACITY {
+            self.compact();
+        }
+        for sample in unsaved {
+            self.append(sample);
+        }
+    }
+
+    pub(crate) fn record(&mut self, snapshot: &RateLimitSnapshot, captured_at: DateTime<Local>) {
+        self.append(RateLimitSample {
+            captured_at: captured_at.with_timezone(&Utc),
+            primary_used: snapshot.primary.as_ref().map(|window| window.used_percent),
+            secondary_used: snapshot
+                .secondary
+                .as_ref()
+                .map(|window| window.used_percent),
+        });
+    }
+
+    /// Attach sparklines for the windows present in `display`.
+    pub(crate) fn annotate(&self, display: &mut RateLimitSnapshotDisplay) {
+        if let Some(primary) = display.primary.as_mut() {
+            primary.history = self.window(|sample| sample.primary_used);
+        }
+        if let Some(secondary) = display.secondary.as_mut() {
+            secondary.history = self.window(|sample| sample.secondary_used);
+        }
+    }
+
+    fn append(&mut self, sample: RateLimitSample) {
+        self.push(sample);
+        if self.file_lines >= FILE_COMPACT_LINES {
+            self.compact();
+            return;
+        }
+        let Some(path) = self.file.as_ref() else {
+            return;
+        };
+        let result = serde_json::to_string(&sample)
+            .map_err(std::io::Error::other)
+            .and_then(|line| {
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{line}")
+            });
+        match result {
+            Ok(()) => self.file_lines += 1,
+            Err(err) => tracing::warn!("failed to write {}: {err}", path.display()),
+        }
+    }
+
+    /// Rewrite the history file with just the samples in the ring buffer.
+    fn compact(&mut self) {
+        let Some(path) = self.file.as_ref() else {
+            return;
+        };
+        let result = self
+            .samples
+            .iter()
+            .map(|sample| serde_json::to_string(sample).map(|line| format!("{line}\n")))
+            .collect::<Result<String, _>>()
+            .map_err(std::io::Error::other)
+            .and_then(|contents| std::fs::write(path, contents));
+        match result {
+            Ok(()) => self.file_lines = self.samples.len(),
+            Err(err) => tracing::warn!("failed to write {}: {err}", path.display()),
+        }
+    }
+
+    fn push(&mut self, sample: RateLimitSample) {
+        if self.samples.len() == HISTORY_CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    fn window(
+        &self,
+        used: impl Fn(&RateLimitSample) -> Option<f64>,
+    ) -> Option<RateLimitWindowHistory> {
+        let points: Vec<(DateTime<Utc>, f64)> = self
+            .samples
+            .iter()
+            .filter_map(|sample| used(sample).map(|percent| (sample.captured_at, percent)))
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+        let sparkline = points
+            .iter()
+            .skip(points.len().saturating_sub(SPARKLINE_WIDTH))
+            .map(|(_, percent)| sparkline_level(*percent))
+            .collect();
+        Some(RateLimitWindowHistory {
+            sparkline,
+            trend: burn_trend(&points),
+        })
+    }
+}
+
+/// Delete history files, other than `keep`, last written more than
+/// `HISTORY_MAX_AGE` before `now`.
+fn prune_old_files(dir: &Path, keep: &Path, now: SystemTime) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path == keep || path.extension().is_none_or(|ext| ext != "jsonl") {
+            continue;
+        }
+        let expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| {
+                now.duration_since(modified)
+                    .is_ok_and(|age| age > HISTORY_MAX_AGE)
+            });
+        if expired && let Err(err) = std::fs::remove_file(&path) {
+            tracing::warn!("failed to remove {}: {err}", path.display());
+        }
+    }
+}
+
+fn sparkline_level(percent_used: f64) -> char {
+    let ratio = (percent_used / 100.0).clamp(0.0, 1.0);
+    let index = (ratio * (SPARKLINE_LEVELS.len() - 1) as f64).round() as usize;
+    SPARKLINE_LEVELS[index]
+}
+
+/// Compares the latest burn rate (percent per hour) with the average of the
+/// earlier ones. Intervals where usage dropped span a window reset and are
+/// ignored.
+fn burn_trend(points: &[(DateTime<Utc>, f64)]) -> Option<RateLimitTrend> {
+    let rates: Vec<f64> = points
+        .windows(2)
+        .filter_map(|pair| {
+            let (start, start_used) = pair[0];
+            let (end, end_used) = pair[1];
+            let hours = (end - start).num_seconds() as f64 / 3600.0;
+            (hours > 0.0 && end_used >= start_used).then(|| (end_used - start_used) / hours)
+        })
+        .collect();
+    let (latest, earlier) = rates.split_last()?;
+    if earlier.is_empty() {
+        return None;
+    }
+    let usual = earlier.iter().sum::<f64>() / earlier.len() as f64;
+    let trend = if *latest > usual * TREND_FASTER_RATIO && *latest > 0.0 {
+        RateLimitTrend::Faster
+    } else if *latest < usual * TREND_SLOWER_RATIO {
+        RateLimitTrend::Slower
+    } else {
+        RateLimitTrend::Steady
+    };
+    Some(trend)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+    use chrono::TimeZone;
+    use codex_core::protocol::RateLimitWindow;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn snapshot(primary_used: f64) -> RateLimitSnapshot {
+        RateLimitSnapshot {
+            primary: Some(RateLimitWindow {
+                used_percent: primary_used,
+                window_minutes: Some(300),
+                resets_at: None,
+            }),
+            secondary: None,
+            credits: None,
+            plan_type: None,
+        }
+    }
+
+    fn record_series(history: &mut RateLimitHistory, series: &[(i64, f64)]) {
+        let start = Local
+            .with_ymd_and_hms(2024, 6, 1, 12, 0, 0)
+            .single()
+            .expect("timestamp");
+        for (minutes, used) in series {
+            history.record(&snapshot(*used), start + ChronoDuration::minutes(*minutes));
+        }
+    }
+
+    fn primary_history(history: &RateLimitHistory) -> Option<RateLimitWindowHistory> {
+        history.window(|sample| sample.primary_used)
+    }
+
+    #[test]
+    fn sparkline_needs_two_samples_and_tracks_usage() {
+        let mut history = RateLimitHistory::default();
+        record_series(&mut history, &[(0, 0.0)]);
+        assert_eq!(primary_history(&history), None);
+
+        record_series(&mut history, &[(10, 50.0), (20, 100.0)]);
+        let window = primary_history(&history).expect("history");
+        assert_eq!(window.sparkline, "▁▅█");
+        assert_eq!(window.trend, Some(RateLimitTrend::Steady));
+    }
+
+    #[test]
+    fn trend_compares_latest_burn_rate_with_earlier_ones() {
+        let mut faster = RateLimitHistory::default();
+        record_series(
+            &mut faster,
+            &[(0, 10.0), (30, 12.0), (60, 14.0), (70, 24.0)],
+        );
+        assert_eq!(
+            primary_history(&faster).and_then(|window| window.trend),
+            Some(RateLimitTrend::Faster)
+        );
+
+        let mut slower = RateLimitHistory::default();
+        record_series(
+            &mut slower,
+            &[(0, 10.0), (30, 20.0), (60, 30.0), (90, 31.0)],
+        );
+        assert_eq!(
+            primary_history(&slower).and_then(|window| window.trend),
+            Some(RateLimitTrend::Slower)
+        );
+
+        // A window reset is not a change in pace.
+        let mut reset = RateLimitHistory::default();
+        record_series(&mut reset, &[(0, 80.0), (30, 90.0), (60, 2.0), (90, 12.0)]);
+        assert_eq!(
+            primary_history(&reset).and_then(|window| window.trend),
+            Some(RateLimitTrend::Steady)
+        );
+    }
+
+    #[test]
+    fn persisted_history_survives_a_new_session_widget() {
+        let temp_home = TempDir::new().expect("temp home");
+        let mut history = RateLimitHistory::default();
+        record_series(&mut history, &[(0, 10.0)]);
+        history.persist_to(temp_home.path(), "session");
+        record_series(&mut history, &[(10, 20.0)]);
+
+        let mut resumed = RateLimitHistory::default();
+        resumed.persist_to(temp_home.path(), "session");
+        let window = primary_history(&resumed).expect("history");
+        assert_eq!(window.sparkline, "▂▂");
+        assert_eq!(resumed.samples.len(), 2);
+    }
+
+    #[test]
+    fn history_file_is_compacted_to_the_ring_buffer() {
+        let temp_home = TempDir::new().expect("temp home");
+        let mut history = RateLimitHistory::default();
+        history.persist_to(temp_home.path(), "session");
+        let series: Vec<(i64, f64)> = (0..=FILE_COMPACT_LINES)
+            .map(|minutes| (minutes as i64, 1.0))
+            .collect();
+        record_series(&mut history, &series);
+
+        let file = temp_home.path().join(HISTORY_DIR).join("session.jsonl");
+        let lines = std::fs::read_to_string(&file)
+            .expect("history file")
+            .lines()
+            .count();
+        assert_eq!(lines, HISTORY_CAPACITY);
+    }
+
+    #[test]
+    fn old_history_files_are_pruned() {
+        let temp_home = TempDir::new().expect("temp home");
+        let dir = temp_home.path().join(HISTORY_DIR);
+        std::fs::create_dir_all(&dir).expect("history dir");
+        let current = dir.join("current.jsonl");
+        let other = dir.join("other.jsonl");
+        std::fs::write(&current, "").expect("current");
+        std::fs::write(&other, "").expect("other");
+
+        prune_old_files(&dir, &current, SystemTime::now());
+        assert!(other.exists());
+
+        let later = SystemTime::now() + HISTORY_MAX_AGE + Duration::from_secs(60);
+        prune_old_files(&dir, &current, later);
+        assert!(current.exists());
+        assert!(!other.exists());
+    }
+}
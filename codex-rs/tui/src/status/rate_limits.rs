@@ -1,6 +1,7 @@
 use crate::chatwidget::get_limits_duration;
 
 use super::helpers::format_reset_timestamp;
+use super::rate_limit_history::RateLimitWindowHistory;
 use chrono::DateTime;
 use chrono::Duration as ChronoDuration;
 use chrono::Local;
@@ -9,6 +10,8 @@ use codex_core::protocol::CreditsSnapshot as CoreCreditsSnapshot;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::RateLimitWindow;
 
+use crate::statusline::StatusLine88CodeSnapshot;
+
 const STATUS_LIMIT_BAR_SEGMENTS: usize = 20;
 const STATUS_LIMIT_BAR_FILLED: &str = "█";
 const STATUS_LIMIT_BAR_EMPTY: &str = "░";
@@ -24,6 +27,7 @@ pub(crate) enum StatusRateLimitValue {
     Window {
         percent_used: f64,
         resets_at: Option<String>,
+        history: Option<RateLimitWindowHistory>,
     },
     Text(String),
 }
@@ -42,6 +46,10 @@ pub(crate) struct RateLimitWindowDisplay {
     pub used_percent: f64,
     pub resets_at: Option<String>,
     pub window_minutes: Option<i64>,
+    /// Row label in place of the one derived from `window_minutes`.
+    pub label: Option<String>,
+    /// Recent usage for this window, filled in by `RateLimitHistory::annotate`.
+    pub history: Option<RateLimitWindowHistory>,
 }
 
 impl RateLimitWindowDisplay {
@@ -56,6 +64,8 @@ impl RateLimitWindowDisplay {
             used_percent: window.used_percent,
             resets_at,
             window_minutes: window.window_minutes,
+            label: None,
+            history: None,
         }
     }
 }
@@ -93,6 +103,52 @@ pub(crate) fn rate_limit_snapshot_display(
     }
 }
 
+/// The 88code subscription's credits from the latest usage poll as a rate
+/// limit snapshot: the share of the credit limit spent as the primary window
+/// and the remaining balance as credits. `None` until a poll succeeded with a
+/// balance, and while a failed poll keeps showing the last good numbers.
+pub(crate) fn code88_rate_limit_snapshot(
+    usage: &StatusLine88CodeSnapshot,
+) -> Option<RateLimitSnapshot> {
+    // Stale numbers were recorded when they were fetched; recording them
+    // again would pass them off as current.
+    if usage.is_error || usage.stale {
+        return None;
+    }
+    let remaining = usage.credits_remaining?;
+    let primary = usage
+        .credit_limit
+        .filter(|limit| *limit > 0.0)
+        .map(|limit| RateLimitWindow {
+            used_percent: ((limit - remaining) / limit * 100.0).clamp(0.0, 100.0),
+            window_minutes: None,
+            resets_at: None,
+        });
+    Some(RateLimitSnapshot {
+        primary,
+        secondary: None,
+        credits: Some(CoreCreditsSnapshot {
+            has_credits: true,
+            unlimited: false,
+            balance: Some(remaining.to_string()),
+        }),
+        plan_type: None,
+    })
+}
+
+/// [`rate_limit_snapshot_display`] for a [`code88_rate_limit_snapshot`]: its
+/// window is the credit limit rather than a time window.
+pub(crate) fn code88_rate_limit_display(
+    snapshot: &RateLimitSnapshot,
+    captured_at: DateTime<Local>,
+) -> RateLimitSnapshotDisplay {
+    let mut display = rate_limit_snapshot_display(snapshot, captured_at);
+    if let Some(primary) = display.primary.as_mut() {
+        primary.label = Some("Credit".to_string());
+    }
+    display
+}
+
 impl From<&CoreCreditsSnapshot> for CreditsSnapshotDisplay {
     fn from(value: &CoreCreditsSnapshot) -> Self {
         Self {
@@ -112,31 +168,37 @@ pub(crate) fn compose_rate_limit_data(
             let mut rows = Vec::with_capacity(3);
 
             if let Some(primary) = snapshot.primary.as_ref() {
-                let label: String = primary
-                    .window_minutes
-                    .map(get_limits_duration)
-                    .unwrap_or_else(|| "5h".to_string());
-                let label = capitalize_first(&label);
+                let label = primary.label.clone().unwrap_or_else(|| {
+                    let label = primary
+                        .window_minutes
+                        .map(get_limits_duration)
+                        .unwrap_or_else(|| "5h".to_string());
+                    capitalize_first(&label)
+                });
                 rows.push(StatusRateLimitRow {
                     label: format!("{label} limit"),
                     value: StatusRateLimitValue::Window {
                         percent_used: primary.used_percent,
                         resets_at: primary.resets_at.clone(),
+                        history: primary.history.clone(),
                     },
                 });
             }
 
             if let Some(secondary) = snapshot.secondary.as_ref() {
-                let label: String = secondary
-                    .window_minutes
-                    .map(get_limits_duration)
-                    .unwrap_or_else(|| "weekly".to_string());
-                let label = capitalize_first(&label);
+                let label = secondary.label.clone().unwrap_or_else(|| {
+                    let label = secondary
+                        .window_minutes
+                        .map(get_limits_duration)
+                        .unwrap_or_else(|| "weekly".to_string());
+                    capitalize_first(&label)
+                });
                 rows.push(StatusRateLimitRow {
                     label: format!("{label} limit"),
                     value: StatusRateLimitValue::Window {
                         percent_used: secondary.used_percent,
                         resets_at: secondary.resets_at.clone(),
+                        history: secondary.history.clone(),
                     },
                 });
             }
@@ -1,6 +1,17 @@
+use super::RateLimitHistory;
+use super::code88_rate_limit_display;
+use super::code88_rate_limit_snapshot;
 use super::new_status_output;
+use super::prefix_within_width;
 use super::rate_limit_snapshot_display;
+use super::truncate_line_to_width;
+use super::truncate_to_width;
+use super::watch::RolloutTail;
+use super::watch::WatchedSession;
+use super::watch::parse_rollout;
+use super::watch::status_lines;
 use crate::history_cell::HistoryCell;
+use crate::statusline::StatusLine88CodeSnapshot;
 use chrono::Duration as ChronoDuration;
 use chrono::TimeZone;
 use chrono::Utc;
@@ -8,11 +19,25 @@ use codex_core::AuthManager;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::ConfigToml;
+use codex_core::config::types::ModelPrice;
+use codex_core::protocol::AskForApproval;
 use codex_core::protocol::CreditsSnapshot;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::GitInfo;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::RateLimitWindow;
+use codex_core::protocol::RolloutItem;
+use codex_core::protocol::RolloutLine;
 use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::TokenCountEvent;
 use codex_core::protocol::TokenUsage;
+use codex_core::protocol::TokenUsageInfo;
+use codex_core::protocol::TurnContextItem;
+use codex_core::workspace_state::persist_mcp_enabled;
+use codex_core::workspace_state::persist_model_selection;
+use codex_core::workspace_state::persist_policy_selection;
+use codex_core::workspace_state::record_session_usage;
+use codex_core::workspace_state::workspace_state_path;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::openai_models::ReasoningEffort;
 use insta::assert_snapshot;
@@ -126,12 +151,15 @@ fn status_snapshot_includes_reasoning_details() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         captured_at,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
@@ -179,12 +207,15 @@ fn status_snapshot_includes_monthly_limit() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         captured_at,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
@@ -220,12 +251,15 @@ fn status_snapshot_shows_unlimited_credits() {
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         captured_at,
     );
     let rendered = render_lines(&composite.display_lines(120));
@@ -260,12 +294,15 @@ fn status_snapshot_shows_positive_credits() {
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         captured_at,
     );
     let rendered = render_lines(&composite.display_lines(120));
@@ -300,12 +337,15 @@ fn status_snapshot_hides_zero_credits() {
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         captured_at,
     );
     let rendered = render_lines(&composite.display_lines(120));
@@ -338,12 +378,15 @@ fn status_snapshot_hides_when_has_no_credits_flag() {
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         captured_at,
     );
     let rendered = render_lines(&composite.display_lines(120));
@@ -376,12 +419,15 @@ fn status_card_token_usage_excludes_cached_tokens() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         None,
         None,
+        None,
+        &[],
         now,
     );
     let rendered = render_lines(&composite.display_lines(120));
@@ -429,12 +475,15 @@ fn status_snapshot_truncates_in_narrow_terminal() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         captured_at,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(70));
@@ -471,12 +520,15 @@ fn status_snapshot_shows_missing_limits_message() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         None,
         None,
+        None,
+        &[],
         now,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
@@ -531,12 +583,15 @@ fn status_snapshot_includes_credits_and_limits() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         captured_at,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
@@ -579,12 +634,15 @@ fn status_snapshot_shows_empty_limits_message() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         captured_at,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
@@ -636,12 +694,15 @@ fn status_snapshot_shows_stale_limits_message() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         now,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
@@ -697,12 +758,15 @@ fn status_snapshot_cached_limits_hide_credits_without_flag() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &usage,
         Some(&usage),
         &None,
         Some(&rate_display),
         None,
+        None,
+        &[],
         now,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
@@ -744,12 +808,15 @@ fn status_context_window_uses_last_usage() {
 
     let composite = new_status_output(
         &config,
+        None,
         &auth_manager,
         &total_usage,
         Some(&last_usage),
         &None,
         None,
         None,
+        None,
+        &[],
         now,
     );
     let rendered_lines = render_lines(&composite.display_lines(80));
@@ -767,3 +834,688 @@ fn status_context_window_uses_last_usage() {
         "context line should not use total aggregated tokens, got: {context_line}"
     );
 }
+
+#[test]
+fn status_card_mentions_a_newer_release() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.cwd = PathBuf::from("/workspace/tests");
+    std::fs::write(
+        config.codex_home.join("release-check.json"),
+        r#"{"release":{"version":"9999.1.1","commit":null,"url":"https://github.com/byebye-code/codex/releases/tag/v9999.1.1"},"last_checked_at":"2024-06-01T12:00:00Z"}"#,
+    )
+    .expect("write release check");
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 6, 1, 12, 0, 0)
+        .single()
+        .expect("timestamp");
+    let render = |config: &Config| {
+        let composite = new_status_output(
+            config,
+            None,
+            &auth_manager,
+            &usage,
+            Some(&usage),
+            &None,
+            None,
+            None,
+            None,
+            &[],
+            now,
+        );
+        render_lines(&composite.display_lines(80)).join("\n")
+    };
+
+    // The check is opt-in; a cache left behind shows nothing without it.
+    assert!(!render(&config).contains("9999.1.1"));
+
+    config.check_for_release_on_startup = true;
+    let rendered = render(&config);
+    assert!(
+        rendered.contains("Update: ")
+            && rendered.contains("9999.1.1 available (run codex upgrade)"),
+        "{rendered}"
+    );
+}
+
+fn write_code88_token(config: &Config, created_at: chrono::DateTime<chrono::Local>) {
+    let file = codex_code88::TokenFile {
+        token: "login-token".to_string(),
+        created_at: created_at.with_timezone(&Utc),
+        source: "browser_login".to_string(),
+        claims: None,
+    };
+    std::fs::write(
+        codex_code88::token_path(&config.codex_home),
+        serde_json::to_string(&file).expect("serialize token"),
+    )
+    .expect("write token");
+}
+
+#[test]
+fn status_snapshot_includes_code88_subscription() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.model = "gpt-5.1-codex-max".to_string();
+    config.model_provider_id = "openai".to_string();
+    config.cwd = PathBuf::from("/workspace/tests");
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 6, 1, 12, 0, 0)
+        .single()
+        .expect("timestamp");
+    write_code88_token(&config, now - ChronoDuration::days(3));
+    let code88 = StatusLine88CodeSnapshot {
+        service_tier: Some("LV5".to_string()),
+        subscription_name: Some("PRO".to_string()),
+        credits_remaining: Some(42.5),
+        credit_limit: Some(100.0),
+        ..Default::default()
+    };
+
+    let composite = new_status_output(
+        &config,
+        None,
+        &auth_manager,
+        &usage,
+        Some(&usage),
+        &None,
+        None,
+        None,
+        Some(&code88),
+        &[],
+        now,
+    );
+    let mut rendered_lines = render_lines(&composite.display_lines(80));
+    if cfg!(windows) {
+        for line in &mut rendered_lines {
+            *line = line.replace('\\', "/");
+        }
+    }
+    let sanitized = sanitize_directory(rendered_lines).join("\n");
+    assert_snapshot!(sanitized);
+}
+
+#[test]
+fn status_shows_code88_api_error_without_token_file() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let now = chrono::Local::now();
+
+    let without_key = new_status_output(
+        &config,
+        None,
+        &auth_manager,
+        &usage,
+        Some(&usage),
+        &None,
+        None,
+        None,
+        None,
+        &[],
+        now,
+    );
+    let rendered = render_lines(&without_key.display_lines(120));
+    assert!(
+        rendered.iter().all(|line| !line.contains("88code")),
+        "88code section should be hidden without a token, got: {rendered:?}"
+    );
+
+    config.tui_code88_api_key = Some("88_key".to_string());
+    let code88 = StatusLine88CodeSnapshot {
+        is_error: true,
+        error_msg: Some("Token expired".to_string()),
+        token_expired: true,
+        ..Default::default()
+    };
+    let composite = new_status_output(
+        &config,
+        None,
+        &auth_manager,
+        &usage,
+        Some(&usage),
+        &None,
+        None,
+        None,
+        Some(&code88),
+        &[],
+        now,
+    );
+    let rendered = render_lines(&composite.display_lines(120));
+    assert!(
+        rendered
+            .iter()
+            .any(|line| line.contains("88code:") && line.contains("data not available yet")),
+        "expected a pending 88code subscription in: {rendered:?}"
+    );
+    for expected in [
+        "API key from config (no saved login token)",
+        "Token expired (refreshing login)",
+    ] {
+        assert!(
+            rendered.iter().any(|line| line.contains(expected)),
+            "expected `{expected}` in: {rendered:?}"
+        );
+    }
+}
+
+#[test]
+fn status_shows_code88_credits_as_rate_limits_for_code88_provider() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.model_provider.code88_auth = true;
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let now = chrono::Local::now();
+    write_code88_token(&config, now);
+    let code88 = StatusLine88CodeSnapshot {
+        subscription_name: Some("PRO".to_string()),
+        credits_remaining: Some(40.0),
+        credit_limit: Some(100.0),
+        ..Default::default()
+    };
+    let snapshot = code88_rate_limit_snapshot(&code88).expect("credits");
+    let rate_limits = code88_rate_limit_display(&snapshot, now);
+
+    let composite = new_status_output(
+        &config,
+        None,
+        &auth_manager,
+        &usage,
+        Some(&usage),
+        &None,
+        Some(&rate_limits),
+        None,
+        Some(&code88),
+        &[],
+        now,
+    );
+    let rendered = render_lines(&composite.display_lines(120));
+    let credit_limit = rendered
+        .iter()
+        .find(|line| line.contains("Credit limit:"))
+        .unwrap_or_else(|| panic!("no credit limit row in {rendered:?}"));
+    assert!(credit_limit.contains("40% left"), "{credit_limit}");
+    let credits: Vec<&String> = rendered
+        .iter()
+        .filter(|line| line.contains("Credits:"))
+        .collect();
+    assert_eq!(credits.len(), 1, "credits shown once: {rendered:?}");
+    assert!(credits[0].contains("40 credits"), "{}", credits[0]);
+
+    let stale = StatusLine88CodeSnapshot {
+        stale: true,
+        ..code88.clone()
+    };
+    assert_eq!(code88_rate_limit_snapshot(&stale), None);
+    let failed = StatusLine88CodeSnapshot {
+        is_error: true,
+        ..code88
+    };
+    assert_eq!(code88_rate_limit_snapshot(&failed), None);
+}
+
+#[test]
+fn status_splits_token_usage_by_model_after_a_switch() {
+    let temp_home = TempDir::new().expect("temp home");
+    let config = test_config(&temp_home);
+    let auth_manager = test_auth_manager(&config);
+    let usage = |input_tokens, output_tokens| TokenUsage {
+        input_tokens,
+        output_tokens,
+        total_tokens: input_tokens + output_tokens,
+        ..TokenUsage::default()
+    };
+    let total = usage(13_000, 3_000);
+    let model_usage = vec![
+        ("gpt-5-codex".to_string(), usage(12_000, 2_000)),
+        ("o3".to_string(), usage(1_000, 1_000)),
+    ];
+    let now = chrono::Local::now();
+
+    let render = |model_usage: &[(String, TokenUsage)]| {
+        let composite = new_status_output(
+            &config,
+            None,
+            &auth_manager,
+            &total,
+            Some(&total),
+            &None,
+            None,
+            None,
+            None,
+            model_usage,
+            now,
+        );
+        render_lines(&composite.display_lines(120))
+    };
+
+    let rendered = render(&model_usage);
+    let model_row = |model: &str| {
+        rendered
+            .iter()
+            .find(|line| line.contains(&format!("{model}: ")))
+            .cloned()
+            .unwrap_or_else(|| panic!("no {model} row in {rendered:?}"))
+    };
+    assert!(model_row("gpt-5-codex").contains("14K total"));
+    assert!(model_row("o3").contains("2K total"));
+
+    let single = render(&model_usage[..1]);
+    assert!(
+        single.iter().all(|line| !line.contains("gpt-5-codex: ")),
+        "one model needs no split, got: {single:?}"
+    );
+}
+
+#[test]
+fn status_shows_code88_account_for_code88_provider() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.model_provider.code88_auth = true;
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let now = chrono::Local::now();
+    let account_line = |config: &Config| {
+        let composite = new_status_output(
+            config,
+            None,
+            &auth_manager,
+            &usage,
+            Some(&usage),
+            &None,
+            None,
+            None,
+            None,
+            &[],
+            now,
+        );
+        render_lines(&composite.display_lines(120))
+            .into_iter()
+            .find(|line| line.contains("Account:"))
+            .expect("account line")
+    };
+
+    assert!(account_line(&config).contains("88code, signed out"));
+
+    write_code88_token(&config, now);
+    let expected = format!("88code (signed in {})", now.format("%Y-%m-%d"));
+    assert!(account_line(&config).contains(&expected));
+}
+
+#[test]
+fn status_snapshot_shows_rate_limit_trend() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.model = "gpt-5.1-codex-max".to_string();
+    config.model_provider_id = "openai".to_string();
+    config.cwd = PathBuf::from("/workspace/tests");
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let start = chrono::Local
+        .with_ymd_and_hms(2024, 6, 1, 12, 0, 0)
+        .single()
+        .expect("timestamp");
+    let mut history = RateLimitHistory::default();
+    let mut display = None;
+    for (minutes, used) in [(0, 10.0), (30, 14.0), (60, 18.0), (70, 35.0)] {
+        let captured_at = start + ChronoDuration::minutes(minutes);
+        let snapshot = RateLimitSnapshot {
+            primary: Some(RateLimitWindow {
+                used_percent: used,
+                window_minutes: Some(300),
+                resets_at: Some(reset_at_from(&captured_at, 3_600)),
+            }),
+            secondary: None,
+            credits: None,
+            plan_type: None,
+        };
+        let mut snapshot_display = rate_limit_snapshot_display(&snapshot, captured_at);
+        history.record(&snapshot, captured_at);
+        history.annotate(&mut snapshot_display);
+        display = Some(snapshot_display);
+    }
+
+    let composite = new_status_output(
+        &config,
+        None,
+        &auth_manager,
+        &usage,
+        Some(&usage),
+        &None,
+        display.as_ref(),
+        None,
+        None,
+        &[],
+        start + ChronoDuration::minutes(70),
+    );
+    let mut rendered_lines = render_lines(&composite.display_lines(80));
+    if cfg!(windows) {
+        for line in &mut rendered_lines {
+            *line = line.replace('\\', "/");
+        }
+    }
+    let sanitized = sanitize_directory(rendered_lines).join("\n");
+    assert_snapshot!(sanitized);
+}
+
+#[tokio::test]
+async fn status_lists_workspace_overrides_with_state_file() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.cwd = PathBuf::from("/workspace/tests");
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let render = |config: &Config| {
+        let composite = new_status_output(
+            config,
+            None,
+            &auth_manager,
+            &usage,
+            Some(&usage),
+            &None,
+            None,
+            None,
+            None,
+            &[],
+            chrono::Local::now(),
+        );
+        render_lines(&composite.display_lines(200))
+    };
+
+    let rendered = render(&config);
+    assert!(
+        rendered.iter().all(|line| !line.contains("Overrides:")),
+        "no overrides without workspace state, got: {rendered:?}"
+    );
+
+    persist_model_selection(
+        &config.codex_home,
+        &config.cwd,
+        "gpt-5-codex",
+        Some(ReasoningEffort::High),
+    )
+    .expect("persist model");
+    persist_policy_selection(
+        &config.codex_home,
+        &config.cwd,
+        AskForApproval::Never,
+        &SandboxPolicy::DangerFullAccess,
+    )
+    .await
+    .expect("persist policy");
+    persist_mcp_enabled(&config.codex_home, &config.cwd, "search", false).expect("persist mcp");
+    persist_mcp_enabled(&config.codex_home, &config.cwd, "docs", false).expect("persist mcp");
+    persist_mcp_enabled(&config.codex_home, &config.cwd, "linear", true).expect("persist mcp");
+
+    let rendered = render(&config);
+    let overrides = rendered
+        .iter()
+        .position(|line| line.contains("Overrides:"))
+        .expect("overrides line");
+    assert!(
+        rendered[overrides].contains(
+            "model gpt-5-codex, effort high, approval never, sandbox danger-full-access, MCP off: docs, search"
+        ),
+        "got: {}",
+        rendered[overrides]
+    );
+    let file_name = workspace_state_path(&config.codex_home, &config.cwd)
+        .file_name()
+        .expect("state file name")
+        .to_string_lossy()
+        .to_string();
+    assert!(
+        rendered[overrides + 1].contains("from ") && rendered[overrides + 1].contains(&file_name),
+        "got: {}",
+        rendered[overrides + 1]
+    );
+}
+
+#[tokio::test]
+async fn status_shows_recorded_workspace_usage() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.cwd = PathBuf::from("/workspace/tests");
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let render = |config: &Config| {
+        let composite = new_status_output(
+            config,
+            None,
+            &auth_manager,
+            &usage,
+            Some(&usage),
+            &None,
+            None,
+            None,
+            None,
+            &[],
+            chrono::Local::now(),
+        );
+        render_lines(&composite.display_lines(200))
+    };
+
+    let rendered = render(&config);
+    assert!(
+        rendered
+            .iter()
+            .all(|line| !line.contains("Workspace usage:")),
+        "no workspace usage before any session ended, got: {rendered:?}"
+    );
+
+    let session = TokenUsage {
+        input_tokens: 900_000,
+        cached_input_tokens: 0,
+        output_tokens: 100_000,
+        reasoning_output_tokens: 0,
+        total_tokens: 1_000_000,
+    };
+    let price = ModelPrice {
+        input: 1.0,
+        cached_input: None,
+        output: 10.0,
+    };
+    record_session_usage(&config.codex_home, &config.cwd, &session, Some(&price))
+        .await
+        .expect("record usage");
+    record_session_usage(&config.codex_home, &config.cwd, &session, None)
+        .await
+        .expect("record usage");
+
+    let rendered = render(&config);
+    let line = rendered
+        .iter()
+        .find(|line| line.contains("Workspace usage:"))
+        .expect("workspace usage line");
+    assert!(
+        line.contains("2M tokens over 2 sessions (~$1.90)"),
+        "got: {line}"
+    );
+}
+
+#[test]
+fn width_truncation_keeps_graphemes_whole() {
+    assert_eq!(prefix_within_width("漢字テスト", 5), "漢字");
+    assert_eq!(prefix_within_width("cafe\u{301}s", 4), "cafe\u{301}");
+    assert_eq!(truncate_to_width("漢字テスト", 10), "漢字テスト");
+    assert_eq!(truncate_to_width("漢字テスト", 7), "漢字テ…");
+    assert_eq!(truncate_to_width("漢字テスト", 0), "");
+
+    let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+    assert_eq!(prefix_within_width(&format!("ab{family}"), 3), "ab");
+
+    let line = Line::from(vec![Span::raw("ab"), Span::raw("漢字")]);
+    let truncated = truncate_line_to_width(line, 5);
+    let text: String = truncated
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect();
+    assert_eq!(text, "ab漢");
+}
+
+#[test]
+fn watch_reads_the_latest_turn_and_token_count_from_the_rollout() {
+    let temp_home = TempDir::new().expect("temp home");
+    let config = test_config(&temp_home);
+    let auth_manager = test_auth_manager(&config);
+    let line = |item: RolloutItem| {
+        serde_json::to_string(&RolloutLine {
+            timestamp: "2024-01-02T03:04:05.000Z".to_string(),
+            item,
+        })
+        .expect("serialize rollout line")
+    };
+    let turn = |model: &str| {
+        RolloutItem::TurnContext(TurnContextItem {
+            cwd: PathBuf::from("/workspace/tests"),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            model: model.to_string(),
+            effort: Some(ReasoningEffort::High),
+            summary: ReasoningSummary::Auto,
+        })
+    };
+    let usage = TokenUsage {
+        input_tokens: 1_200,
+        output_tokens: 300,
+        total_tokens: 1_500,
+        ..TokenUsage::default()
+    };
+    let rollout = [
+        line(turn("gpt-5")),
+        line(turn("gpt-5-codex")),
+        line(RolloutItem::EventMsg(EventMsg::TokenCount(
+            TokenCountEvent {
+                info: Some(TokenUsageInfo {
+                    total_token_usage: usage.clone(),
+                    last_token_usage: usage,
+                    model_context_window: Some(272_000),
+                }),
+                rate_limits: None,
+            },
+        ))),
+        // A line still being written.
+        "{\"timestamp\":\"2024-01-02T03".to_string(),
+    ]
+    .join("\n");
+
+    let session = parse_rollout(&rollout);
+    assert_eq!(
+        session
+            .turn_context
+            .as_ref()
+            .map(|context| context.model.as_str()),
+        Some("gpt-5-codex")
+    );
+    assert_eq!(
+        session
+            .token_info
+            .as_ref()
+            .map(|info| info.total_token_usage.output_tokens),
+        Some(300)
+    );
+
+    let git = GitInfo {
+        commit_hash: Some("1f2e3d4c5b6a".to_string()),
+        branch: Some("main".to_string()),
+        repository_url: None,
+    };
+    let rendered = render_lines(&status_lines(
+        &config,
+        None,
+        &auth_manager,
+        &session,
+        None,
+        Some(&git),
+        80,
+        chrono::Local::now(),
+    ));
+    assert!(
+        rendered.iter().any(|line| line.contains("gpt-5-codex")),
+        "got: {rendered:?}"
+    );
+    assert!(
+        rendered.iter().any(|line| line.contains("1.5K total")),
+        "got: {rendered:?}"
+    );
+    assert_eq!(
+        rendered.last().map(String::as_str),
+        Some(" Git: main @ 1f2e3d4")
+    );
+}
+
+#[tokio::test]
+async fn watch_reads_only_what_was_appended_to_the_rollout() {
+    use std::io::Write as _;
+
+    let dir = TempDir::new().expect("temp dir");
+    let path = dir.path().join("rollout.jsonl");
+    let line = |model: &str| {
+        let mut text = serde_json::to_string(&RolloutLine {
+            timestamp: "2024-01-02T03:04:05.000Z".to_string(),
+            item: RolloutItem::TurnContext(TurnContextItem {
+                cwd: PathBuf::from("/workspace/tests"),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::ReadOnly,
+                model: model.to_string(),
+                effort: None,
+                summary: ReasoningSummary::Auto,
+            }),
+        })
+        .expect("serialize rollout line");
+        text.push('\n');
+        text
+    };
+    let append = |text: &str| {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(text.as_bytes()))
+            .expect("append to rollout");
+    };
+    let model = |session: &WatchedSession| {
+        session
+            .turn_context
+            .as_ref()
+            .map(|context| context.model.clone())
+    };
+
+    let mut tail = RolloutTail::default();
+    append(&line("gpt-5"));
+    assert_eq!(
+        model(tail.read(&path).await.expect("read")),
+        Some("gpt-5".to_string())
+    );
+
+    // Half a line waits for the rest.
+    let next = line("gpt-5-codex");
+    let (head, rest) = next.split_at(next.len() / 2);
+    append(head);
+    assert_eq!(
+        model(tail.read(&path).await.expect("read")),
+        Some("gpt-5".to_string())
+    );
+    append(rest);
+    assert_eq!(
+        model(tail.read(&path).await.expect("read")),
+        Some("gpt-5-codex".to_string())
+    );
+
+    // A rewritten, shorter file is read again from the start.
+    std::fs::write(&path, line("o3")).expect("rewrite rollout");
+    assert_eq!(
+        model(tail.read(&path).await.expect("read")),
+        Some("o3".to_string())
+    );
+}
@@ -0,0 +1,194 @@
+//! Token counts as written by `/status`, the footer and the status line,
+//! following `[tui.token_format]`: compact (`12.3K`) or exact (`12,345`),
+//! with the thousands and decimal separators of the configured locale.
+//! Whoever draws a count carries a [`TokenCountFormatter`] built from the
+//! config it was given.
+
+use codex_core::config::types::TokenCountFormat;
+use codex_core::config::types::TokenCountStyle;
+
+/// Separators used when writing numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct NumberSeparators {
+    thousands: char,
+    decimal: char,
+}
+
+impl NumberSeparators {
+    /// `1,234.5`
+    const POINT: Self = Self {
+        thousands: ',',
+        decimal: '.',
+    };
+    /// `1.234,5`
+    const COMMA: Self = Self {
+        thousands: '.',
+        decimal: ',',
+    };
+    /// `1 234,5`, with a no-break space so counts never wrap.
+    const SPACE_COMMA: Self = Self {
+        thousands: '\u{a0}',
+        decimal: ',',
+    };
+
+    /// Separators for a locale such as `de`, `fr_CA` or `pt_BR.UTF-8`,
+    /// looked up by language; unknown languages, `C` and `POSIX` get
+    /// `1,234.5`.
+    fn for_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "sl" | "hr"
+            | "sr" | "vi" => Self::COMMA,
+            "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "nn" | "no" | "uk" | "hu"
+            | "bg" | "lt" | "lv" | "et" => Self::SPACE_COMMA,
+            _ => Self::POINT,
+        }
+    }
+}
+
+/// The default writes counts the `en` compact way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct TokenCountFormatter {
+    style: TokenCountStyle,
+    separators: NumberSeparators,
+}
+
+impl Default for TokenCountFormatter {
+    fn default() -> Self {
+        Self {
+            style: TokenCountStyle::Compact,
+            separators: NumberSeparators::POINT,
+        }
+    }
+}
+
+impl TokenCountFormatter {
+    /// The configured style and locale, falling back to `LC_ALL`,
+    /// `LC_NUMERIC` and `LANG` when no locale is set.
+    pub(crate) fn from_config(format: &TokenCountFormat) -> Self {
+        let locale = format.locale.clone().or_else(|| {
+            ["LC_ALL", "LC_NUMERIC", "LANG"]
+                .iter()
+                .filter_map(|name| std::env::var(name).ok())
+                .find(|value| !value.trim().is_empty())
+        });
+        Self {
+            style: format.style,
+            separators: locale
+                .as_deref()
+                .map_or(NumberSeparators::POINT, NumberSeparators::for_locale),
+        }
+    }
+
+    /// A token count in the configured format, e.g. `12.3K` or `12.345`.
+    pub(crate) fn format(&self, value: i64) -> String {
+        let value = value.max(0);
+        match self.style {
+            TokenCountStyle::Exact => group_thousands(&value.to_string(), self.separators),
+            TokenCountStyle::Compact => self.compact(value),
+        }
+    }
+
+    fn compact(&self, value: i64) -> String {
+        if value < 1_000 {
+            return value.to_string();
+        }
+
+        let value_f64 = value as f64;
+        let (scaled, suffix) = if value >= 1_000_000_000_000 {
+            (value_f64 / 1_000_000_000_000.0, "T")
+        } else if value >= 1_000_000_000 {
+            (value_f64 / 1_000_000_000.0, "B")
+        } else if value >= 1_000_000 {
+            (value_f64 / 1_000_000.0, "M")
+        } else {
+            (value_f64 / 1_000.0, "K")
+        };
+
+        let decimals = if scaled < 10.0 {
+            2
+        } else if scaled < 100.0 {
+            1
+        } else {
+            0
+        };
+
+        let formatted = format!("{scaled:.decimals$}");
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction.trim_end_matches('0')),
+            None => (formatted.as_str(), ""),
+        };
+        let mut text = group_thousands(integer, self.separators);
+        if !fraction.is_empty() {
+            text.push(self.separators.decimal);
+            text.push_str(fraction);
+        }
+        format!("{text}{suffix}")
+    }
+}
+
+/// `digits` with a thousands separator every three digits from the right.
+fn group_thousands(digits: &str, separators: NumberSeparators) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(separators.thousands);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn formatter(style: TokenCountStyle, locale: &str) -> TokenCountFormatter {
+        TokenCountFormatter::from_config(&TokenCountFormat {
+            style,
+            locale: Some(locale.to_string()),
+        })
+    }
+
+    #[test]
+    fn compact_counts_keep_significant_digits_in_every_locale() {
+        let en = formatter(TokenCountStyle::Compact, "en_US.UTF-8");
+        assert_eq!(en.format(-5), "0");
+        assert_eq!(en.format(999), "999");
+        assert_eq!(en.format(3_100), "3.1K");
+        assert_eq!(en.format(12_345), "12.3K");
+        assert_eq!(en.format(287_093), "287K");
+        assert_eq!(en.format(11_918_798), "11.9M");
+        assert_eq!(en.format(2_000_000_000), "2B");
+
+        let de = formatter(TokenCountStyle::Compact, "de_DE.UTF-8");
+        assert_eq!(de.format(3_100), "3,1K");
+        assert_eq!(de.format(11_918_798), "11,9M");
+        assert_eq!(
+            formatter(TokenCountStyle::Compact, "fr").format(1_234_000_000_000_000),
+            "1\u{a0}234T"
+        );
+    }
+
+    #[test]
+    fn exact_counts_group_thousands_by_locale() {
+        assert_eq!(formatter(TokenCountStyle::Exact, "C").format(999), "999");
+        assert_eq!(
+            formatter(TokenCountStyle::Exact, "en").format(1_234_567),
+            "1,234,567"
+        );
+        assert_eq!(
+            formatter(TokenCountStyle::Exact, "pt-BR").format(1_234_567),
+            "1.234.567"
+        );
+        assert_eq!(
+            formatter(TokenCountStyle::Exact, "sv_SE").format(12_345),
+            "12\u{a0}345"
+        );
+    }
+}
@@ -0,0 +1,352 @@
+//! `codex status`: the `/status` card for a recorded session, printed once or
+//! redrawn every few seconds with `--watch`, for following a headless
+//! `codex exec` run from another shell. Tokens, rate limits, model and
+//! directory come from the session's rollout file, 88code credits from the
+//! usage API and the git line from the session's directory.
+
+use std::io::IsTerminal;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::DateTime;
+use chrono::Local;
+use codex_core::AuthManager;
+use codex_core::RolloutRecorder;
+use codex_core::config::Config;
+use codex_core::find_conversation_path_by_id_str;
+use codex_core::git_info::collect_git_info;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::GitInfo;
+use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::RolloutItem;
+use codex_core::protocol::RolloutLine;
+use codex_core::protocol::TokenUsage;
+use codex_core::protocol::TokenUsageInfo;
+use codex_core::protocol::TurnContextItem;
+use codex_protocol::ConversationId;
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::terminal::Clear;
+use crossterm::terminal::ClearType;
+use ratatui::prelude::*;
+use ratatui::style::Stylize;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+
+use super::card::StatusHistoryCell;
+use super::format::FieldFormatter;
+use super::rate_limits::code88_rate_limit_display;
+use super::rate_limits::code88_rate_limit_snapshot;
+use super::rate_limits::rate_limit_snapshot_display;
+use crate::history_cell::HistoryCell;
+use crate::insert_history::write_spans;
+use crate::statusline::StatusLine88CodeSnapshot;
+use crate::statusline::code88_api::fetch_88code_aggregated;
+use crate::statusline::code88_api::usage_snapshot;
+use crate::statusline::usage_cache::UsageCache;
+
+/// Width used when stdout is not a terminal.
+const DEFAULT_WIDTH: u16 = 80;
+
+#[derive(Debug, Clone, Default)]
+pub struct StatusWatchOptions {
+    /// Session to show; the most recent one when unset.
+    pub session_id: Option<String>,
+    /// Redraw this often until interrupted; `None` prints the card once.
+    pub interval: Option<Duration>,
+    /// Commit this binary was built from, shown after the version.
+    pub build_sha: Option<&'static str>,
+}
+
+/// What the rollout file says about a session so far.
+#[derive(Debug, Default)]
+pub(super) struct WatchedSession {
+    pub id: Option<ConversationId>,
+    pub cwd: Option<PathBuf>,
+    /// Model and policies of the latest turn.
+    pub turn_context: Option<TurnContextItem>,
+    pub token_info: Option<TokenUsageInfo>,
+    /// Latest rate limits and when they were recorded.
+    pub rate_limits: Option<(RateLimitSnapshot, DateTime<Local>)>,
+}
+
+/// Print the status card for `options.session_id`, or the most recent
+/// session, then keep redrawing it while `options.interval` is set.
+pub async fn run_status_watch(config: Config, options: StatusWatchOptions) -> anyhow::Result<()> {
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    let mut stdout = std::io::stdout();
+    let redraw_in_place = options.interval.is_some() && stdout.is_terminal();
+    let mut pinned_path = None;
+    let mut tail = RolloutTail::default();
+    loop {
+        let path = match (pinned_path.clone(), options.session_id.as_deref()) {
+            (Some(path), _) => path,
+            (None, Some(id)) => {
+                let path = find_conversation_path_by_id_str(&config.codex_home, id)
+                    .await?
+                    .with_context(|| format!("no recorded session with id {id}"))?;
+                pinned_path = Some(path.clone());
+                path
+            }
+            (None, None) => latest_rollout(&config).await?,
+        };
+        let session = tail
+            .read(&path)
+            .await
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let git = match session.cwd.as_deref() {
+            Some(cwd) => collect_git_info(cwd).await,
+            None => None,
+        };
+        let code88 = fetch_code88(&config).await;
+        let width = crossterm::terminal::size().map_or(DEFAULT_WIDTH, |(width, _)| width);
+        let mut lines = status_lines(
+            &config,
+            options.build_sha,
+            &auth_manager,
+            session,
+            code88.as_ref(),
+            git.as_ref(),
+            width,
+            Local::now(),
+        );
+        if let Some(interval) = options.interval {
+            lines.push(footer(interval));
+        }
+
+        let mut out = stdout.lock();
+        if redraw_in_place {
+            queue!(out, MoveTo(0, 0), Clear(ClearType::All))?;
+        }
+        for line in &lines {
+            write_spans(&mut out, line.spans.iter())?;
+            writeln!(out)?;
+        }
+        out.flush()?;
+        drop(out);
+
+        let Some(interval) = options.interval else {
+            return Ok(());
+        };
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+}
+
+/// The most recently started session, from any source.
+async fn latest_rollout(config: &Config) -> anyhow::Result<PathBuf> {
+    let page = RolloutRecorder::list_conversations(
+        &config.codex_home,
+        1,
+        None,
+        &[],
+        None,
+        &config.model_provider_id,
+    )
+    .await?;
+    page.items
+        .into_iter()
+        .next()
+        .map(|item| item.path)
+        .with_context(|| {
+            format!(
+                "no recorded sessions under {}",
+                config.codex_home.join("sessions").display()
+            )
+        })
+}
+
+/// Usage from the 88code API when both the login token and API key are set.
+async fn fetch_code88(config: &Config) -> Option<StatusLine88CodeSnapshot> {
+    let login_token = config.code88_token_store().resolve_token()?.token;
+    let api_key = config.tui_code88_api_key.clone()?;
+    let usage = UsageCache::shared(config)
+        .get_or_fetch(&api_key, fetch_88code_aggregated(&login_token, &api_key))
+        .await;
+    Some(usage_snapshot(usage))
+}
+
+/// Read a rollout file. Lines that do not parse, such as one still being
+/// written, are skipped.
+pub(super) fn parse_rollout(text: &str) -> WatchedSession {
+    let mut session = WatchedSession::default();
+    for line in text.lines() {
+        session.apply_line(line);
+    }
+    session
+}
+
+impl WatchedSession {
+    fn apply_line(&mut self, line: &str) {
+        let Ok(line) = serde_json::from_str::<RolloutLine>(line) else {
+            return;
+        };
+        match line.item {
+            RolloutItem::SessionMeta(meta) if self.id.is_none() => {
+                self.id = Some(meta.meta.id);
+                self.cwd = Some(meta.meta.cwd);
+            }
+            RolloutItem::TurnContext(context) => {
+                self.turn_context = Some(context);
+            }
+            RolloutItem::EventMsg(EventMsg::TokenCount(event)) => {
+                if let Some(info) = event.info {
+                    self.token_info = Some(info);
+                }
+                if let Some(rate_limits) = event.rate_limits {
+                    let recorded_at = DateTime::parse_from_rfc3339(&line.timestamp)
+                        .map(|timestamp| timestamp.with_timezone(&Local))
+                        .unwrap_or_else(|_| Local::now());
+                    self.rate_limits = Some((rate_limits, recorded_at));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A rollout file followed across redraws: each read picks up only what was
+/// appended since the last one, so long sessions are not re-parsed every
+/// few seconds.
+#[derive(Debug, Default)]
+pub(super) struct RolloutTail {
+    path: Option<PathBuf>,
+    /// Bytes of `path` read so far.
+    offset: u64,
+    /// The end of the last read when it stopped inside a line.
+    partial: Vec<u8>,
+    session: WatchedSession,
+}
+
+impl RolloutTail {
+    /// The session after reading what was appended to `path`. Another path,
+    /// or a file shorter than what was already read, starts over.
+    pub(super) async fn read(&mut self, path: &Path) -> std::io::Result<&WatchedSession> {
+        let mut file = tokio::fs::File::open(path).await?;
+        let len = file.metadata().await?.len();
+        if self.path.as_deref() != Some(path) || len < self.offset {
+            *self = Self {
+                path: Some(path.to_path_buf()),
+                ..Self::default()
+            };
+        }
+        file.seek(SeekFrom::Start(self.offset)).await?;
+        let mut appended = Vec::new();
+        file.read_to_end(&mut appended).await?;
+        self.offset += u64::try_from(appended.len()).unwrap_or(u64::MAX);
+        self.partial.extend_from_slice(&appended);
+        if let Some(end) = self.partial.iter().rposition(|byte| *byte == b'\n') {
+            let complete: Vec<u8> = self.partial.drain(..=end).collect();
+            for line in String::from_utf8_lossy(&complete).lines() {
+                self.session.apply_line(line);
+            }
+        }
+        Ok(&self.session)
+    }
+}
+
+/// The card for `session`, followed by its git branch and commit.
+pub(super) fn status_lines(
+    config: &Config,
+    build_sha: Option<&str>,
+    auth_manager: &AuthManager,
+    session: &WatchedSession,
+    code88: Option<&StatusLine88CodeSnapshot>,
+    git: Option<&GitInfo>,
+    width: u16,
+    now: DateTime<Local>,
+) -> Vec<Line<'static>> {
+    let config = session_config(config, session);
+    let default_usage = TokenUsage::default();
+    let (total_usage, context_usage) = match session.token_info.as_ref() {
+        Some(info) => (&info.total_token_usage, &info.last_token_usage),
+        None => (&default_usage, &default_usage),
+    };
+    let rate_limits = match session.rate_limits.as_ref() {
+        Some((snapshot, recorded_at)) => Some(rate_limit_snapshot_display(snapshot, *recorded_at)),
+        None if config.model_provider.code88_auth => code88
+            .and_then(code88_rate_limit_snapshot)
+            .map(|snapshot| code88_rate_limit_display(&snapshot, now)),
+        None => None,
+    };
+    let card = StatusHistoryCell::new(
+        &config,
+        build_sha,
+        auth_manager,
+        total_usage,
+        Some(context_usage),
+        &session.id,
+        rate_limits.as_ref(),
+        None,
+        code88,
+        now,
+    );
+    let mut lines = card.display_lines(width);
+    if let Some(git) = git
+        && let Some(text) = git_text(git)
+    {
+        lines.push(Line::from(vec![
+            Span::from(format!("{}Git: ", FieldFormatter::INDENT)).dim(),
+            Span::from(text),
+        ]));
+    }
+    lines
+}
+
+/// `config` with the directory, model and policies the session ran with.
+fn session_config(config: &Config, session: &WatchedSession) -> Config {
+    let mut config = config.clone();
+    if let Some(cwd) = session.cwd.as_ref() {
+        config.cwd = cwd.clone();
+    }
+    if let Some(context) = session.turn_context.as_ref() {
+        config.cwd = context.cwd.clone();
+        config.model = context.model.clone();
+        config.model_reasoning_effort = context.effort;
+        config.approval_policy = context.approval_policy;
+        config.sandbox_policy = context.sandbox_policy.clone();
+    }
+    if let Some(window) = session
+        .token_info
+        .as_ref()
+        .and_then(|info| info.model_context_window)
+    {
+        config.model_context_window = Some(window);
+    }
+    config
+}
+
+/// `main @ 1f2e3d4`, or whichever half is known.
+fn git_text(git: &GitInfo) -> Option<String> {
+    let commit = git
+        .commit_hash
+        .as_deref()
+        .map(|hash| hash.chars().take(7).collect::<String>());
+    match (git.branch.as_deref(), commit) {
+        (Some(branch), Some(commit)) => Some(format!("{branch} @ {commit}")),
+        (Some(branch), None) => Some(branch.to_string()),
+        (None, Some(commit)) => Some(format!("detached @ {commit}")),
+        (None, None) => None,
+    }
+}
+
+fn footer(interval: Duration) -> Line<'static> {
+    Line::from(
+        format!(
+            "{}Refreshing every {}s · ctrl + c to stop",
+            FieldFormatter::INDENT,
+            interval.as_secs().max(1)
+        )
+        .dim(),
+    )
+}
@@ -14,6 +14,7 @@ use ratatui::widgets::WidgetRef;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::exec_cell::SpinnerStyle;
 use crate::exec_cell::spinner;
 use crate::key_hint;
 use crate::render::renderable::Renderable;
@@ -31,6 +32,7 @@ pub(crate) struct StatusIndicatorWidget {
     app_event_tx: AppEventSender,
     frame_requester: FrameRequester,
     animations_enabled: bool,
+    spinner_style: SpinnerStyle,
 }
 
 // Format elapsed seconds into a compact human-friendly form used by the status line.
@@ -66,9 +68,16 @@ impl StatusIndicatorWidget {
             app_event_tx,
             frame_requester,
             animations_enabled,
+            spinner_style: SpinnerStyle::default(),
         }
     }
 
+    /// Draw the spinner with `style` instead of the default dot.
+    pub(crate) fn with_spinner_style(mut self, style: SpinnerStyle) -> Self {
+        self.spinner_style = style;
+        self
+    }
+
     pub(crate) fn interrupt(&self) {
         self.app_event_tx.send(AppEvent::CodexOp(Op::Interrupt));
     }
@@ -152,7 +161,11 @@ impl Renderable for StatusIndicatorWidget {
         let pretty_elapsed = fmt_elapsed_compact(elapsed_duration.as_secs());
 
         let mut spans = Vec::with_capacity(5);
-        spans.push(spinner(Some(self.last_resume_at), self.animations_enabled));
+        spans.push(spinner(
+            &self.spinner_style,
+            Some(self.last_resume_at),
+            self.animations_enabled,
+        ));
         spans.push(" ".into());
         if self.animations_enabled {
             spans.extend(shimmer_spans(&self.header));
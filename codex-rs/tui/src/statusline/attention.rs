@@ -0,0 +1,267 @@
+//! Attention segment: warnings worth keeping in view after they scroll out
+//! of the transcript (core warnings, rejected sign-ins, rate limits nearly
+//! used up, commands run outside the sandbox, command prefixes approved to
+//! run without asking). Core raises most of them as `StructuredWarning`
+//! events; the TUI adds its own for 88code and the skin. The segment
+//! shows the most severe color, a count badge and the latest message; the
+//! full list opens on `ctrl + g`.
+
+use codex_core::protocol::StructuredWarningEvent;
+use codex_core::protocol::WarningKind;
+use codex_core::protocol::WarningSeverity;
+use serde::Serialize;
+
+use super::StatusLineAttentionSnapshot;
+
+/// Entries kept; the oldest are dropped first.
+const MAX_ATTENTION_ENTRIES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AttentionSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// What raised an entry. Sources that describe an ongoing condition update
+/// their entry in place instead of piling up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AttentionSource {
+    /// A `Warning` event from core.
+    Core,
+    /// Sign-in rejected or a token refresh failed.
+    Auth,
+    /// Rate limit usage past the warning thresholds.
+    RateLimit,
+    /// A command prefix approved to run without asking again.
+    Approval,
+    /// A command approved to run outside the sandbox.
+    Sandbox,
+    /// The configured skin panicked and was replaced by `powerline`.
+    Skin,
+}
+
+impl AttentionSeverity {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            AttentionSeverity::Info => "info",
+            AttentionSeverity::Warning => "warning",
+            AttentionSeverity::Critical => "critical",
+        }
+    }
+}
+
+impl AttentionSource {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            AttentionSource::Core => "codex",
+            AttentionSource::Auth => "sign-in",
+            AttentionSource::RateLimit => "rate limit",
+            AttentionSource::Approval => "approval",
+            AttentionSource::Sandbox => "sandbox",
+            AttentionSource::Skin => "skin",
+        }
+    }
+
+    fn updates_in_place(self) -> bool {
+        matches!(self, AttentionSource::Auth | AttentionSource::RateLimit)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct Attention {
+    pub source: AttentionSource,
+    pub severity: AttentionSeverity,
+    pub message: String,
+}
+
+impl Attention {
+    pub(crate) fn new(
+        source: AttentionSource,
+        severity: AttentionSeverity,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            source,
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<StructuredWarningEvent> for Attention {
+    fn from(event: StructuredWarningEvent) -> Self {
+        let source = match event.kind {
+            WarningKind::Auth => AttentionSource::Auth,
+            WarningKind::RateLimit => AttentionSource::RateLimit,
+            WarningKind::SandboxEscalation => AttentionSource::Sandbox,
+        };
+        let severity = match event.severity {
+            WarningSeverity::Info => AttentionSeverity::Info,
+            WarningSeverity::Warning => AttentionSeverity::Warning,
+            WarningSeverity::Critical => AttentionSeverity::Critical,
+        };
+        Attention::new(source, severity, event.message)
+    }
+}
+
+/// Entries raised this session, oldest first.
+#[derive(Debug, Default)]
+pub(crate) struct AttentionLog {
+    entries: Vec<Attention>,
+}
+
+impl AttentionLog {
+    /// Add `attention`. A repeated message moves to the end rather than
+    /// counting twice.
+    pub(crate) fn push(&mut self, attention: Attention) {
+        if attention.source.updates_in_place()
+            && let Some(entry) = self
+                .entries
+                .iter_mut()
+                .find(|entry| entry.source == attention.source)
+        {
+            *entry = attention;
+            return;
+        }
+        self.entries
+            .retain(|entry| entry.message != attention.message);
+        self.entries.push(attention);
+        let overflow = self.entries.len().saturating_sub(MAX_ATTENTION_ENTRIES);
+        self.entries.drain(..overflow);
+    }
+
+    /// Drop the entries from `source`, e.g. once the rate limit resets.
+    /// Returns whether anything was removed.
+    pub(crate) fn clear_source(&mut self, source: AttentionSource) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.source != source);
+        self.entries.len() != before
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn entries(&self) -> &[Attention] {
+        &self.entries
+    }
+
+    pub(crate) fn snapshot(&self) -> Option<StatusLineAttentionSnapshot> {
+        let latest = self.entries.last()?;
+        Some(StatusLineAttentionSnapshot {
+            severity: self
+                .entries
+                .iter()
+                .map(|entry| entry.severity)
+                .max()
+                .unwrap_or(latest.severity),
+            count: self.entries.len(),
+            latest: latest.message.clone(),
+        })
+    }
+}
+
+/// Attention for rate limit usage: a warning from 75% of either window, a
+/// critical entry from 95%. `None` below both.
+pub(crate) fn rate_limit_attention(used_percent: f64, window: &str) -> Option<Attention> {
+    let severity = if used_percent >= 95.0 {
+        AttentionSeverity::Critical
+    } else if used_percent >= 75.0 {
+        AttentionSeverity::Warning
+    } else {
+        return None;
+    };
+    Some(Attention::new(
+        AttentionSource::RateLimit,
+        severity,
+        format!("{used_percent:.0}% of the {window} rate limit used"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn warning(message: &str) -> Attention {
+        Attention::new(AttentionSource::Core, AttentionSeverity::Warning, message)
+    }
+
+    #[test]
+    fn snapshot_counts_entries_and_takes_the_worst_severity() {
+        let mut log = AttentionLog::default();
+        assert_eq!(log.snapshot(), None);
+
+        log.push(rate_limit_attention(97.0, "5h").expect("critical"));
+        log.push(warning("snapshot skipped"));
+        log.push(warning("snapshot skipped"));
+        assert_eq!(
+            log.snapshot(),
+            Some(StatusLineAttentionSnapshot {
+                severity: AttentionSeverity::Critical,
+                count: 2,
+                latest: "snapshot skipped".to_string(),
+            })
+        );
+
+        log.push(rate_limit_attention(80.0, "weekly").expect("warning"));
+        assert_eq!(
+            log.entries()
+                .iter()
+                .map(|entry| entry.message.as_str())
+                .collect::<Vec<_>>(),
+            vec!["80% of the weekly rate limit used", "snapshot skipped"]
+        );
+        assert!(log.clear_source(AttentionSource::RateLimit));
+        assert!(!log.clear_source(AttentionSource::RateLimit));
+        assert_eq!(rate_limit_attention(60.0, "5h"), None);
+    }
+
+    #[test]
+    fn core_warnings_keep_their_kind_and_severity() {
+        let mut log = AttentionLog::default();
+        log.push(Attention::from(StructuredWarningEvent {
+            kind: WarningKind::RateLimit,
+            severity: WarningSeverity::Warning,
+            message: "80% of the 5h rate limit used".to_string(),
+        }));
+        log.push(Attention::from(StructuredWarningEvent {
+            kind: WarningKind::RateLimit,
+            severity: WarningSeverity::Critical,
+            message: "96% of the 5h rate limit used".to_string(),
+        }));
+        log.push(Attention::from(StructuredWarningEvent {
+            kind: WarningKind::SandboxEscalation,
+            severity: WarningSeverity::Warning,
+            message: "`cargo test` is running outside the sandbox".to_string(),
+        }));
+        assert_eq!(
+            log.entries(),
+            &[
+                Attention::new(
+                    AttentionSource::RateLimit,
+                    AttentionSeverity::Critical,
+                    "96% of the 5h rate limit used",
+                ),
+                Attention::new(
+                    AttentionSource::Sandbox,
+                    AttentionSeverity::Warning,
+                    "`cargo test` is running outside the sandbox",
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn oldest_entries_are_dropped_past_the_cap() {
+        let mut log = AttentionLog::default();
+        for index in 0..MAX_ATTENTION_ENTRIES + 3 {
+            log.push(warning(&format!("warning {index}")));
+        }
+        assert_eq!(log.entries().len(), MAX_ATTENTION_ENTRIES);
+        assert_eq!(log.entries()[0].message, "warning 3");
+    }
+}
@@ -0,0 +1,40 @@
+//! Entry points for the status line criterion benchmarks
+//! (`cargo bench -p codex-tui --features bench`).
+
+use std::time::Instant;
+
+use ratatui::text::Line;
+
+use super::StatusLineSnapshot;
+use super::fixtures;
+use super::render_status_line;
+use super::render_status_run_pill;
+
+/// A status line with every segment populated, so narrow widths exercise
+/// the whole degrade sequence.
+pub struct BenchStatusLine {
+    snapshot: StatusLineSnapshot,
+    now: Instant,
+}
+
+impl BenchStatusLine {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        let snapshot = fixtures::full(now);
+        Self { snapshot, now }
+    }
+
+    pub fn render_line(&self, width: u16) -> Line<'static> {
+        render_status_line(&self.snapshot, width, self.now)
+    }
+
+    pub fn render_run_pill(&self, width: u16) -> Line<'static> {
+        render_status_run_pill(&self.snapshot, width, self.now)
+    }
+}
+
+impl Default for BenchStatusLine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,273 @@
+//! Fluent construction of status line snapshots for tests, registered skins
+//! and `codex statusline preview`. Every field starts out as in the `busy`
+//! fixture, so a test only spells out what it cares about.
+
+use std::path::Path;
+use std::time::Instant;
+
+use ratatui::text::Line;
+
+use super::StatusLineContextSnapshot;
+use super::StatusLineGitSnapshot;
+use super::StatusLineModelSnapshot;
+use super::StatusLineProviderSnapshot;
+use super::StatusLineSnapshot;
+use super::fixtures;
+use super::skins::SkinSnapshot;
+use super::skins::StatusLineSkin;
+
+#[cfg(test)]
+impl StatusLineSnapshot {
+    /// A builder starting from the `busy` fixture at the current instant.
+    pub(crate) fn builder() -> StatusLineSnapshotBuilder {
+        StatusLineSnapshotBuilder::new()
+    }
+}
+
+/// Builds the snapshot a status line renders, starting from the `busy`
+/// fixture: a paused `Applying patch` turn with two queued messages on
+/// `gpt-5-codex`.
+#[derive(Debug, Clone)]
+pub struct StatusLineSnapshotBuilder {
+    snapshot: StatusLineSnapshot,
+    now: Instant,
+}
+
+impl StatusLineSnapshotBuilder {
+    pub fn new() -> Self {
+        Self::at(Instant::now())
+    }
+
+    /// Like [`Self::new`], with timers and animations anchored at `now`.
+    pub fn at(now: Instant) -> Self {
+        Self {
+            snapshot: fixtures::busy(now),
+            now,
+        }
+    }
+
+    /// Working directory as displayed; the basename is derived from it.
+    pub fn cwd(mut self, display: impl Into<String>) -> Self {
+        let display = display.into();
+        let basename = Path::new(&display)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| display.clone());
+        self.snapshot.cwd_display = Some(display);
+        self.snapshot.cwd_basename = Some(basename.clone());
+        self.snapshot.cwd_fallback = Some(basename);
+        self
+    }
+
+    /// Model label; the reasoning detail is kept.
+    pub fn model(mut self, label: impl Into<String>) -> Self {
+        let model = self
+            .snapshot
+            .model
+            .get_or_insert_with(StatusLineModelSnapshot::default);
+        model.label = label.into();
+        self
+    }
+
+    /// Reasoning effort shown next to the model, or `None` to hide it.
+    pub fn reasoning(mut self, detail: Option<&str>) -> Self {
+        let model = self
+            .snapshot
+            .model
+            .get_or_insert_with(StatusLineModelSnapshot::default);
+        model.detail = detail.map(str::to_string);
+        self
+    }
+
+    pub fn provider(mut self, name: impl Into<String>, profile: Option<&str>) -> Self {
+        self.snapshot.provider = Some(StatusLineProviderSnapshot {
+            name: name.into(),
+            profile: profile.map(str::to_string),
+        });
+        self
+    }
+
+    /// Git branch and worktree state, with no ahead/behind counts.
+    pub fn git(mut self, branch: impl Into<String>, dirty: bool) -> Self {
+        self.snapshot.git = Some(StatusLineGitSnapshot {
+            branch: Some(branch.into()),
+            dirty,
+            ahead: None,
+            behind: None,
+            base: None,
+            pull_request: None,
+            checkout: None,
+            detached: None,
+        });
+        self
+    }
+
+    /// Commits ahead of and behind upstream. Has no effect without
+    /// [`Self::git`] state.
+    pub fn git_ahead_behind(mut self, ahead: i64, behind: i64) -> Self {
+        if let Some(git) = self.snapshot.git.as_mut() {
+            git.ahead = Some(ahead);
+            git.behind = Some(behind);
+        }
+        self
+    }
+
+    /// Outside a git repository.
+    pub fn no_git(mut self) -> Self {
+        self.snapshot.git = None;
+        self
+    }
+
+    /// Share of the context window left, clamped to 100.
+    pub fn context(mut self, percent_remaining: u8) -> Self {
+        let percent_remaining = percent_remaining.min(100);
+        let context = self
+            .snapshot
+            .context
+            .get_or_insert_with(|| StatusLineContextSnapshot {
+                percent_remaining,
+                tokens_in_context: 0,
+                window: 160_000,
+                low_since: None,
+                suggest_compact: false,
+            });
+        context.percent_remaining = percent_remaining;
+        context.tokens_in_context = context.window * i64::from(100 - percent_remaining) / 100;
+        self
+    }
+
+    /// Between turns: no run state.
+    pub fn idle(mut self) -> Self {
+        self.snapshot.run_state = None;
+        self
+    }
+
+    /// Label of the current turn. Has no effect once [`Self::idle`].
+    pub fn run_label(mut self, label: impl Into<String>) -> Self {
+        if let Some(run_state) = self.snapshot.run_state.as_mut() {
+            run_state.label = label.into();
+        }
+        self
+    }
+
+    /// Messages queued behind the current turn.
+    pub fn queued_messages<I, S>(mut self, messages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        if let Some(run_state) = self.snapshot.run_state.as_mut() {
+            run_state.queued_messages = messages.into_iter().map(Into::into).collect();
+        }
+        self
+    }
+
+    pub fn pending_approvals(mut self, count: i64) -> Self {
+        if let Some(run_state) = self.snapshot.run_state.as_mut() {
+            run_state.pending_approvals = count;
+        }
+        self
+    }
+
+    pub fn hostname(mut self, hostname: Option<&str>) -> Self {
+        self.snapshot.environment.hostname = hostname.map(str::to_string);
+        self
+    }
+
+    pub fn clock(mut self, clock: Option<&str>) -> Self {
+        self.snapshot.clock = clock.map(str::to_string);
+        self
+    }
+
+    pub fn session(mut self, session: Option<&str>) -> Self {
+        self.snapshot.session = session.map(str::to_string);
+        self
+    }
+
+    /// Version and commit of the build, e.g. `0.58.0+1a2b3c4`.
+    pub fn build(mut self, build: Option<&str>) -> Self {
+        self.snapshot.build = build.map(str::to_string);
+        self
+    }
+
+    /// Version of a newer release, shown as a chip next to the build.
+    pub fn update(mut self, update: Option<&str>) -> Self {
+        self.snapshot.update = update.map(str::to_string);
+        self
+    }
+
+    /// Text pushed over the control socket.
+    pub fn custom(mut self, text: Option<&str>) -> Self {
+        self.snapshot.custom = text.map(str::to_string);
+        self
+    }
+
+    /// The snapshot as a registered skin receives it.
+    pub fn skin_snapshot(&self) -> SkinSnapshot<'_> {
+        SkinSnapshot::new(&self.snapshot, self.now)
+    }
+
+    /// Render `skin`'s status line for this snapshot at `width` columns.
+    pub fn render(&self, skin: &dyn StatusLineSkin, width: u16) -> Line<'static> {
+        skin.render(&self.skin_snapshot(), width)
+    }
+
+    pub(crate) fn build(self) -> StatusLineSnapshot {
+        self.snapshot
+    }
+}
+
+impl Default for StatusLineSnapshotBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::render_status_line;
+    use crate::statusline::tests::sample_snapshot;
+    use pretty_assertions::assert_eq;
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn defaults_match_the_sample_snapshot() {
+        let now = Instant::now();
+        let built = StatusLineSnapshotBuilder::at(now).build();
+        assert_eq!(
+            text(&render_status_line(&built, 200, now)),
+            text(&render_status_line(&sample_snapshot(), 200, now))
+        );
+    }
+
+    #[test]
+    fn fluent_setters_shape_the_snapshot() {
+        let snapshot = StatusLineSnapshot::builder()
+            .cwd("~/src/widgets")
+            .model("gpt-5")
+            .reasoning(None)
+            .git("main", false)
+            .context(25)
+            .idle()
+            .build();
+
+        assert_eq!(snapshot.cwd_basename.as_deref(), Some("widgets"));
+        let model = snapshot.model.expect("model");
+        assert_eq!((model.label.as_str(), model.detail), ("gpt-5", None));
+        let git = snapshot.git.expect("git");
+        assert_eq!((git.branch.as_deref(), git.dirty), (Some("main"), false));
+        let context = snapshot.context.expect("context");
+        assert_eq!(
+            (context.percent_remaining, context.tokens_in_context),
+            (25, 120_000)
+        );
+        assert!(snapshot.run_state.is_none());
+    }
+}
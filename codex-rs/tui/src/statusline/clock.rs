@@ -0,0 +1,126 @@
+//! Optional clock segment: the wall clock formatted with
+//! `tui.statusline.clock_format`, or how long ago the session started.
+//! Both only change once a minute, so callers schedule a single frame for
+//! the next minute boundary instead of running a timer.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::Timelike;
+use chrono::format::Item;
+use chrono::format::StrftimeItems;
+use codex_core::config::types::StatusLineClockStyle;
+use codex_core::config::types::StatusLineSettings;
+
+const DEFAULT_CLOCK_FORMAT: &str = "%H:%M";
+const MINUTE: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub(crate) struct ClockSettings {
+    format: String,
+    style: StatusLineClockStyle,
+}
+
+impl ClockSettings {
+    pub(crate) fn from_config(settings: &StatusLineSettings) -> Self {
+        let valid = StrftimeItems::new(&settings.clock_format).all(|item| item != Item::Error);
+        let format = if valid {
+            settings.clock_format.clone()
+        } else {
+            tracing::warn!(
+                "invalid tui.statusline.clock_format `{}`; using `{DEFAULT_CLOCK_FORMAT}`",
+                settings.clock_format
+            );
+            DEFAULT_CLOCK_FORMAT.to_string()
+        };
+        Self {
+            format,
+            style: settings.clock_style,
+        }
+    }
+
+    /// Segment text at `now`, and how long until it next changes.
+    pub(crate) fn text(
+        &self,
+        session_started_at: Instant,
+        now: Instant,
+        wall: DateTime<Local>,
+    ) -> (String, Duration) {
+        match self.style {
+            StatusLineClockStyle::Time => {
+                let into_minute = Duration::from_secs(u64::from(wall.second()))
+                    + Duration::from_nanos(u64::from(wall.nanosecond() % 1_000_000_000));
+                (
+                    wall.format(&self.format).to_string(),
+                    MINUTE.saturating_sub(into_minute),
+                )
+            }
+            StatusLineClockStyle::SessionAge => {
+                let elapsed = now.saturating_duration_since(session_started_at);
+                let into_minute =
+                    Duration::from_nanos((elapsed.as_nanos() % MINUTE.as_nanos()) as u64);
+                (
+                    session_age_text(elapsed),
+                    MINUTE.saturating_sub(into_minute),
+                )
+            }
+        }
+    }
+}
+
+/// `started 1h 05m ago`, rounded down to the minute.
+fn session_age_text(elapsed: Duration) -> String {
+    let minutes = elapsed.as_secs() / 60;
+    match minutes {
+        0 => "started just now".to_string(),
+        1..60 => format!("started {minutes}m ago"),
+        _ => format!("started {}h {:02}m ago", minutes / 60, minutes % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    fn clock(format: &str, style: StatusLineClockStyle) -> ClockSettings {
+        ClockSettings::from_config(&StatusLineSettings {
+            clock_format: format.to_string(),
+            clock_style: style,
+            ..StatusLineSettings::default()
+        })
+    }
+
+    #[test]
+    fn time_style_formats_wall_clock_until_next_minute() {
+        let now = Instant::now();
+        let wall = Local
+            .with_ymd_and_hms(2026, 3, 14, 9, 26, 45)
+            .single()
+            .expect("unambiguous local time");
+        let (text, wait) = clock("%H:%M", StatusLineClockStyle::Time).text(now, now, wall);
+        assert_eq!(text, "09:26");
+        assert_eq!(wait, Duration::from_secs(15));
+
+        // An invalid format falls back to the default instead of panicking.
+        let (text, _) = clock("%H:%Q", StatusLineClockStyle::Time).text(now, now, wall);
+        assert_eq!(text, "09:26");
+    }
+
+    #[test]
+    fn session_age_rounds_down_to_minutes() {
+        let clock = clock(DEFAULT_CLOCK_FORMAT, StatusLineClockStyle::SessionAge);
+        let started = Instant::now();
+        let wall = Local::now();
+        let (text, wait) = clock.text(started, started + Duration::from_secs(20), wall);
+        assert_eq!(text, "started just now");
+        assert_eq!(wait, Duration::from_secs(40));
+        let (text, _) = clock.text(started, started + Duration::from_secs(5 * 60 + 59), wall);
+        assert_eq!(text, "started 5m ago");
+        let (text, _) = clock.text(started, started + Duration::from_secs(65 * 60), wall);
+        assert_eq!(text, "started 1h 05m ago");
+    }
+}
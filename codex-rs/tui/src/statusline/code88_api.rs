@@ -6,16 +6,26 @@
 //! Supported API endpoints:
 //! - GET /admin-api/login/getLoginInfo - Get user info and service tier
 //! - GET /admin-api/cc-admin/user/dashboard - Get today's usage, tokens, and cost
+//! - GET /admin-api/cc-admin/system/subscription/my - Get subscriptions and credits
 
+use chrono::DateTime;
+use chrono::Utc;
 use lazy_static::lazy_static;
 use serde::Deserialize;
+use serde::Serialize;
 use std::time::Duration;
+use std::time::Instant;
 use tracing::warn;
 
+use super::StatusLine88CodeSnapshot;
+
 /// API endpoint for login info (includes service tier).
 const LOGIN_INFO_API_URL: &str = "https://www.88code.org/admin-api/login/getLoginInfo";
 /// API endpoint for user dashboard (includes today's usage and cost).
 const DASHBOARD_API_URL: &str = "https://www.88code.org/admin-api/cc-admin/user/dashboard";
+/// API endpoint for the user's subscriptions (includes credits and limit).
+const SUBSCRIPTION_API_URL: &str =
+    "https://www.88code.org/admin-api/cc-admin/system/subscription/my";
 
 /// Request timeout in seconds.
 const TIMEOUT_SECS: u64 = 10;
@@ -71,8 +81,30 @@ pub(crate) struct RecentActivity {
     pub cost: Option<f64>,
 }
 
+/// Response for GET /admin-api/cc-admin/system/subscription/my.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SubscriptionResponse {
+    pub code: i32,
+    pub ok: bool,
+    pub data: Option<Vec<SubscriptionData>>,
+}
+
+/// One subscription plan with its remaining credits.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SubscriptionData {
+    /// Plan name (e.g., "PRO").
+    pub subscription_name: Option<String>,
+    /// Credits left in the current period.
+    pub current_credits: Option<f64>,
+    /// Credits granted per period.
+    pub credit_limit: Option<f64>,
+    /// False for expired or cancelled subscriptions.
+    pub is_active: Option<bool>,
+}
+
 /// Aggregated 88code usage data for display.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub(crate) struct Code88AggregatedData {
     /// Service tier (e.g., "LV5", "LV3", "LV1").
     pub service_tier: Option<String>,
@@ -90,13 +122,23 @@ pub(crate) struct Code88AggregatedData {
     pub cache_create_tokens: Option<i64>,
     /// Today's cache read tokens.
     pub cache_read_tokens: Option<i64>,
+    /// Name of the active subscription.
+    pub subscription_name: Option<String>,
+    /// Credits left on the active subscription.
+    pub credits_remaining: Option<f64>,
+    /// Credit limit of the active subscription.
+    pub credit_limit: Option<f64>,
+    /// When the API sent this data, for data from the usage cache; `None`
+    /// means just now.
+    #[serde(skip)]
+    pub fetched_at: Option<DateTime<Utc>>,
 }
 
 /// API error code indicating token expiration.
 const TOKEN_EXPIRED_CODE: i32 = 30007;
 
 /// Error types for 88code API requests.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum Code88Error {
     /// Network or connection error.
     Network(String),
@@ -123,6 +165,16 @@ impl Code88Error {
             _ => false,
         }
     }
+
+    /// Whether the same request may succeed if simply sent again: the
+    /// connection failed, or the server was overloaded or briefly down.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Code88Error::Network(_) => true,
+            Code88Error::HttpStatus(status) => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
 }
 
 impl std::fmt::Display for Code88Error {
@@ -242,6 +294,50 @@ pub(crate) async fn fetch_dashboard(login_token: &str) -> Result<DashboardData,
     }
 }
 
+/// Fetches the user's subscriptions (GET /admin-api/cc-admin/system/subscription/my).
+pub(crate) async fn fetch_subscriptions(
+    login_token: &str,
+) -> Result<Vec<SubscriptionData>, Code88Error> {
+    let client = get_client();
+
+    let response = client
+        .get(SUBSCRIPTION_API_URL)
+        .header("Authorization", format!("Bearer {login_token}"))
+        .header("Accept", "*/*")
+        .timeout(Duration::from_secs(TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| {
+            warn!("88code subscription API network error: {}", e);
+            Code88Error::Network(e.to_string())
+        })?;
+
+    check_response_status(response.status())?;
+
+    let body: SubscriptionResponse = response.json().await.map_err(|e| {
+        warn!("88code subscription API parse error: {}", e);
+        Code88Error::Parse(e.to_string())
+    })?;
+
+    if body.ok && body.code == 0 {
+        Ok(body.data.unwrap_or_default())
+    } else {
+        if body.code == TOKEN_EXPIRED_CODE {
+            warn!("88code subscription token expired");
+            return Err(Code88Error::TokenExpired);
+        }
+        warn!("88code subscription API error code: {}", body.code);
+        Err(Code88Error::ApiError(body.code))
+    }
+}
+
+/// Pick the subscription to display: the first one not marked inactive.
+fn active_subscription(subscriptions: Vec<SubscriptionData>) -> Option<SubscriptionData> {
+    subscriptions
+        .into_iter()
+        .find(|subscription| subscription.is_active != Some(false))
+}
+
 /// Convert account group code to display tier (e.g., "service_tier5" -> "LV5").
 pub(crate) fn parse_service_tier(account_group_code: &str) -> String {
     if let Some(num) = account_group_code.strip_prefix("service_tier") {
@@ -253,16 +349,20 @@ pub(crate) fn parse_service_tier(account_group_code: &str) -> String {
 
 /// Fetch all 88code data aggregated into a single structure.
 ///
-/// Uses only the dashboard API for today's usage data, plus login info for service tier.
+/// Uses the dashboard API for today's usage data, plus login info for service tier
+/// and the subscription API for credits. Only a dashboard failure is an error.
 /// Note: `api_key` is kept for API compatibility but currently unused.
 #[allow(unused_variables)]
 pub(crate) async fn fetch_88code_aggregated(
     login_token: &str,
     api_key: &str,
 ) -> Result<Code88AggregatedData, Code88Error> {
-    // Fetch login info and dashboard data concurrently
-    let (login_result, dashboard_result) =
-        tokio::join!(fetch_login_info(login_token), fetch_dashboard(login_token),);
+    // Fetch login info, dashboard and subscription data concurrently
+    let (login_result, dashboard_result, subscription_result) = tokio::join!(
+        fetch_login_info(login_token),
+        fetch_dashboard(login_token),
+        fetch_subscriptions(login_token),
+    );
 
     // Process login info for service tier
     let service_tier = match &login_result {
@@ -278,6 +378,9 @@ pub(crate) async fn fetch_88code_aggregated(
     let dashboard = dashboard_result?;
     let activity = dashboard.recent_activity.unwrap_or_default();
 
+    // Subscription data is optional; older accounts may not have any.
+    let subscription = subscription_result.ok().and_then(active_subscription);
+
     Ok(Code88AggregatedData {
         service_tier,
         daily_cost: activity.cost,
@@ -287,9 +390,58 @@ pub(crate) async fn fetch_88code_aggregated(
         output_tokens: activity.output_tokens_today,
         cache_create_tokens: activity.cache_create_tokens_today,
         cache_read_tokens: activity.cache_read_tokens_today,
+        subscription_name: subscription
+            .as_ref()
+            .and_then(|s| s.subscription_name.clone()),
+        credits_remaining: subscription.as_ref().and_then(|s| s.current_credits),
+        credit_limit: subscription.as_ref().and_then(|s| s.credit_limit),
+        fetched_at: None,
     })
 }
 
+/// What the status line and `/status` show for one fetch: the usage, or the
+/// error and whether signing in again would fix it.
+pub(crate) fn usage_snapshot(
+    result: Result<Code88AggregatedData, Code88Error>,
+) -> StatusLine88CodeSnapshot {
+    match result {
+        Ok(data) => StatusLine88CodeSnapshot {
+            service_tier: data.service_tier,
+            daily_cost: data.daily_cost,
+            daily_tokens: data.daily_tokens,
+            daily_requests: data.daily_requests,
+            input_tokens: data.input_tokens,
+            output_tokens: data.output_tokens,
+            cache_create_tokens: data.cache_create_tokens,
+            cache_read_tokens: data.cache_read_tokens,
+            subscription_name: data.subscription_name,
+            credits_remaining: data.credits_remaining,
+            credit_limit: data.credit_limit,
+            is_error: false,
+            error_msg: None,
+            token_expired: false,
+            stale: false,
+            fetched_at: Some(fetched_instant(data.fetched_at)),
+        },
+        Err(err) => StatusLine88CodeSnapshot {
+            is_error: true,
+            error_msg: Some(err.to_string()),
+            token_expired: err.is_token_expired(),
+            ..Default::default()
+        },
+    }
+}
+
+/// The [`Instant`] matching a wall-clock fetch time, so the stale age of
+/// cached data counts from when it was really fetched.
+fn fetched_instant(fetched_at: Option<DateTime<Utc>>) -> Instant {
+    let now = Instant::now();
+    fetched_at
+        .and_then(|fetched_at| (Utc::now() - fetched_at).to_std().ok())
+        .and_then(|age| now.checked_sub(age))
+        .unwrap_or(now)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -358,6 +510,36 @@ mod tests {
         assert!((activity.cost.unwrap() - 12.568282).abs() < 0.0001);
     }
 
+    #[test]
+    fn test_subscription_deserialize_skips_inactive() {
+        let json = r#"{
+            "code": 0,
+            "ok": true,
+            "data": [
+                {
+                    "subscriptionName": "FREE",
+                    "currentCredits": 0.0,
+                    "creditLimit": 5.0,
+                    "isActive": false
+                },
+                {
+                    "subscriptionName": "PRO",
+                    "currentCredits": 42.5,
+                    "creditLimit": 100.0,
+                    "isActive": true
+                }
+            ]
+        }"#;
+
+        let response: SubscriptionResponse = serde_json::from_str(json).unwrap();
+        assert!(response.ok);
+
+        let active = active_subscription(response.data.unwrap()).unwrap();
+        assert_eq!(active.subscription_name.as_deref(), Some("PRO"));
+        assert_eq!(active.current_credits, Some(42.5));
+        assert_eq!(active.credit_limit, Some(100.0));
+    }
+
     #[test]
     fn test_parse_service_tier() {
         assert_eq!(parse_service_tier("service_tier5"), "LV5");
@@ -375,6 +557,16 @@ mod tests {
         assert_eq!(Code88Error::TokenExpired.to_string(), "Token expired");
     }
 
+    #[test]
+    fn test_transient_errors() {
+        assert!(Code88Error::Network("reset".to_string()).is_transient());
+        assert!(Code88Error::HttpStatus(503).is_transient());
+        assert!(Code88Error::HttpStatus(429).is_transient());
+        assert!(!Code88Error::HttpStatus(401).is_transient());
+        assert!(!Code88Error::TokenExpired.is_transient());
+        assert!(!Code88Error::Parse("eof".to_string()).is_transient());
+    }
+
     #[test]
     fn test_token_expired_detection() {
         assert!(Code88Error::TokenExpired.is_token_expired());
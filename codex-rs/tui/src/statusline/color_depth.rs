@@ -0,0 +1,199 @@
+//! Color depth of the status line (`tui.statusline.color`). Skins always
+//! draw with the RGB palette; finished lines are mapped down afterwards so
+//! every skin, scripts included, degrades the same way.
+
+use codex_core::config::types::StatusLineColorMode;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+
+use super::palette::ANSI16_FALLBACKS;
+use crate::color::perceptual_distance;
+use crate::terminal_palette::XTERM_COLORS;
+use crate::terminal_palette::nearest_xterm_color;
+
+/// The 16 ANSI colors in xterm index order.
+const ANSI16: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    Monochrome,
+}
+
+impl ColorDepth {
+    pub(crate) fn from_config(mode: StatusLineColorMode) -> Self {
+        match mode {
+            StatusLineColorMode::Auto => Self::detect(
+                std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()),
+                supports_color::on_cached(supports_color::Stream::Stdout).map(|level| {
+                    if level.has_16m {
+                        Self::TrueColor
+                    } else if level.has_256 {
+                        Self::Ansi256
+                    } else {
+                        Self::Ansi16
+                    }
+                }),
+            ),
+            StatusLineColorMode::Truecolor => Self::TrueColor,
+            StatusLineColorMode::Ansi256 => Self::Ansi256,
+            StatusLineColorMode::Ansi16 => Self::Ansi16,
+            StatusLineColorMode::None => Self::Monochrome,
+        }
+    }
+
+    /// A terminal that reports nothing keeps the full palette, as it did
+    /// before color depth was configurable.
+    fn detect(no_color: bool, reported: Option<Self>) -> Self {
+        if no_color {
+            return Self::Monochrome;
+        }
+        reported.unwrap_or(Self::TrueColor)
+    }
+
+    pub(crate) fn apply(self, mut line: Line<'static>) -> Line<'static> {
+        if self == Self::TrueColor {
+            return line;
+        }
+        line.style = self.map_style(line.style);
+        for span in &mut line.spans {
+            span.style = self.map_style(span.style);
+        }
+        line
+    }
+
+    fn map_style(self, mut style: Style) -> Style {
+        match self {
+            Self::TrueColor => style,
+            // Reverse video keeps filled segments and capsules visible once
+            // their backgrounds are gone.
+            Self::Monochrome => {
+                if style.bg.is_some_and(|bg| bg != Color::Reset) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                style.fg = None;
+                style.bg = None;
+                style
+            }
+            Self::Ansi256 | Self::Ansi16 => {
+                style.fg = style.fg.map(|color| self.map_color(color));
+                style.bg = style.bg.map(|color| self.map_color(color));
+                style
+            }
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)]
+    fn map_color(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_xterm_color((r, g, b))),
+            (Self::Ansi16, Color::Rgb(r, g, b)) => ANSI16_FALLBACKS
+                .iter()
+                .find(|(rgb, _)| *rgb == color)
+                .map_or_else(|| nearest_ansi16((r, g, b)), |(_, ansi)| *ansi),
+            (Self::Ansi16, Color::Indexed(index)) => match ANSI16.get(usize::from(index)) {
+                Some(ansi) => *ansi,
+                None => nearest_ansi16(XTERM_COLORS[usize::from(index)]),
+            },
+            _ => color,
+        }
+    }
+}
+
+fn nearest_ansi16(target: (u8, u8, u8)) -> Color {
+    XTERM_COLORS[..16]
+        .iter()
+        .zip(ANSI16)
+        .min_by(|(a, _), (b, _)| {
+            perceptual_distance(**a, target)
+                .partial_cmp(&perceptual_distance(**b, target))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map_or(Color::Reset, |(_, ansi)| ansi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::LEFT_CURVE;
+    use crate::statusline::palette::BASE;
+    use crate::statusline::palette::LAVENDER;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Stylize;
+    use ratatui::text::Span;
+
+    fn segment() -> Line<'static> {
+        Line::from(vec![
+            Span::styled(LEFT_CURVE, Style::default().fg(LAVENDER)),
+            Span::styled(" codex ", Style::default().fg(BASE).bg(LAVENDER).bold()),
+        ])
+    }
+
+    #[test]
+    fn no_color_wins_over_terminal_support() {
+        assert_eq!(
+            ColorDepth::detect(true, Some(ColorDepth::TrueColor)),
+            ColorDepth::Monochrome
+        );
+        assert_eq!(
+            ColorDepth::detect(false, Some(ColorDepth::Ansi16)),
+            ColorDepth::Ansi16
+        );
+        assert_eq!(ColorDepth::detect(false, None), ColorDepth::TrueColor);
+    }
+
+    #[test]
+    fn lower_depths_replace_rgb_colors() {
+        let styles = |depth: ColorDepth| {
+            depth
+                .apply(segment())
+                .spans
+                .into_iter()
+                .map(|span| span.style)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            styles(ColorDepth::Ansi16),
+            vec![
+                Style::default().fg(Color::LightBlue),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::LightBlue)
+                    .bold(),
+            ]
+        );
+        assert!(
+            styles(ColorDepth::Ansi256)
+                .iter()
+                .flat_map(|style| [style.fg, style.bg])
+                .flatten()
+                .all(|color| matches!(color, Color::Indexed(_)))
+        );
+        assert_eq!(
+            styles(ColorDepth::Monochrome),
+            vec![Style::default(), Style::default().bold().reversed(),]
+        );
+        assert_eq!(ColorDepth::TrueColor.apply(segment()), segment());
+    }
+}
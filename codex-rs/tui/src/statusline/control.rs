@@ -0,0 +1,428 @@
+//! Opt-in control socket (`tui.statusline.control_socket`). External tools
+//! connect to a unix socket under `CODEX_HOME/statusline/` (a named pipe on
+//! Windows) and exchange newline-delimited JSON: Codex writes the current
+//! run state on connect and on every change, and clients may set the text of
+//! the `custom` segment, e.g. a build system reporting progress.
+//!
+//! Only the user running Codex may connect: the socket directory is `0700`
+//! and the socket `0600`; the Windows pipe rejects remote clients and keeps
+//! the default owner-only write access.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::runtime::Handle;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use super::DEFAULT_STATUS_MESSAGE;
+use super::StatusLineSnapshot;
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+/// Characters kept from text pushed for the custom segment.
+const MAX_SEGMENT_CHARS: usize = 64;
+
+/// Messages Codex writes to connected clients.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ControlEvent {
+    RunState {
+        phase: RunPhase,
+        label: String,
+        elapsed_secs: i64,
+        pending_approvals: i64,
+        queued_messages: i64,
+        /// Exit code of the last command that failed in this run.
+        failed_exit_code: Option<i32>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RunPhase {
+    Idle,
+    Running,
+    Paused,
+}
+
+/// Messages clients send to Codex.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ControlRequest {
+    SetSegment { text: String },
+    ClearSegment,
+}
+
+/// The run state as reported to clients. `task_running` separates a paused
+/// task from the idle pill, which also carries a paused timer.
+pub(crate) fn run_state_event(
+    snapshot: &StatusLineSnapshot,
+    task_running: bool,
+    now: Instant,
+) -> ControlEvent {
+    let run_state = snapshot.run_state.as_ref();
+    let timer = run_state.and_then(|state| state.timer.as_ref());
+    let phase = match (task_running, timer) {
+        (false, _) => RunPhase::Idle,
+        (true, Some(timer)) if timer.is_paused => RunPhase::Paused,
+        (true, _) => RunPhase::Running,
+    };
+    let label = run_state
+        .map(|state| state.label.trim())
+        .filter(|label| !label.is_empty())
+        .unwrap_or(DEFAULT_STATUS_MESSAGE);
+    ControlEvent::RunState {
+        phase,
+        label: label.to_string(),
+        elapsed_secs: timer
+            .map(|timer| i64::try_from(timer.elapsed_at(now).as_secs()).unwrap_or(i64::MAX))
+            .unwrap_or_default(),
+        pending_approvals: run_state.map_or(0, |state| state.pending_approvals),
+        queued_messages: run_state.map_or(0, |state| {
+            i64::try_from(state.queued_messages.len()).unwrap_or(i64::MAX)
+        }),
+        failed_exit_code: run_state
+            .and_then(|state| state.exec_failure.as_ref())
+            .map(|failure| failure.exit_code),
+    }
+}
+
+/// A listening control socket; stops accepting and removes the socket file
+/// when dropped.
+pub(crate) struct ControlSocket {
+    path: PathBuf,
+    /// The socket file this instance bound. `/new` starts the next overlay's
+    /// socket at the same path before this one is dropped, so the file is
+    /// only removed while it is still ours.
+    #[cfg(unix)]
+    bound: Option<SocketFileId>,
+    events: watch::Sender<String>,
+    server: JoinHandle<()>,
+}
+
+/// Device and inode of a socket file.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SocketFileId {
+    dev: u64,
+    ino: u64,
+}
+
+#[cfg(unix)]
+impl SocketFileId {
+    fn of(path: &Path) -> Option<Self> {
+        use std::os::unix::fs::MetadataExt;
+
+        let metadata = std::fs::symlink_metadata(path).ok()?;
+        Some(Self {
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+        })
+    }
+}
+
+impl ControlSocket {
+    /// Socket path for this process: `CODEX_HOME/statusline/<pid>.sock`, or
+    /// the `codex-statusline-<pid>` pipe on Windows.
+    pub(crate) fn path_for(codex_home: &Path) -> PathBuf {
+        let pid = std::process::id();
+        if cfg!(windows) {
+            PathBuf::from(format!(r"\\.\pipe\codex-statusline-{pid}"))
+        } else {
+            codex_home.join("statusline").join(format!("{pid}.sock"))
+        }
+    }
+
+    pub(crate) fn start(
+        handle: &Handle,
+        path: PathBuf,
+        initial: &ControlEvent,
+        app_event_tx: AppEventSender,
+    ) -> io::Result<Self> {
+        let (events, _) = watch::channel(encode(initial));
+        let _guard = handle.enter();
+        let server = listen(&path, events.clone(), app_event_tx)?;
+        tracing::info!("status line control socket listening on {}", path.display());
+        Ok(Self {
+            #[cfg(unix)]
+            bound: SocketFileId::of(&path),
+            path,
+            events,
+            server,
+        })
+    }
+
+    pub(crate) fn publish(&self, event: &ControlEvent) {
+        let line = encode(event);
+        self.events.send_if_modified(|current| {
+            if *current == line {
+                return false;
+            }
+            *current = line;
+            true
+        });
+    }
+}
+
+impl Drop for ControlSocket {
+    fn drop(&mut self) {
+        self.server.abort();
+        #[cfg(unix)]
+        if self.bound.is_some() && SocketFileId::of(&self.path) == self.bound {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn listen(
+    path: &Path,
+    events: watch::Sender<String>,
+    app_event_tx: AppEventSender,
+) -> io::Result<JoinHandle<()>> {
+    use std::os::unix::fs::DirBuilderExt;
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::net::UnixListener;
+
+    if let Some(dir) = path.parent() {
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)?;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    }
+    // A previous process with the same pid left its socket behind.
+    if let Err(err) = std::fs::remove_file(path)
+        && err.kind() != io::ErrorKind::NotFound
+    {
+        return Err(err);
+    }
+    let listener = UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve_client(
+                        stream,
+                        events.subscribe(),
+                        app_event_tx.clone(),
+                    ));
+                }
+                Err(err) => {
+                    tracing::warn!("status line control socket stopped: {err}");
+                    return;
+                }
+            }
+        }
+    }))
+}
+
+#[cfg(windows)]
+fn listen(
+    path: &Path,
+    events: watch::Sender<String>,
+    app_event_tx: AppEventSender,
+) -> io::Result<JoinHandle<()>> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let name = path.as_os_str().to_os_string();
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .reject_remote_clients(true)
+        .create(&name)?;
+    Ok(tokio::spawn(async move {
+        loop {
+            if let Err(err) = server.connect().await {
+                tracing::warn!("status line control pipe stopped: {err}");
+                return;
+            }
+            let next = match ServerOptions::new()
+                .reject_remote_clients(true)
+                .create(&name)
+            {
+                Ok(next) => next,
+                Err(err) => {
+                    tracing::warn!("status line control pipe stopped: {err}");
+                    return;
+                }
+            };
+            let client = std::mem::replace(&mut server, next);
+            tokio::spawn(serve_client(
+                client,
+                events.subscribe(),
+                app_event_tx.clone(),
+            ));
+        }
+    }))
+}
+
+/// Write the current run state, then every change, while applying the
+/// client's requests. Returns when either side hangs up.
+async fn serve_client<S>(
+    stream: S,
+    mut events: watch::Receiver<String>,
+    app_event_tx: AppEventSender,
+) where
+    S: AsyncRead + AsyncWrite,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    let mut outgoing = Some(events.borrow_and_update().clone());
+    loop {
+        if let Some(line) = outgoing.take()
+            && writer.write_all(line.as_bytes()).await.is_err()
+        {
+            return;
+        }
+        tokio::select! {
+            changed = events.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+                outgoing = Some(events.borrow_and_update().clone());
+            }
+            line = lines.next_line() => {
+                let Ok(Some(line)) = line else {
+                    return;
+                };
+                match handle_request(&line) {
+                    Ok(Some(event)) => app_event_tx.send(event),
+                    Ok(None) => {}
+                    Err(message) => outgoing = Some(encode(&ControlEvent::Error { message })),
+                }
+            }
+        }
+    }
+}
+
+/// The app event for one request line; blank lines are ignored.
+fn handle_request(line: &str) -> Result<Option<AppEvent>, String> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    let request: ControlRequest = serde_json::from_str(line).map_err(|err| err.to_string())?;
+    let text = match request {
+        ControlRequest::SetSegment { text } => sanitize_segment_text(&text),
+        ControlRequest::ClearSegment => None,
+    };
+    Ok(Some(AppEvent::StatusLineCustomSegment(text)))
+}
+
+/// Drop control characters, which could smuggle escape sequences into the
+/// terminal, and cap the length. Empty text clears the segment.
+fn sanitize_segment_text(text: &str) -> Option<String> {
+    let text: String = text
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_SEGMENT_CHARS)
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn encode(event: &ControlEvent) -> String {
+    let mut line = serde_json::to_string(event).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::tests::sample_snapshot;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn run_state_events_distinguish_idle_from_paused() {
+        let snapshot = sample_snapshot();
+        let now = Instant::now();
+        assert_eq!(
+            run_state_event(&snapshot, true, now),
+            ControlEvent::RunState {
+                phase: RunPhase::Paused,
+                label: "Applying patch".to_string(),
+                elapsed_secs: 125,
+                pending_approvals: 0,
+                queued_messages: 2,
+                failed_exit_code: None,
+            }
+        );
+        let failed = crate::statusline::fixtures::full(now);
+        let ControlEvent::RunState {
+            failed_exit_code, ..
+        } = run_state_event(&failed, true, now)
+        else {
+            panic!("expected a run state event");
+        };
+        assert_eq!(failed_exit_code, Some(101));
+        let ControlEvent::RunState { phase, .. } = run_state_event(&snapshot, false, now) else {
+            panic!("expected a run state event");
+        };
+        assert_eq!(phase, RunPhase::Idle);
+        assert_eq!(
+            encode(&ControlEvent::Error {
+                message: "bad".to_string()
+            }),
+            "{\"type\":\"error\",\"message\":\"bad\"}\n"
+        );
+    }
+
+    #[test]
+    fn requests_set_and_clear_the_custom_segment() {
+        let text = |line: &str| match handle_request(line) {
+            Ok(Some(AppEvent::StatusLineCustomSegment(text))) => text,
+            other => panic!("unexpected result for {line}: {other:?}"),
+        };
+        assert_eq!(
+            text(r#"{"type":"set_segment","text":"build 42%"}"#),
+            Some("build 42%".to_string())
+        );
+        assert_eq!(
+            text(r#"{"type":"set_segment","text":"\u001b[31mred\u001b[0m"}"#),
+            Some("[31mred[0m".to_string())
+        );
+        assert_eq!(text(r#"{"type":"set_segment","text":"  "}"#), None);
+        assert_eq!(text(r#"{"type":"clear_segment"}"#), None);
+        assert!(handle_request("   ").is_ok_and(|event| event.is_none()));
+        assert!(handle_request(r#"{"type":"reboot"}"#).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn dropping_a_replaced_socket_keeps_the_new_one() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = ControlSocket::path_for(dir.path());
+        let initial = run_state_event(&sample_snapshot(), false, Instant::now());
+        let start = || {
+            let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+            ControlSocket::start(
+                &Handle::current(),
+                path.clone(),
+                &initial,
+                AppEventSender::new(tx),
+            )
+            .expect("start control socket")
+        };
+
+        let old = start();
+        let new = start();
+        drop(old);
+        assert!(path.exists());
+        drop(new);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,247 @@
+//! `codex statusline doctor`: probe what the terminal can draw and explain
+//! which parts of the status line will degrade, with the settings to change.
+
+use std::io::IsTerminal;
+use std::io::Write;
+
+use anyhow::Context;
+use codex_core::config::types::StatusLineColorMode;
+use crossterm::cursor::MoveToColumn;
+use crossterm::terminal::Clear;
+use crossterm::terminal::ClearType;
+use unicode_width::UnicodeWidthStr;
+
+use super::GIT_ICON;
+use super::LEFT_CURVE;
+use super::MODEL_ICONS;
+use super::color_depth::ColorDepth;
+use crate::color::is_light;
+use crate::terminal_palette::default_bg;
+
+/// Glyphs drawn by the built-in skins, one per Nerd Font range they come
+/// from. Supplementary private-use icons are the first to go missing.
+fn glyph_samples() -> [(&'static str, String); 3] {
+    [
+        ("powerline separator", LEFT_CURVE.to_string()),
+        ("git icon", GIT_ICON.trim_end().to_string()),
+        ("model icon", MODEL_ICONS[0].to_string()),
+    ]
+}
+
+struct TerminalProbe {
+    /// Columns the cursor advanced for each of [`glyph_samples`], or `None`
+    /// when there was no interactive terminal to measure.
+    glyph_widths: Option<Vec<u16>>,
+    color_depth: ColorDepth,
+    colorterm: Option<String>,
+    background: Option<(u8, u8, u8)>,
+}
+
+impl TerminalProbe {
+    fn detect() -> Self {
+        let interactive = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+        let glyph_widths = if interactive {
+            measure_glyph_widths()
+                .inspect_err(|err| tracing::warn!("failed to measure glyph widths: {err}"))
+                .ok()
+        } else {
+            None
+        };
+        Self {
+            glyph_widths,
+            color_depth: ColorDepth::from_config(StatusLineColorMode::Auto),
+            colorterm: std::env::var("COLORTERM")
+                .ok()
+                .filter(|value| !value.is_empty()),
+            background: default_bg(),
+        }
+    }
+}
+
+fn measure_glyph_widths() -> std::io::Result<Vec<u16>> {
+    crossterm::terminal::enable_raw_mode()?;
+    let widths = glyph_samples()
+        .iter()
+        .map(|(_, glyph)| measure_width(glyph))
+        .collect();
+    crossterm::terminal::disable_raw_mode()?;
+    widths
+}
+
+/// Draw `glyph` at the start of the current line and ask the terminal where
+/// the cursor ended up, then erase it again.
+fn measure_width(glyph: &str) -> std::io::Result<u16> {
+    let mut stdout = std::io::stdout();
+    crossterm::queue!(stdout, MoveToColumn(0))?;
+    write!(stdout, "{glyph}")?;
+    stdout.flush()?;
+    let (column, _) = crossterm::cursor::position()?;
+    crossterm::execute!(stdout, MoveToColumn(0), Clear(ClearType::CurrentLine))?;
+    Ok(column)
+}
+
+/// Probe the terminal and write the report to `writer`.
+pub fn run_statusline_doctor(writer: &mut impl Write) -> anyhow::Result<()> {
+    let probe = TerminalProbe::detect();
+    write_report(writer, &probe).context("failed to write status line report")
+}
+
+fn write_report(writer: &mut impl Write, probe: &TerminalProbe) -> std::io::Result<()> {
+    let mut suggested: Vec<(&str, &str)> = Vec::new();
+
+    writeln!(writer, "Glyphs")?;
+    match &probe.glyph_widths {
+        Some(widths) => {
+            let mut misaligned = false;
+            for ((label, glyph), width) in glyph_samples().iter().zip(widths) {
+                let expected = glyph.width();
+                if usize::from(*width) == expected {
+                    writeln!(writer, "  {label:<20} {glyph}  ok")?;
+                } else {
+                    misaligned = true;
+                    writeln!(
+                        writer,
+                        "  {label:<20} {glyph}  drawn {width} columns wide, expected {expected}"
+                    )?;
+                }
+            }
+            if misaligned {
+                writeln!(
+                    writer,
+                    "  Segments after a misdrawn glyph shift sideways and the line can wrap."
+                )?;
+                suggested.push(("skin", "\"minimal-plain\""));
+            }
+        }
+        None => {
+            for (label, glyph) in glyph_samples() {
+                writeln!(writer, "  {label:<20} {glyph}")?;
+            }
+            writeln!(
+                writer,
+                "  Widths not measured: run this in the terminal you use Codex in."
+            )?;
+        }
+    }
+    writeln!(
+        writer,
+        "  Codex cannot see which glyphs your font has. If the samples show as boxes or\n  \
+         question marks, select a Nerd Font (https://www.nerdfonts.com) in your terminal\n  \
+         or set skin = \"minimal-plain\"."
+    )?;
+
+    writeln!(writer)?;
+    writeln!(writer, "Colors")?;
+    let colorterm = probe
+        .colorterm
+        .as_deref()
+        .map(|value| format!(" (COLORTERM={value})"))
+        .unwrap_or_default();
+    match probe.color_depth {
+        ColorDepth::TrueColor => writeln!(writer, "  24-bit{colorterm}  ok")?,
+        ColorDepth::Ansi256 => writeln!(
+            writer,
+            "  256 colors{colorterm}: the palette is mapped to the nearest xterm colors.\n  \
+             If your terminal does support 24-bit color, export COLORTERM=truecolor or set\n  \
+             color = \"truecolor\"."
+        )?,
+        ColorDepth::Ansi16 => writeln!(
+            writer,
+            "  16 colors{colorterm}: the palette is replaced by your terminal's ANSI colors."
+        )?,
+        ColorDepth::Monochrome => writeln!(
+            writer,
+            "  none (NO_COLOR is set): segments use bold, dim and reverse video only."
+        )?,
+    }
+
+    writeln!(writer)?;
+    writeln!(writer, "Background")?;
+    match probe.background {
+        Some(bg @ (r, g, b)) if is_light(bg) => {
+            writeln!(
+                writer,
+                "  light (#{r:02x}{g:02x}{b:02x}): the status line palette is made for dark\n  \
+                 backgrounds; 16-color mode uses your theme's colors instead."
+            )?;
+            if matches!(
+                probe.color_depth,
+                ColorDepth::TrueColor | ColorDepth::Ansi256
+            ) {
+                suggested.push(("color", "\"16\""));
+            }
+        }
+        Some((r, g, b)) => writeln!(writer, "  dark (#{r:02x}{g:02x}{b:02x})  ok")?,
+        None => writeln!(
+            writer,
+            "  unknown: the terminal did not report its background color. Codex assumes a\n  \
+             dark background and leaves your messages untinted."
+        )?,
+    }
+
+    writeln!(writer)?;
+    if suggested.is_empty() {
+        writeln!(writer, "No settings to change.")?;
+    } else {
+        writeln!(writer, "Suggested settings for config.toml:")?;
+        writeln!(writer)?;
+        writeln!(writer, "  [tui.statusline]")?;
+        for (key, value) in suggested {
+            writeln!(writer, "  {key} = {value}")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(probe: &TerminalProbe) -> String {
+        let mut out = Vec::new();
+        write_report(&mut out, probe).expect("report renders");
+        String::from_utf8(out).expect("utf-8 report")
+    }
+
+    #[test]
+    fn capable_terminal_needs_no_changes() {
+        let probe = TerminalProbe {
+            glyph_widths: Some(
+                glyph_samples()
+                    .iter()
+                    .map(|(_, glyph)| glyph.width() as u16)
+                    .collect(),
+            ),
+            color_depth: ColorDepth::TrueColor,
+            colorterm: Some("truecolor".into()),
+            background: Some((30, 30, 46)),
+        };
+        insta::assert_snapshot!("statusline_doctor_capable", report(&probe));
+    }
+
+    #[test]
+    fn degraded_terminal_suggests_settings() {
+        let probe = TerminalProbe {
+            glyph_widths: Some(vec![1, 2, 2]),
+            color_depth: ColorDepth::Ansi256,
+            colorterm: None,
+            background: Some((250, 250, 250)),
+        };
+        insta::assert_snapshot!("statusline_doctor_degraded", report(&probe));
+    }
+
+    #[test]
+    fn unmeasured_terminal_reports_unknowns() {
+        let probe = TerminalProbe {
+            glyph_widths: None,
+            color_depth: ColorDepth::Monochrome,
+            colorterm: None,
+            background: None,
+        };
+        let report = report(&probe);
+        assert!(report.contains("Widths not measured"), "{report}");
+        assert!(report.contains("NO_COLOR is set"), "{report}");
+        assert!(report.contains("did not report its background"), "{report}");
+        assert!(report.ends_with("No settings to change.\n"), "{report}");
+    }
+}
@@ -0,0 +1,325 @@
+//! Canned status line snapshots shared by the insta tests, the criterion
+//! benchmarks and `codex statusline preview`.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::config::types::StatusLineMaxWidths;
+
+use super::NixShell;
+use super::PolicyRisk;
+use super::RunCategory;
+use super::RunTimerSnapshot;
+use super::SegmentLayout;
+use super::StatusLineAttentionSnapshot;
+use super::StatusLineContextSnapshot;
+use super::StatusLineDevEnvSnapshot;
+use super::StatusLineDevspaceSnapshot;
+use super::StatusLineDiffStatsSnapshot;
+use super::StatusLineEnvironmentSnapshot;
+use super::StatusLineExecFailure;
+use super::StatusLineGitSnapshot;
+use super::StatusLineIacSnapshot;
+use super::StatusLineModelSnapshot;
+use super::StatusLinePolicySnapshot;
+use super::StatusLineProcessSnapshot;
+use super::StatusLineProviderSnapshot;
+use super::StatusLineRunState;
+use super::StatusLineSnapshot;
+use super::StatusLineSnapshotBuilder;
+use super::StatusLineTokenSnapshot;
+use super::TimerDisplay;
+use super::TokenCountSnapshot;
+use super::attention::AttentionSeverity;
+use super::iac::IacTool;
+use crate::status::TokenCountFormatter;
+
+/// Fixture names in presentation order, paired with a short description.
+pub(crate) const FIXTURES: &[(&str, &str)] = &[
+    (
+        "busy",
+        "A paused turn with queued messages (the snapshot tests' sample)",
+    ),
+    ("idle", "Between turns, showing how long the last turn took"),
+    (
+        "low-context",
+        "A running turn that is about to run out of context",
+    ),
+    ("full", "Every optional segment populated"),
+];
+
+/// Build the fixture called `name` relative to `now`. Returns `None` for
+/// unknown names.
+pub(crate) fn for_name(name: &str, now: Instant) -> Option<StatusLineSnapshot> {
+    match name {
+        "busy" => Some(busy(now)),
+        "idle" => Some(idle(now)),
+        "low-context" => Some(low_context(now)),
+        "full" => Some(full(now)),
+        _ => None,
+    }
+}
+
+/// A paused turn with two queued messages and no optional segments.
+pub(crate) fn busy(now: Instant) -> StatusLineSnapshot {
+    StatusLineSnapshot {
+        cwd_display: Some("~/workspace/codex".to_string()),
+        cwd_basename: Some("codex".to_string()),
+        cwd_fallback: Some("codex".to_string()),
+        model: Some(StatusLineModelSnapshot {
+            label: "gpt-5-codex".to_string(),
+            detail: Some("high".to_string()),
+            icon: None,
+            color: None,
+        }),
+        provider: None,
+        diff_stats: None,
+        custom: None,
+        attention: None,
+        budget: None,
+        clock: None,
+        session: None,
+        build: None,
+        update: None,
+        process: None,
+        tokens: Some(StatusLineTokenSnapshot {
+            total: TokenCountSnapshot {
+                total_tokens: 48_234,
+                input_tokens: 30_000,
+                cached_input_tokens: 8_000,
+                output_tokens: 18_234,
+                reasoning_output_tokens: 234,
+            },
+            last: Some(TokenCountSnapshot {
+                total_tokens: 2_345,
+                input_tokens: 1_200,
+                cached_input_tokens: 200,
+                output_tokens: 900,
+                reasoning_output_tokens: 45,
+            }),
+            output_tokens_per_sec: None,
+            last_turn_duration: None,
+        }),
+        context: Some(StatusLineContextSnapshot {
+            percent_remaining: 68,
+            tokens_in_context: 52_000,
+            window: 160_000,
+            low_since: None,
+            suggest_compact: false,
+        }),
+        run_state: Some(StatusLineRunState {
+            label: "Applying patch".to_string(),
+            spinner_started_at: None,
+            timer: Some(RunTimerSnapshot {
+                elapsed_running: Duration::from_secs(125),
+                last_resume_at: None,
+                is_paused: true,
+            }),
+            queued_messages: vec!["git status".to_string(), "cargo test --all".to_string()],
+            queue_preview_index: None,
+            pending_approvals: 0,
+            show_interrupt_hint: true,
+            paused: false,
+            summary: None,
+            category: RunCategory::Patch,
+            exec_failure: None,
+            status_changed_at: now,
+        }),
+        git: Some(StatusLineGitSnapshot {
+            branch: Some("feature/fix-tests".to_string()),
+            dirty: true,
+            ahead: Some(1),
+            behind: Some(0),
+            base: None,
+            pull_request: None,
+            checkout: None,
+            detached: None,
+        }),
+        policy: Some(StatusLinePolicySnapshot {
+            sandbox: "workspace-write".to_string(),
+            approval: "on-request".to_string(),
+            risk: PolicyRisk::Medium,
+        }),
+        environment: StatusLineEnvironmentSnapshot {
+            dev_env: None,
+            devspace: Some(StatusLineDevspaceSnapshot {
+                name: "earth".to_string(),
+                icon: None,
+                color: None,
+                description: None,
+            }),
+            hostname: Some("vermissian".to_string()),
+            host_color: None,
+            ssh: false,
+            aws_profile: Some("prod".to_string()),
+            iac: None,
+            kubernetes_context: Some("codex-dev".to_string()),
+            code88: None,
+        },
+        offline: false,
+        queued: 0,
+        pending_approvals: 0,
+        middle_queue: Vec::new(),
+        layout: SegmentLayout::default(),
+        timer_display: TimerDisplay::default(),
+        max_widths: StatusLineMaxWidths::default(),
+        token_format: TokenCountFormatter::default(),
+    }
+}
+
+/// [`busy`] after the turn finished.
+pub(crate) fn idle(now: Instant) -> StatusLineSnapshot {
+    let mut snapshot = StatusLineSnapshotBuilder::at(now).idle().build();
+    if let Some(tokens) = snapshot.tokens.as_mut() {
+        tokens.last_turn_duration = Some(Duration::from_secs(95));
+    }
+    snapshot
+}
+
+/// [`busy`] with a running timer and under 10% of the context left.
+pub(crate) fn low_context(now: Instant) -> StatusLineSnapshot {
+    let mut snapshot = busy(now);
+    snapshot.context = Some(StatusLineContextSnapshot {
+        percent_remaining: 6,
+        tokens_in_context: 150_400,
+        window: 160_000,
+        low_since: Some(now),
+        suggest_compact: true,
+    });
+    if let Some(run_state) = snapshot.run_state.as_mut() {
+        run_state.label = "Running cargo test -p codex-tui".to_string();
+        run_state.spinner_started_at = Some(now);
+        run_state.queued_messages.clear();
+        run_state.timer = Some(RunTimerSnapshot {
+            elapsed_running: Duration::from_secs(418),
+            last_resume_at: Some(now),
+            is_paused: false,
+        });
+    }
+    snapshot
+}
+
+/// Every segment populated, so narrow widths exercise the whole degrade
+/// sequence.
+pub(crate) fn full(now: Instant) -> StatusLineSnapshot {
+    StatusLineSnapshot {
+        cwd_display: Some("~/workspace/codex/codex-rs".to_string()),
+        cwd_basename: Some("codex-rs".to_string()),
+        cwd_fallback: Some("codex-rs".to_string()),
+        model: Some(StatusLineModelSnapshot {
+            label: "gpt-5-codex".to_string(),
+            detail: Some("high".to_string()),
+            icon: None,
+            color: None,
+        }),
+        provider: Some(StatusLineProviderSnapshot {
+            name: "Azure".to_string(),
+            profile: Some("work".to_string()),
+        }),
+        diff_stats: Some(StatusLineDiffStatsSnapshot {
+            added: 312,
+            removed: 87,
+        }),
+        custom: Some("build 42%".to_string()),
+        attention: Some(StatusLineAttentionSnapshot {
+            severity: AttentionSeverity::Warning,
+            count: 2,
+            latest: "80% of the 5h rate limit used".to_string(),
+        }),
+        budget: None,
+        clock: Some("09:26".to_string()),
+        session: Some("9f2c41d7".to_string()),
+        build: None,
+        update: None,
+        process: Some(StatusLineProcessSnapshot {
+            rss_bytes: 412 * 1024 * 1024,
+            cpu_percent: 3,
+        }),
+        tokens: Some(StatusLineTokenSnapshot {
+            total: TokenCountSnapshot {
+                total_tokens: 48_234,
+                input_tokens: 30_000,
+                cached_input_tokens: 8_000,
+                output_tokens: 18_234,
+                reasoning_output_tokens: 234,
+            },
+            last: None,
+            output_tokens_per_sec: Some(42.0),
+            last_turn_duration: None,
+        }),
+        context: Some(StatusLineContextSnapshot {
+            percent_remaining: 68,
+            tokens_in_context: 52_000,
+            window: 160_000,
+            low_since: None,
+            suggest_compact: false,
+        }),
+        run_state: Some(StatusLineRunState {
+            label: "Running cargo test -p codex-tui".to_string(),
+            spinner_started_at: Some(now),
+            timer: Some(RunTimerSnapshot {
+                elapsed_running: Duration::from_secs(125),
+                last_resume_at: Some(now),
+                is_paused: false,
+            }),
+            queued_messages: vec!["git status".to_string(), "cargo fmt".to_string()],
+            queue_preview_index: None,
+            pending_approvals: 1,
+            show_interrupt_hint: true,
+            paused: false,
+            summary: None,
+            category: RunCategory::Exec,
+            exec_failure: Some(StatusLineExecFailure {
+                exit_code: 101,
+                command: "cargo build".to_string(),
+                recent: false,
+            }),
+            status_changed_at: now,
+        }),
+        git: Some(StatusLineGitSnapshot {
+            branch: Some("feature/statusline-degrade".to_string()),
+            dirty: true,
+            ahead: Some(2),
+            behind: Some(1),
+            base: None,
+            pull_request: None,
+            checkout: None,
+            detached: None,
+        }),
+        policy: Some(StatusLinePolicySnapshot {
+            sandbox: "workspace-write".to_string(),
+            approval: "on-request".to_string(),
+            risk: PolicyRisk::Medium,
+        }),
+        environment: StatusLineEnvironmentSnapshot {
+            dev_env: Some(StatusLineDevEnvSnapshot {
+                nix: Some(NixShell::Impure),
+                direnv: true,
+            }),
+            devspace: Some(StatusLineDevspaceSnapshot {
+                name: "earth".to_string(),
+                icon: None,
+                color: None,
+                description: None,
+            }),
+            hostname: Some("build-host-01".to_string()),
+            host_color: Some("red".to_string()),
+            ssh: true,
+            aws_profile: Some("prod".to_string()),
+            iac: Some(StatusLineIacSnapshot {
+                tool: IacTool::Terraform,
+                workspace: "staging".to_string(),
+            }),
+            kubernetes_context: Some("codex-dev".to_string()),
+            code88: None,
+        },
+        offline: false,
+        queued: 0,
+        pending_approvals: 0,
+        middle_queue: Vec::new(),
+        layout: SegmentLayout::default(),
+        timer_display: TimerDisplay::default(),
+        max_widths: StatusLineMaxWidths::default(),
+        token_format: TokenCountFormatter::default(),
+    }
+}
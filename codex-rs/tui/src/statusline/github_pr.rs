@@ -0,0 +1,216 @@
+//! Open pull request for the current branch, looked up with the GitHub CLI.
+//! `gh` talks to the network, so results are cached per branch for several
+//! minutes; without `gh`, or outside a GitHub repository, the lookup quietly
+//! yields nothing.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use serde::Deserialize;
+use tokio::process::Command;
+
+use super::PullRequestChecks;
+use super::StatusLinePullRequestSnapshot;
+
+/// How long a lookup, including "no pull request", is reused.
+const PULL_REQUEST_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Upper bound on one `gh pr view` call, which may stall on the network.
+const GH_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Default)]
+pub(crate) struct PullRequestCache {
+    entry: Option<CachedPullRequest>,
+}
+
+#[derive(Debug)]
+struct CachedPullRequest {
+    branch: String,
+    fetched_at: Instant,
+    pull_request: Option<StatusLinePullRequestSnapshot>,
+}
+
+impl PullRequestCache {
+    fn get(&self, branch: &str, now: Instant) -> Option<Option<StatusLinePullRequestSnapshot>> {
+        self.entry
+            .as_ref()
+            .filter(|entry| {
+                entry.branch == branch
+                    && now.duration_since(entry.fetched_at) < PULL_REQUEST_CACHE_TTL
+            })
+            .map(|entry| entry.pull_request.clone())
+    }
+
+    fn insert(
+        &mut self,
+        branch: &str,
+        now: Instant,
+        pull_request: Option<StatusLinePullRequestSnapshot>,
+    ) {
+        self.entry = Some(CachedPullRequest {
+            branch: branch.to_string(),
+            fetched_at: now,
+            pull_request,
+        });
+    }
+}
+
+/// The open pull request for `branch`, from the cache when it is fresh.
+pub(crate) async fn current_pull_request(
+    cwd: &Path,
+    branch: &str,
+    cache: &Mutex<PullRequestCache>,
+) -> Option<StatusLinePullRequestSnapshot> {
+    if let Some(cached) = cache
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(branch, Instant::now()))
+    {
+        return cached;
+    }
+    let pull_request = fetch_pull_request(cwd).await;
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(branch, Instant::now(), pull_request.clone());
+    }
+    pull_request
+}
+
+async fn fetch_pull_request(cwd: &Path) -> Option<StatusLinePullRequestSnapshot> {
+    let output = Command::new("gh")
+        .args(["pr", "view", "--json", "number,state,statusCheckRollup"])
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(GH_TIMEOUT, output).await.ok()?.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_pull_request(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestView {
+    number: i64,
+    state: String,
+    #[serde(default)]
+    status_check_rollup: Vec<CheckView>,
+}
+
+/// A `CheckRun` (`status` / `conclusion`) or a commit `StatusContext`
+/// (`state`); fields of the other kind are absent.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CheckView {
+    status: Option<String>,
+    conclusion: Option<String>,
+    state: Option<String>,
+}
+
+impl CheckView {
+    fn failed(&self) -> bool {
+        matches!(
+            self.conclusion.as_deref(),
+            Some("FAILURE" | "TIMED_OUT" | "CANCELLED" | "ACTION_REQUIRED" | "STARTUP_FAILURE")
+        ) || matches!(self.state.as_deref(), Some("FAILURE" | "ERROR"))
+    }
+
+    fn pending(&self) -> bool {
+        self.status
+            .as_deref()
+            .is_some_and(|status| status != "COMPLETED")
+            || matches!(self.state.as_deref(), Some("PENDING" | "EXPECTED"))
+    }
+}
+
+/// Parses `gh pr view --json`; closed and merged pull requests count as none.
+fn parse_pull_request(json: &str) -> Option<StatusLinePullRequestSnapshot> {
+    let view: PullRequestView = serde_json::from_str(json).ok()?;
+    if view.state != "OPEN" {
+        return None;
+    }
+    let checks = &view.status_check_rollup;
+    let checks = if checks.is_empty() {
+        PullRequestChecks::None
+    } else if checks.iter().any(CheckView::failed) {
+        PullRequestChecks::Failing
+    } else if checks.iter().any(CheckView::pending) {
+        PullRequestChecks::Pending
+    } else {
+        PullRequestChecks::Passing
+    };
+    Some(StatusLinePullRequestSnapshot {
+        number: view.number,
+        checks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn checks(json: &str) -> Option<PullRequestChecks> {
+        parse_pull_request(json).map(|pull_request| pull_request.checks)
+    }
+
+    #[test]
+    fn parses_open_pull_request_and_rolls_up_checks() {
+        let pull_request = parse_pull_request(
+            r#"{"number":1234,"state":"OPEN","statusCheckRollup":[
+                {"__typename":"CheckRun","status":"COMPLETED","conclusion":"SUCCESS"},
+                {"__typename":"StatusContext","state":"SUCCESS"}]}"#,
+        )
+        .expect("open pull request");
+        assert_eq!(pull_request.number, 1234);
+        assert_eq!(pull_request.checks, PullRequestChecks::Passing);
+
+        assert_eq!(
+            checks(
+                r#"{"number":1,"state":"OPEN","statusCheckRollup":[
+                    {"status":"IN_PROGRESS","conclusion":""},
+                    {"state":"ERROR"}]}"#
+            ),
+            Some(PullRequestChecks::Failing)
+        );
+        assert_eq!(
+            checks(
+                r#"{"number":1,"state":"OPEN","statusCheckRollup":[
+                    {"status":"QUEUED","conclusion":""},
+                    {"status":"COMPLETED","conclusion":"SKIPPED"}]}"#
+            ),
+            Some(PullRequestChecks::Pending)
+        );
+        assert_eq!(
+            checks(r#"{"number":1,"state":"OPEN","statusCheckRollup":[]}"#),
+            Some(PullRequestChecks::None)
+        );
+    }
+
+    #[test]
+    fn ignores_closed_pull_requests_and_bad_output() {
+        assert_eq!(
+            parse_pull_request(r#"{"number":1,"state":"MERGED","statusCheckRollup":[]}"#),
+            None
+        );
+        assert_eq!(parse_pull_request("no pull requests found"), None);
+    }
+
+    #[test]
+    fn cache_is_per_branch_and_expires() {
+        let now = Instant::now();
+        let pull_request = StatusLinePullRequestSnapshot {
+            number: 7,
+            checks: PullRequestChecks::Pending,
+        };
+        let mut cache = PullRequestCache::default();
+        cache.insert("feature", now, Some(pull_request.clone()));
+        assert_eq!(cache.get("feature", now), Some(Some(pull_request)));
+        assert_eq!(cache.get("main", now), None);
+        assert_eq!(cache.get("feature", now + PULL_REQUEST_CACHE_TTL), None);
+    }
+}
@@ -0,0 +1,137 @@
+//! Infrastructure-as-code segment: the Terraform workspace or Pulumi stack
+//! commands in the working directory would act on, so a `terraform apply`
+//! against `prod` is visible before it runs.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::process::Command;
+use tokio::task::spawn_blocking;
+
+use super::StatusLineIacSnapshot;
+
+/// Upper bound on `pulumi stack --show-name`, which may reach the backend.
+const PULUMI_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum IacTool {
+    Terraform,
+    Pulumi,
+}
+
+impl IacTool {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            IacTool::Terraform => "terraform",
+            IacTool::Pulumi => "pulumi",
+        }
+    }
+}
+
+impl StatusLineIacSnapshot {
+    /// Whether the workspace looks like production and deserves a warning
+    /// color.
+    pub(crate) fn is_production(&self) -> bool {
+        self.workspace.to_ascii_lowercase().contains("prod")
+    }
+}
+
+/// Terraform first, from `TF_WORKSPACE` or `.terraform/environment`, then
+/// the selected stack of a Pulumi project. `None` outside either kind of
+/// project, or when the Pulumi CLI is missing.
+pub(crate) async fn detect_iac_workspace(
+    cwd: PathBuf,
+    command_env: &HashMap<String, String>,
+) -> Option<StatusLineIacSnapshot> {
+    let tf_workspace = command_env.get("TF_WORKSPACE").cloned();
+    let terraform_cwd = cwd.clone();
+    let terraform = spawn_blocking(move || detect_terraform(&terraform_cwd, tf_workspace))
+        .await
+        .ok()
+        .flatten();
+    if terraform.is_some() {
+        return terraform;
+    }
+    detect_pulumi(&cwd).await
+}
+
+fn detect_terraform(cwd: &Path, tf_workspace: Option<String>) -> Option<StatusLineIacSnapshot> {
+    let workspace = tf_workspace
+        .or_else(|| std::fs::read_to_string(cwd.join(".terraform/environment")).ok())?;
+    snapshot(IacTool::Terraform, &workspace)
+}
+
+async fn detect_pulumi(cwd: &Path) -> Option<StatusLineIacSnapshot> {
+    if !["Pulumi.yaml", "Pulumi.yml"]
+        .iter()
+        .any(|name| cwd.join(name).is_file())
+    {
+        return None;
+    }
+    let output = Command::new("pulumi")
+        .args(["stack", "--show-name", "--non-interactive"])
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(PULUMI_TIMEOUT, output)
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    snapshot(IacTool::Pulumi, &String::from_utf8_lossy(&output.stdout))
+}
+
+fn snapshot(tool: IacTool, workspace: &str) -> Option<StatusLineIacSnapshot> {
+    let workspace = workspace.trim();
+    (!workspace.is_empty()).then(|| StatusLineIacSnapshot {
+        tool,
+        workspace: workspace.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn terraform(workspace: &str) -> StatusLineIacSnapshot {
+        StatusLineIacSnapshot {
+            tool: IacTool::Terraform,
+            workspace: workspace.to_string(),
+        }
+    }
+
+    #[test]
+    fn terraform_workspace_comes_from_env_then_environment_file() {
+        let dir = tempdir().expect("tempdir");
+        assert_eq!(detect_terraform(dir.path(), None), None);
+
+        std::fs::create_dir(dir.path().join(".terraform")).expect("create .terraform");
+        std::fs::write(dir.path().join(".terraform/environment"), "staging\n")
+            .expect("write environment");
+        assert_eq!(
+            detect_terraform(dir.path(), None),
+            Some(terraform("staging"))
+        );
+        assert_eq!(
+            detect_terraform(dir.path(), Some("prod".to_string())),
+            Some(terraform("prod"))
+        );
+    }
+
+    #[test]
+    fn production_workspaces_are_flagged() {
+        assert!(terraform("prod").is_production());
+        assert!(terraform("eu-Production").is_production());
+        assert!(!terraform("staging").is_production());
+    }
+}
@@ -0,0 +1,274 @@
+//! Prometheus text exposition of the session, written to
+//! `tui.statusline.metrics_file` after every task so node_exporter's
+//! textfile collector can pick it up. Everything comes from what the status
+//! line already tracks: per-model token totals, finished tasks and the
+//! latest 88code poll. Every sample carries a `session` label, so a new
+//! session starts new series instead of making the counters go backwards.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_core::protocol::TokenUsage;
+use tokio::runtime::Handle;
+use tokio::sync::watch;
+use tokio::task::spawn_blocking;
+
+use super::StatusLine88CodeSnapshot;
+
+/// Tasks finished this session.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct TaskMetrics {
+    turns: i64,
+    task_seconds: f64,
+}
+
+impl TaskMetrics {
+    pub(crate) fn record(&mut self, elapsed: Duration) {
+        self.turns += 1;
+        self.task_seconds += elapsed.as_secs_f64();
+    }
+}
+
+/// The metrics file contents. `session_id` is empty until the session is
+/// configured.
+pub(crate) fn render_metrics(
+    session_id: &str,
+    tasks: &TaskMetrics,
+    model_tokens: &[(String, TokenUsage)],
+    code88: Option<&StatusLine88CodeSnapshot>,
+) -> String {
+    let session = escape_label(session_id);
+    let mut out = String::new();
+    header(
+        &mut out,
+        "codex_turns_total",
+        "counter",
+        "Turns finished this session.",
+    );
+    let _ = writeln!(
+        out,
+        "codex_turns_total{{session=\"{session}\"}} {}",
+        tasks.turns
+    );
+    header(
+        &mut out,
+        "codex_task_duration_seconds",
+        "summary",
+        "Wall time of the tasks finished this session.",
+    );
+    let _ = writeln!(
+        out,
+        "codex_task_duration_seconds_sum{{session=\"{session}\"}} {}",
+        tasks.task_seconds
+    );
+    let _ = writeln!(
+        out,
+        "codex_task_duration_seconds_count{{session=\"{session}\"}} {}",
+        tasks.turns
+    );
+    header(
+        &mut out,
+        "codex_tokens_total",
+        "counter",
+        "Tokens used this session, by model and kind.",
+    );
+    for (model, usage) in model_tokens {
+        let model = escape_label(model);
+        for (kind, count) in [
+            ("input", usage.input_tokens),
+            ("cached_input", usage.cached_input_tokens),
+            ("output", usage.output_tokens),
+            ("reasoning_output", usage.reasoning_output_tokens),
+        ] {
+            let _ = writeln!(
+                out,
+                "codex_tokens_total{{session=\"{session}\",model=\"{model}\",kind=\"{kind}\"}} {count}"
+            );
+        }
+    }
+    let Some(code88) = code88.filter(|code88| !code88.is_error && !code88.stale) else {
+        return out;
+    };
+    for (name, help, value) in [
+        (
+            "codex_88code_credits_remaining",
+            "Credits left on the active 88code subscription.",
+            code88.credits_remaining,
+        ),
+        (
+            "codex_88code_credit_limit",
+            "Credit limit of the active 88code subscription.",
+            code88.credit_limit,
+        ),
+        (
+            "codex_88code_daily_cost_usd",
+            "88code spend today, in USD.",
+            code88.daily_cost,
+        ),
+    ] {
+        if let Some(value) = value {
+            header(&mut out, name, "gauge", help);
+            let _ = writeln!(out, "{name}{{session=\"{session}\"}} {value}");
+        }
+    }
+    out
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Writes the metrics file on a blocking thread, so a slow or hung
+/// filesystem never stalls the UI. Only the latest contents are kept: writes
+/// that pile up behind a slow one are skipped.
+pub(crate) struct MetricsWriter {
+    path: PathBuf,
+    contents: watch::Sender<String>,
+}
+
+impl MetricsWriter {
+    /// Start the writer task. It finishes the pending write and exits once
+    /// the writer is dropped.
+    pub(crate) fn spawn(handle: &Handle, path: PathBuf) -> Self {
+        let (contents, mut rx) = watch::channel(String::new());
+        let task_path = path.clone();
+        handle.spawn(async move {
+            while rx.changed().await.is_ok() {
+                let contents = rx.borrow_and_update().clone();
+                let path = task_path.clone();
+                let result = spawn_blocking(move || write_metrics_file(&path, &contents)).await;
+                if let Ok(Err(err)) = result {
+                    tracing::warn!(
+                        "failed to write status line metrics to {}: {err}",
+                        task_path.display()
+                    );
+                }
+            }
+        });
+        Self { path, contents }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn write(&self, contents: String) {
+        self.contents.send_replace(contents);
+    }
+}
+
+/// Replace `path` in one step, so the collector never reads half a file.
+fn write_metrics_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn renders_turns_tokens_and_credits() {
+        let mut tasks = TaskMetrics::default();
+        tasks.record(Duration::from_millis(1500));
+        tasks.record(Duration::from_secs(3));
+        let tokens = vec![(
+            "gpt-5 \"beta\"".to_string(),
+            TokenUsage {
+                input_tokens: 1200,
+                cached_input_tokens: 200,
+                output_tokens: 300,
+                reasoning_output_tokens: 40,
+                total_tokens: 1500,
+            },
+        )];
+        let code88 = StatusLine88CodeSnapshot {
+            credits_remaining: Some(12.5),
+            ..StatusLine88CodeSnapshot::default()
+        };
+
+        let text = render_metrics("019a-session", &tasks, &tokens, Some(&code88));
+        let samples: Vec<&str> = text.lines().filter(|line| !line.starts_with('#')).collect();
+        assert_eq!(
+            samples,
+            vec![
+                "codex_turns_total{session=\"019a-session\"} 2",
+                "codex_task_duration_seconds_sum{session=\"019a-session\"} 4.5",
+                "codex_task_duration_seconds_count{session=\"019a-session\"} 2",
+                "codex_tokens_total{session=\"019a-session\",model=\"gpt-5 \\\"beta\\\"\",kind=\"input\"} 1200",
+                "codex_tokens_total{session=\"019a-session\",model=\"gpt-5 \\\"beta\\\"\",kind=\"cached_input\"} 200",
+                "codex_tokens_total{session=\"019a-session\",model=\"gpt-5 \\\"beta\\\"\",kind=\"output\"} 300",
+                "codex_tokens_total{session=\"019a-session\",model=\"gpt-5 \\\"beta\\\"\",kind=\"reasoning_output\"} 40",
+                "codex_88code_credits_remaining{session=\"019a-session\"} 12.5",
+            ]
+        );
+        assert!(text.contains("# TYPE codex_task_duration_seconds summary\n"));
+    }
+
+    #[test]
+    fn failed_88code_polls_are_left_out() {
+        let code88 = StatusLine88CodeSnapshot {
+            credits_remaining: Some(12.5),
+            is_error: true,
+            ..StatusLine88CodeSnapshot::default()
+        };
+        let text = render_metrics("", &TaskMetrics::default(), &[], Some(&code88));
+        assert!(!text.contains("88code"), "{text}");
+
+        let stale = StatusLine88CodeSnapshot {
+            is_error: false,
+            stale: true,
+            ..code88
+        };
+        let text = render_metrics("", &TaskMetrics::default(), &[], Some(&stale));
+        assert!(!text.contains("88code"), "{text}");
+    }
+
+    #[test]
+    fn write_replaces_the_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("textfile").join("codex.prom");
+        write_metrics_file(&path, "codex_turns_total 1\n").expect("first write");
+        write_metrics_file(&path, "codex_turns_total 2\n").expect("second write");
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("metrics file"),
+            "codex_turns_total 2\n"
+        );
+        assert!(!dir.path().join("textfile").join("codex.prom.tmp").exists());
+    }
+
+    #[tokio::test]
+    async fn writer_lands_the_latest_contents_after_it_is_dropped() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("codex.prom");
+        let writer = MetricsWriter::spawn(&Handle::current(), path.clone());
+        writer.write("codex_turns_total 1\n".to_string());
+        writer.write("codex_turns_total 2\n".to_string());
+        drop(writer);
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while std::fs::read_to_string(&path).ok().as_deref() != Some("codex_turns_total 2\n") {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "metrics never written"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
@@ -1,11 +1,25 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::str::FromStr;
 use std::time::Duration;
 use std::time::Instant;
 
+use crate::exec_cell::SpinnerStyle;
 use crate::exec_cell::spinner;
 use crate::key_hint;
+use crate::status::TokenCountFormatter;
 use crate::status::line_display_width;
 use crate::status::truncate_line_to_width;
+use crate::status::truncate_to_width;
+use codex_core::config::types::StatusLineMaxWidths;
+use codex_core::config::types::StatusLineSegmentId;
+use codex_core::config::types::StatusLineSegmentLayout;
+use codex_core::config::types::StatusLineSettings;
+use codex_core::config::types::StatusLineSide;
+use codex_core::config::types::StatusLineTimerFormat;
 use crossterm::event::KeyCode;
 use ratatui::style::Color;
 use ratatui::style::Modifier;
@@ -13,24 +27,54 @@ use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
-use unicode_segmentation::UnicodeSegmentation;
+use serde::Serialize;
 use unicode_width::UnicodeWidthStr;
 
+pub(crate) mod attention;
+#[cfg(feature = "bench")]
+pub mod bench;
+mod builder;
+mod clock;
 pub(crate) mod code88_api;
+pub(crate) mod color_depth;
+mod control;
+mod doctor;
+mod fixtures;
+mod github_pr;
+mod iac;
+mod metrics;
 mod overlay;
 mod palette;
+mod path;
+mod preview;
+mod process;
+mod providers;
+mod reachability;
+pub(crate) mod relogin;
+mod run_label;
+mod settings_reload;
 pub(crate) mod skins;
 pub(crate) mod state;
-
+mod timeline;
+pub(crate) mod usage_cache;
+mod usage_retry;
+
+pub use builder::StatusLineSnapshotBuilder;
+pub use doctor::run_statusline_doctor;
+pub(crate) use overlay::HostEnvironment;
+pub(crate) use overlay::RunPillPlacement;
 pub(crate) use overlay::StatusLineLayout;
 pub(crate) use overlay::StatusLineOverlay;
+pub use preview::write_statusline_preview;
 pub(crate) use skins::CustomStatusLineRenderer;
+pub use timeline::write_session_timeline;
 
 #[cfg(test)]
 pub(crate) use overlay::clear_devspace_override_for_tests;
 #[cfg(test)]
 pub(crate) use overlay::set_devspace_override_for_tests;
 
+use attention::AttentionSeverity;
 use palette::BASE;
 use palette::GREEN;
 use palette::GREEN_LIGHT;
@@ -46,6 +90,8 @@ use palette::SUBTEXT0;
 use palette::TEAL;
 use palette::YELLOW;
 use palette::YELLOW_LIGHT;
+use palette::attention_color;
+use palette::policy_color;
 use palette::queue_preview_style;
 
 const LEFT_CURVE: &str = "";
@@ -55,7 +101,26 @@ const RIGHT_CHEVRON: &str = "";
 const GIT_ICON: &str = " ";
 const AWS_ICON: &str = " ";
 const K8S_ICON: &str = "☸ ";
+const IAC_ICON: &str = "󱁢 ";
+const DEV_ENV_ICON: &str = "󱄅 ";
 const HOSTNAME_ICON: &str = " ";
+const SSH_ICON: &str = "󰣀 ";
+const POLICY_ICON: &str = " ";
+const PROVIDER_ICON: &str = " ";
+const PROCESS_ICON: &str = "󰍛 ";
+const BUDGET_ICON: &str = " ";
+/// Cells in the budget segment's bar.
+const BUDGET_BAR_WIDTH: i64 = 8;
+const CLOCK_ICON: &str = " ";
+const CUSTOM_ICON: &str = " ";
+const ATTENTION_ICON: &str = " ";
+const SESSION_ICON: &str = " ";
+const BUILD_ICON: &str = " ";
+const UPDATE_ICON: &str = " ";
+const RUN_SUMMARY_ICON: &str = "✓";
+const RUN_INTERRUPTED_ICON: &str = "⏹";
+const EXEC_FAILURE_ICON: &str = "✗";
+const PAUSED_ICON: &str = "⏸";
 const CONTEXT_ICON: &str = " ";
 const PROGRESS_LEFT_EMPTY: &str = "";
 const PROGRESS_MID_EMPTY: &str = "";
@@ -66,11 +131,30 @@ const PROGRESS_RIGHT_FULL: &str = "";
 const MODEL_ICONS: &[char] = &['󰚩', '󱚝', '󱚟', '󱚡', '󱚣', '󱚥'];
 const DEVSPACE_ICONS: &[&str] = &["󰠖 ", "󰠶 ", "󰋩 ", "󰚌 "];
 const CONTEXT_PADDING: usize = 4;
+const QUEUE_PREVIEW_WIDTH: usize = 32;
+/// Narrowest spare middle area that lists queued messages; anything less
+/// stays blank.
+const MIDDLE_QUEUE_MIN_WIDTH: usize = 12;
 const DEFAULT_STATUS_MESSAGE: &str = "Ready when you are";
+const PAUSED_STATUS_MESSAGE: &str = "Paused";
+const CONTEXT_WARNING_HINT: &str = "compact soon ";
+const CONTEXT_WARNING_PULSE: Duration = Duration::from_millis(500);
 
 pub(crate) trait StatusLineRenderer: std::fmt::Debug + Send + Sync {
     fn render(&self, snapshot: &StatusLineSnapshot, width: u16, now: Instant) -> Line<'static>;
 
+    /// [`Self::render`] with resize hysteresis from the caller's `memos`.
+    /// Skins without a degrade search ignore them.
+    fn render_banded(
+        &self,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+        _memos: &mut DegradeMemos,
+    ) -> Line<'static> {
+        self.render(snapshot, width, now)
+    }
+
     fn render_run_pill(
         &self,
         snapshot: &StatusLineSnapshot,
@@ -94,8 +178,16 @@ fn segment_fill(color: Color) -> Style {
     Style::default().fg(BASE).bg(color)
 }
 
-fn status_spinner(start_time: Option<Instant>) -> Span<'static> {
-    let mut span = spinner(start_time, true);
+/// The spinner, or a pause sign while the user has paused the task.
+fn run_spinner(style: &SpinnerStyle, state: &StatusLineRunState) -> Span<'static> {
+    if state.paused {
+        return PAUSED_ICON.dim();
+    }
+    status_spinner(style, state.spinner_started_at)
+}
+
+fn status_spinner(style: &SpinnerStyle, start_time: Option<Instant>) -> Span<'static> {
+    let mut span = spinner(style, start_time, true);
     if span.content.as_ref() == "•" {
         return "◦".dim();
     }
@@ -115,43 +207,224 @@ fn dim_text() -> Style {
     Style::default().fg(SUBTEXT0).add_modifier(Modifier::DIM)
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Hash, Serialize)]
 pub(crate) struct StatusLineSnapshot {
     pub cwd_display: Option<String>,
     pub cwd_basename: Option<String>,
     pub cwd_fallback: Option<String>,
     pub model: Option<StatusLineModelSnapshot>,
+    pub provider: Option<StatusLineProviderSnapshot>,
+    pub diff_stats: Option<StatusLineDiffStatsSnapshot>,
+    /// Text an external tool pushed over the control socket.
+    pub custom: Option<String>,
+    pub attention: Option<StatusLineAttentionSnapshot>,
+    pub budget: Option<StatusLineBudgetSnapshot>,
+    /// Clock segment text, refreshed once a minute while enabled.
+    pub clock: Option<String>,
+    /// Last characters of the session id; the full id stays in
+    /// `StatusLineState`.
+    pub session: Option<String>,
+    /// Version and short commit of this build, see `version::build_label`.
+    pub build: Option<String>,
+    /// Version of a newer release of the fork, from the opt-in release
+    /// check; shown as a chip next to the build.
+    pub update: Option<String>,
+    pub process: Option<StatusLineProcessSnapshot>,
     pub tokens: Option<StatusLineTokenSnapshot>,
     pub context: Option<StatusLineContextSnapshot>,
     pub run_state: Option<StatusLineRunState>,
+    /// The last probe of the model endpoint got no answer; see
+    /// `tui.statusline.reachability_probe_secs`.
+    pub offline: bool,
+    /// Queued messages, counted only while the run pill that previews them
+    /// is not rendered.
+    pub queued: i64,
+    /// Approval requests waiting on the user, counted only while the run
+    /// pill that warns about them is not rendered.
+    pub pending_approvals: i64,
+    /// The first `tui.statusline.middle_queue_max` queued messages, listed
+    /// in the middle of the status line when the context bar leaves room.
+    pub middle_queue: Vec<String>,
     pub git: Option<StatusLineGitSnapshot>,
+    pub policy: Option<StatusLinePolicySnapshot>,
     pub environment: StatusLineEnvironmentSnapshot,
+    /// Side and order of the powerline segments; render configuration
+    /// rather than state, so scripts do not see it.
+    #[serde(skip)]
+    pub layout: SegmentLayout,
+    /// How run timers and turn durations are written; render configuration
+    /// like `layout`.
+    #[serde(skip)]
+    pub timer_display: TimerDisplay,
+    /// Widest each text segment may get; render configuration like
+    /// `layout`.
+    #[serde(skip)]
+    pub max_widths: StatusLineMaxWidths,
+    /// How token counts are written (`[tui.token_format]`); render
+    /// configuration like `layout`.
+    #[serde(skip)]
+    pub token_format: TokenCountFormatter,
+    /// The run spinner (`[tui.spinner]`); render configuration like
+    /// `layout`.
+    #[serde(skip)]
+    pub spinner: SpinnerStyle,
+}
+
+/// Warnings raised this session: the most severe one sets the color, the
+/// latest one is shown next to the count.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct StatusLineAttentionSnapshot {
+    pub severity: AttentionSeverity,
+    pub count: usize,
+    pub latest: String,
+}
+
+/// Model provider the session talks to, and the config profile that chose it.
+#[derive(Debug, Clone, Hash, Serialize)]
+pub(crate) struct StatusLineProviderSnapshot {
+    pub name: String,
+    pub profile: Option<String>,
 }
 
-#[derive(Debug, Clone, Default)]
+/// Lines added and removed by the patches applied this session.
+#[derive(Debug, Clone, Default, Hash, Serialize)]
+pub(crate) struct StatusLineDiffStatsSnapshot {
+    pub added: i64,
+    pub removed: i64,
+}
+
+/// The session or workspace budget closest to its limit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct StatusLineBudgetSnapshot {
+    /// `session` or `workspace`.
+    pub scope: String,
+    /// Share of the limit used; past 100 once the user agreed to go over.
+    pub percent_used: i64,
+    /// `$4.20 of $5.00`
+    pub spend: String,
+}
+
+/// Footprint of the codex process, sampled by the overlay's poller.
+#[derive(Debug, Clone, Default, Hash, Serialize)]
+pub(crate) struct StatusLineProcessSnapshot {
+    pub rss_bytes: i64,
+    pub cpu_percent: i64,
+}
+
+/// Sandbox and approval policy in effect for the session.
+#[derive(Debug, Clone, Hash, Serialize)]
+pub(crate) struct StatusLinePolicySnapshot {
+    pub sandbox: String,
+    pub approval: String,
+    pub risk: PolicyRisk,
+}
+
+/// How much the agent may do without asking; drives the policy segment color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum PolicyRisk {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Default, Hash, Serialize)]
 pub(crate) struct StatusLineEnvironmentSnapshot {
+    /// Nix shell or direnv environment commands run in, if any.
+    pub dev_env: Option<StatusLineDevEnvSnapshot>,
     pub devspace: Option<StatusLineDevspaceSnapshot>,
+    /// Hostname, or its configured alias label.
     pub hostname: Option<String>,
+    /// Color configured for the host alias, as written in the config.
+    pub host_color: Option<String>,
+    /// Whether the session runs over SSH (`SSH_CONNECTION` is set).
+    pub ssh: bool,
     pub aws_profile: Option<String>,
+    pub iac: Option<StatusLineIacSnapshot>,
     pub kubernetes_context: Option<String>,
     pub code88: Option<StatusLine88CodeSnapshot>,
 }
 
-#[derive(Debug, Clone, Default)]
+/// Whether commands see a project-pinned toolchain, from `IN_NIX_SHELL` and
+/// `DIRENV_DIR` in the command environment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct StatusLineDevEnvSnapshot {
+    pub nix: Option<NixShell>,
+    pub direnv: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum NixShell {
+    Pure,
+    Impure,
+}
+
+/// Terraform workspace or Pulumi stack of the working directory.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct StatusLineIacSnapshot {
+    pub tool: iac::IacTool,
+    pub workspace: String,
+}
+
+#[derive(Debug, Clone, Default, Hash, Serialize)]
 pub(crate) struct StatusLineModelSnapshot {
     pub label: String,
     pub detail: Option<String>,
+    /// Icon from `tui.statusline.models`.
+    pub icon: Option<String>,
+    /// Color from `tui.statusline.models`, as written in the config.
+    pub color: Option<String>,
 }
 
-#[derive(Debug, Clone, Default)]
+impl StatusLineModelSnapshot {
+    /// The configured icon, or one picked from the model name.
+    fn icon(&self) -> String {
+        self.icon
+            .clone()
+            .unwrap_or_else(|| select_model_icon(&self.label).to_string())
+    }
+
+    /// The configured color when it parses, otherwise sky blue.
+    fn accent(&self) -> Color {
+        self.color
+            .as_deref()
+            .and_then(|color| Color::from_str(color).ok())
+            .unwrap_or(SKY)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub(crate) struct StatusLineTokenSnapshot {
     pub total: TokenCountSnapshot,
     #[allow(dead_code)]
     pub last: Option<TokenCountSnapshot>,
+    /// Output tokens per second across the running task, derived from the
+    /// token updates received since it started.
+    pub output_tokens_per_sec: Option<f64>,
+    /// Wall-clock duration of the last completed task.
+    #[serde(skip)]
+    pub last_turn_duration: Option<Duration>,
+}
+
+// Manual impl: `output_tokens_per_sec` is an `f64`.
+impl std::hash::Hash for StatusLineTokenSnapshot {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            total,
+            last,
+            output_tokens_per_sec,
+            last_turn_duration,
+        } = self;
+        total.hash(state);
+        last.hash(state);
+        output_tokens_per_sec.map(f64::to_bits).hash(state);
+        last_turn_duration.hash(state);
+    }
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Hash, Serialize)]
 pub(crate) struct TokenCountSnapshot {
     pub total_tokens: i64,
     pub input_tokens: i64,
@@ -171,35 +444,139 @@ impl TokenCountSnapshot {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Hash, Serialize)]
 pub(crate) struct StatusLineContextSnapshot {
     pub percent_remaining: u8,
     pub tokens_in_context: i64,
     pub window: i64,
+    /// Set while the remaining context is under the configured warning
+    /// threshold; anchors the pulse animation.
+    #[serde(skip)]
+    pub low_since: Option<Instant>,
+    /// Usage crossed `compact_hint_threshold`, so the context area offers
+    /// the ctrl+k compaction shortcut.
+    pub suggest_compact: bool,
 }
 
 impl StatusLineContextSnapshot {
-    #[allow(dead_code)]
     fn percent_used(&self) -> u8 {
         100u8.saturating_sub(self.percent_remaining)
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Hash, Serialize)]
 pub(crate) struct StatusLineGitSnapshot {
     pub branch: Option<String>,
     pub dirty: bool,
     pub ahead: Option<i64>,
     pub behind: Option<i64>,
+    /// Commits ahead of `tui.statusline.git_base_branch`.
+    pub base: Option<StatusLineGitBaseSnapshot>,
+    /// Open GitHub pull request for the branch, when `gh` finds one.
+    pub pull_request: Option<StatusLinePullRequestSnapshot>,
+    /// Set when this is not a repository's main worktree.
+    pub checkout: Option<StatusLineGitCheckout>,
+    /// Short SHA of a detached HEAD; `branch` is `None` then.
+    pub detached: Option<String>,
+}
+
+impl StatusLineGitSnapshot {
+    /// The branch, or `@<sha>` on a detached HEAD, after the `repo:worktree`
+    /// of a linked worktree or submodule. The branch is left out when it
+    /// matches the worktree name.
+    pub(crate) fn head_label(&self) -> Option<String> {
+        let head = self
+            .branch
+            .clone()
+            .or_else(|| self.detached.as_ref().map(|sha| format!("@{sha}")));
+        let Some(checkout) = self.checkout.as_ref() else {
+            return head;
+        };
+        let place = match checkout.kind {
+            GitCheckoutKind::Bare => format!("{}.git", checkout.repo),
+            GitCheckoutKind::Worktree | GitCheckoutKind::Submodule => {
+                format!("{}:{}", checkout.repo, checkout.name)
+            }
+        };
+        Some(match head {
+            Some(head) if head != checkout.name => format!("{place} {head}"),
+            _ => place,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct StatusLineGitCheckout {
+    pub kind: GitCheckoutKind,
+    /// Main repository, or the superproject of a submodule.
+    pub repo: String,
+    /// Worktree name or submodule path; empty for a bare repository.
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum GitCheckoutKind {
+    /// A linked worktree added with `git worktree add`.
+    Worktree,
+    Submodule,
+    Bare,
+}
+
+#[derive(Debug, Clone, Default, Hash, Serialize)]
+pub(crate) struct StatusLineGitBaseSnapshot {
+    /// Base branch without its `origin/` prefix.
+    pub name: String,
+    pub ahead: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct StatusLinePullRequestSnapshot {
+    pub number: i64,
+    pub checks: PullRequestChecks,
+}
+
+/// Combined CI result of a pull request's checks; colors its number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum PullRequestChecks {
+    /// The pull request has no checks.
+    None,
+    Pending,
+    Passing,
+    Failing,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Hash, Serialize)]
 pub(crate) struct StatusLineDevspaceSnapshot {
     pub name: String,
+    /// Icon for devspaces registered under `tui.devspaces`; others get one
+    /// picked from the name.
+    pub icon: Option<String>,
+    /// Color from `tui.devspaces`, as written in the config.
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
+impl StatusLineDevspaceSnapshot {
+    fn icon(&self) -> &str {
+        self.icon
+            .as_deref()
+            .unwrap_or_else(|| devspace_icon(&self.name))
+            .trim()
+    }
+
+    /// The configured color when it parses, otherwise mauve.
+    fn accent(&self) -> Color {
+        self.color
+            .as_deref()
+            .and_then(|color| Color::from_str(color).ok())
+            .unwrap_or(MAUVE)
+    }
 }
 
 /// 88code usage information snapshot for status line display.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub(crate) struct StatusLine88CodeSnapshot {
     /// Service tier (e.g., "LV5", "LV3", "LV1").
     pub service_tier: Option<String>,
@@ -218,24 +595,158 @@ pub(crate) struct StatusLine88CodeSnapshot {
     pub cache_create_tokens: Option<i64>,
     /// Today's cache read tokens.
     pub cache_read_tokens: Option<i64>,
+    /// Name of the active subscription (shown in `/status`).
+    pub subscription_name: Option<String>,
+    /// Credits left on the active subscription.
+    pub credits_remaining: Option<f64>,
+    /// Credit limit of the active subscription.
+    pub credit_limit: Option<f64>,
     /// True if the API request failed.
     pub is_error: bool,
     /// Error message for debugging (shown in status bar).
     pub error_msg: Option<String>,
     /// True if token has expired and needs browser re-login.
     pub token_expired: bool,
+    /// True if the latest poll failed and these are the last good numbers.
+    pub stale: bool,
+    /// When the numbers were fetched.
+    #[serde(skip)]
+    pub fetched_at: Option<Instant>,
 }
 
-#[derive(Debug, Clone)]
+// Manual impl: `daily_cost` and the credit fields are `f64`. `fetched_at`
+// changes with every poll, so it is left out; the render key hashes the age
+// a stale segment shows instead.
+impl std::hash::Hash for StatusLine88CodeSnapshot {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            service_tier,
+            daily_cost,
+            daily_tokens,
+            daily_requests,
+            input_tokens,
+            output_tokens,
+            cache_create_tokens,
+            cache_read_tokens,
+            subscription_name,
+            credits_remaining,
+            credit_limit,
+            is_error,
+            error_msg,
+            token_expired,
+            stale,
+            fetched_at: _,
+        } = self;
+        service_tier.hash(state);
+        daily_cost.map(f64::to_bits).hash(state);
+        daily_tokens.hash(state);
+        daily_requests.hash(state);
+        input_tokens.hash(state);
+        output_tokens.hash(state);
+        cache_create_tokens.hash(state);
+        cache_read_tokens.hash(state);
+        subscription_name.hash(state);
+        credits_remaining.map(f64::to_bits).hash(state);
+        credit_limit.map(f64::to_bits).hash(state);
+        is_error.hash(state);
+        error_msg.hash(state);
+        token_expired.hash(state);
+        stale.hash(state);
+    }
+}
+
+#[derive(Debug, Clone, Hash, Serialize)]
 pub(crate) struct StatusLineRunState {
     pub label: String,
+    #[serde(skip)]
     pub spinner_started_at: Option<Instant>,
+    #[serde(skip)]
     pub timer: Option<RunTimerSnapshot>,
     pub queued_messages: Vec<String>,
+    /// Queued message currently previewed while previews cycle; `None`
+    /// always previews the first one.
+    pub queue_preview_index: Option<i64>,
+    /// Approval requests waiting on the user, including ones deferred until
+    /// the current stream finishes.
+    pub pending_approvals: i64,
     pub show_interrupt_hint: bool,
+    /// The user paused the task: its turn was stopped but the run stays open,
+    /// with the timer stopped, until they resume or end it.
+    pub paused: bool,
+    /// Recap of the task that just finished, shown instead of the idle
+    /// label for `tui.statusline.run_summary_secs`.
+    pub summary: Option<StatusLineRunSummary>,
+    /// What the agent is doing; picks the run capsule color.
+    pub category: RunCategory,
+    /// The last command the agent ran this task failed.
+    pub exec_failure: Option<StatusLineExecFailure>,
+    #[serde(skip)]
     pub status_changed_at: Instant,
 }
 
+/// A command the agent ran exited non-zero. The run pill spells it out
+/// while `recent`, then keeps a badge until the next task.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct StatusLineExecFailure {
+    pub exit_code: i32,
+    pub command: String,
+    pub recent: bool,
+}
+
+/// What the running task is busy with, so the run capsule can tell editing
+/// files apart from reasoning.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RunCategory {
+    /// Reasoning or streaming a reply; also any other status header.
+    #[default]
+    Thinking,
+    Exec,
+    Patch,
+    ToolCall,
+    /// Blocked on an approval request.
+    Approval,
+}
+
+impl StatusLineRunState {
+    /// Run capsule color: by category while the task runs, yellow while it
+    /// waits on an approval, mauve when idle or paused.
+    pub(crate) fn accent(&self) -> Color {
+        if self.paused {
+            return MAUVE;
+        }
+        let running = self.timer.as_ref().is_some_and(|timer| !timer.is_paused);
+        match self.category {
+            RunCategory::Approval => YELLOW,
+            _ if !running => MAUVE,
+            RunCategory::Thinking => LAVENDER,
+            RunCategory::Exec => GREEN,
+            RunCategory::Patch => PEACH,
+            RunCategory::ToolCall => TEAL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct StatusLineRunSummary {
+    pub label: String,
+    pub elapsed_secs: u64,
+    /// Tokens the task used; `0` when no usage was reported.
+    pub tokens: i64,
+    /// The user stopped the task before it finished.
+    pub interrupted: bool,
+}
+
+impl StatusLineRunSummary {
+    fn icon(&self) -> &'static str {
+        if self.interrupted {
+            RUN_INTERRUPTED_ICON
+        } else {
+            RUN_SUMMARY_ICON
+        }
+    }
+}
+
 impl Default for StatusLineRunState {
     fn default() -> Self {
         Self {
@@ -243,13 +754,19 @@ impl Default for StatusLineRunState {
             spinner_started_at: None,
             timer: None,
             queued_messages: Vec::new(),
+            queue_preview_index: None,
+            pending_approvals: 0,
             show_interrupt_hint: false,
+            paused: false,
+            summary: None,
+            category: RunCategory::default(),
+            exec_failure: None,
             status_changed_at: Instant::now(),
         }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Hash)]
 pub(crate) struct RunTimerSnapshot {
     pub elapsed_running: Duration,
     pub last_resume_at: Option<Instant>,
@@ -269,6 +786,56 @@ impl RunTimerSnapshot {
     }
 }
 
+/// Summary of a run handed back by `StatusLineState::complete_task`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CompletedRun {
+    pub label: String,
+    pub elapsed: Duration,
+}
+
+/// `tui.statusline.timer_format` and `timer_min_secs`.
+#[derive(Debug, Clone, Copy, Default, Hash)]
+pub(crate) struct TimerDisplay {
+    pub format: StatusLineTimerFormat,
+    /// Seconds a run must last before its timer shows.
+    pub min_secs: i64,
+}
+
+impl TimerDisplay {
+    pub(crate) fn from_config(settings: &StatusLineSettings) -> Self {
+        Self {
+            format: settings.timer_format,
+            min_secs: settings.timer_min_secs,
+        }
+    }
+
+    /// Whether a run that has lasted `elapsed` shows its timer yet.
+    pub(crate) fn shows(self, elapsed: Duration) -> bool {
+        i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX) >= self.min_secs
+    }
+
+    /// The run timer's text, or `None` while the run is shorter than
+    /// `min_secs`.
+    pub(crate) fn run_timer_text(self, elapsed: Duration) -> Option<String> {
+        self.shows(elapsed)
+            .then(|| self.format_elapsed(elapsed.as_secs()))
+    }
+
+    pub(crate) fn format_elapsed(self, elapsed_secs: u64) -> String {
+        let minutes = elapsed_secs / 60;
+        let seconds = elapsed_secs % 60;
+        match self.format {
+            StatusLineTimerFormat::Compact => format_elapsed_compact(elapsed_secs),
+            StatusLineTimerFormat::MinutesSeconds => format!("{minutes:02}:{seconds:02}"),
+            StatusLineTimerFormat::HoursMinutesSeconds => {
+                let hours = minutes / 60;
+                let minutes = minutes % 60;
+                format!("{hours:02}:{minutes:02}:{seconds:02}")
+            }
+        }
+    }
+}
+
 pub(crate) fn format_elapsed_compact(elapsed_secs: u64) -> String {
     if elapsed_secs < 60 {
         return format!("{elapsed_secs}s");
@@ -313,6 +880,47 @@ enum GitVariant {
     Hidden,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum ProviderVariant {
+    ProfileAndProvider,
+    ProviderOnly,
+    Hidden,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum AttentionVariant {
+    BadgeAndMessage,
+    Badge,
+    Hidden,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum SessionVariant {
+    IdAndKey,
+    Id,
+    Hidden,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum PolicyVariant {
+    SandboxAndApproval,
+    SandboxOnly,
+    Hidden,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum QueueVariant {
+    Preview,
+    Count,
+    Hidden,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum ApprovalsVariant {
+    Full,
+    Count,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum RunLabelVariant {
     Full,
@@ -321,13 +929,34 @@ enum RunLabelVariant {
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum DegradeOp {
+    DropDevEnv,
     DropDevspace,
     DropKubernetes,
     DropAwsProfile,
+    DropIac,
     DropHostname,
+    SimplifyPolicy,
+    DropPolicy,
+    SimplifyProvider,
+    DropProvider,
+    DropDiffStats,
+    DropBuild,
+    DropClock,
+    DropProcess,
+    DropBudget,
+    DropCustom,
+    CompactSession,
+    DropSession,
+    CompactAttention,
+    DropAttention,
+    HideCompactHint,
+    CollapseQueuePreview,
     DropQueuePreview,
+    CompactApprovals,
     HideInterruptHint,
+    HideExecFailureCommand,
     HideRunTimer,
+    HideTurnTiming,
     ShortenRunLabel,
     HideRunLabel,
     SimplifyGit,
@@ -343,11 +972,307 @@ enum DegradeOp {
     Drop88Code,
 }
 
+impl DegradeOp {
+    /// Status line segment whose width the op can change. `None` for ops
+    /// that only reshape the context area or segments outside the status
+    /// line.
+    fn slot(self) -> Option<SegmentSlot> {
+        match self {
+            DegradeOp::HideInterruptHint
+            | DegradeOp::HideRunTimer
+            | DegradeOp::HideTurnTiming
+            | DegradeOp::ShortenRunLabel
+            | DegradeOp::HideRunLabel => Some(SegmentSlot::RunCapsule),
+            DegradeOp::CollapseQueuePreview | DegradeOp::DropQueuePreview => {
+                Some(SegmentSlot::QueuePreview)
+            }
+            DegradeOp::CompactApprovals => Some(SegmentSlot::Approvals),
+            DegradeOp::BasenamePath | DegradeOp::HidePath => Some(SegmentSlot::Path),
+            DegradeOp::SimplifyTokens | DegradeOp::MinimalTokens | DegradeOp::HideTokens => {
+                Some(SegmentSlot::Model)
+            }
+            DegradeOp::DropDevEnv => Some(SegmentSlot::DevEnv),
+            DegradeOp::DropDevspace => Some(SegmentSlot::Devspace),
+            DegradeOp::DropHostname => Some(SegmentSlot::Hostname),
+            DegradeOp::SimplifyGit | DegradeOp::HideGit => Some(SegmentSlot::Git),
+            DegradeOp::SimplifyPolicy | DegradeOp::DropPolicy => Some(SegmentSlot::Policy),
+            DegradeOp::SimplifyProvider | DegradeOp::DropProvider => Some(SegmentSlot::Provider),
+            DegradeOp::DropDiffStats => Some(SegmentSlot::DiffStats),
+            DegradeOp::DropBuild => Some(SegmentSlot::Build),
+            DegradeOp::DropClock => Some(SegmentSlot::Clock),
+            DegradeOp::DropProcess => Some(SegmentSlot::Process),
+            DegradeOp::DropBudget => Some(SegmentSlot::Budget),
+            DegradeOp::DropCustom => Some(SegmentSlot::Custom),
+            DegradeOp::CompactSession | DegradeOp::DropSession => Some(SegmentSlot::Session),
+            DegradeOp::CompactAttention | DegradeOp::DropAttention => Some(SegmentSlot::Attention),
+            DegradeOp::DropAwsProfile => Some(SegmentSlot::AwsProfile),
+            DegradeOp::DropKubernetes => Some(SegmentSlot::Kubernetes),
+            DegradeOp::DropIac => Some(SegmentSlot::Iac),
+            DegradeOp::SimplifyContext
+            | DegradeOp::HideContext
+            | DegradeOp::HideCompactHint
+            | DegradeOp::HideExecFailureCommand
+            | DegradeOp::Simplify88Code
+            | DegradeOp::Drop88Code => None,
+        }
+    }
+}
+
+/// Powerline segments of the status line.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+enum SegmentSlot {
+    RunCapsule,
+    Approvals,
+    QueuePreview,
+    Path,
+    Model,
+    Provider,
+    DiffStats,
+    Custom,
+    Attention,
+    Budget,
+    Policy,
+    DevEnv,
+    Devspace,
+    Hostname,
+    Git,
+    AwsProfile,
+    Iac,
+    Kubernetes,
+    Process,
+    Clock,
+    Session,
+    Build,
+}
+
+/// Run state segments, which always lead the left side.
+const RUN_SLOTS: &[SegmentSlot] = &[
+    SegmentSlot::RunCapsule,
+    SegmentSlot::Approvals,
+    SegmentSlot::QueuePreview,
+];
+
+/// Configurable segments with their default side and priority.
+const DEFAULT_PLACEMENTS: &[(StatusLineSegmentId, SegmentSlot, StatusLineSide, i32)] = &[
+    (
+        StatusLineSegmentId::Path,
+        SegmentSlot::Path,
+        StatusLineSide::Left,
+        10,
+    ),
+    (
+        StatusLineSegmentId::Model,
+        SegmentSlot::Model,
+        StatusLineSide::Left,
+        20,
+    ),
+    (
+        StatusLineSegmentId::Provider,
+        SegmentSlot::Provider,
+        StatusLineSide::Left,
+        30,
+    ),
+    (
+        StatusLineSegmentId::DiffStats,
+        SegmentSlot::DiffStats,
+        StatusLineSide::Left,
+        40,
+    ),
+    (
+        StatusLineSegmentId::Custom,
+        SegmentSlot::Custom,
+        StatusLineSide::Left,
+        50,
+    ),
+    (
+        StatusLineSegmentId::Attention,
+        SegmentSlot::Attention,
+        StatusLineSide::Right,
+        5,
+    ),
+    (
+        StatusLineSegmentId::Budget,
+        SegmentSlot::Budget,
+        StatusLineSide::Right,
+        7,
+    ),
+    (
+        StatusLineSegmentId::Policy,
+        SegmentSlot::Policy,
+        StatusLineSide::Right,
+        10,
+    ),
+    (
+        StatusLineSegmentId::DevEnv,
+        SegmentSlot::DevEnv,
+        StatusLineSide::Right,
+        15,
+    ),
+    (
+        StatusLineSegmentId::Devspace,
+        SegmentSlot::Devspace,
+        StatusLineSide::Right,
+        20,
+    ),
+    (
+        StatusLineSegmentId::Hostname,
+        SegmentSlot::Hostname,
+        StatusLineSide::Right,
+        30,
+    ),
+    (
+        StatusLineSegmentId::Git,
+        SegmentSlot::Git,
+        StatusLineSide::Right,
+        40,
+    ),
+    (
+        StatusLineSegmentId::AwsProfile,
+        SegmentSlot::AwsProfile,
+        StatusLineSide::Right,
+        50,
+    ),
+    (
+        StatusLineSegmentId::Iac,
+        SegmentSlot::Iac,
+        StatusLineSide::Right,
+        55,
+    ),
+    (
+        StatusLineSegmentId::Kubernetes,
+        SegmentSlot::Kubernetes,
+        StatusLineSide::Right,
+        60,
+    ),
+    (
+        StatusLineSegmentId::Process,
+        SegmentSlot::Process,
+        StatusLineSide::Right,
+        65,
+    ),
+    (
+        StatusLineSegmentId::Clock,
+        SegmentSlot::Clock,
+        StatusLineSide::Right,
+        70,
+    ),
+    (
+        StatusLineSegmentId::Session,
+        SegmentSlot::Session,
+        StatusLineSide::Right,
+        75,
+    ),
+    (
+        StatusLineSegmentId::Build,
+        SegmentSlot::Build,
+        StatusLineSide::Right,
+        80,
+    ),
+];
+
+/// Segments on each side of the status line, in display order, after
+/// applying `tui.statusline.layout`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct SegmentLayout {
+    left: Vec<SegmentSlot>,
+    right: Vec<SegmentSlot>,
+}
+
+impl SegmentLayout {
+    pub(crate) fn from_config(layout: &StatusLineSegmentLayout) -> Self {
+        let mut placed: Vec<(StatusLineSide, i32, SegmentSlot)> = DEFAULT_PLACEMENTS
+            .iter()
+            .map(|&(id, slot, side, priority)| {
+                let placement = layout.placement(id).unwrap_or_default();
+                (
+                    placement.side.unwrap_or(side),
+                    placement.priority.unwrap_or(priority),
+                    slot,
+                )
+            })
+            .collect();
+        // Stable, so equal priorities keep the default order.
+        placed.sort_by_key(|(_, priority, _)| *priority);
+        let mut left = RUN_SLOTS.to_vec();
+        let mut right = Vec::new();
+        for (side, _, slot) in placed {
+            match side {
+                StatusLineSide::Left => left.push(slot),
+                StatusLineSide::Right => right.push(slot),
+            }
+        }
+        Self { left, right }
+    }
+}
+
+impl Default for SegmentLayout {
+    fn default() -> Self {
+        Self::from_config(&StatusLineSegmentLayout::default())
+    }
+}
+
+/// Padded width of every present segment, so a degrade op only re-measures
+/// the one segment it touches.
+struct SegmentWidths {
+    left: Vec<Option<usize>>,
+    right: Vec<Option<usize>>,
+}
+
+impl SegmentWidths {
+    fn measure(model: &RenderModel<'_>) -> Self {
+        let measure = |slots: &[SegmentSlot]| {
+            slots
+                .iter()
+                .map(|slot| model.segment(*slot).map(|segment| segment.padded_width()))
+                .collect()
+        };
+        let layout = &model.snapshot.layout;
+        Self {
+            left: measure(&layout.left),
+            right: measure(&layout.right),
+        }
+    }
+
+    fn update(&mut self, model: &RenderModel<'_>, slot: SegmentSlot) {
+        let width = model.segment(slot).map(|segment| segment.padded_width());
+        let layout = &model.snapshot.layout;
+        if let Some(index) = layout.left.iter().position(|s| *s == slot) {
+            self.left[index] = width;
+        } else if let Some(index) = layout.right.iter().position(|s| *s == slot) {
+            self.right[index] = width;
+        }
+    }
+
+    /// Width of both powerline sides, including curves and chevrons.
+    fn fixed_width(&self) -> usize {
+        Self::side_width(&self.left, LEFT_CURVE, LEFT_CHEVRON, LEFT_CHEVRON)
+            + Self::side_width(&self.right, RIGHT_CHEVRON, RIGHT_CHEVRON, RIGHT_CURVE)
+    }
+
+    fn side_width(widths: &[Option<usize>], first: &str, between: &str, last: &str) -> usize {
+        let mut total = 0;
+        let mut count = 0;
+        for width in widths.iter().flatten() {
+            total += width;
+            count += 1;
+        }
+        if count == 0 {
+            return 0;
+        }
+        total
+            + UnicodeWidthStr::width(first)
+            + (count - 1) * UnicodeWidthStr::width(between)
+            + UnicodeWidthStr::width(last)
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 struct EnvironmentInclusion {
     hostname: bool,
     aws_profile: bool,
+    iac: bool,
     kubernetes: bool,
+    dev_env: bool,
     devspace: bool,
     code88: bool,
     code88_variant: Code88Variant,
@@ -372,7 +1297,9 @@ impl EnvironmentInclusion {
         Self {
             hostname: snapshot.hostname.is_some(),
             aws_profile: snapshot.aws_profile.is_some(),
+            iac: snapshot.iac.is_some(),
             kubernetes: snapshot.kubernetes_context.is_some(),
+            dev_env: snapshot.dev_env.is_some(),
             devspace: snapshot.devspace.is_some(),
             code88: snapshot.code88.is_some(),
             code88_variant: Code88Variant::Full,
@@ -406,17 +1333,70 @@ pub(crate) fn render_status_line(
     now: Instant,
 ) -> Line<'static> {
     let mut model = RenderModel::new(snapshot, now);
-    let target_width = width as usize;
+    render_fitted(&mut model, width as usize)
+}
 
-    loop {
-        if let Some(line) = model.try_render_line(target_width) {
-            return line;
-        }
-        if !model.apply_next_degrade() {
-            let fallback = model.fallback_line();
-            return truncate_line_to_width(fallback, target_width);
-        }
+/// Columns per width band. Within a band the status line keeps the degrade
+/// steps it settled on, so dragging the terminal edge does not pop segments
+/// in and out at every column.
+const DEGRADE_BAND_COLUMNS: u16 = 8;
+
+/// How far the status line degraded for one snapshot within a width band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DegradeMemo {
+    content: u64,
+    cursor: i64,
+}
+
+/// Degrade memos keyed by width band. [`state::StatusLineState`] owns them,
+/// so renderers stay stateless and lines drawn at different widths do not
+/// overwrite each other's memo.
+pub(crate) type DegradeMemos = HashMap<u16, DegradeMemo>;
+
+/// [`render_status_line`] with resize hysteresis: while the width stays in
+/// a band whose memo was recorded for the same snapshot, start from the
+/// steps already taken instead of searching again, so growing back within
+/// the band keeps the same variants. Another band, or any content change,
+/// runs the full search.
+pub(crate) fn render_status_line_banded(
+    snapshot: &StatusLineSnapshot,
+    width: u16,
+    now: Instant,
+    memos: &mut DegradeMemos,
+) -> Line<'static> {
+    let band = width / DEGRADE_BAND_COLUMNS;
+    let content = {
+        let mut hasher = DefaultHasher::new();
+        snapshot.hash(&mut hasher);
+        hasher.finish()
+    };
+    let mut model = RenderModel::new(snapshot, now);
+    if let Some(previous) = memos.get(&band).filter(|memo| memo.content == content) {
+        model.degrade_through(previous.cursor);
+    }
+    let line = render_fitted(&mut model, usize::from(width));
+    memos.insert(
+        band,
+        DegradeMemo {
+            content,
+            cursor: model.degrade_cursor,
+        },
+    );
+    line
+}
+
+fn render_fitted(model: &mut RenderModel<'_>, target_width: usize) -> Line<'static> {
+    if !model.degrade_to_fit(target_width) {
+        let fallback = model.fallback_line();
+        return truncate_line_to_width(fallback, target_width);
     }
+    if let Some(line) = model.try_render_line(target_width) {
+        return line;
+    }
+    // The width estimate and the rendered line should always agree; if they
+    // ever drift apart, say so and draw the fallback rather than overflow.
+    tracing::warn!("status line width estimate disagreed with render at {target_width} columns");
+    truncate_line_to_width(model.fallback_line(), target_width)
 }
 
 pub(crate) fn render_status_run_pill(
@@ -434,17 +1414,29 @@ pub(crate) fn render_status_run_pill(
     model.token_variant = TokenVariant::Hidden;
     model.context_variant = ContextVariant::Hidden;
     model.git_variant = GitVariant::Hidden;
+    model.policy_variant = PolicyVariant::Hidden;
+    model.provider_variant = ProviderVariant::Hidden;
+    model.show_diff_stats = false;
+    model.show_clock = false;
+    model.show_build = false;
+    model.show_process = false;
+    model.show_budget = false;
+    model.show_custom = false;
+    model.session_variant = SessionVariant::Hidden;
+    model.attention_variant = AttentionVariant::Hidden;
+    model.show_compact_hint = false;
     // Keep 88code visible in the run pill area (input box upper-right)
     model.env = EnvironmentInclusion {
         hostname: false,
         aws_profile: false,
+        iac: false,
         kubernetes: false,
+        dev_env: false,
         devspace: false,
         code88: snapshot.environment.code88.is_some(),
         code88_variant: Code88Variant::Full,
     };
-    model.include_queue_preview = true;
-    model.show_interrupt_hint = false;
+    model.queue_variant = QueueVariant::Preview;
 
     let mut attempts = 0usize;
     loop {
@@ -498,19 +1490,35 @@ struct RenderModel<'a> {
     token_variant: TokenVariant,
     context_variant: ContextVariant,
     git_variant: GitVariant,
-    include_queue_preview: bool,
+    policy_variant: PolicyVariant,
+    provider_variant: ProviderVariant,
+    show_diff_stats: bool,
+    show_clock: bool,
+    show_build: bool,
+    show_process: bool,
+    show_budget: bool,
+    show_custom: bool,
+    session_variant: SessionVariant,
+    attention_variant: AttentionVariant,
+    show_compact_hint: bool,
+    queue_variant: QueueVariant,
+    approvals_variant: ApprovalsVariant,
     show_interrupt_hint: bool,
+    show_exec_failure_command: bool,
     show_run_timer: bool,
+    show_turn_timing: bool,
     show_run_label: bool,
     run_label_variant: RunLabelVariant,
     env: EnvironmentInclusion,
-    degrade_cursor: usize,
+    degrade_cursor: i64,
 }
 
 impl<'a> RenderModel<'a> {
     fn new(snapshot: &'a StatusLineSnapshot, now: Instant) -> Self {
         let run_state = snapshot.run_state.as_ref();
-        let has_timer = run_state.and_then(|state| state.timer.as_ref()).is_some();
+        let show_run_timer = run_state
+            .and_then(|state| state.timer.as_ref())
+            .is_some_and(|timer| snapshot.timer_display.shows(timer.elapsed_at(now)));
         let show_hint = run_state
             .map(|state| state.show_interrupt_hint)
             .unwrap_or(false);
@@ -521,9 +1529,28 @@ impl<'a> RenderModel<'a> {
             token_variant: TokenVariant::Hidden,
             context_variant: ContextVariant::Bar,
             git_variant: GitVariant::BranchWithStatus,
-            include_queue_preview: true,
+            policy_variant: PolicyVariant::SandboxAndApproval,
+            provider_variant: ProviderVariant::ProfileAndProvider,
+            show_diff_stats: true,
+            show_clock: true,
+            show_build: true,
+            show_process: true,
+            show_budget: true,
+            show_custom: true,
+            session_variant: SessionVariant::IdAndKey,
+            attention_variant: AttentionVariant::BadgeAndMessage,
+            show_compact_hint: snapshot
+                .context
+                .as_ref()
+                .is_some_and(|context| context.suggest_compact),
+            queue_variant: QueueVariant::Preview,
+            approvals_variant: ApprovalsVariant::Full,
             show_interrupt_hint: show_hint,
-            show_run_timer: has_timer,
+            show_exec_failure_command: run_state
+                .and_then(|state| state.exec_failure.as_ref())
+                .is_some_and(|failure| failure.recent),
+            show_run_timer,
+            show_turn_timing: true,
             show_run_label: run_state.is_some(),
             run_label_variant: RunLabelVariant::Full,
             env: EnvironmentInclusion::new(&snapshot.environment),
@@ -545,56 +1572,134 @@ impl<'a> RenderModel<'a> {
             parts.push(model.label.clone());
         }
         if let Some(git) = self.snapshot.git.as_ref()
-            && let Some(branch) = git.branch.as_ref()
+            && let Some(mut branch_text) = git.head_label()
         {
-            let mut branch_text = branch.clone();
             if git.dirty {
                 branch_text.push('*');
             }
             parts.push(branch_text);
         }
+        if self.snapshot.queued > 0 {
+            parts.insert(0, format!("{} queued", self.snapshot.queued));
+        }
+        if let Some(approvals) = pending_approvals_text(self.snapshot.pending_approvals) {
+            parts.insert(0, approvals);
+        }
         if parts.is_empty() {
             return Line::from("codex");
         }
         Line::from(parts.join(" | "))
     }
 
-    fn apply_next_degrade(&mut self) -> bool {
+    /// Apply degrade ops in order until the fixed segments plus the context
+    /// area fit in `target_width`, tracking segment widths incrementally so
+    /// the line only has to be rendered once. Returns `false` when even the
+    /// fully degraded line does not fit.
+    fn degrade_to_fit(&mut self, target_width: usize) -> bool {
+        let mut widths = SegmentWidths::measure(self);
+        loop {
+            if let Some(available) = target_width.checked_sub(widths.fixed_width())
+                && self.middle_fits(available)
+            {
+                return true;
+            }
+            let Some(op) = self.apply_next_degrade() else {
+                return false;
+            };
+            if let Some(slot) = op.slot() {
+                widths.update(self, slot);
+            }
+        }
+    }
+
+    /// Whether `render_middle` can fill exactly `width` columns.
+    fn middle_fits(&self, width: usize) -> bool {
+        if width == 0 {
+            return true;
+        }
+        match self.context_variant {
+            ContextVariant::Hidden => true,
+            ContextVariant::Bar => self.snapshot.context.as_ref().is_some_and(|context| {
+                !self.show_compact_hint || self.context_bar_min_width(context) <= width
+            }),
+            ContextVariant::Compact => self.snapshot.context.as_ref().is_some_and(|context| {
+                let (text, _) = self.context_compact_text(context);
+                UnicodeWidthStr::width(text.as_str()) + self.compact_hint_width() <= width
+            }),
+        }
+    }
+
+    /// Replay degrade ops until the cursor reaches `cursor`.
+    fn degrade_through(&mut self, cursor: i64) {
+        while self.degrade_cursor < cursor {
+            if self.apply_next_degrade().is_none() {
+                break;
+            }
+        }
+    }
+
+    fn apply_next_degrade(&mut self) -> Option<DegradeOp> {
         const DEGRADE_ORDER: &[DegradeOp] = &[
-            DegradeOp::DropQueuePreview,
+            DegradeOp::CollapseQueuePreview,
             DegradeOp::HideInterruptHint,
+            DegradeOp::HideTurnTiming,
             DegradeOp::HideRunTimer,
             DegradeOp::ShortenRunLabel,
             DegradeOp::HideRunLabel,
+            DegradeOp::DropQueuePreview,
+            DegradeOp::CompactApprovals,
+            DegradeOp::HideCompactHint,
             DegradeOp::BasenamePath,
+            DegradeOp::DropDiffStats,
+            DegradeOp::DropBuild,
+            DegradeOp::DropClock,
+            DegradeOp::DropProcess,
+            DegradeOp::DropBudget,
+            DegradeOp::DropCustom,
+            DegradeOp::CompactSession,
+            DegradeOp::DropSession,
+            DegradeOp::CompactAttention,
+            DegradeOp::SimplifyProvider,
             DegradeOp::SimplifyTokens,
             DegradeOp::MinimalTokens,
             DegradeOp::HideTokens,
+            DegradeOp::DropProvider,
             DegradeOp::SimplifyContext,
             DegradeOp::HideContext,
             DegradeOp::SimplifyGit,
             DegradeOp::HideGit,
+            DegradeOp::SimplifyPolicy,
             DegradeOp::Simplify88Code,
             DegradeOp::Drop88Code,
+            DegradeOp::DropDevEnv,
             DegradeOp::DropDevspace,
             DegradeOp::DropKubernetes,
             DegradeOp::DropAwsProfile,
+            DegradeOp::DropIac,
             DegradeOp::DropHostname,
+            DegradeOp::DropPolicy,
+            DegradeOp::DropAttention,
             DegradeOp::HidePath,
         ];
 
-        while self.degrade_cursor < DEGRADE_ORDER.len() {
-            let op = DEGRADE_ORDER[self.degrade_cursor];
+        while let Some(&op) = usize::try_from(self.degrade_cursor)
+            .ok()
+            .and_then(|index| DEGRADE_ORDER.get(index))
+        {
             self.degrade_cursor += 1;
             if self.apply_degrade(op) {
-                return true;
+                return Some(op);
             }
         }
-        false
+        None
     }
 
     fn apply_degrade(&mut self, op: DegradeOp) -> bool {
         match op {
+            DegradeOp::DropDevEnv if self.env.dev_env => {
+                self.env.dev_env = false;
+                true
+            }
             DegradeOp::DropDevspace if self.env.devspace => {
                 self.env.devspace = false;
                 true
@@ -607,22 +1712,42 @@ impl<'a> RenderModel<'a> {
                 self.env.aws_profile = false;
                 true
             }
+            DegradeOp::DropIac if self.env.iac => {
+                self.env.iac = false;
+                true
+            }
             DegradeOp::DropHostname if self.env.hostname => {
                 self.env.hostname = false;
                 true
             }
-            DegradeOp::DropQueuePreview if self.include_queue_preview => {
-                self.include_queue_preview = false;
+            DegradeOp::CollapseQueuePreview if self.queue_variant == QueueVariant::Preview => {
+                self.queue_variant = QueueVariant::Count;
+                true
+            }
+            DegradeOp::DropQueuePreview if self.queue_variant != QueueVariant::Hidden => {
+                self.queue_variant = QueueVariant::Hidden;
+                true
+            }
+            DegradeOp::CompactApprovals if self.approvals_variant == ApprovalsVariant::Full => {
+                self.approvals_variant = ApprovalsVariant::Count;
                 true
             }
             DegradeOp::HideInterruptHint if self.show_interrupt_hint => {
                 self.show_interrupt_hint = false;
                 true
             }
+            DegradeOp::HideExecFailureCommand if self.show_exec_failure_command => {
+                self.show_exec_failure_command = false;
+                true
+            }
             DegradeOp::HideRunTimer if self.show_run_timer => {
                 self.show_run_timer = false;
                 true
             }
+            DegradeOp::HideTurnTiming if self.show_turn_timing => {
+                self.show_turn_timing = false;
+                true
+            }
             DegradeOp::ShortenRunLabel
                 if self.show_run_label && self.run_label_variant == RunLabelVariant::Full =>
             {
@@ -669,38 +1794,104 @@ impl<'a> RenderModel<'a> {
                 self.git_variant = GitVariant::Hidden;
                 true
             }
-            DegradeOp::Simplify88Code
-                if self.env.code88
-                    && matches!(
-                        self.env.code88_variant,
-                        Code88Variant::Full | Code88Variant::Compact
-                    ) =>
+            DegradeOp::SimplifyPolicy
+                if self.policy_variant == PolicyVariant::SandboxAndApproval =>
             {
-                self.env.code88_variant = match self.env.code88_variant {
-                    Code88Variant::Full => Code88Variant::Compact,
-                    Code88Variant::Compact => Code88Variant::Minimal,
-                    _ => Code88Variant::Minimal,
-                };
+                self.policy_variant = PolicyVariant::SandboxOnly;
                 true
             }
-            DegradeOp::Drop88Code
-                if self.env.code88 && self.env.code88_variant != Code88Variant::Hidden =>
+            DegradeOp::DropPolicy if self.policy_variant != PolicyVariant::Hidden => {
+                self.policy_variant = PolicyVariant::Hidden;
+                true
+            }
+            DegradeOp::SimplifyProvider
+                if self.provider_variant == ProviderVariant::ProfileAndProvider =>
             {
-                self.env.code88_variant = Code88Variant::Hidden;
+                self.provider_variant = ProviderVariant::ProviderOnly;
                 true
             }
-            _ => false,
-        }
-    }
-
-    fn try_render_line(&self, target_width: usize) -> Option<Line<'static>> {
-        let left_spans = self.render_left_segments()?;
-        let right_spans = self.render_right_segments()?;
-
-        let left_line = Line::from(left_spans.clone());
-        let right_line = Line::from(right_spans.clone());
-        let left_width = line_display_width(&left_line);
-        let right_width = line_display_width(&right_line);
+            DegradeOp::DropProvider if self.provider_variant != ProviderVariant::Hidden => {
+                self.provider_variant = ProviderVariant::Hidden;
+                true
+            }
+            DegradeOp::DropDiffStats if self.show_diff_stats => {
+                self.show_diff_stats = false;
+                true
+            }
+            DegradeOp::DropBuild if self.show_build => {
+                self.show_build = false;
+                true
+            }
+            DegradeOp::DropClock if self.show_clock => {
+                self.show_clock = false;
+                true
+            }
+            DegradeOp::DropProcess if self.show_process => {
+                self.show_process = false;
+                true
+            }
+            DegradeOp::DropBudget if self.show_budget => {
+                self.show_budget = false;
+                true
+            }
+            DegradeOp::DropCustom if self.show_custom => {
+                self.show_custom = false;
+                true
+            }
+            DegradeOp::CompactSession if self.session_variant == SessionVariant::IdAndKey => {
+                self.session_variant = SessionVariant::Id;
+                true
+            }
+            DegradeOp::DropSession if self.session_variant != SessionVariant::Hidden => {
+                self.session_variant = SessionVariant::Hidden;
+                true
+            }
+            DegradeOp::CompactAttention
+                if self.attention_variant == AttentionVariant::BadgeAndMessage =>
+            {
+                self.attention_variant = AttentionVariant::Badge;
+                true
+            }
+            DegradeOp::DropAttention if self.attention_variant != AttentionVariant::Hidden => {
+                self.attention_variant = AttentionVariant::Hidden;
+                true
+            }
+            DegradeOp::HideCompactHint if self.show_compact_hint => {
+                self.show_compact_hint = false;
+                true
+            }
+            DegradeOp::Simplify88Code
+                if self.env.code88
+                    && matches!(
+                        self.env.code88_variant,
+                        Code88Variant::Full | Code88Variant::Compact
+                    ) =>
+            {
+                self.env.code88_variant = match self.env.code88_variant {
+                    Code88Variant::Full => Code88Variant::Compact,
+                    Code88Variant::Compact => Code88Variant::Minimal,
+                    _ => Code88Variant::Minimal,
+                };
+                true
+            }
+            DegradeOp::Drop88Code
+                if self.env.code88 && self.env.code88_variant != Code88Variant::Hidden =>
+            {
+                self.env.code88_variant = Code88Variant::Hidden;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn try_render_line(&self, target_width: usize) -> Option<Line<'static>> {
+        let left_spans = self.render_left_segments()?;
+        let right_spans = self.render_right_segments()?;
+
+        let left_line = Line::from(left_spans.clone());
+        let right_line = Line::from(right_spans.clone());
+        let left_width = line_display_width(&left_line);
+        let right_width = line_display_width(&right_line);
         let available_for_middle = target_width.checked_sub(left_width + right_width)?;
         let (middle_spans, _middle_width) = self.render_middle(available_for_middle)?;
 
@@ -743,15 +1934,53 @@ impl<'a> RenderModel<'a> {
     }
 
     fn collect_left_segments(&self) -> Vec<PowerlineSegment> {
-        let mut segments: Vec<PowerlineSegment> = Vec::new();
-        segments.extend(self.run_state_segments(self.snapshot.run_state.as_ref()));
-        if let Some(segment) = self.path_segment() {
-            segments.push(segment);
-        }
-        if let Some(segment) = self.model_segment() {
-            segments.push(segment);
+        self.snapshot
+            .layout
+            .left
+            .iter()
+            .filter_map(|slot| self.segment(*slot))
+            .collect()
+    }
+
+    fn segment(&self, slot: SegmentSlot) -> Option<PowerlineSegment> {
+        match slot {
+            SegmentSlot::RunCapsule => self
+                .snapshot
+                .run_state
+                .as_ref()
+                .map(|state| self.run_capsule_segment(state)),
+            SegmentSlot::Approvals => self.approvals_segment(
+                self.snapshot
+                    .run_state
+                    .as_ref()
+                    .map_or(self.snapshot.pending_approvals, |state| {
+                        state.pending_approvals
+                    }),
+            ),
+            SegmentSlot::QueuePreview => match self.snapshot.run_state.as_ref() {
+                Some(state) => self.queue_preview_segment(state),
+                None => self.queued_count_segment(),
+            },
+            SegmentSlot::Path => self.path_segment(),
+            SegmentSlot::Model => self.model_segment(),
+            SegmentSlot::Provider => self.provider_segment(),
+            SegmentSlot::DiffStats => self.diff_stats_segment(),
+            SegmentSlot::Custom => self.custom_segment(),
+            SegmentSlot::Attention => self.attention_segment(),
+            SegmentSlot::Policy => self.policy_segment(),
+            SegmentSlot::DevEnv => self.dev_env_segment(),
+            SegmentSlot::Devspace => self.devspace_segment(),
+            SegmentSlot::Hostname => self.hostname_segment(),
+            SegmentSlot::Git => self.build_git_segment(),
+            SegmentSlot::AwsProfile => self.aws_profile_segment(),
+            SegmentSlot::Iac => self.iac_segment(),
+            SegmentSlot::Kubernetes => self.kubernetes_segment(),
+            SegmentSlot::Process => self.process_segment(),
+            SegmentSlot::Budget => self.budget_segment(),
+            SegmentSlot::Clock => self.clock_segment(),
+            SegmentSlot::Session => self.session_segment(),
+            SegmentSlot::Build => self.build_segment(),
         }
-        segments
     }
 
     fn path_segment(&self) -> Option<PowerlineSegment> {
@@ -762,25 +1991,21 @@ impl<'a> RenderModel<'a> {
     fn path_text(&self) -> Option<String> {
         match self.path_variant {
             PathVariant::Hidden => None,
-            PathVariant::Full => self
-                .snapshot
-                .cwd_display
-                .as_ref()
-                .map(|path| truncate_graphemes(path, 40)),
+            // Already abbreviated and fitted to `path_max_width` by the state.
+            PathVariant::Full => self.snapshot.cwd_display.clone(),
             PathVariant::Basename => self
                 .snapshot
                 .cwd_basename
                 .clone()
                 .or_else(|| self.snapshot.cwd_fallback.clone())
-                .map(|path| truncate_graphemes(&path, 28)),
+                .map(|path| truncate_to_width(&path, 28)),
         }
     }
 
     fn model_segment(&self) -> Option<PowerlineSegment> {
         let model = self.snapshot.model.as_ref()?;
         let mut spans: Vec<Span<'static>> = Vec::new();
-        let icon = select_model_icon(&model.label).to_string();
-        spans.push(icon.into());
+        spans.push(model.icon().into());
         if !model.label.is_empty() {
             spans.push(" ".into());
             spans.push(Span::styled(
@@ -799,36 +2024,37 @@ impl<'a> RenderModel<'a> {
             spans.push(" ".into());
             spans.push(Span::styled(tokens, dim_text()));
         }
-        Some(PowerlineSegment::from_spans(SKY, spans))
+        Some(PowerlineSegment::from_spans(model.accent(), spans))
     }
 
     fn format_token_summary(&self) -> Option<String> {
         let tokens = self.snapshot.tokens.as_ref()?;
+        let token_format = self.snapshot.token_format;
         match self.token_variant {
             TokenVariant::Hidden => None,
             TokenVariant::Minimal => Some(format!(
                 "Σ{}",
-                format_token_count(tokens.total.blended_total())
+                token_format.format(tokens.total.blended_total())
             )),
             TokenVariant::Compact | TokenVariant::Full => {
                 let mut parts = Vec::new();
                 parts.push(format!(
                     "Σ{}",
-                    format_token_count(tokens.total.blended_total())
+                    token_format.format(tokens.total.blended_total())
                 ));
                 parts.push(format!(
                     "↑{}",
-                    format_token_count(tokens.total.input_without_cache())
+                    token_format.format(tokens.total.input_without_cache())
                 ));
                 if tokens.total.cached_input_tokens > 0 {
                     parts.push(format!(
                         "↺{}",
-                        format_token_count(tokens.total.cached_input_tokens)
+                        token_format.format(tokens.total.cached_input_tokens)
                     ));
                 }
                 parts.push(format!(
                     "↓{}",
-                    format_token_count(tokens.total.output_tokens)
+                    token_format.format(tokens.total.output_tokens)
                 ));
                 Some(parts.join(" "))
             }
@@ -839,9 +2065,24 @@ impl<'a> RenderModel<'a> {
         let Some(state) = state else {
             return Vec::new();
         };
+        let mut segments = vec![self.run_capsule_segment(state)];
+        segments.extend(self.offline_segment());
+        segments.extend(self.exec_failure_segment(state));
+        segments.extend(self.run_keys_segment(state));
+        segments.extend(self.approvals_segment(state.pending_approvals));
+        segments.extend(self.queue_preview_segment(state));
+        segments
+    }
 
-        let mut segments: Vec<PowerlineSegment> = Vec::new();
-
+    fn run_capsule_segment(&self, state: &StatusLineRunState) -> PowerlineSegment {
+        if let Some(summary) = state.summary.as_ref() {
+            let text = if self.show_run_label {
+                run_summary_text(summary, &self.snapshot)
+            } else {
+                summary.icon().to_string()
+            };
+            return PowerlineSegment::text(GREEN, text);
+        }
         let mut capsule_spans: Vec<Span<'static>> = Vec::new();
         if self.show_run_timer {
             let elapsed_secs = state
@@ -851,15 +2092,24 @@ impl<'a> RenderModel<'a> {
                 .unwrap_or(0);
             capsule_spans.push(Span::raw(format!(
                 "󰔟 {}",
-                format_elapsed_compact(elapsed_secs)
+                self.snapshot.timer_display.format_elapsed(elapsed_secs)
             )));
         }
 
+        if self.show_turn_timing
+            && let Some(timing) = self.turn_timing_text(state)
+        {
+            if !capsule_spans.is_empty() {
+                capsule_spans.push(" ".into());
+            }
+            capsule_spans.push(Span::styled(timing, dim_text()));
+        }
+
         if self.show_run_label {
             if !capsule_spans.is_empty() {
                 capsule_spans.push(" ".into());
             }
-            capsule_spans.push(status_spinner(state.spinner_started_at));
+            capsule_spans.push(run_spinner(&self.snapshot.spinner, state));
             let label = self.run_label_text(state);
             if !label.trim().is_empty() {
                 capsule_spans.push(" ".into());
@@ -867,36 +2117,125 @@ impl<'a> RenderModel<'a> {
             }
         }
 
+        let accent = state.accent();
         if capsule_spans.is_empty() {
-            let accent = self.status_capsule_accent(state);
-            segments.push(PowerlineSegment::from_spans(
-                accent,
-                vec![status_spinner(state.spinner_started_at)],
-            ));
+            PowerlineSegment::from_spans(accent, vec![run_spinner(&self.snapshot.spinner, state)])
         } else {
-            let accent = self.status_capsule_accent(state);
-            segments.push(PowerlineSegment::from_spans(accent, capsule_spans));
+            PowerlineSegment::from_spans(accent, capsule_spans)
         }
+    }
 
-        if self.include_queue_preview && !state.queued_messages.is_empty() {
-            let (preview, extra) = queue_preview(&state.queued_messages);
-            let mut spans: Vec<Span<'static>> = Vec::new();
-            spans.push("next:".dim());
-            spans.push(" ".into());
-            spans.push(Span::styled(preview, queue_preview_style()));
-            if extra > 0 {
-                spans.push(" ".into());
-                spans.push(Span::styled(format!("(+{extra})"), queue_preview_style()));
+    fn offline_segment(&self) -> Option<PowerlineSegment> {
+        self.snapshot
+            .offline
+            .then(|| PowerlineSegment::from_spans(RED, vec!["offline".bold()]))
+    }
+
+    /// `✗ exit 1 · cargo test` for a few seconds after a command fails, then
+    /// just `✗` until the next task.
+    fn exec_failure_segment(&self, state: &StatusLineRunState) -> Option<PowerlineSegment> {
+        let failure = state.exec_failure.as_ref()?;
+        let text = exec_failure_text(failure, self.show_exec_failure_command);
+        Some(PowerlineSegment::from_spans(RED, vec![text.bold()]))
+    }
+
+    /// Keys that act on the task: pause or interrupt it while it runs,
+    /// resume or end it once paused.
+    fn run_keys_segment(&self, state: &StatusLineRunState) -> Option<PowerlineSegment> {
+        if !self.show_interrupt_hint {
+            return None;
+        }
+        let pause_key: Span<'static> = key_hint::ctrl(KeyCode::Char('s')).into();
+        let esc_key: Span<'static> = key_hint::plain(KeyCode::Esc).into();
+        let spans = if state.paused {
+            vec![pause_key, " resume · ".dim(), esc_key, " end".dim()]
+        } else {
+            vec![esc_key, " interrupt · ".dim(), pause_key, " pause".dim()]
+        };
+        Some(PowerlineSegment::from_spans(SUBTEXT0, spans))
+    }
+
+    /// Warning chunk while approvals are queued, so a user scrolled up in
+    /// history sees that the agent is blocked on them.
+    fn approvals_segment(&self, pending_approvals: i64) -> Option<PowerlineSegment> {
+        let text = match self.approvals_variant {
+            ApprovalsVariant::Full => pending_approvals_text(pending_approvals)?,
+            ApprovalsVariant::Count if pending_approvals > 0 => {
+                format!("⚠ {pending_approvals}")
+            }
+            ApprovalsVariant::Count => return None,
+        };
+        Some(PowerlineSegment::from_spans(YELLOW, vec![text.bold()]))
+    }
+
+    fn queue_preview_segment(&self, state: &StatusLineRunState) -> Option<PowerlineSegment> {
+        if state.queued_messages.is_empty() {
+            return None;
+        }
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        match self.queue_variant {
+            QueueVariant::Hidden => return None,
+            QueueVariant::Count => {
+                let count = state.queued_messages.len();
+                spans.push(Span::styled(
+                    format!("{count} queued"),
+                    queue_preview_style(),
+                ));
+                return Some(PowerlineSegment::from_spans(MAUVE, spans));
             }
+            QueueVariant::Preview => {}
+        }
+        let (preview, position) = queue_preview(&state.queued_messages, state.queue_preview_index);
+        spans.push("next:".dim());
+        spans.push(" ".into());
+        spans.push(Span::styled(preview, queue_preview_style()));
+        if let Some(position) = position {
             spans.push(" ".into());
-            spans.push(key_hint::alt(KeyCode::Up).into());
-            spans.push(" edit".dim());
-            segments.push(PowerlineSegment::from_spans(MAUVE, spans));
+            spans.push(Span::styled(format!("({position})"), queue_preview_style()));
+        }
+        spans.push(" ".into());
+        spans.push(key_hint::alt(KeyCode::Up).into());
+        spans.push(" edit".dim());
+        Some(PowerlineSegment::from_spans(MAUVE, spans))
+    }
+    /// `2 queued` on the status line while the run pill is hidden. Kept at
+    /// every width, since nothing else on screen shows the queue then.
+    fn queued_count_segment(&self) -> Option<PowerlineSegment> {
+        if self.snapshot.queued <= 0 {
+            return None;
         }
+        Some(PowerlineSegment::from_spans(
+            MAUVE,
+            vec![Span::styled(
+                format!("{} queued", self.snapshot.queued),
+                queue_preview_style(),
+            )],
+        ))
+    }
 
-        segments
+    /// Output tokens/sec while the task runs, or the last task's duration once
+    /// idle.
+    fn turn_timing_text(&self, state: &StatusLineRunState) -> Option<String> {
+        let tokens = self.snapshot.tokens.as_ref()?;
+        let running = state.timer.as_ref().is_some_and(|timer| !timer.is_paused);
+        if running {
+            let rate = tokens.output_tokens_per_sec?;
+            Some(format!("{rate:.0} tok/s"))
+        } else {
+            let duration = tokens.last_turn_duration?;
+            Some(format!(
+                "last {}",
+                self.snapshot
+                    .timer_display
+                    .format_elapsed(duration.as_secs())
+            ))
+        }
     }
+
     fn run_label_text(&self, state: &StatusLineRunState) -> String {
+        if state.paused {
+            return PAUSED_STATUS_MESSAGE.to_string();
+        }
         let mut label = match self.run_label_variant {
             RunLabelVariant::Full => state.label.clone(),
             RunLabelVariant::Short => state
@@ -916,19 +2255,6 @@ impl<'a> RenderModel<'a> {
         }
     }
 
-    fn status_capsule_accent(&self, state: &StatusLineRunState) -> Color {
-        if state
-            .timer
-            .as_ref()
-            .map(|timer| !timer.is_paused)
-            .unwrap_or(false)
-        {
-            GREEN
-        } else {
-            MAUVE
-        }
-    }
-
     fn render_right_segments(&self) -> Option<Vec<Span<'static>>> {
         let segments = self.collect_right_segments();
         if segments.is_empty() {
@@ -953,50 +2279,267 @@ impl<'a> RenderModel<'a> {
     }
 
     fn collect_right_segments(&self) -> Vec<PowerlineSegment> {
-        let mut segments: Vec<PowerlineSegment> = Vec::new();
         // 88code segment is only shown in run_pill (input box area), not in bottom status line
         // to avoid duplicate display
-        if self.env.devspace
-            && let Some(devspace) = self.snapshot.environment.devspace.as_ref()
-        {
-            let icon = devspace_icon(&devspace.name);
-            let text = format!("{icon}{}", truncate_graphemes(&devspace.name, 16));
-            if !text.trim().is_empty() {
-                segments.push(PowerlineSegment::text(MAUVE, text));
+        self.snapshot
+            .layout
+            .right
+            .iter()
+            .filter_map(|slot| self.segment(*slot))
+            .collect()
+    }
+
+    fn provider_segment(&self) -> Option<PowerlineSegment> {
+        let provider = self.snapshot.provider.as_ref()?;
+        let max_width = max_width_columns(self.snapshot.max_widths.provider);
+        let text = match (self.provider_variant, provider.profile.as_ref()) {
+            (ProviderVariant::Hidden, _) => return None,
+            (ProviderVariant::ProfileAndProvider, Some(profile)) => format!(
+                "{PROVIDER_ICON}{} @ {}",
+                truncate_to_width(profile, max_width),
+                truncate_to_width(&provider.name, max_width)
+            ),
+            _ => format!(
+                "{PROVIDER_ICON}{}",
+                truncate_to_width(&provider.name, max_width)
+            ),
+        };
+        Some(PowerlineSegment::text(TEAL, text))
+    }
+
+    fn diff_stats_segment(&self) -> Option<PowerlineSegment> {
+        if !self.show_diff_stats {
+            return None;
+        }
+        let stats = self.snapshot.diff_stats.as_ref()?;
+        Some(PowerlineSegment::text(PEACH, diff_stats_text(stats)))
+    }
+
+    fn custom_segment(&self) -> Option<PowerlineSegment> {
+        if !self.show_custom {
+            return None;
+        }
+        let text = self.snapshot.custom.as_ref()?;
+        Some(PowerlineSegment::text(
+            ROSEWATER,
+            format!(
+                "{CUSTOM_ICON}{}",
+                truncate_to_width(text, max_width_columns(self.snapshot.max_widths.custom))
+            ),
+        ))
+    }
+
+    fn attention_segment(&self) -> Option<PowerlineSegment> {
+        let attention = self.snapshot.attention.as_ref()?;
+        let mut spans = vec![Span::styled(
+            format!("{ATTENTION_ICON}{}", attention.count),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        match self.attention_variant {
+            AttentionVariant::Hidden => return None,
+            AttentionVariant::Badge => {}
+            AttentionVariant::BadgeAndMessage => {
+                let max_width = max_width_columns(self.snapshot.max_widths.attention);
+                spans.push(format!(" {}", truncate_to_width(&attention.latest, max_width)).into());
             }
         }
-        if self.env.hostname
-            && let Some(host) = self.snapshot.environment.hostname.as_ref()
-        {
-            let text = format!("{HOSTNAME_ICON}{}", truncate_graphemes(host, 20));
-            segments.push(PowerlineSegment::text(ROSEWATER, text));
+        Some(PowerlineSegment::from_spans(
+            attention_color(attention.severity),
+            spans,
+        ))
+    }
+
+    fn process_segment(&self) -> Option<PowerlineSegment> {
+        if !self.show_process {
+            return None;
         }
-        if let Some(git) = self.build_git_segment() {
-            segments.push(git);
+        let process = self.snapshot.process.as_ref()?;
+        Some(PowerlineSegment::text(
+            LAVENDER,
+            format!("{PROCESS_ICON}{}", process::process_text(process)),
+        ))
+    }
+
+    /// `  ▰▰▰▰▰▱▱▱ 62%`, colored like the context bar.
+    fn budget_segment(&self) -> Option<PowerlineSegment> {
+        if !self.show_budget {
+            return None;
         }
-        if self.env.aws_profile
-            && let Some(profile) = self.snapshot.environment.aws_profile.as_ref()
-        {
-            let trimmed = profile.trim_start_matches("export AWS_PROFILE=");
-            let text = format!("{AWS_ICON}{}", truncate_graphemes(trimmed, 16));
-            segments.push(PowerlineSegment::text(PEACH, text));
+        let budget = self.snapshot.budget.as_ref()?;
+        let (accent, _) = context_bar_colors(budget.percent_used as f64);
+        Some(PowerlineSegment::text(
+            accent,
+            format!(
+                "{BUDGET_ICON}{} {}%",
+                budget_bar(budget.percent_used),
+                budget.percent_used
+            ),
+        ))
+    }
+
+    fn clock_segment(&self) -> Option<PowerlineSegment> {
+        if !self.show_clock {
+            return None;
         }
-        if self.env.kubernetes
-            && let Some(ctx) = self.snapshot.environment.kubernetes_context.as_ref()
-        {
-            let trimmed = ctx
-                .trim_start_matches("arn:aws:eks:")
-                .trim_start_matches("gke_");
-            let text = format!("{K8S_ICON}{}", truncate_graphemes(trimmed, 18));
-            segments.push(PowerlineSegment::text(TEAL, text));
+        let clock = self.snapshot.clock.as_ref()?;
+        Some(PowerlineSegment::text(SKY, format!("{CLOCK_ICON}{clock}")))
+    }
+
+    fn build_segment(&self) -> Option<PowerlineSegment> {
+        if !self.show_build {
+            return None;
         }
-        segments
+        let text = match (
+            self.snapshot.build.as_deref(),
+            self.snapshot.update.as_deref(),
+        ) {
+            (Some(build), Some(update)) => format!("{BUILD_ICON}v{build} {UPDATE_ICON}{update}"),
+            (Some(build), None) => format!("{BUILD_ICON}v{build}"),
+            (None, Some(update)) => format!("{UPDATE_ICON}{update}"),
+            (None, None) => return None,
+        };
+        Some(PowerlineSegment::text(SUBTEXT0, text))
+    }
+
+    /// The last characters of the session id and the key that copies the
+    /// full id or the resume command.
+    fn session_segment(&self) -> Option<PowerlineSegment> {
+        let session = self.snapshot.session.as_ref()?;
+        let mut spans = vec![Span::from(format!("{SESSION_ICON}{session}"))];
+        match self.session_variant {
+            SessionVariant::Hidden => return None,
+            SessionVariant::Id => {}
+            SessionVariant::IdAndKey => {
+                spans.push(" ".into());
+                spans.push(key_hint::ctrl(KeyCode::Char('o')).into());
+            }
+        }
+        Some(PowerlineSegment::from_spans(SUBTEXT0, spans))
+    }
+
+    fn policy_segment(&self) -> Option<PowerlineSegment> {
+        let policy = self.snapshot.policy.as_ref()?;
+        let text = match self.policy_variant {
+            PolicyVariant::Hidden => return None,
+            PolicyVariant::SandboxOnly => {
+                let short = match policy.sandbox.as_str() {
+                    "read-only" => "ro",
+                    "workspace-write" => "write",
+                    "full-access" => "full",
+                    other => other,
+                };
+                format!("{POLICY_ICON}{short}")
+            }
+            PolicyVariant::SandboxAndApproval => {
+                format!("{POLICY_ICON}{} · {}", policy.sandbox, policy.approval)
+            }
+        };
+        Some(PowerlineSegment::text(policy_color(policy.risk), text))
+    }
+
+    fn dev_env_segment(&self) -> Option<PowerlineSegment> {
+        if !self.env.dev_env {
+            return None;
+        }
+        let dev_env = self.snapshot.environment.dev_env.as_ref()?;
+        let text = format!("{DEV_ENV_ICON}{}", dev_env_text(dev_env)?);
+        Some(PowerlineSegment::text(GREEN, text))
+    }
+
+    fn devspace_segment(&self) -> Option<PowerlineSegment> {
+        if !self.env.devspace {
+            return None;
+        }
+        let devspace = self.snapshot.environment.devspace.as_ref()?;
+        let text = format!(
+            "{} {}",
+            devspace.icon(),
+            truncate_to_width(
+                &devspace.name,
+                max_width_columns(self.snapshot.max_widths.devspace)
+            )
+        );
+        (!text.trim().is_empty()).then(|| PowerlineSegment::text(devspace.accent(), text))
+    }
+
+    fn hostname_segment(&self) -> Option<PowerlineSegment> {
+        if !self.env.hostname {
+            return None;
+        }
+        let environment = &self.snapshot.environment;
+        let host = environment.hostname.as_ref()?;
+        let icon = if environment.ssh {
+            SSH_ICON
+        } else {
+            HOSTNAME_ICON
+        };
+        let text = format!(
+            "{icon}{}",
+            truncate_to_width(host, max_width_columns(self.snapshot.max_widths.hostname))
+        );
+        let accent = environment
+            .host_color
+            .as_deref()
+            .and_then(|color| Color::from_str(color).ok())
+            .unwrap_or(ROSEWATER);
+        Some(PowerlineSegment::text(accent, text))
+    }
+
+    fn aws_profile_segment(&self) -> Option<PowerlineSegment> {
+        if !self.env.aws_profile {
+            return None;
+        }
+        let profile = self.snapshot.environment.aws_profile.as_ref()?;
+        let trimmed = profile.trim_start_matches("export AWS_PROFILE=");
+        let text = format!(
+            "{AWS_ICON}{}",
+            truncate_to_width(
+                trimmed,
+                max_width_columns(self.snapshot.max_widths.aws_profile)
+            )
+        );
+        Some(PowerlineSegment::text(PEACH, text))
+    }
+
+    /// Red for production-looking workspaces, where an apply hurts most.
+    fn iac_segment(&self) -> Option<PowerlineSegment> {
+        if !self.env.iac {
+            return None;
+        }
+        let iac = self.snapshot.environment.iac.as_ref()?;
+        let accent = if iac.is_production() { RED } else { MAUVE };
+        let text = format!(
+            "{IAC_ICON}{}",
+            truncate_to_width(
+                &iac.workspace,
+                max_width_columns(self.snapshot.max_widths.iac)
+            )
+        );
+        Some(PowerlineSegment::text(accent, text))
+    }
+
+    fn kubernetes_segment(&self) -> Option<PowerlineSegment> {
+        if !self.env.kubernetes {
+            return None;
+        }
+        let ctx = self.snapshot.environment.kubernetes_context.as_ref()?;
+        let trimmed = ctx
+            .trim_start_matches("arn:aws:eks:")
+            .trim_start_matches("gke_");
+        let text = format!(
+            "{K8S_ICON}{}",
+            truncate_to_width(
+                trimmed,
+                max_width_columns(self.snapshot.max_widths.kubernetes)
+            )
+        );
+        Some(PowerlineSegment::text(TEAL, text))
     }
 
     fn build_git_segment(&self) -> Option<PowerlineSegment> {
         let git = self.snapshot.git.as_ref()?;
-        let branch = git.branch.as_ref()?;
-        let mut text = format!("{GIT_ICON}{branch}");
+        let label = git.head_label()?;
+        let mut text = format!("{GIT_ICON}{label}");
         if git.dirty {
             text.push('*');
         }
@@ -1006,7 +2549,27 @@ impl<'a> RenderModel<'a> {
         if let Some(behind) = git.behind.filter(|value| *value > 0) {
             text.push_str(&format!(" ↓{behind}"));
         }
-        Some(PowerlineSegment::text(SKY, truncate_graphemes(&text, 24)))
+        if let Some(base) = git_base_text(git) {
+            text.push_str(&format!(" {base}"));
+        }
+        let mut spans = vec![Span::from(truncate_to_width(
+            &text,
+            max_width_columns(self.snapshot.max_widths.git),
+        ))];
+        if let Some(pull_request) = git.pull_request.as_ref() {
+            // Without checks the number keeps the segment's own text color.
+            let color = match pull_request.checks {
+                PullRequestChecks::None => None,
+                PullRequestChecks::Pending => Some(YELLOW_LIGHT),
+                PullRequestChecks::Passing => Some(GREEN_LIGHT),
+                PullRequestChecks::Failing => Some(RED_LIGHT),
+            };
+            let mut style = Style::default().add_modifier(Modifier::BOLD);
+            style.fg = color;
+            spans.push(" ".into());
+            spans.push(Span::styled(format!("#{}", pull_request.number), style));
+        }
+        Some(PowerlineSegment::from_spans(SKY, spans))
     }
 
     fn build_88code_segment(&self) -> Option<PowerlineSegment> {
@@ -1033,6 +2596,17 @@ impl<'a> RenderModel<'a> {
             return Some(PowerlineSegment::text(RED, err_text));
         }
 
+        // Stale state - the last poll failed; keep the old numbers, dimmed
+        if let Some(age) = stale_88code_age(info, self.now) {
+            if self.env.code88_variant == Code88Variant::Hidden {
+                return None;
+            }
+            return Some(PowerlineSegment::text(
+                SUBTEXT0,
+                stale_88code_text(info, age),
+            ));
+        }
+
         // Loading state - data not yet fetched
         if info.service_tier.is_none() && info.daily_tokens.is_none() {
             return Some(PowerlineSegment::text(SUBTEXT0, "88code ...".to_string()));
@@ -1047,12 +2621,13 @@ impl<'a> RenderModel<'a> {
         let cache_create = info.cache_create_tokens.unwrap_or(0);
         let cache_read = info.cache_read_tokens.unwrap_or(0);
 
-        // Format tokens (e.g., 287093 -> "287.09K", 11918798 -> "11.92M")
-        let total_str = format_token_count(daily_tokens);
-        let input_str = format_token_count(input_tokens);
-        let output_str = format_token_count(output_tokens);
-        let cache_in_str = format_token_count(cache_create);
-        let cache_out_str = format_token_count(cache_read);
+        // Format tokens (e.g., 287093 -> "287K", 11918798 -> "11.9M")
+        let token_format = self.snapshot.token_format;
+        let total_str = token_format.format(daily_tokens);
+        let input_str = token_format.format(input_tokens);
+        let output_str = token_format.format(output_tokens);
+        let cache_in_str = token_format.format(cache_create);
+        let cache_out_str = token_format.format(cache_read);
 
         // Format based on variant:
         // Full: "88 LV5 ↑287K ↓68K ⇆864K/10.7M Σ11.9M $12.57"
@@ -1093,9 +2668,7 @@ impl<'a> RenderModel<'a> {
             return Some((Vec::new(), 0));
         }
         match self.context_variant {
-            ContextVariant::Hidden => {
-                Some((vec![span(" ".repeat(width), Style::default())], width))
-            }
+            ContextVariant::Hidden => Some((self.middle_filler(width), width)),
             ContextVariant::Compact => self
                 .render_context_compact(width)
                 .map(|spans| (spans, width)),
@@ -1105,21 +2678,111 @@ impl<'a> RenderModel<'a> {
 
     fn render_context_compact(&self, width: usize) -> Option<Vec<Span<'static>>> {
         let context = self.snapshot.context.as_ref()?;
+        let (text, style) = self.context_compact_text(context);
+        let display_width = UnicodeWidthStr::width(text.as_str()) + self.compact_hint_width();
+        if display_width > width {
+            return None;
+        }
+        let mut spans = self.compact_hint_spans();
+        spans.push(span(text, style));
+        if width > display_width {
+            spans.extend(self.middle_filler(width - display_width));
+        }
+        Some(spans)
+    }
+
+    /// Blank space beside the context, or the queued messages in
+    /// `middle_queue` listed dim when there is room for them.
+    fn middle_filler(&self, width: usize) -> Vec<Span<'static>> {
+        let queue = middle_queue_text(&self.snapshot.middle_queue);
+        if queue.is_empty() || width < MIDDLE_QUEUE_MIN_WIDTH {
+            return vec![span(" ".repeat(width), Style::default())];
+        }
+        let queue = truncate_to_width(&queue, width - 2);
+        let padding = width - 1 - UnicodeWidthStr::width(queue.as_str());
+        vec![
+            span(" ", Style::default()),
+            span(queue, dim_text()),
+            span(" ".repeat(padding), Style::default()),
+        ]
+    }
+
+    fn context_compact_text(&self, context: &StatusLineContextSnapshot) -> (String, Style) {
         let percentage = if context.window > 0 {
             (context.tokens_in_context as f64 / context.window as f64 * 100.0).clamp(0.0, 100.0)
         } else {
             0.0
         };
-        let text = format!("{CONTEXT_ICON} {percentage:.1}%");
-        let display_width = UnicodeWidthStr::width(text.as_str());
-        if display_width > width {
-            return None;
+        match self.context_warning_pulse(context) {
+            Some(pulse) => (
+                format!("{CONTEXT_ICON} {CONTEXT_WARNING_HINT}{percentage:.1}%"),
+                accent_fg(RED).add_modifier(pulse),
+            ),
+            None => (format!("{CONTEXT_ICON} {percentage:.1}%"), dim_text()),
         }
-        let mut spans = vec![span(text, dim_text())];
-        if width > display_width {
-            spans.push(span(" ".repeat(width - display_width), Style::default()));
+    }
+
+    /// `press ctrl+k to compact`, while usage is past the hint threshold and
+    /// the hint has not been degraded away.
+    fn compact_hint_spans(&self) -> Vec<Span<'static>> {
+        if !self.show_compact_hint {
+            return Vec::new();
         }
-        Some(spans)
+        vec![
+            "press ".dim(),
+            key_hint::ctrl(KeyCode::Char('k')).into(),
+            " to compact ".dim(),
+        ]
+    }
+
+    fn compact_hint_width(&self) -> usize {
+        self.compact_hint_spans().iter().map(Span::width).sum()
+    }
+
+    /// Spans before the context bar: the compaction hint, or the low-context
+    /// warning while the hint is hidden.
+    fn context_bar_prefix(&self, warning: bool) -> Vec<Span<'static>> {
+        if self.show_compact_hint {
+            self.compact_hint_spans()
+        } else if warning {
+            vec![span(CONTEXT_WARNING_HINT, accent_fg(RED))]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn context_bar_texts(context: &StatusLineContextSnapshot) -> (String, String) {
+        (
+            format!("{CONTEXT_ICON}Context "),
+            format!(" {:.1}% left", f64::from(context.percent_remaining)),
+        )
+    }
+
+    /// Narrowest middle area in which the context bar still draws, prefix
+    /// included.
+    fn context_bar_min_width(&self, context: &StatusLineContextSnapshot) -> usize {
+        let (label, percent_text) = Self::context_bar_texts(context);
+        let prefix = self.context_bar_prefix(self.context_warning_pulse(context).is_some());
+        let prefix_width: usize = prefix.iter().map(Span::width).sum();
+        CONTEXT_PADDING * 2
+            + prefix_width
+            + UnicodeWidthStr::width(label.as_str())
+            + UnicodeWidthStr::width(percent_text.as_str())
+            + 2
+            + 4
+    }
+
+    /// Pulse phase of the low-context warning: bold on even intervals,
+    /// dimmed on odd ones. `None` when no warning is active.
+    fn context_warning_pulse(&self, context: &StatusLineContextSnapshot) -> Option<Modifier> {
+        let since = context.low_since?;
+        let pulses = self.now.saturating_duration_since(since).as_millis()
+            / CONTEXT_WARNING_PULSE.as_millis();
+        Some(if pulses.is_multiple_of(2) {
+            Modifier::BOLD
+        } else {
+            Modifier::DIM
+        })
     }
 
     fn render_context_bar(&self, width: usize) -> Option<Vec<Span<'static>>> {
@@ -1132,12 +2795,14 @@ impl<'a> RenderModel<'a> {
         let percent_remaining = f64::from(context.percent_remaining);
         let percent_used = (100.0 - percent_remaining).clamp(0.0, 100.0);
 
-        let label = format!("{CONTEXT_ICON}Context ");
-        let percent_text = format!(" {percent_remaining:.1}% left");
+        let pulse = self.context_warning_pulse(context);
+        let prefix = self.context_bar_prefix(pulse.is_some());
+        let (label, percent_text) = Self::context_bar_texts(context);
         let label_width = UnicodeWidthStr::width(label.as_str());
         let percent_width = UnicodeWidthStr::width(percent_text.as_str());
+        let prefix_width: usize = prefix.iter().map(Span::width).sum();
         let curves_width = 2usize;
-        let text_width = label_width + percent_width + curves_width;
+        let text_width = prefix_width + label_width + percent_width + curves_width;
         if available <= text_width {
             return Some(vec![span(" ".repeat(width), Style::default())]);
         }
@@ -1148,26 +2813,41 @@ impl<'a> RenderModel<'a> {
         }
 
         let filled = ((fill_width as f64) * (percent_used / 100.0)).round() as usize;
-        let (accent, light_bg) = context_bar_colors(percent_used);
+        let (accent, light_bg) = if pulse.is_some() {
+            (RED, RED_LIGHT)
+        } else {
+            context_bar_colors(percent_used)
+        };
 
         let mut spans: Vec<Span<'static>> = Vec::new();
         spans.push(span(" ".repeat(CONTEXT_PADDING), Style::default()));
+        spans.extend(prefix);
         spans.push(span(LEFT_CURVE, accent_fg(accent)));
         spans.push(span(label, segment_fill(accent)));
         spans.extend(build_progress_bar(fill_width, filled, accent, light_bg));
         spans.push(span(percent_text, segment_fill(accent)));
         spans.push(span(RIGHT_CURVE, accent_fg(accent)));
         spans.push(span(" ".repeat(CONTEXT_PADDING), Style::default()));
+        if let Some(pulse) = pulse {
+            for span in &mut spans {
+                span.style = span.style.add_modifier(pulse);
+            }
+        }
         Some(spans)
     }
 }
 
 fn degrade_run_capsule(model: &mut RenderModel<'_>) -> bool {
     const OPS: &[DegradeOp] = &[
-        DegradeOp::DropQueuePreview,
+        DegradeOp::HideInterruptHint,
+        DegradeOp::CollapseQueuePreview,
+        DegradeOp::HideTurnTiming,
+        DegradeOp::HideExecFailureCommand,
         DegradeOp::Simplify88Code,
         DegradeOp::Drop88Code,
         DegradeOp::HideRunTimer,
+        DegradeOp::DropQueuePreview,
+        DegradeOp::CompactApprovals,
     ];
     for op in OPS {
         if model.apply_degrade(*op) {
@@ -1194,6 +2874,11 @@ impl PowerlineSegment {
         Self { accent, spans }
     }
 
+    /// Width of `into_padded_spans` without building it.
+    fn padded_width(&self) -> usize {
+        2 + self.spans.iter().map(Span::width).sum::<usize>()
+    }
+
     fn into_padded_spans(self) -> Vec<Span<'static>> {
         let mut output = Vec::with_capacity(self.spans.len() + 2);
         output.push(pad_segment_span(self.accent));
@@ -1237,55 +2922,139 @@ fn apply_segment_fill(span: &mut Span<'static>, accent: Color) {
     }
 }
 
-fn truncate_graphemes(text: &str, max_graphemes: usize) -> String {
-    if max_graphemes == 0 {
-        return String::new();
-    }
-    let graphemes: Vec<&str> = text.graphemes(true).collect();
-    if graphemes.len() <= max_graphemes {
-        return text.to_string();
-    }
-    if max_graphemes == 1 {
-        return "…".to_string();
-    }
-    let mut truncated = graphemes[..max_graphemes - 1].concat();
-    truncated.push('…');
-    truncated
+/// A `[tui.statusline.max_widths]` entry as a column count.
+fn max_width_columns(width: i64) -> usize {
+    usize::try_from(width).unwrap_or(0)
 }
 
-fn queue_preview(commands: &[String]) -> (String, usize) {
-    if commands.is_empty() {
-        return (String::new(), 0);
+/// `⚠ 2 approvals` while approval requests wait on the user.
+fn pending_approvals_text(count: i64) -> Option<String> {
+    match count {
+        ..=0 => None,
+        1 => Some("⚠ 1 approval".to_string()),
+        count => Some(format!("⚠ {count} approvals")),
     }
-    let raw = commands
-        .first()
-        .map(|value| value.lines().next().unwrap_or(""))
-        .unwrap_or("");
-    let normalized = raw.split_whitespace().collect::<Vec<_>>().join(" ");
-    let mut preview = if normalized.is_empty() {
-        String::new()
-    } else {
-        normalized
-    };
+}
 
-    const MAX_WIDTH: usize = 32;
-    let width = UnicodeWidthStr::width(preview.as_str());
-    if width > MAX_WIDTH {
-        let mut truncated = String::new();
-        let mut used = 0usize;
-        for grapheme in preview.graphemes(true) {
-            let g_width = UnicodeWidthStr::width(grapheme);
-            if used + g_width > MAX_WIDTH.saturating_sub(1) {
-                break;
-            }
-            truncated.push_str(grapheme);
-            used += g_width;
-        }
-        truncated.push('…');
-        preview = truncated;
+/// `✗ exit 1 · cargo test` while the failure is recent, `✗` afterwards.
+fn exec_failure_text(failure: &StatusLineExecFailure, show_command: bool) -> String {
+    if show_command && failure.recent {
+        format!(
+            "{EXEC_FAILURE_ICON} exit {} · {}",
+            failure.exit_code, failure.command
+        )
+    } else {
+        EXEC_FAILURE_ICON.to_string()
+    }
+}
+
+/// `+312 −87`, the session's added and removed line counts.
+fn diff_stats_text(stats: &StatusLineDiffStatsSnapshot) -> String {
+    format!("+{} −{}", stats.added, stats.removed)
+}
+
+/// `▰▰▰▰▰▱▱▱` for 62%; full from 100% on.
+fn budget_bar(percent_used: i64) -> String {
+    let filled = (percent_used.clamp(0, 100) * BUDGET_BAR_WIDTH + 50) / 100;
+    (0..BUDGET_BAR_WIDTH)
+        .map(|cell| if cell < filled { '▰' } else { '▱' })
+        .collect()
+}
+
+/// `nix`, `nix pure`, `direnv` or `nix + direnv`; `None` when neither is
+/// active.
+fn dev_env_text(dev_env: &StatusLineDevEnvSnapshot) -> Option<String> {
+    let nix = dev_env.nix.map(|shell| match shell {
+        NixShell::Pure => "nix pure",
+        NixShell::Impure => "nix",
+    });
+    let direnv = dev_env.direnv.then_some("direnv");
+    let parts: Vec<&str> = nix.into_iter().chain(direnv).collect();
+    (!parts.is_empty()).then(|| parts.join(" + "))
+}
+
+/// `main↟12` when the branch has commits the base branch lacks.
+fn git_base_text(git: &StatusLineGitSnapshot) -> Option<String> {
+    let base = git.base.as_ref().filter(|base| base.ahead > 0)?;
+    Some(format!("{}↟{}", base.name, base.ahead))
+}
+
+/// `88 3.2 credits · 12m ago`: the last good numbers and how old they are.
+/// Credits when the subscription reports them, today's spend otherwise.
+fn stale_88code_text(info: &StatusLine88CodeSnapshot, age: Duration) -> String {
+    let value = match (info.credits_remaining, info.daily_cost) {
+        (Some(credits), _) => format!("{credits:.1} credits"),
+        (None, Some(cost)) => format!("${cost:.2}"),
+        (None, None) => "n/a".to_string(),
+    };
+    let (age, _) = stale_age(age);
+    format!("88 {value} · {age} ago")
+}
+
+/// How old the numbers of a stale 88code snapshot are; `None` while they
+/// are fresh.
+pub(crate) fn stale_88code_age(info: &StatusLine88CodeSnapshot, now: Instant) -> Option<Duration> {
+    info.stale.then(|| {
+        info.fetched_at
+            .map(|at| now.saturating_duration_since(at))
+            .unwrap_or_default()
+    })
+}
+
+/// `age` as the stale segment shows it (`42s`, `12m`, `2h`, `3d`), and how
+/// long until that text changes.
+pub(crate) fn stale_age(age: Duration) -> (String, Duration) {
+    let secs = age.as_secs();
+    let (unit, suffix) = match secs {
+        0..60 => (1, "s"),
+        60..3_600 => (60, "m"),
+        3_600..86_400 => (3_600, "h"),
+        _ => (86_400, "d"),
+    };
+    let into_bucket = age.saturating_sub(Duration::from_secs(secs - secs % unit));
+    (
+        format!("{}{suffix}", secs / unit),
+        Duration::from_secs(unit).saturating_sub(into_bucket),
+    )
+}
+
+/// First line of each queued message, whitespace collapsed, joined with
+/// commas.
+fn middle_queue_text(messages: &[String]) -> String {
+    messages
+        .iter()
+        .map(|message| {
+            let first_line = message.lines().next().unwrap_or("");
+            first_line.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .filter(|message| !message.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// First line of the previewed queued message, cut to 32 columns, plus where
+/// it sits in the queue: `+2` for the messages behind the first one, or `2/3`
+/// while previews cycle through the queue.
+fn queue_preview(commands: &[String], index: Option<i64>) -> (String, Option<String>) {
+    if commands.is_empty() {
+        return (String::new(), None);
     }
+    let shown = usize::try_from(index.unwrap_or(0)).unwrap_or(0) % commands.len();
+    let raw = commands
+        .get(shown)
+        .map(|value| value.lines().next().unwrap_or(""))
+        .unwrap_or("");
+    let normalized = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    let preview = truncate_to_width(&normalized, QUEUE_PREVIEW_WIDTH);
 
-    (preview, commands.len().saturating_sub(1))
+    let position = match index {
+        Some(_) if commands.len() > 1 => Some(format!("{}/{}", shown + 1, commands.len())),
+        _ => {
+            let extra = commands.len() - 1;
+            (extra > 0).then(|| format!("+{extra}"))
+        }
+    };
+    (preview, position)
 }
 
 fn build_progress_bar(
@@ -1322,17 +3091,21 @@ fn select_progress_char(position: usize, fill_width: usize, filled_width: usize)
     }
 }
 
-fn format_token_count(value: i64) -> String {
-    const MILLION: f64 = 1_000_000.0;
-    const THOUSAND: f64 = 1_000.0;
-    let clamped = value.max(0);
-    let value_f64 = clamped as f64;
-    if value_f64 >= MILLION {
-        format!("{:.2}M", value_f64 / MILLION)
-    } else if value_f64 >= THOUSAND {
-        format!("{:.2}K", value_f64 / THOUSAND)
+/// `✓ Applied patch in 2m 05s · 3.1K tokens`, the run pill right after a
+/// task finishes; `⏹ Applied patch interrupted after 2m 05s` when the user
+/// stopped it.
+fn run_summary_text(summary: &StatusLineRunSummary, snapshot: &StatusLineSnapshot) -> String {
+    let elapsed = snapshot.timer_display.format_elapsed(summary.elapsed_secs);
+    let icon = summary.icon();
+    let label = &summary.label;
+    let text = if summary.interrupted {
+        format!("{icon} {label} interrupted after {elapsed}")
     } else {
-        clamped.to_string()
+        format!("{icon} {label} in {elapsed}")
+    };
+    match summary.tokens {
+        0 => text,
+        tokens => format!("{text} · {} tokens", snapshot.token_format.format(tokens)),
     }
 }
 
@@ -1352,6 +3125,15 @@ fn select_model_icon(model: &str) -> char {
     }
 }
 
+/// Icon for the `index`th registered devspace, so registered devspaces
+/// only share icons once there are more of them than icons.
+fn registered_devspace_icon(index: usize) -> &'static str {
+    match DEVSPACE_ICONS {
+        [] => "󰠖 ",
+        icons => icons[index % icons.len()],
+    }
+}
+
 fn devspace_icon(name: &str) -> &'static str {
     match DEVSPACE_ICONS {
         [] => "󰠖 ",
@@ -1365,7 +3147,7 @@ fn devspace_icon(name: &str) -> &'static str {
     }
 }
 
-fn context_bar_colors(percent_used: f64) -> (Color, Color) {
+pub(crate) fn context_bar_colors(percent_used: f64) -> (Color, Color) {
     match percent_used {
         value if value <= 60.0 => (GREEN, GREEN_LIGHT),
         value if value <= 80.0 => (YELLOW, YELLOW_LIGHT),
@@ -1393,15 +3175,199 @@ mod tests {
         assert_eq!(format_elapsed_compact(3_661), "1h 01m 01s");
     }
 
+    #[test]
+    fn timer_display_formats_and_waits_for_min_secs() {
+        let display = |format| TimerDisplay {
+            format,
+            min_secs: 5,
+        };
+        let clock = display(StatusLineTimerFormat::MinutesSeconds);
+        assert_eq!(clock.format_elapsed(62), "01:02");
+        assert_eq!(clock.format_elapsed(3_661), "61:01");
+        assert_eq!(
+            display(StatusLineTimerFormat::HoursMinutesSeconds).format_elapsed(3_661),
+            "01:01:01"
+        );
+        assert_eq!(clock.run_timer_text(Duration::from_secs(4)), None);
+        assert_eq!(
+            clock.run_timer_text(Duration::from_secs(5)).as_deref(),
+            Some("00:05")
+        );
+    }
+
+    #[test]
+    fn git_base_text_shows_only_commits_ahead() {
+        let git = |ahead| StatusLineGitSnapshot {
+            branch: Some("feature".to_string()),
+            base: Some(StatusLineGitBaseSnapshot {
+                name: "main".to_string(),
+                ahead,
+            }),
+            ..StatusLineGitSnapshot::default()
+        };
+        assert_eq!(git_base_text(&git(12)).as_deref(), Some("main↟12"));
+        assert_eq!(git_base_text(&git(0)), None);
+        assert_eq!(git_base_text(&StatusLineGitSnapshot::default()), None);
+    }
+
+    #[test]
+    fn stale_age_changes_at_each_bucket_boundary() {
+        assert_eq!(
+            stale_age(Duration::from_millis(42_250)),
+            ("42s".to_string(), Duration::from_millis(750))
+        );
+        assert_eq!(
+            stale_age(Duration::from_secs(12 * 60 + 5)),
+            ("12m".to_string(), Duration::from_secs(55))
+        );
+        assert_eq!(
+            stale_age(Duration::from_secs(3_600)),
+            ("1h".to_string(), Duration::from_secs(3_600))
+        );
+        assert_eq!(
+            stale_age(Duration::from_secs(2 * 86_400 + 10)),
+            ("2d".to_string(), Duration::from_secs(86_390))
+        );
+    }
+
+    #[test]
+    fn stale_88code_text_shows_the_age_of_the_numbers() {
+        let credits = StatusLine88CodeSnapshot {
+            credits_remaining: Some(3.24),
+            daily_cost: Some(1.5),
+            stale: true,
+            ..StatusLine88CodeSnapshot::default()
+        };
+        assert_eq!(
+            stale_88code_text(&credits, Duration::from_secs(12 * 60 + 5)),
+            "88 3.2 credits · 12m ago"
+        );
+        let spend = StatusLine88CodeSnapshot {
+            credits_remaining: None,
+            ..credits.clone()
+        };
+        assert_eq!(
+            stale_88code_text(&spend, Duration::from_secs(42)),
+            "88 $1.50 · 42s ago"
+        );
+        assert_eq!(
+            stale_88code_text(&spend, Duration::from_secs(2 * 3_600)),
+            "88 $1.50 · 2h ago"
+        );
+    }
+
+    #[test]
+    fn head_label_names_worktrees_and_detached_heads() {
+        let checkout = |kind, name: &str| StatusLineGitCheckout {
+            kind,
+            repo: "codex".to_string(),
+            name: name.to_string(),
+        };
+        let worktree = StatusLineGitSnapshot {
+            branch: Some("fix-tests".to_string()),
+            checkout: Some(checkout(GitCheckoutKind::Worktree, "fix-tests")),
+            ..StatusLineGitSnapshot::default()
+        };
+        assert_eq!(worktree.head_label().as_deref(), Some("codex:fix-tests"));
+
+        let submodule = StatusLineGitSnapshot {
+            detached: Some("1f2e3d4".to_string()),
+            checkout: Some(checkout(GitCheckoutKind::Submodule, "vendor/lib")),
+            ..StatusLineGitSnapshot::default()
+        };
+        assert_eq!(
+            submodule.head_label().as_deref(),
+            Some("codex:vendor/lib @1f2e3d4")
+        );
+
+        let bare = StatusLineGitSnapshot {
+            branch: Some("main".to_string()),
+            checkout: Some(checkout(GitCheckoutKind::Bare, "")),
+            ..StatusLineGitSnapshot::default()
+        };
+        assert_eq!(bare.head_label().as_deref(), Some("codex.git main"));
+        assert_eq!(StatusLineGitSnapshot::default().head_label(), None);
+    }
+
+    #[test]
+    fn max_widths_trade_git_width_for_hostname_width() {
+        let branch = "feature/a-rather-long-branch-name";
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        if let Some(git) = snapshot.git.as_mut() {
+            git.branch = Some(branch.to_string());
+        }
+        let render = |snapshot: &StatusLineSnapshot| -> String {
+            render_status_line(snapshot, 240, Instant::now())
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+
+        let text = render(&snapshot);
+        assert!(!text.contains(branch), "{text}");
+        assert!(text.contains("vermissian"), "{text}");
+
+        snapshot.max_widths = StatusLineMaxWidths {
+            git: 48,
+            hostname: 6,
+            ..StatusLineMaxWidths::default()
+        };
+        let text = render(&snapshot);
+        assert!(text.contains(branch), "{text}");
+        assert!(text.contains("vermi…"), "{text}");
+    }
+
     #[test]
     fn queue_preview_handles_extra_count() {
         let long = "x".repeat(80);
-        let (preview, extra) = queue_preview(&[long, "second".to_string(), "third".to_string()]);
+        let queue = [long, "second".to_string(), "third".to_string()];
+        let (preview, position) = queue_preview(&queue, None);
         assert!(preview.ends_with('…'));
-        assert_eq!(extra, 2);
+        assert_eq!(position.as_deref(), Some("+2"));
         assert!(UnicodeWidthStr::width(preview.as_str()) <= 32);
     }
 
+    #[test]
+    fn queue_preview_measures_wide_and_joined_text_in_columns() {
+        let preview = |text: &str| queue_preview(&[text.to_string()], None).0;
+        assert_eq!(
+            preview("修复状态栏中的宽字符截断问题并补充测试用例以及文档说明"),
+            "修复状态栏中的宽字符截断问题并…"
+        );
+        assert_eq!(
+            preview(&"e\u{301}".repeat(40)),
+            format!("{}…", "e\u{301}".repeat(31))
+        );
+        let coder = "\u{1f469}\u{200d}\u{1f4bb}";
+        let emoji = preview(&coder.repeat(20));
+        assert!(UnicodeWidthStr::width(emoji.as_str()) <= QUEUE_PREVIEW_WIDTH);
+        let kept = emoji.strip_suffix('…').expect("truncated");
+        assert!(!kept.is_empty() && kept.split(coder).all(str::is_empty));
+    }
+
+    #[test]
+    fn queue_preview_cycles_with_index() {
+        let queue = [
+            "first".to_string(),
+            "second".to_string(),
+            "third".to_string(),
+        ];
+        assert_eq!(
+            queue_preview(&queue, Some(1)),
+            ("second".to_string(), Some("2/3".to_string()))
+        );
+        assert_eq!(
+            queue_preview(&queue, Some(5)),
+            ("third".to_string(), Some("3/3".to_string()))
+        );
+        assert_eq!(
+            queue_preview(&queue[..1], Some(0)),
+            ("first".to_string(), None)
+        );
+    }
+
     #[test]
     fn context_bar_colors_follow_thresholds() {
         let (green, _) = context_bar_colors(10.0);
@@ -1421,6 +3387,8 @@ mod tests {
             model: Some(StatusLineModelSnapshot {
                 label: "codex-model".to_string(),
                 detail: Some("high".to_string()),
+                icon: None,
+                color: None,
             }),
             tokens: Some(StatusLineTokenSnapshot {
                 total: TokenCountSnapshot {
@@ -1429,7 +3397,7 @@ mod tests {
                     output_tokens: 424,
                     ..TokenCountSnapshot::default()
                 },
-                last: None,
+                ..StatusLineTokenSnapshot::default()
             }),
             context: Some(StatusLineContextSnapshot {
                 percent_remaining: 80,
@@ -1440,6 +3408,10 @@ mod tests {
                 dirty: true,
                 ahead: Some(1),
                 behind: None,
+                base: None,
+                pull_request: None,
+                checkout: None,
+                detached: None,
             }),
             environment: StatusLineEnvironmentSnapshot {
                 hostname: Some("vermissian".to_string()),
@@ -1498,6 +3470,678 @@ mod tests {
         assert!(repr.contains("⌥ + ↑"), "hint missing: {repr}");
     }
 
+    #[test]
+    fn provider_segment_follows_model_and_degrades_to_provider() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.provider = Some(StatusLineProviderSnapshot {
+            name: "Azure".to_string(),
+            profile: Some("work".to_string()),
+        });
+        let now = Instant::now();
+        let wide = snapshot_line_repr(&render_status_line(&snapshot, 200, now));
+        assert!(wide.contains("work @ Azure"), "provider missing: {wide}");
+        let model_at = wide.find("gpt-5-codex").expect("model segment");
+        assert!(wide.find("work @ Azure") > Some(model_at));
+
+        let degraded = (40..200)
+            .map(|width| snapshot_line_repr(&render_status_line(&snapshot, width, now)))
+            .find(|repr| repr.contains("Azure") && !repr.contains("work @"))
+            .expect("some width should drop the profile name");
+        assert!(degraded.contains("\\u{f0c2} Azure"), "{degraded}");
+    }
+
+    #[test]
+    fn clock_segment_renders_and_drops_before_environment() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.clock = Some("09:26".to_string());
+        let wide = snapshot_line_repr(&render_status_line(&snapshot, 200, Instant::now()));
+        assert!(wide.contains("09:26"), "clock missing: {wide}");
+        let narrow = (40..200)
+            .map(|width| snapshot_line_repr(&render_status_line(&snapshot, width, Instant::now())))
+            .find(|repr| repr.contains("vermissian") && !repr.contains("09:26"));
+        assert!(narrow.is_some(), "clock should drop before the hostname");
+    }
+
+    #[test]
+    fn banded_render_keeps_variants_while_resizing_within_a_band() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.clock = Some("09:26".to_string());
+        let now = Instant::now();
+        let shows_clock = |line: Line<'static>| snapshot_line_repr(&line).contains("09:26");
+        // Find where the clock drops, nudging the path until that happens
+        // inside a band rather than on its edge.
+        let mut threshold = 0;
+        for extra in 0..DEGRADE_BAND_COLUMNS as usize {
+            snapshot.cwd_display = Some(format!("~/workspace/codex{}", "x".repeat(extra)));
+            threshold = (40..200)
+                .find(|width| shows_clock(render_status_line(&snapshot, *width, now)))
+                .expect("clock shows at some width");
+            if threshold % DEGRADE_BAND_COLUMNS != 0 {
+                break;
+            }
+        }
+        let narrow = threshold - 1;
+        let wide = threshold;
+
+        let mut memos = DegradeMemos::new();
+        let mut banded = |snapshot: &StatusLineSnapshot, width| {
+            render_status_line_banded(snapshot, width, now, &mut memos)
+        };
+        assert!(shows_clock(banded(&snapshot, wide)));
+        assert!(!shows_clock(banded(&snapshot, narrow)));
+        assert!(
+            !shows_clock(banded(&snapshot, wide)),
+            "growing back within the band keeps the narrower variants"
+        );
+        assert!(
+            shows_clock(banded(&snapshot, wide + DEGRADE_BAND_COLUMNS)),
+            "crossing into the next band searches again"
+        );
+        assert!(
+            !shows_clock(banded(&snapshot, wide)),
+            "a render in another band leaves this band's memo alone"
+        );
+
+        snapshot.clock = Some("09:27".to_string());
+        assert!(
+            snapshot_line_repr(&banded(&snapshot, wide)).contains("09:27"),
+            "a content change searches again"
+        );
+    }
+
+    #[test]
+    fn session_segment_shows_short_id_and_drops_its_key_first() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.session = Some("0a1b2c3d".to_string());
+        let key: Span<'static> = key_hint::ctrl(KeyCode::Char('o')).into();
+        let text = |width| {
+            render_status_line(&snapshot, width, Instant::now())
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        };
+        let wide = text(300);
+        assert!(
+            wide.contains(&format!("{SESSION_ICON}0a1b2c3d {}", key.content)),
+            "session missing: {wide}"
+        );
+        let compact = (40..300)
+            .map(text)
+            .find(|line| line.contains("0a1b2c3d") && !line.contains(key.content.as_ref()));
+        assert!(compact.is_some(), "the key hint should drop before the id");
+    }
+
+    #[test]
+    fn dev_env_text_names_nix_and_direnv() {
+        let text = |nix, direnv| dev_env_text(&StatusLineDevEnvSnapshot { nix, direnv });
+        assert_eq!(text(None, false), None);
+        assert_eq!(text(Some(NixShell::Impure), false).as_deref(), Some("nix"));
+        assert_eq!(
+            text(Some(NixShell::Pure), false).as_deref(),
+            Some("nix pure")
+        );
+        assert_eq!(
+            text(Some(NixShell::Impure), true).as_deref(),
+            Some("nix + direnv")
+        );
+        assert_eq!(text(None, true).as_deref(), Some("direnv"));
+    }
+
+    #[test]
+    fn iac_segment_turns_red_for_production_workspaces() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        let accent = |snapshot: &StatusLineSnapshot| {
+            render_status_line(snapshot, 200, Instant::now())
+                .spans
+                .iter()
+                .find(|span| span.content.contains(IAC_ICON))
+                .and_then(|span| span.style.bg)
+        };
+        assert_eq!(accent(&snapshot), None);
+        for (workspace, color) in [("staging", MAUVE), ("prod", RED)] {
+            snapshot.environment.iac = Some(StatusLineIacSnapshot {
+                tool: iac::IacTool::Terraform,
+                workspace: workspace.to_string(),
+            });
+            assert_eq!(accent(&snapshot), Some(color), "{workspace}");
+        }
+    }
+
+    #[test]
+    fn process_segment_renders_footprint_and_drops_before_environment() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.process = Some(StatusLineProcessSnapshot {
+            rss_bytes: 412 * 1024 * 1024,
+            cpu_percent: 3,
+        });
+        let wide = snapshot_line_repr(&render_status_line(&snapshot, 200, Instant::now()));
+        assert!(wide.contains("412M 3%"), "process missing: {wide}");
+        let narrow = (40..200)
+            .map(|width| snapshot_line_repr(&render_status_line(&snapshot, width, Instant::now())))
+            .find(|repr| repr.contains("vermissian") && !repr.contains("412M"));
+        assert!(narrow.is_some(), "process should drop before the hostname");
+    }
+
+    #[test]
+    fn build_segment_renders_version_and_drops_before_environment() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.build = Some("0.58.0+1a2b3c4".to_string());
+        let wide = snapshot_line_repr(&render_status_line(&snapshot, 200, Instant::now()));
+        assert!(wide.contains("v0.58.0+1a2b3c4"), "build missing: {wide}");
+        let narrow = (40..200)
+            .map(|width| snapshot_line_repr(&render_status_line(&snapshot, width, Instant::now())))
+            .find(|repr| repr.contains("vermissian") && !repr.contains("1a2b3c4"));
+        assert!(narrow.is_some(), "build should drop before the hostname");
+    }
+
+    #[test]
+    fn update_chip_shows_with_or_without_the_build() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.update = Some("2025.12.3".to_string());
+        let alone = snapshot_line_repr(&render_status_line(&snapshot, 200, Instant::now()));
+        assert!(alone.contains("2025.12.3"), "update missing: {alone}");
+
+        snapshot.build = Some("0.58.0+1a2b3c4".to_string());
+        let both = snapshot_line_repr(&render_status_line(&snapshot, 200, Instant::now()));
+        assert!(
+            both.contains("v0.58.0+1a2b3c4") && both.contains("2025.12.3"),
+            "build or update missing: {both}"
+        );
+    }
+
+    #[test]
+    fn diff_stats_segment_renders_added_and_removed() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.diff_stats = Some(StatusLineDiffStatsSnapshot {
+            added: 312,
+            removed: 87,
+        });
+        let repr = snapshot_line_repr(&render_status_line(&snapshot, 200, Instant::now()));
+        assert!(repr.contains("+312 −87"), "diff stats missing: {repr}");
+    }
+
+    #[test]
+    fn budget_segment_draws_a_bar_in_the_context_colors() {
+        assert_eq!(budget_bar(0), "▱▱▱▱▱▱▱▱");
+        assert_eq!(budget_bar(62), "▰▰▰▰▰▱▱▱");
+        assert_eq!(budget_bar(130), "▰▰▰▰▰▰▰▰");
+
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.budget = Some(StatusLineBudgetSnapshot {
+            scope: "session".to_string(),
+            percent_used: 85,
+            spend: "$4.25 of $5.00".to_string(),
+        });
+        let line = render_status_line(&snapshot, 200, Instant::now());
+        let budget = line
+            .spans
+            .iter()
+            .find(|span| span.content.contains("▰▰▰▰▰▰▰▱ 85%"))
+            .expect("budget segment");
+        assert_eq!(budget.style.bg, Some(PEACH));
+    }
+
+    #[test]
+    fn model_segment_uses_configured_icon_and_color() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        let model_span = |snapshot: &StatusLineSnapshot| {
+            render_status_line(snapshot, 200, Instant::now())
+                .spans
+                .into_iter()
+                .find(|span| span.content == "gpt-5-codex")
+                .expect("model label")
+        };
+        assert_eq!(model_span(&snapshot).style.bg, Some(SKY));
+
+        if let Some(model) = snapshot.model.as_mut() {
+            model.icon = Some("G".to_string());
+            model.color = Some("#a6e3a1".to_string());
+        }
+        let line = render_status_line(&snapshot, 200, Instant::now());
+        assert!(line.spans.iter().any(|span| span.content == "G"));
+        assert_eq!(model_span(&snapshot).style.bg, Some(GREEN));
+
+        if let Some(model) = snapshot.model.as_mut() {
+            model.color = Some("not-a-color".to_string());
+        }
+        assert_eq!(model_span(&snapshot).style.bg, Some(SKY));
+    }
+
+    #[test]
+    fn attention_segment_shows_count_and_latest_in_severity_color() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        let spans = |snapshot: &StatusLineSnapshot| {
+            render_status_line(snapshot, 200, Instant::now())
+                .spans
+                .into_iter()
+                .map(|span| (span.content.to_string(), span.style.bg))
+                .collect::<Vec<_>>()
+        };
+        assert!(
+            !spans(&snapshot)
+                .iter()
+                .any(|(text, _)| text.starts_with(ATTENTION_ICON))
+        );
+
+        snapshot.attention = Some(StatusLineAttentionSnapshot {
+            severity: AttentionSeverity::Critical,
+            count: 3,
+            latest: "sign in again".to_string(),
+        });
+        let spans = spans(&snapshot);
+        let badge = spans
+            .iter()
+            .position(|(text, _)| *text == format!("{ATTENTION_ICON}3"))
+            .expect("count badge");
+        assert_eq!(spans[badge].1, Some(RED));
+        assert_eq!(spans[badge + 1].0, " sign in again");
+    }
+
+    #[test]
+    fn devspace_segment_uses_registered_icon_and_color() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        let devspace_span = |snapshot: &StatusLineSnapshot| {
+            render_status_line(snapshot, 200, Instant::now())
+                .spans
+                .into_iter()
+                .find(|span| span.content.ends_with("earth"))
+                .expect("devspace segment")
+        };
+        let span = devspace_span(&snapshot);
+        assert_eq!(span.content, format!("{}earth", devspace_icon("earth")));
+        assert_eq!(span.style.bg, Some(MAUVE));
+
+        snapshot.environment.devspace = Some(StatusLineDevspaceSnapshot {
+            name: "earth".to_string(),
+            icon: Some("E".to_string()),
+            color: Some("#a6e3a1".to_string()),
+            description: Some("payments".to_string()),
+        });
+        let span = devspace_span(&snapshot);
+        assert_eq!(span.content, "E earth");
+        assert_eq!(span.style.bg, Some(GREEN));
+    }
+
+    #[test]
+    fn hostname_segment_uses_alias_color_and_ssh_icon() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.environment.hostname = Some("PROD".to_string());
+        snapshot.environment.host_color = Some("red".to_string());
+        snapshot.environment.ssh = true;
+        let line = render_status_line(&snapshot, 200, Instant::now());
+        let host = line
+            .spans
+            .iter()
+            .find(|span| span.content.contains("PROD"))
+            .expect("hostname segment");
+        assert!(host.content.contains(SSH_ICON.trim()), "{host:?}");
+        assert_eq!(host.style.bg, Some(Color::Red));
+
+        snapshot.environment.host_color = Some("not-a-color".to_string());
+        snapshot.environment.ssh = false;
+        let line = render_status_line(&snapshot, 200, Instant::now());
+        let host = line
+            .spans
+            .iter()
+            .find(|span| span.content.contains("PROD"))
+            .expect("hostname segment");
+        assert!(host.content.contains(HOSTNAME_ICON.trim()), "{host:?}");
+        assert_eq!(host.style.bg, Some(ROSEWATER));
+    }
+
+    #[test]
+    fn run_pill_offers_pause_then_resume() {
+        let mut snapshot = sample_snapshot();
+        let now = Instant::now();
+        let renderer = DefaultStatusLineRenderer;
+        let repr = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 160, now));
+        assert!(repr.contains("interrupt"), "interrupt key missing: {repr}");
+        assert!(repr.contains(" pause"), "pause key missing: {repr}");
+
+        if let Some(state) = snapshot.run_state.as_mut() {
+            state.paused = true;
+        }
+        let line = renderer.render_run_pill(&snapshot, 160, now);
+        let repr = snapshot_line_repr(&line);
+        assert!(repr.contains(PAUSED_ICON), "pause sign missing: {repr}");
+        assert!(
+            repr.contains(PAUSED_STATUS_MESSAGE),
+            "label missing: {repr}"
+        );
+        assert!(repr.contains(" resume"), "resume key missing: {repr}");
+        assert!(!repr.contains("Applying patch"), "{repr}");
+        let label = line
+            .spans
+            .iter()
+            .find(|span| span.content == PAUSED_STATUS_MESSAGE)
+            .expect("paused label");
+        assert_eq!(label.style.bg, Some(MAUVE));
+
+        let narrow = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 40, now));
+        assert!(
+            !narrow.contains(" resume"),
+            "keys should go first: {narrow}"
+        );
+    }
+
+    #[test]
+    fn run_capsule_color_follows_the_task_category() {
+        let mut snapshot = sample_snapshot();
+        let now = Instant::now();
+        let renderer = DefaultStatusLineRenderer;
+        let label_bg = |snapshot: &StatusLineSnapshot| {
+            renderer
+                .render_run_pill(snapshot, 160, now)
+                .spans
+                .into_iter()
+                .find(|span| span.content == "Applying patch")
+                .and_then(|span| span.style.bg)
+        };
+        assert_eq!(label_bg(&snapshot), Some(MAUVE), "timer paused");
+
+        let state = snapshot.run_state.as_mut().expect("run state");
+        state.timer = Some(RunTimerSnapshot {
+            elapsed_running: Duration::from_secs(5),
+            last_resume_at: Some(now),
+            is_paused: false,
+        });
+        for (category, color) in [
+            (RunCategory::Thinking, LAVENDER),
+            (RunCategory::Exec, GREEN),
+            (RunCategory::Patch, PEACH),
+            (RunCategory::ToolCall, TEAL),
+            (RunCategory::Approval, YELLOW),
+        ] {
+            if let Some(state) = snapshot.run_state.as_mut() {
+                state.category = category;
+            }
+            assert_eq!(label_bg(&snapshot), Some(color), "{category:?}");
+        }
+
+        if let Some(state) = snapshot.run_state.as_mut() {
+            state.paused = true;
+        }
+        assert_eq!(
+            snapshot.run_state.as_ref().map(StatusLineRunState::accent),
+            Some(MAUVE)
+        );
+    }
+
+    #[test]
+    fn run_pill_flashes_failed_commands_then_keeps_a_badge() {
+        let mut snapshot = sample_snapshot();
+        let now = Instant::now();
+        let renderer = DefaultStatusLineRenderer;
+        if let Some(state) = snapshot.run_state.as_mut() {
+            state.exec_failure = Some(StatusLineExecFailure {
+                exit_code: 1,
+                command: "cargo test".to_string(),
+                recent: true,
+            });
+        }
+        let line = renderer.render_run_pill(&snapshot, 160, now);
+        let flash = line
+            .spans
+            .iter()
+            .find(|span| span.content == "✗ exit 1 · cargo test")
+            .expect("failure flash");
+        assert_eq!(flash.style.bg, Some(RED));
+
+        let narrow = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 30, now));
+        assert!(!narrow.contains("exit 1"), "{narrow}");
+        assert!(narrow.contains(EXEC_FAILURE_ICON), "{narrow}");
+
+        if let Some(failure) = snapshot
+            .run_state
+            .as_mut()
+            .and_then(|state| state.exec_failure.as_mut())
+        {
+            failure.recent = false;
+        }
+        let repr = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 160, now));
+        assert!(repr.contains(EXEC_FAILURE_ICON), "{repr}");
+        assert!(!repr.contains("cargo test"), "{repr}");
+    }
+
+    #[test]
+    fn run_pill_flags_an_unreachable_endpoint() {
+        let mut snapshot = sample_snapshot();
+        let now = Instant::now();
+        let renderer = DefaultStatusLineRenderer;
+        let repr = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 160, now));
+        assert!(!repr.contains("offline"), "{repr}");
+
+        snapshot.offline = true;
+        let line = renderer.render_run_pill(&snapshot, 160, now);
+        let chip = line
+            .spans
+            .iter()
+            .find(|span| span.content == "offline")
+            .expect("offline chip");
+        assert_eq!(chip.style.bg, Some(RED));
+    }
+
+    #[test]
+    fn run_pill_warns_about_pending_approvals() {
+        let mut snapshot = sample_snapshot();
+        let now = Instant::now();
+        let renderer = DefaultStatusLineRenderer;
+        let repr = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 120, now));
+        assert!(!repr.contains('⚠'), "no approvals pending: {repr}");
+
+        if let Some(state) = snapshot.run_state.as_mut() {
+            state.pending_approvals = 2;
+        }
+        let repr = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 120, now));
+        assert!(repr.contains("⚠ 2 approvals"), "approvals missing: {repr}");
+        let narrow = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 30, now));
+        assert!(narrow.contains("⚠ 2"), "approvals dropped: {narrow}");
+    }
+
+    #[test]
+    fn run_pill_collapses_queue_preview_to_count() {
+        let snapshot = sample_snapshot();
+        let now = Instant::now();
+        let renderer = DefaultStatusLineRenderer;
+        let collapsed = (20..80)
+            .map(|width| snapshot_line_repr(&renderer.render_run_pill(&snapshot, width, now)))
+            .find(|repr| repr.contains("2 queued"))
+            .expect("some width should show the queue count");
+        assert!(
+            !collapsed.contains("next:"),
+            "preview not collapsed: {collapsed}"
+        );
+    }
+
+    #[test]
+    fn status_line_shows_queued_count_only_without_run_pill() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        let now = Instant::now();
+        let renderer = DefaultStatusLineRenderer;
+        let repr = snapshot_line_repr(&renderer.render(&snapshot, 120, now));
+        assert!(!repr.contains("queued"), "run pill shows the queue: {repr}");
+
+        snapshot.queued = 2;
+        for width in [120, 40] {
+            let repr = snapshot_line_repr(&renderer.render(&snapshot, width, now));
+            assert!(repr.contains("2 queued"), "queue count missing: {repr}");
+        }
+
+        snapshot.pending_approvals = 2;
+        let repr = snapshot_line_repr(&renderer.render(&snapshot, 120, now));
+        assert!(repr.contains("⚠ 2 approvals"), "approvals missing: {repr}");
+    }
+
+    #[test]
+    fn spare_middle_lists_queued_messages_when_enabled() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.context = None;
+        let now = Instant::now();
+        let renderer = DefaultStatusLineRenderer;
+        let blank = renderer.render(&snapshot, 200, now);
+        snapshot.middle_queue = vec![
+            "fix the   flaky test".to_string(),
+            "then run lint\nand push".to_string(),
+        ];
+        let listed = renderer.render(&snapshot, 200, now);
+
+        // The same line, with the blank middle replaced by the list.
+        let filler = blank
+            .spans
+            .iter()
+            .zip(&listed.spans)
+            .position(|(blank, listed)| blank != listed)
+            .expect("the middle changes");
+        let queue = "fix the flaky test, then run lint";
+        let padding = blank.spans[filler].content.len() - 1 - queue.len();
+        let mut expected = blank.clone();
+        expected.spans.splice(
+            filler..=filler,
+            [
+                span(" ", Style::default()),
+                span(queue, dim_text()),
+                span(" ".repeat(padding), Style::default()),
+            ],
+        );
+        assert_eq!(listed, expected);
+
+        let lists_queue = |snapshot: &StatusLineSnapshot| {
+            snapshot_line_repr(&renderer.render(snapshot, 200, now)).contains("flaky")
+        };
+        snapshot.context = sample_snapshot().context;
+        assert!(!lists_queue(&snapshot), "the context bar takes the middle");
+        snapshot.context = None;
+        snapshot.middle_queue.clear();
+        assert!(!lists_queue(&snapshot));
+    }
+
+    #[test]
+    fn run_pill_shows_position_while_queue_cycles() {
+        let mut snapshot = sample_snapshot();
+        if let Some(run_state) = snapshot.run_state.as_mut() {
+            run_state.queue_preview_index = Some(1);
+        }
+        let renderer = DefaultStatusLineRenderer;
+        let repr = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 120, Instant::now()));
+        assert!(
+            repr.contains("cargo test --all"),
+            "second preview missing: {repr}"
+        );
+        assert!(repr.contains("(2/2)"), "queue position missing: {repr}");
+    }
+
+    #[test]
+    fn compact_hint_shows_key_and_degrades_before_path() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        if let Some(context) = snapshot.context.as_mut() {
+            context.percent_remaining = 12;
+            context.suggest_compact = true;
+        }
+        let now = Instant::now();
+        let text = |width| {
+            render_status_line(&snapshot, width, now)
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        };
+        let wide = text(300);
+        let key: Span<'static> = key_hint::ctrl(KeyCode::Char('k')).into();
+        let hint = format!("press {} to compact", key.content);
+        assert!(wide.contains(&hint), "hint missing: {wide}");
+        let narrow = (40..300)
+            .map(text)
+            .find(|line| line.contains("workspace/codex") && !line.contains("to compact"));
+        assert!(
+            narrow.is_some(),
+            "hint should hide before the path shortens"
+        );
+    }
+
+    #[test]
+    fn low_context_warning_pulses_and_hints() {
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        let since = Instant::now();
+        snapshot.context = Some(StatusLineContextSnapshot {
+            percent_remaining: 6,
+            tokens_in_context: 150_000,
+            window: 160_000,
+            low_since: Some(since),
+            suggest_compact: false,
+        });
+        let renderer = DefaultStatusLineRenderer;
+        let hint_style = |line: &Line<'static>| {
+            line.spans
+                .iter()
+                .find(|span| span.content.as_ref() == CONTEXT_WARNING_HINT)
+                .map(|span| span.style)
+        };
+
+        let bright = renderer.render(&snapshot, 200, since);
+        let style = hint_style(&bright).expect("compact hint missing");
+        assert_eq!(style.fg, Some(RED));
+        assert!(style.add_modifier.contains(Modifier::BOLD));
+
+        let dimmed = renderer.render(&snapshot, 200, since + CONTEXT_WARNING_PULSE);
+        let style = hint_style(&dimmed).expect("compact hint missing");
+        assert!(style.add_modifier.contains(Modifier::DIM));
+
+        if let Some(context) = snapshot.context.as_mut() {
+            context.low_since = None;
+        }
+        let calm = renderer.render(&snapshot, 200, since);
+        assert_eq!(hint_style(&calm), None);
+    }
+
+    #[test]
+    fn renderer_run_pill_shows_turn_timing() {
+        let mut snapshot = sample_snapshot();
+        if let Some(tokens) = snapshot.tokens.as_mut() {
+            tokens.output_tokens_per_sec = Some(41.6);
+            tokens.last_turn_duration = Some(Duration::from_secs(95));
+        }
+        let now = Instant::now();
+        let renderer = DefaultStatusLineRenderer;
+
+        let idle = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 120, now));
+        assert!(idle.contains("last 1m 35s"), "last turn missing: {idle}");
+        assert!(!idle.contains("tok/s"), "idle pill shows rate: {idle}");
+
+        if let Some(timer) = snapshot
+            .run_state
+            .as_mut()
+            .and_then(|state| state.timer.as_mut())
+        {
+            timer.is_paused = false;
+            timer.last_resume_at = Some(now);
+        }
+        let running = snapshot_line_repr(&renderer.render_run_pill(&snapshot, 120, now));
+        assert!(running.contains("42 tok/s"), "rate missing: {running}");
+        assert!(
+            !running.contains("last "),
+            "running pill shows last turn: {running}"
+        );
+    }
+
     #[test]
     fn renderer_run_pill_idle_is_blank_capsule() {
         let mut snapshot = sample_snapshot();
@@ -1543,6 +4187,8 @@ mod tests {
                 percent_remaining: 100,
                 tokens_in_context: 0,
                 window: 1,
+                low_since: None,
+                suggest_compact: false,
             }),
             run_state: Some(StatusLineRunState {
                 status_changed_at: now,
@@ -1562,66 +4208,125 @@ mod tests {
         );
     }
 
-    fn sample_snapshot() -> StatusLineSnapshot {
-        StatusLineSnapshot {
-            cwd_display: Some("~/workspace/codex".to_string()),
-            cwd_basename: Some("codex".to_string()),
-            cwd_fallback: Some("codex".to_string()),
-            model: Some(StatusLineModelSnapshot {
-                label: "gpt-5-codex".to_string(),
-                detail: Some("high".to_string()),
-            }),
-            tokens: Some(StatusLineTokenSnapshot {
-                total: TokenCountSnapshot {
-                    total_tokens: 48_234,
-                    input_tokens: 30_000,
-                    cached_input_tokens: 8_000,
-                    output_tokens: 18_234,
-                    reasoning_output_tokens: 234,
-                },
-                last: Some(TokenCountSnapshot {
-                    total_tokens: 2_345,
-                    input_tokens: 1_200,
-                    cached_input_tokens: 200,
-                    output_tokens: 900,
-                    reasoning_output_tokens: 45,
-                }),
-            }),
-            context: Some(StatusLineContextSnapshot {
-                percent_remaining: 68,
-                tokens_in_context: 52_000,
-                window: 160_000,
-            }),
-            run_state: Some(StatusLineRunState {
-                label: "Applying patch".to_string(),
-                spinner_started_at: None,
-                timer: Some(RunTimerSnapshot {
-                    elapsed_running: Duration::from_secs(125),
-                    last_resume_at: None,
-                    is_paused: true,
-                }),
-                queued_messages: vec!["git status".to_string(), "cargo test --all".to_string()],
-                show_interrupt_hint: true,
-                status_changed_at: Instant::now(),
-            }),
-            git: Some(StatusLineGitSnapshot {
-                branch: Some("feature/fix-tests".to_string()),
-                dirty: true,
-                ahead: Some(1),
-                behind: Some(0),
-            }),
-            environment: StatusLineEnvironmentSnapshot {
-                devspace: Some(StatusLineDevspaceSnapshot {
-                    name: "earth".to_string(),
-                }),
-                hostname: Some("vermissian".to_string()),
-                aws_profile: Some("prod".to_string()),
-                kubernetes_context: Some("codex-dev".to_string()),
-                code88: None,
-            },
+    /// The pre-`degrade_to_fit` loop: re-render after every degrade op.
+    fn render_status_line_linear(
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Line<'static> {
+        let mut model = RenderModel::new(snapshot, now);
+        let target_width = width as usize;
+        loop {
+            if let Some(line) = model.try_render_line(target_width) {
+                return line;
+            }
+            if model.apply_next_degrade().is_none() {
+                return truncate_line_to_width(model.fallback_line(), target_width);
+            }
         }
     }
 
+    #[test]
+    fn degrade_search_matches_linear_retry_at_every_width() {
+        let now = Instant::now();
+        let full = sample_snapshot();
+        let mut idle = sample_snapshot();
+        idle.run_state = None;
+        let mut low_context = sample_snapshot();
+        if let Some(context) = low_context.context.as_mut() {
+            context.percent_remaining = 4;
+            context.low_since = Some(now);
+        }
+        let mut no_context = sample_snapshot();
+        no_context.context = None;
+        let mut rearranged = sample_snapshot();
+        rearranged.layout = layout_from_toml(
+            r#"
+            git = { side = "left", priority = 5 }
+            path = { side = "right", priority = 100 }
+            hostname = { side = "left" }
+        "#,
+        );
+        for snapshot in [full, idle, low_context, no_context, rearranged] {
+            for width in 0..=220u16 {
+                assert_eq!(
+                    snapshot_line_repr(&render_status_line(&snapshot, width, now)),
+                    snapshot_line_repr(&render_status_line_linear(&snapshot, width, now)),
+                    "width {width}"
+                );
+            }
+        }
+    }
+
+    fn layout_from_toml(toml: &str) -> SegmentLayout {
+        let layout: StatusLineSegmentLayout = toml::from_str(toml).expect("valid layout");
+        SegmentLayout::from_config(&layout)
+    }
+
+    #[test]
+    fn layout_moves_segments_between_sides_in_priority_order() {
+        let layout = layout_from_toml(
+            r#"
+            git = { side = "left", priority = 15 }
+            path = { side = "right", priority = 100 }
+            clock = { side = "left", priority = 5 }
+        "#,
+        );
+        assert_eq!(
+            layout.left,
+            vec![
+                SegmentSlot::RunCapsule,
+                SegmentSlot::Approvals,
+                SegmentSlot::QueuePreview,
+                SegmentSlot::Clock,
+                SegmentSlot::Git,
+                SegmentSlot::Model,
+                SegmentSlot::Provider,
+                SegmentSlot::DiffStats,
+                SegmentSlot::Custom,
+            ]
+        );
+        assert_eq!(
+            layout.right,
+            vec![
+                SegmentSlot::Attention,
+                SegmentSlot::Budget,
+                SegmentSlot::Policy,
+                SegmentSlot::DevEnv,
+                SegmentSlot::Devspace,
+                SegmentSlot::Hostname,
+                SegmentSlot::AwsProfile,
+                SegmentSlot::Iac,
+                SegmentSlot::Kubernetes,
+                SegmentSlot::Process,
+                SegmentSlot::Session,
+                SegmentSlot::Build,
+                SegmentSlot::Path,
+            ]
+        );
+
+        let mut snapshot = sample_snapshot();
+        snapshot.run_state = None;
+        snapshot.layout = layout;
+        let text: String = render_status_line(&snapshot, 200, Instant::now())
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        let branch = text.find("feature/fix-tests").expect("git segment shown");
+        let model = text.find("gpt-5-codex").expect("model segment shown");
+        let hostname = text.find("vermissian").expect("hostname segment shown");
+        let path = text.find("~/workspace/codex").expect("path segment shown");
+        assert!(
+            branch < model && model < hostname && hostname < path,
+            "{text}"
+        );
+    }
+
+    pub(super) fn sample_snapshot() -> StatusLineSnapshot {
+        super::fixtures::busy(Instant::now())
+    }
+
     fn snapshot_line_repr(line: &Line<'_>) -> String {
         line.spans
             .iter()
@@ -1,30 +1,86 @@
+use std::collections::HashMap;
 use std::env;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
-#[cfg(test)]
 use std::sync::Mutex;
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::Ordering;
 use std::time::Duration;
+use std::time::Instant;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::diff_render::count_line_changes;
+use crate::statusline::CompletedRun;
+use crate::statusline::GitCheckoutKind;
+use crate::statusline::NixShell;
+use crate::statusline::RunCategory;
 use crate::statusline::StatusLine88CodeSnapshot;
+use crate::statusline::StatusLineDevEnvSnapshot;
+use crate::statusline::StatusLineGitBaseSnapshot;
+use crate::statusline::StatusLineGitCheckout;
 use crate::statusline::StatusLineGitSnapshot;
+use crate::statusline::StatusLineIacSnapshot;
+use crate::statusline::StatusLineProcessSnapshot;
+use crate::statusline::StatusLineProviderSnapshot;
 use crate::statusline::StatusLineRenderer;
+use crate::statusline::attention::Attention;
+use crate::statusline::attention::AttentionSeverity;
+use crate::statusline::attention::AttentionSource;
+use crate::statusline::code88_api::Code88AggregatedData;
+use crate::statusline::code88_api::Code88Error;
 use crate::statusline::code88_api::fetch_88code_aggregated;
+use crate::statusline::code88_api::usage_snapshot;
+use crate::statusline::control;
+use crate::statusline::control::ControlSocket;
+use crate::statusline::github_pr::PullRequestCache;
+use crate::statusline::github_pr::current_pull_request;
+use crate::statusline::iac::detect_iac_workspace;
+use crate::statusline::metrics::MetricsWriter;
+use crate::statusline::metrics::TaskMetrics;
+use crate::statusline::metrics::render_metrics;
+use crate::statusline::process::PROCESS_POLL_INTERVAL;
+use crate::statusline::process::ProcessSampler;
+use crate::statusline::providers::GitInfoProvider;
+use crate::statusline::providers::KubeProvider;
+use crate::statusline::providers::ProviderFuture;
+use crate::statusline::providers::ReachabilityProvider;
+use crate::statusline::providers::StatusLineProviders;
+use crate::statusline::providers::UsageProvider;
+use crate::statusline::reachability::model_endpoint;
+use crate::statusline::reachability::probe_endpoint;
+use crate::statusline::run_label::command_text;
+use crate::statusline::settings_reload::SETTINGS_POLL_INTERVAL;
+use crate::statusline::settings_reload::SettingsWatcher;
 use crate::statusline::state::StatusLineState;
-use crate::text_formatting::truncate_text;
+use crate::statusline::timeline::SessionTimeline;
+use crate::statusline::timeline::TimelineEvent;
+use crate::statusline::usage_cache::CachedUsage;
+use crate::statusline::usage_cache::UsageCache;
+use crate::statusline::usage_retry::CircuitBreaker;
+use crate::statusline::usage_retry::fetch_with_retry;
+use crate::statusline::usage_retry::stale_snapshot;
+use crate::tui::TerminalProgress;
+use crate::version::BuildStatus;
+use codex_app_server_protocol::AuthMode;
 use codex_core::config::Config;
+use codex_core::config::types::StatusLineHosts;
+use codex_core::config::types::StatusLineSettings;
+use codex_core::exec_env::create_env;
 use codex_core::git_info::collect_git_info;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::protocol::BudgetUsage;
+use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
+use codex_core::workspace_state::WorkspaceStatusLineState;
+use codex_core::workspace_state::load_workspace_state;
 use hostname::get as get_hostname;
 #[cfg(test)]
 use lazy_static::lazy_static;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
+use ratatui::text::Line;
 use ratatui::widgets::Widget as _;
 use tokio::process::Command;
 use tokio::runtime::Handle;
@@ -34,26 +90,96 @@ use tokio::task::spawn_blocking;
 use codex_code88 as code88;
 
 use super::CustomStatusLineRenderer;
+use super::skins;
+
+/// Run label while a patch is being applied.
+const PATCH_RUN_LABEL: &str = "Applying patch";
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct StatusLineLayout {
     pub pane_area: Rect,
     pub run_pill_area: Rect,
     pub status_line_area: Rect,
+    /// Where the run pill goes; `run_pill_area` is empty unless it is
+    /// [`RunPillPlacement::OwnRow`].
+    pub run_pill: RunPillPlacement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RunPillPlacement {
+    /// Its own row above the pane.
+    OwnRow,
+    /// Too short for the full layout: the left of the status line row.
+    Inline,
+    /// A view such as an approval prompt took over the pane; the status line
+    /// shows the queued message count in its place.
+    Hidden,
 }
 
 pub(crate) struct StatusLineOverlay {
     state: StatusLineState,
+    /// Skin in effect, after workspace overrides.
+    skin: String,
+    hosts: StatusLineHosts,
+    /// `tui.statusline.git_base_branch` the git provider was built with.
+    git_base_branch: String,
     app_event_tx: AppEventSender,
     cwd: PathBuf,
     codex_home: PathBuf,
+    /// Environment commands run with, including workspace variables; the AWS
+    /// and Kubernetes segments read from it so they match what commands see.
+    command_env: HashMap<String, String>,
+    /// Where the git, Kubernetes and 88code refreshes get their data.
+    providers: StatusLineProviders,
     /// 88_ prefixed API key for usage API (from settings.json)
     code88_api_key: Option<String>,
     /// Login token from 88code-token.json for getLoginInfo API
     code88_login_token: Option<String>,
-    token_refresh_in_progress: Arc<AtomicBool>,
+    /// Whether the expired-token prompt was raised since the last sign-in
+    /// attempt, so the usage poll asks once rather than on every poll.
+    /// Cleared when a sign-in ends, successfully or not, and when the prompt
+    /// is dismissed.
+    relogin_offered: bool,
+    /// After "Not now", the usage poll does not ask again before this time.
+    relogin_snoozed_until: Option<Instant>,
     /// Background poller for 88code usage data
     code88_poller: Option<JoinHandle<()>>,
+    /// Background sampler for the process segment
+    process_poller: Option<JoinHandle<()>>,
+    /// `tui.statusline.environment_refresh_secs`; `0` turns the re-scan off.
+    environment_refresh: Duration,
+    /// Ticks the environment re-scan
+    environment_poller: Option<JoinHandle<()>>,
+    /// `tui.statusline.hot_reload`
+    hot_reload: bool,
+    /// Polls `config.toml` while `hot_reload` is on
+    settings_poller: Option<JoinHandle<()>>,
+    /// `-c` overrides the settings poller applies to every reload.
+    cli_overrides: Vec<(String, toml::Value)>,
+    /// `tui.statusline.reachability_probe_secs`; `0` turns the probe off.
+    reachability_probe: Duration,
+    /// Base URL of the model provider, probed while a task runs.
+    model_endpoint: String,
+    /// How the user signed in; picks the built-in provider's base URL.
+    auth_mode: Option<AuthMode>,
+    /// Probes `model_endpoint` while a task runs
+    reachability_poller: Option<JoinHandle<()>>,
+    /// `tui.statusline.metrics_file`, resolved against `codex_home`.
+    metrics_file: Option<PathBuf>,
+    /// Writes `metrics_file` off the UI thread, once something changed.
+    metrics_writer: Option<MetricsWriter>,
+    /// Turns and task durations for the metrics file.
+    task_metrics: TaskMetrics,
+    /// `tui.statusline.session_timeline`
+    session_timeline_enabled: bool,
+    /// Run state transitions, written once the session id is known
+    timeline: SessionTimeline,
+    /// The session's rollout file; the timeline is written next to it.
+    rollout_path: Option<PathBuf>,
+    /// `tui.statusline.control_socket`
+    control_socket_enabled: bool,
+    /// Listening control socket, once started
+    control: Option<ControlSocket>,
 }
 
 impl StatusLineOverlay {
@@ -69,33 +195,105 @@ impl StatusLineOverlay {
         + Self::MARGIN_ABOVE_PANE
         + Self::MARGIN_BELOW_PANE
         + Self::STATUS_LINE_HEIGHT;
+    const COMPACT_RESERVED_ROWS: u16 = Self::STATUS_LINE_HEIGHT;
     pub(crate) fn new(
         config: &Config,
         frame_requester: crate::tui::FrameRequester,
         app_event_tx: AppEventSender,
         renderer: Option<Box<dyn StatusLineRenderer>>,
     ) -> Option<Self> {
-        if !config.tui_custom_statusline {
+        let workspace = load_workspace_state(&config.codex_home, &config.cwd)
+            .ok()
+            .and_then(|state| state.statusline)
+            .unwrap_or_default();
+        if !workspace.enabled_or(config.tui_custom_statusline) {
             return None;
         }
-        let renderer = renderer.unwrap_or_else(|| Box::new(CustomStatusLineRenderer));
-        let state = StatusLineState::with_renderer(config, frame_requester, renderer);
+        Some(Self::build(
+            config,
+            &workspace,
+            frame_requester,
+            app_event_tx,
+            renderer,
+        ))
+    }
+
+    /// The overlay for `config` even when the status line is turned off, for
+    /// `/statusline on` and `ctrl + l`. The caller bootstraps it.
+    pub(crate) fn new_enabled(
+        config: &Config,
+        frame_requester: crate::tui::FrameRequester,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let workspace = load_workspace_state(&config.codex_home, &config.cwd)
+            .ok()
+            .and_then(|state| state.statusline)
+            .unwrap_or_default();
+        Self::build(config, &workspace, frame_requester, app_event_tx, None)
+    }
+
+    fn build(
+        config: &Config,
+        workspace: &WorkspaceStatusLineState,
+        frame_requester: crate::tui::FrameRequester,
+        app_event_tx: AppEventSender,
+        renderer: Option<Box<dyn StatusLineRenderer>>,
+    ) -> Self {
+        let settings = workspace.apply_to(&config.tui_statusline);
+        let renderer = renderer.unwrap_or_else(|| {
+            if config.tui_accessible {
+                Box::new(skins::AccessibleRenderer)
+            } else {
+                configured_renderer(&config.codex_home, &settings)
+            }
+        });
+        let state = StatusLineState::with_settings(config, &settings, frame_requester, renderer);
 
         let codex_home = config.codex_home.clone();
 
-        // Load login token from 88code-token.json
-        let code88_login_token = code88::load_token(&codex_home);
+        let code88_login_token = config
+            .code88_token_store()
+            .resolve_token()
+            .map(|resolved| resolved.token);
 
-        Some(Self {
+        Self {
             state,
+            hosts: settings.hosts,
+            git_base_branch: settings.git_base_branch.clone(),
+            skin: settings.skin,
             app_event_tx,
             cwd: config.cwd.clone(),
             codex_home,
+            command_env: create_env(&config.shell_environment_policy),
+            providers: system_providers(settings.git_base_branch.clone(), config),
             code88_api_key: config.tui_code88_api_key.clone(),
             code88_login_token,
-            token_refresh_in_progress: Arc::new(AtomicBool::new(false)),
+            relogin_offered: false,
+            relogin_snoozed_until: None,
             code88_poller: None,
-        })
+            process_poller: None,
+            environment_refresh: Duration::from_secs(
+                u64::try_from(settings.environment_refresh_secs).unwrap_or(0),
+            ),
+            environment_poller: None,
+            hot_reload: settings.hot_reload,
+            settings_poller: None,
+            cli_overrides: config.cli_overrides.clone(),
+            reachability_probe: Duration::from_secs(
+                u64::try_from(settings.reachability_probe_secs).unwrap_or(0),
+            ),
+            model_endpoint: model_endpoint(config, None),
+            auth_mode: None,
+            reachability_poller: None,
+            metrics_file: metrics_file_path(&config.codex_home, &settings),
+            metrics_writer: None,
+            task_metrics: TaskMetrics::default(),
+            session_timeline_enabled: settings.session_timeline,
+            timeline: SessionTimeline::default(),
+            rollout_path: None,
+            control_socket_enabled: settings.control_socket,
+            control: None,
+        }
     }
 
     pub(crate) fn bootstrap(
@@ -104,12 +302,13 @@ impl StatusLineOverlay {
         initial_tokens: Option<TokenUsageInfo>,
         queued_messages: Vec<String>,
     ) {
+        self.state.begin_update();
         self.sync_model(config);
+        self.sync_policy(config);
         self.state.update_tokens(initial_tokens);
-        self.refresh_environment();
         self.state.set_queued_messages(queued_messages);
         self.spawn_git_refresh();
-        self.spawn_kube_refresh();
+        self.spawn_environment_refresh();
         // Initialize 88code with loading state if both tokens are configured
         if self.code88_login_token.is_some() && self.code88_api_key.is_some() {
             self.state.set_88code_info(Some(StatusLine88CodeSnapshot {
@@ -118,25 +317,79 @@ impl StatusLineOverlay {
             }));
         }
         self.start_88code_poller();
+        self.start_process_poller();
+        self.start_environment_poller();
+        self.start_settings_poller();
+        self.start_control_socket();
+        self.state.commit();
+    }
+
+    pub(crate) fn sync_policy(&mut self, config: &Config) {
+        self.state
+            .set_policy(config.approval_policy, &config.sandbox_policy);
+    }
+
+    /// How the user signed in, so the reachability probe targets the same
+    /// backend as requests (the ChatGPT backend or the OpenAI API).
+    pub(crate) fn set_auth_mode(&mut self, config: &Config, auth_mode: Option<AuthMode>) {
+        self.auth_mode = auth_mode;
+        self.model_endpoint = model_endpoint(config, auth_mode);
+    }
+
+    pub(crate) fn set_build(&mut self, build: &BuildStatus) {
+        self.state.set_build(build);
     }
 
     pub(crate) fn sync_model(&mut self, config: &Config) {
+        self.state.begin_update();
         self.state
             .update_model(config.model.clone(), config.model_reasoning_effort);
+        self.state.set_provider(provider_snapshot(config));
+        self.state.commit();
+        self.model_endpoint = model_endpoint(config, self.auth_mode);
     }
 
-    pub(crate) fn refresh_environment(&mut self) {
-        self.state.set_devspace(detect_devspace());
-        self.state.set_hostname(detect_hostname());
-        self.state.set_aws_profile(detect_aws_profile());
+    pub(crate) fn update_host_environment(&mut self, environment: HostEnvironment) {
+        let HostEnvironment {
+            devspace,
+            hostname,
+            ssh,
+            aws_profile,
+            dev_env,
+        } = environment;
+        self.state.begin_update();
+        self.state.set_devspace(devspace);
+        let alias = hostname
+            .as_deref()
+            .and_then(|hostname| self.hosts.alias_for(hostname));
+        self.state
+            .set_host_style(alias.and_then(|alias| alias.color.clone()), ssh);
+        self.state
+            .set_hostname(alias.map(|alias| alias.label.clone()).or(hostname));
+        self.state.set_aws_profile(aws_profile);
+        self.state.set_dev_env(dev_env);
+        self.state.commit();
     }
 
     pub(crate) fn spawn_background_tasks(&self) {
         self.spawn_git_refresh();
-        self.spawn_kube_refresh();
+        self.spawn_environment_refresh();
         // 88code is handled by the poller, no need to manually refresh here
     }
 
+    /// The environment detections that run in the background.
+    pub(crate) fn spawn_environment_refresh(&self) {
+        self.spawn_host_refresh();
+        self.spawn_kube_refresh();
+        self.spawn_iac_refresh();
+    }
+
+    /// Add a successfully applied patch to the session's diff stats.
+    pub(crate) fn record_applied_patch(&mut self, changes: &HashMap<PathBuf, FileChange>) {
+        let (added, removed) = count_line_changes(changes);
+        self.state.add_diff_stats(added, removed);
+    }
+
     pub(crate) fn refresh_git(&self) {
         self.spawn_git_refresh();
     }
@@ -146,28 +399,74 @@ impl StatusLineOverlay {
             return;
         };
         let cwd = self.cwd.clone();
+        // `gh` goes over the network; skip it when the git segment is hidden.
+        let pull_request = self.state.segments().git;
+        let git = Arc::clone(&self.providers.git);
         let tx = self.app_event_tx.clone();
         handle.spawn(async move {
-            let snapshot = collect_status_line_git_snapshot(cwd).await;
+            let snapshot = git.git_snapshot(cwd, pull_request).await;
             tx.send(AppEvent::StatusLineGit(snapshot));
         });
     }
 
+    /// Hostname lookups can stall on NIS or a slow resolver and the devspace
+    /// lookup walks up to the repository root, so they run on a blocking
+    /// thread rather than the UI thread.
+    fn spawn_host_refresh(&self) {
+        let Ok(handle) = Handle::try_current() else {
+            return;
+        };
+        let tx = self.app_event_tx.clone();
+        let cwd = self.cwd.clone();
+        let command_env = self.command_env.clone();
+        handle.spawn(async move {
+            if let Ok(environment) =
+                spawn_blocking(move || HostEnvironment::detect(&cwd, &command_env)).await
+            {
+                tx.send(AppEvent::StatusLineHostEnvironment(environment));
+            }
+        });
+    }
+
     fn spawn_kube_refresh(&self) {
+        if !self.state.segments().kubernetes {
+            return;
+        }
         let Ok(handle) = Handle::try_current() else {
             return;
         };
         let tx = self.app_event_tx.clone();
+        let paths = kube_config_paths(&self.command_env);
+        let kube = Arc::clone(&self.providers.kube);
         handle.spawn(async move {
-            let context = detect_kube_context_async().await;
+            let context = kube.kube_context(paths).await;
             tx.send(AppEvent::StatusLineKubeContext(context));
         });
     }
 
+    /// Commands may switch workspaces or stacks, so this reruns with the
+    /// other background refreshes.
+    fn spawn_iac_refresh(&self) {
+        if !self.state.segments().iac {
+            return;
+        }
+        let Ok(handle) = Handle::try_current() else {
+            return;
+        };
+        let tx = self.app_event_tx.clone();
+        let cwd = self.cwd.clone();
+        let command_env = self.command_env.clone();
+        handle.spawn(async move {
+            let iac = detect_iac_workspace(cwd, &command_env).await;
+            tx.send(AppEvent::StatusLineIac(iac));
+        });
+    }
+
     /// Polling interval for 88code usage data (10 seconds).
     const CODE88_POLL_INTERVAL: Duration = Duration::from_secs(10);
-    /// Maximum backoff multiplier for consecutive errors (60 seconds max).
-    const MAX_BACKOFF_MULTIPLIER: u32 = 6;
+
+    /// How long "Not now" keeps the usage poll from asking to sign in again.
+    const RELOGIN_SNOOZE: Duration = Duration::from_secs(10 * 60);
 
     /// Start background polling for 88code usage data.
     fn start_88code_poller(&mut self) {
@@ -184,51 +483,41 @@ impl StatusLineOverlay {
         };
 
         let tx = self.app_event_tx.clone();
+        let usage = Arc::clone(&self.providers.usage);
         let poller = handle.spawn(async move {
             let mut interval = tokio::time::interval(Self::CODE88_POLL_INTERVAL);
-            let mut consecutive_errors: u32 = 0;
+            let mut breaker = CircuitBreaker::default();
+            let mut last_good: Option<StatusLine88CodeSnapshot> = None;
 
             loop {
                 // Wait for next poll interval (first tick returns immediately)
                 interval.tick().await;
-
-                // Apply exponential backoff on consecutive errors
-                if consecutive_errors > 0 {
-                    let backoff_secs =
-                        10 * consecutive_errors.min(Self::MAX_BACKOFF_MULTIPLIER) as u64;
-                    tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
+                if !breaker.allows(tokio::time::Instant::now()) {
+                    continue;
                 }
 
-                // Fetch data and send update
-                let snapshot = match fetch_88code_aggregated(&login_token, &api_key).await {
+                let result = fetch_with_retry(usage.as_ref(), &login_token, &api_key).await;
+                let snapshot = match result {
                     Ok(data) => {
-                        consecutive_errors = 0;
-                        Some(StatusLine88CodeSnapshot {
-                            service_tier: data.service_tier,
-                            daily_cost: data.daily_cost,
-                            daily_tokens: data.daily_tokens,
-                            daily_requests: data.daily_requests,
-                            input_tokens: data.input_tokens,
-                            output_tokens: data.output_tokens,
-                            cache_create_tokens: data.cache_create_tokens,
-                            cache_read_tokens: data.cache_read_tokens,
-                            is_error: false,
-                            error_msg: None,
-                            token_expired: false,
-                        })
+                        breaker.record_success();
+                        let snapshot = usage_snapshot(Ok(data));
+                        last_good = Some(snapshot.clone());
+                        snapshot
                     }
-                    Err(e) => {
-                        consecutive_errors = consecutive_errors.saturating_add(1);
-                        let token_expired = e.is_token_expired();
-                        Some(StatusLine88CodeSnapshot {
-                            is_error: true,
-                            error_msg: Some(e.to_string()),
-                            token_expired,
-                            ..Default::default()
-                        })
+                    Err(err) => {
+                        breaker.record_failure(tokio::time::Instant::now());
+                        // Old numbers beat an error, unless signing in again
+                        // is what fixes it.
+                        match last_good.as_ref() {
+                            Some(good) if !err.is_token_expired() => stale_snapshot(good, &err),
+                            _ => {
+                                last_good = None;
+                                usage_snapshot(Err(err))
+                            }
+                        }
                     }
                 };
-                tx.send(AppEvent::StatusLine88Code(snapshot));
+                tx.send(AppEvent::StatusLine88Code(Some(snapshot)));
             }
         });
 
@@ -242,55 +531,259 @@ impl StatusLineOverlay {
         }
     }
 
-    pub(crate) fn update_git(&mut self, git: Option<StatusLineGitSnapshot>) {
-        self.state.set_git_info(git);
+    /// Sample the codex process every `PROCESS_POLL_INTERVAL` while the
+    /// process segment is enabled.
+    fn start_process_poller(&mut self) {
+        self.stop_process_poller();
+        if !self.state.segments().process {
+            return;
+        }
+        let Ok(handle) = Handle::try_current() else {
+            return;
+        };
+        let tx = self.app_event_tx.clone();
+        let poller = handle.spawn(async move {
+            let Some(mut sampler) = ProcessSampler::new() else {
+                return;
+            };
+            let mut interval = tokio::time::interval(PROCESS_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                tx.send(AppEvent::StatusLineProcess(sampler.sample()));
+            }
+        });
+        self.process_poller = Some(poller);
     }
 
-    pub(crate) fn update_kube_context(&mut self, context: Option<String>) {
-        self.state.set_kubernetes_context(context);
+    fn stop_process_poller(&mut self) {
+        if let Some(handle) = self.process_poller.take() {
+            handle.abort();
+        }
     }
 
-    pub(crate) fn update_88code(&mut self, data: Option<StatusLine88CodeSnapshot>) {
-        // Check if token expired and trigger refresh
-        if let Some(ref info) = data
-            && info.token_expired
-            && !self.token_refresh_in_progress.load(Ordering::SeqCst)
-        {
-            self.spawn_token_refresh();
+    /// Ask for an environment re-scan every `environment_refresh`. The scan
+    /// itself runs on the UI thread, which owns the state it updates.
+    fn start_environment_poller(&mut self) {
+        self.stop_environment_poller();
+        if self.environment_refresh.is_zero() {
+            return;
         }
-        self.state.set_88code_info(data);
+        let Ok(handle) = Handle::try_current() else {
+            return;
+        };
+        let tx = self.app_event_tx.clone();
+        let period = self.environment_refresh;
+        let poller = handle.spawn(async move {
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                tx.send(AppEvent::StatusLineEnvironmentTick);
+            }
+        });
+        self.environment_poller = Some(poller);
     }
 
-    /// Spawn a background task to refresh the 88code token via browser login.
-    pub(crate) fn spawn_token_refresh(&self) {
-        // Prevent multiple concurrent refresh attempts
-        if self.token_refresh_in_progress.swap(true, Ordering::SeqCst) {
-            return; // Already refreshing
+    fn stop_environment_poller(&mut self) {
+        if let Some(handle) = self.environment_poller.take() {
+            handle.abort();
         }
+    }
 
+    /// Probe the model endpoint every `reachability_probe` while a task
+    /// runs. A probe already running is kept.
+    fn start_reachability_poller(&mut self) {
+        if self.reachability_poller.is_some() || self.reachability_probe.is_zero() {
+            return;
+        }
         let Ok(handle) = Handle::try_current() else {
-            self.token_refresh_in_progress
-                .store(false, Ordering::SeqCst);
             return;
         };
-
-        let codex_home = self.codex_home.clone();
         let tx = self.app_event_tx.clone();
-        let in_progress = self.token_refresh_in_progress.clone();
+        let reachability = Arc::clone(&self.providers.reachability);
+        let url = self.model_endpoint.clone();
+        let period = self.reachability_probe;
+        let poller = handle.spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let reachable = reachability.reachable(url.clone()).await;
+                tx.send(AppEvent::StatusLineReachability(reachable));
+            }
+        });
+        self.reachability_poller = Some(poller);
+    }
 
-        handle.spawn(async move {
-            let result = code88::refresh_token(&codex_home).await;
-            in_progress.store(false, Ordering::SeqCst);
+    /// Stop probing and drop the `offline` chip.
+    fn stop_reachability_poller(&mut self) {
+        if let Some(handle) = self.reachability_poller.take() {
+            handle.abort();
+        }
+        self.state.set_offline(false);
+    }
 
-            match result {
-                Ok(token) => {
-                    tx.send(AppEvent::Refresh88CodeTokenResult(Ok(token)));
-                }
-                Err(e) => {
-                    tx.send(AppEvent::Refresh88CodeTokenResult(Err(e.to_string())));
+    /// Watch `config.toml` for `[tui.statusline]` edits while `hot_reload`
+    /// is on.
+    fn start_settings_poller(&mut self) {
+        self.stop_settings_poller();
+        if !self.hot_reload {
+            return;
+        }
+        let Ok(handle) = Handle::try_current() else {
+            return;
+        };
+        let tx = self.app_event_tx.clone();
+        let codex_home = self.codex_home.clone();
+        let cli_overrides = self.cli_overrides.clone();
+        let poller = handle.spawn(async move {
+            let mut watcher = SettingsWatcher::new(codex_home, cli_overrides).await;
+            let mut interval = tokio::time::interval_at(
+                tokio::time::Instant::now() + SETTINGS_POLL_INTERVAL,
+                SETTINGS_POLL_INTERVAL,
+            );
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Some(result) = watcher.poll().await {
+                    tx.send(AppEvent::StatusLineSettingsReloaded(result.map(Box::new)));
                 }
             }
         });
+        self.settings_poller = Some(poller);
+    }
+
+    fn stop_settings_poller(&mut self) {
+        if let Some(handle) = self.settings_poller.take() {
+            handle.abort();
+        }
+    }
+
+    fn start_control_socket(&mut self) {
+        self.control = None;
+        if !self.control_socket_enabled {
+            return;
+        }
+        let Ok(handle) = Handle::try_current() else {
+            return;
+        };
+        let path = ControlSocket::path_for(&self.codex_home);
+        match ControlSocket::start(
+            &handle,
+            path.clone(),
+            &self.run_state_event(),
+            self.app_event_tx.clone(),
+        ) {
+            Ok(control) => self.control = Some(control),
+            Err(err) => tracing::warn!(
+                "failed to start status line control socket at {}: {err}",
+                path.display()
+            ),
+        }
+    }
+
+    fn run_state_event(&self) -> control::ControlEvent {
+        let now = Instant::now();
+        control::run_state_event(
+            &self.state.snapshot_for_render(now),
+            self.state.task_in_progress(),
+            now,
+        )
+    }
+
+    /// Tell control socket clients and the session timeline about a run
+    /// state change.
+    fn publish_run_state(&mut self) {
+        if let Some(label) = self.state.current_run_label() {
+            let label = label.to_string();
+            self.timeline.record(TimelineEvent::Label { label });
+        }
+        if let Some(control) = self.control.as_ref() {
+            control.publish(&self.run_state_event());
+        }
+    }
+
+    pub(crate) fn update_custom_segment(&mut self, text: Option<String>) {
+        self.state.set_custom_segment(text);
+    }
+
+    pub(crate) fn push_attention(&mut self, attention: Attention) {
+        self.state.push_attention(attention);
+    }
+
+    pub(crate) fn clear_attention_source(&mut self, source: AttentionSource) {
+        self.state.clear_attention_source(source);
+    }
+
+    pub(crate) fn clear_attention(&mut self) {
+        self.state.clear_attention();
+    }
+
+    pub(crate) fn attention_entries(&self) -> &[Attention] {
+        self.state.attention_entries()
+    }
+
+    pub(crate) fn update_reachability(&mut self, reachable: bool) {
+        // A probe that finished after the task ended says nothing about now.
+        if self.reachability_poller.is_some() {
+            self.state.set_offline(!reachable);
+        }
+    }
+
+    pub(crate) fn update_process(&mut self, process: Option<StatusLineProcessSnapshot>) {
+        self.state.set_process(process);
+    }
+
+    pub(crate) fn update_budgets(&mut self, budgets: &[BudgetUsage]) {
+        self.state.set_budgets(budgets);
+    }
+
+    pub(crate) fn update_git(&mut self, git: Option<StatusLineGitSnapshot>) {
+        self.state.set_git_info(git);
+    }
+
+    pub(crate) fn update_kube_context(&mut self, context: Option<String>) {
+        self.state.set_kubernetes_context(context);
+    }
+
+    pub(crate) fn update_iac(&mut self, iac: Option<StatusLineIacSnapshot>) {
+        self.state.set_iac(iac);
+    }
+
+    pub(crate) fn update_88code(&mut self, data: Option<StatusLine88CodeSnapshot>) {
+        // Offer to sign in again; the user confirms before a browser opens.
+        if let Some(ref info) = data
+            && info.token_expired
+            && !self.relogin_offered
+            && self
+                .relogin_snoozed_until
+                .is_none_or(|until| Instant::now() >= until)
+        {
+            self.relogin_offered = true;
+            self.app_event_tx.send(AppEvent::Code88SignInExpired);
+        }
+        self.state.set_88code_info(data);
+        self.write_metrics();
+    }
+
+    /// A sign-in ended without a new token; the next poll that still finds
+    /// the token expired asks again.
+    pub(crate) fn relogin_failed(&mut self) {
+        self.relogin_offered = false;
+    }
+
+    /// The user answered "Not now"; the usage poll asks again after
+    /// [`Self::RELOGIN_SNOOZE`]. A request the relay rejects still asks
+    /// right away.
+    pub(crate) fn relogin_declined(&mut self) {
+        self.relogin_offered = false;
+        self.relogin_snoozed_until = Some(Instant::now() + Self::RELOGIN_SNOOZE);
+    }
+
+    /// Latest 88code usage poll result, for the `/status` card.
+    pub(crate) fn code88_info(&self) -> Option<&StatusLine88CodeSnapshot> {
+        self.state.code88_info()
     }
 
     /// Update authentication tokens after successful browser login refresh.
@@ -301,14 +794,11 @@ impl StatusLineOverlay {
     pub(crate) fn update_api_key(&mut self, login_token: String) {
         // Store the raw login token for getLoginInfo API
         self.code88_login_token = Some(login_token.clone());
+        self.relogin_offered = false;
+        self.relogin_snoozed_until = None;
 
         // Format the token as the API key (88_ prefix) for usage API
-        let api_key = if login_token.starts_with("88_") {
-            login_token
-        } else {
-            format!("88_{login_token}")
-        };
-        self.code88_api_key = Some(api_key);
+        self.code88_api_key = Some(code88::api_key(&login_token));
 
         // Restart the poller with new tokens (this also fetches data immediately)
         self.start_88code_poller();
@@ -318,17 +808,131 @@ impl StatusLineOverlay {
         self.state.set_renderer(renderer);
     }
 
+    pub(crate) fn skin(&self) -> &str {
+        &self.skin
+    }
+
+    /// Switch to the skin called `name`, built-in or registered. Returns
+    /// `false` when no such skin exists and the current renderer is kept.
+    pub(crate) fn set_skin(&mut self, name: &str) -> bool {
+        match skins::for_name(name) {
+            Some(renderer) => {
+                self.set_renderer(renderer);
+                self.skin = name.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Apply `config.tui_statusline` after `config.toml` was edited: the
+    /// skin, colors, segments and layout change on the next frame, and the
+    /// pollers restart when their settings changed. Git and hostname are
+    /// detected again only when `git_base_branch` or `hosts` changed. A
+    /// renderer passed to [`StatusLineOverlay::new`] is replaced by the
+    /// configured one.
+    pub(crate) fn apply_settings(&mut self, config: &Config) {
+        let workspace = load_workspace_state(&config.codex_home, &config.cwd)
+            .ok()
+            .and_then(|state| state.statusline)
+            .unwrap_or_default();
+        let settings = workspace.apply_to(&config.tui_statusline);
+        self.state.begin_update();
+        if !config.tui_accessible {
+            self.set_renderer(configured_renderer(&config.codex_home, &settings));
+        }
+        self.skin = settings.skin.clone();
+        self.state.apply_settings(&settings);
+        self.sync_model(config);
+        self.state.commit();
+
+        if settings.hosts != self.hosts {
+            self.hosts = settings.hosts.clone();
+            self.spawn_host_refresh();
+        }
+        if settings.git_base_branch != self.git_base_branch {
+            self.git_base_branch = settings.git_base_branch.clone();
+            self.providers = system_providers(settings.git_base_branch.clone(), config);
+            self.spawn_git_refresh();
+        }
+        self.start_process_poller();
+        let environment_refresh =
+            Duration::from_secs(u64::try_from(settings.environment_refresh_secs).unwrap_or(0));
+        if environment_refresh != self.environment_refresh {
+            self.environment_refresh = environment_refresh;
+            self.start_environment_poller();
+        }
+        if settings.hot_reload != self.hot_reload {
+            self.hot_reload = settings.hot_reload;
+            self.start_settings_poller();
+        }
+        self.metrics_file = metrics_file_path(&config.codex_home, &settings);
+        if self.metrics_file.is_none() {
+            self.metrics_writer = None;
+        }
+        self.session_timeline_enabled = settings.session_timeline;
+        if self.session_timeline_enabled {
+            self.persist_timeline();
+        } else {
+            self.timeline = SessionTimeline::default();
+        }
+        let reachability_probe =
+            Duration::from_secs(u64::try_from(settings.reachability_probe_secs).unwrap_or(0));
+        if reachability_probe != self.reachability_probe {
+            self.reachability_probe = reachability_probe;
+            if self.reachability_poller.is_some() {
+                self.stop_reachability_poller();
+                self.start_reachability_poller();
+            }
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn state_mut(&mut self) -> &mut StatusLineState {
         &mut self.state
     }
 
+    /// Set before the session id, so the timeline knows where to go.
+    pub(crate) fn set_rollout_path(&mut self, rollout_path: Option<PathBuf>) {
+        self.rollout_path = rollout_path;
+        self.persist_timeline();
+    }
+
     pub(crate) fn set_session_id(&mut self, session_id: Option<String>) {
+        let changed = session_id.as_deref() != self.state.session_id();
         self.state.set_session_id(session_id);
+        self.persist_timeline();
+        // A new session starts its own series in the metrics file.
+        if changed {
+            self.task_metrics = TaskMetrics::default();
+            self.write_metrics();
+        }
+    }
+
+    pub(crate) fn session_id(&self) -> Option<&str> {
+        self.state.session_id()
+    }
+
+    fn persist_timeline(&mut self) {
+        if !self.session_timeline_enabled {
+            return;
+        }
+        if let (Some(rollout_path), Some(id)) =
+            (self.rollout_path.as_ref(), self.state.session_id())
+        {
+            self.timeline.persist_to(rollout_path, id);
+        }
     }
 
     pub(crate) fn set_run_header(&mut self, header: &str) {
-        self.state.update_run_header(header);
+        self.state.update_run_header(header, RunCategory::Thinking);
+        self.publish_run_state();
+    }
+
+    pub(crate) fn set_patch_run_label(&mut self) {
+        self.state
+            .update_run_header(PATCH_RUN_LABEL, RunCategory::Patch);
+        self.publish_run_state();
     }
 
     pub(crate) fn set_interrupt_hint_visible(&mut self, visible: bool) {
@@ -337,26 +941,135 @@ impl StatusLineOverlay {
 
     pub(crate) fn start_task(&mut self, label: &str) {
         self.state.start_task(label);
+        self.timeline.record(TimelineEvent::Start {
+            label: label.to_string(),
+        });
+        self.start_reachability_poller();
+        self.publish_run_state();
+    }
+
+    pub(crate) fn complete_task(&mut self) -> Option<CompletedRun> {
+        let completed = self.state.complete_task();
+        self.finish_task(completed)
     }
 
-    pub(crate) fn complete_task(&mut self) {
-        self.state.complete_task();
+    /// End a run the user stopped with Esc.
+    pub(crate) fn interrupt_task(&mut self) -> Option<CompletedRun> {
+        let completed = self.state.interrupt_task();
+        self.finish_task(completed)
+    }
+
+    fn finish_task(&mut self, completed: Option<CompletedRun>) -> Option<CompletedRun> {
+        self.stop_reachability_poller();
+        self.publish_run_state();
+        if let Some(run) = completed.as_ref() {
+            self.timeline.record(TimelineEvent::Complete {
+                label: run.label.clone(),
+                elapsed_secs: i64::try_from(run.elapsed.as_secs()).unwrap_or(i64::MAX),
+            });
+            self.task_metrics.record(run.elapsed);
+            self.write_metrics();
+        }
+        completed
+    }
+
+    /// Rewrite `metrics_file`, when one is configured.
+    fn write_metrics(&mut self) {
+        let Some(path) = self.metrics_file.as_ref() else {
+            return;
+        };
+        if self
+            .metrics_writer
+            .as_ref()
+            .is_none_or(|writer| writer.path() != path)
+        {
+            let Ok(handle) = Handle::try_current() else {
+                return;
+            };
+            self.metrics_writer = Some(MetricsWriter::spawn(&handle, path.clone()));
+        }
+        let contents = render_metrics(
+            self.state.session_id().unwrap_or_default(),
+            &self.task_metrics,
+            self.state.model_token_usage(),
+            self.state.code88_info(),
+        );
+        if let Some(writer) = self.metrics_writer.as_ref() {
+            writer.write(contents);
+        }
+    }
+
+    pub(crate) fn pause_task(&mut self) {
+        self.state.pause_task();
+        self.timeline.record(TimelineEvent::Pause);
+        self.stop_reachability_poller();
+        self.publish_run_state();
     }
 
     pub(crate) fn resume_timer(&mut self) {
         self.state.resume_timer();
+        self.start_reachability_poller();
+        self.publish_run_state();
     }
 
     pub(crate) fn update_tokens(&mut self, info: Option<TokenUsageInfo>) {
         self.state.update_tokens(info);
     }
 
+    pub(crate) fn model_token_usage(&self) -> &[(String, TokenUsage)] {
+        self.state.model_token_usage()
+    }
+
+    pub(crate) fn compact_suggested(&self) -> bool {
+        self.state.compact_suggested()
+    }
+
+    pub(crate) fn set_pending_approvals(&mut self, count: i64) {
+        self.state.set_pending_approvals(count);
+        self.publish_run_state();
+    }
+
     pub(crate) fn set_queued_messages(&mut self, messages: Vec<String>) {
         self.state.set_queued_messages(messages);
+        self.publish_run_state();
+    }
+
+    pub(crate) fn terminal_progress(&self) -> TerminalProgress {
+        self.state.terminal_progress()
     }
 
-    pub(crate) const fn reserved_rows() -> u16 {
-        Self::RESERVED_ROWS
+    pub(crate) fn terminal_title(&self) -> Option<String> {
+        self.state.terminal_title(Instant::now())
+    }
+
+    /// Pause the status line's animations while the terminal is unfocused.
+    pub(crate) fn set_terminal_focused(&mut self, focused: bool) {
+        self.state.set_terminal_focused(focused);
+    }
+
+    /// Raise an attention entry naming the skin when its renderer panicked
+    /// during the last frame.
+    pub(crate) fn report_renderer_panic(&mut self) {
+        if self.state.take_renderer_panic() {
+            let message = format!(
+                "status line skin `{}` crashed; showing `{}` until the skin is changed",
+                self.skin,
+                skins::DEFAULT_SKIN
+            );
+            self.state.push_attention(Attention::new(
+                AttentionSource::Skin,
+                AttentionSeverity::Warning,
+                message,
+            ));
+        }
+    }
+
+    pub(crate) const fn reserved_rows(has_active_view: bool) -> u16 {
+        if has_active_view {
+            Self::COMPACT_RESERVED_ROWS
+        } else {
+            Self::RESERVED_ROWS
+        }
     }
 
     pub(crate) fn layout(
@@ -366,9 +1079,21 @@ impl StatusLineOverlay {
     ) -> Option<StatusLineLayout> {
         let reserved_height = Self::RESERVED_ROWS;
         let minimum_height = reserved_height + Self::MIN_PANE_CONTENT_HEIGHT;
-        if has_active_view || bottom_pane_area.height < minimum_height {
+        if bottom_pane_area.height < Self::COMPACT_RESERVED_ROWS + Self::MIN_PANE_CONTENT_HEIGHT {
             return None;
         }
+        if has_active_view {
+            return Some(Self::compact_layout(
+                bottom_pane_area,
+                RunPillPlacement::Hidden,
+            ));
+        }
+        if bottom_pane_area.height < minimum_height {
+            return Some(Self::compact_layout(
+                bottom_pane_area,
+                RunPillPlacement::Inline,
+            ));
+        }
 
         let mut y_cursor = bottom_pane_area.y.saturating_add(Self::MARGIN_ABOVE_PILL);
         let run_pill_area = Rect {
@@ -403,9 +1128,37 @@ impl StatusLineOverlay {
             pane_area,
             run_pill_area,
             status_line_area,
+            run_pill: RunPillPlacement::OwnRow,
         })
     }
 
+    /// Single reserved row: the pane takes everything above the status line.
+    fn compact_layout(bottom_pane_area: Rect, run_pill: RunPillPlacement) -> StatusLineLayout {
+        let status_line_y = bottom_pane_area
+            .y
+            .saturating_add(bottom_pane_area.height)
+            .saturating_sub(Self::STATUS_LINE_HEIGHT);
+        StatusLineLayout {
+            pane_area: Rect {
+                height: bottom_pane_area
+                    .height
+                    .saturating_sub(Self::COMPACT_RESERVED_ROWS),
+                ..bottom_pane_area
+            },
+            run_pill_area: Rect {
+                y: status_line_y,
+                height: 0,
+                ..bottom_pane_area
+            },
+            status_line_area: Rect {
+                y: status_line_y,
+                height: Self::STATUS_LINE_HEIGHT,
+                ..bottom_pane_area
+            },
+            run_pill,
+        }
+    }
+
     pub(crate) fn render_run_pill(&self, area: Rect, buf: &mut Buffer) {
         let line = self.state.render_run_pill(area.width);
         line.render(area, buf);
@@ -416,32 +1169,90 @@ impl StatusLineOverlay {
         line.render(area, buf);
     }
 
-    pub(crate) fn exec_status_label(command: &[String]) -> String {
-        if command.is_empty() {
-            return "Running command".to_string();
-        }
-        let joined = command.join(" ");
-        let summary = truncate_text(&joined, 40);
-        format!("Running {summary}")
+    /// Status line for when the run pill is hidden, with the queued message
+    /// count the pill would have shown.
+    pub(crate) fn render_status_line_without_pill(&self, area: Rect, buf: &mut Buffer) {
+        let line = self.state.render_line_without_pill(area.width);
+        line.render(area, buf);
     }
 
-    pub(crate) fn tool_status_label(invocation: &McpInvocation) -> String {
-        let label = if invocation.server.is_empty() {
-            invocation.tool.clone()
-        } else {
-            format!("{}:{}", invocation.server, invocation.tool)
+    /// Compact layout row: the run pill, capped at half the width, then the
+    /// status line in whatever is left.
+    pub(crate) fn render_compact_line(&self, area: Rect, buf: &mut Buffer) {
+        let pill = trim_trailing_padding(self.state.render_run_pill(area.width / 2));
+        let pill_width = u16::try_from(pill.width())
+            .unwrap_or(u16::MAX)
+            .min(area.width);
+        pill.render(area, buf);
+        let offset = if pill_width == 0 { 0 } else { pill_width + 1 };
+        let line_area = Rect {
+            x: area.x.saturating_add(offset),
+            width: area.width.saturating_sub(offset),
+            ..area
         };
-        format!("Running tool {}", truncate_text(&label, 40))
+        if !line_area.is_empty() {
+            self.render_status_line(line_area, buf);
+        }
+    }
+
+    pub(crate) fn set_exec_run_label(&mut self, command: &[String]) {
+        let label = self.state.run_labels().exec(command);
+        self.state.update_run_label(label, RunCategory::Exec);
+        self.publish_run_state();
+    }
+
+    pub(crate) fn record_exec_exit(&mut self, command: &[String], exit_code: i32) {
+        let command = command_text(command);
+        if exit_code != 0 {
+            self.timeline.record(TimelineEvent::ExecFailed {
+                command: command.clone(),
+                exit_code,
+            });
+        }
+        self.state.record_exec_exit(&command, exit_code);
+        self.publish_run_state();
+    }
+
+    pub(crate) fn set_tool_run_label(&mut self, invocation: &McpInvocation) {
+        let label = self.state.run_labels().tool(invocation);
+        self.state.update_run_label(label, RunCategory::ToolCall);
+        self.publish_run_state();
+    }
+
+    pub(crate) fn set_approval_run_label(&mut self, subject: &str) {
+        let label = self.state.run_labels().approval(subject);
+        self.state.update_run_label(label, RunCategory::Approval);
+        self.publish_run_state();
     }
+}
 
-    pub(crate) fn approval_status_label(subject: &str) -> String {
-        format!("Awaiting approval for {subject}")
+/// Drops the blank padding skins add to right-align the pill, so the
+/// compact row can put the status line right after it.
+fn trim_trailing_padding(mut line: Line<'static>) -> Line<'static> {
+    while let Some(span) = line.spans.last_mut() {
+        if span.style.bg.is_some() {
+            break;
+        }
+        let trimmed = span.content.trim_end();
+        if trimmed.is_empty() {
+            line.spans.pop();
+        } else {
+            if trimmed.len() != span.content.len() {
+                span.content = trimmed.to_string().into();
+            }
+            break;
+        }
     }
+    line
 }
 
 impl Drop for StatusLineOverlay {
     fn drop(&mut self) {
         self.stop_88code_poller();
+        self.stop_process_poller();
+        self.stop_environment_poller();
+        self.stop_settings_poller();
+        self.stop_reachability_poller();
     }
 }
 
@@ -454,11 +1265,104 @@ impl std::fmt::Debug for StatusLineOverlay {
             .field("code88_api_key", &self.code88_api_key.is_some())
             .field("code88_login_token", &self.code88_login_token.is_some())
             .field("code88_poller", &self.code88_poller.is_some())
+            .field("process_poller", &self.process_poller.is_some())
+            .field("environment_poller", &self.environment_poller.is_some())
+            .field("settings_poller", &self.settings_poller.is_some())
+            .field("reachability_poller", &self.reachability_poller.is_some())
+            .field("control_socket", &self.control.is_some())
             .finish()
     }
 }
 
-fn detect_devspace() -> Option<String> {
+/// The configured script, falling back to the format string and then the
+/// configured skin when no script is set or it fails to load.
+fn configured_renderer(
+    codex_home: &Path,
+    settings: &StatusLineSettings,
+) -> Box<dyn StatusLineRenderer> {
+    let Some(script) = settings.script.as_ref() else {
+        return configured_format_or_skin(settings);
+    };
+    let path = skins::resolve_script_path(codex_home, script);
+    let budget = Duration::from_millis(u64::try_from(settings.script_timeout_ms).unwrap_or(0));
+    match skins::ScriptedStatusLineRenderer::load(
+        &path,
+        budget,
+        configured_format_or_skin(settings),
+    ) {
+        Ok(renderer) => Box::new(renderer),
+        Err(err) => {
+            tracing::warn!("{err:#}");
+            configured_format_or_skin(settings)
+        }
+    }
+}
+
+fn configured_format_or_skin(settings: &StatusLineSettings) -> Box<dyn StatusLineRenderer> {
+    let skin = configured_skin(&settings.skin);
+    match settings.format.clone() {
+        Some(format) => Box::new(skins::FormatStatusLineRenderer::new(format, skin)),
+        None => skin,
+    }
+}
+
+fn configured_skin(name: &str) -> Box<dyn StatusLineRenderer> {
+    skins::for_name(name).unwrap_or_else(|| {
+        tracing::warn!(
+            "unknown status line skin `{name}`, falling back to `{}`",
+            skins::DEFAULT_SKIN
+        );
+        Box::new(CustomStatusLineRenderer)
+    })
+}
+
+fn metrics_file_path(codex_home: &Path, settings: &StatusLineSettings) -> Option<PathBuf> {
+    let path = settings.metrics_file.as_ref()?;
+    Some(if path.is_absolute() {
+        path.clone()
+    } else {
+        codex_home.join(path)
+    })
+}
+
+/// The provider segment only appears once the session leaves the default
+/// setup: a config profile is active or the provider is not OpenAI.
+fn provider_snapshot(config: &Config) -> Option<StatusLineProviderSnapshot> {
+    if config.active_profile.is_none() && config.model_provider_id == "openai" {
+        return None;
+    }
+    Some(StatusLineProviderSnapshot {
+        name: config.model_provider.name.clone(),
+        profile: config.active_profile.clone(),
+    })
+}
+
+/// Devspace, hostname, AWS profile and dev environment, detected together by
+/// `spawn_host_refresh`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct HostEnvironment {
+    devspace: Option<String>,
+    hostname: Option<String>,
+    ssh: bool,
+    aws_profile: Option<String>,
+    dev_env: Option<StatusLineDevEnvSnapshot>,
+}
+
+impl HostEnvironment {
+    fn detect(cwd: &Path, command_env: &HashMap<String, String>) -> Self {
+        Self {
+            devspace: detect_devspace(cwd),
+            hostname: detect_hostname(),
+            ssh: detect_ssh(),
+            aws_profile: detect_aws_profile(command_env),
+            dev_env: detect_dev_env(command_env),
+        }
+    }
+}
+
+/// `TMUX_DEVSPACE` when set, otherwise the first line of a `.devspace` file
+/// at the repository root (or in `cwd` outside a repository).
+fn detect_devspace(cwd: &Path) -> Option<String> {
     #[cfg(test)]
     if let Some(override_value) = DEVSPACE_OVERRIDE.lock().unwrap().clone() {
         return override_value;
@@ -467,12 +1371,20 @@ fn detect_devspace() -> Option<String> {
     env::var("TMUX_DEVSPACE")
         .ok()
         .filter(|s| !s.trim().is_empty())
+        .or_else(|| read_devspace_file(cwd))
 }
 
-fn detect_aws_profile() -> Option<String> {
-    env::var("AWS_PROFILE")
-        .or_else(|_| env::var("AWS_VAULT"))
-        .ok()
+fn read_devspace_file(cwd: &Path) -> Option<String> {
+    let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+    let contents = std::fs::read_to_string(root.join(".devspace")).ok()?;
+    let name = contents.lines().next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+fn detect_aws_profile(command_env: &HashMap<String, String>) -> Option<String> {
+    command_env
+        .get("AWS_PROFILE")
+        .or_else(|| command_env.get("AWS_VAULT"))
         .map(|profile| {
             profile
                 .trim()
@@ -482,6 +1394,19 @@ fn detect_aws_profile() -> Option<String> {
         .filter(|s| !s.is_empty())
 }
 
+fn detect_dev_env(command_env: &HashMap<String, String>) -> Option<StatusLineDevEnvSnapshot> {
+    let nix = command_env
+        .get("IN_NIX_SHELL")
+        .map(|value| match value.trim() {
+            "pure" => NixShell::Pure,
+            _ => NixShell::Impure,
+        });
+    let direnv = command_env
+        .get("DIRENV_DIR")
+        .is_some_and(|dir| !dir.trim().is_empty());
+    (nix.is_some() || direnv).then_some(StatusLineDevEnvSnapshot { nix, direnv })
+}
+
 fn detect_hostname() -> Option<String> {
     if let Ok(host) = env::var("HOSTNAME")
         && !host.trim().is_empty()
@@ -491,20 +1416,213 @@ fn detect_hostname() -> Option<String> {
     get_hostname().ok().and_then(|os| os.into_string().ok())
 }
 
-async fn collect_status_line_git_snapshot(cwd: PathBuf) -> Option<StatusLineGitSnapshot> {
+fn detect_ssh() -> bool {
+    env::var_os("SSH_CONNECTION").is_some_and(|value| !value.is_empty())
+}
+
+/// The providers a running Codex refreshes from.
+fn system_providers(git_base_branch: String, config: &Config) -> StatusLineProviders {
+    StatusLineProviders {
+        git: Arc::new(SystemGitInfo {
+            base_branch: git_base_branch,
+            base_cache: Arc::new(Mutex::new(None)),
+            pull_request_cache: Arc::new(Mutex::new(PullRequestCache::default())),
+        }),
+        kube: Arc::new(KubeConfigFiles),
+        usage: Arc::new(CachedUsage {
+            inner: Arc::new(Code88Api),
+            cache: UsageCache::shared(config),
+        }),
+        reachability: Arc::new(EndpointProbe),
+    }
+}
+
+/// Git state from the `git` and `gh` command line tools.
+struct SystemGitInfo {
+    /// `tui.statusline.git_base_branch`; empty when off.
+    base_branch: String,
+    /// Last base divergence count, reused while neither HEAD nor the base
+    /// branch moves.
+    base_cache: Arc<Mutex<Option<GitBaseCache>>>,
+    /// Pull request lookups via `gh`, reused for several minutes per branch.
+    pull_request_cache: Arc<Mutex<PullRequestCache>>,
+}
+
+impl GitInfoProvider for SystemGitInfo {
+    fn git_snapshot(
+        &self,
+        cwd: PathBuf,
+        pull_request: bool,
+    ) -> ProviderFuture<Option<StatusLineGitSnapshot>> {
+        Box::pin(collect_status_line_git_snapshot(
+            cwd,
+            self.base_branch.clone(),
+            Arc::clone(&self.base_cache),
+            pull_request.then(|| Arc::clone(&self.pull_request_cache)),
+        ))
+    }
+}
+
+/// The Kubernetes context from kubeconfig files on disk.
+struct KubeConfigFiles;
+
+impl KubeProvider for KubeConfigFiles {
+    fn kube_context(&self, config_paths: Vec<PathBuf>) -> ProviderFuture<Option<String>> {
+        Box::pin(detect_kube_context_async(config_paths))
+    }
+}
+
+/// Usage from the 88code web API.
+struct Code88Api;
+
+impl UsageProvider for Code88Api {
+    fn usage(
+        &self,
+        login_token: String,
+        api_key: String,
+    ) -> ProviderFuture<Result<Code88AggregatedData, Code88Error>> {
+        Box::pin(async move { fetch_88code_aggregated(&login_token, &api_key).await })
+    }
+}
+
+/// `HEAD` requests to the model endpoint.
+struct EndpointProbe;
+
+impl ReachabilityProvider for EndpointProbe {
+    fn reachable(&self, url: String) -> ProviderFuture<bool> {
+        Box::pin(async move { probe_endpoint(&url).await })
+    }
+}
+
+async fn collect_status_line_git_snapshot(
+    cwd: PathBuf,
+    base_branch: String,
+    base_cache: Arc<Mutex<Option<GitBaseCache>>>,
+    pull_request_cache: Option<Arc<Mutex<PullRequestCache>>>,
+) -> Option<StatusLineGitSnapshot> {
     let info = collect_git_info(&cwd).await?;
-    let (dirty, ahead, behind) = git_status_porcelain(&cwd)
-        .await
-        .unwrap_or((false, None, None));
+    let status = git_status_porcelain(&cwd).await.unwrap_or_default();
+    let base = match status.head.as_deref() {
+        Some(head) if !base_branch.is_empty() => {
+            git_base_divergence(&cwd, &base_branch, head, &base_cache).await
+        }
+        _ => None,
+    };
+    let pull_request = match (info.branch.as_deref(), pull_request_cache) {
+        (Some(branch), Some(cache)) => current_pull_request(&cwd, branch, &cache).await,
+        _ => None,
+    };
+    let detached = status
+        .head
+        .as_deref()
+        .filter(|_| status.detached)
+        .map(|oid| oid.chars().take(7).collect());
     Some(StatusLineGitSnapshot {
         branch: info.branch,
-        dirty,
-        ahead,
-        behind,
+        dirty: status.dirty,
+        ahead: status.ahead,
+        behind: status.behind,
+        base,
+        pull_request,
+        checkout: git_checkout(&cwd).await,
+        detached,
+    })
+}
+
+/// The linked worktree, submodule or bare repository `cwd` is in; `None` in
+/// a repository's main worktree.
+async fn git_checkout(cwd: &Path) -> Option<StatusLineGitCheckout> {
+    let dirs = git_stdout(
+        cwd,
+        &[
+            "rev-parse",
+            "--path-format=absolute",
+            "--is-bare-repository",
+            "--git-dir",
+            "--git-common-dir",
+        ],
+    )
+    .await?;
+    let mut lines = dirs.lines();
+    let bare = lines.next()? == "true";
+    let git_dir = Path::new(lines.next()?);
+    let common_dir = Path::new(lines.next()?);
+    if bare || git_dir != common_dir {
+        return classify_git_checkout(bare, git_dir, common_dir);
+    }
+    // `--show-superproject-working-tree` prints nothing outside a submodule.
+    let tops = git_stdout(
+        cwd,
+        &[
+            "rev-parse",
+            "--show-toplevel",
+            "--show-superproject-working-tree",
+        ],
+    )
+    .await?;
+    let mut lines = tops.lines();
+    submodule_checkout(Path::new(lines.next()?), Path::new(lines.next()?))
+}
+
+/// A bare repository or linked worktree from `git rev-parse --git-dir
+/// --git-common-dir`; a linked worktree's git dir is
+/// `<common dir>/worktrees/<name>`.
+fn classify_git_checkout(
+    bare: bool,
+    git_dir: &Path,
+    common_dir: &Path,
+) -> Option<StatusLineGitCheckout> {
+    let repo = repository_name(common_dir)?;
+    if git_dir != common_dir {
+        return Some(StatusLineGitCheckout {
+            kind: GitCheckoutKind::Worktree,
+            repo,
+            name: git_dir.file_name()?.to_string_lossy().into_owned(),
+        });
+    }
+    bare.then(|| StatusLineGitCheckout {
+        kind: GitCheckoutKind::Bare,
+        repo,
+        name: String::new(),
     })
 }
 
-async fn git_status_porcelain(cwd: &Path) -> Option<(bool, Option<i64>, Option<i64>)> {
+/// `widgets` for both `widgets/.git` and a bare `widgets.git`.
+fn repository_name(common_dir: &Path) -> Option<String> {
+    let name = common_dir.file_name()?.to_string_lossy();
+    if name == ".git" {
+        return Some(
+            common_dir
+                .parent()?
+                .file_name()?
+                .to_string_lossy()
+                .into_owned(),
+        );
+    }
+    Some(name.strip_suffix(".git").unwrap_or(&name).to_string())
+}
+
+fn submodule_checkout(toplevel: &Path, superproject: &Path) -> Option<StatusLineGitCheckout> {
+    let path = toplevel.strip_prefix(superproject).ok()?;
+    Some(StatusLineGitCheckout {
+        kind: GitCheckoutKind::Submodule,
+        repo: superproject.file_name()?.to_string_lossy().into_owned(),
+        name: path.to_string_lossy().into_owned(),
+    })
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct GitStatusPorcelain {
+    dirty: bool,
+    ahead: Option<i64>,
+    behind: Option<i64>,
+    /// Commit HEAD points at; `None` before the first commit.
+    head: Option<String>,
+    /// HEAD is not on a branch.
+    detached: bool,
+}
+
+async fn git_status_porcelain(cwd: &Path) -> Option<GitStatusPorcelain> {
     let output = Command::new("git")
         .args(["status", "--porcelain=2", "--branch"])
         .current_dir(cwd)
@@ -514,41 +1632,116 @@ async fn git_status_porcelain(cwd: &Path) -> Option<(bool, Option<i64>, Option<i
     if !output.status.success() {
         return None;
     }
-    let text = String::from_utf8_lossy(&output.stdout);
-    let mut dirty = false;
-    let mut ahead = None;
-    let mut behind = None;
+    Some(parse_git_status_porcelain(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+fn parse_git_status_porcelain(text: &str) -> GitStatusPorcelain {
+    let mut status = GitStatusPorcelain::default();
     for line in text.lines() {
         if !line.starts_with('#') {
-            dirty = true;
+            status.dirty = true;
             continue;
         }
-        if let Some(rest) = line.strip_prefix("# branch.ab ") {
+        if let Some(oid) = line.strip_prefix("# branch.oid ") {
+            status.head = (oid != "(initial)").then(|| oid.to_string());
+        } else if line == "# branch.head (detached)" {
+            status.detached = true;
+        } else if let Some(rest) = line.strip_prefix("# branch.ab ") {
             let mut parts = rest.split_whitespace();
             if let Some(ahead_part) = parts.next() {
-                ahead = ahead_part
+                status.ahead = ahead_part
                     .strip_prefix('+')
                     .and_then(|s| s.parse::<i64>().ok());
             }
             if let Some(behind_part) = parts.next() {
-                behind = behind_part
+                status.behind = behind_part
                     .strip_prefix('-')
                     .and_then(|s| s.parse::<i64>().ok());
             }
         }
     }
-    Some((dirty, ahead, behind))
+    status
+}
+
+/// Base divergence count for one pair of commits.
+#[derive(Debug)]
+struct GitBaseCache {
+    head: String,
+    base: String,
+    ahead: i64,
+}
+
+/// Commits on HEAD that `base_branch` lacks. Counting walks history, so the
+/// result is cached until either commit changes; resolving the base is a
+/// cheap `rev-parse`. `None` when the base branch does not exist.
+async fn git_base_divergence(
+    cwd: &Path,
+    base_branch: &str,
+    head: &str,
+    cache: &Mutex<Option<GitBaseCache>>,
+) -> Option<StatusLineGitBaseSnapshot> {
+    let base = git_stdout(
+        cwd,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{base_branch}^{{commit}}"),
+        ],
+    )
+    .await?;
+    let name = base_branch
+        .strip_prefix("origin/")
+        .unwrap_or(base_branch)
+        .to_string();
+    let cached = cache.lock().ok().and_then(|cache| {
+        cache
+            .as_ref()
+            .filter(|entry| entry.head == head && entry.base == base)
+            .map(|entry| entry.ahead)
+    });
+    if let Some(ahead) = cached {
+        return Some(StatusLineGitBaseSnapshot { name, ahead });
+    }
+    let ahead = git_stdout(cwd, &["rev-list", "--count", &format!("{base}..{head}")])
+        .await?
+        .parse::<i64>()
+        .ok()?;
+    if let Ok(mut cache) = cache.lock() {
+        *cache = Some(GitBaseCache {
+            head: head.to_string(),
+            base,
+            ahead,
+        });
+    }
+    Some(StatusLineGitBaseSnapshot { name, ahead })
 }
 
-async fn detect_kube_context_async() -> Option<String> {
-    spawn_blocking(detect_kube_context_sync)
+/// Trimmed stdout of a successful git command.
+async fn git_stdout(cwd: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+async fn detect_kube_context_async(paths: Vec<PathBuf>) -> Option<String> {
+    spawn_blocking(move || detect_kube_context_sync(paths))
         .await
         .ok()
         .flatten()
 }
 
-fn detect_kube_context_sync() -> Option<String> {
-    for path in kube_config_paths() {
+fn detect_kube_context_sync(paths: Vec<PathBuf>) -> Option<String> {
+    for path in paths {
         if let Ok(contents) = std::fs::read_to_string(&path) {
             for line in contents.lines() {
                 let trimmed = line.trim();
@@ -567,10 +1760,10 @@ fn detect_kube_context_sync() -> Option<String> {
     None
 }
 
-fn kube_config_paths() -> Vec<PathBuf> {
-    if let Some(paths) = env::var_os("KUBECONFIG") {
-        env::split_paths(&paths).collect()
-    } else if let Some(home) = env::var_os("HOME") {
+fn kube_config_paths(command_env: &HashMap<String, String>) -> Vec<PathBuf> {
+    if let Some(paths) = command_env.get("KUBECONFIG") {
+        env::split_paths(paths).collect()
+    } else if let Some(home) = command_env.get("HOME") {
         vec![PathBuf::from(home).join(".kube/config")]
     } else {
         Vec::new()
@@ -602,13 +1795,20 @@ mod tests {
     use crate::app_event::AppEvent;
     use crate::app_event_sender::AppEventSender;
     use crate::statusline::CustomStatusLineRenderer;
+    use crate::statusline::DEFAULT_STATUS_MESSAGE;
     use crate::tui::FrameRequester;
     use codex_core::config::ConfigOverrides;
     use codex_core::config::ConfigToml;
     use ratatui::buffer::Buffer;
+    use std::collections::VecDeque;
+    use tokio::sync::mpsc::UnboundedReceiver;
     use tokio::sync::mpsc::unbounded_channel;
 
     fn overlay_for_tests() -> StatusLineOverlay {
+        overlay_with_events().0
+    }
+
+    fn overlay_with_events() -> (StatusLineOverlay, UnboundedReceiver<AppEvent>) {
         let mut cfg = Config::load_from_base_config_with_overrides(
             ConfigToml::default(),
             ConfigOverrides::default(),
@@ -616,15 +1816,368 @@ mod tests {
         )
         .expect("config");
         cfg.tui_custom_statusline = true;
-        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let (tx, rx) = unbounded_channel::<AppEvent>();
         let app_event_tx = AppEventSender::new(tx);
-        StatusLineOverlay::new(
+        let overlay = StatusLineOverlay::new(
             &cfg,
             FrameRequester::test_dummy(),
             app_event_tx,
             Some(Box::new(CustomStatusLineRenderer) as Box<dyn StatusLineRenderer>),
         )
-        .expect("overlay")
+        .expect("overlay");
+        (overlay, rx)
+    }
+
+    /// The first event `pick` accepts, skipping the others.
+    async fn next_event<T>(
+        rx: &mut UnboundedReceiver<AppEvent>,
+        pick: impl Fn(AppEvent) -> Option<T>,
+    ) -> T {
+        loop {
+            let event = tokio::time::timeout(Duration::from_secs(120), rx.recv())
+                .await
+                .expect("status line event")
+                .expect("channel open");
+            if let Some(value) = pick(event) {
+                return value;
+            }
+        }
+    }
+
+    /// Answers with a fixed snapshot and records whether `gh` was asked.
+    struct FakeGit {
+        snapshot: Option<StatusLineGitSnapshot>,
+        pull_requests: Arc<Mutex<Vec<bool>>>,
+    }
+
+    impl GitInfoProvider for FakeGit {
+        fn git_snapshot(
+            &self,
+            _cwd: PathBuf,
+            pull_request: bool,
+        ) -> ProviderFuture<Option<StatusLineGitSnapshot>> {
+            self.pull_requests.lock().unwrap().push(pull_request);
+            let snapshot = self.snapshot.clone();
+            Box::pin(async move { snapshot })
+        }
+    }
+
+    /// Answers with a fixed context and records the kubeconfig paths.
+    struct FakeKube {
+        context: Option<String>,
+        paths: Arc<Mutex<Vec<PathBuf>>>,
+    }
+
+    impl KubeProvider for FakeKube {
+        fn kube_context(&self, config_paths: Vec<PathBuf>) -> ProviderFuture<Option<String>> {
+            self.paths.lock().unwrap().extend(config_paths);
+            let context = self.context.clone();
+            Box::pin(async move { context })
+        }
+    }
+
+    /// Plays back queued responses, then reports no data.
+    struct FakeUsage {
+        responses: Mutex<VecDeque<Result<Code88AggregatedData, Code88Error>>>,
+    }
+
+    impl UsageProvider for FakeUsage {
+        fn usage(
+            &self,
+            _login_token: String,
+            _api_key: String,
+        ) -> ProviderFuture<Result<Code88AggregatedData, Code88Error>> {
+            let response = self
+                .responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or(Err(Code88Error::NoData));
+            Box::pin(async move { response })
+        }
+    }
+
+    /// Never reaches the endpoint and records the URLs probed.
+    struct Unreachable {
+        urls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ReachabilityProvider for Unreachable {
+        fn reachable(&self, url: String) -> ProviderFuture<bool> {
+            self.urls.lock().unwrap().push(url);
+            Box::pin(async { false })
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn reachability_probe_runs_only_while_a_task_runs() {
+        let (mut overlay, mut rx) = overlay_with_events();
+        let urls = Arc::new(Mutex::new(Vec::new()));
+        overlay.providers.reachability = Arc::new(Unreachable {
+            urls: Arc::clone(&urls),
+        });
+        overlay.model_endpoint = "https://models.example.com/v1".to_string();
+
+        // Off by default.
+        overlay.start_task("Working");
+        assert!(overlay.reachability_poller.is_none());
+        overlay.complete_task();
+
+        overlay.reachability_probe = Duration::from_secs(10);
+        overlay.start_task("Working");
+        let reachable = next_event(&mut rx, |event| match event {
+            AppEvent::StatusLineReachability(reachable) => Some(reachable),
+            _ => None,
+        })
+        .await;
+        assert!(!reachable);
+        overlay.update_reachability(reachable);
+        assert!(overlay.state.snapshot_for_render(Instant::now()).offline);
+        assert_eq!(
+            *urls.lock().unwrap(),
+            vec!["https://models.example.com/v1".to_string()]
+        );
+
+        overlay.complete_task();
+        assert!(overlay.reachability_poller.is_none());
+        assert!(!overlay.state.snapshot_for_render(Instant::now()).offline);
+        // A probe that lands after the task ended is ignored.
+        overlay.update_reachability(false);
+        assert!(!overlay.state.snapshot_for_render(Instant::now()).offline);
+    }
+
+    #[tokio::test]
+    async fn git_refresh_sends_the_provider_snapshot() {
+        let (mut overlay, mut rx) = overlay_with_events();
+        let pull_requests = Arc::new(Mutex::new(Vec::new()));
+        overlay.providers.git = Arc::new(FakeGit {
+            snapshot: Some(StatusLineGitSnapshot {
+                branch: Some("main".to_string()),
+                dirty: true,
+                ..StatusLineGitSnapshot::default()
+            }),
+            pull_requests: Arc::clone(&pull_requests),
+        });
+
+        overlay.refresh_git();
+        let snapshot = next_event(&mut rx, |event| match event {
+            AppEvent::StatusLineGit(snapshot) => Some(snapshot),
+            _ => None,
+        })
+        .await
+        .expect("git snapshot");
+        assert_eq!(snapshot.branch.as_deref(), Some("main"));
+        assert!(snapshot.dirty);
+        assert_eq!(*pull_requests.lock().unwrap(), vec![true]);
+
+        // Outside a repository the segment is cleared.
+        overlay.providers.git = Arc::new(FakeGit {
+            snapshot: None,
+            pull_requests,
+        });
+        overlay.refresh_git();
+        let snapshot = next_event(&mut rx, |event| match event {
+            AppEvent::StatusLineGit(snapshot) => Some(snapshot),
+            _ => None,
+        })
+        .await;
+        assert!(snapshot.is_none());
+    }
+
+    #[tokio::test]
+    async fn kube_refresh_reads_the_command_env_kubeconfig() {
+        let (mut overlay, mut rx) = overlay_with_events();
+        let paths = Arc::new(Mutex::new(Vec::new()));
+        overlay.command_env = HashMap::from([("KUBECONFIG".to_string(), "/tmp/kube".to_string())]);
+        overlay.providers.kube = Arc::new(FakeKube {
+            context: Some("prod-eu".to_string()),
+            paths: Arc::clone(&paths),
+        });
+
+        overlay.spawn_kube_refresh();
+        let context = next_event(&mut rx, |event| match event {
+            AppEvent::StatusLineKubeContext(context) => Some(context),
+            _ => None,
+        })
+        .await;
+        assert_eq!(context.as_deref(), Some("prod-eu"));
+        assert_eq!(*paths.lock().unwrap(), vec![PathBuf::from("/tmp/kube")]);
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn usage_poller_reports_errors_and_recovers() {
+        let (mut overlay, mut rx) = overlay_with_events();
+        overlay.code88_login_token = Some("login".to_string());
+        overlay.code88_api_key = Some("88_key".to_string());
+        overlay.providers.usage = Arc::new(FakeUsage {
+            responses: Mutex::new(VecDeque::from([
+                Err(Code88Error::TokenExpired),
+                Ok(Code88AggregatedData {
+                    service_tier: Some("LV3".to_string()),
+                    daily_requests: Some(12),
+                    ..Code88AggregatedData::default()
+                }),
+            ])),
+        });
+        let usage = |event: AppEvent| match event {
+            AppEvent::StatusLine88Code(snapshot) => snapshot,
+            _ => None,
+        };
+
+        overlay.start_88code_poller();
+        let failed = next_event(&mut rx, usage).await;
+        assert!(failed.is_error);
+        assert!(failed.token_expired);
+
+        // The next poll waits out the interval.
+        let recovered = next_event(&mut rx, usage).await;
+        assert!(!recovered.is_error);
+        assert_eq!(recovered.service_tier.as_deref(), Some("LV3"));
+        assert_eq!(recovered.daily_requests, Some(12));
+
+        overlay.stop_88code_poller();
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn usage_poller_retries_and_keeps_the_last_good_numbers() {
+        let (mut overlay, mut rx) = overlay_with_events();
+        overlay.code88_login_token = Some("login".to_string());
+        overlay.code88_api_key = Some("88_key".to_string());
+        let network = || Err(Code88Error::Network("connection reset".to_string()));
+        overlay.providers.usage = Arc::new(FakeUsage {
+            responses: Mutex::new(VecDeque::from([
+                network(),
+                Ok(Code88AggregatedData {
+                    credits_remaining: Some(3.2),
+                    ..Code88AggregatedData::default()
+                }),
+                network(),
+                network(),
+                network(),
+            ])),
+        });
+        let usage = |event: AppEvent| match event {
+            AppEvent::StatusLine88Code(snapshot) => snapshot,
+            _ => None,
+        };
+
+        overlay.start_88code_poller();
+        // The first failure is retried within the same poll.
+        let fetched = next_event(&mut rx, usage).await;
+        assert!(!fetched.is_error);
+        assert!(!fetched.stale);
+        assert_eq!(fetched.credits_remaining, Some(3.2));
+
+        // A poll that fails every attempt shows the old numbers as stale.
+        let stale = next_event(&mut rx, usage).await;
+        assert!(!stale.is_error);
+        assert!(stale.stale);
+        assert_eq!(stale.credits_remaining, Some(3.2));
+        assert_eq!(stale.fetched_at, fetched.fetched_at);
+        assert_eq!(
+            stale.error_msg.as_deref(),
+            Some("Network error: connection reset")
+        );
+
+        overlay.stop_88code_poller();
+    }
+
+    #[test]
+    fn expired_token_prompt_is_raised_again_after_a_failed_or_dismissed_sign_in() {
+        let (mut overlay, mut rx) = overlay_with_events();
+        let expired = || {
+            Some(StatusLine88CodeSnapshot {
+                token_expired: true,
+                ..StatusLine88CodeSnapshot::default()
+            })
+        };
+        let mut prompts = |overlay: &mut StatusLineOverlay| {
+            overlay.update_88code(expired());
+            overlay.update_88code(expired());
+            std::iter::from_fn(|| rx.try_recv().ok())
+                .filter(|event| matches!(event, AppEvent::Code88SignInExpired))
+                .count()
+        };
+
+        assert_eq!(prompts(&mut overlay), 1);
+        assert_eq!(prompts(&mut overlay), 0);
+
+        overlay.relogin_failed();
+        assert_eq!(prompts(&mut overlay), 1);
+
+        overlay.relogin_declined();
+        assert_eq!(prompts(&mut overlay), 0);
+        overlay.relogin_snoozed_until = Some(Instant::now());
+        assert_eq!(prompts(&mut overlay), 1);
+    }
+
+    #[test]
+    fn git_status_porcelain_reads_head_and_divergence() {
+        let status = parse_git_status_porcelain(
+            "# branch.oid 1f2e3d\n# branch.head feature\n# branch.upstream origin/feature\n# branch.ab +2 -1\n1 .M N... 100644 100644 100644 a b src/lib.rs\n",
+        );
+        assert_eq!(
+            status,
+            GitStatusPorcelain {
+                dirty: true,
+                ahead: Some(2),
+                behind: Some(1),
+                head: Some("1f2e3d".to_string()),
+                detached: false,
+            }
+        );
+        assert_eq!(
+            parse_git_status_porcelain("# branch.oid (initial)\n# branch.head main\n").head,
+            None
+        );
+    }
+
+    #[test]
+    fn git_status_porcelain_flags_a_detached_head() {
+        let status =
+            parse_git_status_porcelain("# branch.oid 1f2e3d4c5b\n# branch.head (detached)\n");
+        assert!(status.detached);
+        assert_eq!(status.head.as_deref(), Some("1f2e3d4c5b"));
+    }
+
+    #[test]
+    fn git_checkout_names_worktrees_bare_repos_and_submodules() {
+        let worktree = classify_git_checkout(
+            false,
+            Path::new("/src/codex/.git/worktrees/fix-tests"),
+            Path::new("/src/codex/.git"),
+        );
+        assert_eq!(
+            worktree,
+            Some(StatusLineGitCheckout {
+                kind: GitCheckoutKind::Worktree,
+                repo: "codex".to_string(),
+                name: "fix-tests".to_string(),
+            })
+        );
+        let bare = classify_git_checkout(
+            true,
+            Path::new("/srv/codex.git"),
+            Path::new("/srv/codex.git"),
+        );
+        assert_eq!(
+            bare.map(|checkout| (checkout.kind, checkout.repo)),
+            Some((GitCheckoutKind::Bare, "codex".to_string()))
+        );
+        assert_eq!(
+            classify_git_checkout(
+                false,
+                Path::new("/src/codex/.git"),
+                Path::new("/src/codex/.git")
+            ),
+            None
+        );
+        let submodule =
+            submodule_checkout(Path::new("/src/codex/vendor/lib"), Path::new("/src/codex"));
+        assert_eq!(
+            submodule.map(|checkout| (checkout.repo, checkout.name)),
+            Some(("codex".to_string(), "vendor/lib".to_string()))
+        );
     }
 
     #[test]
@@ -651,6 +2204,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn short_area_falls_back_to_compact_layout() {
+        let overlay = overlay_for_tests();
+        let area = Rect::new(0, 0, 80, 5);
+        let layout = overlay.layout(area, false).expect("compact layout");
+        assert_eq!(layout.run_pill, RunPillPlacement::Inline);
+        assert!(layout.run_pill_area.is_empty());
+        assert_eq!(layout.pane_area, Rect::new(0, 0, 80, 4));
+        assert_eq!(layout.status_line_area, Rect::new(0, 4, 80, 1));
+
+        assert!(
+            overlay.layout(Rect::new(0, 0, 80, 3), false).is_none(),
+            "no room for the pane and a status line"
+        );
+        assert_eq!(
+            overlay
+                .layout(Rect::new(0, 0, 80, 8), false)
+                .expect("full layout")
+                .run_pill,
+            RunPillPlacement::OwnRow
+        );
+    }
+
+    #[test]
+    fn compact_line_puts_run_pill_before_status_line() {
+        let overlay = overlay_for_tests();
+        let area = Rect::new(0, 0, 120, 1);
+        let mut buf = Buffer::empty(area);
+        overlay.render_compact_line(area, &mut buf);
+        let row: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect();
+        let pill_end = row
+            .find(DEFAULT_STATUS_MESSAGE)
+            .map(|start| start + DEFAULT_STATUS_MESSAGE.len())
+            .expect("run pill renders on the status line row");
+        assert!(
+            !row[pill_end..].trim().is_empty(),
+            "status line should follow the run pill: {row:?}"
+        );
+    }
+
     #[test]
     fn render_leaves_blank_margin_row() {
         let overlay = overlay_for_tests();
@@ -685,4 +2280,80 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn environment_segments_read_the_command_env() {
+        let command_env = HashMap::from([
+            ("AWS_VAULT".to_string(), "vault".to_string()),
+            ("AWS_PROFILE".to_string(), "staging".to_string()),
+            ("HOME".to_string(), "/home/dev".to_string()),
+            ("IN_NIX_SHELL".to_string(), "pure".to_string()),
+        ]);
+        assert_eq!(detect_aws_profile(&command_env).as_deref(), Some("staging"));
+        assert_eq!(
+            detect_dev_env(&command_env),
+            Some(StatusLineDevEnvSnapshot {
+                nix: Some(NixShell::Pure),
+                direnv: false,
+            })
+        );
+        assert_eq!(
+            kube_config_paths(&command_env),
+            vec![PathBuf::from("/home/dev/.kube/config")]
+        );
+
+        let without_profile = HashMap::from([("KUBECONFIG".to_string(), "/tmp/kube".to_string())]);
+        assert_eq!(detect_aws_profile(&without_profile), None);
+        assert_eq!(detect_dev_env(&without_profile), None);
+        assert_eq!(
+            kube_config_paths(&without_profile),
+            vec![PathBuf::from("/tmp/kube")]
+        );
+    }
+
+    #[tokio::test]
+    async fn host_environment_is_detected_off_the_ui_thread() {
+        let cfg = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            std::env::temp_dir(),
+        )
+        .expect("config");
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut overlay = StatusLineOverlay::new(
+            &cfg,
+            FrameRequester::test_dummy(),
+            AppEventSender::new(tx),
+            Some(Box::new(CustomStatusLineRenderer) as Box<dyn StatusLineRenderer>),
+        )
+        .expect("overlay");
+        overlay.command_env = HashMap::from([("AWS_PROFILE".to_string(), "staging".to_string())]);
+
+        overlay.spawn_host_refresh();
+        let environment = loop {
+            let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .expect("host environment event")
+                .expect("channel open");
+            if let AppEvent::StatusLineHostEnvironment(environment) = event {
+                break environment;
+            }
+        };
+        assert_eq!(environment.aws_profile.as_deref(), Some("staging"));
+    }
+
+    #[test]
+    fn devspace_file_is_read_from_the_repository_root() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let nested = dir.path().join("src");
+        std::fs::create_dir(&nested).expect("create src");
+        assert_eq!(read_devspace_file(&nested), None);
+
+        std::fs::write(dir.path().join(".devspace"), "  \n").expect("write .devspace");
+        assert_eq!(read_devspace_file(dir.path()), None);
+
+        std::fs::create_dir(dir.path().join(".git")).expect("create .git");
+        std::fs::write(dir.path().join(".devspace"), "earth\nignored\n").expect("write .devspace");
+        assert_eq!(read_devspace_file(&nested).as_deref(), Some("earth"));
+    }
 }
@@ -2,6 +2,10 @@ use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 
+use super::PolicyRisk;
+use super::PullRequestChecks;
+use super::attention::AttentionSeverity;
+
 #[allow(clippy::disallowed_methods)]
 pub(crate) const BASE: Color = Color::Rgb(30, 30, 46);
 #[allow(clippy::disallowed_methods)]
@@ -23,7 +27,6 @@ pub(crate) const ROSEWATER: Color = Color::Rgb(245, 224, 220);
 #[allow(clippy::disallowed_methods)]
 pub(crate) const TEAL: Color = Color::Rgb(148, 226, 213);
 #[allow(clippy::disallowed_methods)]
-#[allow(dead_code)]
 pub(crate) const SURFACE0: Color = Color::Rgb(49, 50, 68);
 #[allow(clippy::disallowed_methods)]
 pub(crate) const SUBTEXT0: Color = Color::Rgb(166, 173, 200);
@@ -36,6 +39,52 @@ pub(crate) const PEACH_LIGHT: Color = Color::Rgb(150, 107, 81);
 #[allow(clippy::disallowed_methods)]
 pub(crate) const RED_LIGHT: Color = Color::Rgb(146, 83, 100);
 
+/// ANSI stand-ins for the palette on 16-color terminals, picked by hue
+/// rather than distance so the pastel accents stay distinguishable.
+pub(crate) const ANSI16_FALLBACKS: &[(Color, Color)] = &[
+    (BASE, Color::Black),
+    (LAVENDER, Color::LightBlue),
+    (SKY, Color::LightCyan),
+    (MAUVE, Color::LightMagenta),
+    (PEACH, Color::Yellow),
+    (GREEN, Color::LightGreen),
+    (YELLOW, Color::LightYellow),
+    (RED, Color::LightRed),
+    (ROSEWATER, Color::White),
+    (TEAL, Color::Cyan),
+    (SURFACE0, Color::DarkGray),
+    (SUBTEXT0, Color::Gray),
+    (GREEN_LIGHT, Color::Green),
+    (YELLOW_LIGHT, Color::Yellow),
+    (PEACH_LIGHT, Color::Red),
+    (RED_LIGHT, Color::Red),
+];
+
+pub(crate) fn policy_color(risk: PolicyRisk) -> Color {
+    match risk {
+        PolicyRisk::Low => GREEN,
+        PolicyRisk::Medium => YELLOW,
+        PolicyRisk::High => RED,
+    }
+}
+
+pub(crate) fn attention_color(severity: AttentionSeverity) -> Color {
+    match severity {
+        AttentionSeverity::Info => SKY,
+        AttentionSeverity::Warning => YELLOW,
+        AttentionSeverity::Critical => RED,
+    }
+}
+
+pub(crate) fn pull_request_color(checks: PullRequestChecks) -> Color {
+    match checks {
+        PullRequestChecks::None => SUBTEXT0,
+        PullRequestChecks::Pending => YELLOW,
+        PullRequestChecks::Passing => GREEN,
+        PullRequestChecks::Failing => RED,
+    }
+}
+
 pub(crate) fn queue_preview_style() -> Style {
     Style::default()
         .fg(SUBTEXT0)
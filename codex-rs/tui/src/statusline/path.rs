@@ -0,0 +1,147 @@
+//! Directory abbreviation for the path segment, in the spirit of starship's
+//! `truncation_length` and fish's `prompt_pwd`: keep the last few
+//! directories and collapse or abbreviate the ones before them.
+
+use codex_core::config::types::StatusLineSettings;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::text_formatting::center_truncate_path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PathStyle {
+    max_width: usize,
+    truncation_length: usize,
+    fish_style_length: usize,
+    fish_style_tail: bool,
+}
+
+impl Default for PathStyle {
+    fn default() -> Self {
+        Self::from_config(&StatusLineSettings::default())
+    }
+}
+
+impl PathStyle {
+    pub(crate) fn from_config(settings: &StatusLineSettings) -> Self {
+        Self {
+            max_width: usize::try_from(settings.path_max_width).unwrap_or(0),
+            truncation_length: usize::try_from(settings.path_truncation_length).unwrap_or(0),
+            fish_style_length: usize::try_from(settings.path_fish_style_length).unwrap_or(0),
+            fish_style_tail: settings.path_fish_style_tail,
+        }
+    }
+
+    /// Abbreviates a home-relative display path (`~/a/b/c`) or an absolute
+    /// one. The first component (`~`, the root, or a drive) is always kept.
+    pub(crate) fn abbreviate(&self, display: &str) -> String {
+        let sep = std::path::MAIN_SEPARATOR;
+        let mut components: Vec<&str> = display.split(sep).collect();
+        if components.len() <= 2 {
+            return display.to_string();
+        }
+        let anchor = components.remove(0);
+        let Some(last) = components.pop() else {
+            return display.to_string();
+        };
+
+        let kept_full = self.truncation_length.saturating_sub(1);
+        let split = if self.truncation_length == 0 {
+            0
+        } else {
+            components.len().saturating_sub(kept_full)
+        };
+        let (head, tail) = components.split_at(split);
+
+        let mut parts: Vec<String> = vec![anchor.to_string()];
+        if !head.is_empty() {
+            if self.fish_style_length == 0 {
+                parts.push("…".to_string());
+            } else {
+                parts.extend(head.iter().map(|dir| self.fish(dir)));
+            }
+        }
+        parts.extend(tail.iter().map(|dir| {
+            if self.fish_style_tail && self.fish_style_length > 0 {
+                self.fish(dir)
+            } else {
+                (*dir).to_string()
+            }
+        }));
+        parts.push(last.to_string());
+        parts.join(&sep.to_string())
+    }
+
+    /// Center-truncates the full path segment to `path_max_width`.
+    pub(crate) fn fit(&self, display: &str) -> String {
+        center_truncate_path(display, self.max_width)
+    }
+
+    /// First `fish_style_length` characters; a leading `.` does not count.
+    fn fish(&self, dir: &str) -> String {
+        let (dot, name) = match dir.strip_prefix('.') {
+            Some(rest) => (".", rest),
+            None => ("", dir),
+        };
+        let short: String = name.graphemes(true).take(self.fish_style_length).collect();
+        format!("{dot}{short}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn style(truncation_length: i64, fish_style_length: i64, tail: bool) -> PathStyle {
+        PathStyle::from_config(&StatusLineSettings {
+            path_truncation_length: truncation_length,
+            path_fish_style_length: fish_style_length,
+            path_fish_style_tail: tail,
+            ..StatusLineSettings::default()
+        })
+    }
+
+    fn native(path: &str) -> String {
+        path.replace('/', std::path::MAIN_SEPARATOR_STR)
+    }
+
+    #[test]
+    fn defaults_keep_the_full_path() {
+        let path = native("~/work/monorepo/services/billing/api");
+        assert_eq!(PathStyle::default().abbreviate(&path), path);
+    }
+
+    #[test]
+    fn truncation_collapses_or_abbreviates_leading_directories() {
+        let path = native("~/work/monorepo/services/billing/api");
+        assert_eq!(
+            style(2, 0, false).abbreviate(&path),
+            native("~/…/billing/api")
+        );
+        assert_eq!(
+            style(2, 1, false).abbreviate(&path),
+            native("~/w/m/s/billing/api")
+        );
+        assert_eq!(
+            style(3, 2, true).abbreviate(&native("/srv/.config/deep/services/billing/api")),
+            native("/sr/.co/de/se/bi/api")
+        );
+        // Fewer directories than the limit: nothing to collapse.
+        assert_eq!(style(8, 0, false).abbreviate(&path), path);
+    }
+
+    #[test]
+    fn fish_style_tail_abbreviates_all_but_the_last_directory() {
+        let path = native("~/work/monorepo/services/billing/api");
+        assert_eq!(style(0, 1, true).abbreviate(&path), native("~/w/m/s/b/api"));
+    }
+
+    #[test]
+    fn fit_keeps_both_ends_of_long_paths() {
+        let path = native("~/work/monorepo/services/billing/internal/handlers/api");
+        let fitted = style(0, 0, false).fit(&path);
+        assert!(fitted.starts_with('~'), "{fitted}");
+        assert!(fitted.ends_with("api"), "{fitted}");
+        assert!(fitted.contains('…'), "{fitted}");
+    }
+}
@@ -0,0 +1,88 @@
+//! `codex statusline preview`: render a canned snapshot with a skin
+//! so skins and palettes can be iterated on without driving a TUI session.
+
+use std::io::Write;
+use std::time::Instant;
+
+use anyhow::Context;
+
+use super::fixtures;
+use super::skins;
+use crate::insert_history::write_spans;
+
+/// Write the run pill and status line that `skin` renders for `fixture` at
+/// `width` columns, one per line, with ANSI colors.
+pub fn write_statusline_preview(
+    writer: &mut impl Write,
+    skin: &str,
+    fixture: &str,
+    width: u16,
+) -> anyhow::Result<()> {
+    let Some(renderer) = skins::for_name(skin) else {
+        anyhow::bail!(
+            "unknown skin `{skin}`; expected one of: {}",
+            skins::skin_names().join(", ")
+        );
+    };
+    let now = Instant::now();
+    let Some(snapshot) = fixtures::for_name(fixture, now) else {
+        anyhow::bail!(
+            "unknown fixture `{fixture}`; expected one of: {}",
+            known_names(fixtures::FIXTURES)
+        );
+    };
+    for line in [
+        renderer.render_run_pill(&snapshot, width, now),
+        renderer.render(&snapshot, width, now),
+    ] {
+        write_spans(writer, line.spans.iter())
+            .and_then(|()| writeln!(writer))
+            .context("failed to write status line preview")?;
+    }
+    Ok(())
+}
+
+fn known_names(entries: &[(&str, &str)]) -> String {
+    entries
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn renders_every_skin_and_fixture_with_ansi_colors() {
+        for (skin, _) in skins::SKINS {
+            for (fixture, _) in fixtures::FIXTURES {
+                let mut out = Vec::new();
+                write_statusline_preview(&mut out, skin, fixture, 200).expect("preview renders");
+                let out = String::from_utf8(out).expect("utf-8 output");
+                assert_eq!(out.lines().count(), 2, "{skin} / {fixture}");
+                assert!(out.contains("gpt-5-codex"), "{skin} / {fixture}: {out}");
+                assert!(out.contains("\u{1b}["), "{skin} / {fixture}: {out}");
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_names_list_the_alternatives() {
+        let err = write_statusline_preview(&mut Vec::new(), "neon", "busy", 80)
+            .expect_err("unknown skin");
+        assert_eq!(
+            err.to_string(),
+            "unknown skin `neon`; expected one of: powerline, minimal-plain, \
+             compact-single-capsule, verbose-two-line"
+        );
+        let err = write_statusline_preview(&mut Vec::new(), "powerline", "chaos", 80)
+            .expect_err("unknown fixture");
+        assert_eq!(
+            err.to_string(),
+            "unknown fixture `chaos`; expected one of: busy, idle, low-context, full"
+        );
+    }
+}
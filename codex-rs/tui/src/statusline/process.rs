@@ -0,0 +1,83 @@
+//! Optional process segment: resident memory and CPU use of the codex
+//! process, sampled on a background interval so long sessions on small
+//! remote boxes can keep an eye on their footprint.
+
+use std::time::Duration;
+
+use sysinfo::Pid;
+use sysinfo::ProcessRefreshKind;
+use sysinfo::ProcessesToUpdate;
+use sysinfo::System;
+
+use super::StatusLineProcessSnapshot;
+
+/// How often the overlay samples the process.
+pub(crate) const PROCESS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const MIB: i64 = 1024 * 1024;
+const GIB: i64 = 1024 * MIB;
+
+pub(crate) struct ProcessSampler {
+    system: System,
+    pid: Pid,
+}
+
+impl ProcessSampler {
+    pub(crate) fn new() -> Option<Self> {
+        let pid = sysinfo::get_current_pid().ok()?;
+        Some(Self {
+            system: System::new(),
+            pid,
+        })
+    }
+
+    /// CPU use is averaged over the time since the previous sample, so the
+    /// first sample always reads 0%.
+    pub(crate) fn sample(&mut self) -> Option<StatusLineProcessSnapshot> {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[self.pid]),
+            true,
+            ProcessRefreshKind::nothing().with_memory().with_cpu(),
+        );
+        let process = self.system.process(self.pid)?;
+        Some(StatusLineProcessSnapshot {
+            rss_bytes: i64::try_from(process.memory()).unwrap_or(i64::MAX),
+            cpu_percent: process.cpu_usage().round() as i64,
+        })
+    }
+}
+
+/// `412M 3%`, switching to tenths of a GiB from 1 GiB up.
+pub(crate) fn process_text(process: &StatusLineProcessSnapshot) -> String {
+    let rss = if process.rss_bytes >= GIB {
+        format!("{:.1}G", process.rss_bytes as f64 / GIB as f64)
+    } else {
+        format!("{}M", process.rss_bytes / MIB)
+    };
+    format!("{rss} {}%", process.cpu_percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn formats_memory_in_mib_then_gib() {
+        let text = |rss_bytes, cpu_percent| {
+            process_text(&StatusLineProcessSnapshot {
+                rss_bytes,
+                cpu_percent,
+            })
+        };
+        assert_eq!(text(412 * MIB + 300, 3), "412M 3%");
+        assert_eq!(text(GIB + GIB / 4, 117), "1.2G 117%");
+    }
+
+    #[test]
+    fn samples_the_current_process() {
+        let mut sampler = ProcessSampler::new().expect("current pid");
+        let process = sampler.sample().expect("codex process is running");
+        assert!(process.rss_bytes > 0);
+    }
+}
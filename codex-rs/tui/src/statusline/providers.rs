@@ -0,0 +1,56 @@
+//! Data sources behind the overlay's background refreshes. The system
+//! providers in `overlay.rs` run git and `gh`, read kubeconfig files, call
+//! the 88code API and probe the model endpoint; tests swap in their own so event delivery can be checked
+//! without touching the machine or the network.
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use super::StatusLineGitSnapshot;
+use super::code88_api::Code88AggregatedData;
+use super::code88_api::Code88Error;
+
+/// What a provider hands back to the task that awaits it.
+pub(crate) type ProviderFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// Branch, working tree and pull request state for the git segment.
+pub(crate) trait GitInfoProvider: Send + Sync {
+    /// The snapshot for `cwd`, or `None` outside a repository. The pull
+    /// request lookup is skipped unless `pull_request` is set.
+    fn git_snapshot(
+        &self,
+        cwd: PathBuf,
+        pull_request: bool,
+    ) -> ProviderFuture<Option<StatusLineGitSnapshot>>;
+}
+
+/// The current context for the Kubernetes segment.
+pub(crate) trait KubeProvider: Send + Sync {
+    /// `current-context` from the first of `config_paths` that sets one.
+    fn kube_context(&self, config_paths: Vec<PathBuf>) -> ProviderFuture<Option<String>>;
+}
+
+/// 88code usage for the usage segments.
+pub(crate) trait UsageProvider: Send + Sync {
+    fn usage(
+        &self,
+        login_token: String,
+        api_key: String,
+    ) -> ProviderFuture<Result<Code88AggregatedData, Code88Error>>;
+}
+
+/// Whether the model endpoint answers, for the `offline` chip.
+pub(crate) trait ReachabilityProvider: Send + Sync {
+    fn reachable(&self, url: String) -> ProviderFuture<bool>;
+}
+
+/// The providers one overlay refreshes from.
+#[derive(Clone)]
+pub(crate) struct StatusLineProviders {
+    pub git: Arc<dyn GitInfoProvider>,
+    pub kube: Arc<dyn KubeProvider>,
+    pub usage: Arc<dyn UsageProvider>,
+    pub reachability: Arc<dyn ReachabilityProvider>,
+}
@@ -0,0 +1,33 @@
+//! Reachability probe of the model endpoint, run on the
+//! `tui.statusline.reachability_probe_secs` interval while a task runs so a
+//! network outage shows up as an `offline` chip instead of an endless
+//! spinner.
+
+use std::time::Duration;
+
+use codex_app_server_protocol::AuthMode;
+use codex_core::config::Config;
+use lazy_static::lazy_static;
+
+/// How long one probe may take before the endpoint counts as unreachable.
+pub(crate) const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+lazy_static! {
+    static ref HTTP_CLIENT: reqwest::Client = reqwest::Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+}
+
+/// The URL probed for the session's model provider: the one requests go
+/// to, which for the built-in provider depends on `auth_mode`.
+pub(crate) fn model_endpoint(config: &Config, auth_mode: Option<AuthMode>) -> String {
+    config.model_provider.base_url_for(auth_mode)
+}
+
+/// Whether a `HEAD` request to `url` gets any HTTP response. Error statuses
+/// still count: the endpoint answered, it just wants credentials or a
+/// different method.
+pub(crate) async fn probe_endpoint(url: &str) -> bool {
+    HTTP_CLIENT.head(url).send().await.is_ok()
+}
@@ -0,0 +1,150 @@
+//! Signing in to 88code again without leaving the TUI. When the relay
+//! rejects a request or the usage poll reports the token expired, the user is
+//! asked first; the browser login then runs on a background task and reports
+//! each step in the attention segment instead of printing over the screen.
+//!
+//! Where no browser can be launched, such as over ssh, the prompt also offers
+//! pasting a token copied from the 88code dashboard on any other device; it
+//! is checked against the 88code API before it is saved. 88code has no
+//! device-code endpoint to poll, so there is no code-and-wait flow.
+
+use codex_code88 as code88;
+
+use super::attention::Attention;
+use super::attention::AttentionSeverity;
+use super::attention::AttentionSource;
+use super::code88_api::Code88Error;
+use super::code88_api::LoginInfoData;
+use super::code88_api::fetch_login_info;
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::custom_prompt_view::CustomPromptView;
+use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+
+/// The expired-token prompt. Signing in sends
+/// [`AppEvent::Start88CodeRelogin`].
+pub(crate) fn relogin_confirmation_params() -> SelectionViewParams {
+    SelectionViewParams {
+        title: Some("88code token expired — open browser to re-login?".to_string()),
+        footer_hint: Some(standard_popup_hint_line()),
+        items: vec![
+            SelectionItem {
+                name: "Yes, open the browser".to_string(),
+                description: Some("Progress shows in the status line".to_string()),
+                actions: vec![Box::new(|tx| tx.send(AppEvent::Start88CodeRelogin))],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Paste a token instead".to_string(),
+                description: Some(
+                    "For ssh sessions: copy one from the 88code dashboard".to_string(),
+                ),
+                actions: vec![Box::new(|tx| tx.send(AppEvent::Open88CodeTokenPrompt))],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Not now".to_string(),
+                description: Some("Requests to the relay fail until you sign in".to_string()),
+                actions: vec![Box::new(|tx| tx.send(AppEvent::Code88ReloginDeclined))],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+/// Run the browser login on a background task. Each step replaces the
+/// sign-in attention entry; the outcome arrives as
+/// [`AppEvent::Refresh88CodeTokenResult`].
+pub(crate) fn spawn_relogin(
+    store: code88::TokenStore,
+    browser: code88::BrowserOptions,
+    tx: AppEventSender,
+) {
+    tokio::spawn(async move {
+        let progress_tx = tx.clone();
+        let progress = move |message: &str| {
+            progress_tx.send(AppEvent::StatusLineAttention(Attention::new(
+                AttentionSource::Auth,
+                AttentionSeverity::Info,
+                message,
+            )));
+        };
+        let result = code88::refresh_token_with_progress(&store, &browser, &progress).await;
+        tx.send(AppEvent::Refresh88CodeTokenResult(
+            result.map_err(|err| err.to_string()),
+        ));
+    });
+}
+
+/// The paste-a-token popup: where to get a token and a field for it.
+/// Submitting sends [`AppEvent::Submit88CodeToken`].
+pub(crate) fn token_prompt_view(tx: AppEventSender) -> CustomPromptView {
+    CustomPromptView::new(
+        "Paste an 88code token".to_string(),
+        "Paste the token and press Enter".to_string(),
+        Some(format!(
+            "Sign in at {} on any device and copy a token from the dashboard",
+            code88::LOGIN_URL
+        )),
+        Box::new(move |token: String| {
+            tx.send(AppEvent::Submit88CodeToken(token.trim().to_string()));
+        }),
+    )
+}
+
+/// Check a pasted token against the 88code API and save it when accepted.
+/// The outcome arrives as [`AppEvent::Refresh88CodeTokenResult`], as for the
+/// browser login.
+pub(crate) fn spawn_token_check(store: code88::TokenStore, token: String, tx: AppEventSender) {
+    tokio::spawn(async move {
+        let result = token_check_outcome(fetch_login_info(&token).await).and_then(|()| {
+            store
+                .save_token(&token, "pasted")
+                .map(|()| token)
+                .map_err(|err| err.to_string())
+        });
+        tx.send(AppEvent::Refresh88CodeTokenResult(result));
+    });
+}
+
+/// Whether the 88code API accepted a pasted token, as the message the
+/// sign-in attention entry shows when it did not.
+fn token_check_outcome(result: Result<LoginInfoData, Code88Error>) -> Result<(), String> {
+    match result {
+        Ok(_) => Ok(()),
+        Err(err) if err.is_token_expired() => {
+            Err("88code did not accept the pasted token".to_string())
+        }
+        Err(err) => Err(format!("could not check the pasted token: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn rejected_and_unchecked_tokens_say_why() {
+        assert_eq!(
+            token_check_outcome(Ok(LoginInfoData {
+                account_group_code: None,
+            })),
+            Ok(())
+        );
+        assert_eq!(
+            token_check_outcome(Err(Code88Error::TokenExpired)),
+            Err("88code did not accept the pasted token".to_string())
+        );
+        assert_eq!(
+            token_check_outcome(Err(Code88Error::Network("timed out".to_string()))),
+            Err("could not check the pasted token: Network error: timed out".to_string())
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Run labels shown in the run capsule while a command, tool call, or
+//! approval is in flight. Labels come from the `tui.statusline.*_label`
+//! templates; the first placeholder is the part that gets truncated or
+//! scrolled when it exceeds `run_label_max_width`.
+
+use std::path::Path;
+use std::time::Duration;
+
+use codex_core::config::types::StatusLineSettings;
+use codex_core::parse_command::extract_shell_command;
+use codex_core::protocol::McpInvocation;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::status::prefix_within_width;
+
+/// How long each marquee position stays on screen.
+pub(crate) const MARQUEE_STEP: Duration = Duration::from_millis(250);
+
+/// Blank columns between the end of a scrolling value and its restart.
+const MARQUEE_GAP: &str = "   ";
+
+#[derive(Debug, Clone)]
+pub(crate) struct RunLabelSettings {
+    exec: String,
+    tool: String,
+    approval: String,
+    max_width: usize,
+    marquee: bool,
+}
+
+impl RunLabelSettings {
+    pub(crate) fn from_config(settings: &StatusLineSettings) -> Self {
+        Self {
+            exec: settings.exec_label.clone(),
+            tool: settings.tool_label.clone(),
+            approval: settings.approval_label.clone(),
+            max_width: usize::try_from(settings.run_label_max_width).unwrap_or(0),
+            marquee: settings.run_label_marquee,
+        }
+    }
+
+    pub(crate) fn max_width(&self) -> usize {
+        self.max_width
+    }
+
+    pub(crate) fn marquee(&self) -> bool {
+        self.marquee
+    }
+
+    /// Label for an exec command. `{command}` is the full command line and
+    /// `{binary}` the file name of the program, looking through any
+    /// `bash -lc` wrapper.
+    pub(crate) fn exec(&self, command: &[String]) -> RunLabel {
+        if command.is_empty() {
+            return RunLabel::plain("Running command");
+        }
+        let joined = command.join(" ");
+        let binary = command_binary(command);
+        RunLabel::from_template(
+            &self.exec,
+            &[("command", joined.as_str()), ("binary", binary.as_str())],
+        )
+    }
+
+    /// Label for an MCP tool call. `{tool}` is `server:tool`, `{server}` and
+    /// `{name}` are its parts.
+    pub(crate) fn tool(&self, invocation: &McpInvocation) -> RunLabel {
+        let tool = if invocation.server.is_empty() {
+            invocation.tool.clone()
+        } else {
+            format!("{}:{}", invocation.server, invocation.tool)
+        };
+        RunLabel::from_template(
+            &self.tool,
+            &[
+                ("tool", tool.as_str()),
+                ("server", invocation.server.as_str()),
+                ("name", invocation.tool.as_str()),
+            ],
+        )
+    }
+
+    /// Label while waiting on the user; `{subject}` is what needs approval.
+    pub(crate) fn approval(&self, subject: &str) -> RunLabel {
+        RunLabel::from_template(&self.approval, &[("subject", subject)])
+    }
+}
+
+impl Default for RunLabelSettings {
+    fn default() -> Self {
+        Self::from_config(&StatusLineSettings::default())
+    }
+}
+
+/// A rendered template split around its first placeholder, so the variable
+/// part can be shortened without touching the surrounding text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RunLabel {
+    prefix: String,
+    value: String,
+    suffix: String,
+}
+
+impl RunLabel {
+    fn plain(text: &str) -> Self {
+        Self {
+            prefix: text.to_string(),
+            value: String::new(),
+            suffix: String::new(),
+        }
+    }
+
+    fn from_template(template: &str, values: &[(&str, &str)]) -> Self {
+        let first = values
+            .iter()
+            .filter_map(|(name, value)| {
+                let placeholder = format!("{{{name}}}");
+                template
+                    .find(&placeholder)
+                    .map(|start| (start, start + placeholder.len(), *value))
+            })
+            .min_by_key(|(start, _, _)| *start);
+        let Some((start, end, value)) = first else {
+            return Self::plain(&substitute(template, values));
+        };
+        Self {
+            prefix: substitute(&template[..start], values),
+            value: value.to_string(),
+            suffix: substitute(&template[end..], values),
+        }
+    }
+
+    /// Whether the variable part is wider than `max_width` columns.
+    pub(crate) fn overflows(&self, max_width: usize) -> bool {
+        UnicodeWidthStr::width(self.value.as_str()) > max_width
+    }
+
+    /// The label with its variable part cut to `max_width` columns, ending in
+    /// `...` when there is room for it.
+    pub(crate) fn truncated(&self, max_width: usize) -> String {
+        let value = if !self.overflows(max_width) {
+            self.value.clone()
+        } else if max_width >= 3 {
+            format!("{}...", prefix_within_width(&self.value, max_width - 3))
+        } else {
+            prefix_within_width(&self.value, max_width).to_string()
+        };
+        format!("{}{value}{}", self.prefix, self.suffix)
+    }
+
+    /// The label with a `max_width` column window over its variable part,
+    /// advanced one grapheme per `MARQUEE_STEP` of `elapsed`. The window is
+    /// padded to its full width when a wide character does not fit, so the
+    /// capsule keeps its size while the text scrolls.
+    pub(crate) fn marquee(&self, max_width: usize, elapsed: Duration) -> String {
+        if !self.overflows(max_width) {
+            return self.truncated(max_width);
+        }
+        let graphemes: Vec<&str> = self
+            .value
+            .graphemes(true)
+            .chain(MARQUEE_GAP.graphemes(true))
+            .collect();
+        let steps = elapsed.as_millis() / MARQUEE_STEP.as_millis();
+        let offset = (steps % graphemes.len() as u128) as usize;
+        let mut window = String::new();
+        let mut used = 0usize;
+        for grapheme in graphemes.iter().cycle().skip(offset) {
+            let width = UnicodeWidthStr::width(*grapheme);
+            if used + width > max_width {
+                break;
+            }
+            window.push_str(grapheme);
+            used += width;
+        }
+        window.push_str(&" ".repeat(max_width - used));
+        format!("{}{window}{}", self.prefix, self.suffix)
+    }
+}
+
+fn substitute(text: &str, values: &[(&str, &str)]) -> String {
+    values.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), value)
+    })
+}
+
+/// A command as the user would type it: the script inside a `bash -lc`
+/// wrapper, otherwise the words joined with spaces.
+pub(crate) fn command_text(command: &[String]) -> String {
+    match extract_shell_command(command) {
+        Some((_, script)) => script.to_string(),
+        None => command.join(" "),
+    }
+}
+
+fn command_binary(command: &[String]) -> String {
+    let program = match extract_shell_command(command) {
+        Some((_, script)) => shlex::split(script)
+            .and_then(|words| words.into_iter().next())
+            .unwrap_or_else(|| script.to_string()),
+        None => command[0].clone(),
+    };
+    Path::new(&program)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn settings(exec: &str) -> RunLabelSettings {
+        RunLabelSettings {
+            exec: exec.to_string(),
+            ..RunLabelSettings::default()
+        }
+    }
+
+    fn command(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn default_templates_match_previous_labels() {
+        let labels = RunLabelSettings::default();
+        let long = command(&[
+            "cargo",
+            "test",
+            "-p",
+            "codex-tui",
+            "--",
+            "statusline::state",
+        ]);
+        assert_eq!(
+            labels.exec(&long).truncated(labels.max_width()),
+            "Running cargo test -p codex-tui -- statusline..."
+        );
+        let invocation = McpInvocation {
+            server: "docs".to_string(),
+            tool: "search".to_string(),
+            arguments: None,
+        };
+        assert_eq!(
+            labels.tool(&invocation).truncated(labels.max_width()),
+            "Running tool docs:search"
+        );
+        assert_eq!(
+            labels.approval("patch").truncated(labels.max_width()),
+            "Awaiting approval for patch"
+        );
+        assert_eq!(
+            labels.exec(&[]).truncated(labels.max_width()),
+            "Running command"
+        );
+    }
+
+    #[test]
+    fn binary_placeholder_unwraps_shell_and_path() {
+        let labels = settings("{binary} ({command})");
+        let label = labels.exec(&command(&["bash", "-lc", "/usr/bin/git status --short"]));
+        // Only the first placeholder is shortened.
+        assert_eq!(
+            label.truncated(2),
+            "gi (bash -lc /usr/bin/git status --short)"
+        );
+        let label = settings("$ {binary}").exec(&command(&["/opt/bin/rg", "needle"]));
+        assert_eq!(label.truncated(40), "$ rg");
+    }
+
+    #[test]
+    fn marquee_scrolls_value_and_wraps() {
+        let label = settings("> {command} <").exec(&command(&["abcdefgh"]));
+        assert_eq!(label.marquee(4, Duration::ZERO), "> abcd <");
+        assert_eq!(label.marquee(4, MARQUEE_STEP * 2), "> cdef <");
+        assert_eq!(label.marquee(4, MARQUEE_STEP * 6), "> gh   <");
+        assert_eq!(label.marquee(4, MARQUEE_STEP * 11), "> abcd <");
+        assert_eq!(label.marquee(20, MARQUEE_STEP * 3), "> abcdefgh <");
+    }
+
+    #[test]
+    fn wide_values_are_measured_in_columns() {
+        let label = settings("$ {command}").exec(&command(&["构建", "发布版本"]));
+        assert!(label.overflows(10));
+        assert_eq!(label.truncated(10), "$ 构建 发...");
+        assert_eq!(label.truncated(13), "$ 构建 发布版本");
+        // A wide character that would straddle the window edge is replaced
+        // by padding instead of overflowing the capsule.
+        assert_eq!(label.marquee(4, MARQUEE_STEP), "$ 建  ");
+
+        let label = settings("$ {command}").exec(&command(&["e\u{301}cho"]));
+        assert!(!label.overflows(4));
+        assert_eq!(label.truncated(2), "$ e\u{301}c");
+    }
+}
@@ -0,0 +1,156 @@
+//! Opt-in hot reload of `[tui.statusline]` (`tui.statusline.hot_reload`).
+//! The overlay polls `CODEX_HOME/config.toml` and hands the re-parsed
+//! settings to the app whenever they change, so skins, colors and segments
+//! can be tweaked without restarting the TUI.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::load_config_as_toml_with_cli_overrides;
+use codex_core::config::types::StatusLineSettings;
+use toml::Value as TomlValue;
+
+/// How often the overlay checks `config.toml` for changes.
+pub(crate) const SETTINGS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Remembers what `config.toml` held and the settings it resolved to, so
+/// only edits that change `[tui.statusline]` are reported.
+pub(crate) struct SettingsWatcher {
+    codex_home: PathBuf,
+    /// `-c` overrides from the command line, applied again on every reload.
+    cli_overrides: Vec<(String, TomlValue)>,
+    last_contents: Option<String>,
+    last_settings: Option<StatusLineSettings>,
+}
+
+impl SettingsWatcher {
+    pub(crate) async fn new(codex_home: PathBuf, cli_overrides: Vec<(String, TomlValue)>) -> Self {
+        let last_contents = tokio::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE))
+            .await
+            .ok();
+        let last_settings = resolve_statusline_settings(&codex_home, cli_overrides.clone())
+            .await
+            .ok();
+        Self {
+            codex_home,
+            cli_overrides,
+            last_contents,
+            last_settings,
+        }
+    }
+
+    /// `[tui.statusline]` when an edit since the last poll changed it, or
+    /// the parse error. `None` while the file is unchanged or missing, or
+    /// the edit touched other settings only; a deleted file keeps the
+    /// settings in effect.
+    ///
+    /// The file goes through the same loader as at startup, so `-c`
+    /// overrides and managed config still win over it. Profiles carry no
+    /// `[tui.statusline]`, and the app replaces only that table, so the
+    /// active profile keeps applying.
+    pub(crate) async fn poll(&mut self) -> Option<Result<StatusLineSettings, String>> {
+        let contents = tokio::fs::read_to_string(self.codex_home.join(CONFIG_TOML_FILE))
+            .await
+            .ok()?;
+        if self.last_contents.as_ref() == Some(&contents) {
+            return None;
+        }
+        self.last_contents = Some(contents);
+        match resolve_statusline_settings(&self.codex_home, self.cli_overrides.clone()).await {
+            Ok(settings) if self.last_settings.as_ref() == Some(&settings) => None,
+            Ok(settings) => {
+                self.last_settings = Some(settings.clone());
+                Some(Ok(settings))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// The `[tui.statusline]` table `config.toml` resolves to with
+/// `cli_overrides` applied, with defaults for anything left out.
+async fn resolve_statusline_settings(
+    codex_home: &Path,
+    cli_overrides: Vec<(String, TomlValue)>,
+) -> Result<StatusLineSettings, String> {
+    let config = load_config_as_toml_with_cli_overrides(codex_home, cli_overrides)
+        .await
+        .map_err(|err| err.to_string())?;
+    let settings = config.tui.map(|tui| tui.statusline).unwrap_or_default();
+    settings.validate()?;
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn reports_each_statusline_change_once() {
+        let codex_home = tempdir().expect("tempdir");
+        let path = codex_home.path().join(CONFIG_TOML_FILE);
+        std::fs::write(&path, "[tui.statusline]\nskin = \"powerline\"\n").expect("write");
+        let mut watcher = SettingsWatcher::new(codex_home.path().to_path_buf(), Vec::new()).await;
+        assert!(watcher.poll().await.is_none(), "nothing changed yet");
+
+        std::fs::write(&path, "[tui.statusline]\nskin = \"minimal-plain\"\n").expect("write");
+        let settings = watcher
+            .poll()
+            .await
+            .expect("edit is reported")
+            .expect("settings parse");
+        assert_eq!(settings.skin, "minimal-plain");
+        assert!(
+            watcher.poll().await.is_none(),
+            "same edit is not reported twice"
+        );
+
+        std::fs::write(
+            &path,
+            "model = \"o3\"\n[tui.statusline]\nskin = \"minimal-plain\"\n",
+        )
+        .expect("write");
+        assert!(
+            watcher.poll().await.is_none(),
+            "edits outside [tui.statusline] are not reported"
+        );
+
+        std::fs::write(&path, "[tui.statusline\n").expect("write");
+        assert!(matches!(watcher.poll().await, Some(Err(_))));
+
+        std::fs::remove_file(&path).expect("remove");
+        assert!(
+            watcher.poll().await.is_none(),
+            "a deleted file keeps the settings"
+        );
+    }
+
+    #[tokio::test]
+    async fn cli_overrides_still_win_after_a_reload() {
+        let codex_home = tempdir().expect("tempdir");
+        let path = codex_home.path().join(CONFIG_TOML_FILE);
+        std::fs::write(&path, "[tui.statusline]\nskin = \"powerline\"\n").expect("write");
+        let overrides = vec![(
+            "tui.statusline.skin".to_string(),
+            TomlValue::String("verbose-two-line".to_string()),
+        )];
+        let mut watcher = SettingsWatcher::new(codex_home.path().to_path_buf(), overrides).await;
+
+        std::fs::write(
+            &path,
+            "[tui.statusline]\nskin = \"minimal-plain\"\nhot_reload = true\n",
+        )
+        .expect("write");
+        let settings = watcher
+            .poll()
+            .await
+            .expect("edit is reported")
+            .expect("settings parse");
+        assert_eq!(settings.skin, "verbose-two-line");
+        assert!(settings.hot_reload);
+    }
+}
@@ -0,0 +1,166 @@
+//! Screen-reader status line used when `tui.accessible` is set: unstyled
+//! words separated by commas, with no glyphs, icons or spinner, so the line
+//! reads as a sentence ("dir codex, model gpt-5, context 68 percent left,
+//! branch main dirty").
+
+use std::time::Instant;
+
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use super::join_fitting;
+use super::run_elapsed;
+use super::run_label;
+use crate::statusline::StatusLineRenderer;
+use crate::statusline::StatusLineSnapshot;
+
+const SEPARATOR: &str = ", ";
+
+#[derive(Debug, Default)]
+pub(crate) struct AccessibleRenderer;
+
+impl StatusLineRenderer for AccessibleRenderer {
+    fn render(&self, snapshot: &StatusLineSnapshot, width: u16, _now: Instant) -> Line<'static> {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(dir) = snapshot
+            .cwd_basename
+            .as_ref()
+            .or(snapshot.cwd_fallback.as_ref())
+        {
+            parts.push(format!("dir {dir}"));
+        }
+        if let Some(model) = snapshot.model.as_ref() {
+            parts.push(match model.detail.as_ref() {
+                Some(detail) => format!("model {} {detail}", model.label),
+                None => format!("model {}", model.label),
+            });
+        }
+        if let Some(context) = snapshot.context.as_ref() {
+            parts.push(format!(
+                "context {} percent left",
+                context.percent_remaining
+            ));
+        }
+        if let Some(git) = snapshot.git.as_ref()
+            && let Some(branch) = git.head_label()
+        {
+            parts.push(if git.dirty {
+                format!("branch {branch} dirty")
+            } else {
+                format!("branch {branch}")
+            });
+        }
+        if let Some(text) = snapshot.custom.as_ref() {
+            parts.push(text.clone());
+        }
+        if let Some(attention) = snapshot.attention.as_ref() {
+            parts.push(match attention.count {
+                1 => format!("1 warning: {}", attention.latest),
+                count => format!("{count} warnings, latest: {}", attention.latest),
+            });
+        }
+        if let Some(budget) = snapshot.budget.as_ref() {
+            parts.push(format!(
+                "{} budget {} percent used",
+                budget.scope, budget.percent_used
+            ));
+        }
+        if let Some(session) = snapshot.session.as_ref() {
+            parts.push(format!("session {session}"));
+        }
+        join_plain(parts, width)
+    }
+
+    fn render_run_pill(
+        &self,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Line<'static> {
+        let Some(state) = snapshot.run_state.as_ref() else {
+            return Line::default();
+        };
+        if let Some(summary) = state.summary.as_ref() {
+            let elapsed = snapshot.timer_display.format_elapsed(summary.elapsed_secs);
+            let label = &summary.label;
+            let mut parts = vec![if summary.interrupted {
+                format!("interrupted {label} after {elapsed}")
+            } else {
+                format!("finished {label} in {elapsed}")
+            }];
+            if summary.tokens > 0 {
+                parts.push(format!("{} tokens", summary.tokens));
+            }
+            return join_plain(parts, width);
+        }
+        let mut parts = vec![run_label(state).to_string()];
+        if let Some(elapsed) = run_elapsed(snapshot, state, now) {
+            parts.push(elapsed);
+        }
+        match state.pending_approvals {
+            ..=0 => {}
+            1 => parts.push("1 approval waiting".to_string()),
+            count => parts.push(format!("{count} approvals waiting")),
+        }
+        if let Some(failure) = state.exec_failure.as_ref() {
+            parts.push(if failure.recent {
+                format!(
+                    "{} failed with exit code {}",
+                    failure.command, failure.exit_code
+                )
+            } else {
+                "last command failed".to_string()
+            });
+        }
+        match state.queued_messages.len() {
+            0 => {}
+            1 => parts.push("1 message queued".to_string()),
+            count => parts.push(format!("{count} messages queued")),
+        }
+        join_plain(parts, width)
+    }
+}
+
+fn join_plain(parts: Vec<String>, width: u16) -> Line<'static> {
+    let parts = parts
+        .into_iter()
+        .map(|part| vec![Span::raw(part)])
+        .collect();
+    join_fitting(parts, &Span::raw(SEPARATOR), usize::from(width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::tests::sample_snapshot;
+    use pretty_assertions::assert_eq;
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn reads_as_plain_words() {
+        let snapshot = sample_snapshot();
+        let now = Instant::now();
+        let renderer = AccessibleRenderer;
+        let line = renderer.render(&snapshot, 200, now);
+        assert_eq!(
+            text(&line),
+            "dir codex, model gpt-5-codex high, context 68 percent left, \
+             branch feature/fix-tests dirty"
+        );
+        assert!(
+            line.spans
+                .iter()
+                .all(|span| span.style == ratatui::style::Style::default())
+        );
+        assert_eq!(
+            text(&renderer.render_run_pill(&snapshot, 200, now)),
+            "Applying patch, 2m 05s, 2 messages queued"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! `compact-single-capsule`: model, branch and context packed into a single
+//! rounded capsule whose color tracks context usage.
+
+use std::time::Instant;
+
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use super::git_branch_text;
+use super::run_elapsed;
+use super::run_label;
+use super::run_summary;
+use super::styled;
+use crate::status::truncate_line_to_width;
+use crate::statusline::LEFT_CURVE;
+use crate::statusline::RIGHT_CURVE;
+use crate::statusline::StatusLineRenderer;
+use crate::statusline::StatusLineSnapshot;
+use crate::statusline::context_bar_colors;
+use crate::statusline::palette::BASE;
+use crate::statusline::palette::GREEN;
+use crate::statusline::palette::LAVENDER;
+use crate::statusline::palette::RED;
+use crate::statusline::pending_approvals_text;
+
+const DIVIDER: &str = " │ ";
+
+#[derive(Debug, Default)]
+pub(crate) struct CompactCapsuleRenderer;
+
+impl StatusLineRenderer for CompactCapsuleRenderer {
+    fn render(&self, snapshot: &StatusLineSnapshot, width: u16, _now: Instant) -> Line<'static> {
+        let accent = match snapshot.context.as_ref() {
+            Some(context) if context.low_since.is_some() => RED,
+            Some(context) => context_bar_colors(f64::from(context.percent_used())).0,
+            None => LAVENDER,
+        };
+        let mut parts: Vec<String> = Vec::new();
+        parts.extend(pending_approvals_text(snapshot.pending_approvals));
+        if snapshot.queued > 0 {
+            parts.push(format!("{} queued", snapshot.queued));
+        }
+        if let Some(model) = snapshot.model.as_ref() {
+            parts.push(model.label.clone());
+        }
+        if let Some(branch) = snapshot.git.as_ref().and_then(git_branch_text) {
+            parts.push(branch);
+        }
+        if let Some(context) = snapshot.context.as_ref() {
+            parts.push(format!("{}%", context.percent_remaining));
+        }
+        capsule(parts, accent, usize::from(width))
+    }
+
+    fn render_run_pill(
+        &self,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Line<'static> {
+        let Some(state) = snapshot.run_state.as_ref() else {
+            return Line::default();
+        };
+        if let Some(summary) = run_summary(snapshot, state) {
+            return capsule(vec![summary], GREEN, usize::from(width));
+        }
+        let accent = state.accent();
+        let mut parts = vec![run_label(state).to_string()];
+        parts.extend(pending_approvals_text(state.pending_approvals));
+        parts.extend(run_elapsed(snapshot, state, now));
+        if !state.queued_messages.is_empty() {
+            parts.push(format!("{} queued", state.queued_messages.len()));
+        }
+        capsule(parts, accent, usize::from(width))
+    }
+}
+
+/// Render `parts` inside one capsule, dropping trailing parts until it fits.
+fn capsule(mut parts: Vec<String>, accent: Color, width: usize) -> Line<'static> {
+    let fill = Style::default()
+        .fg(BASE)
+        .bg(accent)
+        .add_modifier(Modifier::BOLD);
+    loop {
+        let body = format!(" {} ", parts.join(DIVIDER));
+        let line = Line::from(vec![
+            styled(LEFT_CURVE, Style::default().fg(accent)),
+            styled(body, fill),
+            styled(RIGHT_CURVE, Style::default().fg(accent)),
+        ]);
+        let line_width: usize = line.spans.iter().map(Span::width).sum();
+        if line_width <= width {
+            return line;
+        }
+        if parts.len() <= 1 {
+            return truncate_line_to_width(line, width);
+        }
+        parts.pop();
+    }
+}
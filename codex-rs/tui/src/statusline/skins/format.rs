@@ -0,0 +1,216 @@
+//! `tui.statusline.format`: the status line drawn from a format string such
+//! as `"{path} · {model}{fill}{git}"`. Placeholders render through the same
+//! segment builders as the `powerline` skin, as colored text without fills,
+//! and degrade in the same order when the line is too narrow.
+
+use std::time::Instant;
+
+use codex_core::config::types::StatusLineFormat;
+use codex_core::config::types::StatusLineFormatItem;
+use codex_core::config::types::StatusLineSegmentId;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::status::line_display_width;
+use crate::status::truncate_line_to_width;
+use crate::statusline::ContextVariant;
+use crate::statusline::DEFAULT_PLACEMENTS;
+use crate::statusline::PowerlineSegment;
+use crate::statusline::RenderModel;
+use crate::statusline::StatusLineRenderer;
+use crate::statusline::StatusLineSnapshot;
+use crate::statusline::span;
+
+/// Draws the status line from a format string; the run pill comes from
+/// `fallback`, the configured skin.
+#[derive(Debug)]
+pub(crate) struct FormatStatusLineRenderer {
+    format: StatusLineFormat,
+    fallback: Box<dyn StatusLineRenderer>,
+}
+
+impl FormatStatusLineRenderer {
+    pub(crate) fn new(format: StatusLineFormat, fallback: Box<dyn StatusLineRenderer>) -> Self {
+        Self { format, fallback }
+    }
+
+    fn render_line(&self, model: &RenderModel<'_>, width: usize) -> Line<'static> {
+        let items = self.format.items();
+        let (left, right) = match items
+            .iter()
+            .position(|item| *item == StatusLineFormatItem::Fill)
+        {
+            Some(fill) => (&items[..fill], Some(&items[fill + 1..])),
+            None => (items, None),
+        };
+        let mut spans = render_group(model, left);
+        if let Some(right) = right {
+            let right = render_group(model, right);
+            let used: usize = spans.iter().chain(&right).map(Span::width).sum();
+            if let Some(padding) = width.checked_sub(used).filter(|padding| *padding > 0) {
+                spans.push(Span::raw(" ".repeat(padding)));
+            }
+            spans.extend(right);
+        }
+        Line::from(spans)
+    }
+}
+
+impl StatusLineRenderer for FormatStatusLineRenderer {
+    fn render(&self, snapshot: &StatusLineSnapshot, width: u16, now: Instant) -> Line<'static> {
+        let width = usize::from(width);
+        let mut model = RenderModel::new(snapshot, now);
+        loop {
+            let line = self.render_line(&model, width);
+            if line_display_width(&line) <= width {
+                return line;
+            }
+            if model.apply_next_degrade().is_none() {
+                return truncate_line_to_width(line, width);
+            }
+        }
+    }
+
+    fn render_run_pill(
+        &self,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Line<'static> {
+        self.fallback.render_run_pill(snapshot, width, now)
+    }
+}
+
+/// Render the items on one side of `{fill}`. Text after a placeholder only
+/// shows when something was drawn before it and the next placeholder draws
+/// too, so separators around missing segments do not pile up.
+fn render_group(model: &RenderModel<'_>, items: &[StatusLineFormatItem]) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut seen_placeholder = false;
+    let mut drawn = false;
+    let mut pending: Option<&str> = None;
+    for item in items {
+        let placeholder = match item {
+            StatusLineFormatItem::Text(text) => {
+                if seen_placeholder {
+                    pending = Some(text);
+                } else {
+                    spans.push(Span::raw(text.clone()));
+                }
+                continue;
+            }
+            StatusLineFormatItem::Segment(id) => segment_spans(model, *id),
+            StatusLineFormatItem::Context => context_spans(model),
+            StatusLineFormatItem::Fill => Vec::new(),
+        };
+        seen_placeholder = true;
+        if placeholder.is_empty() {
+            pending = None;
+            continue;
+        }
+        if let Some(text) = pending.take()
+            && drawn
+        {
+            spans.push(Span::raw(text.to_string()));
+        }
+        spans.extend(placeholder);
+        drawn = true;
+    }
+    if let Some(text) = pending
+        && drawn
+    {
+        spans.push(Span::raw(text.to_string()));
+    }
+    spans
+}
+
+fn segment_spans(model: &RenderModel<'_>, id: StatusLineSegmentId) -> Vec<Span<'static>> {
+    DEFAULT_PLACEMENTS
+        .iter()
+        .find(|(placed, ..)| *placed == id)
+        .and_then(|(_, slot, ..)| model.segment(*slot))
+        .map(unfilled_spans)
+        .unwrap_or_default()
+}
+
+/// A powerline segment's text with its accent as the foreground instead of
+/// the fill.
+fn unfilled_spans(segment: PowerlineSegment) -> Vec<Span<'static>> {
+    let accent = segment.accent;
+    segment
+        .spans
+        .into_iter()
+        .map(|mut span| {
+            if span.style.fg.is_none() {
+                span.style = span.style.fg(accent);
+            }
+            span
+        })
+        .collect()
+}
+
+fn context_spans(model: &RenderModel<'_>) -> Vec<Span<'static>> {
+    if model.context_variant == ContextVariant::Hidden {
+        return Vec::new();
+    }
+    let Some(context) = model.snapshot.context.as_ref() else {
+        return Vec::new();
+    };
+    let (text, style) = model.context_compact_text(context);
+    vec![span(text, style)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::skins::MinimalPlainRenderer;
+    use crate::statusline::tests::sample_snapshot;
+    use pretty_assertions::assert_eq;
+
+    fn renderer(format: &str) -> FormatStatusLineRenderer {
+        let format = StatusLineFormat::try_from(format.to_string()).expect("valid format");
+        FormatStatusLineRenderer::new(format, Box::new(MinimalPlainRenderer))
+    }
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn placeholders_follow_the_format_and_fill_right_aligns() {
+        let snapshot = sample_snapshot();
+        let line = renderer("[{model}] {fill}{path}").render(&snapshot, 120, Instant::now());
+        let rendered = text(&line);
+        assert_eq!(line_display_width(&line), 120, "{rendered}");
+        assert!(rendered.starts_with("["), "{rendered}");
+        assert!(rendered.contains("gpt-5-codex"), "{rendered}");
+        let path = snapshot.cwd_display.clone().expect("sample path");
+        assert!(rendered.ends_with(&path), "{rendered}");
+    }
+
+    #[test]
+    fn separators_around_missing_segments_are_dropped() {
+        let mut snapshot = sample_snapshot();
+        snapshot.git = None;
+        snapshot.cwd_display = None;
+        snapshot.cwd_fallback = None;
+        let line =
+            renderer("{path} · {git} · {model} · {git}").render(&snapshot, 200, Instant::now());
+        let rendered = text(&line);
+        assert!(rendered.contains("gpt-5-codex"), "{rendered}");
+        assert!(!rendered.contains('·'), "{rendered}");
+    }
+
+    #[test]
+    fn narrow_widths_degrade_before_truncating() {
+        let snapshot = sample_snapshot();
+        let renderer = renderer("{path} {model} {git} {context}");
+        for width in [10u16, 30, 60] {
+            let line = renderer.render(&snapshot, width, Instant::now());
+            assert!(line_display_width(&line) <= usize::from(width));
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! `minimal-plain`: plain text separated by dim dots, with no powerline
+//! glyphs or background fills. Works on terminals without a nerd font.
+
+use std::time::Instant;
+
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use super::git_branch_text;
+use super::join_fitting;
+use super::run_elapsed;
+use super::run_label;
+use super::run_summary;
+use crate::statusline::StatusLineRenderer;
+use crate::statusline::StatusLineSnapshot;
+use crate::statusline::pending_approvals_text;
+use crate::statusline::queue_preview;
+
+#[derive(Debug, Default)]
+pub(crate) struct MinimalPlainRenderer;
+
+impl StatusLineRenderer for MinimalPlainRenderer {
+    fn render(&self, snapshot: &StatusLineSnapshot, width: u16, _now: Instant) -> Line<'static> {
+        let mut parts: Vec<Vec<Span<'static>>> = Vec::new();
+        if let Some(approvals) = pending_approvals_text(snapshot.pending_approvals) {
+            parts.push(vec![approvals.bold()]);
+        }
+        if snapshot.queued > 0 {
+            parts.push(vec![format!("{} queued", snapshot.queued).italic()]);
+        }
+        if let Some(path) = snapshot
+            .cwd_display
+            .as_ref()
+            .or(snapshot.cwd_fallback.as_ref())
+        {
+            parts.push(vec![path.clone().into()]);
+        }
+        if let Some(model) = snapshot.model.as_ref() {
+            let mut spans = vec![model.label.clone().bold()];
+            if let Some(detail) = model.detail.as_ref() {
+                spans.push(" ".into());
+                spans.push(detail.clone().italic());
+            }
+            parts.push(spans);
+        }
+        if let Some(context) = snapshot.context.as_ref() {
+            let text = format!("{}% context left", context.percent_remaining);
+            parts.push(vec![if context.low_since.is_some() {
+                text.red().bold()
+            } else {
+                text.into()
+            }]);
+        }
+        if let Some(branch) = snapshot.git.as_ref().and_then(git_branch_text) {
+            parts.push(vec![branch.into()]);
+        }
+        join_fitting(parts, &" · ".dim(), usize::from(width))
+    }
+
+    fn render_run_pill(
+        &self,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Line<'static> {
+        let Some(state) = snapshot.run_state.as_ref() else {
+            return Line::default();
+        };
+        if let Some(summary) = run_summary(snapshot, state) {
+            return join_fitting(
+                vec![vec![summary.green()]],
+                &" · ".dim(),
+                usize::from(width),
+            );
+        }
+        let mut parts: Vec<Vec<Span<'static>>> = vec![vec![run_label(state).to_string().into()]];
+        if let Some(approvals) = pending_approvals_text(state.pending_approvals) {
+            parts.push(vec![approvals.bold()]);
+        }
+        if let Some(elapsed) = run_elapsed(snapshot, state, now) {
+            parts.push(vec![elapsed.dim()]);
+        }
+        if !state.queued_messages.is_empty() {
+            let (preview, position) =
+                queue_preview(&state.queued_messages, state.queue_preview_index);
+            let mut spans = vec!["next: ".dim(), preview.italic()];
+            if let Some(position) = position {
+                spans.push(format!(" ({position})").dim());
+            }
+            parts.push(spans);
+        }
+        join_fitting(parts, &" · ".dim(), usize::from(width))
+    }
+}
@@ -1,12 +1,80 @@
 use std::time::Instant;
 
+use ratatui::style::Style;
 use ratatui::text::Line;
+use ratatui::text::Span;
 
+use super::DEFAULT_STATUS_MESSAGE;
+use super::DegradeMemos;
+use super::PAUSED_STATUS_MESSAGE;
+use super::StatusLineGitSnapshot;
 use super::StatusLineRenderer;
+use super::StatusLineRunState;
 use super::StatusLineSnapshot;
 use super::render_status_line;
+use super::render_status_line_banded;
 use super::render_status_run_pill;
+use super::run_summary_text;
+use crate::status::line_display_width;
+use crate::status::truncate_line_to_width;
 
+mod accessible;
+mod capsule;
+mod format;
+mod minimal;
+mod registry;
+mod script;
+mod verbose;
+
+pub(crate) use accessible::AccessibleRenderer;
+pub(crate) use capsule::CompactCapsuleRenderer;
+pub(crate) use format::FormatStatusLineRenderer;
+pub(crate) use minimal::MinimalPlainRenderer;
+pub use registry::SkinSnapshot;
+pub use registry::StatusLineSkin;
+pub use registry::register_skin;
+pub(crate) use registry::registered_skins;
+pub use registry::skin_names;
+pub(crate) use script::ScriptedStatusLineRenderer;
+pub(crate) use script::resolve_script_path;
+pub(crate) use verbose::VerboseTwoLineRenderer;
+
+/// Name of the skin used when the configured one is unknown.
+pub(crate) const DEFAULT_SKIN: &str = "powerline";
+
+/// Built-in skins in presentation order, paired with a short description.
+pub(crate) const SKINS: &[(&str, &str)] = &[
+    (
+        "powerline",
+        "Catppuccin powerline segments with a context bar",
+    ),
+    (
+        "minimal-plain",
+        "Plain text without glyphs or background colors",
+    ),
+    (
+        "compact-single-capsule",
+        "Model, branch and context packed into one capsule",
+    ),
+    (
+        "verbose-two-line",
+        "Every detail, spread over the run pill and status rows",
+    ),
+];
+
+/// Look up a skin by name, built-in ones first, then those added with
+/// [`register_skin`]. Returns `None` for unknown names.
+pub(crate) fn for_name(name: &str) -> Option<Box<dyn StatusLineRenderer>> {
+    match name {
+        "powerline" => Some(Box::new(CustomStatusLineRenderer)),
+        "minimal-plain" => Some(Box::new(MinimalPlainRenderer)),
+        "compact-single-capsule" => Some(Box::new(CompactCapsuleRenderer)),
+        "verbose-two-line" => Some(Box::new(VerboseTwoLineRenderer)),
+        _ => registry::registered(name),
+    }
+}
+
+/// The `powerline` skin: the default status line layout.
 #[derive(Debug, Default)]
 pub(crate) struct CustomStatusLineRenderer;
 
@@ -15,6 +83,16 @@ impl StatusLineRenderer for CustomStatusLineRenderer {
         render_status_line(snapshot, width, now)
     }
 
+    fn render_banded(
+        &self,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+        memos: &mut DegradeMemos,
+    ) -> Line<'static> {
+        render_status_line_banded(snapshot, width, now, memos)
+    }
+
     fn render_run_pill(
         &self,
         snapshot: &StatusLineSnapshot,
@@ -24,3 +102,135 @@ impl StatusLineRenderer for CustomStatusLineRenderer {
         render_status_run_pill(snapshot, width, now)
     }
 }
+
+/// Join `parts` with `separator`, keeping as many leading parts as fit in
+/// `width`. A first part that is too wide on its own is truncated.
+fn join_fitting(
+    parts: Vec<Vec<Span<'static>>>,
+    separator: &Span<'static>,
+    width: usize,
+) -> Line<'static> {
+    let separator_width = separator.width();
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut used = 0usize;
+    for part in parts {
+        let part_width: usize = part.iter().map(Span::width).sum();
+        if spans.is_empty() {
+            spans.extend(part);
+            used = part_width;
+            continue;
+        }
+        if used + separator_width + part_width > width {
+            break;
+        }
+        spans.push(separator.clone());
+        spans.extend(part);
+        used += separator_width + part_width;
+    }
+    let line = Line::from(spans);
+    if line_display_width(&line) > width {
+        truncate_line_to_width(line, width)
+    } else {
+        line
+    }
+}
+
+fn run_label(state: &StatusLineRunState) -> &str {
+    if state.paused {
+        return PAUSED_STATUS_MESSAGE;
+    }
+    match state.label.trim() {
+        "" => DEFAULT_STATUS_MESSAGE,
+        label => label,
+    }
+}
+
+/// Run timer text, or `None` until it reaches `tui.statusline.timer_min_secs`.
+fn run_elapsed(
+    snapshot: &StatusLineSnapshot,
+    state: &StatusLineRunState,
+    now: Instant,
+) -> Option<String> {
+    let elapsed = state
+        .timer
+        .as_ref()
+        .map(|timer| timer.elapsed_at(now))
+        .unwrap_or_default();
+    snapshot.timer_display.run_timer_text(elapsed)
+}
+
+/// Recap of the task that just finished, while the pill shows one.
+fn run_summary(snapshot: &StatusLineSnapshot, state: &StatusLineRunState) -> Option<String> {
+    state
+        .summary
+        .as_ref()
+        .map(|summary| run_summary_text(summary, snapshot))
+}
+
+fn is_running(state: &StatusLineRunState) -> bool {
+    state.timer.as_ref().is_some_and(|timer| !timer.is_paused)
+}
+
+fn git_branch_text(git: &StatusLineGitSnapshot) -> Option<String> {
+    let label = git.head_label()?;
+    Some(if git.dirty {
+        format!("{label}*")
+    } else {
+        label
+    })
+}
+
+fn styled<S: Into<String>>(text: S, style: Style) -> Span<'static> {
+    Span::styled(text.into(), style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::tests::sample_snapshot;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn for_name_knows_every_listed_skin() {
+        for (name, _) in SKINS {
+            assert!(for_name(name).is_some(), "missing skin {name}");
+        }
+        assert!(for_name(DEFAULT_SKIN).is_some());
+        assert!(for_name("no-such-skin").is_none());
+    }
+
+    #[test]
+    fn every_skin_fits_requested_width() {
+        let snapshot = sample_snapshot();
+        let now = Instant::now();
+        for (name, _) in SKINS {
+            let Some(renderer) = for_name(name) else {
+                panic!("missing skin {name}");
+            };
+            for width in [20u16, 40, 80, 200] {
+                let line = renderer.render(&snapshot, width, now);
+                assert!(
+                    line_display_width(&line) <= usize::from(width),
+                    "{name} status line overflows {width} columns"
+                );
+                let pill = renderer.render_run_pill(&snapshot, width, now);
+                assert!(
+                    line_display_width(&pill) <= usize::from(width),
+                    "{name} run pill overflows {width} columns"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn join_fitting_drops_trailing_parts() {
+        let parts = vec![
+            vec![Span::raw("alpha")],
+            vec![Span::raw("beta")],
+            vec![Span::raw("gamma")],
+        ];
+        let line = join_fitting(parts, &Span::raw(" · "), 12);
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "alpha · beta");
+    }
+}
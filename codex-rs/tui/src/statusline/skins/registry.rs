@@ -0,0 +1,305 @@
+//! Skins contributed by applications that embed the TUI. A crate calls
+//! [`register_skin`] before starting Codex; `tui.statusline.skin`, the
+//! `/statusline` picker and `codex statusline preview` then find the skin by
+//! name next to the built-in ones. [`SkinRegistry`] holds the skins, so a
+//! test can fill its own without touching the process-wide one.
+//!
+//! Registered skins see the snapshot the way scripts do, as JSON, so the
+//! internal snapshot types can keep changing without breaking them.
+
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::RwLock;
+use std::time::Instant;
+
+use ratatui::text::Line;
+use serde_json::Value;
+
+use super::SKINS;
+use super::is_running;
+use crate::status::line_display_width;
+use crate::status::truncate_line_to_width;
+use crate::statusline::StatusLineRenderer;
+use crate::statusline::StatusLineSnapshot;
+use crate::statusline::render_status_line;
+use crate::statusline::render_status_run_pill;
+
+type SkinFactory = Arc<dyn Fn() -> Box<dyn StatusLineSkin> + Send + Sync>;
+
+static REGISTERED_SKINS: LazyLock<RwLock<SkinRegistry>> =
+    LazyLock::new(|| RwLock::new(SkinRegistry::default()));
+
+/// A status line skin provided by an embedding application.
+pub trait StatusLineSkin: Send + Sync {
+    /// The status line row, at most `width` columns; wider lines are cut.
+    fn render(&self, snapshot: &SkinSnapshot<'_>, width: u16) -> Line<'static>;
+
+    /// The run pill row. Defaults to the `powerline` run pill.
+    fn render_run_pill(&self, snapshot: &SkinSnapshot<'_>, width: u16) -> Line<'static> {
+        snapshot.powerline_run_pill(width)
+    }
+
+    /// Shown next to the name in the `/statusline` picker.
+    fn description(&self) -> &str {
+        "Registered by the embedding application"
+    }
+}
+
+/// What a registered skin draws: the session state at one frame.
+pub struct SkinSnapshot<'a> {
+    snapshot: &'a StatusLineSnapshot,
+    now: Instant,
+}
+
+impl<'a> SkinSnapshot<'a> {
+    pub(crate) fn new(snapshot: &'a StatusLineSnapshot, now: Instant) -> Self {
+        Self { snapshot, now }
+    }
+
+    /// The snapshot as status line scripts receive it, with
+    /// `run_state.elapsed_secs` and `run_state.running` filled in for this
+    /// frame.
+    pub fn to_json(&self) -> Value {
+        let mut value = serde_json::to_value(self.snapshot).unwrap_or_default();
+        if let Some(state) = self.snapshot.run_state.as_ref()
+            && let Some(Value::Object(run_state)) = value.get_mut("run_state")
+        {
+            let elapsed = state
+                .timer
+                .as_ref()
+                .map(|timer| timer.elapsed_at(self.now))
+                .unwrap_or_default();
+            run_state.insert("elapsed_secs".to_string(), elapsed.as_secs().into());
+            run_state.insert("running".to_string(), is_running(state).into());
+        }
+        value
+    }
+
+    /// The `powerline` status line, for skins that decorate or wrap it.
+    pub fn powerline(&self, width: u16) -> Line<'static> {
+        render_status_line(self.snapshot, width, self.now)
+    }
+
+    /// The `powerline` run pill.
+    pub fn powerline_run_pill(&self, width: u16) -> Line<'static> {
+        render_status_run_pill(self.snapshot, width, self.now)
+    }
+}
+
+/// Skins registered by name, in registration order.
+#[derive(Clone, Default)]
+pub(crate) struct SkinRegistry {
+    skins: Vec<(String, SkinFactory)>,
+}
+
+impl SkinRegistry {
+    /// Make `factory`'s skin available as `name`. Registering a name again
+    /// replaces the earlier skin; built-in names cannot be taken.
+    pub(crate) fn register<F>(&mut self, name: impl Into<String>, factory: F) -> anyhow::Result<()>
+    where
+        F: Fn() -> Box<dyn StatusLineSkin> + Send + Sync + 'static,
+    {
+        let name = name.into();
+        if name.trim().is_empty() {
+            anyhow::bail!("status line skin names cannot be empty");
+        }
+        if SKINS.iter().any(|(builtin, _)| *builtin == name) {
+            anyhow::bail!("`{name}` is a built-in status line skin");
+        }
+        let factory: SkinFactory = Arc::new(factory);
+        match self
+            .skins
+            .iter_mut()
+            .find(|(registered, _)| *registered == name)
+        {
+            Some(entry) => entry.1 = factory,
+            None => self.skins.push((name, factory)),
+        }
+        Ok(())
+    }
+
+    /// The skin called `name`, ready to render.
+    pub(crate) fn renderer(&self, name: &str) -> Option<Box<dyn StatusLineRenderer>> {
+        let (name, factory) = self
+            .skins
+            .iter()
+            .find(|(registered, _)| registered == name)?;
+        Some(Box::new(RegisteredSkinRenderer {
+            name: name.clone(),
+            skin: factory(),
+        }))
+    }
+
+    /// Skin names paired with their description.
+    pub(crate) fn descriptions(&self) -> Vec<(String, String)> {
+        self.skins
+            .iter()
+            .map(|(name, factory)| (name.clone(), factory().description().to_string()))
+            .collect()
+    }
+
+    /// Built-in skin names, then the registered ones.
+    pub(crate) fn skin_names(&self) -> Vec<String> {
+        SKINS
+            .iter()
+            .map(|(name, _)| (*name).to_string())
+            .chain(self.skins.iter().map(|(name, _)| name.clone()))
+            .collect()
+    }
+}
+
+/// The process-wide registry; factories are shared, so the copy is cheap.
+fn global() -> SkinRegistry {
+    REGISTERED_SKINS
+        .read()
+        .map(|registry| registry.clone())
+        .unwrap_or_default()
+}
+
+/// Make `factory`'s skin available as `name` for the whole process. See
+/// [`SkinRegistry::register`].
+pub fn register_skin<F>(name: impl Into<String>, factory: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Box<dyn StatusLineSkin> + Send + Sync + 'static,
+{
+    REGISTERED_SKINS
+        .write()
+        .map_err(|_| anyhow::anyhow!("status line skin registry is poisoned"))?
+        .register(name, factory)
+}
+
+/// The registered skin called `name`, ready to render.
+pub(crate) fn registered(name: &str) -> Option<Box<dyn StatusLineRenderer>> {
+    global().renderer(name)
+}
+
+/// Registered skins in registration order, paired with their description.
+pub(crate) fn registered_skins() -> Vec<(String, String)> {
+    global().descriptions()
+}
+
+/// Every skin name `for_name` accepts: built-in ones, then registered ones.
+pub fn skin_names() -> Vec<String> {
+    global().skin_names()
+}
+
+/// Adapts a [`StatusLineSkin`] to the renderer the status line drives.
+struct RegisteredSkinRenderer {
+    name: String,
+    skin: Box<dyn StatusLineSkin>,
+}
+
+impl std::fmt::Debug for RegisteredSkinRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegisteredSkinRenderer")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StatusLineRenderer for RegisteredSkinRenderer {
+    fn render(&self, snapshot: &StatusLineSnapshot, width: u16, now: Instant) -> Line<'static> {
+        let line = self.skin.render(&SkinSnapshot { snapshot, now }, width);
+        fit(line, width)
+    }
+
+    fn render_run_pill(
+        &self,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Line<'static> {
+        let line = self
+            .skin
+            .render_run_pill(&SkinSnapshot { snapshot, now }, width);
+        fit(line, width)
+    }
+}
+
+fn fit(line: Line<'static>, width: u16) -> Line<'static> {
+    let width = usize::from(width);
+    if line_display_width(&line) > width {
+        truncate_line_to_width(line, width)
+    } else {
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::tests::sample_snapshot;
+    use pretty_assertions::assert_eq;
+
+    struct ModelOnly;
+
+    impl StatusLineSkin for ModelOnly {
+        fn render(&self, snapshot: &SkinSnapshot<'_>, _width: u16) -> Line<'static> {
+            let model = snapshot.to_json()["model"]["label"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            Line::from(format!("model: {model}"))
+        }
+
+        fn description(&self) -> &str {
+            "Just the model"
+        }
+    }
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn registered_skins_are_found_by_name() {
+        let mut registry = SkinRegistry::default();
+        assert!(registry.renderer("model-only").is_none());
+        registry
+            .register("model-only", || Box::new(ModelOnly))
+            .expect("register");
+        assert_eq!(
+            registry.skin_names(),
+            vec![
+                "powerline",
+                "minimal-plain",
+                "compact-single-capsule",
+                "verbose-two-line",
+                "model-only",
+            ]
+        );
+        assert_eq!(
+            registry.descriptions(),
+            vec![("model-only".to_string(), "Just the model".to_string())]
+        );
+
+        let snapshot = sample_snapshot();
+        let now = Instant::now();
+        let renderer = registry.renderer("model-only").expect("registered skin");
+        assert_eq!(
+            text(&renderer.render(&snapshot, 200, now)),
+            "model: gpt-5-codex"
+        );
+        assert_eq!(text(&renderer.render(&snapshot, 8, now)), "model: g");
+        assert_eq!(
+            renderer.render_run_pill(&snapshot, 80, now),
+            render_status_run_pill(&snapshot, 80, now)
+        );
+    }
+
+    #[test]
+    fn built_in_and_empty_names_cannot_be_registered() {
+        let mut registry = SkinRegistry::default();
+        assert!(
+            registry
+                .register("powerline", || Box::new(ModelOnly))
+                .is_err()
+        );
+        assert!(registry.register("  ", || Box::new(ModelOnly)).is_err());
+        assert!(registry.renderer("  ").is_none());
+        assert_eq!(registry.descriptions(), Vec::new());
+    }
+}
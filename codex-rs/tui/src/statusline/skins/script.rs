@@ -0,0 +1,419 @@
+//! User-scriptable status line backed by a sandboxed Rhai engine.
+//!
+//! The script defines `render(snapshot, width)` and optionally
+//! `render_run_pill(snapshot, width)`. Both receive the serialized
+//! [`StatusLineSnapshot`] as a map and return an array of spans, where each
+//! span is either a string or a map such as
+//! `#{ text: "main", fg: "#a6e3a1", bg: "black", bold: true }`.
+//!
+//! Every call runs under a wall-clock budget. The first error or timeout
+//! disables the script for the rest of the session and the fallback renderer
+//! takes over.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use rhai::AST;
+use rhai::Array;
+use rhai::Dynamic;
+use rhai::Engine;
+use rhai::Map;
+use rhai::Scope;
+use rhai::module_resolvers::DummyModuleResolver;
+
+use super::is_running;
+use crate::status::line_display_width;
+use crate::status::truncate_line_to_width;
+use crate::statusline::StatusLineRenderer;
+use crate::statusline::StatusLineSnapshot;
+
+const RENDER_FN: &str = "render";
+const RENDER_RUN_PILL_FN: &str = "render_run_pill";
+/// How many script operations run between wall-clock budget checks.
+const BUDGET_CHECK_INTERVAL: u64 = 256;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_STRING_SIZE: usize = 4 * 1024;
+const MAX_COLLECTION_SIZE: usize = 1024;
+
+pub(crate) struct ScriptedStatusLineRenderer {
+    source: String,
+    engine: Engine,
+    ast: AST,
+    has_run_pill: bool,
+    deadline: Arc<Mutex<Option<Instant>>>,
+    budget: Duration,
+    failed: AtomicBool,
+    fallback: Box<dyn StatusLineRenderer>,
+}
+
+impl std::fmt::Debug for ScriptedStatusLineRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScriptedStatusLineRenderer")
+            .field("source", &self.source)
+            .field("budget", &self.budget)
+            .field("failed", &self.failed.load(Ordering::Relaxed))
+            .field("fallback", &self.fallback)
+            .finish()
+    }
+}
+
+impl ScriptedStatusLineRenderer {
+    /// Compile the script at `path`. Errors cover unreadable files, syntax
+    /// errors and scripts without a `render` function.
+    pub(crate) fn load(
+        path: &Path,
+        budget: Duration,
+        fallback: Box<dyn StatusLineRenderer>,
+    ) -> anyhow::Result<Self> {
+        let script = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read status line script {}", path.display()))?;
+        Self::from_script(path.display().to_string(), &script, budget, fallback)
+    }
+
+    fn from_script(
+        source: String,
+        script: &str,
+        budget: Duration,
+        fallback: Box<dyn StatusLineRenderer>,
+    ) -> anyhow::Result<Self> {
+        let deadline: Arc<Mutex<Option<Instant>>> = Arc::new(Mutex::new(None));
+        let engine = sandboxed_engine(Arc::clone(&deadline));
+        let ast = engine
+            .compile(script)
+            .map_err(|err| anyhow::anyhow!("failed to compile {source}: {err}"))?;
+        if !ast.iter_functions().any(|f| f.name == RENDER_FN) {
+            anyhow::bail!("{source} does not define `fn {RENDER_FN}(snapshot, width)`");
+        }
+        let has_run_pill = ast.iter_functions().any(|f| f.name == RENDER_RUN_PILL_FN);
+        Ok(Self {
+            source,
+            engine,
+            ast,
+            has_run_pill,
+            deadline,
+            budget,
+            failed: AtomicBool::new(false),
+            fallback,
+        })
+    }
+
+    fn call(
+        &self,
+        fn_name: &str,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Option<Line<'static>> {
+        if self.failed.load(Ordering::Relaxed) {
+            return None;
+        }
+        match self.try_call(fn_name, snapshot, width, now) {
+            Ok(line) => Some(line),
+            Err(err) => {
+                if !self.failed.swap(true, Ordering::Relaxed) {
+                    tracing::warn!(
+                        "status line script {} failed, using the configured skin: {err}",
+                        self.source
+                    );
+                }
+                None
+            }
+        }
+    }
+
+    fn try_call(
+        &self,
+        fn_name: &str,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> anyhow::Result<Line<'static>> {
+        let input = snapshot_to_dynamic(snapshot, now)?;
+        self.set_deadline(Some(Instant::now() + self.budget));
+        let result = self.engine.call_fn::<Dynamic>(
+            &mut Scope::new(),
+            &self.ast,
+            fn_name,
+            (input, i64::from(width)),
+        );
+        self.set_deadline(None);
+        let result = result.map_err(|err| anyhow::anyhow!("`{fn_name}` failed: {err}"))?;
+        let line = spans_from_dynamic(result)?;
+        let width = usize::from(width);
+        Ok(if line_display_width(&line) > width {
+            truncate_line_to_width(line, width)
+        } else {
+            line
+        })
+    }
+
+    fn set_deadline(&self, deadline: Option<Instant>) {
+        if let Ok(mut guard) = self.deadline.lock() {
+            *guard = deadline;
+        }
+    }
+}
+
+impl StatusLineRenderer for ScriptedStatusLineRenderer {
+    fn render(&self, snapshot: &StatusLineSnapshot, width: u16, now: Instant) -> Line<'static> {
+        self.call(RENDER_FN, snapshot, width, now)
+            .unwrap_or_else(|| self.fallback.render(snapshot, width, now))
+    }
+
+    fn render_run_pill(
+        &self,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Line<'static> {
+        if !self.has_run_pill {
+            return self.fallback.render_run_pill(snapshot, width, now);
+        }
+        self.call(RENDER_RUN_PILL_FN, snapshot, width, now)
+            .unwrap_or_else(|| self.fallback.render_run_pill(snapshot, width, now))
+    }
+}
+
+/// Resolve a configured script path against `codex_home`.
+pub(crate) fn resolve_script_path(codex_home: &Path, script: &Path) -> PathBuf {
+    if script.is_absolute() {
+        script.to_path_buf()
+    } else {
+        codex_home.join(script)
+    }
+}
+
+/// An engine that cannot import modules, print to the terminal, or run past
+/// the deadline stored in `deadline`.
+fn sandboxed_engine(deadline: Arc<Mutex<Option<Instant>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_array_size(MAX_COLLECTION_SIZE);
+    engine.set_max_map_size(MAX_COLLECTION_SIZE);
+    engine.on_print(|text| tracing::debug!("status line script: {text}"));
+    engine.on_debug(|text, _, _| tracing::debug!("status line script: {text}"));
+    engine.on_progress(move |operations| {
+        if !operations.is_multiple_of(BUDGET_CHECK_INTERVAL) {
+            return None;
+        }
+        let expired = deadline
+            .lock()
+            .ok()
+            .and_then(|guard| *guard)
+            .is_some_and(|deadline| Instant::now() >= deadline);
+        expired.then_some(Dynamic::UNIT)
+    });
+    engine
+}
+
+/// Serialize `snapshot` and add the values that depend on `now`, which the
+/// snapshot itself only stores as instants.
+fn snapshot_to_dynamic(snapshot: &StatusLineSnapshot, now: Instant) -> anyhow::Result<Dynamic> {
+    let mut map = rhai::serde::to_dynamic(snapshot)
+        .map_err(|err| anyhow::anyhow!("failed to serialize snapshot: {err}"))?
+        .try_cast::<Map>()
+        .context("snapshot did not serialize to a map")?;
+    if let Some(state) = snapshot.run_state.as_ref()
+        && let Some(run_state) = map.get_mut("run_state")
+        && let Some(mut run_map) = run_state.write_lock::<Map>()
+    {
+        let elapsed = state
+            .timer
+            .as_ref()
+            .map(|timer| timer.elapsed_at(now))
+            .unwrap_or_default();
+        run_map.insert("elapsed_secs".into(), elapsed_secs(elapsed));
+        run_map.insert("running".into(), Dynamic::from(is_running(state)));
+    }
+    if let Some(tokens) = snapshot.tokens.as_ref()
+        && let Some(duration) = tokens.last_turn_duration
+        && let Some(tokens_value) = map.get_mut("tokens")
+        && let Some(mut tokens_map) = tokens_value.write_lock::<Map>()
+    {
+        tokens_map.insert("last_turn_secs".into(), elapsed_secs(duration));
+    }
+    if let Some(context) = snapshot.context.as_ref()
+        && let Some(context_value) = map.get_mut("context")
+        && let Some(mut context_map) = context_value.write_lock::<Map>()
+    {
+        context_map.insert("low".into(), Dynamic::from(context.low_since.is_some()));
+    }
+    Ok(Dynamic::from_map(map))
+}
+
+fn elapsed_secs(duration: Duration) -> Dynamic {
+    Dynamic::from(i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+}
+
+fn spans_from_dynamic(value: Dynamic) -> anyhow::Result<Line<'static>> {
+    if value.is_string() {
+        return Ok(Line::from(span_from_dynamic(value)?));
+    }
+    let items = value
+        .try_cast::<Array>()
+        .context("script must return a string or an array of spans")?;
+    let spans = items
+        .into_iter()
+        .map(span_from_dynamic)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(Line::from(spans))
+}
+
+fn span_from_dynamic(value: Dynamic) -> anyhow::Result<Span<'static>> {
+    if value.is_string() {
+        let text = value
+            .into_string()
+            .map_err(|ty| anyhow::anyhow!("expected a string, got {ty}"))?;
+        return Ok(Span::raw(text));
+    }
+    let map = value
+        .try_cast::<Map>()
+        .context("each span must be a string or a map with a `text` field")?;
+    let text = map
+        .get("text")
+        .context("span map is missing `text`")?
+        .to_string();
+    let mut style = Style::default();
+    if let Some(fg) = map.get("fg") {
+        style = style.fg(parse_color(fg)?);
+    }
+    if let Some(bg) = map.get("bg") {
+        style = style.bg(parse_color(bg)?);
+    }
+    for (key, modifier) in [
+        ("bold", Modifier::BOLD),
+        ("dim", Modifier::DIM),
+        ("italic", Modifier::ITALIC),
+        ("underline", Modifier::UNDERLINED),
+    ] {
+        if map
+            .get(key)
+            .and_then(|flag| flag.as_bool().ok())
+            .unwrap_or(false)
+        {
+            style = style.add_modifier(modifier);
+        }
+    }
+    Ok(Span::styled(text, style))
+}
+
+/// Accepts ratatui color names (`"red"`, `"lightblue"`), `"#rrggbb"` and
+/// ANSI indices.
+fn parse_color(value: &Dynamic) -> anyhow::Result<Color> {
+    let text = value.to_string();
+    Color::from_str(&text).map_err(|_| anyhow::anyhow!("unknown color `{text}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::palette::GREEN;
+    use crate::statusline::skins::MinimalPlainRenderer;
+    use crate::statusline::tests::sample_snapshot;
+    use pretty_assertions::assert_eq;
+
+    fn renderer(script: &str, budget: Duration) -> ScriptedStatusLineRenderer {
+        ScriptedStatusLineRenderer::from_script(
+            "test.rhai".to_string(),
+            script,
+            budget,
+            Box::new(MinimalPlainRenderer),
+        )
+        .expect("script should compile")
+    }
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn script_renders_styled_spans_from_snapshot() {
+        let renderer = renderer(
+            r##"
+            fn render(snapshot, width) {
+                [
+                    #{ text: snapshot.model.label, fg: "#a6e3a1", bold: true },
+                    " ",
+                    `${snapshot.context.percent_remaining}% w=${width}`,
+                ]
+            }
+            "##,
+            Duration::from_secs(1),
+        );
+        let snapshot = sample_snapshot();
+        let line = renderer.render(&snapshot, 80, Instant::now());
+
+        let model = snapshot.model.as_ref().expect("sample has a model");
+        let context = snapshot.context.as_ref().expect("sample has context");
+        assert_eq!(
+            text(&line),
+            format!("{} {}% w=80", model.label, context.percent_remaining)
+        );
+        assert_eq!(line.spans[0].style.fg, Some(GREEN));
+        assert!(line.spans[0].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn script_over_budget_falls_back_to_configured_skin() {
+        let renderer = renderer(
+            "fn render(snapshot, width) { loop {} }",
+            Duration::from_millis(5),
+        );
+        let snapshot = sample_snapshot();
+        let now = Instant::now();
+
+        let line = renderer.render(&snapshot, 80, now);
+
+        assert_eq!(
+            text(&line),
+            text(&MinimalPlainRenderer.render(&snapshot, 80, now))
+        );
+        assert!(renderer.failed.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn invalid_span_falls_back_to_configured_skin() {
+        let renderer = renderer(
+            r#"fn render(snapshot, width) { [#{ text: "x", fg: "not-a-color" }] }"#,
+            Duration::from_secs(1),
+        );
+        let snapshot = sample_snapshot();
+        let now = Instant::now();
+
+        let line = renderer.render(&snapshot, 80, now);
+
+        assert_eq!(
+            text(&line),
+            text(&MinimalPlainRenderer.render(&snapshot, 80, now))
+        );
+    }
+
+    #[test]
+    fn script_without_render_is_rejected() {
+        let result = ScriptedStatusLineRenderer::from_script(
+            "test.rhai".to_string(),
+            "fn other() { 1 }",
+            Duration::from_secs(1),
+            Box::new(MinimalPlainRenderer),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,267 @@
+//! `verbose-two-line`: labelled fields for everything the snapshot carries.
+//! The run pill row holds the task, location and model; the status row holds
+//! usage, git and environment details.
+
+use std::str::FromStr;
+use std::time::Instant;
+
+use ratatui::style::Color;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use super::git_branch_text;
+use super::join_fitting;
+use super::run_elapsed;
+use super::run_label;
+use super::run_summary;
+use super::styled;
+use crate::statusline::StatusLineRenderer;
+use crate::statusline::StatusLineSnapshot;
+use crate::statusline::context_bar_colors;
+use crate::statusline::dev_env_text;
+use crate::statusline::diff_stats_text;
+use crate::statusline::exec_failure_text;
+use crate::statusline::git_base_text;
+use crate::statusline::palette::GREEN;
+use crate::statusline::palette::LAVENDER;
+use crate::statusline::palette::MAUVE;
+use crate::statusline::palette::PEACH;
+use crate::statusline::palette::RED;
+use crate::statusline::palette::ROSEWATER;
+use crate::statusline::palette::SKY;
+use crate::statusline::palette::SUBTEXT0;
+use crate::statusline::palette::TEAL;
+use crate::statusline::palette::YELLOW;
+use crate::statusline::palette::attention_color;
+use crate::statusline::palette::policy_color;
+use crate::statusline::palette::pull_request_color;
+use crate::statusline::pending_approvals_text;
+use crate::statusline::process::process_text;
+use crate::statusline::queue_preview;
+use crate::statusline::run_spinner;
+
+#[derive(Debug, Default)]
+pub(crate) struct VerboseTwoLineRenderer;
+
+impl StatusLineRenderer for VerboseTwoLineRenderer {
+    fn render(&self, snapshot: &StatusLineSnapshot, width: u16, _now: Instant) -> Line<'static> {
+        let mut parts: Vec<Vec<Span<'static>>> = Vec::new();
+        if let Some(tokens) = snapshot.tokens.as_ref() {
+            let total = &tokens.total;
+            let mut text = format!(
+                "Σ{} ↑{}",
+                snapshot.token_format.format(total.blended_total()),
+                snapshot.token_format.format(total.input_without_cache())
+            );
+            if total.cached_input_tokens > 0 {
+                text.push_str(&format!(
+                    " ↺{}",
+                    snapshot.token_format.format(total.cached_input_tokens)
+                ));
+            }
+            text.push_str(&format!(
+                " ↓{}",
+                snapshot.token_format.format(total.output_tokens)
+            ));
+            parts.push(field("tokens", text, SKY));
+        }
+        if let Some(context) = snapshot.context.as_ref() {
+            let color = if context.low_since.is_some() {
+                RED
+            } else {
+                GREEN
+            };
+            parts.push(field(
+                "context",
+                format!(
+                    "{}/{} ({}% left)",
+                    snapshot.token_format.format(context.tokens_in_context),
+                    snapshot.token_format.format(context.window),
+                    context.percent_remaining
+                ),
+                color,
+            ));
+        }
+        if let Some(git) = snapshot.git.as_ref()
+            && let Some(mut text) = git_branch_text(git)
+        {
+            if let Some(ahead) = git.ahead.filter(|ahead| *ahead > 0) {
+                text.push_str(&format!(" ↑{ahead}"));
+            }
+            if let Some(behind) = git.behind.filter(|behind| *behind > 0) {
+                text.push_str(&format!(" ↓{behind}"));
+            }
+            if let Some(base) = git_base_text(git) {
+                text.push_str(&format!(" {base}"));
+            }
+            parts.push(field("git", text, PEACH));
+            if let Some(pull_request) = git.pull_request.as_ref() {
+                parts.push(field(
+                    "pr",
+                    format!("#{}", pull_request.number),
+                    pull_request_color(pull_request.checks),
+                ));
+            }
+        }
+        if let Some(stats) = snapshot.diff_stats.as_ref() {
+            parts.push(field("diff", diff_stats_text(stats), PEACH));
+        }
+        if let Some(text) = snapshot.custom.as_ref() {
+            parts.push(field("custom", text.clone(), ROSEWATER));
+        }
+        if let Some(attention) = snapshot.attention.as_ref() {
+            parts.push(field(
+                "attention",
+                format!("{} · {}", attention.count, attention.latest),
+                attention_color(attention.severity),
+            ));
+        }
+        if let Some(budget) = snapshot.budget.as_ref() {
+            let (color, _) = context_bar_colors(budget.percent_used as f64);
+            parts.push(field(
+                "budget",
+                format!("{} · {}%", budget.spend, budget.percent_used),
+                color,
+            ));
+        }
+        if let Some(provider) = snapshot.provider.as_ref() {
+            let text = match provider.profile.as_ref() {
+                Some(profile) => format!("{} ({profile})", provider.name),
+                None => provider.name.clone(),
+            };
+            parts.push(field("provider", text, TEAL));
+        }
+        if let Some(policy) = snapshot.policy.as_ref() {
+            let text = format!("{} / {}", policy.sandbox, policy.approval);
+            parts.push(field("policy", text, policy_color(policy.risk)));
+        }
+        let env = &snapshot.environment;
+        if let Some(text) = env.dev_env.as_ref().and_then(dev_env_text) {
+            parts.push(field("env", text, GREEN));
+        }
+        if let Some(devspace) = env.devspace.as_ref() {
+            let text = match devspace.description.as_ref() {
+                Some(description) => format!("{} ({description})", devspace.name),
+                None => devspace.name.clone(),
+            };
+            parts.push(field("devspace", text, devspace.accent()));
+        }
+        if let Some(hostname) = env.hostname.as_ref() {
+            let text = if env.ssh {
+                format!("{hostname} (ssh)")
+            } else {
+                hostname.clone()
+            };
+            let color = env
+                .host_color
+                .as_deref()
+                .and_then(|color| Color::from_str(color).ok())
+                .unwrap_or(TEAL);
+            parts.push(field("host", text, color));
+        }
+        if let Some(profile) = env.aws_profile.as_ref() {
+            parts.push(field("aws", profile.clone(), YELLOW));
+        }
+        if let Some(iac) = env.iac.as_ref() {
+            let color = if iac.is_production() { RED } else { MAUVE };
+            parts.push(field(iac.tool.name(), iac.workspace.clone(), color));
+        }
+        if let Some(context) = env.kubernetes_context.as_ref() {
+            parts.push(field("k8s", context.clone(), SKY));
+        }
+        if let Some(process) = snapshot.process.as_ref() {
+            parts.push(field("proc", process_text(process), LAVENDER));
+        }
+        if let Some(clock) = snapshot.clock.as_ref() {
+            parts.push(field("clock", clock.clone(), SKY));
+        }
+        if let Some(session) = snapshot.session.as_ref() {
+            parts.push(field("session", session.clone(), SUBTEXT0));
+        }
+        if let Some(build) = snapshot.build.as_ref() {
+            parts.push(field("build", build.clone(), SUBTEXT0));
+        }
+        if let Some(update) = snapshot.update.as_ref() {
+            parts.push(field("update", update.clone(), SUBTEXT0));
+        }
+        join_fitting(parts, &"  ".into(), usize::from(width))
+    }
+
+    fn render_run_pill(
+        &self,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Line<'static> {
+        let mut parts: Vec<Vec<Span<'static>>> = Vec::new();
+        if let Some(state) = snapshot.run_state.as_ref() {
+            if let Some(summary) = run_summary(snapshot, state) {
+                parts.push(vec![styled(summary, Style::default().fg(GREEN).bold())]);
+            } else {
+                let color = state.accent();
+                let mut run = vec![
+                    run_spinner(&snapshot.spinner, state),
+                    " ".into(),
+                    styled(run_label(state), Style::default().fg(color).bold()),
+                ];
+                if let Some(elapsed) = run_elapsed(snapshot, state, now) {
+                    run.extend([" ".into(), elapsed.dim()]);
+                }
+                parts.push(run);
+            }
+            if let Some(approvals) = pending_approvals_text(state.pending_approvals) {
+                parts.push(vec![styled(approvals, Style::default().fg(YELLOW).bold())]);
+            }
+            if let Some(failure) = state.exec_failure.as_ref() {
+                let text = exec_failure_text(failure, true);
+                parts.push(vec![styled(text, Style::default().fg(RED).bold())]);
+            }
+        }
+        if let Some(path) = snapshot
+            .cwd_display
+            .as_ref()
+            .or(snapshot.cwd_fallback.as_ref())
+        {
+            parts.push(field("cwd", path.clone(), TEAL));
+        }
+        if let Some(model) = snapshot.model.as_ref() {
+            let text = match model.detail.as_ref() {
+                Some(detail) => format!("{} {detail}", model.label),
+                None => model.label.clone(),
+            };
+            parts.push(field("model", text, model.accent()));
+        }
+        if let Some(tokens) = snapshot.tokens.as_ref() {
+            if let Some(rate) = tokens.output_tokens_per_sec {
+                parts.push(field("rate", format!("{rate:.0} tok/s"), GREEN));
+            } else if let Some(duration) = tokens.last_turn_duration {
+                parts.push(field(
+                    "last turn",
+                    snapshot.timer_display.format_elapsed(duration.as_secs()),
+                    MAUVE,
+                ));
+            }
+        }
+        if let Some(state) = snapshot.run_state.as_ref()
+            && !state.queued_messages.is_empty()
+        {
+            let (preview, position) =
+                queue_preview(&state.queued_messages, state.queue_preview_index);
+            let text = match position {
+                Some(position) => format!("{preview} ({position})"),
+                None => preview,
+            };
+            parts.push(field("next", text, MAUVE));
+        }
+        join_fitting(parts, &"  ".into(), usize::from(width))
+    }
+}
+
+fn field(name: &str, value: String, color: Color) -> Vec<Span<'static>> {
+    vec![
+        format!("{name} ").dim(),
+        styled(value, Style::default().fg(color)),
+    ]
+}
@@ -1,57 +1,266 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::panic::AssertUnwindSafe;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 use std::time::Instant;
 
+use crate::exec_cell::SpinnerStyle;
+use crate::status::TokenCountFormatter;
 use crate::status::format_directory_display;
+use crate::status::truncate_to_width;
 use crate::tui::FrameRequester;
+use crate::tui::TerminalProgress;
+use crate::version::BuildStatus;
+use crate::version::build_label;
+use chrono::Local;
 use codex_core::config::Config;
+use codex_core::config::types::Devspaces;
+use codex_core::config::types::StatusLineModels;
+use codex_core::config::types::StatusLineSegments;
+use codex_core::config::types::StatusLineSettings;
+use codex_core::config::types::TerminalProgressMode;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::BudgetScope;
+use codex_core::protocol::BudgetUsage;
+use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
 use codex_protocol::openai_models::ReasoningEffort;
 use ratatui::text::Line;
 
+use super::CONTEXT_WARNING_PULSE;
+use super::CompletedRun;
+use super::CustomStatusLineRenderer;
 use super::DEFAULT_STATUS_MESSAGE;
+use super::DegradeMemos;
+use super::PolicyRisk;
+use super::RunCategory;
 use super::RunTimerSnapshot;
+use super::SegmentLayout;
 use super::StatusLine88CodeSnapshot;
+use super::StatusLineBudgetSnapshot;
 use super::StatusLineContextSnapshot;
+use super::StatusLineDevEnvSnapshot;
 use super::StatusLineDevspaceSnapshot;
+use super::StatusLineDiffStatsSnapshot;
+use super::StatusLineExecFailure;
 use super::StatusLineGitSnapshot;
+use super::StatusLineIacSnapshot;
 use super::StatusLineModelSnapshot;
+use super::StatusLinePolicySnapshot;
+use super::StatusLineProcessSnapshot;
+use super::StatusLineProviderSnapshot;
 use super::StatusLineRenderer;
 use super::StatusLineRunState;
+use super::StatusLineRunSummary;
 use super::StatusLineSnapshot;
 use super::StatusLineTokenSnapshot;
+use super::TimerDisplay;
 use super::TokenCountSnapshot;
+use super::attention::Attention;
+use super::attention::AttentionLog;
+use super::attention::AttentionSource;
+use super::clock::ClockSettings;
+use super::color_depth::ColorDepth;
+use super::path::PathStyle;
+use super::registered_devspace_icon;
+use super::run_label::MARQUEE_STEP;
+use super::run_label::RunLabel;
+use super::run_label::RunLabelSettings;
+use super::stale_88code_age;
+use super::stale_age;
+
+const TERMINAL_TITLE_PREFIX: &str = "codex";
+
+/// Characters of the session id shown in the session segment.
+const SESSION_ID_CHARS: usize = 8;
+
+/// Redraw interval while a run is timed: fast enough for the spinner, or
+/// once a second in accessible mode or while the terminal is unfocused,
+/// where only the timer text changes.
+const TIMER_FRAME_STEP: Duration = Duration::from_millis(48);
+const ACCESSIBLE_TIMER_FRAME_STEP: Duration = Duration::from_secs(1);
+
+/// How long the run pill spells out a failed command before only the badge
+/// remains.
+const EXEC_FAILURE_FLASH: Duration = Duration::from_secs(5);
+/// Columns of the failed command shown in the run pill.
+const EXEC_FAILURE_COMMAND_WIDTH: usize = 24;
 
 #[derive(Debug)]
 pub(crate) struct StatusLineState {
     cwd: PathBuf,
     frame_requester: FrameRequester,
     renderer: Box<dyn StatusLineRenderer>,
+    // Drawn instead of `renderer` once it panicked.
+    fallback_renderer: CustomStatusLineRenderer,
+    renderer_panicked: Cell<bool>,
+    // Set by a panic until the overlay reports it.
+    renderer_panic_pending: Cell<bool>,
+    color_depth: ColorDepth,
     snapshot: StatusLineSnapshot,
     run_timer: Option<RunTimer>,
     queued_messages: Vec<String>,
+    queue_changed_at: Instant,
+    // How long each queued message is previewed before moving to the next.
+    queue_cycle: Option<Duration>,
+    // `tui.statusline.middle_queue_max`.
+    middle_queue_max: i64,
+    pending_approvals: i64,
     esc_hint: bool,
     context_window_hint: Option<i64>,
+    terminal_progress_mode: TerminalProgressMode,
+    terminal_title_enabled: bool,
+    // Total output tokens when the current run started, for tokens/sec.
+    run_output_tokens_start: i64,
+    // Total tokens when the current run started, for the run summary.
+    run_total_tokens_start: i64,
+    last_turn_duration: Option<Duration>,
+    // Session token totals split by the model that was active when they were
+    // used, in first-use order.
+    model_tokens: Vec<(String, TokenUsage)>,
+    // Session total at the last token update, so the next update knows how
+    // much to attribute to the active model.
+    session_tokens: Option<TokenUsage>,
+    // How long the run pill recaps a finished task; `None` when off.
+    run_summary: Option<Duration>,
+    last_run: Option<LastRun>,
+    // Last command of the current task that exited non-zero.
+    exec_failure: Option<ExecFailure>,
+    context_warning_threshold: i64,
+    compact_hint_threshold: i64,
+    context_baseline_tokens: i64,
+    run_labels: RunLabelSettings,
+    // Set while the run label came from a template, so snapshots can scroll
+    // its variable part.
+    run_label: Option<RunLabel>,
+    segments: StatusLineSegments,
+    models: StatusLineModels,
+    devspaces: Devspaces,
+    attention: AttentionLog,
+    session_id: Option<String>,
+    path_style: PathStyle,
+    // Set while the clock segment is enabled.
+    clock: Option<ClockSettings>,
+    session_started_at: Instant,
+    // `tui.accessible`: no marquee or pulse, and the timer redraws once a
+    // second.
+    accessible: bool,
+    // Cleared while the terminal reports focus lost: the spinner, marquee,
+    // queue preview and context pulse stop animating until focus returns.
+    terminal_focused: bool,
+    line_cache: RefCell<Option<CachedLine>>,
+    run_pill_cache: RefCell<Option<CachedLine>>,
+    // Resize hysteresis for the status line, per width band.
+    degrade_memos: RefCell<DegradeMemos>,
+    // Nesting depth of `begin_update`; redraws wait for the last `commit`.
+    update_depth: i64,
+    // Something changed inside `begin_update`/`commit`.
+    update_dirty: Cell<bool>,
+}
+
+thread_local! {
+    static IN_GUARDED_RENDER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether this thread is running a status line renderer under
+/// [`StatusLineState`]'s panic guard. The panic hook stays quiet then: the
+/// guard catches the panic, logs it and falls back to `powerline`.
+pub(crate) fn in_guarded_render() -> bool {
+    IN_GUARDED_RENDER.with(Cell::get)
+}
+
+/// Last line produced by the renderer, reused while neither the snapshot nor
+/// the width changed.
+#[derive(Debug)]
+struct CachedLine {
+    key: u64,
+    width: u16,
+    line: Line<'static>,
 }
 
 impl StatusLineState {
+    #[cfg(test)]
     pub(crate) fn with_renderer(
         config: &Config,
         frame_requester: FrameRequester,
         renderer: Box<dyn StatusLineRenderer>,
+    ) -> Self {
+        Self::with_settings(config, &config.tui_statusline, frame_requester, renderer)
+    }
+
+    /// `settings` replaces `config.tui_statusline`, so callers can apply
+    /// workspace overrides first.
+    pub(crate) fn with_settings(
+        config: &Config,
+        settings: &StatusLineSettings,
+        frame_requester: FrameRequester,
+        renderer: Box<dyn StatusLineRenderer>,
     ) -> Self {
         let cwd = config.cwd.clone();
         let mut state = Self {
             cwd: cwd.clone(),
             frame_requester,
             renderer,
-            snapshot: StatusLineSnapshot::default(),
+            fallback_renderer: CustomStatusLineRenderer,
+            renderer_panicked: Cell::new(false),
+            renderer_panic_pending: Cell::new(false),
+            color_depth: ColorDepth::from_config(settings.color),
+            snapshot: StatusLineSnapshot {
+                layout: SegmentLayout::from_config(&settings.layout),
+                timer_display: TimerDisplay::from_config(settings),
+                max_widths: settings.max_widths,
+                token_format: TokenCountFormatter::from_config(&config.tui_token_format),
+                spinner: SpinnerStyle::from_config(&config.tui_spinner),
+                build: Some(build_label(None)),
+                ..StatusLineSnapshot::default()
+            },
             run_timer: None,
             queued_messages: Vec::new(),
+            queue_changed_at: Instant::now(),
+            middle_queue_max: settings.middle_queue_max,
+            queue_cycle: enabled_secs(settings.queue_preview_cycle_secs),
+            pending_approvals: 0,
             esc_hint: true,
             context_window_hint: config.model_context_window,
+            terminal_progress_mode: config.tui_terminal_progress,
+            terminal_title_enabled: config.tui_terminal_title,
+            run_output_tokens_start: 0,
+            run_total_tokens_start: 0,
+            last_turn_duration: None,
+            model_tokens: Vec::new(),
+            session_tokens: None,
+            run_summary: enabled_secs(settings.run_summary_secs),
+            last_run: None,
+            exec_failure: None,
+            context_warning_threshold: settings.context_warning_threshold,
+            compact_hint_threshold: settings.compact_hint_threshold,
+            context_baseline_tokens: settings.context_baseline_tokens,
+            run_labels: RunLabelSettings::from_config(settings),
+            run_label: None,
+            segments: settings.segments,
+            models: settings.models.clone(),
+            devspaces: config.tui_devspaces.clone(),
+            attention: AttentionLog::default(),
+            session_id: None,
+            path_style: PathStyle::from_config(settings),
+            clock: settings
+                .segments
+                .clock
+                .then(|| ClockSettings::from_config(settings)),
+            session_started_at: Instant::now(),
+            accessible: config.tui_accessible,
+            terminal_focused: true,
+            line_cache: RefCell::new(None),
+            run_pill_cache: RefCell::new(None),
+            degrade_memos: RefCell::new(DegradeMemos::new()),
+            update_depth: 0,
+            update_dirty: Cell::new(false),
         };
         state.set_working_directory(&cwd);
         state.set_idle_run_state(Instant::now());
@@ -60,12 +269,47 @@ impl StatusLineState {
 
     pub(crate) fn set_renderer(&mut self, renderer: Box<dyn StatusLineRenderer>) {
         self.renderer = renderer;
+        self.renderer_panicked.set(false);
+        self.line_cache.take();
+        self.run_pill_cache.take();
         self.request_redraw();
     }
 
+    /// Re-read everything `with_settings` took from `settings`, for a
+    /// `[tui.statusline]` edited while the TUI runs. The model icon and the
+    /// context thresholds take effect on the next model or token update.
+    pub(crate) fn apply_settings(&mut self, settings: &StatusLineSettings) {
+        self.color_depth = ColorDepth::from_config(settings.color);
+        self.snapshot.layout = SegmentLayout::from_config(&settings.layout);
+        self.snapshot.timer_display = TimerDisplay::from_config(settings);
+        self.snapshot.max_widths = settings.max_widths;
+        self.middle_queue_max = settings.middle_queue_max;
+        self.queue_cycle = enabled_secs(settings.queue_preview_cycle_secs);
+        self.run_summary = enabled_secs(settings.run_summary_secs);
+        self.context_warning_threshold = settings.context_warning_threshold;
+        self.compact_hint_threshold = settings.compact_hint_threshold;
+        self.context_baseline_tokens = settings.context_baseline_tokens;
+        self.run_labels = RunLabelSettings::from_config(settings);
+        self.segments = settings.segments;
+        self.models = settings.models.clone();
+        self.path_style = PathStyle::from_config(settings);
+        self.clock = settings
+            .segments
+            .clock
+            .then(|| ClockSettings::from_config(settings));
+        self.line_cache.take();
+        self.run_pill_cache.take();
+        let cwd = self.cwd.clone();
+        self.set_working_directory(&cwd);
+    }
+
     pub(crate) fn set_working_directory(&mut self, cwd: &Path) {
         self.cwd = cwd.to_path_buf();
-        let display = format_directory_display(cwd, None);
+        let display = self.path_style.fit(
+            &self
+                .path_style
+                .abbreviate(&format_directory_display(cwd, None)),
+        );
         let basename = cwd
             .file_name()
             .map(|os| os.to_string_lossy().to_string())
@@ -81,36 +325,140 @@ impl StatusLineState {
         label: impl Into<String>,
         effort: Option<ReasoningEffort>,
     ) {
+        let label = label.into();
         let detail = reasoning_detail(effort);
+        let style = self.models.style_for(&label);
         self.snapshot.model = Some(StatusLineModelSnapshot {
-            label: label.into(),
+            label,
             detail,
+            icon: style.and_then(|style| style.icon.clone()),
+            color: style.and_then(|style| style.color.clone()),
         });
+        if !self.model_tokens.is_empty()
+            && let Some(tokens) = self.snapshot.tokens.as_mut()
+        {
+            tokens.total = active_model_tokens(&self.model_tokens, self.snapshot.model.as_ref())
+                .map(token_count)
+                .unwrap_or_default();
+        }
         self.request_redraw();
     }
 
     pub(crate) fn update_tokens(&mut self, info: Option<TokenUsageInfo>) {
         if let Some(info) = info {
+            self.attribute_model_tokens(&info.total_token_usage);
             let context_window = info.model_context_window.or(self.context_window_hint);
-            let (token_snapshot, context_snapshot) =
-                token_snapshot_from_info(&info, context_window);
+            let (mut token_snapshot, mut context_snapshot) =
+                token_snapshot_from_info(&info, context_window, self.context_baseline_tokens);
+            if let Some(context) = context_snapshot.as_mut() {
+                context.low_since = self.low_context_since(context.percent_remaining);
+                context.suggest_compact = self.compact_hint_threshold > 0
+                    && i64::from(context.percent_used()) >= self.compact_hint_threshold;
+            }
+            if let Some(usage) =
+                active_model_tokens(&self.model_tokens, self.snapshot.model.as_ref())
+            {
+                token_snapshot.total = token_count(usage);
+            }
+            token_snapshot.output_tokens_per_sec =
+                self.output_tokens_per_sec(token_snapshot.total.output_tokens, Instant::now());
+            token_snapshot.last_turn_duration = self.last_turn_duration;
             self.snapshot.tokens = Some(token_snapshot);
             self.snapshot.context = context_snapshot;
         } else {
             self.snapshot.tokens = None;
             self.snapshot.context = None;
+            self.model_tokens.clear();
+            self.session_tokens = None;
         }
         self.request_redraw();
     }
 
+    /// Session token totals per model label, in first-use order, for
+    /// `/status`.
+    pub(crate) fn model_token_usage(&self) -> &[(String, TokenUsage)] {
+        &self.model_tokens
+    }
+
+    /// Credit the growth of the session total since the last update to the
+    /// active model. A total that shrank belongs to a new session, so the
+    /// per-model split starts over.
+    fn attribute_model_tokens(&mut self, total: &TokenUsage) {
+        let previous = self.session_tokens.replace(total.clone());
+        let Some(model) = self.snapshot.model.as_ref() else {
+            return;
+        };
+        let label = model.label.clone();
+        let delta = match previous {
+            Some(previous) if total.total_tokens >= previous.total_tokens => {
+                token_usage_delta(total, &previous)
+            }
+            _ => {
+                self.model_tokens.clear();
+                total.clone()
+            }
+        };
+        match self
+            .model_tokens
+            .iter_mut()
+            .find(|(model, _)| *model == label)
+        {
+            Some((_, usage)) => usage.add_assign(&delta),
+            None => self.model_tokens.push((label, delta)),
+        }
+    }
+
     pub(crate) fn set_git_info(&mut self, git: Option<StatusLineGitSnapshot>) {
         self.snapshot.git = git;
         self.request_redraw();
     }
 
-    pub(crate) fn set_devspace(&mut self, devspace: Option<String>) {
-        self.snapshot.environment.devspace =
-            devspace.map(|name| StatusLineDevspaceSnapshot { name });
+    pub(crate) fn add_diff_stats(&mut self, added: i64, removed: i64) {
+        let stats = self
+            .snapshot
+            .diff_stats
+            .get_or_insert_with(StatusLineDiffStatsSnapshot::default);
+        stats.added = stats.added.saturating_add(added);
+        stats.removed = stats.removed.saturating_add(removed);
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_provider(&mut self, provider: Option<StatusLineProviderSnapshot>) {
+        self.snapshot.provider = provider;
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_policy(&mut self, approval: AskForApproval, sandbox: &SandboxPolicy) {
+        self.snapshot.policy = Some(policy_snapshot(approval, sandbox));
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_dev_env(&mut self, dev_env: Option<StatusLineDevEnvSnapshot>) {
+        self.snapshot.environment.dev_env = dev_env;
+        self.request_redraw();
+    }
+
+    /// Show the devspace `name`, styled from `tui.devspaces` when it is
+    /// registered there.
+    pub(crate) fn set_devspace(&mut self, name: Option<String>) {
+        let devspace = name.map(|name| match self.devspaces.get(&name) {
+            Some((index, entry)) => StatusLineDevspaceSnapshot {
+                icon: Some(
+                    entry
+                        .icon
+                        .clone()
+                        .unwrap_or_else(|| registered_devspace_icon(index).to_string()),
+                ),
+                color: entry.color.clone(),
+                description: entry.description.clone(),
+                name,
+            },
+            None => StatusLineDevspaceSnapshot {
+                name,
+                ..StatusLineDevspaceSnapshot::default()
+            },
+        });
+        self.snapshot.environment.devspace = devspace;
         self.request_redraw();
     }
 
@@ -119,6 +467,23 @@ impl StatusLineState {
         self.request_redraw();
     }
 
+    pub(crate) fn set_host_style(&mut self, color: Option<String>, ssh: bool) {
+        self.snapshot.environment.host_color = color;
+        self.snapshot.environment.ssh = ssh;
+        self.request_redraw();
+    }
+
+    /// The `build` segment: this binary's version and commit, and the newer
+    /// release found at startup.
+    pub(crate) fn set_build(&mut self, build: &BuildStatus) {
+        self.snapshot.build = Some(build_label(build.sha));
+        self.snapshot.update = build
+            .newer_release
+            .as_ref()
+            .map(|release| release.version.clone());
+        self.request_redraw();
+    }
+
     pub(crate) fn set_interrupt_hint_visible(&mut self, visible: bool) {
         if self.esc_hint == visible {
             return;
@@ -135,21 +500,106 @@ impl StatusLineState {
         self.request_redraw();
     }
 
+    pub(crate) fn set_custom_segment(&mut self, text: Option<String>) {
+        self.snapshot.custom = text;
+        self.request_redraw();
+    }
+
+    pub(crate) fn push_attention(&mut self, attention: Attention) {
+        self.attention.push(attention);
+        self.sync_attention();
+    }
+
+    pub(crate) fn clear_attention_source(&mut self, source: AttentionSource) {
+        if self.attention.clear_source(source) {
+            self.sync_attention();
+        }
+    }
+
+    pub(crate) fn clear_attention(&mut self) {
+        self.attention.clear();
+        self.sync_attention();
+    }
+
+    /// Every attention entry, oldest first, for the popup.
+    pub(crate) fn attention_entries(&self) -> &[Attention] {
+        self.attention.entries()
+    }
+
+    fn sync_attention(&mut self) {
+        self.snapshot.attention = self.attention.snapshot();
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_iac(&mut self, iac: Option<StatusLineIacSnapshot>) {
+        self.snapshot.environment.iac = iac;
+        self.request_redraw();
+    }
+
     pub(crate) fn set_kubernetes_context(&mut self, context: Option<String>) {
         self.snapshot.environment.kubernetes_context = context;
         self.request_redraw();
     }
 
+    pub(crate) fn set_process(&mut self, process: Option<StatusLineProcessSnapshot>) {
+        self.snapshot.process = process;
+        self.request_redraw();
+    }
+
+    /// Show or clear the `offline` chip in the run pill.
+    pub(crate) fn set_offline(&mut self, offline: bool) {
+        if self.snapshot.offline != offline {
+            self.snapshot.offline = offline;
+            self.request_redraw();
+        }
+    }
+
+    /// Show whichever budget is closest to its limit.
+    pub(crate) fn set_budgets(&mut self, budgets: &[BudgetUsage]) {
+        self.snapshot.budget = budgets
+            .iter()
+            .filter_map(|budget| Some((budget, budget.used_fraction()?)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(budget, fraction)| StatusLineBudgetSnapshot {
+                scope: match budget.scope {
+                    BudgetScope::Session => "session".to_string(),
+                    BudgetScope::Workspace => "workspace".to_string(),
+                },
+                percent_used: (fraction * 100.0).round() as i64,
+                spend: budget.spend_text(),
+            });
+        self.request_redraw();
+    }
+
     pub(crate) fn set_88code_info(&mut self, info: Option<StatusLine88CodeSnapshot>) {
         self.snapshot.environment.code88 = info;
         self.request_redraw();
     }
 
+    pub(crate) fn code88_info(&self) -> Option<&StatusLine88CodeSnapshot> {
+        self.snapshot.environment.code88.as_ref()
+    }
+
     pub(crate) fn set_session_id(&mut self, session_id: Option<String>) {
-        let _ = session_id;
+        self.snapshot.session = session_id.as_deref().map(|id| {
+            id.char_indices()
+                .rev()
+                .nth(SESSION_ID_CHARS - 1)
+                .map_or(id, |(start, _)| &id[start..])
+                .to_string()
+        });
+        self.session_id = session_id;
+        self.request_redraw();
+    }
+
+    pub(crate) fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
     }
 
     pub(crate) fn set_queued_messages(&mut self, messages: Vec<String>) {
+        if self.queued_messages != messages {
+            self.queue_changed_at = Instant::now();
+        }
         self.queued_messages = messages;
         if let Some(run_state) = self.snapshot.run_state.as_mut() {
             run_state.queued_messages = self.queued_messages.clone();
@@ -157,10 +607,57 @@ impl StatusLineState {
         self.request_redraw();
     }
 
-    pub(crate) fn update_run_header(&mut self, header: &str) {
+    pub(crate) fn set_pending_approvals(&mut self, count: i64) {
+        if self.pending_approvals != count {
+            self.pending_approvals = count;
+            self.request_redraw();
+        }
+    }
+
+    /// Whether the context area currently offers ctrl+k to compact.
+    pub(crate) fn compact_suggested(&self) -> bool {
+        self.segments.context
+            && self
+                .snapshot
+                .context
+                .as_ref()
+                .is_some_and(|context| context.suggest_compact)
+    }
+
+    pub(crate) fn segments(&self) -> StatusLineSegments {
+        self.segments
+    }
+
+    pub(crate) fn run_labels(&self) -> &RunLabelSettings {
+        &self.run_labels
+    }
+
+    /// Label of the task in progress, as the run pill shows it.
+    pub(crate) fn current_run_label(&self) -> Option<&str> {
+        if !self.task_in_progress() {
+            return None;
+        }
+        self.snapshot
+            .run_state
+            .as_ref()
+            .map(|run_state| run_state.label.as_str())
+    }
+
+    pub(crate) fn update_run_header(&mut self, header: &str, category: RunCategory) {
+        self.run_label = None;
+        self.set_run_label_text(header, category);
+    }
+
+    pub(crate) fn update_run_label(&mut self, label: RunLabel, category: RunCategory) {
+        self.set_run_label_text(&label.truncated(self.run_labels.max_width()), category);
+        self.run_label = Some(label);
+    }
+
+    fn set_run_label_text(&mut self, header: &str, category: RunCategory) {
         if let Some(run_state) = self.snapshot.run_state.as_mut() {
-            if run_state.label != header {
+            if run_state.label != header || run_state.category != category {
                 run_state.label = header.to_string();
+                run_state.category = category;
                 run_state.status_changed_at = Instant::now();
                 self.request_redraw();
             }
@@ -169,13 +666,16 @@ impl StatusLineState {
                 label: header.to_string(),
                 show_interrupt_hint: self.esc_hint,
                 queued_messages: self.queued_messages.clone(),
+                category,
                 status_changed_at: Instant::now(),
                 ..StatusLineRunState::default()
             });
             self.request_redraw();
         }
     }
+
     fn set_idle_run_state(&mut self, now: Instant) {
+        self.run_label = None;
         let run_state = StatusLineRunState {
             label: DEFAULT_STATUS_MESSAGE.to_string(),
             spinner_started_at: None,
@@ -185,7 +685,13 @@ impl StatusLineState {
                 is_paused: true,
             }),
             queued_messages: self.queued_messages.clone(),
+            queue_preview_index: None,
+            pending_approvals: self.pending_approvals,
             show_interrupt_hint: false,
+            paused: false,
+            summary: None,
+            category: RunCategory::default(),
+            exec_failure: None,
             status_changed_at: now,
         };
         self.snapshot.run_state = Some(run_state);
@@ -197,10 +703,22 @@ impl StatusLineState {
         let now = Instant::now();
         match self.run_timer.as_mut() {
             Some(timer) => timer.resume(now),
-            None => self.run_timer = Some(RunTimer::new(now)),
+            None => {
+                self.run_timer = Some(RunTimer::new(now));
+                self.exec_failure = None;
+                let total = self.snapshot.tokens.as_ref().map(|tokens| &tokens.total);
+                self.run_output_tokens_start = total.map_or(0, |total| total.output_tokens);
+                self.run_total_tokens_start = total.map_or(0, |total| total.total_tokens);
+                if let Some(tokens) = self.snapshot.tokens.as_mut() {
+                    tokens.output_tokens_per_sec = None;
+                }
+            }
         }
+        self.run_label = None;
         let mut run_state = self.snapshot.run_state.clone().unwrap_or_default();
         run_state.label = header;
+        run_state.category = RunCategory::Thinking;
+        run_state.paused = false;
         run_state.show_interrupt_hint = self.esc_hint;
         run_state.queued_messages = self.queued_messages.clone();
         run_state.status_changed_at = now;
@@ -208,18 +726,102 @@ impl StatusLineState {
         self.request_redraw();
     }
 
-    pub(crate) fn complete_task(&mut self) {
+    /// Note how a command the agent ran exited. A failure flashes in the run
+    /// pill and leaves a badge until the next task starts.
+    pub(crate) fn record_exec_exit(&mut self, command: &str, exit_code: i32) {
+        if exit_code == 0 {
+            return;
+        }
+        self.exec_failure = Some(ExecFailure {
+            exit_code,
+            command: truncate_to_width(command.trim(), EXEC_FAILURE_COMMAND_WIDTH),
+            failed_at: Instant::now(),
+        });
+        self.request_redraw();
+    }
+
+    /// Stop the run timer and return the label and elapsed time of the run
+    /// that just finished, if one was in progress.
+    pub(crate) fn complete_task(&mut self) -> Option<CompletedRun> {
+        self.finish_task(false)
+    }
+
+    /// Like `complete_task`, for a run the user stopped; the recap marks it
+    /// as interrupted instead of finished.
+    pub(crate) fn interrupt_task(&mut self) -> Option<CompletedRun> {
+        self.finish_task(true)
+    }
+
+    fn finish_task(&mut self, interrupted: bool) -> Option<CompletedRun> {
         let now = Instant::now();
-        if let Some(timer) = self.run_timer.as_mut() {
+        let completed = self.run_timer.take().map(|mut timer| {
             timer.pause(now);
+            let label = self
+                .snapshot
+                .run_state
+                .as_ref()
+                .map(|run_state| run_state.label.trim())
+                .filter(|label| !label.is_empty())
+                .unwrap_or(DEFAULT_STATUS_MESSAGE)
+                .to_string();
+            CompletedRun {
+                label,
+                elapsed: timer.elapsed_running,
+            }
+        });
+        if let Some(completed) = completed.as_ref() {
+            self.last_turn_duration = Some(completed.elapsed);
+            let mut tokens_used = 0;
+            if let Some(tokens) = self.snapshot.tokens.as_mut() {
+                tokens.last_turn_duration = Some(completed.elapsed);
+                tokens_used = tokens
+                    .total
+                    .total_tokens
+                    .saturating_sub(self.run_total_tokens_start)
+                    .max(0);
+            }
+            self.last_run = Some(LastRun {
+                summary: StatusLineRunSummary {
+                    label: completed.label.clone(),
+                    elapsed_secs: completed.elapsed.as_secs(),
+                    tokens: tokens_used,
+                    interrupted,
+                },
+                finished_at: now,
+            });
         }
-        self.run_timer = None;
         self.set_idle_run_state(now);
         self.request_redraw();
+        completed
+    }
+
+    /// Stop the run timer while the user has paused the task. The run stays
+    /// open: `start_task` resumes it and `complete_task` ends it.
+    pub(crate) fn pause_task(&mut self) {
+        let now = Instant::now();
+        let Some(timer) = self.run_timer.as_mut() else {
+            return;
+        };
+        timer.pause(now);
+        if let Some(run_state) = self.snapshot.run_state.as_mut() {
+            run_state.paused = true;
+            run_state.status_changed_at = now;
+        }
+        self.request_redraw();
+    }
+
+    /// Whether a task is in progress, running or paused.
+    pub(crate) fn task_in_progress(&self) -> bool {
+        self.run_timer.is_some()
     }
 
     pub(crate) fn resume_timer(&mut self) {
-        if let Some(timer) = self.run_timer.as_mut() {
+        let paused = self
+            .snapshot
+            .run_state
+            .as_ref()
+            .is_some_and(|run_state| run_state.paused);
+        if !paused && let Some(timer) = self.run_timer.as_mut() {
             timer.resume(Instant::now());
             self.request_redraw();
         }
@@ -227,6 +829,7 @@ impl StatusLineState {
 
     pub(crate) fn snapshot_for_render(&self, now: Instant) -> StatusLineSnapshot {
         let mut snapshot = self.snapshot.clone();
+        self.hide_disabled_segments(&mut snapshot);
         if let (Some(run_state), Some(timer)) =
             (snapshot.run_state.as_mut(), self.run_timer.as_ref())
         {
@@ -235,6 +838,79 @@ impl StatusLineState {
             run_state.queued_messages = self.queued_messages.clone();
             run_state.show_interrupt_hint = self.esc_hint;
         }
+        if let Some(run_state) = snapshot.run_state.as_mut() {
+            run_state.pending_approvals = self.pending_approvals;
+        }
+        snapshot.middle_queue = self
+            .queued_messages
+            .iter()
+            .take(usize::try_from(self.middle_queue_max).unwrap_or(0))
+            .cloned()
+            .collect();
+        if let Some(period) = self.queue_cycle
+            && self.terminal_focused
+            && let Some(run_state) = snapshot.run_state.as_mut()
+            && run_state.queued_messages.len() > 1
+        {
+            let elapsed = now.saturating_duration_since(self.queue_changed_at);
+            let steps = elapsed.as_millis() / period.as_millis();
+            let index = steps % run_state.queued_messages.len() as u128;
+            run_state.queue_preview_index = Some(i64::try_from(index).unwrap_or(0));
+            let into_step =
+                Duration::from_millis((elapsed.as_millis() % period.as_millis()) as u64);
+            self.frame_requester
+                .schedule_frame_in(period.saturating_sub(into_step));
+        }
+        let max_width = self.run_labels.max_width();
+        let marquee = !self.accessible
+            && self.terminal_focused
+            && self.run_labels.marquee()
+            && self
+                .run_label
+                .as_ref()
+                .is_some_and(|label| label.overflows(max_width));
+        if marquee
+            && let (Some(run_state), Some(label)) =
+                (snapshot.run_state.as_mut(), self.run_label.as_ref())
+        {
+            let elapsed = now.saturating_duration_since(run_state.status_changed_at);
+            run_state.label = label.marquee(max_width, elapsed);
+        }
+        if let Some(remaining) = self.run_summary_remaining(now)
+            && let (Some(last_run), Some(run_state)) =
+                (self.last_run.as_ref(), snapshot.run_state.as_mut())
+        {
+            run_state.summary = Some(last_run.summary.clone());
+            self.frame_requester.schedule_frame_in(remaining);
+        }
+        if let (Some(failure), Some(run_state)) =
+            (self.exec_failure.as_ref(), snapshot.run_state.as_mut())
+        {
+            let shown_for = now.saturating_duration_since(failure.failed_at);
+            let remaining = EXEC_FAILURE_FLASH.checked_sub(shown_for);
+            run_state.exec_failure = Some(StatusLineExecFailure {
+                exit_code: failure.exit_code,
+                command: failure.command.clone(),
+                recent: remaining.is_some_and(|remaining| !remaining.is_zero()),
+            });
+            if let Some(remaining) = remaining {
+                self.frame_requester.schedule_frame_in(remaining);
+            }
+        }
+        if let Some(age) = snapshot
+            .environment
+            .code88
+            .as_ref()
+            .and_then(|info| stale_88code_age(info, now))
+        {
+            let (_, next_change) = stale_age(age);
+            self.frame_requester.schedule_frame_in(next_change);
+        }
+        let clock_refresh = self.clock.as_ref().map(|clock| {
+            let (text, refresh) = clock.text(self.session_started_at, now, Local::now());
+            snapshot.clock = Some(text);
+            refresh
+        });
         let timer_active = self
             .run_timer
             .as_ref()
@@ -242,16 +918,136 @@ impl StatusLineState {
             .unwrap_or(false);
         if timer_active {
             self.frame_requester
-                .schedule_frame_in(Duration::from_millis(48));
+                .schedule_frame_in(if self.accessible || !self.terminal_focused {
+                    ACCESSIBLE_TIMER_FRAME_STEP
+                } else {
+                    TIMER_FRAME_STEP
+                });
+        } else if marquee {
+            self.frame_requester.schedule_frame_in(MARQUEE_STEP);
+        } else if !self.accessible
+            && self.terminal_focused
+            && snapshot
+                .context
+                .as_ref()
+                .is_some_and(|context| context.low_since.is_some())
+        {
+            self.frame_requester
+                .schedule_frame_in(CONTEXT_WARNING_PULSE);
+        } else if let Some(refresh) = clock_refresh {
+            self.frame_requester.schedule_frame_in(refresh);
         }
         snapshot
     }
 
+    /// Time left to recap the last task: only while idle, within
+    /// `run_summary_secs` of it finishing.
+    fn run_summary_remaining(&self, now: Instant) -> Option<Duration> {
+        if self.run_timer.is_some() {
+            return None;
+        }
+        let shown_for = self.run_summary?;
+        let last_run = self.last_run.as_ref()?;
+        shown_for
+            .checked_sub(now.saturating_duration_since(last_run.finished_at))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    fn hide_disabled_segments(&self, snapshot: &mut StatusLineSnapshot) {
+        let segments = self.segments;
+        if !segments.path {
+            snapshot.cwd_display = None;
+            snapshot.cwd_basename = None;
+            snapshot.cwd_fallback = None;
+        }
+        if !segments.model {
+            snapshot.model = None;
+        }
+        if !segments.context {
+            snapshot.context = None;
+        }
+        if !segments.git {
+            snapshot.git = None;
+        }
+        if !segments.policy {
+            snapshot.policy = None;
+        }
+        if !segments.provider {
+            snapshot.provider = None;
+        }
+        if !segments.diff_stats {
+            snapshot.diff_stats = None;
+        }
+        if !segments.custom {
+            snapshot.custom = None;
+        }
+        if !segments.attention {
+            snapshot.attention = None;
+        }
+        if !segments.process {
+            snapshot.process = None;
+        }
+        if !segments.budget {
+            snapshot.budget = None;
+        }
+        if !segments.session {
+            snapshot.session = None;
+        }
+        if !segments.build {
+            snapshot.build = None;
+        }
+        let environment = &mut snapshot.environment;
+        if !segments.dev_env {
+            environment.dev_env = None;
+        }
+        if !segments.devspace {
+            environment.devspace = None;
+        }
+        if !segments.hostname {
+            environment.hostname = None;
+            environment.host_color = None;
+            environment.ssh = false;
+        }
+        if !segments.aws_profile {
+            environment.aws_profile = None;
+        }
+        if !segments.iac {
+            environment.iac = None;
+        }
+        if !segments.kubernetes {
+            environment.kubernetes_context = None;
+        }
+        if !segments.code88 {
+            environment.code88 = None;
+        }
+    }
+
     pub(crate) fn render_line(&self, width: u16) -> Line<'static> {
         let now = Instant::now();
         let mut snapshot = self.snapshot_for_render(now);
         snapshot.run_state = None;
-        self.renderer.render(&snapshot, width, now)
+        let key = render_key(&snapshot, now);
+        cached_render(&self.line_cache, key, width, || {
+            self.color_depth.apply(
+                self.render_guarded(|renderer| self.render_banded(renderer, &snapshot, width, now)),
+            )
+        })
+    }
+
+    /// Status line with the queued message and pending approval counts, for
+    /// when the run pill that normally shows them is not rendered.
+    pub(crate) fn render_line_without_pill(&self, width: u16) -> Line<'static> {
+        let now = Instant::now();
+        let mut snapshot = self.snapshot_for_render(now);
+        snapshot.run_state = None;
+        snapshot.queued = i64::try_from(self.queued_messages.len()).unwrap_or(i64::MAX);
+        snapshot.pending_approvals = self.pending_approvals;
+        let key = render_key(&snapshot, now);
+        cached_render(&self.line_cache, key, width, || {
+            self.color_depth.apply(
+                self.render_guarded(|renderer| self.render_banded(renderer, &snapshot, width, now)),
+            )
+        })
     }
 
     pub(crate) fn render_run_pill(&self, width: u16) -> Line<'static> {
@@ -267,18 +1063,200 @@ impl StatusLineState {
                     is_paused: true,
                 }),
                 queued_messages: Vec::new(),
+                queue_preview_index: None,
+                pending_approvals: self.pending_approvals,
                 show_interrupt_hint: false,
+                paused: false,
+                summary: None,
+                category: RunCategory::default(),
+                exec_failure: None,
                 status_changed_at: now,
             });
         }
-        self.renderer.render_run_pill(&snapshot, width, now)
+        // The spinner and elapsed time change on every frame while the timer
+        // runs, so only idle and paused pills are worth caching.
+        if self
+            .run_timer
+            .as_ref()
+            .is_some_and(|timer| !timer.is_paused)
+        {
+            return self.color_depth.apply(
+                self.render_guarded(|renderer| renderer.render_run_pill(&snapshot, width, now)),
+            );
+        }
+        let key = render_key(&snapshot, now);
+        cached_render(&self.run_pill_cache, key, width, || {
+            self.color_depth.apply(
+                self.render_guarded(|renderer| renderer.render_run_pill(&snapshot, width, now)),
+            )
+        })
+    }
+
+    fn render_banded(
+        &self,
+        renderer: &dyn StatusLineRenderer,
+        snapshot: &StatusLineSnapshot,
+        width: u16,
+        now: Instant,
+    ) -> Line<'static> {
+        let mut memos = self.degrade_memos.borrow_mut();
+        renderer.render_banded(snapshot, width, now, &mut memos)
+    }
+
+    /// Run `render` with the configured renderer, or with `powerline` once
+    /// that panicked, so a broken skin cannot take the TUI down with it.
+    fn render_guarded(
+        &self,
+        render: impl Fn(&dyn StatusLineRenderer) -> Line<'static>,
+    ) -> Line<'static> {
+        if !self.renderer_panicked.get() {
+            let was_guarded = IN_GUARDED_RENDER.replace(true);
+            let result =
+                std::panic::catch_unwind(AssertUnwindSafe(|| render(self.renderer.as_ref())));
+            IN_GUARDED_RENDER.set(was_guarded);
+            match result {
+                Ok(line) => return line,
+                Err(payload) => {
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .copied()
+                        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                        .unwrap_or("unknown panic");
+                    tracing::error!(
+                        "status line renderer panicked: {message}; falling back to powerline"
+                    );
+                    self.renderer_panicked.set(true);
+                    self.renderer_panic_pending.set(true);
+                    self.request_redraw();
+                }
+            }
+        }
+        render(&self.fallback_renderer)
+    }
+
+    /// Whether the renderer panicked since the last call.
+    pub(crate) fn take_renderer_panic(&mut self) -> bool {
+        self.renderer_panic_pending.replace(false)
+    }
+
+    /// Progress to report to the terminal: only while a task runs, cleared
+    /// again once `complete_task` drops the run timer.
+    pub(crate) fn terminal_progress(&self) -> TerminalProgress {
+        if self.run_timer.is_none() {
+            return TerminalProgress::Clear;
+        }
+        match self.terminal_progress_mode {
+            TerminalProgressMode::Off => TerminalProgress::Clear,
+            TerminalProgressMode::Spinner => TerminalProgress::Indeterminate,
+            TerminalProgressMode::Context => self
+                .snapshot
+                .context
+                .as_ref()
+                .map(|context| TerminalProgress::Percent(context.percent_used()))
+                .unwrap_or(TerminalProgress::Indeterminate),
+        }
+    }
+
+    /// When the context first dropped under the warning threshold; kept
+    /// across updates so the pulse animation does not restart.
+    fn low_context_since(&self, percent_remaining: u8) -> Option<Instant> {
+        if i64::from(percent_remaining) >= self.context_warning_threshold {
+            return None;
+        }
+        self.snapshot
+            .context
+            .as_ref()
+            .and_then(|context| context.low_since)
+            .or_else(|| Some(Instant::now()))
+    }
+
+    /// Output tokens produced by the running task divided by its elapsed
+    /// running time. `None` while idle or before the first full second.
+    fn output_tokens_per_sec(&self, total_output_tokens: i64, now: Instant) -> Option<f64> {
+        let elapsed = self.run_timer.as_ref()?.snapshot(now).elapsed_running;
+        if elapsed < Duration::from_secs(1) {
+            return None;
+        }
+        let produced = total_output_tokens.saturating_sub(self.run_output_tokens_start);
+        (produced > 0).then(|| produced as f64 / elapsed.as_secs_f64())
+    }
+
+    /// Terminal title mirroring the run pill, e.g. `codex ▸ Working (2m 05s)`.
+    /// Returns `None` when title updates are disabled.
+    pub(crate) fn terminal_title(&self, now: Instant) -> Option<String> {
+        if !self.terminal_title_enabled {
+            return None;
+        }
+        if self.run_timer.is_none() {
+            return Some(TERMINAL_TITLE_PREFIX.to_string());
+        }
+        let snapshot = self.snapshot_for_render(now);
+        let Some(run_state) = snapshot.run_state else {
+            return Some(TERMINAL_TITLE_PREFIX.to_string());
+        };
+        let label = match run_state.label.trim() {
+            "" => DEFAULT_STATUS_MESSAGE,
+            label => label,
+        };
+        let elapsed = run_state
+            .timer
+            .as_ref()
+            .map(|timer| timer.elapsed_at(now))
+            .unwrap_or_default();
+        Some(match snapshot.timer_display.run_timer_text(elapsed) {
+            Some(elapsed) => format!("{TERMINAL_TITLE_PREFIX} ▸ {label} ({elapsed})"),
+            None => format!("{TERMINAL_TITLE_PREFIX} ▸ {label}"),
+        })
+    }
+
+    /// Hold redraws until the matching [`Self::commit`], so a change that
+    /// touches several fields schedules a single frame. Calls nest.
+    pub(crate) fn begin_update(&mut self) {
+        self.update_depth += 1;
+    }
+
+    /// End a [`Self::begin_update`]; the outermost commit schedules one frame
+    /// if anything changed in between.
+    pub(crate) fn commit(&mut self) {
+        self.update_depth = (self.update_depth - 1).max(0);
+        if self.update_depth == 0 && self.update_dirty.replace(false) {
+            self.request_redraw();
+        }
     }
 
+    /// Follow the terminal's focus reports. Regaining focus draws at once so
+    /// the animations pick up where the clock is now.
+    pub(crate) fn set_terminal_focused(&mut self, focused: bool) {
+        if std::mem::replace(&mut self.terminal_focused, focused) != focused && focused {
+            self.request_redraw();
+        }
+    }
+
+    /// Ask for a frame; the frame scheduler merges requests made before it
+    /// draws.
     fn request_redraw(&self) {
-        self.frame_requester.schedule_frame();
+        if self.update_depth > 0 {
+            self.update_dirty.set(true);
+        } else {
+            self.frame_requester.schedule_frame();
+        }
     }
 }
 
+#[derive(Debug)]
+struct ExecFailure {
+    exit_code: i32,
+    command: String,
+    failed_at: Instant,
+}
+
+/// Stats of the last finished task, recapped in the run pill for a moment.
+#[derive(Debug)]
+struct LastRun {
+    summary: StatusLineRunSummary,
+    finished_at: Instant,
+}
+
 #[derive(Debug)]
 struct RunTimer {
     elapsed_running: Duration,
@@ -331,6 +1309,63 @@ impl RunTimer {
     }
 }
 
+/// A `*_secs` setting as a duration; `None` for `0`, which turns the
+/// feature off.
+fn enabled_secs(secs: i64) -> Option<Duration> {
+    u64::try_from(secs)
+        .ok()
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+}
+
+/// Hash of everything a renderer sees. Outside the run pill only the context
+/// warning pulse and the age of stale 88code numbers change with time, so
+/// the pulse phase and the age text are folded into the key.
+fn render_key(snapshot: &StatusLineSnapshot, now: Instant) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    snapshot.hash(&mut hasher);
+    if let Some(age) = snapshot
+        .environment
+        .code88
+        .as_ref()
+        .and_then(|info| stale_88code_age(info, now))
+    {
+        stale_age(age).0.hash(&mut hasher);
+    }
+    if let Some(since) = snapshot
+        .context
+        .as_ref()
+        .and_then(|context| context.low_since)
+    {
+        let phase =
+            now.saturating_duration_since(since).as_millis() / CONTEXT_WARNING_PULSE.as_millis();
+        phase.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn cached_render(
+    cache: &RefCell<Option<CachedLine>>,
+    key: u64,
+    width: u16,
+    render: impl FnOnce() -> Line<'static>,
+) -> Line<'static> {
+    let mut cache = cache.borrow_mut();
+    if let Some(cached) = cache.as_ref()
+        && cached.key == key
+        && cached.width == width
+    {
+        return cached.line.clone();
+    }
+    let line = render();
+    *cache = Some(CachedLine {
+        key,
+        width,
+        line: line.clone(),
+    });
+    line
+}
+
 fn reasoning_detail(effort: Option<ReasoningEffort>) -> Option<String> {
     match effort {
         Some(ReasoningEffort::High) => Some("high".to_string()),
@@ -339,52 +1374,109 @@ fn reasoning_detail(effort: Option<ReasoningEffort>) -> Option<String> {
     }
 }
 
+/// Sandbox and approval labels for the policy segment. Full access is always
+/// high risk; never asking for approval raises the sandbox's risk one level.
+pub(crate) fn policy_snapshot(
+    approval: AskForApproval,
+    sandbox: &SandboxPolicy,
+) -> StatusLinePolicySnapshot {
+    let (label, risk) = match sandbox {
+        SandboxPolicy::ReadOnly => ("read-only", PolicyRisk::Low),
+        SandboxPolicy::WorkspaceWrite { .. } => ("workspace-write", PolicyRisk::Medium),
+        SandboxPolicy::DangerFullAccess => ("full-access", PolicyRisk::High),
+    };
+    let risk = match (approval, risk) {
+        (AskForApproval::Never, PolicyRisk::Low) => PolicyRisk::Medium,
+        (AskForApproval::Never, _) => PolicyRisk::High,
+        (_, risk) => risk,
+    };
+    StatusLinePolicySnapshot {
+        sandbox: label.to_string(),
+        approval: approval.to_string(),
+        risk,
+    }
+}
+
 fn token_snapshot_from_info(
     info: &TokenUsageInfo,
     context_window: Option<i64>,
+    baseline_tokens: i64,
 ) -> (StatusLineTokenSnapshot, Option<StatusLineContextSnapshot>) {
     let total = info.total_token_usage.clone();
     let last = info.last_token_usage.clone();
 
     let token_snapshot = StatusLineTokenSnapshot {
-        total: TokenCountSnapshot {
-            total_tokens: total.total_tokens,
-            input_tokens: total.input_tokens,
-            cached_input_tokens: total.cached_input_tokens,
-            output_tokens: total.output_tokens,
-            reasoning_output_tokens: total.reasoning_output_tokens,
-        },
-        last: Some(TokenCountSnapshot {
-            total_tokens: last.total_tokens,
-            input_tokens: last.input_tokens,
-            cached_input_tokens: last.cached_input_tokens,
-            output_tokens: last.output_tokens,
-            reasoning_output_tokens: last.reasoning_output_tokens,
-        }),
+        total: token_count(&total),
+        last: Some(token_count(&last)),
+        output_tokens_per_sec: None,
+        last_turn_duration: None,
     };
 
     let context_snapshot = context_window.map(|window| {
-        let percent = context_percent_remaining(&last, window);
+        let percent = context_percent_remaining(&last, window, baseline_tokens);
         StatusLineContextSnapshot {
             percent_remaining: percent,
             tokens_in_context: last.tokens_in_context_window(),
             window,
+            low_since: None,
+            suggest_compact: false,
         }
     });
 
     (token_snapshot, context_snapshot)
 }
 
-fn context_percent_remaining(last: &TokenUsage, context_window: i64) -> u8 {
-    const BASELINE_TOKENS: i64 = 12_000;
-    if context_window <= BASELINE_TOKENS {
-        return 0;
+fn token_count(usage: &TokenUsage) -> TokenCountSnapshot {
+    TokenCountSnapshot {
+        total_tokens: usage.total_tokens,
+        input_tokens: usage.input_tokens,
+        cached_input_tokens: usage.cached_input_tokens,
+        output_tokens: usage.output_tokens,
+        reasoning_output_tokens: usage.reasoning_output_tokens,
     }
-    let effective_window = context_window - BASELINE_TOKENS;
-    if effective_window <= 0 {
+}
+
+/// `total - previous`, field by field, never below zero.
+fn token_usage_delta(total: &TokenUsage, previous: &TokenUsage) -> TokenUsage {
+    let delta = |now: i64, before: i64| now.saturating_sub(before).max(0);
+    TokenUsage {
+        input_tokens: delta(total.input_tokens, previous.input_tokens),
+        cached_input_tokens: delta(total.cached_input_tokens, previous.cached_input_tokens),
+        output_tokens: delta(total.output_tokens, previous.output_tokens),
+        reasoning_output_tokens: delta(
+            total.reasoning_output_tokens,
+            previous.reasoning_output_tokens,
+        ),
+        total_tokens: delta(total.total_tokens, previous.total_tokens),
+    }
+}
+
+/// Tokens the session spent on the active model, once any were attributed.
+fn active_model_tokens<'a>(
+    model_tokens: &'a [(String, TokenUsage)],
+    model: Option<&StatusLineModelSnapshot>,
+) -> Option<&'a TokenUsage> {
+    let label = &model?.label;
+    model_tokens
+        .iter()
+        .find(|(model, _)| model == label)
+        .map(|(_, usage)| usage)
+}
+
+/// Percentage of the context window left after subtracting the fixed
+/// baseline. Windows no larger than the baseline (small-context models) are
+/// measured against the raw window instead.
+fn context_percent_remaining(last: &TokenUsage, context_window: i64, baseline_tokens: i64) -> u8 {
+    if context_window <= 0 {
         return 0;
     }
-    let used = (last.tokens_in_context_window() - BASELINE_TOKENS).max(0);
+    let baseline = if context_window > baseline_tokens {
+        baseline_tokens.max(0)
+    } else {
+        0
+    };
+    let effective_window = context_window - baseline;
+    let used = (last.tokens_in_context_window() - baseline).max(0);
     let remaining = (effective_window - used).max(0);
     let percent = (remaining * 100) / effective_window;
     percent.clamp(0, 100) as u8
@@ -393,7 +1485,18 @@ fn context_percent_remaining(last: &TokenUsage, context_window: i64) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::statusline::CustomStatusLineRenderer;
+    use crate::statusline::run_summary_text;
+    use codex_core::config::ConfigOverrides;
+    use codex_core::config::ConfigToml;
+    use codex_core::config::types::StatusLineColorMode;
     use codex_core::protocol::TokenUsage;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use tokio::sync::mpsc::UnboundedReceiver;
 
     #[test]
     fn context_snapshot_matches_status_values() {
@@ -416,7 +1519,8 @@ mod tests {
             model_context_window: Some(window),
         };
 
-        let (_, context_snapshot) = token_snapshot_from_info(&info, info.model_context_window);
+        let (_, context_snapshot) =
+            token_snapshot_from_info(&info, info.model_context_window, 12_000);
         let context = context_snapshot.expect("context snapshot");
 
         assert_eq!(context.window, window);
@@ -424,6 +1528,890 @@ mod tests {
         assert_eq!(context.percent_remaining, 66);
     }
 
+    #[test]
+    fn context_percent_respects_configured_baseline() {
+        let last = TokenUsage {
+            input_tokens: 4_000,
+            total_tokens: 4_000,
+            ..TokenUsage::default()
+        };
+
+        assert_eq!(context_percent_remaining(&last, 112_000, 12_000), 100);
+        assert_eq!(context_percent_remaining(&last, 100_000, 0), 96);
+        // Small windows ignore a baseline they cannot accommodate.
+        assert_eq!(context_percent_remaining(&last, 8_000, 12_000), 50);
+        assert_eq!(context_percent_remaining(&last, 0, 12_000), 0);
+    }
+
+    #[test]
+    fn terminal_progress_tracks_task_lifecycle() {
+        let mut config = test_config();
+        config.tui_terminal_progress = TerminalProgressMode::Context;
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        assert_eq!(state.terminal_progress(), TerminalProgress::Clear);
+
+        state.start_task("Working");
+        assert_eq!(state.terminal_progress(), TerminalProgress::Indeterminate);
+
+        state.snapshot.context = Some(StatusLineContextSnapshot {
+            percent_remaining: 70,
+            ..StatusLineContextSnapshot::default()
+        });
+        assert_eq!(state.terminal_progress(), TerminalProgress::Percent(30));
+
+        state.complete_task();
+        assert_eq!(state.terminal_progress(), TerminalProgress::Clear);
+    }
+
+    #[test]
+    fn terminal_title_mirrors_run_state() {
+        let mut config = test_config();
+        config.tui_terminal_title = true;
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        assert_eq!(
+            state.terminal_title(Instant::now()).as_deref(),
+            Some("codex")
+        );
+
+        state.start_task("Applying patch");
+        let later = Instant::now() + Duration::from_secs(125);
+        assert_eq!(
+            state.terminal_title(later).as_deref(),
+            Some("codex ▸ Applying patch (2m 05s)")
+        );
+
+        state.complete_task();
+        assert_eq!(state.terminal_title(later).as_deref(), Some("codex"));
+
+        config.tui_terminal_title = false;
+        let state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        assert_eq!(state.terminal_title(Instant::now()), None);
+    }
+
+    #[test]
+    fn complete_task_reports_finished_run() {
+        let config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        assert_eq!(state.complete_task(), None);
+
+        state.start_task("Applying patch");
+        let completed = state.complete_task().expect("run should be reported");
+        assert_eq!(completed.label, "Applying patch");
+        assert_eq!(state.complete_task(), None);
+    }
+
+    #[test]
+    fn token_totals_follow_the_active_model() {
+        let config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        let usage = |total_tokens| TokenUsageInfo {
+            total_token_usage: TokenUsage {
+                total_tokens,
+                ..TokenUsage::default()
+            },
+            last_token_usage: TokenUsage::default(),
+            model_context_window: None,
+        };
+        let bar_total = |state: &StatusLineState| {
+            state
+                .snapshot_for_render(Instant::now())
+                .tokens
+                .map(|tokens| tokens.total.total_tokens)
+        };
+
+        state.update_model("gpt-5-codex", None);
+        state.update_tokens(Some(usage(1_000)));
+        state.update_tokens(Some(usage(4_000)));
+        assert_eq!(bar_total(&state), Some(4_000));
+
+        state.update_model("o3", None);
+        assert_eq!(bar_total(&state), Some(0));
+        state.update_tokens(Some(usage(4_500)));
+        assert_eq!(bar_total(&state), Some(500));
+
+        state.update_model("gpt-5-codex", None);
+        assert_eq!(bar_total(&state), Some(4_000));
+        let split: Vec<(&str, i64)> = state
+            .model_token_usage()
+            .iter()
+            .map(|(model, usage)| (model.as_str(), usage.total_tokens))
+            .collect();
+        assert_eq!(split, vec![("gpt-5-codex", 4_000), ("o3", 500)]);
+
+        state.update_tokens(None);
+        assert!(state.model_token_usage().is_empty());
+    }
+
+    #[test]
+    fn finished_task_is_recapped_until_the_summary_expires() {
+        let config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        let usage = |total_tokens| TokenUsageInfo {
+            total_token_usage: TokenUsage {
+                total_tokens,
+                ..TokenUsage::default()
+            },
+            last_token_usage: TokenUsage::default(),
+            model_context_window: None,
+        };
+        state.update_tokens(Some(usage(1_000)));
+        state.start_task("Applying patch");
+        state.update_tokens(Some(usage(4_100)));
+        state.complete_task();
+
+        let now = Instant::now();
+        let summary = |now| {
+            state
+                .snapshot_for_render(now)
+                .run_state
+                .and_then(|run_state| run_state.summary)
+        };
+        assert_eq!(
+            summary(now),
+            Some(StatusLineRunSummary {
+                label: "Applying patch".to_string(),
+                elapsed_secs: 0,
+                tokens: 3_100,
+                interrupted: false,
+            })
+        );
+        assert_eq!(summary(now + Duration::from_secs(5)), None);
+    }
+
+    #[test]
+    fn interrupted_task_is_recapped_as_interrupted() {
+        let config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.start_task("Applying patch");
+        state.interrupt_task();
+
+        let summary = state
+            .snapshot_for_render(Instant::now())
+            .run_state
+            .and_then(|run_state| run_state.summary)
+            .expect("interrupted run should be recapped");
+        assert_eq!(
+            summary,
+            StatusLineRunSummary {
+                label: "Applying patch".to_string(),
+                elapsed_secs: 0,
+                tokens: 0,
+                interrupted: true,
+            }
+        );
+        assert_eq!(
+            run_summary_text(&summary, &StatusLineSnapshot::default()),
+            "⏹ Applying patch interrupted after 0s"
+        );
+    }
+
+    #[test]
+    fn failed_commands_flash_then_leave_a_badge_until_the_next_task() {
+        let config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        let failure = |state: &StatusLineState, now| {
+            state
+                .snapshot_for_render(now)
+                .run_state
+                .and_then(|run_state| run_state.exec_failure)
+        };
+        state.start_task("Working");
+        state.record_exec_exit("cargo fmt --check", 0);
+        assert_eq!(failure(&state, Instant::now()), None);
+
+        state.record_exec_exit("cargo test --all-features --workspace", 101);
+        let now = Instant::now();
+        assert_eq!(
+            failure(&state, now),
+            Some(StatusLineExecFailure {
+                exit_code: 101,
+                command: "cargo test --all-featur…".to_string(),
+                recent: true,
+            })
+        );
+        state.complete_task();
+        assert_eq!(
+            failure(&state, now + EXEC_FAILURE_FLASH).map(|failure| failure.recent),
+            Some(false)
+        );
+
+        state.start_task("Working");
+        assert_eq!(failure(&state, now), None);
+    }
+
+    #[test]
+    fn paused_task_keeps_its_timer_until_resumed() {
+        let config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.start_task("Working");
+        state.pause_task();
+        let run_state = |state: &StatusLineState| {
+            state
+                .snapshot_for_render(Instant::now())
+                .run_state
+                .expect("run state")
+        };
+        let paused = run_state(&state);
+        assert!(paused.paused);
+        assert!(paused.timer.is_some_and(|timer| timer.is_paused));
+        assert!(state.task_in_progress());
+
+        // Late tool events do not restart the timer while paused.
+        state.resume_timer();
+        assert!(run_state(&state).timer.is_some_and(|timer| timer.is_paused));
+
+        state.start_task("Working");
+        let resumed = run_state(&state);
+        assert!(!resumed.paused);
+        assert!(resumed.timer.is_some_and(|timer| !timer.is_paused));
+    }
+
+    #[test]
+    fn templated_run_labels_truncate_or_scroll() {
+        let mut config = test_config();
+        config.tui_statusline.run_label_max_width = 8;
+        config.tui_statusline.exec_label = "$ {command}".to_string();
+        let command = vec!["cargo".to_string(), "clippy".to_string()];
+        let label_at = |state: &StatusLineState, now: Instant| {
+            state
+                .snapshot_for_render(now)
+                .run_state
+                .map(|run_state| run_state.label)
+        };
+
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.update_run_label(state.run_labels().exec(&command), RunCategory::Exec);
+        let later = Instant::now() + MARQUEE_STEP * 3;
+        assert_eq!(label_at(&state, later), Some("$ cargo...".to_string()));
+
+        config.tui_statusline.run_label_marquee = true;
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.update_run_label(state.run_labels().exec(&command), RunCategory::Exec);
+        let changed_at = state
+            .snapshot
+            .run_state
+            .as_ref()
+            .expect("run state")
+            .status_changed_at;
+        assert_eq!(
+            label_at(&state, changed_at + MARQUEE_STEP * 3),
+            Some("$ go clipp".to_string())
+        );
+
+        let category = |state: &StatusLineState| {
+            state
+                .snapshot
+                .run_state
+                .as_ref()
+                .map(|run_state| run_state.category)
+        };
+        assert_eq!(category(&state), Some(RunCategory::Exec));
+
+        state.update_run_header("Working", RunCategory::Thinking);
+        assert_eq!(
+            label_at(&state, changed_at + MARQUEE_STEP * 3),
+            Some("Working".to_string())
+        );
+        assert_eq!(category(&state), Some(RunCategory::Thinking));
+    }
+
+    #[test]
+    fn queue_previews_cycle_when_enabled() {
+        let queue = vec![
+            "first".to_string(),
+            "second".to_string(),
+            "third".to_string(),
+        ];
+        let index_at = |state: &StatusLineState, now: Instant| {
+            state
+                .snapshot_for_render(now)
+                .run_state
+                .and_then(|run_state| run_state.queue_preview_index)
+        };
+
+        let mut config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.set_queued_messages(queue.clone());
+        assert_eq!(index_at(&state, Instant::now()), None);
+
+        config.tui_statusline.queue_preview_cycle_secs = 3;
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.set_queued_messages(queue);
+        let changed_at = state.queue_changed_at;
+        assert_eq!(index_at(&state, changed_at), Some(0));
+        assert_eq!(
+            index_at(&state, changed_at + Duration::from_secs(4)),
+            Some(1)
+        );
+        assert_eq!(
+            index_at(&state, changed_at + Duration::from_secs(9)),
+            Some(0)
+        );
+
+        state.set_queued_messages(vec!["only".to_string()]);
+        assert_eq!(index_at(&state, Instant::now()), None);
+    }
+
+    #[test]
+    fn disabled_segments_are_left_out_of_snapshots() {
+        let config = test_config();
+        let mut settings = config.tui_statusline.clone();
+        settings.segments.aws_profile = false;
+        settings.segments.git = false;
+        let mut state = StatusLineState::with_settings(
+            &config,
+            &settings,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.set_aws_profile(Some("prod".to_string()));
+        state.set_hostname(Some("build-host".to_string()));
+        state.set_git_info(Some(StatusLineGitSnapshot {
+            branch: Some("main".to_string()),
+            dirty: false,
+            ahead: None,
+            behind: None,
+            base: None,
+            pull_request: None,
+            checkout: None,
+            detached: None,
+        }));
+
+        let snapshot = state.snapshot_for_render(Instant::now());
+        assert_eq!(snapshot.environment.aws_profile, None);
+        assert_eq!(snapshot.environment.hostname.as_deref(), Some("build-host"));
+        assert!(snapshot.git.is_none());
+    }
+
+    #[test]
+    fn session_id_is_kept_whole_and_shown_by_its_tail() {
+        let config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        let id = "0199a213-81c0-7800-8aa1-bbab2a035a53";
+        state.set_session_id(Some(id.to_string()));
+        assert_eq!(state.session_id(), Some(id));
+        assert_eq!(
+            state.snapshot_for_render(Instant::now()).session.as_deref(),
+            Some("2a035a53")
+        );
+        state.set_session_id(Some("short".to_string()));
+        assert_eq!(
+            state.snapshot_for_render(Instant::now()).session.as_deref(),
+            Some("short")
+        );
+
+        let mut settings = config.tui_statusline.clone();
+        settings.segments.session = false;
+        let mut state = StatusLineState::with_settings(
+            &config,
+            &settings,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.set_session_id(Some(id.to_string()));
+        assert_eq!(state.session_id(), Some(id));
+        assert_eq!(state.snapshot_for_render(Instant::now()).session, None);
+    }
+
+    #[test]
+    fn registered_devspaces_take_icons_in_name_order() {
+        let mut config = test_config();
+        config.tui_devspaces = toml::from_str(
+            r##"
+            earth = { color = "blue", description = "payments" }
+            mars = { icon = "M" }
+            venus = {}
+            "##,
+        )
+        .expect("devspaces");
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        let devspace = |state: &mut StatusLineState, name: &str| {
+            state.set_devspace(Some(name.to_string()));
+            state
+                .snapshot_for_render(Instant::now())
+                .environment
+                .devspace
+                .expect("devspace")
+        };
+
+        let earth = devspace(&mut state, "earth");
+        assert_eq!(earth.icon.as_deref(), Some(registered_devspace_icon(0)));
+        assert_eq!(earth.color.as_deref(), Some("blue"));
+        assert_eq!(earth.description.as_deref(), Some("payments"));
+        assert_eq!(devspace(&mut state, "mars").icon.as_deref(), Some("M"));
+        assert_eq!(
+            devspace(&mut state, "venus").icon.as_deref(),
+            Some(registered_devspace_icon(2))
+        );
+        let unknown = devspace(&mut state, "pluto");
+        assert_eq!(unknown.icon, None);
+        assert_eq!(unknown.color, None);
+    }
+
+    #[test]
+    fn policy_risk_follows_sandbox_and_approval() {
+        let workspace_write = SandboxPolicy::new_workspace_write_policy();
+        let cases = [
+            (
+                AskForApproval::OnRequest,
+                SandboxPolicy::ReadOnly,
+                PolicyRisk::Low,
+            ),
+            (
+                AskForApproval::Never,
+                SandboxPolicy::ReadOnly,
+                PolicyRisk::Medium,
+            ),
+            (
+                AskForApproval::OnRequest,
+                workspace_write.clone(),
+                PolicyRisk::Medium,
+            ),
+            (AskForApproval::Never, workspace_write, PolicyRisk::High),
+            (
+                AskForApproval::UnlessTrusted,
+                SandboxPolicy::DangerFullAccess,
+                PolicyRisk::High,
+            ),
+        ];
+        for (approval, sandbox, risk) in cases {
+            assert_eq!(
+                policy_snapshot(approval, &sandbox).risk,
+                risk,
+                "{approval} / {sandbox}"
+            );
+        }
+        let snapshot = policy_snapshot(AskForApproval::UnlessTrusted, &SandboxPolicy::ReadOnly);
+        assert_eq!(snapshot.sandbox, "read-only");
+        assert_eq!(snapshot.approval, "untrusted");
+    }
+
+    #[test]
+    fn diff_stats_accumulate_when_enabled() {
+        let config = test_config();
+        let mut settings = config.tui_statusline.clone();
+        let mut state = StatusLineState::with_settings(
+            &config,
+            &settings,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.add_diff_stats(12, 3);
+        assert!(
+            state
+                .snapshot_for_render(Instant::now())
+                .diff_stats
+                .is_none()
+        );
+
+        settings.segments.diff_stats = true;
+        let mut state = StatusLineState::with_settings(
+            &config,
+            &settings,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.add_diff_stats(12, 3);
+        state.add_diff_stats(300, 84);
+        let stats = state
+            .snapshot_for_render(Instant::now())
+            .diff_stats
+            .expect("diff stats");
+        assert_eq!((stats.added, stats.removed), (312, 87));
+    }
+
+    #[test]
+    fn token_updates_track_rate_and_last_turn_duration() {
+        let config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        let usage = |output_tokens| TokenUsageInfo {
+            total_token_usage: TokenUsage {
+                output_tokens,
+                ..TokenUsage::default()
+            },
+            last_token_usage: TokenUsage::default(),
+            model_context_window: None,
+        };
+        state.update_tokens(Some(usage(1_000)));
+
+        state.start_task("Working");
+        state.run_timer = Some(RunTimer::new(Instant::now() - Duration::from_secs(10)));
+        state.update_tokens(Some(usage(1_500)));
+        let rate = state
+            .snapshot
+            .tokens
+            .as_ref()
+            .and_then(|tokens| tokens.output_tokens_per_sec)
+            .expect("rate while running");
+        assert!((rate - 50.0).abs() < 1.0, "unexpected rate {rate}");
+
+        let completed = state.complete_task().expect("completed run");
+        let tokens = state.snapshot.tokens.as_ref().expect("tokens");
+        assert_eq!(tokens.last_turn_duration, Some(completed.elapsed));
+        assert!(completed.elapsed >= Duration::from_secs(10));
+    }
+
+    #[test]
+    fn low_context_warning_follows_threshold() {
+        let mut config = test_config();
+        config.model_context_window = Some(100_000);
+        config.tui_statusline.context_warning_threshold = 20;
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        let usage = |input_tokens| TokenUsageInfo {
+            total_token_usage: TokenUsage::default(),
+            last_token_usage: TokenUsage {
+                input_tokens,
+                total_tokens: input_tokens,
+                ..TokenUsage::default()
+            },
+            model_context_window: None,
+        };
+        let low_since =
+            |state: &StatusLineState| state.snapshot.context.as_ref().and_then(|c| c.low_since);
+
+        state.update_tokens(Some(usage(20_000)));
+        assert_eq!(low_since(&state), None);
+
+        state.update_tokens(Some(usage(95_000)));
+        let since = low_since(&state).expect("warning should start");
+
+        state.update_tokens(Some(usage(96_000)));
+        assert_eq!(low_since(&state), Some(since));
+
+        state.update_tokens(Some(usage(30_000)));
+        assert_eq!(low_since(&state), None);
+    }
+
+    #[test]
+    fn compact_hint_follows_usage_threshold() {
+        let mut config = test_config();
+        config.model_context_window = Some(100_000);
+        config.tui_statusline.context_baseline_tokens = 0;
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        let usage = |input_tokens| TokenUsageInfo {
+            total_token_usage: TokenUsage::default(),
+            last_token_usage: TokenUsage {
+                input_tokens,
+                total_tokens: input_tokens,
+                ..TokenUsage::default()
+            },
+            model_context_window: None,
+        };
+
+        state.update_tokens(Some(usage(80_000)));
+        assert!(!state.compact_suggested());
+        state.update_tokens(Some(usage(86_000)));
+        assert!(state.compact_suggested());
+
+        config.tui_statusline.compact_hint_threshold = 0;
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.update_tokens(Some(usage(99_000)));
+        assert!(!state.compact_suggested());
+    }
+
+    #[test]
+    fn monochrome_mode_strips_colors_from_every_line() {
+        let mut config = test_config();
+        config.tui_statusline.color = StatusLineColorMode::None;
+        let state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        for line in [state.render_line(120), state.render_run_pill(120)] {
+            assert!(!line.spans.is_empty());
+            assert!(
+                line.spans
+                    .iter()
+                    .all(|span| span.style.fg.is_none() && span.style.bg.is_none()),
+                "{line:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn line_without_pill_keeps_pending_approvals() {
+        let config = test_config();
+        let mut state = StatusLineState::with_renderer(
+            &config,
+            FrameRequester::test_dummy(),
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.set_pending_approvals(2);
+        state.set_queued_messages(vec!["next".to_string()]);
+        let text: String = state
+            .render_line_without_pill(160)
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert!(text.contains("⚠ 2 approvals"), "{text}");
+        assert!(text.contains("1 queued"), "{text}");
+    }
+
+    #[derive(Debug, Default)]
+    struct CountingRenderer {
+        renders: Arc<AtomicUsize>,
+    }
+
+    impl StatusLineRenderer for CountingRenderer {
+        fn render(&self, _: &StatusLineSnapshot, width: u16, _: Instant) -> Line<'static> {
+            self.renders.fetch_add(1, Ordering::Relaxed);
+            Line::from(format!("w{width}"))
+        }
+
+        fn render_run_pill(&self, _: &StatusLineSnapshot, width: u16, _: Instant) -> Line<'static> {
+            self.renders.fetch_add(1, Ordering::Relaxed);
+            Line::from(format!("w{width}"))
+        }
+    }
+
+    #[test]
+    fn render_cache_skips_unchanged_frames() {
+        let renders = Arc::new(AtomicUsize::new(0));
+        let mut state = StatusLineState::with_renderer(
+            &test_config(),
+            FrameRequester::test_dummy(),
+            Box::new(CountingRenderer {
+                renders: Arc::clone(&renders),
+            }),
+        );
+
+        state.render_line(80);
+        state.render_line(80);
+        assert_eq!(renders.load(Ordering::Relaxed), 1);
+
+        state.render_line(60);
+        assert_eq!(renders.load(Ordering::Relaxed), 2);
+
+        state.set_hostname(Some("devbox".to_string()));
+        state.render_line(60);
+        assert_eq!(renders.load(Ordering::Relaxed), 3);
+
+        state.render_run_pill(60);
+        state.render_run_pill(60);
+        assert_eq!(renders.load(Ordering::Relaxed), 4);
+    }
+
+    #[derive(Debug, Default)]
+    struct PanickingRenderer {
+        renders: Arc<AtomicUsize>,
+        guarded: Arc<AtomicBool>,
+    }
+
+    impl StatusLineRenderer for PanickingRenderer {
+        fn render(&self, _: &StatusLineSnapshot, _: u16, _: Instant) -> Line<'static> {
+            self.renders.fetch_add(1, Ordering::Relaxed);
+            self.guarded.store(in_guarded_render(), Ordering::Relaxed);
+            panic!("broken skin");
+        }
+
+        fn render_run_pill(&self, _: &StatusLineSnapshot, _: u16, _: Instant) -> Line<'static> {
+            self.renders.fetch_add(1, Ordering::Relaxed);
+            panic!("broken skin");
+        }
+    }
+
+    #[test]
+    fn panicking_renderer_falls_back_to_powerline() {
+        let renders = Arc::new(AtomicUsize::new(0));
+        let guarded = Arc::new(AtomicBool::new(false));
+        let mut state = StatusLineState::with_renderer(
+            &test_config(),
+            FrameRequester::test_dummy(),
+            Box::new(PanickingRenderer {
+                renders: Arc::clone(&renders),
+                guarded: Arc::clone(&guarded),
+            }),
+        );
+        state.set_hostname(Some("devbox".to_string()));
+
+        let text = |line: Line<'static>| -> String {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+        assert!(text(state.render_line(120)).contains("devbox"));
+        assert!(
+            guarded.load(Ordering::Relaxed),
+            "the panic hook can tell the panic is caught"
+        );
+        assert!(!in_guarded_render());
+        assert!(state.take_renderer_panic());
+        assert!(!state.take_renderer_panic(), "reported once");
+
+        state.render_run_pill(120);
+        state.set_hostname(Some("buildbox".to_string()));
+        assert!(text(state.render_line(120)).contains("buildbox"));
+        assert_eq!(
+            renders.load(Ordering::Relaxed),
+            1,
+            "broken skin is not retried"
+        );
+        assert!(!state.take_renderer_panic());
+
+        state.set_renderer(Box::new(CountingRenderer::default()));
+        assert_eq!(text(state.render_line(80)), "w80");
+    }
+
+    #[test]
+    fn running_run_pill_is_never_cached() {
+        let renders = Arc::new(AtomicUsize::new(0));
+        let mut state = StatusLineState::with_renderer(
+            &test_config(),
+            FrameRequester::test_dummy(),
+            Box::new(CountingRenderer {
+                renders: Arc::clone(&renders),
+            }),
+        );
+
+        state.start_task("Working");
+        state.render_run_pill(60);
+        state.render_run_pill(60);
+        assert_eq!(renders.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn changes_inside_an_update_share_one_frame() {
+        let (frame_requester, mut frames) = FrameRequester::test_recorder();
+        let mut state = StatusLineState::with_renderer(
+            &test_config(),
+            frame_requester,
+            Box::new(CustomStatusLineRenderer),
+        );
+        let mut scheduled = || std::iter::from_fn(|| frames.try_recv().ok()).count();
+
+        state.set_hostname(Some("devbox".to_string()));
+        assert_eq!(scheduled(), 1);
+
+        state.begin_update();
+        state.set_aws_profile(Some("prod".to_string()));
+        state.set_custom_segment(Some("build 42%".to_string()));
+        state.begin_update();
+        state.set_aws_profile(None);
+        state.commit();
+        assert_eq!(scheduled(), 0);
+        state.commit();
+        assert_eq!(scheduled(), 1);
+
+        state.begin_update();
+        state.commit();
+        assert_eq!(scheduled(), 0);
+    }
+
+    #[test]
+    fn unfocused_terminal_drops_to_one_frame_a_second() {
+        let (frame_requester, mut frames) = FrameRequester::test_recorder();
+        let mut state = StatusLineState::with_renderer(
+            &test_config(),
+            frame_requester,
+            Box::new(CustomStatusLineRenderer),
+        );
+        state.start_task("Working");
+        let next_frame_in = |state: &StatusLineState, frames: &mut UnboundedReceiver<Instant>| {
+            while frames.try_recv().is_ok() {}
+            let now = Instant::now();
+            state.snapshot_for_render(now);
+            std::iter::from_fn(|| frames.try_recv().ok())
+                .map(|at| at.saturating_duration_since(now))
+                .min()
+                .expect("frame scheduled")
+        };
+
+        assert!(next_frame_in(&state, &mut frames) < ACCESSIBLE_TIMER_FRAME_STEP);
+        state.set_terminal_focused(false);
+        assert!(next_frame_in(&state, &mut frames) >= ACCESSIBLE_TIMER_FRAME_STEP);
+        state.set_terminal_focused(true);
+        assert!(frames.try_recv().is_ok(), "regaining focus redraws");
+        assert!(next_frame_in(&state, &mut frames) < ACCESSIBLE_TIMER_FRAME_STEP);
+    }
+
+    fn test_config() -> Config {
+        Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            std::env::temp_dir(),
+        )
+        .expect("config")
+    }
+
     #[test]
     fn run_timer_snapshot_advances_in_real_seconds() {
         let start = Instant::now();
@@ -0,0 +1,382 @@
+//! Run state transitions of a session, for postmortems ("what was it doing
+//! for those 11 minutes?").
+//!
+//! With `tui.statusline.session_timeline` every task start, label change,
+//! pause, failed command and completion is appended to
+//! `timeline-<session id>.jsonl` next to the session's rollout file;
+//! `codex sessions timeline <id>` prints it back.
+
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::Utc;
+use codex_core::find_conversation_path_by_id_str;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufWriter;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::status_indicator_widget::fmt_elapsed_compact;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TimelineEntry {
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: TimelineEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum TimelineEvent {
+    /// A task started, or a paused one was picked back up.
+    Start {
+        label: String,
+    },
+    /// The run label changed while the task ran.
+    Label {
+        label: String,
+    },
+    /// The user paused the task.
+    Pause,
+    /// A command the agent ran exited non-zero.
+    ExecFailed {
+        command: String,
+        exit_code: i32,
+    },
+    Complete {
+        label: String,
+        elapsed_secs: i64,
+    },
+}
+
+/// Appends the transitions of the current session, once its id and rollout
+/// file are known.
+#[derive(Debug, Default)]
+pub(crate) struct SessionTimeline {
+    writer: Option<TimelineWriter>,
+    /// Last label written, so a header set again unchanged is not repeated.
+    last_label: Option<String>,
+}
+
+impl SessionTimeline {
+    /// Append to the timeline of `session_id`, next to its `rollout_path`,
+    /// from now on.
+    pub(crate) fn persist_to(&mut self, rollout_path: &Path, session_id: &str) {
+        let Some(path) = timeline_path(rollout_path, session_id) else {
+            tracing::warn!("not recording a timeline for invalid session id `{session_id}`");
+            return;
+        };
+        if self
+            .writer
+            .as_ref()
+            .is_some_and(|writer| writer.path == path)
+        {
+            return;
+        }
+        let Ok(handle) = Handle::try_current() else {
+            return;
+        };
+        self.writer = Some(TimelineWriter::spawn(&handle, path));
+        self.last_label = None;
+    }
+
+    pub(crate) fn record(&mut self, event: TimelineEvent) {
+        self.record_at(Utc::now(), event);
+    }
+
+    fn record_at(&mut self, at: DateTime<Utc>, event: TimelineEvent) {
+        let Some(writer) = self.writer.as_ref() else {
+            return;
+        };
+        match &event {
+            TimelineEvent::Label { label } if self.last_label.as_ref() == Some(label) => return,
+            TimelineEvent::Start { label } | TimelineEvent::Label { label } => {
+                self.last_label = Some(label.clone());
+            }
+            TimelineEvent::Complete { .. } => self.last_label = None,
+            TimelineEvent::Pause | TimelineEvent::ExecFailed { .. } => {}
+        }
+        match serde_json::to_string(&TimelineEntry { at, event }) {
+            Ok(line) => writer.append(line),
+            Err(err) => tracing::warn!("failed to serialize timeline entry: {err}"),
+        }
+    }
+}
+
+/// Appends lines to the timeline file from a background task, so the UI
+/// thread never waits on the disk. Lines that arrive together are written
+/// and flushed together.
+#[derive(Debug)]
+struct TimelineWriter {
+    path: PathBuf,
+    lines: mpsc::UnboundedSender<String>,
+}
+
+impl TimelineWriter {
+    /// The task flushes what was sent and exits once the writer is dropped.
+    fn spawn(handle: &Handle, path: PathBuf) -> Self {
+        let (lines, mut rx) = mpsc::unbounded_channel::<String>();
+        let task_path = path.clone();
+        handle.spawn(async move {
+            let file = match tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&task_path)
+                .await
+            {
+                Ok(file) => file,
+                Err(err) => {
+                    tracing::warn!("failed to open {}: {err}", task_path.display());
+                    return;
+                }
+            };
+            let mut file = BufWriter::new(file);
+            while let Some(line) = rx.recv().await {
+                let mut batch = vec![line];
+                while let Ok(line) = rx.try_recv() {
+                    batch.push(line);
+                }
+                let mut result = Ok(());
+                for line in batch {
+                    result = file.write_all(format!("{line}\n").as_bytes()).await;
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                if let Err(err) = result.and(file.flush().await) {
+                    tracing::warn!("failed to write {}: {err}", task_path.display());
+                }
+            }
+        });
+        Self { path, lines }
+    }
+
+    fn append(&self, line: String) {
+        if self.lines.send(line).is_err() {
+            tracing::debug!("timeline writer for {} is gone", self.path.display());
+        }
+    }
+}
+
+/// `timeline-<id>.jsonl` in the directory of the session's rollout file;
+/// `None` unless `session_id` is a UUID, so it cannot leave that directory.
+fn timeline_path(rollout_path: &Path, session_id: &str) -> Option<PathBuf> {
+    let id = Uuid::parse_str(session_id).ok()?;
+    Some(rollout_path.with_file_name(format!("timeline-{id}.jsonl")))
+}
+
+/// `codex sessions timeline <id>`: print the recorded timeline of
+/// `session_id`, one transition per line, with how long each label lasted.
+pub async fn write_session_timeline(
+    writer: &mut impl Write,
+    codex_home: &Path,
+    session_id: &str,
+) -> anyhow::Result<()> {
+    if Uuid::parse_str(session_id).is_err() {
+        bail!("`{session_id}` is not a session id; use the id shown by /status or on exit");
+    }
+    let Some(rollout_path) = find_conversation_path_by_id_str(codex_home, session_id)
+        .await
+        .context("failed to look up the session")?
+    else {
+        bail!(
+            "no session {session_id} under {}",
+            codex_home.join("sessions").display()
+        );
+    };
+    let Some(path) = timeline_path(&rollout_path, session_id) else {
+        bail!("`{session_id}` is not a session id");
+    };
+    let contents = std::fs::read_to_string(&path).with_context(|| {
+        format!(
+            "no timeline for session {session_id} at {}; enable tui.statusline.session_timeline to record one",
+            path.display()
+        )
+    })?;
+    let entries: Vec<TimelineEntry> = contents
+        .lines()
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(err) => {
+                tracing::debug!("skipping timeline line: {err}");
+                None
+            }
+        })
+        .collect();
+    for line in format_timeline(&entries) {
+        writeln!(writer, "{line}").context("failed to write session timeline")?;
+    }
+    Ok(())
+}
+
+/// One line per entry in local time, with a date line whenever the day
+/// changes. Starts and label changes end with how long they held, up to the
+/// next entry.
+fn format_timeline(entries: &[TimelineEntry]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut day = None;
+    for (index, entry) in entries.iter().enumerate() {
+        let at = entry.at.with_timezone(&Local);
+        if day != Some(at.date_naive()) {
+            day = Some(at.date_naive());
+            lines.push(at.format("%Y-%m-%d").to_string());
+        }
+        let held = || {
+            entries
+                .get(index + 1)
+                .and_then(|next| (next.at - entry.at).to_std().ok())
+                .map(|held| format!("  ({})", fmt_elapsed_compact(held.as_secs())))
+                .unwrap_or_default()
+        };
+        let text = match &entry.event {
+            TimelineEvent::Start { label } => format!("▶ start     {label}{}", held()),
+            TimelineEvent::Label { label } => format!("· label     {label}{}", held()),
+            TimelineEvent::Pause => "‖ pause".to_string(),
+            TimelineEvent::ExecFailed { command, exit_code } => {
+                format!("✗ exit {exit_code}    {command}")
+            }
+            TimelineEvent::Complete {
+                label,
+                elapsed_secs,
+            } => format!(
+                "✓ complete  {label} in {}",
+                fmt_elapsed_compact(u64::try_from(*elapsed_secs).unwrap_or(0))
+            ),
+        };
+        lines.push(format!("  {}  {text}", at.format("%H:%M:%S")));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    const SESSION_ID: &str = "0199a213-81c0-7800-8aa1-bbab2a035a53";
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Local
+            .with_ymd_and_hms(2025, 3, 4, 14, 2, 0)
+            .single()
+            .expect("local time")
+            .with_timezone(&Utc)
+            + chrono::Duration::seconds(secs)
+    }
+
+    /// An empty rollout file for `SESSION_ID` under `codex_home`.
+    fn rollout_file(codex_home: &Path) -> PathBuf {
+        let dir = codex_home
+            .join("sessions")
+            .join("2025")
+            .join("03")
+            .join("04");
+        std::fs::create_dir_all(&dir).expect("sessions dir");
+        let path = dir.join(format!("rollout-2025-03-04T14-02-00-{SESSION_ID}.jsonl"));
+        std::fs::write(&path, "").expect("rollout file");
+        path
+    }
+
+    #[tokio::test]
+    async fn records_transitions_without_repeating_labels() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let rollout = rollout_file(dir.path());
+        let mut timeline = SessionTimeline::default();
+        // Nothing is written before the session id is known.
+        timeline.record_at(at(0), TimelineEvent::Pause);
+        timeline.persist_to(&rollout, SESSION_ID);
+        let label = |label: &str| TimelineEvent::Label {
+            label: label.to_string(),
+        };
+        timeline.record_at(
+            at(0),
+            TimelineEvent::Start {
+                label: "Working".to_string(),
+            },
+        );
+        timeline.record_at(at(4), label("Working"));
+        timeline.record_at(at(5), label("Running cargo test"));
+        timeline.record_at(at(5), label("Running cargo test"));
+        timeline.record_at(
+            at(671),
+            TimelineEvent::ExecFailed {
+                command: "cargo test".to_string(),
+                exit_code: 101,
+            },
+        );
+        timeline.record_at(
+            at(680),
+            TimelineEvent::Complete {
+                label: "Running cargo test".to_string(),
+                elapsed_secs: 680,
+            },
+        );
+        drop(timeline);
+
+        let file = rollout.with_file_name(format!("timeline-{SESSION_ID}.jsonl"));
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while std::fs::read_to_string(&file).map_or(0, |contents| contents.lines().count()) < 4 {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "timeline never written"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let mut out = Vec::new();
+        write_session_timeline(&mut out, dir.path(), SESSION_ID)
+            .await
+            .expect("timeline");
+        assert_eq!(
+            String::from_utf8(out).expect("utf8"),
+            "2025-03-04\n\
+             \x20 14:02:00  ▶ start     Working  (5s)\n\
+             \x20 14:02:05  · label     Running cargo test  (11m 06s)\n\
+             \x20 14:13:11  ✗ exit 101    cargo test\n\
+             \x20 14:13:20  ✓ complete  Running cargo test in 11m 20s\n"
+        );
+    }
+
+    #[test]
+    fn session_ids_that_are_not_uuids_get_no_path() {
+        let rollout = Path::new("/home/me/.codex/sessions/2025/03/04/rollout.jsonl");
+        assert_eq!(timeline_path(rollout, "../../../../etc/passwd"), None);
+        assert_eq!(
+            timeline_path(rollout, SESSION_ID),
+            Some(PathBuf::from(format!(
+                "/home/me/.codex/sessions/2025/03/04/timeline-{SESSION_ID}.jsonl"
+            )))
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_timeline_names_the_setting() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        rollout_file(dir.path());
+        let err = write_session_timeline(&mut Vec::new(), dir.path(), SESSION_ID)
+            .await
+            .expect_err("no timeline");
+        assert!(
+            err.to_string().contains("tui.statusline.session_timeline"),
+            "{err}"
+        );
+
+        let err = write_session_timeline(&mut Vec::new(), dir.path(), "../nope")
+            .await
+            .expect_err("not a session id");
+        assert_eq!(
+            err.to_string(),
+            "`../nope` is not a session id; use the id shown by /status or on exit"
+        );
+    }
+}
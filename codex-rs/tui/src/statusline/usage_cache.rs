@@ -0,0 +1,421 @@
+//! Shared cache of 88code usage responses, so the status line and
+//! `codex status --watch` ask the API at most once per
+//! `[code88] usage_cache_ttl_secs` for the same API key. With
+//! `usage_cache_file` the entries are also kept in
+//! `CODEX_HOME/88code-usage-cache.json`, shared by every Codex process.
+//!
+//! Only successful responses are cached; failures reach the caller so the
+//! retry wrapper can act on them. Callers that ask for a key while its fetch
+//! is under way share that fetch's result instead of sending their own.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_core::config::Config;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::sync::watch;
+
+use super::code88_api::Code88AggregatedData;
+use super::code88_api::Code88Error;
+use super::providers::ProviderFuture;
+use super::providers::UsageProvider;
+
+const CACHE_FILE: &str = "88code-usage-cache.json";
+
+/// Entries by API key digest; the key itself is never stored.
+type Entries = HashMap<String, CacheEntry>;
+
+type Fetched = Result<Code88AggregatedData, Code88Error>;
+
+lazy_static! {
+    /// The in-memory cache every [`UsageCache::shared`] uses.
+    static ref SHARED: Arc<Mutex<CacheState>> = Arc::new(Mutex::new(CacheState::default()));
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: Entries,
+    /// Fetches under way, by key digest. The receiver sees `Some` once the
+    /// fetch is done.
+    in_flight: HashMap<String, watch::Receiver<Option<Fetched>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_at: DateTime<Utc>,
+    data: Code88AggregatedData,
+}
+
+impl CacheEntry {
+    /// Whether the entry is younger than `ttl` at `now`. Entries from the
+    /// future (a clock that jumped back) are treated as expired.
+    fn is_fresh(&self, ttl: Duration, now: DateTime<Utc>) -> bool {
+        (now - self.fetched_at).to_std().is_ok_and(|age| age < ttl)
+    }
+
+    /// The cached usage, stamped with when it was fetched.
+    fn data(&self) -> Code88AggregatedData {
+        Code88AggregatedData {
+            fetched_at: Some(self.fetched_at),
+            ..self.data.clone()
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct UsageCache {
+    ttl: Duration,
+    /// `CODEX_HOME/88code-usage-cache.json` when the disk cache is on.
+    file: Option<PathBuf>,
+    /// Only held to look up or update entries, never across a fetch.
+    state: Arc<Mutex<CacheState>>,
+}
+
+/// Whether a caller sends the request for a key or waits for the one
+/// already under way.
+enum Turn {
+    Fetch(watch::Sender<Option<Fetched>>),
+    Wait(watch::Receiver<Option<Fetched>>),
+}
+
+/// Ends the key's in-flight fetch when the fetching caller finishes or is
+/// dropped; waiters of a dropped fetch then send their own request.
+struct InFlight<'a> {
+    cache: &'a UsageCache,
+    key: &'a str,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.cache.state().in_flight.remove(self.key);
+    }
+}
+
+impl UsageCache {
+    /// The process-wide cache, with the TTL and disk setting of `config`.
+    pub(crate) fn shared(config: &Config) -> Self {
+        Self {
+            ttl: config.code88_usage_cache_ttl,
+            file: config
+                .code88_usage_cache_file
+                .then(|| config.codex_home.join(CACHE_FILE)),
+            state: Arc::clone(&SHARED),
+        }
+    }
+
+    #[cfg(test)]
+    fn new(ttl: Duration, file: Option<PathBuf>) -> Self {
+        Self {
+            ttl,
+            file,
+            state: Arc::new(Mutex::new(CacheState::default())),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The cached usage for `api_key` while it is fresh, otherwise the
+    /// result of `fetch`, cached when it succeeds. Cached data carries the
+    /// time it was fetched in `fetched_at`.
+    pub(crate) async fn get_or_fetch(
+        &self,
+        api_key: &str,
+        fetch: impl Future<Output = Fetched>,
+    ) -> Fetched {
+        if self.ttl.is_zero() {
+            return fetch.await;
+        }
+        let key = cache_key(api_key);
+        let turn = {
+            let mut state = self.state();
+            if let Some(entry) = state.entries.get(&key)
+                && entry.is_fresh(self.ttl, Utc::now())
+            {
+                return Ok(entry.data());
+            }
+            match state.in_flight.get(&key) {
+                Some(receiver) => Turn::Wait(receiver.clone()),
+                None => {
+                    let (sender, receiver) = watch::channel(None);
+                    state.in_flight.insert(key.clone(), receiver);
+                    Turn::Fetch(sender)
+                }
+            }
+        };
+        let sender = match turn {
+            Turn::Fetch(sender) => sender,
+            Turn::Wait(mut receiver) => {
+                if let Ok(done) = receiver.wait_for(Option::is_some).await
+                    && let Some(result) = done.clone()
+                {
+                    return result;
+                }
+                return fetch.await;
+            }
+        };
+
+        let _in_flight = InFlight {
+            cache: self,
+            key: &key,
+        };
+        let result = self.load_or_fetch(&key, fetch).await;
+        if let Ok(entry) = &result {
+            self.state().entries.insert(key.clone(), entry.clone());
+        }
+        let result = result.map(|entry| entry.data());
+        sender.send_replace(Some(result.clone()));
+        result
+    }
+
+    /// A fresh entry from the disk cache, otherwise the result of `fetch`,
+    /// written to disk when it succeeds.
+    async fn load_or_fetch(
+        &self,
+        key: &str,
+        fetch: impl Future<Output = Fetched>,
+    ) -> Result<CacheEntry, Code88Error> {
+        if let Some(entry) = self
+            .file
+            .as_deref()
+            .and_then(|file| read_cache_file(file).remove(key))
+            .filter(|entry| entry.is_fresh(self.ttl, Utc::now()))
+        {
+            return Ok(entry);
+        }
+        let entry = CacheEntry {
+            data: fetch.await?,
+            fetched_at: Utc::now(),
+        };
+        if let Some(file) = self.file.as_deref() {
+            write_cache_entry(file, key, &entry, self.ttl);
+        }
+        Ok(entry)
+    }
+}
+
+/// A [`UsageProvider`] answered from a [`UsageCache`] when it can.
+pub(crate) struct CachedUsage {
+    pub inner: Arc<dyn UsageProvider>,
+    pub cache: UsageCache,
+}
+
+impl UsageProvider for CachedUsage {
+    fn usage(
+        &self,
+        login_token: String,
+        api_key: String,
+    ) -> ProviderFuture<Result<Code88AggregatedData, Code88Error>> {
+        let inner = Arc::clone(&self.inner);
+        let cache = self.cache.clone();
+        Box::pin(async move {
+            let key = api_key.clone();
+            let fetch = async move { inner.usage(login_token, api_key).await };
+            cache.get_or_fetch(&key, fetch).await
+        })
+    }
+}
+
+fn cache_key(api_key: &str) -> String {
+    Sha256::digest(api_key.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// The entries on disk; a missing or unreadable file is an empty cache.
+fn read_cache_file(file: &Path) -> Entries {
+    let Ok(contents) = std::fs::read_to_string(file) else {
+        return Entries::new();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|err| {
+        tracing::debug!("ignoring {}: {err}", file.display());
+        Entries::new()
+    })
+}
+
+/// Store `entry` on disk, dropping expired entries of other keys. The file
+/// is replaced in one step so a concurrent reader never sees half of it.
+fn write_cache_entry(file: &Path, key: &str, entry: &CacheEntry, ttl: Duration) {
+    let now = Utc::now();
+    let mut entries = read_cache_file(file);
+    entries.retain(|_, entry| entry.is_fresh(ttl, now));
+    entries.insert(key.to_string(), entry.clone());
+    let result = serde_json::to_string(&entries)
+        .map_err(std::io::Error::other)
+        .and_then(|json| {
+            let mut temp = file.as_os_str().to_owned();
+            temp.push(".tmp");
+            std::fs::write(&temp, json)?;
+            std::fs::rename(&temp, file)
+        });
+    if let Err(err) = result {
+        tracing::warn!("failed to write {}: {err}", file.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    /// Counts requests and answers each with its number as the credits.
+    #[derive(Default)]
+    struct CountingUsage {
+        calls: AtomicUsize,
+    }
+
+    impl UsageProvider for CountingUsage {
+        fn usage(
+            &self,
+            _login_token: String,
+            api_key: String,
+        ) -> ProviderFuture<Result<Code88AggregatedData, Code88Error>> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Box::pin(async move {
+                if api_key == "88_broken" {
+                    return Err(Code88Error::HttpStatus(503));
+                }
+                Ok(Code88AggregatedData {
+                    credits_remaining: Some(call as f64),
+                    ..Code88AggregatedData::default()
+                })
+            })
+        }
+    }
+
+    async fn credits(provider: &CachedUsage, api_key: &str) -> Option<f64> {
+        provider
+            .usage("login".to_string(), api_key.to_string())
+            .await
+            .ok()
+            .and_then(|data| data.credits_remaining)
+    }
+
+    #[tokio::test]
+    async fn fresh_responses_are_reused_per_api_key() {
+        let counting = Arc::new(CountingUsage::default());
+        let provider = CachedUsage {
+            inner: Arc::clone(&counting) as Arc<dyn UsageProvider>,
+            cache: UsageCache::new(Duration::from_secs(60), None),
+        };
+
+        assert_eq!(credits(&provider, "88_a").await, Some(1.0));
+        assert_eq!(credits(&provider, "88_a").await, Some(1.0));
+        assert_eq!(credits(&provider, "88_b").await, Some(2.0));
+        assert_eq!(counting.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn callers_share_a_fetch_under_way_and_its_fetch_time() {
+        let cache = UsageCache::new(Duration::from_secs(60), None);
+        let usage = |credits| Code88AggregatedData {
+            credits_remaining: Some(credits),
+            ..Code88AggregatedData::default()
+        };
+        let (release, released) = tokio::sync::oneshot::channel();
+        let (first, second, other, ()) = tokio::join!(
+            cache.get_or_fetch("88_a", async {
+                released.await.ok();
+                Ok(usage(1.0))
+            }),
+            cache.get_or_fetch("88_a", async { Err(Code88Error::NoData) }),
+            // Another key is not held up by the slow fetch.
+            cache.get_or_fetch("88_b", async { Ok(usage(2.0)) }),
+            async {
+                release.send(()).ok();
+            },
+        );
+        let first = first.expect("first fetch");
+        let second = second.expect("shared fetch");
+        assert_eq!(
+            (first.credits_remaining, second.credits_remaining),
+            (Some(1.0), Some(1.0))
+        );
+        assert_eq!(
+            other.ok().and_then(|data| data.credits_remaining),
+            Some(2.0)
+        );
+        assert!(first.fetched_at.is_some());
+
+        let hit = cache
+            .get_or_fetch("88_a", async { Err(Code88Error::NoData) })
+            .await
+            .expect("cached");
+        assert_eq!(
+            (hit.credits_remaining, hit.fetched_at),
+            (Some(1.0), first.fetched_at)
+        );
+    }
+
+    #[tokio::test]
+    async fn failures_and_zero_ttl_are_not_cached() {
+        let counting = Arc::new(CountingUsage::default());
+        let provider = CachedUsage {
+            inner: Arc::clone(&counting) as Arc<dyn UsageProvider>,
+            cache: UsageCache::new(Duration::from_secs(60), None),
+        };
+        assert_eq!(credits(&provider, "88_broken").await, None);
+        assert_eq!(credits(&provider, "88_broken").await, None);
+        assert_eq!(counting.calls.load(Ordering::SeqCst), 2);
+
+        let uncached = CachedUsage {
+            inner: Arc::clone(&counting) as Arc<dyn UsageProvider>,
+            cache: UsageCache::new(Duration::ZERO, None),
+        };
+        assert_eq!(credits(&uncached, "88_a").await, Some(3.0));
+        assert_eq!(credits(&uncached, "88_a").await, Some(4.0));
+    }
+
+    #[tokio::test]
+    async fn disk_cache_is_shared_and_keeps_no_api_key() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join(CACHE_FILE);
+        let counting = Arc::new(CountingUsage::default());
+        let first = CachedUsage {
+            inner: Arc::clone(&counting) as Arc<dyn UsageProvider>,
+            cache: UsageCache::new(Duration::from_secs(60), Some(file.clone())),
+        };
+        assert_eq!(credits(&first, "88_secret").await, Some(1.0));
+
+        // Another process: its own memory, the same file.
+        let second = CachedUsage {
+            inner: Arc::clone(&counting) as Arc<dyn UsageProvider>,
+            cache: UsageCache::new(Duration::from_secs(60), Some(file.clone())),
+        };
+        assert_eq!(credits(&second, "88_secret").await, Some(1.0));
+        assert_eq!(counting.calls.load(Ordering::SeqCst), 1);
+
+        let contents = std::fs::read_to_string(&file).expect("cache file");
+        assert!(!contents.contains("88_secret"), "{contents}");
+    }
+
+    #[test]
+    fn entries_expire_after_the_ttl_and_from_the_future() {
+        let now = Utc::now();
+        let entry = |age_secs| CacheEntry {
+            fetched_at: now - chrono::Duration::seconds(age_secs),
+            data: Code88AggregatedData::default(),
+        };
+        let ttl = Duration::from_secs(10);
+        assert!(entry(9).is_fresh(ttl, now));
+        assert!(!entry(10).is_fresh(ttl, now));
+        assert!(!entry(-5).is_fresh(ttl, now));
+    }
+}
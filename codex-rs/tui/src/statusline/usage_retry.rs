@@ -0,0 +1,157 @@
+//! Retries and a circuit breaker around the 88code usage poll, so a flaky
+//! network keeps the last good numbers on the status line (with their age)
+//! instead of flashing an error segment.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use super::StatusLine88CodeSnapshot;
+use super::code88_api::Code88AggregatedData;
+use super::code88_api::Code88Error;
+use super::providers::UsageProvider;
+
+/// Attempts per poll, including the first.
+const MAX_ATTEMPTS: i64 = 3;
+
+/// Delay before the first retry; doubled for every further one.
+const BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Cap on a single retry delay.
+const MAX_DELAY: Duration = Duration::from_secs(8);
+
+/// Failed polls in a row that open the breaker.
+const BREAKER_THRESHOLD: i64 = 5;
+
+/// How long an open breaker skips polls before trying the API again.
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// Delay before retry number `attempt` (1-based): exponential, with full
+/// jitter so many sessions that lost the network together do not retry in
+/// lockstep. `jitter` is in `0.0..1.0`.
+pub(crate) fn backoff_delay(attempt: i64, jitter: f64) -> Duration {
+    let exponential = BASE_DELAY
+        .saturating_mul(1 << attempt.saturating_sub(1).clamp(0, 16))
+        .min(MAX_DELAY);
+    exponential.mul_f64(jitter.clamp(0.0, 1.0))
+}
+
+/// One poll of the usage API, retrying transient failures. Expired tokens
+/// and malformed responses are returned right away; retrying cannot fix
+/// them.
+pub(crate) async fn fetch_with_retry(
+    usage: &dyn UsageProvider,
+    login_token: &str,
+    api_key: &str,
+) -> Result<Code88AggregatedData, Code88Error> {
+    let mut attempt = 1;
+    loop {
+        let result = usage
+            .usage(login_token.to_string(), api_key.to_string())
+            .await;
+        match result {
+            Err(err) if err.is_transient() && attempt < MAX_ATTEMPTS => {
+                let delay = backoff_delay(attempt, rand::random::<f64>());
+                tracing::debug!(
+                    "88code usage attempt {attempt} failed: {err}; retrying in {delay:?}"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Stops polling for [`BREAKER_COOLDOWN`] after [`BREAKER_THRESHOLD`]
+/// failed polls in a row, then lets a single poll through to probe the API.
+#[derive(Debug, Default)]
+pub(crate) struct CircuitBreaker {
+    failures: i64,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Whether a poll may run at `now`.
+    pub(crate) fn allows(&self, now: Instant) -> bool {
+        self.open_until.is_none_or(|until| now >= until)
+    }
+
+    pub(crate) fn record_success(&mut self) {
+        self.failures = 0;
+        self.open_until = None;
+    }
+
+    pub(crate) fn record_failure(&mut self, now: Instant) {
+        self.failures = self.failures.saturating_add(1);
+        if self.failures >= BREAKER_THRESHOLD {
+            self.open_until = Some(now + BREAKER_COOLDOWN);
+        }
+    }
+}
+
+/// The last good usage, kept on screen after a failed poll. The error is
+/// kept for `/status`.
+pub(crate) fn stale_snapshot(
+    last_good: &StatusLine88CodeSnapshot,
+    err: &Code88Error,
+) -> StatusLine88CodeSnapshot {
+    StatusLine88CodeSnapshot {
+        stale: true,
+        error_msg: Some(err.to_string()),
+        ..last_good.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap_and_is_jittered() {
+        assert_eq!(backoff_delay(1, 1.0), Duration::from_millis(500));
+        assert_eq!(backoff_delay(2, 1.0), Duration::from_secs(1));
+        assert_eq!(backoff_delay(3, 1.0), Duration::from_secs(2));
+        assert_eq!(backoff_delay(10, 1.0), MAX_DELAY);
+        assert_eq!(backoff_delay(i64::MAX, 1.0), MAX_DELAY);
+        assert_eq!(backoff_delay(2, 0.25), Duration::from_millis(250));
+        assert_eq!(backoff_delay(2, 0.0), Duration::ZERO);
+        assert_eq!(backoff_delay(0, 1.0), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn breaker_opens_after_repeated_failures_and_closes_on_success() {
+        let start = Instant::now();
+        let mut breaker = CircuitBreaker::default();
+        for _ in 1..BREAKER_THRESHOLD {
+            breaker.record_failure(start);
+        }
+        assert!(breaker.allows(start));
+
+        breaker.record_failure(start);
+        assert!(!breaker.allows(start));
+        assert!(!breaker.allows(start + BREAKER_COOLDOWN - Duration::from_secs(1)));
+        assert!(breaker.allows(start + BREAKER_COOLDOWN));
+
+        // A failed probe opens it again right away.
+        breaker.record_failure(start + BREAKER_COOLDOWN);
+        assert!(!breaker.allows(start + BREAKER_COOLDOWN));
+
+        breaker.record_success();
+        assert!(breaker.allows(start + BREAKER_COOLDOWN));
+    }
+
+    #[test]
+    fn stale_snapshot_keeps_the_numbers() {
+        let good = StatusLine88CodeSnapshot {
+            credits_remaining: Some(3.2),
+            ..StatusLine88CodeSnapshot::default()
+        };
+        let stale = stale_snapshot(&good, &Code88Error::Network("timed out".to_string()));
+        assert!(stale.stale);
+        assert!(!stale.is_error);
+        assert_eq!(stale.credits_remaining, Some(3.2));
+        assert_eq!(stale.error_msg.as_deref(), Some("Network error: timed out"));
+    }
+}
@@ -10,15 +10,9 @@ pub fn best_color(target: (u8, u8, u8)) -> Color {
         let (r, g, b) = target;
         #[allow(clippy::disallowed_methods)]
         Color::Rgb(r, g, b)
-    } else if color_level.has_256
-        && let Some((i, _)) = xterm_fixed_colors().min_by(|(_, a), (_, b)| {
-            perceptual_distance(*a, target)
-                .partial_cmp(&perceptual_distance(*b, target))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        })
-    {
+    } else if color_level.has_256 {
         #[allow(clippy::disallowed_methods)]
-        Color::Indexed(i as u8)
+        Color::Indexed(nearest_xterm_color(target))
     } else {
         #[allow(clippy::disallowed_methods)]
         Color::default()
@@ -132,6 +126,18 @@ mod imp {
     pub(super) fn requery_default_colors() {}
 }
 
+/// Index of the xterm color closest to `target`, skipping the 16 themed
+/// system colors.
+pub(crate) fn nearest_xterm_color(target: (u8, u8, u8)) -> u8 {
+    xterm_fixed_colors()
+        .min_by(|(_, a), (_, b)| {
+            perceptual_distance(*a, target)
+                .partial_cmp(&perceptual_distance(*b, target))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map_or(16, |(i, _)| i as u8)
+}
+
 /// The subset of Xterm colors that are usually consistent across terminals.
 fn xterm_fixed_colors() -> impl Iterator<Item = (usize, (u8, u8, u8))> {
     XTERM_COLORS.into_iter().enumerate().skip(16)
@@ -23,6 +23,7 @@ use crossterm::event::PopKeyboardEnhancementFlags;
 use crossterm::event::PushKeyboardEnhancementFlags;
 use crossterm::terminal::EnterAlternateScreen;
 use crossterm::terminal::LeaveAlternateScreen;
+use crossterm::terminal::SetTitle;
 use crossterm::terminal::supports_keyboard_enhancement;
 use ratatui::backend::Backend;
 use ratatui::backend::CrosstermBackend;
@@ -172,6 +173,10 @@ pub struct Tui {
     alt_screen_active: Arc<AtomicBool>,
     // True when terminal/tab is focused; updated internally from crossterm events
     terminal_focused: Arc<AtomicBool>,
+    // Last progress state written via OSC 9;4, used to skip redundant writes
+    terminal_progress: TerminalProgress,
+    // Last title written via OSC 0; `Some` once the original title was pushed
+    terminal_title: Option<String>,
     enhanced_keys_supported: bool,
 }
 
@@ -197,6 +202,8 @@ impl Tui {
             suspend_context: SuspendContext::new(),
             alt_screen_active: Arc::new(AtomicBool::new(false)),
             terminal_focused: Arc::new(AtomicBool::new(true)),
+            terminal_progress: TerminalProgress::Clear,
+            terminal_title: None,
             enhanced_keys_supported,
         }
     }
@@ -209,6 +216,12 @@ impl Tui {
         self.enhanced_keys_supported
     }
 
+    /// Whether the terminal last reported having focus. Terminals without
+    /// focus reporting always count as focused.
+    pub fn is_terminal_focused(&self) -> bool {
+        self.terminal_focused.load(Ordering::Relaxed)
+    }
+
     /// Emit a desktop notification now if the terminal is unfocused.
     /// Returns true if a notification was posted.
     pub fn notify(&mut self, message: impl AsRef<str>) -> bool {
@@ -220,6 +233,66 @@ impl Tui {
         }
     }
 
+    /// Post an OSC 777 notification now if the terminal is unfocused.
+    /// Returns true if a notification was posted.
+    pub fn notify_osc777(&mut self, title: &str, body: &str) -> bool {
+        if !self.terminal_focused.load(Ordering::Relaxed) {
+            let _ = execute!(
+                stdout(),
+                PostOsc777Notification {
+                    title: title.to_string(),
+                    body: body.to_string(),
+                }
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Ring the terminal bell now if the terminal is unfocused.
+    /// Returns true if the bell was rung.
+    pub fn ring_bell(&mut self) -> bool {
+        if !self.terminal_focused.load(Ordering::Relaxed) {
+            let _ = execute!(stdout(), RingBell);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Report task progress to the terminal via OSC 9;4. Writes are skipped
+    /// when the progress state has not changed since the last call.
+    pub fn set_terminal_progress(&mut self, progress: TerminalProgress) {
+        if self.terminal_progress == progress {
+            return;
+        }
+        self.terminal_progress = progress;
+        let _ = execute!(stdout(), SetTerminalProgress(progress));
+    }
+
+    /// Set the terminal title. The title in place before the first call is
+    /// saved on the terminal's title stack so `restore_terminal_title` can
+    /// bring it back.
+    pub fn set_terminal_title(&mut self, title: &str) {
+        if self.terminal_title.as_deref() == Some(title) {
+            return;
+        }
+        if self.terminal_title.is_none() {
+            let _ = execute!(stdout(), PushTerminalTitle);
+        }
+        self.terminal_title = Some(title.to_string());
+        let _ = execute!(stdout(), SetTitle(title));
+    }
+
+    /// Restore the title that was active before `set_terminal_title` was
+    /// first called. Does nothing if the title was never changed.
+    pub fn restore_terminal_title(&mut self) {
+        if self.terminal_title.take().is_some() {
+            let _ = execute!(stdout(), PopTerminalTitle);
+        }
+    }
+
     pub fn event_stream(&self) -> Pin<Box<dyn Stream<Item = TuiEvent> + Send + 'static>> {
         use tokio_stream::StreamExt;
 
@@ -439,3 +512,172 @@ impl Command for PostNotification {
         true
     }
 }
+
+/// Command that posts an OSC 777 notification, as understood by urxvt, foot,
+/// Ghostty and WezTerm.
+#[derive(Debug, Clone)]
+pub struct PostOsc777Notification {
+    pub title: String,
+    pub body: String,
+}
+
+impl Command for PostOsc777Notification {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(
+            f,
+            "\x1b]777;notify;{};{}\x07",
+            strip_control_chars(&self.title),
+            strip_control_chars(&self.body)
+        )
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute PostOsc777Notification using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// `text` without control characters, so a BEL or ESC in a message cannot
+/// end the escape sequence early and reach the terminal as a command. Line
+/// breaks and tabs become spaces.
+fn strip_control_chars(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// Command that rings the terminal bell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RingBell;
+
+impl Command for RingBell {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x07")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute RingBell using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Progress state reported to the terminal through OSC 9;4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalProgress {
+    /// Remove any progress indicator.
+    Clear,
+    /// Busy indicator without a known completion percentage.
+    Indeterminate,
+    /// Progress bar filled to the given percentage (0-100).
+    Percent(u8),
+}
+
+/// Command that emits an OSC 9;4 progress report, as understood by ConEmu,
+/// Windows Terminal and WezTerm.
+#[derive(Debug, Clone, Copy)]
+pub struct SetTerminalProgress(pub TerminalProgress);
+
+impl Command for SetTerminalProgress {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match self.0 {
+            TerminalProgress::Clear => write!(f, "\x1b]9;4;0;0\x07"),
+            TerminalProgress::Indeterminate => write!(f, "\x1b]9;4;3;0\x07"),
+            TerminalProgress::Percent(percent) => {
+                write!(f, "\x1b]9;4;1;{}\x07", percent.min(100))
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute SetTerminalProgress using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Command that saves the current window title on the terminal's title stack
+/// (XTWINOPS 22).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PushTerminalTitle;
+
+impl Command for PushTerminalTitle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1b[22;0t")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute PushTerminalTitle using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Command that restores the window title saved by `PushTerminalTitle`
+/// (XTWINOPS 23).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PopTerminalTitle;
+
+impl Command for PopTerminalTitle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1b[23;0t")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute PopTerminalTitle using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn osc777_notification_drops_control_characters() {
+        let command = PostOsc777Notification {
+            title: "Codex\x07\x1b]0;pwned".to_string(),
+            body: "done\nin 3s\u{9b}".to_string(),
+        };
+        let mut out = String::new();
+        command.write_ansi(&mut out).expect("write");
+        assert_eq!(out, "\x1b]777;notify;Codex]0;pwned;done in 3s\x07");
+    }
+}
@@ -63,6 +63,18 @@ impl FrameRequester {
             frame_schedule_tx: tx,
         }
     }
+
+    /// Create a frame requester whose requests can be inspected, without a
+    /// scheduler task.
+    pub(crate) fn test_recorder() -> (Self, mpsc::UnboundedReceiver<Instant>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (
+            FrameRequester {
+                frame_schedule_tx: tx,
+            },
+            rx,
+        )
+    }
 }
 
 /// A scheduler for coalescing frame draw requests and notifying the TUI event loop.
@@ -8,6 +8,13 @@ pub enum UpdateAction {
 }
 
 impl UpdateAction {
+    /// The update for how this install is managed: bun when
+    /// `CODEX_MANAGED_BY_BUN` is set, npm otherwise.
+    pub fn for_environment() -> Self {
+        let managed_by_bun = std::env::var_os("CODEX_MANAGED_BY_BUN").is_some();
+        detect_update_action(managed_by_bun).unwrap_or(UpdateAction::NpmGlobalLatest)
+    }
+
     /// Returns the list of command-line arguments for invoking the update.
     pub fn command_args(self) -> (&'static str, &'static [&'static str]) {
         match self {
@@ -26,12 +33,9 @@ impl UpdateAction {
 
 #[cfg(not(debug_assertions))]
 pub(crate) fn get_update_action() -> Option<UpdateAction> {
-    let managed_by_bun = std::env::var_os("CODEX_MANAGED_BY_BUN").is_some();
-
-    detect_update_action(managed_by_bun)
+    Some(UpdateAction::for_environment())
 }
 
-#[cfg(any(not(debug_assertions), test))]
 fn detect_update_action(managed_by_bun: bool) -> Option<UpdateAction> {
     if managed_by_bun {
         Some(UpdateAction::BunGlobalLatest)
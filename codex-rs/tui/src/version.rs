@@ -1,2 +1,97 @@
+use crate::release_check::LatestRelease;
+
 /// The current Codex CLI version as embedded at compile time.
 pub const CODEX_CLI_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Characters of the commit hash shown after the version.
+const SHORT_SHA_LEN: usize = 7;
+
+/// This binary's commit and the newer release the startup check found, for
+/// the `build` status line segment of every session.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct BuildStatus {
+    /// The CLI's `CODEX_CLI_GIT_SHA`, see [`crate::Cli::build_sha`].
+    pub sha: Option<&'static str>,
+    pub newer_release: Option<LatestRelease>,
+}
+
+/// The commit a build recorded, unless it recorded none.
+pub(crate) fn known_sha(sha: Option<&str>) -> Option<&str> {
+    sha.map(str::trim)
+        .filter(|sha| !sha.is_empty() && *sha != "unknown")
+}
+
+/// `0.58.0+1a2b3c4`: the version with the commit it was built from, or the
+/// bare version when the commit is unknown.
+pub(crate) fn build_label(sha: Option<&str>) -> String {
+    match known_sha(sha) {
+        Some(sha) => {
+            let short: String = sha.chars().take(SHORT_SHA_LEN).collect();
+            format!("{CODEX_CLI_VERSION}+{short}")
+        }
+        None => CODEX_CLI_VERSION.to_string(),
+    }
+}
+
+/// Whether `latest` is a later `major.minor.patch` than `current`; `None`
+/// when either is not one (pre-releases included).
+pub(crate) fn is_newer(latest: &str, current: &str) -> Option<bool> {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(l), Some(c)) => Some(l > c),
+        _ => None,
+    }
+}
+
+fn parse_version(v: &str) -> Option<(u64, u64, u64)> {
+    let mut iter = v.trim().split('.');
+    let maj = iter.next()?.parse::<u64>().ok()?;
+    let min = iter.next()?.parse::<u64>().ok()?;
+    let pat = iter.next()?.parse::<u64>().ok()?;
+    Some((maj, min, pat))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn build_label_shortens_the_sha_and_skips_unknown_ones() {
+        assert_eq!(
+            build_label(Some("1a2b3c4d5e6f7a8b9c0d")),
+            format!("{CODEX_CLI_VERSION}+1a2b3c4")
+        );
+        assert_eq!(build_label(Some("abc")), format!("{CODEX_CLI_VERSION}+abc"));
+        assert_eq!(build_label(Some("unknown")), CODEX_CLI_VERSION);
+        assert_eq!(build_label(Some("  ")), CODEX_CLI_VERSION);
+        assert_eq!(build_label(None), CODEX_CLI_VERSION);
+    }
+
+    #[test]
+    fn prerelease_version_is_not_considered_newer() {
+        assert_eq!(is_newer("0.11.0-beta.1", "0.11.0"), None);
+        assert_eq!(is_newer("1.0.0-rc.1", "1.0.0"), None);
+    }
+
+    #[test]
+    fn plain_semver_comparisons_work() {
+        assert_eq!(is_newer("0.11.1", "0.11.0"), Some(true));
+        assert_eq!(is_newer("0.11.0", "0.11.1"), Some(false));
+        assert_eq!(is_newer("1.0.0", "0.9.9"), Some(true));
+        assert_eq!(is_newer("0.9.9", "1.0.0"), Some(false));
+    }
+
+    #[test]
+    fn date_version_comparisons_work() {
+        // 88code uses date-based versions like 2025.12.2
+        assert_eq!(is_newer("2025.12.3", "2025.12.2"), Some(true));
+        assert_eq!(is_newer("2025.12.2", "2025.12.3"), Some(false));
+        assert_eq!(is_newer("2026.1.1", "2025.12.31"), Some(true));
+    }
+
+    #[test]
+    fn whitespace_is_ignored() {
+        assert_eq!(parse_version(" 1.2.3 \n"), Some((1, 2, 3)));
+        assert_eq!(is_newer(" 1.2.3 ", "1.2.2"), Some(true));
+    }
+}